@@ -1,6 +1,13 @@
+#[cfg(feature = "shim")]
+use std::collections::HashSet;
 use std::env;
 use std::fs;
+#[cfg(feature = "shim")]
+use std::path::Component;
+use std::path::Path;
 use std::path::PathBuf;
+#[cfg(any(feature = "shim", feature = "prebuilt-kernels"))]
+use std::process::Command;
 use std::thread;
 use std::time::Duration;
 
@@ -21,6 +28,54 @@ fn main() {
         pkg_version, cutlass_version
     );
 
+    let (cutlass_root, include_dir) = locate_cutlass(&cutlass_version);
+    emit_cargo_keys(&cutlass_root, &include_dir, &cutlass_version);
+    let cuda_root = locate_cuda_toolkit();
+    if let Some(cuda_root) = &cuda_root {
+        emit_cuda_keys(cuda_root);
+    }
+    #[cfg(any(feature = "shim", feature = "driver"))]
+    if is_wsl2() {
+        configure_wsl2();
+    }
+    #[cfg(not(feature = "shim"))]
+    emit_toolchain_requirements(&cutlass_version, &[], None);
+
+    #[cfg(feature = "shim")]
+    {
+        let archs = select_archs();
+        // Dependent build scripts (build::CutlassBuild) see them as DEP_CUTLASS_ARCHS
+        let list: Vec<String> = archs.iter().map(|arch| arch.to_string()).collect();
+        println!("cargo:archs={}", list.join(","));
+        let (compiler, toolkit) = select_cuda_compiler(&cutlass_version, &archs);
+        emit_toolchain_requirements(&cutlass_version, &archs, toolkit);
+        build_shim(
+            &compiler,
+            select_cudart(),
+            &include_dir,
+            &archs,
+            &cutlass_version,
+            toolkit,
+        );
+
+        #[cfg(feature = "library")]
+        build_library(
+            &compiler.cmake_compiler(),
+            &cutlass_root,
+            &cutlass_version,
+            &archs,
+        );
+    }
+
+    #[cfg(feature = "prebuilt-kernels")]
+    build_prebuilt_kernels(&include_dir, &cutlass_version);
+
+    #[cfg(feature = "driver")]
+    link_driver(cuda_root.as_deref());
+}
+
+/// Resolve the CUTLASS source tree, returning `(root, include_dir)`
+fn locate_cutlass(cutlass_version: &str) -> (PathBuf, PathBuf) {
     // 1. Check for user-provided CUTLASS_DIR (highest priority)
     if let Ok(custom_dir) = env::var("CUTLASS_DIR") {
         let cutlass_root = PathBuf::from(&custom_dir);
@@ -39,12 +94,11 @@ fn main() {
             "cargo:warning=Using CUTLASS from CUTLASS_DIR: {}",
             cutlass_root.display()
         );
-        emit_cargo_keys(&cutlass_root, &include_dir);
-        return;
+        return (cutlass_root, include_dir);
     }
 
     // 2. Check persistent cache directory
-    let cache_dir = get_cache_dir().join("cutlass").join(&cutlass_version);
+    let cache_dir = get_cache_dir().join("cutlass").join(cutlass_version);
     let cached_include = cache_dir.join("include");
 
     if cached_include.exists() {
@@ -53,8 +107,7 @@ fn main() {
             cutlass_version,
             cache_dir.display()
         );
-        emit_cargo_keys(&cache_dir, &cached_include);
-        return;
+        return (cache_dir, cached_include);
     }
 
     // 3. Download CUTLASS (with retry logic)
@@ -67,7 +120,7 @@ fn main() {
     let temp_dir = out_dir.join("cutlass_download_temp");
     fs::create_dir_all(&temp_dir).expect("Failed to create temp directory");
 
-    match download_cutlass_with_retry(&cutlass_version, &temp_dir) {
+    match download_cutlass_with_retry(cutlass_version, &temp_dir) {
         Ok(extracted_root) => {
             // Move to persistent cache
             fs::create_dir_all(&cache_dir).expect("Failed to create cache directory");
@@ -78,10 +131,11 @@ fn main() {
                 "cargo:warning=CUTLASS {} downloaded and cached successfully",
                 cutlass_version
             );
-            emit_cargo_keys(&cache_dir, &include_dir);
 
             // Clean up temp directory
             let _ = fs::remove_dir_all(&temp_dir);
+
+            (cache_dir, include_dir)
         }
         Err(e) => {
             eprintln!("\n========================================");
@@ -105,13 +159,39 @@ fn main() {
     }
 }
 
-fn emit_cargo_keys(root: &PathBuf, include_dir: &PathBuf) {
+fn emit_cargo_keys(root: &PathBuf, include_dir: &PathBuf, cutlass_version: &str) {
     // Emit multiple keys for maximum compatibility with consumers
     println!("cargo:root={}", root.display());
     println!("cargo:include={}", include_dir.display());
     println!("cargo:include_dir={}", include_dir.display());
     println!("cargo:INCLUDE_DIR={}", include_dir.display());
 
+    // CuTe (CUTLASS 3.0 on) lives in include/cute; consumers using only CuTe add this directory
+    // alone, and check the headers' version rather than the one requested
+    if include_dir.join("cute").is_dir() {
+        println!("cargo:cute_include={}", include_dir.display());
+        if let Some(version) = header_version(include_dir) {
+            println!("cargo:cute_version={}", version);
+        }
+    }
+
+    // The fetched tree is the whole repository; some kernels (FMHA, for one) only exist as
+    // examples, so tooling can compile or include them from here. A CUTLASS_DIR holding only
+    // headers has none
+    let examples_dir = root.join("examples");
+    if examples_dir.is_dir() {
+        println!("cargo:examples_dir={}", examples_dir.display());
+    }
+
+    write_pkg_config(root, include_dir, cutlass_version);
+
+    // Where compiled objects are cached by configuration; build::KernelManifest and
+    // build::KernelGenerator keep their libraries there too
+    println!(
+        "cargo:object_cache={}",
+        get_cache_dir().join("objects").display()
+    );
+
     // Also set rustc-env so compiled Rust code can access it
     println!(
         "cargo:rustc-env=CUTLASS_INCLUDE_DIR={}",
@@ -125,6 +205,2199 @@ fn emit_cargo_keys(root: &PathBuf, include_dir: &PathBuf) {
     );
 }
 
+/// Write OUT_DIR/pkgconfig/cutlass.pc for the resolved headers, so C++ builds sharing the
+/// repository find the same copy through `PKG_CONFIG_PATH`. Its directory is exported as the
+/// `pkgconfig_dir` metadata key
+fn write_pkg_config(root: &Path, include_dir: &Path, cutlass_version: &str) {
+    let pkgconfig_dir = PathBuf::from(env::var("OUT_DIR").unwrap()).join("pkgconfig");
+    fs::create_dir_all(&pkgconfig_dir).expect("Failed to create the pkgconfig directory");
+    // The headers' own version, in case CUTLASS_DIR points at a different release
+    let version = header_version(include_dir)
+        .unwrap_or_else(|| cutlass_version.trim_start_matches('v').to_string());
+    let contents = format!(
+        "prefix={}\n\
+         includedir={}\n\
+         utilincludedir=${{prefix}}/tools/util/include\n\
+         \n\
+         Name: CUTLASS\n\
+         Description: CUDA Templates for Linear Algebra Subroutines, via cutlass-sys\n\
+         URL: https://github.com/NVIDIA/cutlass\n\
+         Version: {}\n\
+         Cflags: -I${{includedir}} -I${{utilincludedir}}\n",
+        root.display(),
+        include_dir.display(),
+        version
+    );
+    fs::write(pkgconfig_dir.join("cutlass.pc"), contents).expect("Failed to write cutlass.pc");
+    println!("cargo:pkgconfig_dir={}", pkgconfig_dir.display());
+}
+
+/// The `MAJOR.MINOR.PATCH` version of the headers in `include_dir`, from cutlass/version.h
+fn header_version(include_dir: &Path) -> Option<String> {
+    let header = fs::read_to_string(include_dir.join("cutlass").join("version.h")).ok()?;
+    let define = |name: &str| {
+        header.lines().find_map(|line| {
+            let mut words = line.split_whitespace();
+            if words.next() != Some("#define") || words.next() != Some(name) {
+                return None;
+            }
+            words.next()?.parse::<u32>().ok()
+        })
+    };
+    Some(format!(
+        "{}.{}.{}",
+        define("CUTLASS_MAJOR")?,
+        define("CUTLASS_MINOR")?,
+        define("CUTLASS_PATCH")?
+    ))
+}
+
+/// The SM versions the shim (and the `library` build) compile for, ascending
+#[cfg(feature = "shim")]
+fn select_archs() -> Vec<u32> {
+    println!("cargo:rerun-if-env-changed=CUTLASS_CUDA_ARCH");
+    println!("cargo:rerun-if-env-changed=CUTLASS_DETECT_ARCH");
+
+    // The sm* features and CUTLASS_CUDA_ARCH together select the archs; with neither, those of
+    // the local GPUs, and sm_80 without any. The build machine's GPUs say nothing about an
+    // aarch64 board the shim is cross-compiled for
+    let cross = cross_aarch64();
+    let mut archs = match env::var("CUTLASS_CUDA_ARCH") {
+        Ok(value) => parse_cuda_archs(&value),
+        Err(_) => Vec::new(),
+    };
+    archs.extend(
+        [75, 80, 86, 89, 90, 100]
+            .into_iter()
+            .filter(|arch| env::var_os(format!("CARGO_FEATURE_SM{}", arch)).is_some()),
+    );
+    if archs.is_empty()
+        && !cross
+        && env::var("CUTLASS_DETECT_ARCH").map_or(true, |value| value != "0")
+    {
+        archs = detect_archs();
+        if !archs.is_empty() {
+            let list: Vec<String> = archs.iter().map(|arch| format!("sm_{}", arch)).collect();
+            println!(
+                "cargo:warning=Compiling for the local GPU ({}); set CUTLASS_CUDA_ARCH to \
+                target others",
+                list.join(", ")
+            );
+        }
+    }
+    if archs.is_empty() && cross {
+        // Jetson Orin and Grace Hopper
+        archs.extend([87, 90]);
+        println!(
+            "cargo:warning=Cross-compiling for aarch64: compiling for sm_87 (Jetson Orin) and \
+            sm_90 (Grace Hopper); set CUTLASS_CUDA_ARCH to target one"
+        );
+    } else if archs.is_empty() {
+        archs.push(80);
+    }
+    archs.sort_unstable();
+    archs.dedup();
+
+    // sm_75 is the lowest arch with any kernel we instantiate
+    if archs[0] < 75 {
+        panic!(
+            "sm_{} was selected, but the shim's kernels need sm_75 or newer",
+            archs[0]
+        );
+    }
+    archs
+}
+
+/// The SM versions of the local GPUs' compute capabilities, from `nvidia-smi`. Empty when it is
+/// missing or fails, as on build machines without a GPU or driver, and without the GPUs older
+/// than sm_75, which the shim has no kernels for
+#[cfg(feature = "shim")]
+fn detect_archs() -> Vec<u32> {
+    // WSL2 ships nvidia-smi with the driver library, which is not always on PATH
+    let query = |nvidia_smi: &Path| {
+        Command::new(nvidia_smi)
+            .args(["--query-gpu=compute_cap", "--format=csv,noheader"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+    };
+    let output = match query(Path::new("nvidia-smi"))
+        .or_else(|| query(&Path::new(WSL_LIB_DIR).join("nvidia-smi")))
+    {
+        Some(output) => output,
+        None => return Vec::new(),
+    };
+    // One "MAJOR.MINOR" line per GPU, e.g. "8.6"
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (major, minor) = line.trim().split_once('.')?;
+            Some(major.parse::<u32>().ok()? * 10 + minor.parse::<u32>().ok()?)
+        })
+        .filter(|&arch| arch >= 75)
+        .collect()
+}
+
+/// The compiler the shim's CUDA sources are built with
+#[cfg(feature = "shim")]
+enum CudaCompiler {
+    Nvcc(PathBuf),
+    /// clang's CUDA frontend, with the toolkit it compiles against (`--cuda-path`)
+    Clang {
+        clang: PathBuf,
+        cuda_root: PathBuf,
+    },
+    /// The NVIDIA HPC SDK's nvc++ (`CUTLASS_CXX_TOOLCHAIN=nvhpc`), with the toolkit it compiles
+    /// against (`NVHPC_CUDA_HOME`)
+    Nvhpc {
+        nvcxx: PathBuf,
+        cuda_root: PathBuf,
+    },
+}
+
+#[cfg(feature = "shim")]
+impl CudaCompiler {
+    /// The compiler executable
+    fn path(&self) -> &Path {
+        match self {
+            CudaCompiler::Nvcc(nvcc) => nvcc,
+            CudaCompiler::Clang { clang, .. } => clang,
+            CudaCompiler::Nvhpc { nvcxx, .. } => nvcxx,
+        }
+    }
+
+    /// CMake's CUDA compiler for the `library` build, which only knows nvcc and clang: nvc++
+    /// hands over to the nvcc of its toolkit
+    #[cfg(feature = "library")]
+    fn cmake_compiler(&self) -> PathBuf {
+        match self {
+            CudaCompiler::Nvhpc { cuda_root, .. } => cuda_root
+                .join("bin")
+                .join(format!("nvcc{}", env::consts::EXE_SUFFIX)),
+            _ => self.path().to_path_buf(),
+        }
+    }
+
+    /// The CUDA toolkit the compiler builds against: clang's `--cuda-path`, nvc++'s
+    /// `NVHPC_CUDA_HOME`, or nvcc's own
+    fn cuda_root(&self) -> Option<PathBuf> {
+        match self {
+            CudaCompiler::Nvcc(nvcc) => nvcc_toolkit_root(nvcc),
+            CudaCompiler::Clang { cuda_root, .. } | CudaCompiler::Nvhpc { cuda_root, .. } => {
+                Some(cuda_root.clone())
+            }
+        }
+    }
+}
+
+/// Which CUDA runtime the shim links
+#[cfg(feature = "shim")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cudart {
+    /// `cudart_static`: nothing to deploy beside the binary, but one runtime per linked copy
+    Static,
+    /// The shared `cudart` (`libcudart.so`, `cudart64_*.dll`), which must be on the loader path
+    /// at runtime and can be shared with other CUDA libraries in the process
+    Shared,
+}
+
+/// Select the CUDA runtime from `CUTLASS_CUDART` (`static` or `shared`), by default shared with
+/// the `cudart-shared` feature and static otherwise
+#[cfg(feature = "shim")]
+fn select_cudart() -> Cudart {
+    println!("cargo:rerun-if-env-changed=CUTLASS_CUDART");
+    match env::var("CUTLASS_CUDART").as_deref() {
+        Ok("static") => Cudart::Static,
+        Ok("shared") => Cudart::Shared,
+        Ok(other) => panic!("CUTLASS_CUDART is '{}'; expected static or shared", other),
+        Err(_) if cfg!(feature = "cudart-shared") => Cudart::Shared,
+        Err(_) => Cudart::Static,
+    }
+}
+
+/// Pick the shim's CUDA compiler from `CUTLASS_CUDA_COMPILER`: `nvcc` (the default), `clang`
+/// (the default with the `clang` feature) for `clang++`, or the path of a clang executable;
+/// `CUTLASS_CXX_TOOLCHAIN=nvhpc` selects the HPC SDK's nvc++ instead. The toolkit is checked
+/// against CUTLASS and `archs` whenever its nvcc can be found, and its release returned alongside
+#[cfg(feature = "shim")]
+fn select_cuda_compiler(
+    cutlass_version: &str,
+    archs: &[u32],
+) -> (CudaCompiler, Option<(u32, u32)>) {
+    println!("cargo:rerun-if-env-changed=CUTLASS_CXX_TOOLCHAIN");
+    println!("cargo:rerun-if-env-changed=CUTLASS_CUDA_COMPILER");
+
+    match env::var("CUTLASS_CXX_TOOLCHAIN").as_deref() {
+        Ok("nvhpc") if cfg!(any(feature = "rdc", feature = "dlto")) => {
+            panic!(
+                "The rdc and dlto features need nvcc, which does the device link; unset \
+                CUTLASS_CXX_TOOLCHAIN"
+            )
+        }
+        Ok("nvhpc") => return select_nvhpc(cutlass_version, archs),
+        Ok("cuda") | Err(_) => {}
+        Ok(other) => panic!(
+            "CUTLASS_CXX_TOOLCHAIN is '{}'; expected cuda or nvhpc",
+            other
+        ),
+    }
+
+    // rdc and dlto outrank the clang feature, so --all-features still builds; only an explicit
+    // CUTLASS_CUDA_COMPILER=clang conflicts with them
+    let device_link = cfg!(any(feature = "rdc", feature = "dlto"));
+    let choice = env::var("CUTLASS_CUDA_COMPILER").unwrap_or_else(|_| {
+        if cfg!(feature = "clang") && device_link {
+            println!(
+                "cargo:warning=Compiling the shim with nvcc despite the clang feature: the rdc \
+                and dlto features need nvcc's device link"
+            );
+        }
+        let default = if cfg!(feature = "clang") && !device_link {
+            "clang"
+        } else {
+            "nvcc"
+        };
+        default.to_string()
+    });
+    let clang = match choice.as_str() {
+        "nvcc" => None,
+        "clang" => Some(PathBuf::from("clang++")),
+        path if Path::new(path)
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().contains("clang")) =>
+        {
+            Some(PathBuf::from(path))
+        }
+        other => panic!(
+            "CUTLASS_CUDA_COMPILER is '{}'; expected nvcc, clang or the path of a clang \
+            executable (set NVCC to use a different nvcc)",
+            other
+        ),
+    };
+
+    if device_link && clang.is_some() {
+        panic!(
+            "CUTLASS_CUDA_COMPILER selects clang, but the rdc and dlto features need nvcc, which \
+            does the device link; unset it or set it to nvcc"
+        );
+    }
+    let Some(clang) = clang else {
+        let nvcc = locate_nvcc().unwrap_or_else(|| {
+            panic!(
+                "The shim feature needs nvcc, which was not found: set NVCC to its path, set \
+                CUDA_HOME or CUDA_PATH to the CUDA toolkit, or add the toolkit's bin directory \
+                to PATH. Set CUTLASS_CUDA_COMPILER=clang to build with clang instead."
+            )
+        });
+        let toolkit = check_cuda_toolkit(&nvcc, cutlass_version, archs);
+        return (CudaCompiler::Nvcc(nvcc), Some(toolkit));
+    };
+
+    // clang needs the toolkit's headers, libdevice and cudart, but not its nvcc
+    let cuda_root = env::var_os("CUDA_HOME")
+        .or_else(|| env::var_os("CUDA_PATH"))
+        .map(PathBuf::from)
+        .or_else(|| {
+            let nvcc = locate_nvcc()?;
+            Some(nvcc.parent()?.parent()?.to_path_buf())
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "Building the shim with clang needs the CUDA toolkit for --cuda-path: set \
+                CUDA_HOME or CUDA_PATH to it"
+            )
+        });
+    let nvcc = cuda_root
+        .join("bin")
+        .join(format!("nvcc{}", env::consts::EXE_SUFFIX));
+    let toolkit = nvcc
+        .is_file()
+        .then(|| check_cuda_toolkit(&nvcc, cutlass_version, archs));
+    println!(
+        "cargo:warning=Using {} with the CUDA toolkit at {}",
+        clang.display(),
+        cuda_root.display()
+    );
+    (CudaCompiler::Clang { clang, cuda_root }, toolkit)
+}
+
+/// Find the HPC SDK's nvc++ (`NVCXX`, then `$NVHPC_ROOT/compilers/bin`, then `PATH`) and the CUDA
+/// toolkit it compiles against: `NVHPC_CUDA_HOME`, otherwise the newest one bundled with the SDK
+/// under `cuda/<version>`
+#[cfg(feature = "shim")]
+fn select_nvhpc(cutlass_version: &str, archs: &[u32]) -> (CudaCompiler, Option<(u32, u32)>) {
+    println!("cargo:rerun-if-env-changed=NVCXX");
+    println!("cargo:rerun-if-env-changed=NVHPC_ROOT");
+    println!("cargo:rerun-if-env-changed=NVHPC_CUDA_HOME");
+
+    if env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os != "linux") {
+        panic!("CUTLASS_CXX_TOOLCHAIN=nvhpc needs a Linux target; the HPC SDK only targets Linux");
+    }
+    let nvcxx = env::var_os("NVCXX")
+        .map(PathBuf::from)
+        .or_else(|| {
+            let root = PathBuf::from(env::var_os("NVHPC_ROOT")?);
+            Some(root.join("compilers").join("bin").join("nvc++"))
+                .filter(|candidate| candidate.is_file())
+        })
+        .or_else(|| {
+            let path = env::var_os("PATH")?;
+            env::split_paths(&path)
+                .map(|dir| dir.join("nvc++"))
+                .find(|candidate| candidate.is_file())
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "CUTLASS_CXX_TOOLCHAIN=nvhpc needs nvc++, which was not found: set NVCXX to its \
+                path, set NVHPC_ROOT to the SDK (e.g. /opt/nvidia/hpc_sdk/Linux_x86_64/24.5), or \
+                add its compilers/bin directory to PATH"
+            )
+        });
+
+    // nvc++ lives in <sdk>/compilers/bin and its toolkits in <sdk>/cuda/<version>
+    let cuda_root = env::var_os("NVHPC_CUDA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            let bundled = nvcxx
+                .canonicalize()
+                .ok()?
+                .parent()?
+                .parent()?
+                .parent()?
+                .join("cuda");
+            fs::read_dir(&bundled)
+                .ok()?
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|root| root.join("include").join("cuda.h").is_file())
+                .max_by_key(|root| cuda_header_version(root))
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "No CUDA toolkit was found next to {}: set NVHPC_CUDA_HOME to the toolkit nvc++ \
+                should compile against",
+                nvcxx.display()
+            )
+        });
+    let nvcc = cuda_root
+        .join("bin")
+        .join(format!("nvcc{}", env::consts::EXE_SUFFIX));
+    let toolkit = if nvcc.is_file() {
+        Some(check_cuda_toolkit(&nvcc, cutlass_version, archs))
+    } else {
+        cuda_header_version(&cuda_root)
+    };
+    println!(
+        "cargo:warning=Using {} with the CUDA toolkit at {}",
+        nvcxx.display(),
+        cuda_root.display()
+    );
+    (CudaCompiler::Nvhpc { nvcxx, cuda_root }, toolkit)
+}
+
+/// Link the runtime libraries nvc++'s objects call into (atomics, math and its C runtime) from the
+/// SDK's compilers/lib. They are shared libraries, found at runtime through `LD_LIBRARY_PATH` as
+/// the SDK's environment modules set it
+#[cfg(feature = "shim")]
+fn link_nvhpc_runtime(nvcxx: &Path) {
+    let Some(lib_dir) = nvcxx
+        .canonicalize()
+        .ok()
+        .and_then(|nvcxx| Some(nvcxx.parent()?.parent()?.join("lib")))
+        .filter(|dir| dir.is_dir())
+    else {
+        println!(
+            "cargo:warning=The HPC SDK's compilers/lib was not found next to {}; its runtime \
+            libraries are not linked",
+            nvcxx.display()
+        );
+        return;
+    };
+    println!("cargo:rustc-link-search=native={}", lib_dir.display());
+    for lib in ["nvhpcatm", "nvcpumath", "nvc"] {
+        println!("cargo:rustc-link-lib=dylib={}", lib);
+    }
+}
+
+/// How the shim's device code is compiled for a cargo profile
+#[cfg(feature = "shim")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NvccProfile {
+    /// `-O3` and `NDEBUG`: device asserts compiled out
+    Release,
+    /// Host debug info, line info for the device code (profilers and compute-sanitizer map it to
+    /// source lines) and device asserts, keeping the kernels optimized
+    Debug,
+    /// Unoptimized device code with full debug info (`-G`), for stepping through kernels in
+    /// cuda-gdb; CUTLASS kernels get far slower and take longer to compile
+    DeviceDebug,
+}
+
+/// Apply the `CUTLASS_NVCC_PROFILE` profile (`release`, `debug` or `device-debug`; by default
+/// `debug` when cargo builds without optimizations and `release` otherwise) to `build`, followed
+/// by the whitespace-separated extra flags of `CUTLASS_NVCC_FLAGS`
+#[cfg(feature = "shim")]
+fn apply_nvcc_profile(build: &mut cc::Build, compiler: &CudaCompiler) {
+    println!("cargo:rerun-if-env-changed=CUTLASS_NVCC_PROFILE");
+    println!("cargo:rerun-if-env-changed=CUTLASS_NVCC_FLAGS");
+
+    let profile = match env::var("CUTLASS_NVCC_PROFILE").as_deref() {
+        Ok("release") => NvccProfile::Release,
+        Ok("debug") => NvccProfile::Debug,
+        Ok("device-debug") => NvccProfile::DeviceDebug,
+        Ok(other) => panic!(
+            "CUTLASS_NVCC_PROFILE is '{}'; expected release, debug or device-debug",
+            other
+        ),
+        Err(_) if env::var("OPT_LEVEL").is_ok_and(|level| level == "0") => NvccProfile::Debug,
+        Err(_) => NvccProfile::Release,
+    };
+
+    // cc's debug setting would add nvcc's -G to every debug build, so the profile sets the debug
+    // flags itself
+    build.debug(false);
+    let clang = matches!(compiler, CudaCompiler::Clang { .. });
+    let nvhpc = matches!(compiler, CudaCompiler::Nvhpc { .. });
+    match profile {
+        NvccProfile::Release => {
+            build.opt_level(3).define("NDEBUG", None);
+        }
+        NvccProfile::Debug if nvhpc => {
+            build.flag("-g").flag("-gpu=lineinfo");
+        }
+        NvccProfile::DeviceDebug if nvhpc => {
+            build.flag("-g").flag("-gpu=debug");
+        }
+        NvccProfile::Debug if clang => {
+            build.flag("-g");
+        }
+        NvccProfile::Debug => {
+            build.flag("-g").flag("-lineinfo");
+        }
+        NvccProfile::DeviceDebug if clang => {
+            build.flag("-g").flag("--cuda-noopt-device-debug");
+        }
+        NvccProfile::DeviceDebug => {
+            build.flag("-g").flag("-G");
+        }
+    }
+
+    if let Ok(flags) = env::var("CUTLASS_NVCC_FLAGS") {
+        // -stdpar offloads the standard algorithms of the shim's host code and moves its heap
+        // into managed memory, behind the back of the shim's explicit device allocations
+        if nvhpc
+            && flags
+                .split_whitespace()
+                .any(|flag| flag.starts_with("-stdpar"))
+        {
+            panic!(
+                "CUTLASS_NVCC_FLAGS has -stdpar, which the shim must be built without; code of \
+                your own compiled with -stdpar links against it as usual"
+            );
+        }
+        for flag in flags.split_whitespace() {
+            build.flag(flag);
+        }
+    }
+}
+
+/// On MSVC targets, make sure nvcc finds cl.exe and pass the host flags CUTLASS's headers need
+/// under it: the real `__cplusplus` value (MSVC reports 199711L otherwise, hiding the C++17
+/// paths), standards conformance, C++ exceptions, and `/bigobj` for the larger translation units
+#[cfg(feature = "shim")]
+fn configure_msvc_host(build: &mut cc::Build) {
+    if !env::var("CARGO_CFG_TARGET_ENV").is_ok_and(|target_env| target_env == "msvc") {
+        return;
+    }
+    for flag in ["/Zc:__cplusplus", "/permissive-", "/EHsc", "/bigobj"] {
+        build.flag(format!("-Xcompiler={}", flag));
+    }
+
+    // nvcc only looks for cl.exe on PATH, which only a developer prompt sets up; otherwise find
+    // it the way cc does and hand nvcc its path and environment (INCLUDE, LIB, ...)
+    let on_path = env::var_os("PATH")
+        .is_some_and(|path| env::split_paths(&path).any(|dir| dir.join("cl.exe").is_file()));
+    if on_path {
+        return;
+    }
+    let target = env::var("TARGET").unwrap();
+    let cl = cc::windows_registry::find_tool(&target, "cl.exe").unwrap_or_else(|| {
+        panic!(
+            "nvcc needs the MSVC host compiler (cl.exe) on Windows, but it is not on PATH and no \
+            Visual Studio installation with the C++ build tools was found for {}. Install the \
+            \"Desktop development with C++\" workload or build from a Developer Command Prompt.",
+            target
+        )
+    });
+    for (key, value) in cl.get_envs() {
+        env::set_var(key, value);
+    }
+    build.flag(format!("--compiler-bindir={}", cl.path().display()));
+    println!(
+        "cargo:warning=Using the MSVC host compiler at {}",
+        cl.path().display()
+    );
+}
+
+/// Link the `cudart` CUDA runtime from the library directory of `cuda_root`, exported to dependent
+/// build scripts as DEP_CUTLASS_CUDART and DEP_CUTLASS_CUDART_DIR. Without a known toolkit the
+/// linker's default search path has to hold it
+#[cfg(feature = "shim")]
+fn link_cudart(cuda_root: Option<&Path>, cudart: Cudart) {
+    if let Some(cuda_root) = cuda_root {
+        let lib_dir = cuda_lib_dir(cuda_root);
+        println!("cargo:rustc-link-search=native={}", lib_dir.display());
+        println!("cargo:cudart_dir={}", lib_dir.display());
+    }
+    match cudart {
+        Cudart::Static => {
+            println!("cargo:cudart=static");
+            println!("cargo:rustc-link-lib=static=cudart_static");
+            if env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "linux") {
+                for lib in ["rt", "pthread", "dl"] {
+                    println!("cargo:rustc-link-lib=dylib={}", lib);
+                }
+            }
+        }
+        Cudart::Shared => {
+            println!("cargo:cudart=shared");
+            println!("cargo:rustc-link-lib=dylib=cudart");
+        }
+    }
+}
+
+/// Compile the C ABI shim in `src/shim` with `compiler` against the resolved CUTLASS headers, or
+/// reuse the copy the object cache holds for the same configuration, and link it with `cudart`
+#[cfg(feature = "shim")]
+fn build_shim(
+    compiler: &CudaCompiler,
+    cudart: Cudart,
+    include_dir: &Path,
+    archs: &[u32],
+    cutlass_version: &str,
+    toolkit: Option<(u32, u32)>,
+) {
+    let shim_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("src")
+        .join("shim");
+    println!("cargo:rerun-if-changed={}", shim_dir.display());
+
+    // Kernels specific to an arch are only instantiated when it is selected
+    let sm75 = archs.contains(&75);
+    let sm90 = archs.contains(&90);
+    let sm100 = archs.contains(&100);
+
+    // The shim reads the compiled list back at runtime to reject devices none of them cover
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let list: Vec<String> = archs.iter().map(|arch| arch.to_string()).collect();
+    fs::write(
+        out_dir.join("cutlass_sys_archs.h"),
+        format!(
+            "// Generated by build.rs from CUTLASS_CUDA_ARCH and the sm* features.\n\
+             #pragma once\n\n\
+             #define CUTLASS_SYS_ARCHS {}\n",
+            list.join(", ")
+        ),
+    )
+    .expect("Failed to write cutlass_sys_archs.h");
+
+    let mut sources: Vec<PathBuf> = fs::read_dir(&shim_dir)
+        .expect("Failed to read shim directory")
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "cu"))
+        .collect();
+    sources.sort();
+    // One translation unit per kernel family, compiled in parallel by cc within cargo's jobserver
+    // (or NUM_JOBS); the largest go first so the longest compiles don't start last
+    sources.sort_by_key(|source| {
+        std::cmp::Reverse(fs::metadata(source).map_or(0, |metadata| metadata.len()))
+    });
+
+    // cc runs a single executable, so a launcher goes in front of the compiler through a script
+    let executable = match compiler_launcher() {
+        Some(launcher) => launcher_script(&launcher, compiler.path(), &out_dir),
+        None => compiler.path().to_path_buf(),
+    };
+
+    let mut build = cc::Build::new();
+    build.flag("-std=c++17");
+    match compiler {
+        CudaCompiler::Nvcc(_) => {
+            // cc takes the CUDA compiler from NVCC, so it uses the nvcc checked by
+            // check_cuda_toolkit
+            env::set_var("NVCC", &executable);
+            // The runtime is linked by link_cudart for either compiler: cc would look it up
+            // next to NVCC, which is the launcher script when there is one
+            build
+                .cuda(true)
+                .cudart("none")
+                .flag("--expt-relaxed-constexpr");
+            configure_msvc_host(&mut build);
+            // Cross-compiling for aarch64, nvcc takes the target's headers and libraries from
+            // targets/<dir> of the toolkit; cc already passes the target's C++ compiler
+            // (aarch64-linux-gnu-g++, or CXX_aarch64_unknown_linux_gnu) as -ccbin
+            if let Some(target_dir) = compiler.cuda_root().and_then(|root| cuda_target_dir(&root)) {
+                build.flag(format!("--target-directory={}", target_dir));
+            }
+            // Device LTO compiles to intermediate code (lto_*), which device_link_lto turns into
+            // machine code; it needs relocatable device code like rdc
+            let code = if cfg!(feature = "dlto") { "lto" } else { "sm" };
+            for &arch in archs {
+                build.flag(format!(
+                    "-gencode=arch=compute_{0},code={1}_{0}",
+                    arch_target(arch),
+                    code
+                ));
+            }
+            // PTX for the newest arch lets the driver JIT the kernels for devices newer than
+            // the list; a device-linked LTO build has no PTX to embed
+            if !cfg!(feature = "dlto") {
+                let newest = archs.last().unwrap();
+                build.flag(format!(
+                    "-gencode=arch=compute_{0},code=compute_{0}",
+                    newest
+                ));
+            }
+            if cfg!(any(feature = "rdc", feature = "dlto")) {
+                build.flag("-rdc=true");
+            }
+        }
+        CudaCompiler::Clang { cuda_root, .. } => {
+            // clang embeds PTX next to the SASS of every arch, which covers newer devices
+            build
+                .cpp(true)
+                .compiler(&executable)
+                .flag("-x")
+                .flag("cuda")
+                .flag(format!("--cuda-path={}", cuda_root.display()))
+                .flag("-Wno-unknown-cuda-version");
+            for &arch in archs {
+                build.flag(format!("--cuda-gpu-arch=sm_{}", arch_target(arch)));
+            }
+        }
+        CudaCompiler::Nvhpc { cuda_root, .. } => {
+            // nvc++ takes its toolkit from NVHPC_CUDA_HOME, so it compiles against the one
+            // link_cudart links; -cuda makes it compile the .cu files as CUDA, without -stdpar
+            // or -acc offloading the shim's host code
+            env::set_var("NVHPC_CUDA_HOME", cuda_root);
+            let gpus: Vec<String> = archs
+                .iter()
+                .map(|&arch| format!("cc{}", arch_target(arch)))
+                .collect();
+            build
+                .cpp(true)
+                .compiler(&executable)
+                .flag("-cuda")
+                .flag(format!("-gpu={}", gpus.join(",")));
+        }
+    }
+    apply_nvcc_profile(&mut build, compiler);
+    let mut defines = Vec::new();
+    if sm75 {
+        defines.push("CUTLASS_SYS_SM75");
+    }
+    if sm90 {
+        defines.push("CUTLASS_SYS_SM90");
+    }
+    if sm100 {
+        defines.push("CUTLASS_SYS_SM100");
+    }
+    // CUTLASS directories on the include path, relative to its root, in search order
+    let mut header_dirs = Vec::new();
+    // The library.cu entry points wrap cutlass::library, linked by build_library
+    #[cfg(feature = "library")]
+    {
+        defines.push("CUTLASS_SYS_LIBRARY");
+        header_dirs.push("tools/library/include");
+    }
+    // The reference.cu entry points wrap the host references of tools/util
+    #[cfg(feature = "reference")]
+    {
+        defines.push("CUTLASS_SYS_REFERENCE");
+        header_dirs.push("tools/util/include");
+    }
+    for define in &defines {
+        build.define(define, None);
+    }
+    // gemm_softmax.cu, gemm_layernorm.cu, b2b_gemm.cu and b2b_conv.cu instantiate
+    // operators of CUTLASS's examples; example 13 includes its own headers
+    // relative to its directory
+    header_dirs.extend(["include", "examples", "examples/13_two_tensor_op_fusion"]);
+    let cutlass_root = include_dir.join("..");
+    let smoke_test = smoke_test_enabled();
+    // Only the headers the enabled families include, unless full-headers asks for the tree
+    let header_root = if cfg!(feature = "full-headers") {
+        cutlass_root
+    } else {
+        let extra_headers: &[&str] = if smoke_test {
+            &[SMOKE_TEST_HEADER]
+        } else {
+            &[]
+        };
+        install_headers(
+            &normalize_path(&cutlass_root),
+            &header_dirs,
+            &shim_dir,
+            &sources,
+            extra_headers,
+            &defines,
+            &out_dir,
+        )
+    };
+    for dir in &header_dirs {
+        build.include(header_root.join(dir));
+    }
+    build.include(&shim_dir).include(&out_dir);
+    write_compile_commands(&build, compiler.path(), &shim_dir, &sources, &out_dir);
+    write_kernel_manifest(&sources, &defines, archs, &out_dir);
+    if smoke_test {
+        smoke_compile(&build, compiler.path(), &out_dir);
+    }
+
+    // Keyed by everything that reaches the compiler: the real compiler rather than a launcher
+    // script, its arguments (arch list, profile, defines, include paths) and the shim's sources
+    // and headers, so a cleaned target/ or a branch switch reuses an earlier build
+    let entry = object_cache_enabled().then(|| {
+        let mut inputs = vec![
+            cutlass_version.as_bytes().to_vec(),
+            format!("{:?}", toolkit).into_bytes(),
+            env::var("TARGET").unwrap_or_default().into_bytes(),
+            compiler.path().to_string_lossy().into_owned().into_bytes(),
+        ];
+        inputs.extend(
+            build
+                .get_compiler()
+                .args()
+                .iter()
+                .map(|arg| arg.to_string_lossy().into_owned().into_bytes()),
+        );
+        let mut files: Vec<PathBuf> = fs::read_dir(&shim_dir)
+            .expect("Failed to read shim directory")
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| path.is_file())
+            .collect();
+        files.sort();
+        files.push(out_dir.join("cutlass_sys_archs.h"));
+        for file in &files {
+            inputs.push(
+                file.file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+                    .into_bytes(),
+            );
+            inputs.push(fs::read(file).expect("Failed to read a shim source"));
+        }
+        get_cache_dir()
+            .join("objects")
+            .join(cutlass_version)
+            .join(format!("cutlass_sys_shim-{}", object_cache_key(&inputs)))
+    });
+    let lib_file = static_lib_file("cutlass_sys_shim");
+    // build::compile_cached device-links a dependent crate's relocatable objects with these
+    if cfg!(feature = "rdc") {
+        println!("cargo:rdc_lib={}", out_dir.join(&lib_file).display());
+        if cfg!(feature = "dlto") {
+            println!("cargo:dlto=1");
+        }
+    }
+    if let Some(cached) = entry.as_ref().map(|entry| entry.join(&lib_file)) {
+        if cached.is_file() {
+            println!(
+                "cargo:warning=Using the cached shim at {}",
+                cached.display()
+            );
+            fs::copy(&cached, out_dir.join(&lib_file)).expect("Failed to copy the cached shim");
+            // What cc prints for the build below
+            println!("cargo:rustc-link-search=native={}", out_dir.display());
+            println!("cargo:rustc-link-lib=static=cutlass_sys_shim");
+            link_cudart(compiler.cuda_root().as_deref(), cudart);
+            if let CudaCompiler::Nvhpc { nvcxx, .. } = compiler {
+                link_nvhpc_runtime(nvcxx);
+            }
+            link_cxx_stdlib();
+            return;
+        }
+    }
+    if cfg!(any(feature = "rdc", feature = "dlto")) {
+        // cc device-links every CUDA library it compiles, which would leave the kernels
+        // registered by an object of their own; the dependent crate's single device link covers
+        // the shim's relocatable objects instead, so they are archived as they are
+        let mut objects = build.files(&sources).compile_intermediates();
+        // With dlto alone, the shim's own device link is the link-time optimized one
+        if !cfg!(feature = "rdc") {
+            let dlink = device_link_lto(&build, &objects, archs, &out_dir);
+            objects.push(dlink);
+        }
+        cc::Build::new()
+            .objects(&objects)
+            .compile("cutlass_sys_shim");
+        link_cxx_stdlib();
+    } else {
+        build.files(&sources).compile("cutlass_sys_shim");
+    }
+    // After the shim, whose objects reference it
+    link_cudart(compiler.cuda_root().as_deref(), cudart);
+    if let CudaCompiler::Nvhpc { nvcxx, .. } = compiler {
+        link_nvhpc_runtime(nvcxx);
+    }
+    if let Some(entry) = entry {
+        store_cached_object(&out_dir.join(&lib_file), &entry);
+    }
+}
+
+/// Device-link `objects`, compiled to LTO intermediate code, into machine code for `archs` with
+/// nvcc's link-time optimization (`-dlto`), which inlines and optimizes across the shim's
+/// translation units; returns the object holding the linked device code
+#[cfg(feature = "shim")]
+fn device_link_lto(
+    build: &cc::Build,
+    objects: &[PathBuf],
+    archs: &[u32],
+    out_dir: &Path,
+) -> PathBuf {
+    let dlink = out_dir.join("cutlass_sys_shim_dlink.o");
+    let tool = build.get_compiler();
+    let mut command = Command::new(tool.path());
+    // The compile's arguments, with machine code in place of its lto_* targets
+    command.args(
+        tool.args()
+            .iter()
+            .filter(|arg| !arg.to_string_lossy().starts_with("-gencode")),
+    );
+    for &arch in archs {
+        command.arg(format!(
+            "-gencode=arch=compute_{0},code=sm_{0}",
+            arch_target(arch)
+        ));
+    }
+    let status = command
+        .arg("--device-link")
+        .arg("-dlto")
+        .arg("-o")
+        .arg(&dlink)
+        .args(objects)
+        .status()
+        .unwrap_or_else(|e| panic!("Failed to run {} ({})", tool.path().display(), e));
+    if !status.success() {
+        panic!("The LTO device link of the shim failed ({})", status);
+    }
+    dlink
+}
+
+/// One `#if` level of a shim source: the define each `#if` / `#elif` branch so far tests and
+/// whether it must be defined, and whether the `#else` branch has been reached
+#[cfg(feature = "shim")]
+struct Guard {
+    branches: Vec<(String, bool)>,
+    otherwise: bool,
+}
+
+#[cfg(feature = "shim")]
+impl Guard {
+    fn new(define: String, defined: bool) -> Self {
+        Guard {
+            branches: vec![(define, defined)],
+            otherwise: false,
+        }
+    }
+
+    /// The defines the current branch needs set (`true`) or unset (`false`): every earlier
+    /// branch's condition failed, and its own holds
+    fn conditions(&self) -> impl Iterator<Item = (&str, bool)> {
+        let current = self.branches.len() - usize::from(!self.otherwise);
+        self.branches
+            .iter()
+            .enumerate()
+            .map(move |(i, (define, defined))| (define.as_str(), (i == current) == *defined))
+    }
+}
+
+/// The lines of the shim file `source` other than its `#if` / `#ifdef` / `#ifndef` / `#elif` /
+/// `#else` / `#endif` directives, trimmed, each with the defines its branches need set (`true`)
+/// or unset (`false`). A guard other than a lone `defined(CUTLASS_SYS_*)` panics, so a shim edit
+/// fails the build rather than silently writing a wrong manifest or installing the wrong headers
+#[cfg(feature = "shim")]
+fn guarded_lines<'a>(source: &Path, text: &'a str) -> Vec<(&'a str, Vec<(String, bool)>)> {
+    let mut lines = Vec::new();
+    let mut guards: Vec<Guard> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        let directive = line.split("//").next().unwrap().trim_end();
+        let define = |condition: &str, bare: bool| {
+            let condition = condition.trim();
+            let define = if bare {
+                Some(condition)
+            } else {
+                condition
+                    .strip_prefix("defined(")
+                    .and_then(|rest| rest.strip_suffix(')'))
+            };
+            match define {
+                Some(define)
+                    if define.starts_with("CUTLASS_SYS_")
+                        && define
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+                {
+                    define.to_string()
+                }
+                _ => panic!(
+                    "{}: unsupported preprocessor condition `{}`; build.rs only follows a lone \
+                     `defined(CUTLASS_SYS_*)`",
+                    source.display(),
+                    line
+                ),
+            }
+        };
+        if let Some(condition) = directive.strip_prefix("#ifdef ") {
+            guards.push(Guard::new(define(condition, true), true));
+        } else if let Some(condition) = directive.strip_prefix("#ifndef ") {
+            guards.push(Guard::new(define(condition, true), false));
+        } else if let Some(condition) = directive.strip_prefix("#if ") {
+            guards.push(Guard::new(define(condition, false), true));
+        } else if let Some(condition) = directive.strip_prefix("#elif ") {
+            let define = define(condition, false);
+            let guard = guards
+                .last_mut()
+                .expect("#elif outside #if in a shim source");
+            guard.branches.push((define, true));
+        } else if directive == "#else" {
+            guards
+                .last_mut()
+                .expect("#else outside #if in a shim source")
+                .otherwise = true;
+        } else if directive == "#endif" {
+            guards.pop();
+        } else if directive.starts_with('#')
+            && ["if", "elif", "else", "endif"]
+                .iter()
+                .any(|keyword| directive[1..].trim_start().starts_with(keyword))
+        {
+            panic!(
+                "{}: unsupported preprocessor directive `{}` in a shim source",
+                source.display(),
+                line
+            );
+        } else {
+            let conditions = guards
+                .iter()
+                .flat_map(Guard::conditions)
+                .map(|(define, defined)| (define.to_string(), defined))
+                .collect();
+            lines.push((line, conditions));
+        }
+    }
+    lines
+}
+
+/// Write OUT_DIR/cutlass_sys_kernels.json, listing the `extern "C"` entry points the shim is
+/// compiled with: those of the enabled `CUTLASS_SYS_*` defines only, so dispatch layers and code
+/// generators bind what the library holds rather than keeping a list of their own. Each carries
+/// its family (the source file's stem) and the defines its `#if` / `#ifdef` / `#ifndef` / `#elif`
+/// / `#else` branches need set or (prefixed with `!`) unset; element type, layout and tile come
+/// from the config at runtime. The path is exported as the `kernels` key
+#[cfg(feature = "shim")]
+fn write_kernel_manifest(sources: &[PathBuf], defines: &[&str], archs: &[u32], out_dir: &Path) {
+    let mut sources = sources.to_vec();
+    sources.sort();
+    let mut entries = Vec::new();
+    for source in &sources {
+        let text = fs::read_to_string(source).expect("Failed to read a shim source");
+        let family = source.file_stem().unwrap().to_string_lossy().into_owned();
+        for (line, conditions) in guarded_lines(source, &text) {
+            let Some(declaration) = line.strip_prefix("extern \"C\"") else {
+                continue;
+            };
+            if conditions
+                .iter()
+                .any(|(define, defined)| defines.contains(&define.as_str()) != *defined)
+            {
+                continue;
+            }
+            let Some(name) = declaration
+                .split('(')
+                .next()
+                .and_then(|head| head.rsplit([' ', '*']).next())
+                .filter(|name| name.starts_with("cutlass_sys_"))
+            else {
+                continue;
+            };
+            let requires: Vec<String> = conditions
+                .iter()
+                .map(|(define, defined)| {
+                    format!("\"{}{}\"", if *defined { "" } else { "!" }, define)
+                })
+                .collect();
+            entries.push(format!(
+                "    {{ \"name\": \"{}\", \"family\": \"{}\", \"source\": \"{}.cu\", \
+                 \"requires\": [{}] }}",
+                name,
+                family,
+                family,
+                requires.join(", ")
+            ));
+        }
+    }
+
+    let list: Vec<String> = archs.iter().map(|arch| arch.to_string()).collect();
+    let path = out_dir.join("cutlass_sys_kernels.json");
+    fs::write(
+        &path,
+        format!(
+            "{{\n  \"archs\": [{}],\n  \"kernels\": [\n{}\n  ]\n}}\n",
+            list.join(", "),
+            entries.join(",\n")
+        ),
+    )
+    .expect("Failed to write cutlass_sys_kernels.json");
+    println!("cargo:kernels={}", path.display());
+}
+
+/// Install the CUTLASS headers the shim's enabled kernel families include under OUT_DIR/cutlass,
+/// laid out as in `cutlass_root`, and return that directory to compile against in its place.
+/// The headers are found by following `#include`s from `sources` (and `extra_headers`), looked
+/// up in the including file's directory, `header_dirs` of the root and the shim's directory as
+/// the compiler would. The shim's `.cu` / `.cuh` files are only followed in the branches the
+/// enabled `CUTLASS_SYS_*` defines take, so the sm90 and sm100 kernels, the references and the
+/// library bring in their sub-trees only when selected. CUTLASS's own headers are followed
+/// whatever their conditions, which may install a few headers no branch includes but never
+/// misses one. Headers installed by an earlier build and unchanged since are not copied again
+#[cfg(feature = "shim")]
+fn install_headers(
+    cutlass_root: &Path,
+    header_dirs: &[&str],
+    shim_dir: &Path,
+    sources: &[PathBuf],
+    extra_headers: &[&str],
+    defines: &[&str],
+    out_dir: &Path,
+) -> PathBuf {
+    let installed_root = out_dir.join("cutlass");
+    let search: Vec<PathBuf> = header_dirs
+        .iter()
+        .map(|dir| cutlass_root.join(dir))
+        .chain([shim_dir.to_path_buf()])
+        .collect();
+    // A quoted include is looked up next to the file including it first
+    let resolve = |name: &str, including: Option<&Path>| {
+        including
+            .and_then(Path::parent)
+            .into_iter()
+            .chain(search.iter().map(PathBuf::as_path))
+            .map(|dir| normalize_path(&dir.join(name)))
+            .find(|path| path.is_file())
+    };
+
+    let mut pending: Vec<PathBuf> = sources.to_vec();
+    pending.extend(extra_headers.iter().filter_map(|name| resolve(name, None)));
+    let mut seen: HashSet<PathBuf> = pending.iter().cloned().collect();
+    let (mut copied, mut total) = (0, 0);
+    while let Some(file) = pending.pop() {
+        let text = fs::read_to_string(&file)
+            .unwrap_or_else(|e| panic!("Failed to read {} ({})", file.display(), e));
+        let guarded = file.starts_with(shim_dir)
+            && file
+                .extension()
+                .is_some_and(|extension| extension == "cu" || extension == "cuh");
+        let lines: Vec<&str> = if guarded {
+            guarded_lines(&file, &text)
+                .into_iter()
+                .filter(|(_, conditions)| {
+                    conditions
+                        .iter()
+                        .all(|(define, defined)| defines.contains(&define.as_str()) == *defined)
+                })
+                .map(|(line, _)| line)
+                .collect()
+        } else {
+            text.lines().map(str::trim).collect()
+        };
+        for line in lines {
+            let Some(include) = line
+                .strip_prefix('#')
+                .and_then(|directive| directive.trim_start().strip_prefix("include"))
+                .map(str::trim)
+            else {
+                continue;
+            };
+            let (name, including) = match include.chars().next() {
+                Some('"') => (include[1..].split('"').next(), Some(file.as_path())),
+                Some('<') => (include[1..].split('>').next(), None),
+                _ => continue,
+            };
+            // Not found: a system, C++ or CUDA toolkit header
+            let Some(path) = name.and_then(|name| resolve(name, including)) else {
+                continue;
+            };
+            if seen.insert(path.clone()) {
+                pending.push(path);
+            }
+        }
+
+        let Ok(relative) = file.strip_prefix(cutlass_root) else {
+            continue;
+        };
+        total += 1;
+        let installed = installed_root.join(relative);
+        let source = fs::metadata(&file).expect("Failed to read a CUTLASS header's metadata");
+        let current = fs::metadata(&installed).is_ok_and(|metadata| {
+            metadata.len() == source.len() && metadata.modified().ok() >= source.modified().ok()
+        });
+        if !current {
+            fs::create_dir_all(installed.parent().unwrap())
+                .expect("Failed to create a header directory");
+            fs::copy(&file, &installed).expect("Failed to install a CUTLASS header");
+            copied += 1;
+        }
+    }
+    println!(
+        "cargo:warning=Installed {} CUTLASS headers for the enabled kernels at {} ({} copied); \
+         enable full-headers to compile against the whole tree",
+        total,
+        installed_root.display(),
+        copied
+    );
+    installed_root
+}
+
+/// `path` with its `.` and `..` components resolved lexically
+#[cfg(feature = "shim")]
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// `CUTLASS_SMOKE_TEST=1`: compile a one-kernel translation unit before the shim
+#[cfg(feature = "shim")]
+fn smoke_test_enabled() -> bool {
+    println!("cargo:rerun-if-env-changed=CUTLASS_SMOKE_TEST");
+    env::var("CUTLASS_SMOKE_TEST").is_ok_and(|value| value == "1")
+}
+
+/// The CUTLASS header the smoke test's translation unit includes
+#[cfg(feature = "shim")]
+const SMOKE_TEST_HEADER: &str = "cutlass/gemm/device/gemm.h";
+
+/// Compile a translation unit instantiating a single small SIMT GEMM with the shim's exact
+/// command line, so a CUDA compiler, host compiler and CUTLASS tree that don't work together fail
+/// with the compiler's first errors and the three of them named, not with the shim's template
+/// errors in every translation unit
+#[cfg(feature = "shim")]
+fn smoke_compile(build: &cc::Build, compiler: &Path, out_dir: &Path) {
+    let dir = out_dir.join("smoke");
+    fs::create_dir_all(&dir).expect("Failed to create the smoke test directory");
+    let source = dir.join("cutlass_sys_smoke.cu");
+    fs::write(
+        &source,
+        format!(
+            "// Generated by build.rs for CUTLASS_SMOKE_TEST.\n#include <{}>\n\n",
+            SMOKE_TEST_HEADER
+        ) + "using SmokeGemm = cutlass::gemm::device::Gemm<\n    \
+             float, cutlass::layout::RowMajor, float, cutlass::layout::RowMajor, float,\n    \
+             cutlass::layout::RowMajor>;\n\n\
+             int cutlass_sys_smoke(const float* a, const float* b, float* c) {\n  \
+             SmokeGemm gemm;\n  \
+             return static_cast<int>(\n      \
+             gemm({{8, 8, 8}, {a, 8}, {b, 8}, {c, 8}, {c, 8}, {1.0f, 0.0f}}));\n\
+             }\n",
+    )
+    .expect("Failed to write the smoke test source");
+
+    let tool = build.get_compiler();
+    let output = Command::new(compiler)
+        .args(tool.args())
+        .arg("-c")
+        .arg(&source)
+        .arg("-o")
+        .arg(dir.join("cutlass_sys_smoke.o"))
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to run {} ({})", compiler.display(), e));
+    if output.status.success() {
+        println!("cargo:warning=CUTLASS smoke test compiled");
+        return;
+    }
+
+    // nvcc runs the host compiler cc passes as -ccbin; clang and nvc++ compile host code
+    // themselves
+    let host = tool
+        .args()
+        .iter()
+        .skip_while(|arg| *arg != "-ccbin")
+        .nth(1)
+        .map_or_else(
+            || compiler.display().to_string(),
+            |host| host.to_string_lossy().into_owned(),
+        );
+    let log = String::from_utf8_lossy(&output.stderr).into_owned()
+        + &String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = log.lines().take(40).collect();
+    eprintln!("\n========================================");
+    eprintln!("ERROR: The CUTLASS smoke test failed to compile");
+    eprintln!("========================================");
+    eprintln!("CUDA compiler: {}", compiler.display());
+    eprintln!("Host compiler: {}", host);
+    eprintln!("Source:        {}", source.display());
+    eprintln!("\n{}", lines.join("\n"));
+    if log.lines().count() > lines.len() {
+        eprintln!("... (output truncated)");
+    }
+    eprintln!("\nA single CUTLASS kernel does not build with this toolchain. Check that the host");
+    eprintln!("compiler is one the CUDA toolkit supports (set CXX to pick another) and that the");
+    eprintln!("toolkit is recent enough for the CUTLASS version.");
+    eprintln!("========================================\n");
+    panic!(
+        "CUTLASS smoke test failed ({}). See the error above.",
+        output.status
+    );
+}
+
+/// Whether compiled objects are looked up in and stored to the persistent cache;
+/// `CUTLASS_OBJECT_CACHE=0` bypasses it, recompiling every time
+#[cfg(feature = "shim")]
+fn object_cache_enabled() -> bool {
+    println!("cargo:rerun-if-env-changed=CUTLASS_OBJECT_CACHE");
+    env::var("CUTLASS_OBJECT_CACHE").map_or(true, |value| value != "0")
+}
+
+/// 64-bit FNV-1a hash of `inputs`, each prefixed with its length, as 16 hex digits. Stable across
+/// toolchains, unlike std's hashers
+#[cfg(feature = "shim")]
+fn object_cache_key(inputs: &[Vec<u8>]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for input in inputs {
+        for &byte in (input.len() as u64).to_le_bytes().iter().chain(input) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// The file name cc gives the static library `name` on the target
+#[cfg(feature = "shim")]
+fn static_lib_file(name: &str) -> String {
+    if env::var("CARGO_CFG_TARGET_ENV").is_ok_and(|target_env| target_env == "msvc") {
+        format!("{}.lib", name)
+    } else {
+        format!("lib{}.a", name)
+    }
+}
+
+/// Copy the freshly built `lib` into the cache `entry`, through a temporary name so a concurrent
+/// or interrupted build never leaves a partial library there
+#[cfg(feature = "shim")]
+fn store_cached_object(lib: &Path, entry: &Path) {
+    let file_name = lib.file_name().unwrap();
+    let partial = entry.join(format!(
+        "{}.{}.partial",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+    let stored = fs::create_dir_all(entry)
+        .and_then(|_| fs::copy(lib, &partial))
+        .and_then(|_| fs::rename(&partial, entry.join(file_name)));
+    if let Err(e) = stored {
+        let _ = fs::remove_file(&partial);
+        println!(
+            "cargo:warning=Failed to cache {} in {}: {}",
+            lib.display(),
+            entry.display(),
+            e
+        );
+    }
+}
+
+/// Link the C++ standard library as cc does after a C++ or CUDA build (`CXXSTDLIB` overrides it)
+#[cfg(feature = "shim")]
+fn link_cxx_stdlib() {
+    println!("cargo:rerun-if-env-changed=CXXSTDLIB");
+    let stdlib = env::var("CXXSTDLIB").unwrap_or_else(|_| {
+        let target = env::var("TARGET").unwrap_or_default();
+        if target.contains("msvc") {
+            String::new()
+        } else if ["apple", "freebsd", "openbsd", "android"]
+            .iter()
+            .any(|os| target.contains(os))
+        {
+            "c++".to_string()
+        } else {
+            "stdc++".to_string()
+        }
+    });
+    if !stdlib.is_empty() {
+        println!("cargo:rustc-link-lib={}", stdlib);
+    }
+}
+
+/// Write OUT_DIR/compile_commands.json with the command `build` compiles each of `sources` with,
+/// naming `compiler` rather than any launcher script, so clangd and other IDE tooling see the
+/// shim's include paths, defines and flags. Exported to dependent build scripts as
+/// DEP_CUTLASS_COMPILE_COMMANDS
+#[cfg(feature = "shim")]
+fn write_compile_commands(
+    build: &cc::Build,
+    compiler: &Path,
+    directory: &Path,
+    sources: &[PathBuf],
+    out_dir: &Path,
+) {
+    // JSON string literal of a path or argument
+    fn quote(value: &std::ffi::OsStr) -> String {
+        let mut quoted = String::from("\"");
+        for c in value.to_string_lossy().chars() {
+            match c {
+                '"' => quoted.push_str("\\\""),
+                '\\' => quoted.push_str("\\\\"),
+                c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+                c => quoted.push(c),
+            }
+        }
+        quoted.push('"');
+        quoted
+    }
+
+    let tool = build.get_compiler();
+    let mut prefix = vec![quote(compiler.as_os_str())];
+    prefix.extend(tool.args().iter().map(|arg| quote(arg)));
+    let entries: Vec<String> = sources
+        .iter()
+        .map(|source| {
+            let mut arguments = prefix.clone();
+            arguments.push(quote("-c".as_ref()));
+            arguments.push(quote(source.as_os_str()));
+            format!(
+                "  {{\n    \"directory\": {},\n    \"file\": {},\n    \"arguments\": [{}]\n  }}",
+                quote(directory.as_os_str()),
+                quote(source.as_os_str()),
+                arguments.join(", ")
+            )
+        })
+        .collect();
+
+    let path = out_dir.join("compile_commands.json");
+    fs::write(&path, format!("[\n{}\n]\n", entries.join(",\n")))
+        .expect("Failed to write compile_commands.json");
+    println!("cargo:compile_commands={}", path.display());
+}
+
+/// Architectures the prebuilt kernel pack carries SASS for; the PTX of the last covers newer
+/// devices
+#[cfg(feature = "prebuilt-kernels")]
+const PREBUILT_ARCHS: &[u32] = &[80, 86, 89, 90];
+
+/// Provide the fatbin the `prebuilt-kernels` feature embeds as OUT_DIR/prebuilt_gemm.fatbin: the
+/// copy shipped in src/prebuilt (or CUTLASS_PREBUILT_KERNELS) when there is one, so consumers need
+/// no nvcc, and otherwise one compiled here from src/prebuilt/gemm.cu
+#[cfg(feature = "prebuilt-kernels")]
+fn build_prebuilt_kernels(include_dir: &Path, cutlass_version: &str) {
+    println!("cargo:rerun-if-env-changed=CUTLASS_PREBUILT_KERNELS");
+    let prebuilt_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("src")
+        .join("prebuilt");
+    println!("cargo:rerun-if-changed={}", prebuilt_dir.display());
+
+    let out_file = PathBuf::from(env::var("OUT_DIR").unwrap()).join("prebuilt_gemm.fatbin");
+    let shipped = env::var_os("CUTLASS_PREBUILT_KERNELS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| prebuilt_dir.clone())
+        .join("gemm.fatbin");
+    if shipped.exists() {
+        fs::copy(&shipped, &out_file).expect("Failed to copy the prebuilt kernel pack");
+        return;
+    }
+
+    let nvcc = locate_nvcc().unwrap_or_else(|| {
+        panic!(
+            "No prebuilt kernel pack found at '{}' and nvcc was not found to compile one. Set \
+            NVCC, CUDA_HOME or CUDA_PATH, add the CUDA toolkit's bin directory to PATH, or set \
+            CUTLASS_PREBUILT_KERNELS to a directory holding gemm.fatbin.",
+            shipped.display()
+        )
+    });
+    check_cuda_toolkit(&nvcc, cutlass_version, PREBUILT_ARCHS);
+    let mut command = match compiler_launcher() {
+        Some(launcher) => {
+            let mut command = Command::new(launcher);
+            command.arg(&nvcc);
+            command
+        }
+        None => Command::new(&nvcc),
+    };
+    command
+        .arg("-fatbin")
+        .arg("-std=c++17")
+        .arg("--expt-relaxed-constexpr")
+        .arg("-I")
+        .arg(include_dir);
+    for arch in PREBUILT_ARCHS {
+        command.arg(format!("-gencode=arch=compute_{0},code=sm_{0}", arch));
+    }
+    command.arg(format!(
+        "-gencode=arch=compute_{0},code=compute_{0}",
+        PREBUILT_ARCHS.last().unwrap()
+    ));
+    command
+        .arg("-o")
+        .arg(&out_file)
+        .arg(prebuilt_dir.join("gemm.cu"));
+    let status = command.status().unwrap_or_else(|e| {
+        panic!(
+            "No prebuilt kernel pack found at '{}' and running {} failed: {}. \
+            Install the CUDA toolkit or set CUTLASS_PREBUILT_KERNELS to a directory holding \
+            gemm.fatbin.",
+            shipped.display(),
+            nvcc.display(),
+            e
+        )
+    });
+    if !status.success() {
+        panic!(
+            "{} failed to compile the prebuilt kernel pack",
+            nvcc.display()
+        );
+    }
+}
+
+/// Link the CUDA driver API for the `driver` feature, plus NVRTC and the toolkit headers it needs
+/// alongside CUTLASS's for `jit`
+#[cfg(feature = "driver")]
+fn link_driver(cuda_root: Option<&Path>) {
+    let cuda_root = cuda_root.unwrap_or(Path::new("/usr/local/cuda"));
+    let lib_dir = cuda_lib_dir(cuda_root);
+    println!("cargo:rustc-link-search=native={}", lib_dir.display());
+    // libcuda ships with the driver; the stub lets machines without one link
+    println!(
+        "cargo:rustc-link-search=native={}",
+        lib_dir.join("stubs").display()
+    );
+    println!("cargo:rustc-link-lib=dylib=cuda");
+
+    #[cfg(feature = "jit")]
+    {
+        println!("cargo:rustc-link-lib=dylib=nvrtc");
+        println!(
+            "cargo:rustc-env=CUTLASS_SYS_CUDA_INCLUDE={}",
+            cuda_root.join("include").display()
+        );
+    }
+}
+
+/// The nvcc target name of `arch`: Hopper and Blackwell compile for the arch-specific sm_90a /
+/// sm_100a so TMA, WGMMA and tcgen05 are available
+#[cfg(feature = "shim")]
+fn arch_target(arch: u32) -> String {
+    match arch {
+        90 | 100 => format!("{}a", arch),
+        _ => arch.to_string(),
+    }
+}
+
+/// Parse `CUTLASS_CUDA_ARCH`, a comma-separated list such as `80,86,89,90`, into SM versions
+#[cfg(feature = "shim")]
+fn parse_cuda_archs(value: &str) -> Vec<u32> {
+    let archs: Vec<u32> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|arch| !arch.is_empty())
+        .map(|arch| {
+            let digits = arch.trim_start_matches("sm_");
+            digits.parse::<u32>().unwrap_or_else(|_| {
+                panic!(
+                    "CUTLASS_CUDA_ARCH entry '{}' is not an SM version such as 80 or sm_86",
+                    arch
+                )
+            })
+        })
+        .collect();
+    if archs.is_empty() {
+        panic!("CUTLASS_CUDA_ARCH is set but lists no architectures");
+    }
+    archs
+}
+
+/// Build CUTLASS's instantiated kernel library (`tools/library`) with its own CMake build for
+/// `archs` and link it statically. The libraries are cached per CUTLASS version, CUDA toolkit,
+/// arch list and kernel filter, since a full build takes a long time
+#[cfg(feature = "library")]
+fn build_library(nvcc: &Path, cutlass_root: &Path, cutlass_version: &str, archs: &[u32]) {
+    println!("cargo:rerun-if-env-changed=CUTLASS_LIBRARY_KERNELS");
+    println!("cargo:rerun-if-env-changed=CUTLASS_CMAKE_TARGETS");
+    println!("cargo:rerun-if-env-changed=CMAKE");
+
+    let toolkit = cuda_toolkit_version(nvcc);
+    let kernels = env::var("CUTLASS_LIBRARY_KERNELS").unwrap_or_default();
+    // Further targets of CUTLASS's CMake build, such as cutlass_test_unit_util, built alongside
+    let tools: Vec<String> = env::var("CUTLASS_CMAKE_TARGETS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|target| target.trim().to_string())
+        .filter(|target| !target.is_empty())
+        .collect();
+    let tests = tools.iter().any(|target| target.contains("test"));
+
+    let targets: Vec<String> = archs.iter().map(|&arch| arch_target(arch)).collect();
+    let mut key = format!("cuda{}-sm{}", toolkit, targets.join("_"));
+    if !kernels.is_empty() {
+        key = format!("{}-{}", key, cache_key_component(&kernels));
+    }
+    if !tools.is_empty() {
+        key = format!("{}-t{}", key, cache_key_component(&tools.join(",")));
+    }
+    let cache_dir = get_cache_dir()
+        .join("libcutlass")
+        .join(cutlass_version)
+        .join(key);
+    let lib_dir = cache_dir.join("lib");
+    // Written last, so an interrupted build is redone rather than linked half-populated
+    let complete = cache_dir.join(".complete");
+    // cutlass_profiler is built from the same kernels, next to the library, when requested
+    let profiler = cfg!(feature = "profiler");
+    let bin_dir = cache_dir.join("bin");
+    let profiler_bin = bin_dir.join(format!("cutlass_profiler{}", env::consts::EXE_SUFFIX));
+
+    if complete.exists() && (!profiler || profiler_bin.exists()) {
+        println!(
+            "cargo:warning=Using cached CUTLASS library at {}",
+            lib_dir.display()
+        );
+    } else {
+        println!(
+            "cargo:warning=Building the CUTLASS library for sm_{} (CUDA {}); this takes a while",
+            targets.join(", sm_"),
+            toolkit
+        );
+        let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+        let build_dir = out_dir.join("libcutlass-build");
+        fs::create_dir_all(&build_dir).expect("Failed to create CUTLASS library build directory");
+
+        let cmake = env::var("CMAKE").unwrap_or_else(|_| "cmake".to_string());
+        let mut configure = Command::new(&cmake);
+        configure
+            .arg("-S")
+            .arg(cutlass_root)
+            .arg("-B")
+            .arg(&build_dir)
+            .arg("-DCMAKE_BUILD_TYPE=Release")
+            .arg(format!("-DCMAKE_CUDA_COMPILER={}", nvcc.display()))
+            .arg(format!("-DCUTLASS_NVCC_ARCHS={}", targets.join(";")))
+            .arg("-DCUTLASS_ENABLE_LIBRARY=ON")
+            .arg(format!(
+                "-DCUTLASS_ENABLE_PROFILER={}",
+                if profiler { "ON" } else { "OFF" }
+            ))
+            .arg(format!(
+                "-DCUTLASS_ENABLE_TESTS={}",
+                if tests { "ON" } else { "OFF" }
+            ))
+            .arg("-DCUTLASS_ENABLE_EXAMPLES=OFF")
+            .arg("-DCUTLASS_UNITY_BUILD_ENABLED=ON");
+        if !kernels.is_empty() {
+            configure.arg(format!("-DCUTLASS_LIBRARY_KERNELS={}", kernels));
+        }
+        if let Some(launcher) = compiler_launcher() {
+            configure
+                .arg(format!("-DCMAKE_CUDA_COMPILER_LAUNCHER={}", launcher))
+                .arg(format!("-DCMAKE_CXX_COMPILER_LAUNCHER={}", launcher));
+        }
+        run_cmake(configure, "configure");
+
+        let mut build = Command::new(&cmake);
+        build.arg("--build").arg(&build_dir).args([
+            "--config",
+            "Release",
+            "--target",
+            "cutlass_library_static",
+        ]);
+        if profiler {
+            build.arg("cutlass_profiler");
+        }
+        build.args(&tools);
+        if let Ok(jobs) = env::var("NUM_JOBS") {
+            build.args(["--parallel", jobs.as_str()]);
+        }
+        run_cmake(build, "build");
+
+        // The main library plus one static library per generated kernel group
+        let mut libs = Vec::new();
+        collect_libs(
+            &build_dir.join("tools").join("library"),
+            &mut libs,
+            &|path| static_lib_name(path).is_some(),
+        )
+        .expect("Failed to scan the CUTLASS library build directory");
+        if libs.is_empty() {
+            panic!(
+                "The CUTLASS library build in {} produced no static libraries",
+                build_dir.display()
+            );
+        }
+        let _ = fs::remove_dir_all(&cache_dir);
+        fs::create_dir_all(&lib_dir).expect("Failed to create cache directory");
+        for lib in &libs {
+            fs::copy(lib, lib_dir.join(lib.file_name().unwrap()))
+                .expect("Failed to copy CUTLASS library to cache");
+        }
+        if profiler {
+            install_profiler(&build_dir, &bin_dir);
+        }
+        install_tools(&build_dir, &bin_dir, &tools);
+        fs::write(&complete, "").expect("Failed to mark CUTLASS library cache complete");
+        println!(
+            "cargo:warning=CUTLASS library built and cached at {}",
+            lib_dir.display()
+        );
+    }
+
+    // libcutlass_static goes first and whole: it holds the manifest the kernel libraries
+    // register into, and its initialize_all is what pulls them in
+    let mut names: Vec<String> = fs::read_dir(&lib_dir)
+        .expect("Failed to read CUTLASS library cache")
+        .filter_map(|e| e.ok())
+        .filter_map(|e| static_lib_name(&e.path()))
+        .filter(|name| name != "cutlass_static")
+        .collect();
+    names.sort();
+    println!("cargo:rustc-link-search=native={}", lib_dir.display());
+    println!("cargo:rustc-link-lib=static:+whole-archive=cutlass_static");
+    for name in &names {
+        println!("cargo:rustc-link-lib=static={}", name);
+    }
+    println!("cargo:library_dir={}", lib_dir.display());
+    if !tools.is_empty() {
+        println!("cargo:tools_dir={}", bin_dir.display());
+    }
+
+    // The profiler runs from OUT_DIR/profiler, next to the shared libraries it loads, where
+    // Profiler::new finds it. Dependent build scripts get the same path as DEP_CUTLASS_PROFILER_BIN
+    // (and DEP_CUTLASS_PROFILER), rather than digging through the cached CMake build
+    if profiler {
+        let out_bin = PathBuf::from(env::var("OUT_DIR").unwrap()).join("profiler");
+        let _ = fs::remove_dir_all(&out_bin);
+        copy_dir_all(&bin_dir, &out_bin).expect("Failed to copy cutlass_profiler to OUT_DIR");
+        let path = out_bin.join(profiler_bin.file_name().unwrap());
+        println!("cargo:rustc-env=CUTLASS_PROFILER={}", path.display());
+        println!("cargo:profiler={}", path.display());
+        println!("cargo:profiler_bin={}", path.display());
+        println!("cargo:profiler_dir={}", out_bin.display());
+    }
+    println!(
+        "cargo:library_include={}",
+        cutlass_root
+            .join("tools")
+            .join("library")
+            .join("include")
+            .display()
+    );
+}
+
+/// Copy the `cutlass_profiler` executable out of the CMake build into `bin_dir`, along with the
+/// shared CUTLASS libraries it may load
+#[cfg(feature = "library")]
+fn install_profiler(build_dir: &Path, bin_dir: &Path) {
+    let exe = format!("cutlass_profiler{}", env::consts::EXE_SUFFIX);
+    let built = build_dir.join("tools").join("profiler").join(&exe);
+    if !built.exists() {
+        panic!(
+            "The CUTLASS profiler build did not produce {}",
+            built.display()
+        );
+    }
+    fs::create_dir_all(bin_dir).expect("Failed to create cache directory");
+    fs::copy(&built, bin_dir.join(&exe)).expect("Failed to copy cutlass_profiler to cache");
+
+    let mut shared = Vec::new();
+    collect_libs(
+        &build_dir.join("tools").join("library"),
+        &mut shared,
+        &|path| {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            name.ends_with(".dll") || name.ends_with(".dylib") || name.contains(".so")
+        },
+    )
+    .expect("Failed to scan the CUTLASS library build directory");
+    for lib in &shared {
+        fs::copy(lib, bin_dir.join(lib.file_name().unwrap()))
+            .expect("Failed to copy CUTLASS shared library to cache");
+    }
+}
+
+/// `CUTLASS_NVCC_LAUNCHER`: a compiler cache such as `sccache` or `ccache` to run the CUDA compiler
+/// through, as `RUSTC_WRAPPER` does for rustc
+#[cfg(any(feature = "shim", feature = "prebuilt-kernels"))]
+fn compiler_launcher() -> Option<String> {
+    println!("cargo:rerun-if-env-changed=CUTLASS_NVCC_LAUNCHER");
+    env::var("CUTLASS_NVCC_LAUNCHER")
+        .ok()
+        .filter(|launcher| !launcher.trim().is_empty())
+}
+
+/// Write a script into `out_dir` that runs `compiler` through `launcher` with its arguments, for
+/// cc, which takes the compiler as a single executable
+#[cfg(feature = "shim")]
+fn launcher_script(launcher: &str, compiler: &Path, out_dir: &Path) -> PathBuf {
+    #[cfg(windows)]
+    let script = {
+        let script = out_dir.join("cuda-launcher.cmd");
+        let contents = format!("@\"{}\" \"{}\" %*\r\n", launcher, compiler.display());
+        fs::write(&script, contents).expect("Failed to write the compiler launcher script");
+        script
+    };
+    #[cfg(not(windows))]
+    let script = {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = out_dir.join("cuda-launcher.sh");
+        let contents = format!(
+            "#!/bin/sh\nexec '{}' '{}' \"$@\"\n",
+            launcher,
+            compiler.display()
+        );
+        fs::write(&script, contents).expect("Failed to write the compiler launcher script");
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755))
+            .expect("Failed to make the compiler launcher script executable");
+        script
+    };
+    println!("cargo:warning=Compiling CUDA sources through {}", launcher);
+    script
+}
+
+/// Where WSL2 mounts the Windows driver's libcuda.so.1 and nvidia-smi into the distribution
+#[cfg(any(feature = "shim", feature = "driver"))]
+const WSL_LIB_DIR: &str = "/usr/lib/wsl/lib";
+
+/// Whether the build runs natively under WSL2: a Linux target built on a Microsoft kernel that
+/// exposes the Windows driver
+#[cfg(any(feature = "shim", feature = "driver"))]
+fn is_wsl2() -> bool {
+    env::var("TARGET").ok() == env::var("HOST").ok()
+        && env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "linux")
+        && fs::read_to_string("/proc/version")
+            .is_ok_and(|version| version.to_lowercase().contains("microsoft"))
+        && Path::new("/usr/lib/wsl").is_dir()
+}
+
+/// Put WSL2's driver directory on the link search path ahead of the toolkit's libcuda stub, and
+/// warn about the setups that fail to load CUDA at runtime
+#[cfg(any(feature = "shim", feature = "driver"))]
+fn configure_wsl2() {
+    println!("cargo:rerun-if-env-changed=LD_LIBRARY_PATH");
+    let wsl_lib = Path::new(WSL_LIB_DIR);
+    println!("cargo:rustc-link-search=native={}", wsl_lib.display());
+
+    if !wsl_lib.join("libcuda.so.1").is_file() {
+        println!(
+            "cargo:warning=WSL2 detected, but {} has no libcuda.so.1: install or update the \
+            NVIDIA driver on Windows (not inside WSL) so the GPU is exposed to Linux",
+            wsl_lib.display()
+        );
+    }
+    // The stub only satisfies the linker; loaded at runtime every CUDA call fails with
+    // CUDA_ERROR_STUB_LIBRARY
+    if let Some(path) = env::var_os("LD_LIBRARY_PATH") {
+        if let Some(stubs) = env::split_paths(&path).find(|dir| dir.ends_with("stubs")) {
+            println!(
+                "cargo:warning=LD_LIBRARY_PATH contains {}; its stub libcuda.so would be loaded \
+                instead of WSL2's driver in {} and CUDA would report no device. Remove it from \
+                LD_LIBRARY_PATH",
+                stubs.display(),
+                wsl_lib.display()
+            );
+        }
+    }
+    // A Linux driver installed inside the distribution shadows the one WSL2 provides
+    for dir in ["/usr/lib/x86_64-linux-gnu", "/usr/lib64", "/usr/lib"] {
+        let shadowing = Path::new(dir).join("libcuda.so.1");
+        if shadowing.exists() {
+            println!(
+                "cargo:warning=WSL2 detected, but {} exists: a Linux NVIDIA driver installed \
+                inside WSL replaces the Windows driver's libcuda and breaks CUDA. Remove it and \
+                install only the CUDA toolkit (the WSL-Ubuntu package, without a driver)",
+                shadowing.display()
+            );
+        }
+    }
+}
+
+/// Find the CUDA toolkit: the one `locate_nvcc` finds nvcc in, otherwise the newest of the standard
+/// install locations (`/usr/local/cuda`, `/opt/cuda`, or `CUDA\v*` under `Program Files\NVIDIA GPU
+/// Computing Toolkit` on Windows) holding `include/cuda.h`
+fn locate_cuda_toolkit() -> Option<PathBuf> {
+    if let Some(root) = locate_nvcc().and_then(|nvcc| nvcc_toolkit_root(&nvcc)) {
+        return Some(root);
+    }
+    // CUDA_HOME / CUDA_PATH without a bin/nvcc, such as a runtime-only install
+    if let Some(root) = ["CUDA_HOME", "CUDA_PATH"].into_iter().find_map(env::var_os) {
+        return Some(PathBuf::from(root));
+    }
+
+    let mut candidates = vec![PathBuf::from("/usr/local/cuda"), PathBuf::from("/opt/cuda")];
+    if let Some(program_files) = env::var_os("ProgramFiles") {
+        let versions = PathBuf::from(program_files)
+            .join("NVIDIA GPU Computing Toolkit")
+            .join("CUDA");
+        if let Ok(entries) = fs::read_dir(&versions) {
+            let mut installed: Vec<(Option<(u32, u32)>, PathBuf)> = entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter_map(|path| {
+                    let name = path.file_name()?.to_str()?.strip_prefix('v')?.to_string();
+                    Some((parse_release(&name), path))
+                })
+                .collect();
+            // Newest first
+            installed.sort();
+            candidates.extend(installed.into_iter().rev().map(|(_, path)| path));
+        }
+    }
+    candidates
+        .into_iter()
+        .find(|root| root.join("include").join("cuda.h").is_file())
+}
+
+/// The toolkit directory above the `bin` of `nvcc`, looked up on `PATH` for a bare `nvcc`
+fn nvcc_toolkit_root(nvcc: &Path) -> Option<PathBuf> {
+    let nvcc = if nvcc.components().count() > 1 {
+        nvcc.to_path_buf()
+    } else {
+        let path = env::var_os("PATH")?;
+        env::split_paths(&path)
+            .map(|dir| dir.join(nvcc))
+            .find(|candidate| candidate.is_file())?
+    };
+    Some(nvcc.parent()?.parent()?.to_path_buf())
+}
+
+/// The library directory of the toolkit at `cuda_root` for the target
+fn cuda_lib_dir(cuda_root: &Path) -> PathBuf {
+    if let Some(target_dir) = cuda_target_dir(cuda_root) {
+        cuda_root.join("targets").join(target_dir).join("lib")
+    } else if env::var("CARGO_CFG_WINDOWS").is_ok() {
+        cuda_root.join("lib").join("x64")
+    } else {
+        cuda_root.join("lib64")
+    }
+}
+
+/// Whether the build cross-compiles for aarch64 Linux (Jetson, Grace) from another host
+fn cross_aarch64() -> bool {
+    env::var("TARGET").ok() != env::var("HOST").ok()
+        && env::var("CARGO_CFG_TARGET_ARCH").is_ok_and(|arch| arch == "aarch64")
+        && env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "linux")
+}
+
+/// The directory under `targets/` of the toolkit at `cuda_root` holding the headers and libraries
+/// of an aarch64 target being cross-compiled for: `CUTLASS_CUDA_TARGET_DIR` if set, otherwise
+/// `aarch64-linux` (Jetson), or `sbsa-linux` (Grace and other server-class Arm) when the toolkit
+/// only has that
+fn cuda_target_dir(cuda_root: &Path) -> Option<String> {
+    println!("cargo:rerun-if-env-changed=CUTLASS_CUDA_TARGET_DIR");
+    if !cross_aarch64() {
+        return None;
+    }
+    if let Ok(target_dir) = env::var("CUTLASS_CUDA_TARGET_DIR") {
+        return Some(target_dir);
+    }
+    let targets = cuda_root.join("targets");
+    let sbsa_only = !targets.join("aarch64-linux").is_dir() && targets.join("sbsa-linux").is_dir();
+    Some(
+        if sbsa_only {
+            "sbsa-linux"
+        } else {
+            "aarch64-linux"
+        }
+        .to_string(),
+    )
+}
+
+/// The toolkit release `include/cuda.h` of `cuda_root` declares (`CUDA_VERSION 12040` -> 12.4),
+/// read without running anything from the toolkit
+fn cuda_header_version(cuda_root: &Path) -> Option<(u32, u32)> {
+    let header = fs::read_to_string(cuda_root.join("include").join("cuda.h")).ok()?;
+    let version: u32 = header.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("#define CUDA_VERSION")?;
+        value.trim().parse().ok()
+    })?;
+    Some((version / 1000, version % 1000 / 10))
+}
+
+/// Export the CUDA toolkit at `cuda_root` to dependent build scripts as DEP_CUTLASS_CUDA_ROOT,
+/// DEP_CUTLASS_CUDA_INCLUDE, DEP_CUTLASS_CUDA_LIB and DEP_CUTLASS_CUDA_VERSION (`12.4`), so
+/// they find the toolkit CUTLASS is used with without a discovery crate of their own
+fn emit_cuda_keys(cuda_root: &Path) {
+    println!("cargo:cuda_root={}", cuda_root.display());
+    // A cross build's headers, like its libraries, are the target's
+    let include_dir = match cuda_target_dir(cuda_root) {
+        Some(target_dir) => cuda_root.join("targets").join(target_dir).join("include"),
+        None => cuda_root.join("include"),
+    };
+    println!("cargo:cuda_include={}", include_dir.display());
+    println!("cargo:cuda_lib={}", cuda_lib_dir(cuda_root).display());
+    if let Some((major, minor)) = cuda_header_version(cuda_root) {
+        println!("cargo:cuda_version={}.{}", major, minor);
+    }
+}
+
+/// Find nvcc: `NVCC` if set, otherwise `bin/nvcc` under `CUDA_HOME` or `CUDA_PATH`, otherwise the
+/// first one on `PATH`
+fn locate_nvcc() -> Option<PathBuf> {
+    println!("cargo:rerun-if-env-changed=NVCC");
+    println!("cargo:rerun-if-env-changed=CUDA_HOME");
+    println!("cargo:rerun-if-env-changed=CUDA_PATH");
+
+    if let Some(nvcc) = env::var_os("NVCC") {
+        return Some(PathBuf::from(nvcc));
+    }
+    // The build script runs on the host, so the host's executable suffix applies
+    let exe = format!("nvcc{}", env::consts::EXE_SUFFIX);
+    let toolkits = ["CUDA_HOME", "CUDA_PATH"]
+        .into_iter()
+        .filter_map(env::var_os)
+        .map(|root| PathBuf::from(root).join("bin"));
+    let path = env::var_os("PATH")
+        .map(|path| env::split_paths(&path).collect::<Vec<_>>())
+        .unwrap_or_default();
+    toolkits
+        .chain(path)
+        .map(|dir| dir.join(&exe))
+        .find(|candidate| candidate.is_file())
+}
+
+/// The `MAJOR.MINOR` release of the CUDA toolkit `nvcc` belongs to, from `nvcc --version`
+#[cfg(any(feature = "shim", feature = "prebuilt-kernels"))]
+fn cuda_toolkit_version(nvcc: &Path) -> String {
+    let output = Command::new(nvcc)
+        .arg("--version")
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to run '{} --version': {}", nvcc.display(), e));
+    let text = String::from_utf8_lossy(&output.stdout);
+    // "Cuda compilation tools, release 12.4, V12.4.131"
+    text.split("release ")
+        .nth(1)
+        .and_then(|rest| rest.split(',').next())
+        .map(|version| version.trim().to_string())
+        .unwrap_or_else(|| {
+            panic!(
+                "Could not find the CUDA release in '{} --version'",
+                nvcc.display()
+            )
+        })
+}
+
+/// Parse a `MAJOR.MINOR` release such as `12.4` for comparison
+fn parse_release(release: &str) -> Option<(u32, u32)> {
+    let mut parts = release.split('.');
+    let major = parts.next()?.trim().parse().ok()?;
+    let minor = parts.next().unwrap_or("0").trim().parse().ok()?;
+    Some((major, minor))
+}
+
+/// The major version of CUTLASS `cutlass_version` (e.g. `v4.2.0`)
+fn cutlass_major(cutlass_version: &str) -> u32 {
+    cutlass_version
+        .trim_start_matches('v')
+        .split('.')
+        .next()
+        .and_then(|major| major.parse().ok())
+        .unwrap_or(0)
+}
+
+/// The oldest CUDA toolkit CUTLASS `cutlass_version` (e.g. `v4.2.0`) supports: 11.4 from CUTLASS
+/// 3.0 on, whose CuTe headers need it, and 10.2 before
+fn cutlass_min_cuda(cutlass_version: &str) -> (u32, u32) {
+    if cutlass_major(cutlass_version) >= 3 {
+        (11, 4)
+    } else {
+        (10, 2)
+    }
+}
+
+/// The oldest CUDA toolkit nvcc can compile for SM version `arch` with: sm_90a needs 12.0 and the
+/// Blackwell targets 12.8
+fn arch_min_cuda(arch: u32) -> (u32, u32) {
+    match arch {
+        0..=80 => (11, 0),
+        81..=86 => (11, 1),
+        87 => (11, 4),
+        88..=89 => (11, 8),
+        90..=99 => (12, 0),
+        _ => (12, 8),
+    }
+}
+
+/// Check that the CUDA toolkit of `nvcc` is new enough for CUTLASS `cutlass_version` and every
+/// arch of `archs`, panicking with the first requirement it misses. Returns its release
+#[cfg(any(feature = "shim", feature = "prebuilt-kernels"))]
+fn check_cuda_toolkit(nvcc: &Path, cutlass_version: &str, archs: &[u32]) -> (u32, u32) {
+    let toolkit = cuda_toolkit_version(nvcc);
+    let found = parse_release(&toolkit).unwrap_or_else(|| {
+        panic!(
+            "'{} --version' reports CUDA release '{}', which is not MAJOR.MINOR",
+            nvcc.display(),
+            toolkit
+        )
+    });
+
+    let (major, minor) = cutlass_min_cuda(cutlass_version);
+    if found < (major, minor) {
+        panic!(
+            "CUTLASS {} needs CUDA {}.{} or newer, but {} is from CUDA {}. Point NVCC, CUDA_HOME \
+            or CUDA_PATH at a newer toolkit.",
+            cutlass_version,
+            major,
+            minor,
+            nvcc.display(),
+            toolkit
+        );
+    }
+    for &arch in archs {
+        let (major, minor) = arch_min_cuda(arch);
+        if found < (major, minor) {
+            panic!(
+                "sm_{} needs CUDA {}.{} or newer, but {} is from CUDA {}. Point NVCC, CUDA_HOME \
+                or CUDA_PATH at a newer toolkit, or leave {} out of CUTLASS_CUDA_ARCH and the sm* \
+                features.",
+                arch,
+                major,
+                minor,
+                nvcc.display(),
+                toolkit,
+                arch
+            );
+        }
+    }
+    println!(
+        "cargo:warning=Using nvcc {} (CUDA {})",
+        nvcc.display(),
+        toolkit
+    );
+    found
+}
+
+/// Export what the build needs of the toolchain and what its output needs of the driver, as the
+/// `min_cuda_version`, `cxx_standard` and `min_driver_version` metadata keys and as the constants
+/// of the context module. CUDA versions use the `CUDA_VERSION` encoding (`major * 1000 + minor *
+/// 10`). The shim links the CUDA runtime statically, so thanks to minor version compatibility it
+/// needs a driver of the same major release as the `toolkit` it was built with, or as the oldest
+/// usable toolkit when that is unknown
+fn emit_toolchain_requirements(cutlass_version: &str, archs: &[u32], toolkit: Option<(u32, u32)>) {
+    let min_cuda = archs
+        .iter()
+        .map(|&arch| arch_min_cuda(arch))
+        .fold(cutlass_min_cuda(cutlass_version), std::cmp::max);
+    // CUTLASS 3.0 moved to C++17 along with CuTe; 2.x builds as C++11
+    let cxx_standard = if cutlass_major(cutlass_version) >= 3 {
+        17
+    } else {
+        11
+    };
+    let driver_major = toolkit.map_or(min_cuda.0, |(major, _)| major);
+    for (key, value) in [
+        ("min_cuda_version", min_cuda.0 * 1000 + min_cuda.1 * 10),
+        ("cxx_standard", cxx_standard),
+        ("min_driver_version", driver_major * 1000),
+    ] {
+        println!("cargo:{}={}", key, value);
+        println!(
+            "cargo:rustc-env=CUTLASS_SYS_{}={}",
+            key.to_uppercase(),
+            value
+        );
+    }
+}
+
+/// `value` reduced to characters safe in a cache directory name
+#[cfg(feature = "library")]
+fn cache_key_component(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Copy the executables of the CUTLASS_CMAKE_TARGETS `tools` out of `build_dir` into `bin_dir`.
+/// Targets without an executable of their name (aggregate or library targets) are only built
+#[cfg(feature = "library")]
+fn install_tools(build_dir: &Path, bin_dir: &Path, tools: &[String]) {
+    for tool in tools {
+        let name = format!("{}{}", tool, env::consts::EXE_SUFFIX);
+        let mut found = Vec::new();
+        collect_libs(build_dir, &mut found, &|path| {
+            path.file_name().is_some_and(|file| file == name.as_str())
+        })
+        .expect("Failed to scan the CUTLASS build directory");
+        match found.first() {
+            Some(executable) => {
+                fs::create_dir_all(bin_dir).expect("Failed to create cache directory");
+                fs::copy(executable, bin_dir.join(&name))
+                    .unwrap_or_else(|e| panic!("Failed to copy {} to the cache: {}", tool, e));
+            }
+            None => println!(
+                "cargo:warning=CMake target {} produced no executable of that name",
+                tool
+            ),
+        }
+    }
+}
+
+/// Run a CMake step of the CUTLASS library build, panicking with its output on failure
+#[cfg(feature = "library")]
+fn run_cmake(mut command: Command, step: &str) {
+    let status = command.status().unwrap_or_else(|e| {
+        panic!(
+            "Failed to run CMake ({}); install CMake 3.19+ or point CMAKE at it",
+            e
+        )
+    });
+    if !status.success() {
+        panic!(
+            "CMake {} of the CUTLASS library failed ({}). The `library` feature needs CMake \
+            3.19+, Python 3 and nvcc; set CUTLASS_LIBRARY_KERNELS to build fewer kernels.",
+            step, status
+        );
+    }
+}
+
+/// Recursively collect the files under `dir` that `wanted` accepts
+#[cfg(feature = "library")]
+fn collect_libs(
+    dir: &Path,
+    libs: &mut Vec<PathBuf>,
+    wanted: &dyn Fn(&Path) -> bool,
+) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_libs(&path, libs, wanted)?;
+        } else if wanted(&path) {
+            libs.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The link name of a static library (`libfoo.a` or `foo.lib` -> `foo`)
+#[cfg(feature = "library")]
+fn static_lib_name(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_str()?;
+    if let Some(stem) = file_name.strip_suffix(".a") {
+        stem.strip_prefix("lib").map(str::to_string)
+    } else {
+        file_name.strip_suffix(".lib").map(str::to_string)
+    }
+}
+
 fn get_cache_dir() -> PathBuf {
     // Try CARGO_HOME first, then user cache directory, finally temp
     if let Ok(cargo_home) = env::var("CARGO_HOME") {
@@ -0,0 +1,570 @@
+//! Kernel autotuning with a persistent result cache (`autotune` feature).
+//!
+//! An [`Autotuner`] times every candidate kernel for a problem with CUDA
+//! events, keeps the fastest, and records its name in a JSON file keyed by
+//! device, crate (and so CUTLASS) version, operation and problem bucket, so
+//! later runs, in this process or the next, launch the winner without
+//! measuring again. Problem extents are rounded up to powers of two for the
+//! key, so nearby shapes share one measurement.
+//!
+//! [`Autotuner::tune`] works over any candidate list; [`Autotuner::tune_gemm`]
+//! tunes the tile scheduling and split-K of the shim's [`cutlass_sys_gemm`],
+//! and with the `library` feature [`Autotuner::tune_library_gemm`] picks among
+//! the CUTLASS library's GEMM operations.
+//!
+//! The cache file is `CUTLASS_AUTOTUNE_CACHE` if set, otherwise
+//! `cutlass-sys/autotune.json` in the user cache directory.
+//!
+//! [`cutlass_sys_gemm`]: crate::gemm::cutlass_sys_gemm
+
+use core::ffi::{c_int, c_void};
+use core::ptr;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+use std::vec::Vec;
+use std::{env, fs, io, process};
+
+use crate::cache::user_cache_dir;
+use crate::context::CutlassContext;
+use crate::coord::GemmCoord;
+use crate::error::{
+    cudaEventCreate, cudaEventDestroy, cudaEventElapsedTime, cudaEventRecord, cudaEventSynchronize,
+    cudaEvent_t, on_device, CutlassError,
+};
+use crate::gemm::{cutlass_sys_gemm, GemmConfig, GemmOperands, GemmPlan, Schedule, TileScheduler};
+#[cfg(feature = "library")]
+use crate::manifest::{
+    cutlass_sys_operation_gemm, GemmFunctionalKey, LibraryGemmArguments, Manifest, Operation,
+};
+use crate::types::{cudaStream_t, Status};
+
+/// Split-K slice counts tried with data-parallel scheduling.
+const SPLIT_K_SLICES: [i32; 4] = [1, 2, 4, 8];
+
+/// Times candidate kernels and remembers the fastest for each problem.
+#[derive(Clone, Debug)]
+pub struct Autotuner {
+    path: Option<PathBuf>,
+    results: BTreeMap<String, String>,
+    warmup: u32,
+    iterations: u32,
+}
+
+impl Default for Autotuner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Autotuner {
+    /// An autotuner backed by the default cache file.
+    pub fn new() -> Self {
+        let path = env::var_os("CUTLASS_AUTOTUNE_CACHE")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| user_cache_dir().join("autotune.json"));
+        Self::with_cache_file(Some(path))
+    }
+
+    /// An autotuner backed by `path`, or by nothing for `None`. Results
+    /// already in the file are loaded; an unreadable file starts empty.
+    pub fn with_cache_file(path: Option<PathBuf>) -> Self {
+        let results = path.as_deref().map(read_results).unwrap_or_default();
+        Self {
+            path,
+            results,
+            warmup: 2,
+            iterations: 10,
+        }
+    }
+
+    /// Untimed launches of each candidate before measuring (default 2).
+    pub fn warmup(mut self, warmup: u32) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    /// Timed launches of each candidate, averaged (default 10).
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations.max(1);
+        self
+    }
+
+    /// The cache key of `operation` (e.g. `"gemm F16"`) on problem `problem`
+    /// and `context`'s device.
+    pub fn key(context: &CutlassContext, operation: &str, problem: GemmCoord) -> String {
+        let bucket = |extent: i32| (extent.max(1) as u32).next_power_of_two();
+        std::format!(
+            "sm{}x{}/cutlass-sys {}/{}/{}x{}x{}",
+            context.compute_capability(),
+            context.sm_count,
+            env!("CARGO_PKG_VERSION"),
+            operation,
+            bucket(problem.m),
+            bucket(problem.n),
+            bucket(problem.k)
+        )
+    }
+
+    /// The recorded winner for `key`, if any.
+    pub fn lookup(&self, key: &str) -> Option<&str> {
+        self.results.get(key).map(String::as_str)
+    }
+
+    /// Drops every recorded result, in memory and on disk.
+    pub fn clear(&mut self) -> io::Result<()> {
+        self.results.clear();
+        match &self.path {
+            Some(path) if path.exists() => fs::remove_file(path),
+            _ => Ok(()),
+        }
+    }
+
+    /// Writes the results to the cache file, merged with any written there
+    /// since it was loaded.
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut results = read_results(path);
+        results.extend(self.results.iter().map(|(k, v)| (k.clone(), v.clone())));
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Written under a unique name and renamed, so concurrent processes
+        // never read a partial file
+        let partial = path.with_extension(std::format!("{}.tmp", process::id()));
+        fs::write(&partial, write_json(&results))?;
+        fs::rename(&partial, path)
+    }
+
+    /// Index of the fastest of `candidates` for `key`. `name` must give each
+    /// candidate a stable, unique name; `run` launches one on `stream`.
+    ///
+    /// A recorded winner still among the candidates is returned without
+    /// launching anything. Otherwise every candidate is launched, those whose
+    /// `run` fails are skipped, the fastest is recorded and the cache file
+    /// updated (best effort). [`Status::ErrorNotSupported`] if none ran, and
+    /// [`CutlassError::Cuda`] if the CUDA events timing them fail.
+    pub fn tune<C>(
+        &mut self,
+        context: &CutlassContext,
+        key: &str,
+        candidates: &[C],
+        name: impl Fn(&C) -> String,
+        mut run: impl FnMut(&C) -> Result<(), CutlassError>,
+        stream: cudaStream_t,
+    ) -> Result<usize, CutlassError> {
+        if let Some(winner) = self.lookup(key) {
+            if let Some(index) = candidates.iter().position(|c| name(c) == winner) {
+                return Ok(index);
+            }
+        }
+
+        let timer = EventTimer::new(context)?;
+        let mut best: Option<(f32, usize)> = None;
+        for (index, candidate) in candidates.iter().enumerate() {
+            if (0..self.warmup).try_for_each(|_| run(candidate)).is_err() {
+                continue;
+            }
+            let elapsed = timer.time(stream, || {
+                (0..self.iterations).try_for_each(|_| run(candidate))
+            });
+            let Ok(Some(ms)) = elapsed else {
+                continue;
+            };
+            if best.is_none_or(|(fastest, _)| ms < fastest) {
+                best = Some((ms, index));
+            }
+        }
+
+        let (_, index) = best.ok_or(Status::ErrorNotSupported)?;
+        self.results
+            .insert(key.to_string(), name(&candidates[index]));
+        let _ = self.save();
+        Ok(index)
+    }
+
+    /// Bytes of workspace [`tune_gemm`](Self::tune_gemm) needs for `config`:
+    /// the most any of its candidates needs.
+    pub fn gemm_workspace_size(context: &CutlassContext, config: &GemmConfig) -> usize {
+        gemm_candidates(context, config)
+            .iter()
+            .map(|candidate| candidate.workspace_size(context))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// `config` with the fastest tile scheduling and split-K slice count for
+    /// its problem on `context`'s device: data-parallel with 1, 2, 4 or 8
+    /// slices, Stream-K or persistent.
+    ///
+    /// # Safety
+    ///
+    /// As for [`cutlass_sys_gemm`], with `workspace` holding at least
+    /// [`gemm_workspace_size`](Self::gemm_workspace_size) bytes. While
+    /// tuning, `D` is written repeatedly: with `beta != 0` and `C` aliasing
+    /// `D` its contents are undefined afterwards.
+    pub unsafe fn tune_gemm(
+        &mut self,
+        context: &CutlassContext,
+        config: &GemmConfig,
+        operands: &GemmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<GemmConfig, CutlassError> {
+        let candidates = gemm_candidates(context, config);
+        let index = self.tune(
+            context,
+            &gemm_key(context, config),
+            &candidates,
+            |candidate| Schedule(candidate).to_string(),
+            // SAFETY: the caller vouches for the operands, workspace and
+            // stream.
+            |candidate| {
+                CutlassError::check(unsafe {
+                    cutlass_sys_gemm(context, candidate, operands, workspace, stream)
+                })
+            },
+            stream,
+        )?;
+        Ok(candidates[index])
+    }
+
+    /// The plan to launch `config` with: the recorded winner of
+    /// [`tune_gemm`](Self::tune_gemm) for its problem if there is one,
+    /// otherwise the built-in heuristic of [`GemmConfig::plan`].
+    pub fn plan_gemm(
+        &self,
+        context: &CutlassContext,
+        config: &GemmConfig,
+    ) -> Result<GemmPlan, Status> {
+        if let Some(winner) = self.lookup(&gemm_key(context, config)) {
+            let tuned = gemm_candidates(context, config)
+                .into_iter()
+                .find(|candidate| Schedule(candidate).to_string() == winner);
+            if let Some(tuned) = tuned {
+                return GemmPlan::new(context, tuned);
+            }
+        }
+        config.plan(context)
+    }
+
+    /// The fastest GEMM operation of `manifest` matching `key` for
+    /// `arguments` on `context`'s device, among those
+    /// [`Manifest::find_gemm`] lists that can run them within
+    /// `workspace_size` bytes of workspace.
+    ///
+    /// # Safety
+    ///
+    /// As for [`cutlass_sys_operation_gemm`], with `workspace` holding
+    /// `workspace_size` bytes. `D` is written repeatedly while tuning.
+    #[cfg(feature = "library")]
+    #[allow(clippy::too_many_arguments)]
+    pub unsafe fn tune_library_gemm<'m>(
+        &mut self,
+        context: &CutlassContext,
+        manifest: &'m Manifest,
+        key: &GemmFunctionalKey,
+        arguments: &LibraryGemmArguments,
+        workspace: *mut c_void,
+        workspace_size: usize,
+        stream: cudaStream_t,
+    ) -> Result<Operation<'m>, CutlassError> {
+        let candidates: Vec<Operation<'m>> = manifest
+            .find_gemm(key, context.compute_capability())
+            .into_iter()
+            .filter(|operation| {
+                operation.gemm_supports(context, arguments).is_ok()
+                    && operation.gemm_workspace_size(context, arguments) <= workspace_size
+            })
+            .collect();
+        let cache_key = Self::key(
+            context,
+            &std::format!(
+                "library gemm {:?}:{:?} {:?}:{:?} {:?}",
+                key.element_a,
+                key.layout_a,
+                key.element_b,
+                key.layout_b,
+                key.element_accumulator
+            ),
+            arguments.problem,
+        );
+        let index = self.tune(
+            context,
+            &cache_key,
+            &candidates,
+            |operation| operation.name().to_string_lossy().into_owned(),
+            // SAFETY: the caller vouches for the arguments, workspace and
+            // stream.
+            |operation| {
+                CutlassError::check(unsafe {
+                    cutlass_sys_operation_gemm(
+                        context,
+                        operation.as_ptr(),
+                        arguments,
+                        workspace,
+                        stream,
+                    )
+                })
+            },
+            stream,
+        )?;
+        Ok(candidates[index])
+    }
+}
+
+/// The cache key of GEMMs of `config`'s element, accumulator and output
+/// types, operand layouts and problem.
+fn gemm_key(context: &CutlassContext, config: &GemmConfig) -> String {
+    Autotuner::key(
+        context,
+        &std::format!(
+            "gemm {:?} {:?} {:?} {:?} {:?}",
+            config.element,
+            config.accumulator,
+            config.output,
+            config.layout_a,
+            config.layout_b
+        ),
+        config.problem,
+    )
+}
+
+/// The scheduling variants of `config` the shim can run.
+fn gemm_candidates(context: &CutlassContext, config: &GemmConfig) -> Vec<GemmConfig> {
+    let data_parallel = SPLIT_K_SLICES.iter().map(|&split_k_slices| GemmConfig {
+        scheduler: TileScheduler::DataParallel,
+        split_k_slices,
+        ..*config
+    });
+    let stream_k = GemmConfig {
+        scheduler: TileScheduler::StreamK,
+        split_k_slices: 1,
+        ..*config
+    };
+    let persistent = GemmConfig {
+        scheduler: TileScheduler::Persistent,
+        split_k_slices: 1,
+        ..*config
+    };
+    data_parallel
+        .chain([stream_k, persistent])
+        .filter(|candidate| candidate.supports(context).is_ok())
+        .collect()
+}
+
+/// A pair of CUDA events on one device, for timing work on its streams.
+struct EventTimer {
+    device: c_int,
+    start: cudaEvent_t,
+    stop: cudaEvent_t,
+}
+
+impl EventTimer {
+    fn new(context: &CutlassContext) -> Result<Self, CutlassError> {
+        let mut timer = Self {
+            device: context.device,
+            start: ptr::null_mut(),
+            stop: ptr::null_mut(),
+        };
+        let (mut start, mut stop) = (ptr::null_mut(), ptr::null_mut());
+        let created = on_device(timer.device, || {
+            // SAFETY: only writes the two event handles.
+            CutlassError::cuda(unsafe { cudaEventCreate(&mut start) })?;
+            CutlassError::cuda(unsafe { cudaEventCreate(&mut stop) })
+        });
+        // Stored before checking, so drop destroys whichever was created
+        (timer.start, timer.stop) = (start, stop);
+        created?;
+        Ok(timer)
+    }
+
+    /// Milliseconds `f` took on `stream`, or `None` if `f` failed.
+    fn time(
+        &self,
+        stream: cudaStream_t,
+        f: impl FnOnce() -> Result<(), CutlassError>,
+    ) -> Result<Option<f32>, CutlassError> {
+        on_device(self.device, || {
+            // SAFETY: the events belong to this device; the caller vouches
+            // for `stream`.
+            CutlassError::cuda(unsafe { cudaEventRecord(self.start, stream) })?;
+            if f().is_err() {
+                return Ok(None);
+            }
+            let mut ms = 0.0;
+            // SAFETY: both events were recorded on `stream`.
+            unsafe {
+                CutlassError::cuda(cudaEventRecord(self.stop, stream))?;
+                CutlassError::cuda(cudaEventSynchronize(self.stop))?;
+                CutlassError::cuda(cudaEventElapsedTime(&mut ms, self.start, self.stop))?;
+            }
+            Ok(Some(ms))
+        })
+    }
+}
+
+impl Drop for EventTimer {
+    fn drop(&mut self) {
+        // SAFETY: the events were created by `new` and are destroyed once;
+        // null handles are ignored.
+        unsafe {
+            cudaEventDestroy(self.start);
+            cudaEventDestroy(self.stop);
+        }
+    }
+}
+
+/// The results recorded in `path`, or none if it is missing or malformed.
+fn read_results(path: &Path) -> BTreeMap<String, String> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|json| parse_json(&json))
+        .unwrap_or_default()
+}
+
+/// Serializes `results` as a JSON object of strings, one entry per line.
+fn write_json(results: &BTreeMap<String, String>) -> String {
+    let entries: Vec<String> = results
+        .iter()
+        .map(|(key, value)| std::format!("  {}: {}", json_string(key), json_string(value)))
+        .collect();
+    std::format!("{{\n{}\n}}\n", entries.join(",\n"))
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&std::format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Parses a JSON object whose values are all strings, as [`write_json`]
+/// writes.
+fn parse_json(json: &str) -> Option<BTreeMap<String, String>> {
+    let mut chars = json.chars().peekable();
+    let mut results = BTreeMap::new();
+    let skip_space = |chars: &mut core::iter::Peekable<core::str::Chars<'_>>| {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    };
+    let string = |chars: &mut core::iter::Peekable<core::str::Chars<'_>>| -> Option<String> {
+        if chars.next()? != '"' {
+            return None;
+        }
+        let mut s = String::new();
+        loop {
+            match chars.next()? {
+                '"' => return Some(s),
+                '\\' => match chars.next()? {
+                    'n' => s.push('\n'),
+                    't' => s.push('\t'),
+                    'r' => s.push('\r'),
+                    'u' => {
+                        let hex: String = chars.by_ref().take(4).collect();
+                        s.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                    }
+                    c => s.push(c),
+                },
+                c => s.push(c),
+            }
+        }
+    };
+
+    skip_space(&mut chars);
+    if chars.next()? != '{' {
+        return None;
+    }
+    skip_space(&mut chars);
+    if chars.next_if_eq(&'}').is_none() {
+        loop {
+            skip_space(&mut chars);
+            let key = string(&mut chars)?;
+            skip_space(&mut chars);
+            if chars.next()? != ':' {
+                return None;
+            }
+            skip_space(&mut chars);
+            results.insert(key, string(&mut chars)?);
+            skip_space(&mut chars);
+            match chars.next()? {
+                ',' => continue,
+                '}' => break,
+                _ => return None,
+            }
+        }
+    }
+    Some(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|&(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let results = results(&[
+            ("sm80x108/cutlass-sys 4.2.0/gemm F16/128x128x64", "stream-k"),
+            ("quote \" and backslash \\", "tab\tnewline\ncontrol \u{1}"),
+            ("unicode \u{e9}\u{1f600}", ""),
+        ]);
+        let json = write_json(&results);
+        assert!(json.contains(r#""quote \" and backslash \\""#));
+        assert!(json.contains(r#""tab\u0009newline\u000acontrol \u0001""#));
+        assert_eq!(parse_json(&json), Some(results));
+        assert_eq!(
+            parse_json(&write_json(&BTreeMap::new())),
+            Some(BTreeMap::new())
+        );
+    }
+
+    #[test]
+    fn json_reads_escapes_it_does_not_write() {
+        assert_eq!(
+            parse_json(r#" { "a\/b" : "\n\r\té" , "c": "" } "#),
+            Some(results(&[("a/b", "\n\r\t\u{e9}"), ("c", "")]))
+        );
+    }
+
+    #[test]
+    fn truncated_json_is_rejected() {
+        let json = write_json(&results(&[("first", "a"), ("second", "b\\c")]));
+        for end in 0..json.trim_end().len() {
+            assert_eq!(parse_json(&json[..end]), None, "{:?}", &json[..end]);
+        }
+        assert_eq!(parse_json(r#"{"a": "\u00"}"#), None);
+        assert_eq!(parse_json(r#"{"a": "b",}"#), None);
+        assert_eq!(parse_json(r#"{"a" "b"}"#), None);
+        assert_eq!(parse_json(r#"["a"]"#), None);
+    }
+
+    #[test]
+    fn a_truncated_cache_file_starts_empty() {
+        let path = std::env::temp_dir().join(std::format!(
+            "cutlass-sys-autotune-test-{}.json",
+            process::id()
+        ));
+        let json = write_json(&results(&[("key", "winner")]));
+        fs::write(&path, &json[..json.len() / 2]).unwrap();
+        assert!(read_results(&path).is_empty());
+        fs::write(&path, &json).unwrap();
+        assert_eq!(read_results(&path), results(&[("key", "winner")]));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,238 @@
+//! Two 2-D forward convolutions fused into one kernel, as in CUTLASS
+//! example 13:
+//!
+//! ```text
+//! D = alpha1 * conv1(activation(alpha0 * conv0(A, B0)), B1) + beta1 * C
+//! ```
+//!
+//! Each threadblock computes all output channels of `conv0` for its tile of
+//! output pixels and keeps them in registers as the input of `conv1`, so the
+//! intermediate activation never reaches global memory. That only works when
+//! `conv1` reads exactly the pixel `conv0` wrote, which restricts the chains
+//! that fuse:
+//!
+//! - `conv1` is pointwise: a 1x1 filter with unit stride and no padding. The
+//!   common bottleneck pattern is therefore RxS-then-1x1; a 1x1-then-3x3
+//!   chain needs the neighbours of each intermediate pixel and does not fuse.
+//! - `conv1`'s input is `conv0`'s output: the same `N x P x Q` extent, with
+//!   `conv1.c == conv0.k`.
+//! - `conv0.k` is 64 and `conv1.k` 64 or 128, one threadblock tile each.
+//! - Both are ungrouped, unsplit 2-D cross-correlations or convolutions of
+//!   `F16` or `BF16` tensors, `conv0.c` a multiple of 8, and `conv0.beta` is
+//!   0 (the intermediate has no source tensor).
+//!
+//! [`B2bConvConfig::check`] tests these on the host and names the one a
+//! chain breaks; [`B2bConvConfig::supports`] additionally asks the kernel.
+//! The launch rejects a broken chain with [`Status::ErrorNotSupported`] or
+//! [`Status::ErrorInvalidProblem`]. Tensors are packed NHWC, as for
+//! [`conv`](crate::conv).
+
+use core::ffi::c_void;
+use core::fmt;
+
+use crate::context::CutlassContext;
+use crate::conv::ConvConfig;
+use crate::error::CutlassError;
+use crate::gemm::Activation;
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Output channels of `conv0` the fused kernels support.
+pub const INTERMEDIATE_CHANNELS: i32 = 64;
+
+/// Output channels of `conv1` the fused kernels support.
+pub const OUTPUT_CHANNELS: [i32; 2] = [64, 128];
+
+/// A chain of two convolutions to fuse.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct B2bConvConfig {
+    pub conv0: ConvConfig,
+    pub conv1: ConvConfig,
+    /// Applied to the output of `conv0`.
+    pub activation: Activation,
+}
+
+impl B2bConvConfig {
+    /// `conv0` followed by a pointwise convolution of its output into `k1`
+    /// channels, with `activation` in between.
+    pub const fn pointwise(conv0: ConvConfig, k1: i32, activation: Activation) -> Self {
+        let (p, q) = conv0.output_size();
+        let conv1 = ConvConfig::new(conv0.n, p, q, conv0.k, k1, 1, 1, conv0.element);
+        Self {
+            conv0,
+            conv1,
+            activation,
+        }
+    }
+
+    /// Checks the chain against the restrictions of the fused kernels,
+    /// without a device.
+    pub fn check(&self) -> Result<(), B2bConvError> {
+        let (c0, c1) = (&self.conv0, &self.conv1);
+        let conv2d = |c: &ConvConfig| c.d == 1 && c.t == 1 && c.pad_d == 0;
+        if !conv2d(c0) || !conv2d(c1) {
+            return Err(B2bConvError::NotConv2d);
+        }
+        if c0.groups != 1 || c1.groups != 1 || c0.split_k_slices != 1 || c1.split_k_slices != 1 {
+            return Err(B2bConvError::GroupedOrSplit);
+        }
+        if c0.element != c1.element || !matches!(c0.element, DataType::F16 | DataType::BF16) {
+            return Err(B2bConvError::UnsupportedElement);
+        }
+        if c1.r != 1
+            || c1.s != 1
+            || c1.stride_h != 1
+            || c1.stride_w != 1
+            || c1.pad_h != 0
+            || c1.pad_w != 0
+        {
+            return Err(B2bConvError::NotPointwise);
+        }
+        let (p, q) = c0.output_size();
+        if (c1.n, c1.h, c1.w, c1.c) != (c0.n, p, q, c0.k) {
+            return Err(B2bConvError::ShapeMismatch);
+        }
+        if c0.k != INTERMEDIATE_CHANNELS || !OUTPUT_CHANNELS.contains(&c1.k) {
+            return Err(B2bConvError::UnsupportedChannels { k0: c0.k, k1: c1.k });
+        }
+        if c0.c % 8 != 0 {
+            return Err(B2bConvError::MisalignedChannels);
+        }
+        if c0.beta != 0.0 {
+            return Err(B2bConvError::IntermediateSource);
+        }
+        Ok(())
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_b2b_conv2d_fprop`] needs for
+    /// this config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_b2b_conv2d_fprop_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_b2b_conv2d_fprop`] can run this config
+    /// before any operand is allocated, returning the status the launch would
+    /// fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_b2b_conv2d_fprop_can_implement(context, self) }.result()
+    }
+
+    /// Launches the fused convolutions on `stream` through
+    /// [`cutlass_sys_b2b_conv2d_fprop`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address packed NHWC tensors in device
+    /// memory of `context`'s device covering both convolutions and stay valid
+    /// until the launch completes on `stream`, a stream of that device.
+    /// `workspace` must hold [`workspace_size`](Self::workspace_size) bytes
+    /// of device memory, or may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &B2bConvOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_b2b_conv2d_fprop(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers of a fused convolution chain.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct B2bConvOperands {
+    /// Input activation of `conv0`.
+    pub a: *const c_void,
+    pub filter0: *const c_void,
+    pub filter1: *const c_void,
+    /// May be null when `conv1.beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+}
+
+/// Restriction of the fused kernels a [`B2bConvConfig`] breaks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum B2bConvError {
+    /// A convolution has a depth dimension.
+    NotConv2d,
+    /// A convolution is grouped or split along K.
+    GroupedOrSplit,
+    /// The element types differ or are not `F16` or `BF16`.
+    UnsupportedElement,
+    /// `conv1` is not a 1x1, unit-stride, unpadded convolution.
+    NotPointwise,
+    /// `conv1`'s input is not the `N x P x Q x K` output of `conv0`.
+    ShapeMismatch,
+    /// The output channels are not [`INTERMEDIATE_CHANNELS`] and one of
+    /// [`OUTPUT_CHANNELS`].
+    UnsupportedChannels { k0: i32, k1: i32 },
+    /// `conv0.c` is not a multiple of 8.
+    MisalignedChannels,
+    /// `conv0.beta` is not 0.
+    IntermediateSource,
+}
+
+impl fmt::Display for B2bConvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            B2bConvError::NotConv2d => f.write_str("fused convolutions must be 2-D"),
+            B2bConvError::GroupedOrSplit => {
+                f.write_str("fused convolutions must be ungrouped and not split along K")
+            }
+            B2bConvError::UnsupportedElement => {
+                f.write_str("fused convolutions must both be f16 or both be bf16")
+            }
+            B2bConvError::NotPointwise => {
+                f.write_str("second convolution must be 1x1 with unit stride and no padding")
+            }
+            B2bConvError::ShapeMismatch => {
+                f.write_str("second convolution does not read the output of the first")
+            }
+            B2bConvError::UnsupportedChannels { k0, k1 } => write!(
+                f,
+                "unsupported output channels {} -> {} (need {} -> {:?})",
+                k0, k1, INTERMEDIATE_CHANNELS, OUTPUT_CHANNELS
+            ),
+            B2bConvError::MisalignedChannels => {
+                f.write_str("input channels must be a multiple of 8")
+            }
+            B2bConvError::IntermediateSource => {
+                f.write_str("first convolution cannot have a source tensor (beta != 0)")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for B2bConvError {}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_b2b_conv2d_fprop`] needs.
+    pub fn cutlass_sys_b2b_conv2d_fprop_workspace_size(
+        context: *const CutlassContext,
+        config: *const B2bConvConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_b2b_conv2d_fprop`] can run `config`, checked
+    /// without touching any operand: the status the launch would fail with,
+    /// or [`Status::Success`].
+    pub fn cutlass_sys_b2b_conv2d_fprop_can_implement(
+        context: *const CutlassContext,
+        config: *const B2bConvConfig,
+    ) -> Status;
+
+    /// Launch the fused convolutions on `stream`.
+    pub fn cutlass_sys_b2b_conv2d_fprop(
+        context: *const CutlassContext,
+        config: *const B2bConvConfig,
+        operands: *const B2bConvOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
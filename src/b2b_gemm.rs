@@ -0,0 +1,208 @@
+//! Two GEMMs fused into one kernel, keeping the intermediate on chip:
+//!
+//! ```text
+//! BackToBack:  D = alpha1 * activation(alpha0 * A * B0) * B1 + beta1 * C
+//! Gated:       D = activation(alpha0 * A * B0) * (alpha1 * A * B1)
+//! ```
+//!
+//! `BackToBack` is the register-resident B2B GEMM of CUTLASS example 13:
+//! GEMM0 (`m x n0 x k`) produces each threadblock's full `n0`-column slice of
+//! the intermediate, which feeds GEMM1 (`m x n1 x n0`) straight from
+//! registers. Both `n0` and `n1` must therefore fit one threadblock: `n0` is
+//! 64 and `n1` 64 or 128, anything else returns [`Status::ErrorNotSupported`].
+//! This suits chains of narrow layers, e.g. small MLPs applied per point or
+//! per token.
+//!
+//! `Gated` is the dual GEMM of CUTLASS example 45: both products share `A`
+//! and run in the same mainloop, and the epilogue multiplies the activated
+//! first by the second, as in the SwiGLU (`Silu`) and GeGLU (`Gelu`) input
+//! projections of transformer MLPs. Both problem shapes are `m x n x k`.
+//!
+//! `A`, `C` and `D` are row-major and `B0` and `B1` column-major (i.e. `B^T`
+//! stored row-major, as weight matrices usually are). The element type is
+//! `F16` or `BF16`, accumulating in `f32`; anything else returns
+//! [`Status::ErrorInvalidDataType`]. With `batch_count > 1` the launch
+//! computes that many independent problems, operand `i` starting
+//! `i * batch_stride_*` elements after its pointer.
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::coord::GemmCoord;
+use crate::error::CutlassError;
+use crate::gemm::Activation;
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// How the two GEMMs of a [`B2bGemmConfig`] are combined.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum B2bMode {
+    /// The activated output of GEMM0 is the `A` operand of GEMM1.
+    #[default]
+    BackToBack = 0,
+    /// Both GEMMs read `A`; their outputs are combined elementwise.
+    Gated = 1,
+}
+
+/// Problem description of a (batched) fused pair of GEMMs.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct B2bGemmConfig {
+    pub mode: B2bMode,
+    /// `m x n0 x k`.
+    pub problem0: GemmCoord,
+    /// `m x n1 x n0` for [`B2bMode::BackToBack`], equal to `problem0` for
+    /// [`B2bMode::Gated`].
+    pub problem1: GemmCoord,
+    pub element: DataType,
+    /// Applied to the output of GEMM0.
+    pub activation: Activation,
+    pub batch_count: i32,
+    pub lda: i64,
+    pub ldb0: i64,
+    pub ldb1: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub batch_stride_a: i64,
+    pub batch_stride_b0: i64,
+    pub batch_stride_b1: i64,
+    pub batch_stride_c: i64,
+    pub batch_stride_d: i64,
+    pub alpha0: f32,
+    pub alpha1: f32,
+    /// Scale of `C`; [`B2bMode::BackToBack`] only.
+    pub beta1: f32,
+}
+
+impl B2bGemmConfig {
+    /// A single tightly packed back-to-back problem, `m x n0 x k` then
+    /// `m x n1 x n0`, with unit alphas and `beta1 = 0`.
+    pub const fn back_to_back(
+        m: i32,
+        n0: i32,
+        n1: i32,
+        k: i32,
+        element: DataType,
+        activation: Activation,
+    ) -> Self {
+        Self::packed(
+            B2bMode::BackToBack,
+            GemmCoord::new(m, n0, k),
+            GemmCoord::new(m, n1, n0),
+            element,
+            activation,
+        )
+    }
+
+    /// A single tightly packed gated problem, two `m x n x k` products, with
+    /// unit alphas.
+    pub const fn gated(m: i32, n: i32, k: i32, element: DataType, activation: Activation) -> Self {
+        let problem = GemmCoord::new(m, n, k);
+        Self::packed(B2bMode::Gated, problem, problem, element, activation)
+    }
+
+    const fn packed(
+        mode: B2bMode,
+        problem0: GemmCoord,
+        problem1: GemmCoord,
+        element: DataType,
+        activation: Activation,
+    ) -> Self {
+        let (m, k) = (problem0.m as i64, problem0.k as i64);
+        let (k1, n1) = (problem1.k as i64, problem1.n as i64);
+        Self {
+            mode,
+            problem0,
+            problem1,
+            element,
+            activation,
+            batch_count: 1,
+            lda: k,
+            ldb0: k,
+            ldb1: k1,
+            ldc: n1,
+            ldd: n1,
+            batch_stride_a: m * k,
+            batch_stride_b0: k * problem0.n as i64,
+            batch_stride_b1: k1 * n1,
+            batch_stride_c: m * n1,
+            batch_stride_d: m * n1,
+            alpha0: 1.0,
+            alpha1: 1.0,
+            beta1: 0.0,
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_b2b_gemm`] needs for this
+    /// config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_b2b_gemm_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_b2b_gemm`] can run this config before any
+    /// operand is allocated, returning the status the launch would fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_b2b_gemm_can_implement(context, self) }.result()
+    }
+
+    /// Launches the fused GEMMs on `stream` through [`cutlass_sys_b2b_gemm`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering both problems and stay valid until the launch
+    /// completes on `stream`, a stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory, or
+    /// may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &B2bGemmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_b2b_gemm(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers of a fused GEMM pair.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct B2bGemmOperands {
+    pub a: *const c_void,
+    pub b0: *const c_void,
+    pub b1: *const c_void,
+    /// May be null when `beta1 == 0`, and is ignored by [`B2bMode::Gated`].
+    pub c: *const c_void,
+    pub d: *mut c_void,
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_b2b_gemm`] needs.
+    pub fn cutlass_sys_b2b_gemm_workspace_size(
+        context: *const CutlassContext,
+        config: *const B2bGemmConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_b2b_gemm`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_b2b_gemm_can_implement(
+        context: *const CutlassContext,
+        config: *const B2bGemmConfig,
+    ) -> Status;
+
+    /// Launch the fused GEMMs on `stream`.
+    pub fn cutlass_sys_b2b_gemm(
+        context: *const CutlassContext,
+        config: *const B2bGemmConfig,
+        operands: *const B2bGemmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
@@ -0,0 +1,653 @@
+//! BLAS3 routines over dense column-major matrices, following the BLAS and
+//! cuBLAS conventions:
+//!
+//! | routine | operation                                                                |
+//! |---------|--------------------------------------------------------------------------|
+//! | SYRK    | `D = alpha * op(A) * op(A)^T + beta * C`                                 |
+//! | HERK    | `D = alpha * op(A) * op(A)^H + beta * C`                                 |
+//! | SYR2K   | `D = alpha * (op(A) * op(B)^T + op(B) * op(A)^T) + beta * C`             |
+//! | HER2K   | `D = alpha * op(A) * op(B)^H + conj(alpha) * op(B) * op(A)^H + beta * C` |
+//! | TRMM    | `D = alpha * op(A) * B` or `alpha * B * op(A)`                           |
+//! | SYMM    | `D = alpha * A * B + beta * C` or `B * A`                                |
+//! | HEMM    | as SYMM, with `A` Hermitian                                              |
+//!
+//! For the rank-k and rank-2k updates `op(X)` is `X` (`n x k`) for
+//! [`Transpose::N`] and `X^T` (`X` is `k x n`) for [`Transpose::T`], and only
+//! the `fill` triangle of `C` / `D` is read and written. For TRMM, SYMM and
+//! HEMM `A` is square, on the [`SideMode`] side of the `m x n` `B`, and only
+//! its `fill` triangle is read.
+//!
+//! The element type is one of `F32`, `F64`, `C32` or `C64`. HERK, HER2K and
+//! HEMM only accept the complex types; HERK uses the real parts of `alpha`
+//! and `beta`, and HER2K the real part of `beta`. SYRK and SYR2K of a
+//! complex matrix transpose it without conjugating, as `csyrk` / `zsyr2k`
+//! do.
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::error::CutlassError;
+use crate::types::{cudaStream_t, Complex, DataType, Status};
+
+/// Which triangle of a symmetric or Hermitian matrix is stored.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FillMode {
+    #[default]
+    Lower = 0,
+    Upper = 1,
+}
+
+/// Whether an operand is used as stored or transposed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Transpose {
+    #[default]
+    N = 0,
+    T = 1,
+}
+
+/// Which side of `B` the square matrix `A` multiplies from.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SideMode {
+    /// `op(A) * B`, with `A` `m x m`.
+    #[default]
+    Left = 0,
+    /// `B * op(A)`, with `A` `n x n`.
+    Right = 1,
+}
+
+/// Whether the diagonal of a triangular matrix is read or assumed to be 1.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DiagType {
+    #[default]
+    NonUnit = 0,
+    Unit = 1,
+}
+
+/// Problem description of a rank-k update (SYRK / HERK).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RankKConfig {
+    /// Order of `C`.
+    pub n: i32,
+    pub k: i32,
+    pub element: DataType,
+    pub fill: FillMode,
+    pub trans: Transpose,
+    pub lda: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub alpha: Complex<f64>,
+    pub beta: Complex<f64>,
+}
+
+impl RankKConfig {
+    /// A tightly packed `C = A * A^T` (or `A * A^H`) of the lower triangle,
+    /// with `alpha = 1`, `beta = 0`.
+    pub const fn new(n: i32, k: i32, element: DataType) -> Self {
+        Self {
+            n,
+            k,
+            element,
+            fill: FillMode::Lower,
+            trans: Transpose::N,
+            lda: n as i64,
+            ldc: n as i64,
+            ldd: n as i64,
+            alpha: Complex::new(1.0, 0.0),
+            beta: Complex::new(0.0, 0.0),
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_syrk`] needs for this config.
+    pub fn syrk_workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_syrk_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_syrk`] can run this config before any operand
+    /// is allocated, returning the status the launch would fail with.
+    pub fn syrk_supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_syrk_can_implement(context, self) }.result()
+    }
+
+    /// Launches SYRK on `stream`, `D = alpha * A * A^T + beta * C`.
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`syrk_workspace_size`](Self::syrk_workspace_size) bytes of device
+    /// memory, or may be null when that is zero.
+    pub unsafe fn syrk(
+        &self,
+        context: &CutlassContext,
+        operands: &RankKOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe { cutlass_sys_syrk(context, self, operands, workspace, stream) })
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_herk`] needs for this config.
+    pub fn herk_workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_herk_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_herk`] can run this config before any operand
+    /// is allocated, returning the status the launch would fail with.
+    pub fn herk_supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_herk_can_implement(context, self) }.result()
+    }
+
+    /// Launches HERK on `stream`, `D = alpha * A * A^H + beta * C`.
+    ///
+    /// # Safety
+    ///
+    /// As for [`syrk`](Self::syrk), with the workspace of
+    /// [`herk_workspace_size`](Self::herk_workspace_size).
+    pub unsafe fn herk(
+        &self,
+        context: &CutlassContext,
+        operands: &RankKOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe { cutlass_sys_herk(context, self, operands, workspace, stream) })
+    }
+}
+
+/// Device pointers of a rank-k update. `d` may alias `c` for an in-place
+/// update.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct RankKOperands {
+    pub a: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+}
+
+/// Problem description of a symmetric or Hermitian rank-2k update (SYR2K /
+/// HER2K).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rank2KConfig {
+    /// Order of `C`.
+    pub n: i32,
+    pub k: i32,
+    pub element: DataType,
+    pub fill: FillMode,
+    /// Applies to both `A` and `B`.
+    pub trans: Transpose,
+    pub lda: i64,
+    pub ldb: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub alpha: Complex<f64>,
+    pub beta: Complex<f64>,
+}
+
+impl Rank2KConfig {
+    /// A tightly packed `C = A * B^T + B * A^T` (or `A * B^H + B * A^H`) of
+    /// the lower triangle, with `alpha = 1`, `beta = 0`.
+    pub const fn new(n: i32, k: i32, element: DataType) -> Self {
+        Self {
+            n,
+            k,
+            element,
+            fill: FillMode::Lower,
+            trans: Transpose::N,
+            lda: n as i64,
+            ldb: n as i64,
+            ldc: n as i64,
+            ldd: n as i64,
+            alpha: Complex::new(1.0, 0.0),
+            beta: Complex::new(0.0, 0.0),
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_syr2k`] needs for this config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_syr2k_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_syr2k`] can run this config before any operand
+    /// is allocated, returning the status the launch would fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_syr2k_can_implement(context, self) }.result()
+    }
+
+    /// Launches SYR2K on `stream` through [`cutlass_sys_syr2k`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory, or
+    /// may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &Rank2KOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_syr2k(context, self, operands, workspace, stream)
+        })
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_her2k`] needs for this config.
+    pub fn her2k_workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_her2k_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_her2k`] can run this config before any operand
+    /// is allocated, returning the status the launch would fail with.
+    pub fn her2k_supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_her2k_can_implement(context, self) }.result()
+    }
+
+    /// Launches HER2K on `stream`,
+    /// `D = alpha * A * B^H + conj(alpha) * B * A^H + beta * C`.
+    ///
+    /// # Safety
+    ///
+    /// As for [`launch`](Self::launch), with the workspace of
+    /// [`her2k_workspace_size`](Self::her2k_workspace_size).
+    pub unsafe fn her2k(
+        &self,
+        context: &CutlassContext,
+        operands: &Rank2KOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_her2k(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers of a rank-2k update. `d` may alias `c` for an in-place
+/// update.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Rank2KOperands {
+    pub a: *const c_void,
+    pub b: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+}
+
+/// Problem description of a triangular matrix-matrix multiply. The output is
+/// written out of place to `D`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrmmConfig {
+    pub m: i32,
+    pub n: i32,
+    pub element: DataType,
+    pub side: SideMode,
+    pub fill: FillMode,
+    pub trans: Transpose,
+    pub diag: DiagType,
+    pub lda: i64,
+    pub ldb: i64,
+    pub ldd: i64,
+    pub alpha: Complex<f64>,
+}
+
+impl TrmmConfig {
+    /// A tightly packed `D = L * B` with a non-unit lower-triangular `A` on
+    /// the left, with `alpha = 1`.
+    pub const fn new(m: i32, n: i32, element: DataType) -> Self {
+        Self {
+            m,
+            n,
+            element,
+            side: SideMode::Left,
+            fill: FillMode::Lower,
+            trans: Transpose::N,
+            diag: DiagType::NonUnit,
+            lda: m as i64,
+            ldb: m as i64,
+            ldd: m as i64,
+            alpha: Complex::new(1.0, 0.0),
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_trmm`] needs for this config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_trmm_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_trmm`] can run this config before any operand
+    /// is allocated, returning the status the launch would fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_trmm_can_implement(context, self) }.result()
+    }
+
+    /// Launches TRMM on `stream` through [`cutlass_sys_trmm`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory, or
+    /// may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &TrmmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe { cutlass_sys_trmm(context, self, operands, workspace, stream) })
+    }
+}
+
+/// Device pointers of a triangular matrix-matrix multiply.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct TrmmOperands {
+    pub a: *const c_void,
+    pub b: *const c_void,
+    pub d: *mut c_void,
+}
+
+/// Problem description of a symmetric or Hermitian matrix-matrix multiply.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SymmConfig {
+    pub m: i32,
+    pub n: i32,
+    pub element: DataType,
+    pub side: SideMode,
+    pub fill: FillMode,
+    pub lda: i64,
+    pub ldb: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub alpha: Complex<f64>,
+    pub beta: Complex<f64>,
+}
+
+impl SymmConfig {
+    /// A tightly packed `D = A * B` with `A` stored in its lower triangle on
+    /// the left, with `alpha = 1`, `beta = 0`.
+    pub const fn new(m: i32, n: i32, element: DataType) -> Self {
+        Self {
+            m,
+            n,
+            element,
+            side: SideMode::Left,
+            fill: FillMode::Lower,
+            lda: m as i64,
+            ldb: m as i64,
+            ldc: m as i64,
+            ldd: m as i64,
+            alpha: Complex::new(1.0, 0.0),
+            beta: Complex::new(0.0, 0.0),
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_symm`] needs for this config.
+    pub fn symm_workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_symm_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_symm`] can run this config before any operand
+    /// is allocated, returning the status the launch would fail with.
+    pub fn symm_supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_symm_can_implement(context, self) }.result()
+    }
+
+    /// Launches SYMM on `stream` through [`cutlass_sys_symm`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`symm_workspace_size`](Self::symm_workspace_size) bytes of device
+    /// memory, or may be null when that is zero.
+    pub unsafe fn symm(
+        &self,
+        context: &CutlassContext,
+        operands: &SymmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe { cutlass_sys_symm(context, self, operands, workspace, stream) })
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_hemm`] needs for this config.
+    pub fn hemm_workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_hemm_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_hemm`] can run this config before any operand
+    /// is allocated, returning the status the launch would fail with.
+    pub fn hemm_supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_hemm_can_implement(context, self) }.result()
+    }
+
+    /// Launches HEMM on `stream` through [`cutlass_sys_hemm`].
+    ///
+    /// # Safety
+    ///
+    /// As for [`symm`](Self::symm), with the workspace of
+    /// [`hemm_workspace_size`](Self::hemm_workspace_size).
+    pub unsafe fn hemm(
+        &self,
+        context: &CutlassContext,
+        operands: &SymmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe { cutlass_sys_hemm(context, self, operands, workspace, stream) })
+    }
+}
+
+/// Device pointers of a symmetric or Hermitian matrix-matrix multiply.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct SymmOperands {
+    pub a: *const c_void,
+    pub b: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_syrk`] needs.
+    pub fn cutlass_sys_syrk_workspace_size(
+        context: *const CutlassContext,
+        config: *const RankKConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_syrk`] can run `config`, checked without touching
+    /// any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_syrk_can_implement(
+        context: *const CutlassContext,
+        config: *const RankKConfig,
+    ) -> Status;
+
+    /// Launch a symmetric rank-k update on `stream`.
+    pub fn cutlass_sys_syrk(
+        context: *const CutlassContext,
+        config: *const RankKConfig,
+        operands: *const RankKOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_herk`] needs.
+    pub fn cutlass_sys_herk_workspace_size(
+        context: *const CutlassContext,
+        config: *const RankKConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_herk`] can run `config`, checked without touching
+    /// any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_herk_can_implement(
+        context: *const CutlassContext,
+        config: *const RankKConfig,
+    ) -> Status;
+
+    /// Launch a Hermitian rank-k update on `stream`. With [`Transpose::T`]
+    /// the operand is conjugate-transposed, computing `A^H * A`.
+    pub fn cutlass_sys_herk(
+        context: *const CutlassContext,
+        config: *const RankKConfig,
+        operands: *const RankKOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_syr2k`] needs.
+    pub fn cutlass_sys_syr2k_workspace_size(
+        context: *const CutlassContext,
+        config: *const Rank2KConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_syr2k`] can run `config`, checked without touching
+    /// any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_syr2k_can_implement(
+        context: *const CutlassContext,
+        config: *const Rank2KConfig,
+    ) -> Status;
+
+    /// Launch a symmetric rank-2k update on `stream`.
+    pub fn cutlass_sys_syr2k(
+        context: *const CutlassContext,
+        config: *const Rank2KConfig,
+        operands: *const Rank2KOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_her2k`] needs.
+    pub fn cutlass_sys_her2k_workspace_size(
+        context: *const CutlassContext,
+        config: *const Rank2KConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_her2k`] can run `config`, checked without touching
+    /// any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_her2k_can_implement(
+        context: *const CutlassContext,
+        config: *const Rank2KConfig,
+    ) -> Status;
+
+    /// Launch a Hermitian rank-2k update on `stream`.
+    pub fn cutlass_sys_her2k(
+        context: *const CutlassContext,
+        config: *const Rank2KConfig,
+        operands: *const Rank2KOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_trmm`] needs.
+    pub fn cutlass_sys_trmm_workspace_size(
+        context: *const CutlassContext,
+        config: *const TrmmConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_trmm`] can run `config`, checked without touching
+    /// any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_trmm_can_implement(
+        context: *const CutlassContext,
+        config: *const TrmmConfig,
+    ) -> Status;
+
+    /// Launch a triangular matrix-matrix multiply on `stream`.
+    pub fn cutlass_sys_trmm(
+        context: *const CutlassContext,
+        config: *const TrmmConfig,
+        operands: *const TrmmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_symm`] needs.
+    pub fn cutlass_sys_symm_workspace_size(
+        context: *const CutlassContext,
+        config: *const SymmConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_symm`] can run `config`, checked without touching
+    /// any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_symm_can_implement(
+        context: *const CutlassContext,
+        config: *const SymmConfig,
+    ) -> Status;
+
+    /// Launch a symmetric matrix-matrix multiply on `stream`.
+    pub fn cutlass_sys_symm(
+        context: *const CutlassContext,
+        config: *const SymmConfig,
+        operands: *const SymmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_hemm`] needs.
+    pub fn cutlass_sys_hemm_workspace_size(
+        context: *const CutlassContext,
+        config: *const SymmConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_hemm`] can run `config`, checked without touching
+    /// any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_hemm_can_implement(
+        context: *const CutlassContext,
+        config: *const SymmConfig,
+    ) -> Status;
+
+    /// Launch a Hermitian matrix-matrix multiply on `stream`.
+    pub fn cutlass_sys_hemm(
+        context: *const CutlassContext,
+        config: *const SymmConfig,
+        operands: *const SymmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
@@ -0,0 +1,209 @@
+//! Block-scaled narrow-precision GEMM on Blackwell (SM100) tensor cores:
+//!
+//! ```text
+//! D = alpha * (SFA . A) * (SFB . B) + beta * C
+//! ```
+//!
+//! where `A` and `B` hold FP4, FP6 or FP8 values and every run of 16 (NVFP4)
+//! or 32 (MX formats) consecutive elements along K shares one scale factor
+//! from `SFA` / `SFB`. `A` is row-major (`M x K`), `B` column-major (`N x K`
+//! stored row-major) and `C` / `D` row-major. Sub-byte elements are packed
+//! along K, low bits first; leading dimensions count elements. MXFP8
+//! operands can be prepared on the host as [`F8E4M3`] / [`F8E5M2`].
+//!
+//! [`F8E4M3`]: crate::types::F8E4M3
+//! [`F8E5M2`]: crate::types::F8E5M2
+//!
+//! Scale factors are read in CUTLASS's interleaved `Sm1xxBlkScaledConfig`
+//! layout (128 x 4 tiles of scales), not row-major; size their buffers with
+//! [`BlockScaledGemmConfig::sfa_size`] and [`BlockScaledGemmConfig::sfb_size`].
+//!
+//! Only available with the `sm100` feature, and only on compute capability
+//! 10.0 devices; anything else returns [`Status::ErrorArchMismatch`].
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::coord::GemmCoord;
+use crate::error::CutlassError;
+use crate::library::NumericTypeID;
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Element and scale-factor format of the `A` and `B` operands.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlockScaledType {
+    /// E2M1 values with a UE4M3 scale per 16 elements.
+    Nvfp4 = 0,
+    /// E2M1 values with a UE8M0 scale per 32 elements.
+    Mxfp4 = 1,
+    /// E3M2 values with a UE8M0 scale per 32 elements.
+    Mxfp6E3M2 = 2,
+    /// E2M3 values with a UE8M0 scale per 32 elements.
+    Mxfp6E2M3 = 3,
+    /// E4M3 values with a UE8M0 scale per 32 elements.
+    Mxfp8E4M3 = 4,
+    /// E5M2 values with a UE8M0 scale per 32 elements.
+    Mxfp8E5M2 = 5,
+}
+
+impl BlockScaledType {
+    /// Type of the operand values.
+    pub const fn data_type(self) -> NumericTypeID {
+        match self {
+            Self::Nvfp4 | Self::Mxfp4 => NumericTypeID::FE2M1,
+            Self::Mxfp6E3M2 => NumericTypeID::FE3M2,
+            Self::Mxfp6E2M3 => NumericTypeID::FE2M3,
+            Self::Mxfp8E4M3 => NumericTypeID::FE4M3,
+            Self::Mxfp8E5M2 => NumericTypeID::FE5M2,
+        }
+    }
+
+    /// Type of the scale factors.
+    pub const fn scale_type(self) -> NumericTypeID {
+        match self {
+            Self::Nvfp4 => NumericTypeID::FUE4M3,
+            _ => NumericTypeID::FUE8M0,
+        }
+    }
+
+    /// Number of consecutive K elements sharing one scale factor.
+    pub const fn scale_vector_size(self) -> i32 {
+        match self {
+            Self::Nvfp4 => 16,
+            _ => 32,
+        }
+    }
+}
+
+/// Problem description of a block-scaled GEMM.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockScaledGemmConfig {
+    pub problem: GemmCoord,
+    pub element: BlockScaledType,
+    /// Type of `C` and `D`: `F16`, `BF16` or `F32`. Accumulation is always
+    /// `f32`.
+    pub output: DataType,
+    pub lda: i64,
+    pub ldb: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub alpha: f32,
+    pub beta: f32,
+}
+
+impl BlockScaledGemmConfig {
+    /// A tightly packed `m x n x k` problem with `alpha = 1`, `beta = 0`.
+    pub const fn new(m: i32, n: i32, k: i32, element: BlockScaledType, output: DataType) -> Self {
+        Self {
+            problem: GemmCoord::new(m, n, k),
+            element,
+            output,
+            lda: k as i64,
+            ldb: k as i64,
+            ldc: n as i64,
+            ldd: n as i64,
+            alpha: 1.0,
+            beta: 0.0,
+        }
+    }
+
+    /// Bytes of scale factors of `A` in the layout the kernel reads; see
+    /// [`cutlass_sys_block_scaled_sfa_size`].
+    pub fn sfa_size(&self) -> usize {
+        // SAFETY: only reads `self`.
+        unsafe { cutlass_sys_block_scaled_sfa_size(self) }
+    }
+
+    /// Bytes of scale factors of `B` in the layout the kernel reads.
+    pub fn sfb_size(&self) -> usize {
+        // SAFETY: only reads `self`.
+        unsafe { cutlass_sys_block_scaled_sfb_size(self) }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_block_scaled_gemm`] needs for
+    /// this config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_block_scaled_gemm_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_block_scaled_gemm`] can run this config
+    /// before any operand is allocated, returning the status the launch would
+    /// fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_block_scaled_gemm_can_implement(context, self) }.result()
+    }
+
+    /// Launches the GEMM on `stream` through [`cutlass_sys_block_scaled_gemm`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory, or
+    /// may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &BlockScaledGemmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_block_scaled_gemm(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers of a block-scaled GEMM launch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct BlockScaledGemmOperands {
+    pub a: *const c_void,
+    pub b: *const c_void,
+    /// Scale factors of `a`, [`BlockScaledGemmConfig::sfa_size`] bytes.
+    pub sfa: *const c_void,
+    /// Scale factors of `b`, [`BlockScaledGemmConfig::sfb_size`] bytes.
+    pub sfb: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+}
+
+extern "C" {
+    /// Bytes of scale factors of `A` for `config`, or 0 for an unsupported
+    /// element or output type.
+    pub fn cutlass_sys_block_scaled_sfa_size(config: *const BlockScaledGemmConfig) -> usize;
+
+    /// Bytes of scale factors of `B` for `config`, or 0 for an unsupported
+    /// element or output type.
+    pub fn cutlass_sys_block_scaled_sfb_size(config: *const BlockScaledGemmConfig) -> usize;
+
+    /// Bytes of device workspace [`cutlass_sys_block_scaled_gemm`] needs.
+    pub fn cutlass_sys_block_scaled_gemm_workspace_size(
+        context: *const CutlassContext,
+        config: *const BlockScaledGemmConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_block_scaled_gemm`] can run `config`, checked
+    /// without touching any operand: the status the launch would fail with,
+    /// or [`Status::Success`].
+    pub fn cutlass_sys_block_scaled_gemm_can_implement(
+        context: *const CutlassContext,
+        config: *const BlockScaledGemmConfig,
+    ) -> Status;
+
+    /// Launch a block-scaled GEMM on `stream`.
+    pub fn cutlass_sys_block_scaled_gemm(
+        context: *const CutlassContext,
+        config: *const BlockScaledGemmConfig,
+        operands: *const BlockScaledGemmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
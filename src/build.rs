@@ -0,0 +1,1530 @@
+//! `cc::Build` configuration for crates compiling their own CUTLASS kernels
+//! (`build` feature).
+//!
+//! Add `cutlass-sys` with this feature as a build-dependency (next to the
+//! regular dependency that pulls in the headers) and let [`apply_to`] set
+//! up the build the way this crate's shim is compiled: CUDA mode,
+//! `-std=c++17`, `--expt-relaxed-constexpr`, the CUTLASS and `tools/util`
+//! include directories, and `-gencode` targets for the selected SM versions
+//! plus PTX for the newest:
+//!
+//! ```ignore
+//! // build.rs
+//! let mut build = cc::Build::new();
+//! cutlass_sys::build::apply_to(&mut build);
+//! build.file("src/kernels.cu").compile("kernels");
+//! ```
+//!
+//! The SM versions are those of `CUTLASS_CUDA_ARCH` as the shim reads it.
+//! When it is unset they are the ones the shim compiled for, including those
+//! of the local GPU it detects, when the build script's crate also depends on
+//! this one with `shim`, and `80` otherwise; [`CutlassBuild::arch`] names
+//! them in code instead. Like
+//! the shim, sm_90 and sm_100 compile for their arch-specific `sm_90a` and
+//! `sm_100a` targets, which WGMMA, TMA and tcgen05 kernels need.
+//!
+//! With the `bindgen` feature, [`bindgen_builder`] starts a `bindgen::Builder`
+//! for headers that include CUTLASS or CuTe: the same include directories,
+//! clang's CUDA mode and the blocklists that keep bindgen out of the template
+//! machinery it cannot translate.
+//!
+//! [`KernelGenerator`] runs CUTLASS's Python kernel generator, the one
+//! behind the official kernel library, for an operation and kernel name
+//! filter, and compiles what it emits into a static library.
+//! [`KernelManifest`] instantiates GEMMs declared in a TOML manifest without
+//! Python. Both keep their compiled libraries in the persistent object cache
+//! the crate's build script exports, keyed by toolkit, flags and sources.
+//!
+//! [`gencode_flags`] turns an arch list of its own, such as
+//! `["80", "86", "90a", "compute_90"]` or the `CUDA_ARCHS` variable, into the
+//! `-gencode` flags for it.
+
+use core::fmt;
+use std::env;
+use std::format;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::string::{String, ToString};
+use std::vec;
+use std::vec::Vec;
+
+use crate::library::LayoutTypeID;
+use crate::types::DataType;
+
+/// The CUTLASS headers and SM versions a build compiles against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CutlassBuild {
+    include_dir: PathBuf,
+    archs: Vec<u32>,
+    /// Whether `archs` came from [`arch`](Self::arch) rather than the
+    /// environment.
+    explicit: bool,
+}
+
+impl CutlassBuild {
+    /// The CUTLASS headers this crate resolved, and the SM versions of
+    /// `CUTLASS_CUDA_ARCH`. Reads the `links` metadata when the build
+    /// script's crate also depends on this one, so both see the same headers
+    /// (`DEP_CUTLASS_INCLUDE_DIR`) and, without `CUTLASS_CUDA_ARCH`, the SM
+    /// versions the shim compiled for (`DEP_CUTLASS_ARCHS`). Otherwise uses
+    /// the directory this copy was built against and `80`. Prints the `rerun-if-env-changed`
+    /// line for `CUTLASS_CUDA_ARCH`.
+    ///
+    /// # Panics
+    ///
+    /// If `CUTLASS_CUDA_ARCH` is set but is not a comma-separated list of SM
+    /// versions such as `80,86` or `sm_90`.
+    pub fn new() -> Self {
+        std::println!("cargo:rerun-if-env-changed=CUTLASS_CUDA_ARCH");
+        let include_dir = env::var_os("DEP_CUTLASS_INCLUDE_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(env!("CUTLASS_INCLUDE_DIR")));
+        let archs = env::var("CUTLASS_CUDA_ARCH").or_else(|_| env::var("DEP_CUTLASS_ARCHS"));
+        let archs = match archs {
+            Ok(value) => parse_archs(&value),
+            Err(_) => Vec::from([80]),
+        };
+        Self {
+            include_dir,
+            archs,
+            explicit: false,
+        }
+    }
+
+    /// Compiles for SM version `arch` (e.g. `86`) as well. The first call
+    /// replaces the versions [`new`](Self::new) read from the environment.
+    pub fn arch(mut self, arch: u32) -> Self {
+        if !self.explicit {
+            self.archs.clear();
+            self.explicit = true;
+        }
+        self.archs.push(arch);
+        self.archs.sort_unstable();
+        self.archs.dedup();
+        self
+    }
+
+    /// The CUTLASS `include` directory.
+    pub fn include_dir(&self) -> &Path {
+        &self.include_dir
+    }
+
+    /// The SM versions compiled for, ascending.
+    pub fn archs(&self) -> &[u32] {
+        &self.archs
+    }
+
+    /// Configures `build` for CUDA sources including CUTLASS and CuTe
+    /// headers. Leaves the source files, the CUDA runtime linkage and
+    /// anything else the caller sets alone.
+    pub fn apply_to(&self, build: &mut cc::Build) {
+        build
+            .cuda(true)
+            .flag("-std=c++17")
+            .flag("--expt-relaxed-constexpr")
+            .include(&self.include_dir)
+            .include(self.include_dir.join("../tools/util/include"));
+        let mut targets: Vec<String> = self.archs.iter().map(|&arch| arch_target(arch)).collect();
+        if let Some(&newest) = self.archs.last() {
+            targets.push(format!("compute_{}", newest));
+        }
+        for flag in gencode_flags(&targets) {
+            build.flag(flag);
+        }
+    }
+}
+
+#[cfg(feature = "bindgen")]
+impl CutlassBuild {
+    /// A `bindgen::Builder` for headers including CUTLASS or CuTe, with the
+    /// include directories of [`apply_to`](Self::apply_to), clang's CUDA mode
+    /// (`-x cuda`, `-std=c++17`, host-side only, for the newest SM version)
+    /// and C++ namespaces. `--cuda-path` comes from `CUDA_HOME` or
+    /// `CUDA_PATH` when set.
+    ///
+    /// The headers' templates are out of bindgen's reach: `cute/`, the
+    /// CUTLASS kernel, epilogue and layout headers, and the CUDA fp16, bf16
+    /// and CUB / Thrust headers are blocklisted, and `std`, `cute` and
+    /// `cutlass` types are opaque, keeping their size and alignment. Add the
+    /// wrapper header and an allowlist of its own declarations:
+    ///
+    /// ```ignore
+    /// let bindings = cutlass_sys::bindgen_builder()
+    ///     .header("src/wrapper.h")
+    ///     .allowlist_function("my_.*")
+    ///     .generate()?;
+    /// ```
+    pub fn bindgen_builder(&self) -> bindgen::Builder {
+        let mut builder = bindgen::Builder::default()
+            .clang_args(["-x", "cuda", "-std=c++17", "--cuda-host-only", "-nocudalib"])
+            .clang_arg(format!("-I{}", self.include_dir.display()))
+            .clang_arg(format!(
+                "-I{}",
+                self.include_dir.join("../tools/util/include").display()
+            ))
+            .enable_cxx_namespaces()
+            .layout_tests(false);
+        if let Some(cuda) = env::var_os("CUDA_HOME").or_else(|| env::var_os("CUDA_PATH")) {
+            builder = builder.clang_arg(format!("--cuda-path={}", Path::new(&cuda).display()));
+        }
+        if let Some(&newest) = self.archs.last() {
+            builder = builder.clang_arg(format!("--cuda-gpu-arch=sm_{}", arch_target(newest)));
+        }
+        for file in BINDGEN_BLOCKLIST {
+            builder = builder.blocklist_file(*file);
+        }
+        builder
+            .opaque_type("std::.*")
+            .opaque_type("cute::.*")
+            .opaque_type("cutlass::.*")
+    }
+}
+
+/// Headers bindgen fails on or expands into unusable bindings: CuTe, the
+/// CUTLASS kernel-level templates, and the CUDA toolkit's template libraries.
+#[cfg(feature = "bindgen")]
+const BINDGEN_BLOCKLIST: &[&str] = &[
+    ".*/cute/.*",
+    ".*/cutlass/(arch|conv|epilogue|gemm|layout|pipeline|reduction|transform)/.*",
+    ".*/cutlass/(fast_math|numeric_conversion|array|functional)\\.h",
+    ".*/cuda_(fp16|bf16|fp8)\\.hpp",
+    ".*/(cub|thrust|cuda/std)/.*",
+];
+
+impl CutlassBuild {
+    /// A [`KernelGenerator`] for these headers' CUTLASS tree and SM versions.
+    pub fn kernel_generator(&self) -> KernelGenerator {
+        KernelGenerator {
+            build: self.clone(),
+            operations: "all".to_string(),
+            kernels: String::new(),
+            ignore_kernels: String::new(),
+            cuda_version: None,
+        }
+    }
+}
+
+/// Instantiates kernels with CUTLASS's Python generator
+/// (`python/cutlass_library/generator.py`), invoked as CUTLASS's own CMake
+/// build does, and compiles them together with the `cutlass::library`
+/// runtime (`tools/library/src`) into a static library:
+///
+/// ```ignore
+/// // build.rs
+/// cutlass_sys::build::CutlassBuild::new()
+///     .arch(80)
+///     .kernel_generator()
+///     .operations("gemm")
+///     .kernels("cutlass_tensorop_h*gemm_*align8")
+///     .compile("my_cutlass_kernels");
+/// ```
+///
+/// The kernels register with the library's manifest, so they are found
+/// through `cutlass::library::Handle` or `Singleton`, like those of the
+/// `library` feature. The generator needs Python 3: `PYTHON` names the
+/// interpreter, `python3` otherwise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KernelGenerator {
+    build: CutlassBuild,
+    operations: String,
+    kernels: String,
+    ignore_kernels: String,
+    cuda_version: Option<String>,
+}
+
+impl KernelGenerator {
+    /// The operation kinds to generate, comma-separated, as `--operations`
+    /// takes them: `gemm`, `conv2d`, `conv3d`, `rank_k`, `rank_2k`, `trmm`,
+    /// `symm` or `all` (the default).
+    pub fn operations(mut self, operations: &str) -> Self {
+        self.operations = operations.to_string();
+        self
+    }
+
+    /// Kernel name filters, comma-separated with `*` wildcards, as
+    /// `CUTLASS_LIBRARY_KERNELS` takes them. Without one the generator
+    /// emits only the largest tile of each kernel family.
+    pub fn kernels(mut self, kernels: &str) -> Self {
+        self.kernels = kernels.to_string();
+        self
+    }
+
+    /// Kernel name filters to leave out, in the form of
+    /// [`kernels`](Self::kernels).
+    pub fn ignore_kernels(mut self, kernels: &str) -> Self {
+        self.ignore_kernels = kernels.to_string();
+        self
+    }
+
+    /// The CUDA toolkit release (e.g. `12.4`) the generator targets, which
+    /// gates the kernels newer toolkits are needed for. By default the
+    /// release of `NVCC` or `nvcc` on `PATH`.
+    pub fn cuda_version(mut self, version: &str) -> Self {
+        self.cuda_version = Some(version.to_string());
+        self
+    }
+
+    /// Runs the generator into `out_dir` and returns the sources it emitted,
+    /// under `out_dir/generated`.
+    ///
+    /// # Panics
+    ///
+    /// If the generator cannot be run or fails.
+    pub fn generate(&self, out_dir: &Path) -> Vec<PathBuf> {
+        std::println!("cargo:rerun-if-env-changed=PYTHON");
+        let root = self.build.include_dir.join("..");
+        let python = env::var("PYTHON").unwrap_or_else(|_| "python3".to_string());
+        let archs: Vec<String> = self.build.archs.iter().map(|&a| arch_target(a)).collect();
+        let cuda_version = self.cuda_version.clone().or_else(nvcc_release);
+        fs::create_dir_all(out_dir).expect("Failed to create the generator's output directory");
+
+        let mut command = Command::new(&python);
+        command
+            .env("PYTHONPATH", root.join("python"))
+            .arg(root.join("python/cutlass_library/generator.py"))
+            .arg("--operations")
+            .arg(&self.operations)
+            .arg("--build-dir")
+            .arg(out_dir)
+            .arg("--curr-build-dir")
+            .arg(out_dir)
+            .args(["--generator-target", "library"])
+            .arg("--architectures")
+            .arg(archs.join(";"))
+            .arg("--kernels")
+            .arg(&self.kernels)
+            .arg("--ignore-kernels")
+            .arg(&self.ignore_kernels)
+            .arg("--disable-cutlass-package-imports");
+        if let Some(version) = &cuda_version {
+            command.arg("--cuda-version").arg(version);
+        }
+        let status = command.status().unwrap_or_else(|e| {
+            panic!(
+                "Failed to run the CUTLASS kernel generator with {}: {}. It needs Python 3; set \
+                PYTHON to the interpreter",
+                python, e
+            )
+        });
+        if !status.success() {
+            panic!("The CUTLASS kernel generator failed ({})", status);
+        }
+
+        let mut sources = Vec::new();
+        collect_sources(&out_dir.join("generated"), &mut sources)
+            .expect("Failed to collect the generated kernel sources");
+        sources.sort();
+        sources
+    }
+
+    /// Generates the kernels into `OUT_DIR/<name>` and compiles them, the
+    /// `cutlass::library` runtime and the static CUDA runtime into the static
+    /// library `name`, configured by [`CutlassBuild::apply_to`].
+    ///
+    /// # Panics
+    ///
+    /// If generating or compiling fails.
+    pub fn compile(&self, name: &str) {
+        let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is not set"));
+        let generated = self.generate(&out_dir.join(name));
+        let library = self.build.include_dir.join("../tools/library");
+        let mut runtime = Vec::new();
+        collect_sources(&library.join("src"), &mut runtime)
+            .expect("Failed to collect the cutlass::library sources");
+        runtime.sort();
+
+        let mut build = cc::Build::new();
+        self.build.apply_to(&mut build);
+        build
+            .include(library.join("include"))
+            .include(library.join("src"));
+        runtime.extend(generated);
+        compile_cached(&mut build, name, &runtime);
+    }
+}
+
+/// The release of `NVCC` (or `nvcc` on `PATH`) from `nvcc --version`, if it
+/// runs.
+fn nvcc_release() -> Option<String> {
+    let nvcc = env::var("NVCC").unwrap_or_else(|_| "nvcc".to_string());
+    let output = Command::new(nvcc).arg("--version").output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    // "Cuda compilation tools, release 12.4, V12.4.131"
+    let release = text.split("release ").nth(1)?.split(',').next()?;
+    Some(release.trim().to_string())
+}
+
+/// Compiles `sources` with `build` into the static library `name`, or reuses
+/// the copy the persistent object cache (`DEP_CUTLASS_OBJECT_CACHE`) holds for
+/// the same toolkit, compiler arguments and source contents, and links it
+/// with the CUDA runtime the shim uses. `CUTLASS_OBJECT_CACHE=0` bypasses the
+/// cache.
+fn compile_cached(build: &mut cc::Build, name: &str, sources: &[PathBuf]) {
+    std::println!("cargo:rerun-if-env-changed=CUTLASS_OBJECT_CACHE");
+    // link_cudart links the runtime after the library
+    build.cudart("none");
+    // A shim built with the rdc feature leaves its device link to this crate
+    let rdc_lib = env::var_os("DEP_CUTLASS_RDC_LIB").map(PathBuf::from);
+    if rdc_lib.is_some() {
+        build.flag("-rdc=true");
+    }
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is not set"));
+    let cache = env::var_os("DEP_CUTLASS_OBJECT_CACHE")
+        .filter(|_| env::var("CUTLASS_OBJECT_CACHE").map_or(true, |value| value != "0"));
+    let Some(cache) = cache else {
+        compile_library(build, name, sources, rdc_lib.as_deref());
+        link_cudart(build.get_compiler().path());
+        return;
+    };
+
+    // 64-bit FNV-1a over the inputs, each prefixed with its length, as the
+    // crate's build script keys the shim
+    let tool = build.get_compiler();
+    let mut inputs = vec![
+        nvcc_release().unwrap_or_default().into_bytes(),
+        env::var("TARGET").unwrap_or_default().into_bytes(),
+        tool.path().to_string_lossy().into_owned().into_bytes(),
+    ];
+    inputs.extend(
+        tool.args()
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned().into_bytes()),
+    );
+    for source in sources {
+        inputs.push(source.to_string_lossy().into_owned().into_bytes());
+        inputs.push(fs::read(source).expect("Failed to read a kernel source"));
+    }
+    // The device link takes the shim's device code in as well
+    if let Some(rdc_lib) = &rdc_lib {
+        inputs.push(fs::read(rdc_lib).expect("Failed to read the shim library"));
+    }
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for input in &inputs {
+        for &byte in (input.len() as u64).to_le_bytes().iter().chain(input) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    let msvc = env::var("CARGO_CFG_TARGET_ENV").is_ok_and(|target_env| target_env == "msvc");
+    let lib_file = if msvc {
+        format!("{}.lib", name)
+    } else {
+        format!("lib{}.a", name)
+    };
+    let entry = PathBuf::from(cache).join(format!("{}-{:016x}", name, hash));
+    let cached = entry.join(&lib_file);
+    if cached.is_file() {
+        std::println!(
+            "cargo:warning=Using the cached {} at {}",
+            name,
+            cached.display()
+        );
+        fs::copy(&cached, out_dir.join(&lib_file)).expect("Failed to copy a cached library");
+        // What cc prints after compiling
+        std::println!("cargo:rustc-link-search=native={}", out_dir.display());
+        std::println!("cargo:rustc-link-lib=static={}", name);
+        link_cudart(tool.path());
+        link_cxx_stdlib();
+        return;
+    }
+
+    compile_library(build, name, sources, rdc_lib.as_deref());
+    link_cudart(tool.path());
+    // Stored under a temporary name first, so concurrent builds never link a
+    // partial library
+    let partial = entry.join(format!("{}.{}.partial", lib_file, std::process::id()));
+    let stored = fs::create_dir_all(&entry)
+        .and_then(|_| fs::copy(out_dir.join(&lib_file), &partial))
+        .and_then(|_| fs::rename(&partial, &cached));
+    if let Err(e) = stored {
+        let _ = fs::remove_file(&partial);
+        std::println!("cargo:warning=Failed to cache {}: {}", name, e);
+    }
+}
+
+/// Compiles `sources` with `build` into the static library `name`. With
+/// `rdc_lib`, the shim's library of relocatable device code, the objects are
+/// device-linked together with it, in what must be the program's only device
+/// link, so their device code can call into CUTLASS's. A shim built with
+/// `dlto` as well (`DEP_CUTLASS_DLTO`) holds LTO intermediate code, which
+/// the link optimizes (`-dlto`) into machine code for the SM versions of
+/// `build`.
+fn compile_library(build: &mut cc::Build, name: &str, sources: &[PathBuf], rdc_lib: Option<&Path>) {
+    let Some(rdc_lib) = rdc_lib else {
+        build.files(sources).compile(name);
+        return;
+    };
+    // cc would device-link the objects on their own
+    let mut objects = build.files(sources).compile_intermediates();
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is not set"));
+    let dlink = out_dir.join(format!("{}_dlink.o", name));
+    let dlto = env::var("DEP_CUTLASS_DLTO").is_ok_and(|dlto| dlto == "1");
+    let tool = build.get_compiler();
+    let mut command = Command::new(tool.path());
+    // An LTO link generates machine code only, not the PTX targets
+    command.args(tool.args().iter().filter(|arg| {
+        let arg = arg.to_string_lossy();
+        !(dlto && arg.starts_with("-gencode") && arg.contains("code=compute_"))
+    }));
+    if dlto {
+        command.arg("-dlto");
+    }
+    let status = command
+        .arg("--device-link")
+        .arg("-o")
+        .arg(&dlink)
+        .args(&objects)
+        .arg(rdc_lib)
+        .status()
+        .expect("Failed to run nvcc for the device link");
+    if !status.success() {
+        panic!(
+            "The device link of {} with {} failed ({})",
+            name,
+            rdc_lib.display(),
+            status
+        );
+    }
+    objects.push(dlink);
+    cc::Build::new().objects(&objects).compile(name);
+    link_cxx_stdlib();
+}
+
+/// Links the C++ standard library, as cc does after compiling C++ sources.
+fn link_cxx_stdlib() {
+    let stdlib = env::var("CXXSTDLIB").unwrap_or_else(|_| {
+        let target = env::var("TARGET").unwrap_or_default();
+        if target.contains("msvc") {
+            String::new()
+        } else if ["apple", "freebsd", "openbsd", "android"]
+            .iter()
+            .any(|os| target.contains(os))
+        {
+            "c++".to_string()
+        } else {
+            "stdc++".to_string()
+        }
+    });
+    if !stdlib.is_empty() {
+        std::println!("cargo:rustc-link-lib={}", stdlib);
+    }
+}
+
+/// Links the CUDA runtime the crate's shim links (`DEP_CUTLASS_CUDART`,
+/// static unless `shared`) from `DEP_CUTLASS_CUDART_DIR`, or else from the
+/// toolkit `nvcc` belongs to, so the process holds a single runtime.
+fn link_cudart(nvcc: &Path) {
+    let lib_dir = env::var_os("DEP_CUTLASS_CUDART_DIR")
+        .map(PathBuf::from)
+        .or_else(|| {
+            let nvcc = if nvcc.components().count() > 1 {
+                Some(nvcc.to_path_buf())
+            } else {
+                env::var_os("PATH").and_then(|path| {
+                    env::split_paths(&path)
+                        .map(|dir| dir.join(nvcc))
+                        .find(|candidate| candidate.is_file())
+                })
+            };
+            let root = env::var_os("CUDA_HOME")
+                .or_else(|| env::var_os("CUDA_PATH"))
+                .map(PathBuf::from)
+                .or_else(|| Some(nvcc?.parent()?.parent()?.to_path_buf()))?;
+            Some(if env::var("CARGO_CFG_WINDOWS").is_ok() {
+                root.join("lib").join("x64")
+            } else {
+                root.join("lib64")
+            })
+        });
+    if let Some(lib_dir) = lib_dir {
+        std::println!("cargo:rustc-link-search=native={}", lib_dir.display());
+    }
+    if env::var("DEP_CUTLASS_CUDART").is_ok_and(|cudart| cudart == "shared") {
+        std::println!("cargo:rustc-link-lib=dylib=cudart");
+    } else {
+        std::println!("cargo:rustc-link-lib=static=cudart_static");
+        if env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "linux") {
+            for lib in ["rt", "pthread", "dl"] {
+                std::println!("cargo:rustc-link-lib=dylib={}", lib);
+            }
+        }
+    }
+}
+
+/// Appends the `.cu` and `.cpp` files under `dir` to `sources`.
+fn collect_sources(dir: &Path, sources: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_sources(&path, sources)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == "cu" || ext == "cpp")
+        {
+            sources.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Kernels declared in a TOML manifest, instantiated by the build script
+/// itself with no Python involved: one translation unit per kernel plus an
+/// `extern "C"` entry point, a C header and Rust declarations for them.
+///
+/// Each `[[gemm]]` table of the manifest (conventionally
+/// `cutlass-kernels.toml`) declares a CUTLASS 2.x tensor-core GEMM,
+/// `cutlass::gemm::device::Gemm`:
+///
+/// ```toml
+/// [[gemm]]
+/// name = "hgemm_128x128"   # the entry point's name
+/// element = "f16"          # A and B: f16, bf16, f32 (3xTF32), f64 or s8
+/// accumulator = "f32"      # default f32; f16 for f16, f64 for f64, s32 for s8
+/// output = "f16"           # C and D, default element (s32 for s8)
+/// layout_a = "row"         # row (default) or column
+/// layout_b = "column"      # s8 needs row-major A and column-major B
+/// tile = [128, 128, 32]    # threadblock tile M, N, K
+/// warp = [64, 64, 32]      # warp tile, same K as the threadblock
+/// stages = 3               # default 3; sm_75 kernels have 2
+/// arch = 80                # 75 or 80 (the default, for sm_80 and newer)
+/// alignment = 8            # elements, default a 128-bit access
+/// ```
+///
+/// The manifest understands this subset of TOML: `[[gemm]]` headers,
+/// `key = value` pairs with string, integer or integer array values, and
+/// `#` comments. Every kernel `name` becomes
+///
+/// ```c
+/// int name(int m, int n, int k,
+///          void const* a, int64_t lda, void const* b, int64_t ldb,
+///          void const* c, int64_t ldc, void* d, int64_t ldd,
+///          Scalar alpha, Scalar beta, cudaStream_t stream);
+/// ```
+///
+/// returning a `cutlass::Status`, with `Scalar` `double` for f64 kernels
+/// and `float` otherwise. [`compile`](Self::compile) builds them into a
+/// static library and writes the Rust declarations to
+/// `OUT_DIR/cutlass_kernels.rs`:
+///
+/// ```ignore
+/// // build.rs
+/// cutlass_sys::build::KernelManifest::from_file("cutlass-kernels.toml")
+///     .unwrap_or_else(|e| panic!("{}", e))
+///     .compile(&cutlass_sys::build::CutlassBuild::new(), "cutlass_kernels");
+///
+/// // lib.rs
+/// include!(concat!(env!("OUT_DIR"), "/cutlass_kernels.rs"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KernelManifest {
+    gemms: Vec<GemmKernel>,
+}
+
+/// One `[[gemm]]` entry of a [`KernelManifest`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GemmKernel {
+    pub name: String,
+    pub element: DataType,
+    pub accumulator: DataType,
+    pub output: DataType,
+    pub layout_a: LayoutTypeID,
+    pub layout_b: LayoutTypeID,
+    pub tile: [i32; 3],
+    pub warp: [i32; 3],
+    pub stages: i32,
+    pub arch: u32,
+    pub alignment: i32,
+}
+
+/// A [`KernelManifest`] that could not be read or describes a kernel that
+/// cannot be instantiated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestError {
+    /// 1-based line of the offending entry, 0 when the file could not be
+    /// read.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "kernel manifest: {}", self.message)
+        } else {
+            write!(f, "kernel manifest line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+fn manifest_error(line: usize, message: String) -> ManifestError {
+    ManifestError { line, message }
+}
+
+/// A `key = value` line of a `[[gemm]]` table and its line number.
+type Entry = (usize, String, Value);
+
+/// A value of the manifest's TOML subset.
+enum Value {
+    Str(String),
+    Int(i64),
+    Ints(Vec<i64>),
+}
+
+impl KernelManifest {
+    /// Reads the manifest at `path` and prints its `rerun-if-changed` line.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ManifestError> {
+        let path = path.as_ref();
+        std::println!("cargo:rerun-if-changed={}", path.display());
+        let text = fs::read_to_string(path)
+            .map_err(|e| manifest_error(0, format!("reading {}: {}", path.display(), e)))?;
+        Self::parse(&text)
+    }
+
+    /// Parses and validates manifest text.
+    pub fn parse(text: &str) -> Result<Self, ManifestError> {
+        let mut gemms = Vec::new();
+        // The open [[gemm]] table: its header line and entries
+        let mut table: Option<(usize, Vec<Entry>)> = None;
+        for (index, line) in text.lines().enumerate() {
+            let number = index + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with('[') {
+                if line.replace(' ', "") != "[[gemm]]" {
+                    return Err(manifest_error(
+                        number,
+                        format!("unsupported table '{}'; only [[gemm]] is known", line),
+                    ));
+                }
+                if let Some((start, entries)) = table.take() {
+                    gemms.push((start, GemmKernel::from_entries(start, entries)?));
+                }
+                table = Some((number, Vec::new()));
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                manifest_error(number, format!("expected 'key = value', found '{}'", line))
+            })?;
+            let (_, entries) = table.as_mut().ok_or_else(|| {
+                manifest_error(number, "key outside a [[gemm]] table".to_string())
+            })?;
+            entries.push((
+                number,
+                key.trim().to_string(),
+                parse_value(number, value.trim())?,
+            ));
+        }
+        if let Some((start, entries)) = table {
+            gemms.push((start, GemmKernel::from_entries(start, entries)?));
+        }
+
+        for (i, (start, gemm)) in gemms.iter().enumerate() {
+            if gemms[..i].iter().any(|(_, other)| other.name == gemm.name) {
+                return Err(manifest_error(
+                    *start,
+                    format!("kernel name '{}' is declared twice", gemm.name),
+                ));
+            }
+        }
+        Ok(Self {
+            gemms: gemms.into_iter().map(|(_, gemm)| gemm).collect(),
+        })
+    }
+
+    /// The declared GEMMs, in manifest order.
+    pub fn gemms(&self) -> &[GemmKernel] {
+        &self.gemms
+    }
+
+    /// Writes `<name>.cu` for every kernel, `cutlass_kernels.h` and
+    /// `cutlass_kernels.rs` into `out_dir`, returning the `.cu` files.
+    ///
+    /// # Panics
+    ///
+    /// If a file cannot be written.
+    pub fn generate(&self, out_dir: &Path) -> Vec<PathBuf> {
+        fs::create_dir_all(out_dir).expect("Failed to create the kernel output directory");
+        let mut header = String::from(
+            "// Generated by cutlass-sys from a kernel manifest; do not edit.\n\
+             #pragma once\n\n\
+             #include <stdint.h>\n\
+             #include <cuda_runtime_api.h>\n\n\
+             #ifdef __cplusplus\nextern \"C\" {\n#endif\n\n",
+        );
+        let mut rust = String::from(
+            "// Generated by cutlass-sys from a kernel manifest; do not edit.\n\n\
+             extern \"C\" {\n",
+        );
+        let mut sources = Vec::new();
+        for gemm in &self.gemms {
+            let source = out_dir.join(format!("{}.cu", gemm.name));
+            fs::write(&source, gemm.source()).expect("Failed to write a generated kernel");
+            sources.push(source);
+
+            let scalar = gemm.scalar();
+            header.push_str(&format!(
+                "// {}\nint {}(int m, int n, int k, void const* a, int64_t lda, void const* b, \
+                 int64_t ldb,\n    void const* c, int64_t ldc, void* d, int64_t ldd, {2} alpha, \
+                 {2} beta, cudaStream_t stream);\n\n",
+                gemm.summary(),
+                gemm.name,
+                scalar
+            ));
+            let scalar = if scalar == "double" { "f64" } else { "f32" };
+            rust.push_str(&format!(
+                "    /// {}\n    pub fn {}(\n",
+                gemm.summary(),
+                gemm.name
+            ));
+            for (parameter, ty) in [
+                ("m", "i32"),
+                ("n", "i32"),
+                ("k", "i32"),
+                ("a", "*const ::core::ffi::c_void"),
+                ("lda", "i64"),
+                ("b", "*const ::core::ffi::c_void"),
+                ("ldb", "i64"),
+                ("c", "*const ::core::ffi::c_void"),
+                ("ldc", "i64"),
+                ("d", "*mut ::core::ffi::c_void"),
+                ("ldd", "i64"),
+                ("alpha", scalar),
+                ("beta", scalar),
+                ("stream", "::cutlass_sys::types::cudaStream_t"),
+            ] {
+                rust.push_str(&format!("        {}: {},\n", parameter, ty));
+            }
+            rust.push_str("    ) -> ::cutlass_sys::types::Status;\n");
+        }
+        header.push_str("#ifdef __cplusplus\n}\n#endif\n");
+        rust.push_str("}\n");
+        fs::write(out_dir.join("cutlass_kernels.h"), header)
+            .expect("Failed to write cutlass_kernels.h");
+        fs::write(out_dir.join("cutlass_kernels.rs"), rust)
+            .expect("Failed to write cutlass_kernels.rs");
+        sources
+    }
+
+    /// Generates the kernels into `OUT_DIR` and compiles them, configured by
+    /// `build`, with the static CUDA runtime into the static library `name`.
+    ///
+    /// # Panics
+    ///
+    /// If a file cannot be written or compiling fails.
+    pub fn compile(&self, build: &CutlassBuild, name: &str) {
+        let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is not set"));
+        let sources = self.generate(&out_dir);
+        let mut cc = cc::Build::new();
+        build.apply_to(&mut cc);
+        compile_cached(&mut cc, name, &sources);
+    }
+}
+
+impl GemmKernel {
+    fn from_entries(start: usize, entries: Vec<Entry>) -> Result<Self, ManifestError> {
+        let mut gemm = Self {
+            name: String::new(),
+            element: DataType::F16,
+            accumulator: DataType::F32,
+            output: DataType::F16,
+            layout_a: LayoutTypeID::RowMajor,
+            layout_b: LayoutTypeID::RowMajor,
+            tile: [0; 3],
+            warp: [0; 3],
+            stages: 3,
+            arch: 80,
+            alignment: 0,
+        };
+        let (mut accumulator, mut output, mut alignment) = (None, None, None);
+        for (line, key, value) in entries {
+            let error = |message: &str| manifest_error(line, format!("{}: {}", key, message));
+            let in_range = |value: i64, low: i64, high: i64| {
+                if (low..=high).contains(&value) {
+                    Ok(value)
+                } else {
+                    Err(error(&format!(
+                        "{} is out of range {}..={}",
+                        value, low, high
+                    )))
+                }
+            };
+            match (key.as_str(), value) {
+                ("name", Value::Str(name)) => {
+                    let identifier = name
+                        .chars()
+                        .next()
+                        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                    if !identifier {
+                        return Err(error("not a C identifier"));
+                    }
+                    gemm.name = name;
+                }
+                ("element", Value::Str(element)) => {
+                    gemm.element =
+                        parse_data_type(&element).ok_or_else(|| error("unknown type"))?;
+                }
+                ("accumulator", Value::Str(element)) => {
+                    accumulator =
+                        Some(parse_data_type(&element).ok_or_else(|| error("unknown type"))?);
+                }
+                ("output", Value::Str(element)) => {
+                    output = Some(parse_data_type(&element).ok_or_else(|| error("unknown type"))?);
+                }
+                ("layout_a", Value::Str(layout)) => {
+                    gemm.layout_a =
+                        parse_layout(&layout).ok_or_else(|| error("expected row or column"))?;
+                }
+                ("layout_b", Value::Str(layout)) => {
+                    gemm.layout_b =
+                        parse_layout(&layout).ok_or_else(|| error("expected row or column"))?;
+                }
+                ("tile", Value::Ints(shape)) => {
+                    gemm.tile = parse_shape(&shape).ok_or_else(|| error("expected [M, N, K]"))?
+                }
+                ("warp", Value::Ints(shape)) => {
+                    gemm.warp = parse_shape(&shape).ok_or_else(|| error("expected [M, N, K]"))?
+                }
+                ("stages", Value::Int(stages)) => gemm.stages = in_range(stages, 1, 16)? as i32,
+                ("arch", Value::Int(arch)) => gemm.arch = in_range(arch, 1, 1000)? as u32,
+                ("alignment", Value::Int(value)) => {
+                    alignment = Some(in_range(value, 1, 64)? as i32)
+                }
+                ("name" | "element" | "accumulator" | "output" | "layout_a" | "layout_b", _) => {
+                    return Err(error("expected a string"))
+                }
+                ("tile" | "warp", _) => return Err(error("expected an array of integers")),
+                ("stages" | "arch" | "alignment", _) => return Err(error("expected an integer")),
+                _ => return Err(error("unknown key")),
+            }
+        }
+        gemm.accumulator = accumulator.unwrap_or(match gemm.element {
+            DataType::F64 => DataType::F64,
+            DataType::S8 => DataType::S32,
+            _ => DataType::F32,
+        });
+        gemm.output = output.unwrap_or(match gemm.element {
+            DataType::S8 => DataType::S32,
+            element => element,
+        });
+        gemm.alignment = alignment.unwrap_or(match gemm.element {
+            DataType::F64 => 1,
+            element => 128 / data_type_bits(element),
+        });
+        gemm.validate()
+            .map_err(|message| manifest_error(start, message))?;
+        Ok(gemm)
+    }
+
+    /// Rejects combinations `cutlass::gemm::device::Gemm` has no kernel for.
+    fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("[[gemm]] without a name".to_string());
+        }
+        let name = &self.name;
+        let valid_types = match self.element {
+            DataType::F16 => {
+                matches!(self.accumulator, DataType::F16 | DataType::F32)
+                    && matches!(self.output, DataType::F16 | DataType::F32)
+            }
+            DataType::BF16 => {
+                self.accumulator == DataType::F32
+                    && matches!(self.output, DataType::BF16 | DataType::F32)
+            }
+            DataType::F32 => self.accumulator == DataType::F32 && self.output == DataType::F32,
+            DataType::F64 => self.accumulator == DataType::F64 && self.output == DataType::F64,
+            DataType::S8 => {
+                self.accumulator == DataType::S32
+                    && matches!(self.output, DataType::S8 | DataType::S32)
+            }
+            _ => false,
+        };
+        if !valid_types {
+            return Err(format!(
+                "{}: no tensor-core GEMM takes {:?} operands with {:?} accumulation \
+                 and {:?} output",
+                name, self.element, self.accumulator, self.output
+            ));
+        }
+        if self.element == DataType::S8
+            && (self.layout_a != LayoutTypeID::RowMajor
+                || self.layout_b != LayoutTypeID::ColumnMajor)
+        {
+            return Err(format!(
+                "{}: s8 GEMMs need row-major A and column-major B",
+                name
+            ));
+        }
+        match self.arch {
+            75 if !matches!(self.element, DataType::F16 | DataType::S8) => {
+                return Err(format!("{}: sm_75 tensor cores take only f16 and s8", name))
+            }
+            75 if self.stages != 2 => return Err(format!("{}: sm_75 kernels have 2 stages", name)),
+            75 => {}
+            arch if arch >= 80 && self.stages >= 2 => {}
+            arch if arch >= 80 => return Err(format!("{}: stages must be at least 2", name)),
+            arch => return Err(format!("{}: arch {} is not 75 or 80 and newer", name, arch)),
+        }
+        if self
+            .tile
+            .iter()
+            .chain(&self.warp)
+            .any(|&extent| extent <= 0)
+        {
+            return Err(format!(
+                "{}: tile and warp need positive [M, N, K] shapes",
+                name
+            ));
+        }
+        if self.tile[0] % self.warp[0] != 0
+            || self.tile[1] % self.warp[1] != 0
+            || self.tile[2] != self.warp[2]
+        {
+            return Err(format!(
+                "{}: the warp tile must divide the threadblock tile in M and N and match its K",
+                name
+            ));
+        }
+        if self.alignment <= 0 || 128 % (self.alignment * data_type_bits(self.element)) != 0 {
+            return Err(format!(
+                "{}: alignment {} is not a power of two of at most 128 bits",
+                name, self.alignment
+            ));
+        }
+        Ok(())
+    }
+
+    /// The type of `alpha` and `beta`.
+    fn scalar(&self) -> &'static str {
+        match self.element {
+            DataType::F64 => "double",
+            _ => "float",
+        }
+    }
+
+    /// One line describing the kernel, for the generated declarations.
+    fn summary(&self) -> String {
+        let layout = |layout: LayoutTypeID| match layout {
+            LayoutTypeID::ColumnMajor => "column",
+            _ => "row",
+        };
+        format!(
+            "{}: {:?} GEMM ({} A, {} B), {:?} accumulation, {:?} output, \
+             {}x{}x{} tile, {} stages, sm_{}",
+            self.name,
+            self.element,
+            layout(self.layout_a),
+            layout(self.layout_b),
+            self.accumulator,
+            self.output,
+            self.tile[0],
+            self.tile[1],
+            self.tile[2],
+            self.stages,
+            self.arch
+        )
+    }
+
+    /// CUDA C++ source of the kernel's translation unit.
+    fn source(&self) -> String {
+        let sm75 = self.arch == 75;
+        let instruction = match (self.element, sm75) {
+            (DataType::F16, true) => "16, 8, 8",
+            (DataType::S8, true) => "8, 8, 16",
+            (DataType::F16 | DataType::BF16, false) => "16, 8, 16",
+            (DataType::F32, _) => "16, 8, 8",
+            (DataType::F64, _) => "8, 8, 4",
+            _ => "16, 8, 32",
+        };
+        let math = match self.element {
+            DataType::F32 => "OpMultiplyAddFastF32",
+            DataType::S8 => "OpMultiplyAddSaturate",
+            _ => "OpMultiplyAdd",
+        };
+        let (epilogue, epilogue_width) = match self.output {
+            DataType::S8 => ("LinearCombinationClamp", 16),
+            DataType::F64 => ("LinearCombination", 1),
+            output => ("LinearCombination", 128 / data_type_bits(output)),
+        };
+        let layout = |layout: LayoutTypeID| match layout {
+            LayoutTypeID::ColumnMajor => "cutlass::layout::ColumnMajor",
+            _ => "cutlass::layout::RowMajor",
+        };
+        let scalar = self.scalar();
+        let compute = match self.element {
+            DataType::F64 => "double",
+            _ => "float",
+        };
+        format!(
+            r#"// Generated by cutlass-sys from a kernel manifest; do not edit.
+// {summary}
+
+#include <cuda_runtime_api.h>
+#include <cutlass/epilogue/thread/{epilogue_header}.h>
+#include <cutlass/gemm/device/gemm.h>
+
+#include <cstdint>
+
+namespace {{
+
+using Gemm = cutlass::gemm::device::Gemm<
+    {element}, {layout_a},
+    {element}, {layout_b},
+    {output}, cutlass::layout::RowMajor,
+    {accumulator},
+    cutlass::arch::OpClassTensorOp, cutlass::arch::{arch},
+    cutlass::gemm::GemmShape<{tbm}, {tbn}, {tbk}>,
+    cutlass::gemm::GemmShape<{wm}, {wn}, {wk}>,
+    cutlass::gemm::GemmShape<{instruction}>,
+    cutlass::epilogue::thread::{epilogue}<{output}, {epilogue_width}, {accumulator}, {compute}>,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+    {stages}, {alignment}, {alignment}, false, cutlass::arch::{math}>;
+
+}}  // namespace
+
+extern "C" int {name}(int m, int n, int k,
+                     void const* a, int64_t lda, void const* b, int64_t ldb,
+                     void const* c, int64_t ldc, void* d, int64_t ldd,
+                     {scalar} alpha, {scalar} beta, cudaStream_t stream) {{
+  typename Gemm::Arguments arguments(
+      {{m, n, k}},
+      {{static_cast<{element} const*>(a), typename Gemm::LayoutA(int(lda))}},
+      {{static_cast<{element} const*>(b), typename Gemm::LayoutB(int(ldb))}},
+      {{static_cast<{output} const*>(c), typename Gemm::LayoutC(int(ldc))}},
+      {{static_cast<{output}*>(d), typename Gemm::LayoutC(int(ldd))}},
+      {{{compute}(alpha), {compute}(beta)}});
+  Gemm gemm;
+  cutlass::Status status = gemm.can_implement(arguments);
+  if (status == cutlass::Status::kSuccess) {{
+    status = gemm.initialize(arguments, nullptr, stream);
+  }}
+  if (status == cutlass::Status::kSuccess) {{
+    status = gemm.run(stream);
+  }}
+  return int(status);
+}}
+"#,
+            summary = self.summary(),
+            epilogue_header = if epilogue == "LinearCombinationClamp" {
+                "linear_combination_clamp"
+            } else {
+                "linear_combination"
+            },
+            element = cpp_type(self.element),
+            output = cpp_type(self.output),
+            accumulator = cpp_type(self.accumulator),
+            layout_a = layout(self.layout_a),
+            layout_b = layout(self.layout_b),
+            arch = if sm75 { "Sm75" } else { "Sm80" },
+            tbm = self.tile[0],
+            tbn = self.tile[1],
+            tbk = self.tile[2],
+            wm = self.warp[0],
+            wn = self.warp[1],
+            wk = self.warp[2],
+            stages = self.stages,
+            alignment = self.alignment,
+            name = self.name,
+        )
+    }
+}
+
+/// `line` without a trailing `#` comment outside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Parses a string, integer or integer array value.
+fn parse_value(line: usize, value: &str) -> Result<Value, ManifestError> {
+    let error = || manifest_error(line, format!("unsupported value '{}'", value));
+    if let Some(string) = value.strip_prefix('"') {
+        let string = string.strip_suffix('"').ok_or_else(error)?;
+        if string.contains(['"', '\\']) {
+            return Err(error());
+        }
+        return Ok(Value::Str(string.to_string()));
+    }
+    if let Some(array) = value.strip_prefix('[') {
+        let array = array.strip_suffix(']').ok_or_else(error)?;
+        let ints = array
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| item.replace('_', "").parse::<i64>().map_err(|_| error()))
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(Value::Ints(ints));
+    }
+    value
+        .replace('_', "")
+        .parse()
+        .map(Value::Int)
+        .map_err(|_| error())
+}
+
+fn parse_data_type(name: &str) -> Option<DataType> {
+    Some(match name {
+        "f16" => DataType::F16,
+        "bf16" => DataType::BF16,
+        "f32" => DataType::F32,
+        "f64" => DataType::F64,
+        "s8" => DataType::S8,
+        "s32" => DataType::S32,
+        _ => return None,
+    })
+}
+
+fn parse_layout(name: &str) -> Option<LayoutTypeID> {
+    match name {
+        "row" => Some(LayoutTypeID::RowMajor),
+        "column" => Some(LayoutTypeID::ColumnMajor),
+        _ => None,
+    }
+}
+
+fn parse_shape(shape: &[i64]) -> Option<[i32; 3]> {
+    match shape {
+        &[m, n, k] => Some([
+            i32::try_from(m).ok()?,
+            i32::try_from(n).ok()?,
+            i32::try_from(k).ok()?,
+        ]),
+        _ => None,
+    }
+}
+
+/// Bits per element of the manifest's types.
+fn data_type_bits(element: DataType) -> i32 {
+    match element {
+        DataType::F16 | DataType::BF16 => 16,
+        DataType::F64 => 64,
+        DataType::S8 => 8,
+        _ => 32,
+    }
+}
+
+fn cpp_type(element: DataType) -> &'static str {
+    match element {
+        DataType::F16 => "cutlass::half_t",
+        DataType::BF16 => "cutlass::bfloat16_t",
+        DataType::F64 => "double",
+        DataType::S8 => "int8_t",
+        DataType::S32 => "int32_t",
+        _ => "float",
+    }
+}
+
+impl Default for CutlassBuild {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// [`CutlassBuild::new`] applied to `build`.
+pub fn apply_to(build: &mut cc::Build) {
+    CutlassBuild::new().apply_to(build);
+}
+
+/// nvcc `-gencode` flags for the targets `archs` lists, in order and without
+/// duplicates.
+///
+/// Each entry is an SM version such as `86`, `sm_86` or `8.6`, and compiles
+/// machine code (SASS) for it. An `a` suffix (`90a`, `sm_100a`) selects the
+/// arch-specific target, whose instructions run only on that exact SM
+/// version. A `compute_` prefix (`compute_90`) embeds PTX only, which the
+/// driver JIT-compiles on load, so it also covers newer devices; a `+PTX`
+/// suffix (`90+PTX`) embeds both.
+///
+/// ```
+/// use cutlass_sys::build::gencode_flags;
+///
+/// assert_eq!(
+///     gencode_flags(&["80", "90a", "compute_90"]),
+///     [
+///         "-gencode=arch=compute_80,code=sm_80",
+///         "-gencode=arch=compute_90a,code=sm_90a",
+///         "-gencode=arch=compute_90,code=compute_90",
+///     ]
+/// );
+/// ```
+///
+/// # Panics
+///
+/// If an entry is not one of these forms.
+pub fn gencode_flags<S: AsRef<str>>(archs: &[S]) -> Vec<String> {
+    let mut flags: Vec<String> = Vec::new();
+    for entry in archs {
+        let entry = entry.as_ref().trim();
+        let (target, sass, ptx) = if let Some(target) = entry.strip_prefix("compute_") {
+            (target, false, true)
+        } else if let Some(target) = entry
+            .strip_suffix("+PTX")
+            .or_else(|| entry.strip_suffix("+ptx"))
+        {
+            (target, true, true)
+        } else {
+            (entry, true, false)
+        };
+        let target = target.strip_prefix("sm_").unwrap_or(target);
+        let (version, suffix) = match target.strip_suffix('a') {
+            Some(version) => (version, "a"),
+            None => (target, ""),
+        };
+        let version: u32 = version.replace('.', "").parse().unwrap_or_else(|_| {
+            panic!(
+                "CUDA arch '{}' is not an SM version such as 86, sm_90a, compute_90 or 90+PTX",
+                entry
+            )
+        });
+        let target = format!("{}{}", version, suffix);
+        let sass = sass.then(|| format!("-gencode=arch=compute_{0},code=sm_{0}", target));
+        let ptx = ptx.then(|| format!("-gencode=arch=compute_{0},code=compute_{0}", target));
+        for flag in [sass, ptx].into_iter().flatten() {
+            if !flags.contains(&flag) {
+                flags.push(flag);
+            }
+        }
+    }
+    flags
+}
+
+/// [`gencode_flags`] of the `CUDA_ARCHS` environment variable, whose entries
+/// are separated by commas, semicolons (as CMake writes them) or spaces, or
+/// `None` when it is unset. Prints its `rerun-if-env-changed` line.
+///
+/// # Panics
+///
+/// If `CUDA_ARCHS` is set but an entry is not a valid target.
+pub fn gencode_flags_from_env() -> Option<Vec<String>> {
+    std::println!("cargo:rerun-if-env-changed=CUDA_ARCHS");
+    let value = env::var("CUDA_ARCHS").ok()?;
+    let archs: Vec<&str> = value
+        .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .filter(|arch| !arch.is_empty())
+        .collect();
+    Some(gencode_flags(&archs))
+}
+
+/// [`CutlassBuild::bindgen_builder`] of [`CutlassBuild::new`].
+#[cfg(feature = "bindgen")]
+pub fn bindgen_builder() -> bindgen::Builder {
+    CutlassBuild::new().bindgen_builder()
+}
+
+/// The `-gencode` target of `arch`: the arch-specific one for SM versions
+/// whose tensor core instructions only exist there.
+fn arch_target(arch: u32) -> String {
+    match arch {
+        90 | 100 => format!("{}a", arch),
+        _ => format!("{}", arch),
+    }
+}
+
+/// Parses `CUTLASS_CUDA_ARCH`, a comma-separated list such as `80,86,89,90`,
+/// into ascending SM versions.
+fn parse_archs(value: &str) -> Vec<u32> {
+    let mut archs: Vec<u32> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|arch| !arch.is_empty())
+        .map(|arch| {
+            arch.trim_start_matches("sm_")
+                .parse::<u32>()
+                .unwrap_or_else(|_| {
+                    panic!(
+                        "CUTLASS_CUDA_ARCH entry '{}' is not an SM version such as 80 or sm_86",
+                        arch
+                    )
+                })
+        })
+        .collect();
+    if archs.is_empty() {
+        panic!("CUTLASS_CUDA_ARCH is set but lists no architectures");
+    }
+    archs.sort_unstable();
+    archs.dedup();
+    archs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A valid f16 kernel named `name`, with `extra` appended to its table.
+    fn gemm(name: &str, extra: &str) -> String {
+        format!(
+            "[[gemm]]\nname = \"{}\"\nelement = \"f16\"\ntile = [128, 128, 32]\n\
+             warp = [64, 64, 32]\n{}",
+            name, extra
+        )
+    }
+
+    fn parse_error(text: &str) -> ManifestError {
+        KernelManifest::parse(text).expect_err(text)
+    }
+
+    #[test]
+    fn parses_defaults() {
+        let manifest = KernelManifest::parse(&gemm("hgemm", "")).unwrap();
+        let hgemm = &manifest.gemms()[0];
+        assert_eq!(hgemm.name, "hgemm");
+        assert_eq!(hgemm.accumulator, DataType::F32);
+        assert_eq!(hgemm.output, DataType::F16);
+        assert_eq!(
+            (hgemm.layout_a, hgemm.layout_b),
+            (LayoutTypeID::RowMajor, LayoutTypeID::RowMajor)
+        );
+        assert_eq!((hgemm.stages, hgemm.arch, hgemm.alignment), (3, 80, 8));
+
+        let text = "[[gemm]]\nname = \"igemm\"\nelement = \"s8\"\nlayout_b = \"column\"\n\
+                    tile = [128, 128, 64]\nwarp = [64, 64, 64]\n\
+                    [[gemm]]\nname = \"dgemm\"\nelement = \"f64\"\ntile = [64, 64, 16]\n\
+                    warp = [32, 32, 16]\n";
+        let manifest = KernelManifest::parse(text).unwrap();
+        let [igemm, dgemm] = manifest.gemms() else {
+            panic!("expected two kernels");
+        };
+        assert_eq!(
+            (igemm.accumulator, igemm.output),
+            (DataType::S32, DataType::S32)
+        );
+        assert_eq!(igemm.alignment, 16);
+        assert_eq!(
+            (dgemm.accumulator, dgemm.output),
+            (DataType::F64, DataType::F64)
+        );
+        assert_eq!(dgemm.alignment, 1);
+        assert_eq!(dgemm.scalar(), "double");
+    }
+
+    #[test]
+    fn comments_end_outside_strings() {
+        let text = gemm("k", "# a comment\nstages = 4 # trailing\n  \n");
+        assert_eq!(KernelManifest::parse(&text).unwrap().gemms()[0].stages, 4);
+
+        // A `#` inside a string is part of it, not a comment
+        let error = parse_error("[[gemm]]\nname = \"a#b\" # \"quoted\" comment\n");
+        assert_eq!(error.line, 2);
+        assert_eq!(error.message, "name: not a C identifier");
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_tables() {
+        let error = parse_error(&gemm("k", "epilogue = \"relu\"\n"));
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (6, "epilogue: unknown key")
+        );
+
+        let error = parse_error("[conv]\nname = \"k\"\n");
+        assert_eq!(error.line, 1);
+        assert!(error.message.starts_with("unsupported table '[conv]'"));
+
+        let error = parse_error("name = \"k\"\n");
+        assert_eq!(error.message, "key outside a [[gemm]] table");
+
+        let error = parse_error(&gemm("k", "stages = \"3\"\n"));
+        assert_eq!(error.message, "stages: expected an integer");
+        let error = parse_error(&gemm("k", "tile = 128\n"));
+        assert_eq!(error.message, "tile: expected an array of integers");
+        let error = parse_error(&gemm("k", "stages\n"));
+        assert_eq!(error.message, "expected 'key = value', found 'stages'");
+        let error = parse_error(&gemm("k", "output = \"f16\n"));
+        assert_eq!(error.message, "unsupported value '\"f16'");
+    }
+
+    #[test]
+    fn rejects_duplicate_names() {
+        let text = gemm("k", "") + &gemm("other", "") + &gemm("k", "");
+        let error = parse_error(&text);
+        assert_eq!(error.line, 11);
+        assert_eq!(error.message, "kernel name 'k' is declared twice");
+    }
+
+    #[test]
+    fn rejects_out_of_range_integers() {
+        for (extra, message) in [
+            ("stages = 17", "stages: 17 is out of range 1..=16"),
+            ("stages = 0", "stages: 0 is out of range 1..=16"),
+            ("arch = 5000", "arch: 5000 is out of range 1..=1000"),
+            ("arch = -80", "arch: -80 is out of range 1..=1000"),
+            ("alignment = 0", "alignment: 0 is out of range 1..=64"),
+            ("alignment = 128", "alignment: 128 is out of range 1..=64"),
+        ] {
+            let error = parse_error(&gemm("k", extra));
+            assert_eq!((error.line, error.message.as_str()), (6, message));
+        }
+        let error = parse_error(&gemm("k", "").replace("128, 128", "4_294_967_296, 128"));
+        assert_eq!(error.message, "tile: expected [M, N, K]");
+    }
+
+    #[test]
+    fn validate_rejects_impossible_kernels() {
+        for (text, message) in [
+            (
+                "[[gemm]]\nelement = \"f16\"\n".to_string(),
+                "[[gemm]] without a name",
+            ),
+            (
+                gemm("k", "accumulator = \"f16\"\n").replace("f16\"\ntile", "bf16\"\ntile"),
+                "k: no tensor-core GEMM takes BF16 operands with F16 accumulation and BF16 \
+                 output",
+            ),
+            (
+                gemm("k", "").replace("f16", "s8"),
+                "k: s8 GEMMs need row-major A and column-major B",
+            ),
+            (
+                gemm("k", "arch = 75\nstages = 2\n").replace("f16", "bf16"),
+                "k: sm_75 tensor cores take only f16 and s8",
+            ),
+            (gemm("k", "arch = 75\n"), "k: sm_75 kernels have 2 stages"),
+            (gemm("k", "stages = 1\n"), "k: stages must be at least 2"),
+            (
+                gemm("k", "arch = 70\n"),
+                "k: arch 70 is not 75 or 80 and newer",
+            ),
+            (
+                gemm("k", "").replace("[64, 64, 32]", "[0, 64, 32]"),
+                "k: tile and warp need positive [M, N, K] shapes",
+            ),
+            (
+                gemm("k", "").replace("[64, 64, 32]", "[48, 64, 32]"),
+                "k: the warp tile must divide the threadblock tile in M and N and match its K",
+            ),
+            (
+                gemm("k", "").replace("[64, 64, 32]", "[64, 64, 16]"),
+                "k: the warp tile must divide the threadblock tile in M and N and match its K",
+            ),
+            (
+                gemm("k", "alignment = 16\n"),
+                "k: alignment 16 is not a power of two of at most 128 bits",
+            ),
+            (
+                gemm("k", "alignment = 3\n"),
+                "k: alignment 3 is not a power of two of at most 128 bits",
+            ),
+        ] {
+            let error = parse_error(&text);
+            assert_eq!(
+                (error.line, error.message.as_str()),
+                (1, message),
+                "{}",
+                text
+            );
+        }
+    }
+}
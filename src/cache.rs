@@ -0,0 +1,15 @@
+//! The on-disk cache directory shared by the `jit` and `autotune` features.
+
+use std::env;
+use std::path::PathBuf;
+
+/// `cutlass-sys` under the user cache directory: `XDG_CACHE_HOME`, then
+/// `LOCALAPPDATA`, then `~/.cache`, then the temp directory.
+pub(crate) fn user_cache_dir() -> PathBuf {
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(env::temp_dir)
+        .join("cutlass-sys")
+}
@@ -0,0 +1,123 @@
+//! Per-device handle passed to every shim entry point.
+//!
+//! The shim keeps no global state: everything it needs to know about the
+//! device a launch targets travels in a [`CutlassContext`]. Each entry point
+//! makes the context's device current for the duration of the call and
+//! restores the calling thread's previous device before returning, so one
+//! thread can drive several GPUs, and several threads can share a context,
+//! without coordinating `cudaSetDevice` calls.
+//!
+//! Streams and workspaces passed alongside a context must belong to its
+//! device.
+//!
+//! The shim is compiled for every SM version selected by the `sm*` features
+//! or listed in `CUTLASS_CUDA_ARCH` (e.g. `80,86,89,90`) plus PTX for the
+//! newest of them, and the CUDA runtime picks the matching code for the
+//! device at launch, so one binary serves A100, L40 and H100 alike. A device
+//! none of the compiled code can run on fails every entry point with
+//! [`Status::ErrorArchMismatch`]; [`CutlassContext::check_arch`] reports that
+//! up front. Turing (sm_75) devices only run GEMM, GEMV, reductions and
+//! transforms, and GEMM only for `F16` and `S8` without a fused epilogue.
+
+use crate::types::Status;
+
+/// A CUDA device and the properties the shim's kernels depend on.
+///
+/// Build one with [`CutlassContext::new`]. A null context pointer makes an
+/// entry point run on the calling thread's current device instead.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CutlassContext {
+    /// CUDA device ordinal.
+    pub device: i32,
+    /// Number of SMs, which sizes Stream-K grids and their workspaces.
+    pub sm_count: i32,
+    pub cc_major: i32,
+    pub cc_minor: i32,
+}
+
+impl CutlassContext {
+    /// Queries the properties of device `device`. An ordinal that does not
+    /// name a device returns [`Status::ErrorInvalidProblem`].
+    pub fn new(device: i32) -> Result<Self, Status> {
+        let mut context = Self {
+            device,
+            sm_count: 0,
+            cc_major: 0,
+            cc_minor: 0,
+        };
+        // SAFETY: `context` is a valid, writable `CutlassContext`.
+        unsafe { cutlass_sys_context_init(device, &mut context) }.result()?;
+        Ok(context)
+    }
+
+    /// Compute capability as `major * 10 + minor`, e.g. `80` for an A100.
+    pub const fn compute_capability(&self) -> i32 {
+        self.cc_major * 10 + self.cc_minor
+    }
+
+    /// [`Status::ErrorArchMismatch`] unless the shim carries code of its
+    /// SM80-class kernels, which every operation but GEMM, GEMV, reductions
+    /// and transforms needs, for this device: SASS for an arch of the same
+    /// major version and no newer minor, or the PTX of the newest arch in
+    /// [`compiled_archs`] for a later device.
+    pub fn check_arch(&self) -> Result<(), Status> {
+        // SAFETY: only reads `self`.
+        unsafe { cutlass_sys_context_check_arch(self) }.result()
+    }
+}
+
+/// SM versions the shim was compiled for, ascending, e.g. `[80, 86, 90]`.
+pub fn compiled_archs() -> &'static [i32] {
+    let mut archs = core::ptr::null();
+    // SAFETY: the shim points `archs` at a static array of the returned
+    // length.
+    unsafe {
+        let len = cutlass_sys_compiled_archs(&mut archs);
+        core::slice::from_raw_parts(archs, len)
+    }
+}
+
+/// The oldest CUDA toolkit the shim's CUTLASS version and compiled archs
+/// build with, in the `CUDA_VERSION` encoding (`major * 1000 + minor * 10`),
+/// e.g. `12000` once sm_90 is among them.
+pub const MIN_CUDA_VERSION: i32 = parse_env(env!("CUTLASS_SYS_MIN_CUDA_VERSION"));
+
+/// The C++ standard CUTLASS needs of code including it: `17` from CUTLASS
+/// 3.0 on.
+pub const CXX_STANDARD: i32 = parse_env(env!("CUTLASS_SYS_CXX_STANDARD"));
+
+/// The oldest driver the shim runs on, as `cudaDriverGetVersion` reports it
+/// (e.g. `12000` for any CUDA 12 driver). The CUDA runtime is linked
+/// statically, so a driver of the same major release as the toolkit the shim
+/// was built with suffices; JIT-compiling its PTX for a device newer than the
+/// compiled archs needs one at least as new as that toolkit.
+pub const MIN_DRIVER_VERSION: i32 = parse_env(env!("CUTLASS_SYS_MIN_DRIVER_VERSION"));
+
+/// The decimal number the build script exported.
+const fn parse_env(value: &str) -> i32 {
+    let bytes = value.as_bytes();
+    let mut parsed = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(
+            bytes[i].is_ascii_digit(),
+            "build script exported a non-number"
+        );
+        parsed = parsed * 10 + (bytes[i] - b'0') as i32;
+        i += 1;
+    }
+    parsed
+}
+
+extern "C" {
+    /// Fill `context` with the properties of device `device`.
+    pub fn cutlass_sys_context_init(device: i32, context: *mut CutlassContext) -> Status;
+
+    /// Point `archs` at the compiled SM versions and return their count.
+    pub fn cutlass_sys_compiled_archs(archs: *mut *const i32) -> usize;
+
+    /// Whether the shim carries code of its SM80-class kernels for the device
+    /// of `context`; a null context checks the current device.
+    pub fn cutlass_sys_context_check_arch(context: *const CutlassContext) -> Status;
+}
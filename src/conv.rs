@@ -0,0 +1,521 @@
+//! Implicit-GEMM convolution over NHWC and NDHWC tensors.
+//!
+//! One [`ConvConfig`] describes both ranks; a 2-D convolution is a 3-D one
+//! with unit depth (`d == t == 1`, `pad_d == 0`), and the `conv2d` entry
+//! points reject anything else with [`Status::ErrorInvalidProblem`].
+//!
+//! Tensors are packed NDHWC: activations are `N x D x H x W x C`, filters are
+//! `K x T x R x S x C` and outputs are `N x Z x P x Q x K`, where `Z` is given
+//! by [`ConvConfig::output_depth`] and `P x Q` by [`ConvConfig::output_size`].
+//! For 2-D the depth dimension drops out, leaving NHWC. The element types
+//! follow [`DataType`]: int8 convolutions accumulate into and write `i32`.
+//! Only 2-D fprop is available for `S8`; everything else returns
+//! [`Status::ErrorInvalidDataType`] for it.
+//!
+//! Every direction computes `D = alpha * conv(A, B) + beta * C` with the
+//! operands of [`ConvOperands`] bound as follows:
+//!
+//! | direction | `a`         | `b`        | `c` / `d`       |
+//! |-----------|-------------|------------|-----------------|
+//! | fprop     | activation  | filter     | output          |
+//! | dgrad     | output grad | filter     | activation grad |
+//! | wgrad     | output grad | activation | filter grad     |
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::coord::Tensor4DCoord;
+use crate::error::CutlassError;
+use crate::library::NumericTypeID;
+use crate::tensor::TensorView;
+use crate::types::{cudaStream_t, DataType, SplitKMode, Status};
+
+/// Direction of a convolution, as `cutlass::conv::Operator`. Selects which
+/// tensor each of [`ConvOperands`] is in [`ConvConfig::bind_views`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConvOperator {
+    Fprop,
+    Dgrad,
+    Wgrad,
+}
+
+/// Whether the filter is flipped, as in the mathematical definition of
+/// convolution. Deep learning frameworks use cross-correlation.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ConvMode {
+    #[default]
+    CrossCorrelation = 0,
+    Convolution = 1,
+}
+
+/// Problem description of a 2-D or 3-D convolution.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConvConfig {
+    pub n: i32,
+    pub d: i32,
+    pub h: i32,
+    pub w: i32,
+    pub c: i32,
+    pub k: i32,
+    pub t: i32,
+    pub r: i32,
+    pub s: i32,
+    pub pad_d: i32,
+    pub pad_h: i32,
+    pub pad_w: i32,
+    pub stride_d: i32,
+    pub stride_h: i32,
+    pub stride_w: i32,
+    pub dilation_d: i32,
+    pub dilation_h: i32,
+    pub dilation_w: i32,
+    /// Number of channel groups; `C` and `K` must both be divisible by it.
+    /// Each group convolves `C / groups` input channels into `K / groups`
+    /// output channels, so filters are `K x R x S x (C / groups)`.
+    /// `groups == C == K` selects the depthwise kernels. Only 2-D fprop
+    /// supports `groups != 1`.
+    pub groups: i32,
+    pub mode: ConvMode,
+    pub element: DataType,
+    pub alpha: f32,
+    pub beta: f32,
+    /// Number of partitions of the GEMM-K dimension (`T * R * S * C` for
+    /// fprop, `T * R * S * K` for dgrad, `N * Z * P * Q` for wgrad).
+    pub split_k_slices: i32,
+    /// [`SplitKMode::Parallel`] is only supported by wgrad.
+    pub split_k_mode: SplitKMode,
+    /// Check `D` against the device reference after the launch, as for
+    /// [`GemmConfig::verify`](crate::gemm::GemmConfig::verify).
+    pub verify: bool,
+}
+
+impl ConvConfig {
+    /// An unpadded, unit-stride, undilated 2-D cross-correlation of an
+    /// `n x h x w x c` activation with `k` filters of `r x s x c`.
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(
+        n: i32,
+        h: i32,
+        w: i32,
+        c: i32,
+        k: i32,
+        r: i32,
+        s: i32,
+        element: DataType,
+    ) -> Self {
+        Self::new_3d(n, 1, h, w, c, k, 1, r, s, element)
+    }
+
+    /// An unpadded, unit-stride, undilated 3-D cross-correlation of an
+    /// `n x d x h x w x c` activation with `k` filters of `t x r x s x c`.
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new_3d(
+        n: i32,
+        d: i32,
+        h: i32,
+        w: i32,
+        c: i32,
+        k: i32,
+        t: i32,
+        r: i32,
+        s: i32,
+        element: DataType,
+    ) -> Self {
+        Self {
+            n,
+            d,
+            h,
+            w,
+            c,
+            k,
+            t,
+            r,
+            s,
+            pad_d: 0,
+            pad_h: 0,
+            pad_w: 0,
+            stride_d: 1,
+            stride_h: 1,
+            stride_w: 1,
+            dilation_d: 1,
+            dilation_h: 1,
+            dilation_w: 1,
+            groups: 1,
+            mode: ConvMode::CrossCorrelation,
+            element,
+            alpha: 1.0,
+            beta: 0.0,
+            split_k_slices: 1,
+            split_k_mode: SplitKMode::Serial,
+            verify: false,
+        }
+    }
+
+    /// Depth `z` of the output; 1 for a 2-D convolution.
+    pub const fn output_depth(&self) -> i32 {
+        (self.d + 2 * self.pad_d - self.dilation_d * (self.t - 1) - 1) / self.stride_d + 1
+    }
+
+    /// Spatial extent `(p, q)` of the output.
+    pub const fn output_size(&self) -> (i32, i32) {
+        let p = (self.h + 2 * self.pad_h - self.dilation_h * (self.r - 1) - 1) / self.stride_h + 1;
+        let q = (self.w + 2 * self.pad_w - self.dilation_w * (self.s - 1) - 1) / self.stride_w + 1;
+        (p, q)
+    }
+
+    /// Sets the tensor extents of a 2-D convolution from NHWC views of the
+    /// operands and returns their pointers, ready for the `conv2d` entry
+    /// point of `operator`. Padding, stride, dilation and `groups` are kept
+    /// and determine the output extent `d` (and `c`) must have.
+    ///
+    /// Views that are not packed NHWC, disagreeing extents and a config with
+    /// depth return the errors of [`TensorView::validate`] and
+    /// [`Status::ErrorInvalidProblem`]; debug builds also reject null and
+    /// misaligned pointers (see [`TensorView::check_pointer`]).
+    pub fn bind_views<T, U>(
+        &mut self,
+        operator: ConvOperator,
+        a: TensorView<T, Tensor4DCoord>,
+        b: TensorView<T, Tensor4DCoord>,
+        c: Option<TensorView<U, Tensor4DCoord>>,
+        d: TensorView<U, Tensor4DCoord>,
+    ) -> Result<ConvOperands, Status> {
+        if self.d != 1 || self.t != 1 || self.pad_d != 0 || self.groups < 1 {
+            return Err(Status::ErrorInvalidProblem);
+        }
+        let (activation, filter) = match operator {
+            ConvOperator::Fprop => (a.extent, b.extent),
+            ConvOperator::Dgrad => (d.extent, b.extent),
+            ConvOperator::Wgrad => (b.extent, d.extent),
+        };
+        if filter.c * self.groups != activation.c {
+            return Err(Status::ErrorInvalidProblem);
+        }
+
+        let mut config = *self;
+        config.n = activation.n;
+        config.h = activation.h;
+        config.w = activation.w;
+        config.c = activation.c;
+        config.k = filter.n;
+        config.r = filter.h;
+        config.s = filter.w;
+        let (p, q) = config.output_size();
+        let output = Tensor4DCoord::new(config.n, p, q, config.k);
+        let (a_extent, b_extent, d_extent) = match operator {
+            ConvOperator::Fprop => (activation, filter, output),
+            ConvOperator::Dgrad => (output, filter, activation),
+            ConvOperator::Wgrad => (output, activation, filter),
+        };
+
+        let element_bytes = NumericTypeID::from(self.element).size_in_bits() as usize / 8;
+        let output_bytes = match self.element {
+            DataType::S8 => 4,
+            _ => element_bytes,
+        };
+        check_operand(&a, a_extent, element_bytes)?;
+        check_operand(&b, b_extent, element_bytes)?;
+        if let Some(c) = &c {
+            check_operand(c, d_extent, output_bytes)?;
+        }
+        check_operand(&d, d_extent, output_bytes)?;
+
+        *self = config;
+        Ok(ConvOperands {
+            a: a.ptr().cast_const().cast(),
+            b: b.ptr().cast_const().cast(),
+            c: c.map_or(core::ptr::null(), |c| c.ptr().cast_const().cast()),
+            d: d.ptr().cast(),
+        })
+    }
+
+    /// Bytes of device workspace the `conv2d` entry point of `operator`
+    /// needs for this config.
+    pub fn conv2d_workspace_size(&self, context: &CutlassContext, operator: ConvOperator) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe {
+            match operator {
+                ConvOperator::Fprop => cutlass_sys_conv2d_fprop_workspace_size(context, self),
+                ConvOperator::Dgrad => cutlass_sys_conv2d_dgrad_workspace_size(context, self),
+                ConvOperator::Wgrad => cutlass_sys_conv2d_wgrad_workspace_size(context, self),
+            }
+        }
+    }
+
+    /// Checks that the `conv2d` entry point of `operator` can run this config
+    /// before any operand is allocated, returning the status the launch would
+    /// fail with.
+    pub fn conv2d_supports(
+        &self,
+        context: &CutlassContext,
+        operator: ConvOperator,
+    ) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe {
+            match operator {
+                ConvOperator::Fprop => cutlass_sys_conv2d_fprop_can_implement(context, self),
+                ConvOperator::Dgrad => cutlass_sys_conv2d_dgrad_can_implement(context, self),
+                ConvOperator::Wgrad => cutlass_sys_conv2d_wgrad_can_implement(context, self),
+            }
+        }
+        .result()
+    }
+
+    /// Launches the `conv2d` entry point of `operator` on `stream`.
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address packed NHWC (NDHWC) tensors
+    /// in device memory of `context`'s device, bound as in the
+    /// [module docs](crate::conv), and stay valid until the launch completes on
+    /// `stream`, a stream of that device. `workspace` must hold
+    /// [`conv2d_workspace_size`](Self::conv2d_workspace_size) bytes of device
+    /// memory for `operator`, or may be null when that is zero.
+    pub unsafe fn conv2d(
+        &self,
+        context: &CutlassContext,
+        operator: ConvOperator,
+        operands: &ConvOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        let launch = match operator {
+            ConvOperator::Fprop => cutlass_sys_conv2d_fprop,
+            ConvOperator::Dgrad => cutlass_sys_conv2d_dgrad,
+            ConvOperator::Wgrad => cutlass_sys_conv2d_wgrad,
+        };
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe { launch(context, self, operands, workspace, stream) })
+    }
+
+    /// Bytes of device workspace the `conv3d` entry point of `operator`
+    /// needs for this config.
+    pub fn conv3d_workspace_size(&self, context: &CutlassContext, operator: ConvOperator) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe {
+            match operator {
+                ConvOperator::Fprop => cutlass_sys_conv3d_fprop_workspace_size(context, self),
+                ConvOperator::Dgrad => cutlass_sys_conv3d_dgrad_workspace_size(context, self),
+                ConvOperator::Wgrad => cutlass_sys_conv3d_wgrad_workspace_size(context, self),
+            }
+        }
+    }
+
+    /// Checks that the `conv3d` entry point of `operator` can run this config
+    /// before any operand is allocated, returning the status the launch would
+    /// fail with.
+    pub fn conv3d_supports(
+        &self,
+        context: &CutlassContext,
+        operator: ConvOperator,
+    ) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe {
+            match operator {
+                ConvOperator::Fprop => cutlass_sys_conv3d_fprop_can_implement(context, self),
+                ConvOperator::Dgrad => cutlass_sys_conv3d_dgrad_can_implement(context, self),
+                ConvOperator::Wgrad => cutlass_sys_conv3d_wgrad_can_implement(context, self),
+            }
+        }
+        .result()
+    }
+
+    /// Launches the `conv3d` entry point of `operator` on `stream`.
+    ///
+    /// # Safety
+    ///
+    /// As for [`conv2d`](Self::conv2d), with the workspace of
+    /// [`conv3d_workspace_size`](Self::conv3d_workspace_size).
+    pub unsafe fn conv3d(
+        &self,
+        context: &CutlassContext,
+        operator: ConvOperator,
+        operands: &ConvOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        let launch = match operator {
+            ConvOperator::Fprop => cutlass_sys_conv3d_fprop,
+            ConvOperator::Dgrad => cutlass_sys_conv3d_dgrad,
+            ConvOperator::Wgrad => cutlass_sys_conv3d_wgrad,
+        };
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe { launch(context, self, operands, workspace, stream) })
+    }
+}
+
+fn check_operand<T>(
+    view: &TensorView<T, Tensor4DCoord>,
+    extent: Tensor4DCoord,
+    alignment: usize,
+) -> Result<(), Status> {
+    view.validate()?;
+    if view.extent != extent {
+        return Err(Status::ErrorInvalidProblem);
+    }
+    view.check_pointer(alignment)
+}
+
+/// Device pointers of a convolution launch; see the module docs for how they
+/// map to each direction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ConvOperands {
+    pub a: *const c_void,
+    pub b: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_conv2d_fprop`] needs.
+    pub fn cutlass_sys_conv2d_fprop_workspace_size(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_conv2d_fprop`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_conv2d_fprop_can_implement(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+    ) -> Status;
+
+    /// Launch a forward-propagation convolution on `stream`. Grouped and
+    /// depthwise problems (`groups != 1`) dispatch to dedicated kernels, for
+    /// the floating-point types only.
+    pub fn cutlass_sys_conv2d_fprop(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+        operands: *const ConvOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_conv2d_dgrad`] needs.
+    pub fn cutlass_sys_conv2d_dgrad_workspace_size(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_conv2d_dgrad`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_conv2d_dgrad_can_implement(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+    ) -> Status;
+
+    /// Launch a data-gradient convolution on `stream`, computing the gradient
+    /// of the activation from the gradient of the output. Unit-stride and
+    /// strided problems dispatch to separately specialized kernels.
+    pub fn cutlass_sys_conv2d_dgrad(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+        operands: *const ConvOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_conv2d_wgrad`] needs. With
+    /// [`SplitKMode::Parallel`] this includes the partial accumulators.
+    pub fn cutlass_sys_conv2d_wgrad_workspace_size(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_conv2d_wgrad`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_conv2d_wgrad_can_implement(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+    ) -> Status;
+
+    /// Launch a weight-gradient convolution on `stream`, computing the
+    /// gradient of the filter from the gradient of the output and the
+    /// activation. With [`SplitKMode::Parallel`] this enqueues the
+    /// convolution followed by the split-K reduction.
+    pub fn cutlass_sys_conv2d_wgrad(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+        operands: *const ConvOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_conv3d_fprop`] needs.
+    pub fn cutlass_sys_conv3d_fprop_workspace_size(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_conv3d_fprop`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_conv3d_fprop_can_implement(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+    ) -> Status;
+
+    /// Launch a 3-D forward-propagation convolution on `stream`.
+    pub fn cutlass_sys_conv3d_fprop(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+        operands: *const ConvOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_conv3d_dgrad`] needs.
+    pub fn cutlass_sys_conv3d_dgrad_workspace_size(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_conv3d_dgrad`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_conv3d_dgrad_can_implement(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+    ) -> Status;
+
+    /// Launch a 3-D data-gradient convolution on `stream`. Strided problems
+    /// use the slower analytic iterators.
+    pub fn cutlass_sys_conv3d_dgrad(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+        operands: *const ConvOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_conv3d_wgrad`] needs. With
+    /// [`SplitKMode::Parallel`] this includes the partial accumulators.
+    pub fn cutlass_sys_conv3d_wgrad_workspace_size(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_conv3d_wgrad`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_conv3d_wgrad_can_implement(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+    ) -> Status;
+
+    /// Launch a 3-D weight-gradient convolution on `stream`.
+    pub fn cutlass_sys_conv3d_wgrad(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+        operands: *const ConvOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
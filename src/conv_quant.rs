@@ -0,0 +1,140 @@
+//! Quantized int8 2-D forward convolution over NHWC tensors, with the
+//! requantizing epilogue of TensorRT-style int8 inference:
+//!
+//! ```text
+//! D[n, p, q, k] = clamp(round(scale[k] * acc + bias[k]), clamp_min, clamp_max)
+//! acc           = conv(A, B)[n, p, q, k]      (i8 x i8, accumulated in i32)
+//! ```
+//!
+//! `A` and the filters `B` are `S8`, as is the output `D`, so a chain of
+//! quantized layers never leaves int8 between kernels. `scale` and `bias`
+//! are length-`K` `f32` vectors: fold the input, per-channel weight and
+//! output quantization scales into `scale` (`s_in * s_w[k] / s_out`) and
+//! express `bias` in output units. Rounding is to nearest even; the clamp
+//! saturates to `[clamp_min, clamp_max]`, so `clamp_min = 0` fuses a ReLU.
+//!
+//! Tensors are packed NHWC as in [`conv`](crate::conv). `C` and `K` must be
+//! multiples of 16 (128-bit accesses), otherwise
+//! [`Status::ErrorMisalignedOperand`] is returned.
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::conv::ConvConfig;
+use crate::error::CutlassError;
+use crate::types::{cudaStream_t, Status};
+
+/// Problem description of a quantized convolution.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConvQuantConfig {
+    /// The convolution, with [`DataType::S8`](crate::types::DataType::S8)
+    /// elements, one group and no split-K. The epilogue is the one of the
+    /// module docs: `alpha` is ignored, and `beta` and `verify` must keep
+    /// their defaults.
+    pub conv: ConvConfig,
+    pub clamp_min: f32,
+    pub clamp_max: f32,
+}
+
+impl ConvQuantConfig {
+    /// `conv`, saturating to the full `i8` range.
+    pub const fn new(conv: ConvConfig) -> Self {
+        Self {
+            conv,
+            clamp_min: i8::MIN as f32,
+            clamp_max: i8::MAX as f32,
+        }
+    }
+
+    /// `conv` followed by a ReLU: saturates to `[0, 127]`.
+    pub const fn relu(conv: ConvConfig) -> Self {
+        Self {
+            conv,
+            clamp_min: 0.0,
+            clamp_max: i8::MAX as f32,
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_conv2d_fprop_quant`] needs for
+    /// this config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_conv2d_fprop_quant_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_conv2d_fprop_quant`] can run this config
+    /// before any operand is allocated, returning the status the launch would
+    /// fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_conv2d_fprop_quant_can_implement(context, self) }.result()
+    }
+
+    /// Launches the convolution on `stream` through
+    /// [`cutlass_sys_conv2d_fprop_quant`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address packed NHWC tensors and
+    /// length-`K` vectors in device memory of `context`'s device and stay
+    /// valid until the launch completes on `stream`, a stream of that device.
+    /// `workspace` must hold [`workspace_size`](Self::workspace_size) bytes
+    /// of device memory, or may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &ConvQuantOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_conv2d_fprop_quant(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers of a quantized convolution launch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ConvQuantOperands {
+    /// `N x H x W x C` activation.
+    pub a: *const c_void,
+    /// `K x R x S x C` filters.
+    pub b: *const c_void,
+    /// `N x P x Q x K` output.
+    pub d: *mut c_void,
+    /// Per-output-channel scale; null scales by 1.
+    pub scale: *const f32,
+    /// Per-output-channel bias; null adds nothing.
+    pub bias: *const f32,
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_conv2d_fprop_quant`] needs.
+    pub fn cutlass_sys_conv2d_fprop_quant_workspace_size(
+        context: *const CutlassContext,
+        config: *const ConvQuantConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_conv2d_fprop_quant`] can run `config`, checked
+    /// without touching any operand: the status the launch would fail with,
+    /// or [`Status::Success`].
+    pub fn cutlass_sys_conv2d_fprop_quant_can_implement(
+        context: *const CutlassContext,
+        config: *const ConvQuantConfig,
+    ) -> Status;
+
+    /// Launch a quantized int8 convolution on `stream`.
+    ///
+    /// A non-`S8` element returns [`Status::ErrorInvalidDataType`]; groups,
+    /// split-K, `beta != 0` or `verify` return [`Status::ErrorNotSupported`].
+    pub fn cutlass_sys_conv2d_fprop_quant(
+        context: *const CutlassContext,
+        config: *const ConvQuantConfig,
+        operands: *const ConvQuantOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
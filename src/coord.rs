@@ -0,0 +1,141 @@
+//! Coordinate and extent types laid out like CUTLASS's `cutlass::Coord<N, int>`
+//! specializations.
+//!
+//! | Rust              | CUTLASS                    | C header                       |
+//! |-------------------|----------------------------|--------------------------------|
+//! | [`GemmCoord`]     | `cutlass::gemm::GemmCoord` | `cutlass_sys_gemm_coord_t`     |
+//! | [`MatrixCoord`]   | `cutlass::MatrixCoord`     | `cutlass_sys_matrix_coord_t`   |
+//! | [`Tensor4DCoord`] | `cutlass::Tensor4DCoord`   | `cutlass_sys_tensor4d_coord_t` |
+//!
+//! Arithmetic is elementwise, as in CUTLASS, and [`product`](GemmCoord::product)
+//! gives the number of points covered by an extent.
+
+use core::ops::{Add, Div, Mul, Sub};
+
+/// Size of a GEMM problem, `M x N x K`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GemmCoord {
+    pub m: i32,
+    pub n: i32,
+    pub k: i32,
+}
+
+impl GemmCoord {
+    pub const fn new(m: i32, n: i32, k: i32) -> Self {
+        Self { m, n, k }
+    }
+
+    /// `M x K`, the extent of `A`.
+    pub const fn mk(self) -> MatrixCoord {
+        MatrixCoord::new(self.m, self.k)
+    }
+
+    /// `K x N`, the extent of `B`.
+    pub const fn kn(self) -> MatrixCoord {
+        MatrixCoord::new(self.k, self.n)
+    }
+
+    /// `M x N`, the extent of `C` and `D`.
+    pub const fn mn(self) -> MatrixCoord {
+        MatrixCoord::new(self.m, self.n)
+    }
+
+    /// Number of multiply-adds, `M * N * K`.
+    pub const fn product(self) -> i64 {
+        self.m as i64 * self.n as i64 * self.k as i64
+    }
+}
+
+/// A `(row, column)` position or matrix extent.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MatrixCoord {
+    pub row: i32,
+    pub column: i32,
+}
+
+impl MatrixCoord {
+    pub const fn new(row: i32, column: i32) -> Self {
+        Self { row, column }
+    }
+
+    /// Number of elements of a matrix of this extent.
+    pub const fn product(self) -> i64 {
+        self.row as i64 * self.column as i64
+    }
+}
+
+/// An `(n, h, w, c)` position or activation tensor extent. The order is
+/// logical; the storage order is up to the layout using it.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Tensor4DCoord {
+    pub n: i32,
+    pub h: i32,
+    pub w: i32,
+    pub c: i32,
+}
+
+impl Tensor4DCoord {
+    pub const fn new(n: i32, h: i32, w: i32, c: i32) -> Self {
+        Self { n, h, w, c }
+    }
+
+    /// Number of elements of a tensor of this extent.
+    pub const fn product(self) -> i64 {
+        self.n as i64 * self.h as i64 * self.w as i64 * self.c as i64
+    }
+}
+
+macro_rules! impl_coord {
+    ($ty:ident, $n:literal, $($field:ident),+) => {
+        impl From<[i32; $n]> for $ty {
+            fn from([$($field),+]: [i32; $n]) -> Self {
+                Self { $($field),+ }
+            }
+        }
+
+        impl From<$ty> for [i32; $n] {
+            fn from(coord: $ty) -> Self {
+                [$(coord.$field),+]
+            }
+        }
+
+        impl_coord!(@op $ty, Add, add, $($field),+);
+        impl_coord!(@op $ty, Sub, sub, $($field),+);
+        impl_coord!(@op $ty, Mul, mul, $($field),+);
+        impl_coord!(@op $ty, Div, div, $($field),+);
+    };
+    (@op $ty:ident, $trait:ident, $method:ident, $($field:ident),+) => {
+        impl $trait for $ty {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                Self { $($field: self.$field.$method(rhs.$field)),+ }
+            }
+        }
+    };
+}
+
+impl_coord!(GemmCoord, 3, m, n, k);
+impl_coord!(MatrixCoord, 2, row, column);
+impl_coord!(Tensor4DCoord, 4, n, h, w, c);
+
+impl From<(i32, i32, i32)> for GemmCoord {
+    fn from((m, n, k): (i32, i32, i32)) -> Self {
+        Self::new(m, n, k)
+    }
+}
+
+impl From<(i32, i32)> for MatrixCoord {
+    fn from((row, column): (i32, i32)) -> Self {
+        Self::new(row, column)
+    }
+}
+
+impl From<(i32, i32, i32, i32)> for Tensor4DCoord {
+    fn from((n, h, w, c): (i32, i32, i32, i32)) -> Self {
+        Self::new(n, h, w, c)
+    }
+}
@@ -0,0 +1,1267 @@
+//! CuTe layout algebra on the host (`cute` feature).
+//!
+//! A port of the dynamic-integer half of `cute/layout.hpp`, for build
+//! scripts and code generators that need to reason about the layouts a
+//! kernel will use without compiling C++. A [`Layout`] maps a (possibly
+//! hierarchical) coordinate to an index through a [`Shape`] and a
+//! [`Stride`] of the same profile, both [`IntTuple`]s, and the operations
+//! follow CuTe's definitions:
+//!
+//! - [`Layout::coalesce`] merges modes the layout walks contiguously;
+//! - [`Layout::complement`] fills in the indices a layout does not reach;
+//! - [`Layout::compose`] is functional composition, `A ∘ B`;
+//! - [`Layout::logical_divide`] and [`Layout::logical_product`] split a
+//!   layout into tiles and repeat a tile across a layout, by a single
+//!   layout or mode by mode with a [`Tiler`].
+//!
+//! [`MmaAtom`] and [`CopyAtom`] describe the instructions CuTe wraps as MMA
+//! and copy atoms: tile shapes, thread counts, element types, alignments
+//! and the architectures that have them, so code generators can pick one
+//! with [`MmaAtom::find`] and [`CopyAtom::find`] rather than hard-coding
+//! the numbers.
+//!
+//! Layouts print the way CuTe prints dynamic layouts, e.g. `(2,4):(1,2)`.
+//! Preconditions CuTe asserts (shapes that do not divide one another) panic
+//! here too. Static integers are not modelled, so results are identical to
+//! CuTe's up to the `_N` spelling of its compile-time values.
+
+use core::fmt;
+use std::vec;
+use std::vec::Vec;
+
+use crate::types::DataType;
+
+/// An integer or a tuple of `IntTuple`s, as `cute::IntTuple`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IntTuple {
+    Int(i64),
+    Tuple(Vec<IntTuple>),
+}
+
+/// Extents of a layout's modes.
+pub type Shape = IntTuple;
+
+/// Index steps of a layout's modes.
+pub type Stride = IntTuple;
+
+impl IntTuple {
+    /// Number of top-level modes; `1` for an integer.
+    pub fn rank(&self) -> usize {
+        match self {
+            Self::Int(_) => 1,
+            Self::Tuple(modes) => modes.len(),
+        }
+    }
+
+    /// Nesting depth; `0` for an integer.
+    pub fn depth(&self) -> usize {
+        match self {
+            Self::Int(_) => 0,
+            Self::Tuple(modes) => 1 + modes.iter().map(Self::depth).max().unwrap_or(0),
+        }
+    }
+
+    /// Product of every integer, as `cute::size` of a shape.
+    pub fn product(&self) -> i64 {
+        match self {
+            Self::Int(value) => *value,
+            Self::Tuple(modes) => modes.iter().map(Self::product).product(),
+        }
+    }
+
+    /// Top-level mode `i`; an integer is its own mode 0.
+    pub fn mode(&self, i: usize) -> &IntTuple {
+        match self {
+            Self::Int(_) => {
+                assert_eq!(i, 0, "mode {i} of an integer");
+                self
+            }
+            Self::Tuple(modes) => &modes[i],
+        }
+    }
+
+    /// Every integer, depth first.
+    pub fn flatten(&self) -> Vec<i64> {
+        let mut flat = Vec::new();
+        self.flatten_into(&mut flat);
+        flat
+    }
+
+    fn flatten_into(&self, flat: &mut Vec<i64>) {
+        match self {
+            Self::Int(value) => flat.push(*value),
+            Self::Tuple(modes) => modes.iter().for_each(|mode| mode.flatten_into(flat)),
+        }
+    }
+
+    /// A single integer stays one; anything else becomes a tuple.
+    fn from_flat(flat: Vec<i64>) -> Self {
+        if flat.len() == 1 {
+            Self::Int(flat[0])
+        } else {
+            Self::Tuple(flat.into_iter().map(Self::Int).collect())
+        }
+    }
+
+    /// Whether `self` and `other` nest the same way.
+    pub fn congruent(&self, other: &IntTuple) -> bool {
+        match (self, other) {
+            (Self::Int(_), Self::Int(_)) => true,
+            (Self::Tuple(a), Self::Tuple(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.congruent(b))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<i64> for IntTuple {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for IntTuple {
+    fn from(values: [i64; N]) -> Self {
+        Self::Tuple(values.into_iter().map(Self::Int).collect())
+    }
+}
+
+impl From<Vec<IntTuple>> for IntTuple {
+    fn from(modes: Vec<IntTuple>) -> Self {
+        Self::Tuple(modes)
+    }
+}
+
+impl fmt::Display for IntTuple {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Tuple(modes) => {
+                f.write_str("(")?;
+                for (i, mode) in modes.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{mode}")?;
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+/// `a / b` where one divides the other, `±1` when `b` is the larger, as
+/// `cute::shape_div` of integers.
+fn shape_div(a: i64, b: i64) -> i64 {
+    assert!(
+        a % b == 0 || b % a == 0,
+        "shape_div: {a} and {b} do not divide one another"
+    );
+    if a / b != 0 {
+        a / b
+    } else {
+        a.signum() * b.signum()
+    }
+}
+
+/// A map from coordinates in `shape` to indices, as `cute::Layout`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Layout {
+    pub shape: Shape,
+    pub stride: Stride,
+}
+
+impl Layout {
+    /// `shape:stride`. Panics unless the two are congruent.
+    pub fn new(shape: impl Into<Shape>, stride: impl Into<Stride>) -> Self {
+        let (shape, stride) = (shape.into(), stride.into());
+        assert!(
+            shape.congruent(&stride),
+            "shape {shape} and stride {stride} differ in profile"
+        );
+        Self { shape, stride }
+    }
+
+    /// The compact generalized column-major layout of `shape`, CuTe's
+    /// default (`LayoutLeft`).
+    pub fn column_major(shape: impl Into<Shape>) -> Self {
+        let shape = shape.into();
+        let mut next = 1;
+        let stride = compact(&shape, &mut next, false);
+        Self { shape, stride }
+    }
+
+    /// The compact generalized row-major layout of `shape` (`LayoutRight`).
+    pub fn row_major(shape: impl Into<Shape>) -> Self {
+        let shape = shape.into();
+        let mut next = 1;
+        let stride = compact(&shape, &mut next, true);
+        Self { shape, stride }
+    }
+
+    /// The layout whose modes are `layouts`, as `cute::make_layout` of
+    /// layouts.
+    pub fn concat(layouts: impl IntoIterator<Item = Layout>) -> Self {
+        let (shape, stride) = layouts.into_iter().map(|l| (l.shape, l.stride)).unzip();
+        Self {
+            shape: IntTuple::Tuple(shape),
+            stride: IntTuple::Tuple(stride),
+        }
+    }
+
+    pub fn rank(&self) -> usize {
+        self.shape.rank()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.shape.depth()
+    }
+
+    /// Top-level mode `i` as a layout of its own.
+    pub fn mode(&self, i: usize) -> Layout {
+        Self {
+            shape: self.shape.mode(i).clone(),
+            stride: self.stride.mode(i).clone(),
+        }
+    }
+
+    /// Number of coordinates in the domain.
+    pub fn size(&self) -> i64 {
+        self.shape.product()
+    }
+
+    /// One past the largest index in the image, for non-negative strides.
+    pub fn cosize(&self) -> i64 {
+        let size = self.size();
+        if size == 0 {
+            0
+        } else {
+            self.index(size - 1) + 1
+        }
+    }
+
+    /// The index of the 1-D coordinate `coord`, unfolded colexicographically
+    /// over the shape: `layout(coord)`.
+    pub fn index(&self, coord: i64) -> i64 {
+        crd2idx(coord, &self.shape, &self.stride)
+    }
+
+    /// The flat `(shape, stride)` pairs, depth first.
+    fn flat_modes(&self) -> Vec<(i64, i64)> {
+        self.shape
+            .flatten()
+            .into_iter()
+            .zip(self.stride.flatten())
+            .collect()
+    }
+
+    /// The same function with the fewest modes: shape-1 modes dropped and
+    /// neighbours `(s0, d0), (s1, s0 * d0)` merged, flattened to a single
+    /// integer or a flat tuple.
+    pub fn coalesce(&self) -> Layout {
+        let mut modes: Vec<(i64, i64)> = Vec::new();
+        for (shape, stride) in self.flat_modes() {
+            if shape == 1 {
+                continue;
+            }
+            match modes.last_mut() {
+                Some(last) if last.0 * last.1 == stride => last.0 *= shape,
+                _ => modes.push((shape, stride)),
+            }
+        }
+        if modes.is_empty() {
+            return Layout::new(1, 0);
+        }
+        let (shape, stride) = modes.into_iter().unzip();
+        Self {
+            shape: IntTuple::from_flat(shape),
+            stride: IntTuple::from_flat(stride),
+        }
+    }
+
+    /// The layout of the indices below `cotarget` this layout does not
+    /// reach, ordered so that `(self, complement)` is a bijection onto
+    /// them, as `cute::complement`. Strides must be non-negative and each
+    /// a multiple of the extent the smaller ones cover.
+    pub fn complement(&self, cotarget: i64) -> Layout {
+        let mut modes: Vec<(i64, i64)> = self
+            .flat_modes()
+            .into_iter()
+            .filter(|&(shape, stride)| stride != 0 && shape != 1)
+            .collect();
+        modes.sort_by_key(|&(_, stride)| stride);
+
+        let mut shape = Vec::new();
+        let mut stride = Vec::new();
+        let mut current = 1;
+        for (s, d) in modes {
+            assert!(
+                d % current == 0,
+                "complement: stride {d} is not a multiple of {current}"
+            );
+            shape.push(d / current);
+            stride.push(current);
+            current = s * d;
+        }
+        shape.push((cotarget + current - 1) / current);
+        stride.push(current);
+        Self {
+            shape: IntTuple::from_flat(shape),
+            stride: IntTuple::from_flat(stride),
+        }
+        .coalesce()
+    }
+
+    /// `self ∘ rhs`: the layout mapping `c` to `self(rhs(c))`, with the
+    /// profile of `rhs`, as `cute::composition`.
+    pub fn compose(&self, rhs: &Layout) -> Layout {
+        match (&rhs.shape, &rhs.stride) {
+            (IntTuple::Tuple(shapes), IntTuple::Tuple(strides)) => Self::concat(
+                shapes
+                    .iter()
+                    .zip(strides)
+                    .map(|(s, d)| self.compose(&Layout::new(s.clone(), d.clone()))),
+            ),
+            (&IntTuple::Int(shape), &IntTuple::Int(stride)) => self.compose_mode(shape, stride),
+            _ => unreachable!("congruent by construction"),
+        }
+    }
+
+    /// Composition with the single mode `shape:stride`. The lhs is
+    /// coalesced first (integer strides have an integer coprofile, so fully,
+    /// as `detail::coalesce_x`): modes the rhs walks across contiguously
+    /// need not divide its stride and shape one by one.
+    fn compose_mode(&self, rhs_shape: i64, rhs_stride: i64) -> Layout {
+        if rhs_stride == 0 {
+            return Layout::new(rhs_shape, 0);
+        }
+        let flat = self.coalesce().flat_modes();
+        let (&(_, last_stride), init) = flat.split_last().expect("a layout has a mode");
+        if init.is_empty() {
+            return Layout::new(rhs_shape, rhs_stride * last_stride);
+        }
+
+        // Divide the rhs stride out of the leading lhs modes...
+        let mut rest_stride = rhs_stride;
+        let mut divided = Vec::with_capacity(init.len());
+        for &(shape, stride) in init {
+            let new_shape = shape_div(shape, rest_stride);
+            divided.push((new_shape, stride * shape_div(shape, new_shape)));
+            rest_stride = shape_div(rest_stride, shape);
+        }
+        // ...then keep the rhs shape's worth of what is left
+        let mut rest_shape = rhs_shape;
+        let mut kept = Vec::with_capacity(init.len());
+        for (shape, stride) in divided {
+            kept.push((shape.abs().min(rest_shape), stride));
+            rest_shape = shape_div(rest_shape, shape.abs());
+        }
+        bw_coalesce(&kept, rest_shape, rest_stride * last_stride)
+    }
+
+    /// `self ∘ (tiler, complement(tiler, size(self)))`: mode 0 is the
+    /// elements of one tile, mode 1 the tiles, as `cute::logical_divide`.
+    /// A [`Tiler::Modes`] tiler divides each leading mode of `self` by its
+    /// own tiler instead and leaves the remaining modes as they are; it
+    /// must not have more modes than `self`.
+    pub fn logical_divide(&self, tiler: impl Into<Tiler>) -> Layout {
+        match tiler.into() {
+            Tiler::Layout(tiler) => {
+                let complement = tiler.complement(self.size());
+                self.compose(&Self::concat([tiler, complement]))
+            }
+            Tiler::Modes(tilers) => self.by_mode(tilers, |mode, tiler| mode.logical_divide(tiler)),
+        }
+    }
+
+    /// `(self, complement(self, size(self) * cosize(tiler)) ∘ tiler)`: mode 0
+    /// is `self`, mode 1 its repetitions as laid out by `tiler`, as
+    /// `cute::logical_product`. A [`Tiler::Modes`] tiler takes the product
+    /// of each leading mode of `self` with its own tiler instead, as for
+    /// [`logical_divide`](Self::logical_divide).
+    pub fn logical_product(&self, tiler: impl Into<Tiler>) -> Layout {
+        match tiler.into() {
+            Tiler::Layout(tiler) => {
+                let repeat = self
+                    .complement(self.size() * tiler.cosize())
+                    .compose(&tiler);
+                Self::concat([self.clone(), repeat])
+            }
+            Tiler::Modes(tilers) => self.by_mode(tilers, |mode, tiler| mode.logical_product(tiler)),
+        }
+    }
+
+    /// `op` applied to each leading mode of `self` and its tiler, the
+    /// remaining modes kept, as CuTe's `transform_layout` of a tuple tiler.
+    fn by_mode(&self, tilers: Vec<Tiler>, op: fn(&Layout, Tiler) -> Layout) -> Layout {
+        assert!(
+            tilers.len() <= self.rank(),
+            "a tiler of {} modes for the rank-{} layout {self}",
+            tilers.len(),
+            self.rank()
+        );
+        let mut tilers = tilers.into_iter();
+        Self::concat((0..self.rank()).map(|i| match tilers.next() {
+            Some(tiler) => op(&self.mode(i), tiler),
+            None => self.mode(i),
+        }))
+    }
+}
+
+/// The tiler of [`Layout::logical_divide`] and [`Layout::logical_product`]:
+/// a layout, or a tuple of tilers applied mode by mode (CuTe's `Tile`,
+/// `make_tile(...)`).
+///
+/// `(3:3,(2,4):(1,8))` as a [`Layout`] is one rank-2 layout tiling the
+/// whole of a layout; as [`Tiler::Modes`] it tiles mode 0 by `3:3` and
+/// mode 1 by `(2,4):(1,8)`. An integer `n` in a tile, as in
+/// `Tiler::from([2, 4])`, is the layout `n:1`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Tiler {
+    Layout(Layout),
+    Modes(Vec<Tiler>),
+}
+
+impl From<Layout> for Tiler {
+    fn from(layout: Layout) -> Self {
+        Self::Layout(layout)
+    }
+}
+
+impl From<&Layout> for Tiler {
+    fn from(layout: &Layout) -> Self {
+        Self::Layout(layout.clone())
+    }
+}
+
+impl From<Vec<Tiler>> for Tiler {
+    fn from(modes: Vec<Tiler>) -> Self {
+        Self::Modes(modes)
+    }
+}
+
+impl From<Vec<Layout>> for Tiler {
+    fn from(modes: Vec<Layout>) -> Self {
+        Self::Modes(modes.into_iter().map(Self::Layout).collect())
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for Tiler {
+    fn from(extents: [i64; N]) -> Self {
+        Self::Modes(
+            extents
+                .into_iter()
+                .map(|extent| Self::Layout(Layout::new(extent, 1)))
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for Tiler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Layout(layout) => write!(f, "{layout}"),
+            Self::Modes(modes) => {
+                f.write_str("(")?;
+                for (i, mode) in modes.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{mode}")?;
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+/// `crd2idx` of a 1-D coordinate: each mode but the last takes its share
+/// of `coord` modulo its size, the last everything that remains.
+fn crd2idx(coord: i64, shape: &IntTuple, stride: &IntTuple) -> i64 {
+    match (shape, stride) {
+        (IntTuple::Int(_), IntTuple::Int(stride)) => coord * stride,
+        (IntTuple::Tuple(shapes), IntTuple::Tuple(strides)) => {
+            let mut rest = coord;
+            let mut index = 0;
+            for (i, (s, d)) in shapes.iter().zip(strides).enumerate() {
+                if i + 1 == shapes.len() {
+                    index += crd2idx(rest, s, d);
+                } else {
+                    let size = s.product();
+                    index += crd2idx(rest % size, s, d);
+                    rest /= size;
+                }
+            }
+            index
+        }
+        _ => panic!("shape {shape} and stride {stride} differ in profile"),
+    }
+}
+
+/// Compact strides of `shape` starting at `*next`, leftmost mode fastest
+/// unless `row_major`.
+fn compact(shape: &IntTuple, next: &mut i64, row_major: bool) -> IntTuple {
+    match shape {
+        IntTuple::Int(extent) => {
+            let stride = *next;
+            *next *= extent;
+            IntTuple::Int(stride)
+        }
+        IntTuple::Tuple(modes) => {
+            let mut strides: Vec<IntTuple> = Vec::with_capacity(modes.len());
+            if row_major {
+                for mode in modes.iter().rev() {
+                    strides.push(compact(mode, next, row_major));
+                }
+                strides.reverse();
+            } else {
+                for mode in modes {
+                    strides.push(compact(mode, next, row_major));
+                }
+            }
+            IntTuple::Tuple(strides)
+        }
+    }
+}
+
+/// Prepends `modes` to the trailing `rest_shape:rest_stride` mode back to
+/// front, dropping shape-1 modes and merging contiguous ones, as CuTe's
+/// `detail::bw_coalesce`.
+fn bw_coalesce(modes: &[(i64, i64)], rest_shape: i64, rest_stride: i64) -> Layout {
+    let mut shape = vec![rest_shape];
+    let mut stride = vec![rest_stride];
+    for &(s, d) in modes.iter().rev() {
+        if s == 1 {
+            continue;
+        }
+        if shape[0] == 1 {
+            shape[0] = s;
+            stride[0] = d;
+        } else if s * d == stride[0] {
+            shape[0] *= s;
+            stride[0] = d;
+        } else {
+            shape.insert(0, s);
+            stride.insert(0, d);
+        }
+    }
+    if shape == [1] {
+        return Layout::new(1, 0);
+    }
+    Layout {
+        shape: IntTuple::from_flat(shape),
+        stride: IntTuple::from_flat(stride),
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.shape, self.stride)
+    }
+}
+
+/// An MMA atom from `cute/arch/mma_sm*.hpp`: one hardware MMA instruction,
+/// the tile it computes and the threads that issue it together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MmaAtom {
+    /// The CuTe operation's name; `N` stands for the tile width of
+    /// warpgroup atoms, which come in every multiple of
+    /// [`n_step`](Self::n_step).
+    pub name: &'static str,
+    /// Oldest compute capability with the instruction, e.g. `80`.
+    pub arch: i32,
+    /// Whether the instruction exists only on `arch` (the `sm_90a` wgmma
+    /// family) rather than on every newer device too.
+    pub arch_specific: bool,
+    /// `(M, N, K)` of the tile, with the largest `N` for warpgroup atoms.
+    pub shape_mnk: [i64; 3],
+    /// `0` for a fixed tile, otherwise the step between valid `N`s.
+    pub n_step: i64,
+    /// Threads that issue one instruction: a quadpair (8), a warp (32) or
+    /// a warpgroup (128).
+    pub threads: i64,
+    /// Element types of `A` and `B`. tf32 atoms are listed with
+    /// [`DataType::F32`] operands, which they round.
+    pub a: DataType,
+    pub b: DataType,
+    /// Element type of `C` and `D`, the accumulator.
+    pub c: DataType,
+}
+
+impl MmaAtom {
+    /// Whether a device of compute capability `cc` runs the instruction.
+    pub const fn available_on(&self, cc: i32) -> bool {
+        if self.arch_specific {
+            cc == self.arch
+        } else {
+            cc >= self.arch
+        }
+    }
+
+    /// Whether the atom computes an `M x n x K` tile.
+    pub const fn supports_n(&self, n: i64) -> bool {
+        if self.n_step == 0 {
+            n == self.shape_mnk[1]
+        } else {
+            n > 0 && n <= self.shape_mnk[1] && n % self.n_step == 0
+        }
+    }
+
+    /// Every atom in the table.
+    pub fn all() -> &'static [MmaAtom] {
+        MMA_ATOMS
+    }
+
+    /// Atoms a device of compute capability `cc` runs for `a x b + c`
+    /// operands, newest first.
+    pub fn find(
+        cc: i32,
+        a: DataType,
+        b: DataType,
+        c: DataType,
+    ) -> impl Iterator<Item = &'static MmaAtom> {
+        MMA_ATOMS
+            .iter()
+            .rev()
+            .filter(move |atom| atom.available_on(cc) && (atom.a, atom.b, atom.c) == (a, b, c))
+    }
+}
+
+const fn mma(
+    name: &'static str,
+    arch: i32,
+    shape_mnk: [i64; 3],
+    threads: i64,
+    (a, b, c): (DataType, DataType, DataType),
+) -> MmaAtom {
+    MmaAtom {
+        name,
+        arch,
+        arch_specific: false,
+        shape_mnk,
+        n_step: 0,
+        threads,
+        a,
+        b,
+        c,
+    }
+}
+
+const fn gmma(name: &'static str, k: i64, (a, b, c): (DataType, DataType, DataType)) -> MmaAtom {
+    MmaAtom {
+        name,
+        arch: 90,
+        arch_specific: true,
+        shape_mnk: [64, 256, k],
+        n_step: 8,
+        threads: 128,
+        a,
+        b,
+        c,
+    }
+}
+
+const F16: (DataType, DataType, DataType) = (DataType::F16, DataType::F16, DataType::F16);
+const F16_F32: (DataType, DataType, DataType) = (DataType::F16, DataType::F16, DataType::F32);
+const BF16_F32: (DataType, DataType, DataType) = (DataType::BF16, DataType::BF16, DataType::F32);
+const TF32: (DataType, DataType, DataType) = (DataType::F32, DataType::F32, DataType::F32);
+const F64: (DataType, DataType, DataType) = (DataType::F64, DataType::F64, DataType::F64);
+const C64: (DataType, DataType, DataType) = (DataType::C64, DataType::C64, DataType::C64);
+const S8: (DataType, DataType, DataType) = (DataType::S8, DataType::S8, DataType::S32);
+const E4M3: (DataType, DataType, DataType) = (DataType::E4M3, DataType::E4M3, DataType::F32);
+const E4M3_E5M2: (DataType, DataType, DataType) = (DataType::E4M3, DataType::E5M2, DataType::F32);
+const E5M2_E4M3: (DataType, DataType, DataType) = (DataType::E5M2, DataType::E4M3, DataType::F32);
+const E5M2: (DataType, DataType, DataType) = (DataType::E5M2, DataType::E5M2, DataType::F32);
+
+/// Oldest architecture first, so [`MmaAtom::find`] yields the newest.
+static MMA_ATOMS: &[MmaAtom] = &[
+    mma("SM70_8x8x4_F16F16F16F16_TN", 70, [8, 8, 4], 8, F16),
+    mma("SM70_8x8x4_F32F16F16F32_TN", 70, [8, 8, 4], 8, F16_F32),
+    mma("SM75_16x8x8_F32F16F16F32_TN", 75, [16, 8, 8], 32, F16_F32),
+    mma("SM75_8x8x16_S32S8S8S32_TN", 75, [8, 8, 16], 32, S8),
+    mma("SM80_16x8x8_F16F16F16F16_TN", 80, [16, 8, 8], 32, F16),
+    mma("SM80_16x8x16_F16F16F16F16_TN", 80, [16, 8, 16], 32, F16),
+    mma("SM80_16x8x8_F32F16F16F32_TN", 80, [16, 8, 8], 32, F16_F32),
+    mma("SM80_16x8x16_F32F16F16F32_TN", 80, [16, 8, 16], 32, F16_F32),
+    mma(
+        "SM80_16x8x8_F32BF16BF16F32_TN",
+        80,
+        [16, 8, 8],
+        32,
+        BF16_F32,
+    ),
+    mma(
+        "SM80_16x8x16_F32BF16BF16F32_TN",
+        80,
+        [16, 8, 16],
+        32,
+        BF16_F32,
+    ),
+    mma("SM80_16x8x4_F32TF32TF32F32_TN", 80, [16, 8, 4], 32, TF32),
+    mma("SM80_16x8x8_F32TF32TF32F32_TN", 80, [16, 8, 8], 32, TF32),
+    mma("SM80_8x8x4_F64F64F64F64_TN", 80, [8, 8, 4], 32, F64),
+    mma("SM80_8x8x4_C64C64C64C64_TN", 80, [8, 8, 4], 32, C64),
+    mma("SM80_16x8x16_S32S8S8S32_TN", 80, [16, 8, 16], 32, S8),
+    mma("SM80_16x8x32_S32S8S8S32_TN", 80, [16, 8, 32], 32, S8),
+    mma("SM89_16x8x32_F32E4M3E4M3F32_TN", 89, [16, 8, 32], 32, E4M3),
+    mma(
+        "SM89_16x8x32_F32E4M3E5M2F32_TN",
+        89,
+        [16, 8, 32],
+        32,
+        E4M3_E5M2,
+    ),
+    mma(
+        "SM89_16x8x32_F32E5M2E4M3F32_TN",
+        89,
+        [16, 8, 32],
+        32,
+        E5M2_E4M3,
+    ),
+    mma("SM89_16x8x32_F32E5M2E5M2F32_TN", 89, [16, 8, 32], 32, E5M2),
+    gmma("SM90_64xNx16_F16F16F16_SS", 16, F16),
+    gmma("SM90_64xNx16_F32F16F16_SS", 16, F16_F32),
+    gmma("SM90_64xNx16_F32BF16BF16_SS", 16, BF16_F32),
+    gmma("SM90_64xNx8_F32TF32TF32_SS_TN", 8, TF32),
+    gmma("SM90_64xNx32_S32S8S8_SS_TN", 32, S8),
+    gmma("SM90_64xNx32_F32E4M3E4M3_SS_TN", 32, E4M3),
+    gmma("SM90_64xNx32_F32E4M3E5M2_SS_TN", 32, E4M3_E5M2),
+    gmma("SM90_64xNx32_F32E5M2E4M3_SS_TN", 32, E5M2_E4M3),
+    gmma("SM90_64xNx32_F32E5M2E5M2_SS_TN", 32, E5M2),
+];
+
+/// Where a copy atom moves data from and to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CopyPath {
+    /// Plain loads and stores, between any two spaces.
+    Any,
+    GlobalToShared,
+    SharedToGlobal,
+    SharedToRegister,
+    RegisterToShared,
+}
+
+/// A copy atom from `cute/arch/copy_sm*.hpp`: one load or store
+/// instruction and what it needs of its addresses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CopyAtom {
+    /// The CuTe operation's name.
+    pub name: &'static str,
+    /// Oldest compute capability with the instruction.
+    pub arch: i32,
+    pub path: CopyPath,
+    /// Threads that issue one instruction together.
+    pub threads: i64,
+    /// Bits each thread moves per instruction; for TMA, which moves a
+    /// whole box from one thread, the granule the box's inner extent must
+    /// be a multiple of.
+    pub bits: i64,
+    /// Alignment in bytes the instruction needs of its source and
+    /// destination addresses.
+    pub alignment: i64,
+}
+
+impl CopyAtom {
+    /// Whether a device of compute capability `cc` runs the instruction.
+    pub const fn available_on(&self, cc: i32) -> bool {
+        cc >= self.arch
+    }
+
+    /// Elements of `dtype` each thread moves per instruction; `0` if one is
+    /// wider than that.
+    pub const fn values(&self, dtype: DataType) -> i64 {
+        self.bits / dtype_bits(dtype)
+    }
+
+    /// Every atom in the table.
+    pub fn all() -> &'static [CopyAtom] {
+        COPY_ATOMS
+    }
+
+    /// Atoms a device of compute capability `cc` runs along `path`, widest
+    /// first; [`CopyPath::Any`] atoms are always included.
+    pub fn find(cc: i32, path: CopyPath) -> impl Iterator<Item = &'static CopyAtom> {
+        COPY_ATOMS.iter().rev().filter(move |atom| {
+            atom.available_on(cc) && (atom.path == path || atom.path == CopyPath::Any)
+        })
+    }
+}
+
+const fn copy(name: &'static str, arch: i32, path: CopyPath, threads: i64, bits: i64) -> CopyAtom {
+    CopyAtom {
+        name,
+        arch,
+        path,
+        threads,
+        bits,
+        alignment: bits / 8,
+    }
+}
+
+/// ldmatrix and stmatrix take a 16-byte row address from each thread, and
+/// TMA a 16-byte aligned tensor, whatever they move.
+const fn matrix(atom: CopyAtom) -> CopyAtom {
+    CopyAtom {
+        alignment: 16,
+        ..atom
+    }
+}
+
+/// Narrowest first within each family, so [`CopyAtom::find`] yields the
+/// widest.
+static COPY_ATOMS: &[CopyAtom] = &[
+    copy("UniversalCopy<uint8_t>", 0, CopyPath::Any, 1, 8),
+    copy("UniversalCopy<uint16_t>", 0, CopyPath::Any, 1, 16),
+    copy("UniversalCopy<uint32_t>", 0, CopyPath::Any, 1, 32),
+    copy("UniversalCopy<uint64_t>", 0, CopyPath::Any, 1, 64),
+    copy("UniversalCopy<uint128_t>", 0, CopyPath::Any, 1, 128),
+    matrix(copy(
+        "SM75_U32x1_LDSM_N",
+        75,
+        CopyPath::SharedToRegister,
+        32,
+        32,
+    )),
+    matrix(copy(
+        "SM75_U32x2_LDSM_N",
+        75,
+        CopyPath::SharedToRegister,
+        32,
+        64,
+    )),
+    matrix(copy(
+        "SM75_U32x4_LDSM_N",
+        75,
+        CopyPath::SharedToRegister,
+        32,
+        128,
+    )),
+    matrix(copy(
+        "SM75_U16x2_LDSM_T",
+        75,
+        CopyPath::SharedToRegister,
+        32,
+        32,
+    )),
+    matrix(copy(
+        "SM75_U16x4_LDSM_T",
+        75,
+        CopyPath::SharedToRegister,
+        32,
+        64,
+    )),
+    matrix(copy(
+        "SM75_U16x8_LDSM_T",
+        75,
+        CopyPath::SharedToRegister,
+        32,
+        128,
+    )),
+    copy(
+        "SM80_CP_ASYNC_CACHEALWAYS<uint32_t>",
+        80,
+        CopyPath::GlobalToShared,
+        1,
+        32,
+    ),
+    copy(
+        "SM80_CP_ASYNC_CACHEALWAYS<uint64_t>",
+        80,
+        CopyPath::GlobalToShared,
+        1,
+        64,
+    ),
+    copy(
+        "SM80_CP_ASYNC_CACHEALWAYS<uint128_t>",
+        80,
+        CopyPath::GlobalToShared,
+        1,
+        128,
+    ),
+    copy(
+        "SM80_CP_ASYNC_CACHEGLOBAL<uint128_t>",
+        80,
+        CopyPath::GlobalToShared,
+        1,
+        128,
+    ),
+    matrix(copy(
+        "SM90_U32x1_STSM_N",
+        90,
+        CopyPath::RegisterToShared,
+        32,
+        32,
+    )),
+    matrix(copy(
+        "SM90_U32x2_STSM_N",
+        90,
+        CopyPath::RegisterToShared,
+        32,
+        64,
+    )),
+    matrix(copy(
+        "SM90_U32x4_STSM_N",
+        90,
+        CopyPath::RegisterToShared,
+        32,
+        128,
+    )),
+    matrix(copy("SM90_TMA_LOAD", 90, CopyPath::GlobalToShared, 1, 128)),
+    matrix(copy(
+        "SM90_TMA_LOAD_MULTICAST",
+        90,
+        CopyPath::GlobalToShared,
+        1,
+        128,
+    )),
+    matrix(copy("SM90_TMA_STORE", 90, CopyPath::SharedToGlobal, 1, 128)),
+];
+
+/// Bits of one element of `dtype`.
+const fn dtype_bits(dtype: DataType) -> i64 {
+    match dtype {
+        DataType::S8 | DataType::E4M3 | DataType::E5M2 => 8,
+        DataType::F16 | DataType::BF16 => 16,
+        DataType::F32 | DataType::S32 => 32,
+        DataType::F64 | DataType::C32 => 64,
+        DataType::C64 => 128,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+
+    use super::*;
+
+    fn tuple(modes: impl IntoIterator<Item = IntTuple>) -> IntTuple {
+        IntTuple::Tuple(modes.into_iter().collect())
+    }
+
+    fn layout(shape: impl Into<Shape>, stride: impl Into<Stride>) -> Layout {
+        Layout::new(shape, stride)
+    }
+
+    /// `(2,2)` as a nested mode.
+    fn pair(a: i64, b: i64) -> IntTuple {
+        IntTuple::from([a, b])
+    }
+
+    /// `c(i) == a(b(i))` over the domain of `b`, with the modes of a tuple
+    /// `b`, as CuTe's `test_composition`.
+    fn check_composition(a: &Layout, b: &Layout) -> Layout {
+        let c = a.compose(b);
+        assert_eq!(c.size(), b.size(), "{a} o {b} = {c}");
+        if let IntTuple::Tuple(_) = b.shape {
+            assert_eq!(c.rank(), b.rank(), "{a} o {b} = {c}");
+        }
+        for i in 0..b.size() {
+            assert_eq!(c.index(i), a.index(b.index(i)), "{a} o {b} = {c} at {i}");
+        }
+        c
+    }
+
+    /// The complement is ordered, disjoint from the layout's image and,
+    /// together with it, covers `cotarget`, as CuTe's `test_complement`.
+    fn check_complement(a: &Layout, cotarget: i64) -> Layout {
+        let c = a.complement(cotarget);
+        for i in 1..c.size() {
+            assert!(
+                c.index(i - 1) < c.index(i),
+                "complement({a}, {cotarget}) = {c}"
+            );
+            for j in 0..a.size() {
+                assert_ne!(c.index(i), a.index(j), "complement({a}, {cotarget}) = {c}");
+            }
+        }
+        let filtered: i64 = a
+            .flat_modes()
+            .into_iter()
+            .filter(|&(_, stride)| stride != 0)
+            .map(|(shape, _)| shape)
+            .product();
+        assert!(
+            filtered * c.size() >= cotarget,
+            "complement({a}, {cotarget}) = {c}"
+        );
+        c
+    }
+
+    #[test]
+    fn coalesce() {
+        let cases = [
+            (layout(1, 0), "1:0"),
+            (layout(1, 1), "1:0"),
+            (layout([2, 4], [1, 2]), "8:1"),
+            (layout([2, 4, 6], [1, 2, 8]), "48:1"),
+            (layout([2, 1, 6], [1, 6, 2]), "12:1"),
+            (layout([2, 1, 6], [1, 7, 2]), "12:1"),
+            (layout([2, 1, 6], [4, 1, 8]), "12:4"),
+            (layout([2, 1, 6], [4, 1, 16]), "(2,6):(4,16)"),
+            (layout([2, 4], [4, 1]), "(2,4):(4,1)"),
+            (layout([2, 4], [0, 2]), "(2,4):(0,2)"),
+            (
+                layout(tuple([2.into(), pair(1, 6)]), tuple([1.into(), pair(6, 2)])),
+                "12:1",
+            ),
+            (
+                layout(tuple([pair(2, 2), 4.into()]), tuple([pair(1, 2), 4.into()])),
+                "16:1",
+            ),
+        ];
+        for (a, expected) in cases {
+            let c = a.coalesce();
+            assert_eq!(c.to_string(), expected, "coalesce({a})");
+            assert_eq!(c.size(), a.size());
+            assert!(c.depth() <= 1);
+            for i in 0..a.size() {
+                assert_eq!(c.index(i), a.index(i), "coalesce({a}) at {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn complement() {
+        let cases = [
+            (layout(1, 0), 1, "1:0"),
+            (layout(1, 0), 4, "4:1"),
+            (layout(4, 1), 24, "6:4"),
+            (layout(6, 4), 24, "4:1"),
+            (layout(4, 2), 24, "(2,3):(1,8)"),
+            (layout([4, 6], [1, 4]), 24, "1:0"),
+            (layout([2, 4], [1, 6]), 24, "3:2"),
+            (layout([2, 2], [1, 6]), 24, "(3,2):(2,12)"),
+            (layout([4, 2], [1, 16]), 64, "(4,2):(4,32)"),
+            (layout([4, 6], [0, 4]), 24, "4:1"),
+        ];
+        for (a, cotarget, expected) in cases {
+            let c = check_complement(&a, cotarget);
+            assert_eq!(c.to_string(), expected, "complement({a}, {cotarget})");
+        }
+        // Uncoalesced layouts complement like their coalesced forms
+        let a = layout(
+            tuple([pair(2, 2), pair(2, 3)]),
+            tuple([pair(1, 2), pair(4, 8)]),
+        );
+        assert_eq!(check_complement(&a, 48).to_string(), "2:24");
+        check_complement(&layout([2, 4, 8], [8, 1, 64]), 512);
+    }
+
+    #[test]
+    fn composition() {
+        let cases = [
+            (layout(1, 0), layout(1, 0)),
+            (layout(1, 0), layout(1, 1)),
+            (layout(1, 1), layout(1, 0)),
+            (layout(1, 1), layout(1, 1)),
+            (layout(4, 1), layout(4, 1)),
+            (layout(4, 2), layout(4, 1)),
+            (layout(4, 0), layout(4, 1)),
+            (layout(4, 1), layout(4, 0)),
+            (layout(4, 1), layout(1, 0)),
+            (layout(4, 1), layout(2, 1)),
+            (layout(4, 2), layout(2, 1)),
+            (layout(4, 1), layout(2, 2)),
+            (layout(4, 2), layout(2, 2)),
+            (Layout::column_major([4, 3]), layout(12, 1)),
+            (layout(12, 1), Layout::column_major([4, 3])),
+            (layout(12, 2), Layout::column_major([4, 3])),
+            (layout(12, 1), layout([4, 3], [3, 1])),
+            (layout(12, 2), layout([4, 3], [3, 1])),
+            (layout(12, 1), layout([2, 3], [2, 4])),
+            (Layout::column_major([4, 3]), Layout::column_major([4, 3])),
+            (Layout::column_major([4, 3]), layout(6, 2)),
+            (Layout::column_major([4, 3]), layout([6, 2], [2, 1])),
+            (layout([4, 3], [3, 1]), Layout::column_major([4, 3])),
+            (layout([4, 3], [3, 1]), layout(12, 1)),
+            (layout([4, 3], [3, 1]), layout(6, 2)),
+            (layout([4, 3], [3, 1]), layout([6, 2], [2, 1])),
+            (
+                Layout::column_major([8, 8]),
+                layout(
+                    tuple([IntTuple::from([2, 2, 2]), IntTuple::from([2, 2, 2])]),
+                    tuple([IntTuple::from([1, 16, 4]), IntTuple::from([8, 2, 32])]),
+                ),
+            ),
+            (
+                layout([8, 8], [8, 1]),
+                layout(
+                    tuple([IntTuple::from([2, 2, 2]), IntTuple::from([2, 2, 2])]),
+                    tuple([IntTuple::from([1, 16, 4]), IntTuple::from([8, 2, 32])]),
+                ),
+            ),
+            (layout([4, 2], [1, 16]), layout([4, 2], [2, 1])),
+            (layout([2, 2], [2, 1]), layout([2, 2], [2, 1])),
+            (
+                Layout::column_major([4, 8, 2]),
+                layout([2, 2, 2], [2, 8, 1]),
+            ),
+            (layout([4, 8, 2], [2, 8, 1]), layout([2, 2, 2], [1, 8, 2])),
+            (layout([4, 8, 2], [2, 8, 1]), layout([4, 2, 2], [2, 8, 1])),
+            // Uncoalesced lhs layouts
+            (layout([4, 6], [1, 4]), layout(3, 2)),
+            (layout([4, 6], [1, 4]), layout([3, 2], [2, 12])),
+            (layout([4, 6, 8], [1, 4, 7]), layout(6, 1)),
+            (layout([4, 6, 8], [1, 4, 7]), layout([6, 2], [1, 24])),
+            (layout([2, 2, 6], [1, 2, 4]), layout(4, 6)),
+            (
+                layout(
+                    tuple([pair(2, 2), pair(2, 3)]),
+                    tuple([pair(1, 2), pair(4, 8)]),
+                ),
+                layout([3, 4], [2, 6]),
+            ),
+        ];
+        for (a, b) in &cases {
+            check_composition(a, b);
+        }
+
+        let exact = [
+            (layout([4, 6], [1, 4]), layout(3, 2), "3:2"),
+            (layout([4, 6, 8], [1, 4, 7]), layout(6, 1), "6:1"),
+            (layout([4, 6, 8], [1, 4, 7]), layout(24, 2), "(12,2):(2,7)"),
+            (layout(20, 2), layout([5, 4], [4, 1]), "(5,4):(8,2)"),
+            (
+                layout([6, 2], [8, 2]),
+                layout([4, 3], [3, 1]),
+                "((2,2),3):((24,2),8)",
+            ),
+            (
+                layout([10, 2], [16, 4]),
+                layout([5, 4], [1, 5]),
+                "(5,(2,2)):(16,(80,4))",
+            ),
+        ];
+        for (a, b, expected) in exact {
+            assert_eq!(check_composition(&a, &b).to_string(), expected, "{a} o {b}");
+        }
+    }
+
+    #[test]
+    fn logical_divide() {
+        let cases = [
+            (layout(1, 0), layout(1, 0), "(1,1):(0,0)"),
+            (layout(6, 1), layout(2, 1), "(2,3):(1,2)"),
+            (layout(6, 1), layout(2, 3), "(2,3):(3,1)"),
+            (
+                layout([4, 2, 3], [2, 1, 8]),
+                layout(4, 2),
+                "((2,2),(2,3)):((4,1),(2,8))",
+            ),
+            (
+                layout(48, 1),
+                layout([2, 2], [1, 8]),
+                "((2,2),(4,3)):((1,8),(2,16))",
+            ),
+            (layout([4, 6], [1, 4]), layout(3, 2), "(3,(2,4)):(2,(1,6))"),
+        ];
+        for (a, tiler, expected) in cases {
+            let c = a.logical_divide(&tiler);
+            assert_eq!(c.to_string(), expected, "logical_divide({a}, {tiler})");
+            assert_eq!(c.size(), a.size().max(tiler.size()));
+            // Mode 0 is the tile, gathered through `tiler`
+            for i in 0..tiler.size() {
+                assert_eq!(c.mode(0).index(i), a.index(tiler.index(i)));
+            }
+        }
+    }
+
+    #[test]
+    fn logical_divide_by_mode() {
+        // The example of CuTe's layout algebra docs
+        let a = layout(
+            tuple([9.into(), pair(4, 8)]),
+            tuple([59.into(), pair(13, 1)]),
+        );
+        let tiler = Tiler::from(vec![layout(3, 3), layout([2, 4], [1, 8])]);
+        let c = a.logical_divide(tiler.clone());
+        assert_eq!(
+            c.to_string(),
+            "((3,3),((2,4),(2,2))):((177,59),((13,2),(26,1)))"
+        );
+        assert_eq!(tiler.to_string(), "(3:3,(2,4):(1,8))");
+
+        let cases = [
+            (
+                layout([6, 8], [1, 6]),
+                Tiler::from([2, 4]),
+                "((2,3),(4,2)):((1,2),(6,24))",
+            ),
+            (
+                layout([6, 8], [8, 1]),
+                Tiler::from([3]),
+                "((3,2),8):((8,24),1)",
+            ),
+            (
+                layout([4, 6, 2], [1, 4, 24]),
+                Tiler::from(vec![layout(2, 2), layout(3, 1)]),
+                "((2,2),(3,2),2):((2,1),(4,12),24)",
+            ),
+        ];
+        for (a, tiler, expected) in cases {
+            let c = a.logical_divide(tiler.clone());
+            assert_eq!(c.to_string(), expected, "logical_divide({a}, {tiler})");
+            let Tiler::Modes(tilers) = &tiler else {
+                unreachable!()
+            };
+            for i in 0..a.rank() {
+                let mode = match tilers.get(i) {
+                    Some(tiler) => a.mode(i).logical_divide(tiler.clone()),
+                    None => a.mode(i),
+                };
+                assert_eq!(c.mode(i), mode, "logical_divide({a}, {tiler}) mode {i}");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "a tiler of 3 modes for the rank-2 layout")]
+    fn by_mode_tiler_must_not_outrank_the_layout() {
+        layout([4, 4], [1, 4]).logical_divide([2, 2, 2]);
+    }
+
+    #[test]
+    fn logical_product() {
+        let cases = [
+            (layout(1, 0), layout(1, 0), "(1,1):(0,0)"),
+            (layout(2, 1), layout(3, 1), "(2,3):(1,2)"),
+            (layout(2, 5), layout(3, 1), "(2,3):(5,1)"),
+            (
+                layout([2, 2], [4, 1]),
+                layout(6, 1),
+                "((2,2),(2,3)):((4,1),(2,8))",
+            ),
+            (
+                layout([2, 2], [4, 1]),
+                layout([4, 2], [2, 1]),
+                "((2,2),(4,2)):((4,1),(8,2))",
+            ),
+            (layout(4, 2), layout(2, 1), "(4,2):(2,1)"),
+            (
+                layout([2, 5], [5, 1]),
+                layout([3, 4], [1, 3]),
+                "((2,5),(3,4)):((5,1),(10,30))",
+            ),
+        ];
+        for (a, tiler, expected) in cases {
+            let c = a.logical_product(&tiler);
+            assert_eq!(c.to_string(), expected, "logical_product({a}, {tiler})");
+            assert_eq!(c.size(), a.size() * tiler.size());
+            assert_eq!(c.mode(0), a);
+        }
+    }
+
+    #[test]
+    fn logical_product_by_mode() {
+        // The 2-D example of CuTe's layout algebra docs, whose single-layout
+        // counterpart above repeats the whole block instead
+        let a = layout([2, 5], [5, 1]);
+        let c = a.logical_product(vec![layout(3, 5), layout(4, 6)]);
+        assert_eq!(c.to_string(), "((2,3),(5,4)):((5,10),(1,30))");
+        assert_eq!(c.size(), a.size() * 12);
+
+        let c = layout([2, 2], [1, 2]).logical_product([3]);
+        assert_eq!(c.to_string(), "((2,3),2):((1,2),2)");
+    }
+}
@@ -0,0 +1,194 @@
+//! Blocked-ELL sparse x dense GEMM, as in CUTLASS example 43:
+//!
+//! ```text
+//! D = alpha * A * B + beta * C
+//! ```
+//!
+//! where `A` is stored in the Blocked-Ellpack format: its rows are split into
+//! strips of [`BlockedEll::block_size`] rows, and every strip keeps the same
+//! number of non-zero `block_size x block_size` blocks. The values are an
+//! `m x columns` row-major matrix holding each strip's blocks side by side,
+//! and a row-major `m / block_size x columns / block_size` tensor of `i32`
+//! block-column indices says where in `A` each block belongs. A strip with
+//! fewer blocks pads with index `-1` (relative to
+//! [`BlockedEll::index_base`]), whose values are skipped. This is the format
+//! cuSPARSE's `cusparseSpMM` uses for Blocked-ELL, and what block-wise
+//! structured pruning produces; unlike [`sparse`](crate::sparse) it allows
+//! any density at block granularity, on ordinary Tensor Cores.
+//!
+//! `B` is `K x N` and `C` / `D` are `M x N`, all row-major. The element type
+//! is `F16`, `BF16` or `F32`; `S8` returns [`Status::ErrorInvalidDataType`].
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::coord::GemmCoord;
+use crate::error::CutlassError;
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Storage shape of a Blocked-ELL matrix.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockedEll {
+    /// Rows and columns of each block, a power of two.
+    pub block_size: i32,
+    /// Columns of stored values per row: the number of blocks per strip
+    /// times `block_size`.
+    pub columns: i32,
+    /// Index of the first block column, 0 or 1 for one-based indices.
+    pub index_base: i32,
+}
+
+impl BlockedEll {
+    /// Zero-based storage of `blocks_per_row` blocks of `block_size` per
+    /// strip.
+    pub const fn new(block_size: i32, blocks_per_row: i32) -> Self {
+        Self {
+            block_size,
+            columns: blocks_per_row * block_size,
+            index_base: 0,
+        }
+    }
+
+    /// Blocks stored per strip of `block_size` rows.
+    pub const fn blocks_per_row(&self) -> i32 {
+        self.columns / self.block_size
+    }
+
+    /// Number of `i32` block-column indices describing an `m`-row matrix.
+    pub const fn index_len(&self, m: i32) -> usize {
+        (m / self.block_size) as usize * self.blocks_per_row() as usize
+    }
+
+    /// Checks that this storage describes an `m x k` matrix: a power-of-two
+    /// block size dividing `m`, `k` and [`columns`](Self::columns), and no
+    /// more stored columns than `k`. Returns
+    /// [`Status::ErrorInvalidProblem`] otherwise.
+    pub fn check(&self, m: i32, k: i32) -> Result<(), Status> {
+        let bs = self.block_size;
+        if bs <= 0
+            || bs & (bs - 1) != 0
+            || m % bs != 0
+            || k % bs != 0
+            || self.columns <= 0
+            || self.columns % bs != 0
+            || self.columns > k
+        {
+            return Err(Status::ErrorInvalidProblem);
+        }
+        Ok(())
+    }
+}
+
+/// Problem description of a Blocked-ELL GEMM.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EllGemmConfig {
+    /// The dense `m x n x k` extent of the product.
+    pub problem: GemmCoord,
+    pub element: DataType,
+    pub ell: BlockedEll,
+    /// Leading dimension of the stored values of `A`, at least
+    /// `ell.columns`.
+    pub lda: i64,
+    pub ldb: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub alpha: f32,
+    pub beta: f32,
+}
+
+impl EllGemmConfig {
+    /// A tightly packed `m x n x k` problem with `alpha = 1`, `beta = 0`.
+    pub const fn new(m: i32, n: i32, k: i32, ell: BlockedEll, element: DataType) -> Self {
+        Self {
+            problem: GemmCoord::new(m, n, k),
+            element,
+            ell,
+            lda: ell.columns as i64,
+            ldb: n as i64,
+            ldc: n as i64,
+            ldd: n as i64,
+            alpha: 1.0,
+            beta: 0.0,
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_ell_gemm`] needs for this
+    /// config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_ell_gemm_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_ell_gemm`] can run this config before any
+    /// operand is allocated, returning the status the launch would fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_ell_gemm_can_implement(context, self) }.result()
+    }
+
+    /// Launches the GEMM on `stream` through [`cutlass_sys_ell_gemm`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem, with
+    /// [`ell.index_len(m)`](BlockedEll::index_len) indices in
+    /// `column_indices`, each `-1` or a block column inside `k`, and stay
+    /// valid until the launch completes on `stream`, a stream of that device.
+    /// `workspace` must hold [`workspace_size`](Self::workspace_size) bytes
+    /// of device memory, or may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &EllGemmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_ell_gemm(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers of a Blocked-ELL GEMM launch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct EllGemmOperands {
+    /// Stored values of `A`, `m x ell.columns`.
+    pub a: *const c_void,
+    /// Block-column indices of `a`, [`BlockedEll::index_len`] of them.
+    pub column_indices: *const i32,
+    pub b: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_ell_gemm`] needs.
+    pub fn cutlass_sys_ell_gemm_workspace_size(
+        context: *const CutlassContext,
+        config: *const EllGemmConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_ell_gemm`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_ell_gemm_can_implement(
+        context: *const CutlassContext,
+        config: *const EllGemmConfig,
+    ) -> Status;
+
+    /// Launch a Blocked-ELL GEMM on `stream`. A `config.ell` that fails
+    /// [`BlockedEll::check`] returns [`Status::ErrorInvalidProblem`].
+    pub fn cutlass_sys_ell_gemm(
+        context: *const CutlassContext,
+        config: *const EllGemmConfig,
+        operands: *const EllGemmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
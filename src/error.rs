@@ -0,0 +1,303 @@
+//! Readable diagnostics for CUTLASS statuses and CUDA runtime errors.
+//!
+//! [`CutlassError`] folds the two error domains the bindings meet into one
+//! type: a [`Status`] returned by a shim entry point, or a `cudaError_t`
+//! returned by a CUDA runtime call made on its behalf. Its `Display` uses
+//! the strings CUTLASS and CUDA themselves print (`cutlassGetStatusString`,
+//! `cudaGetErrorName` and `cudaGetErrorString`), exposed through the shim, so
+//! a failure reads the same as it would in C++.
+//!
+//! Configs that can be checked on the host first, such as
+//! [`GemmConfig::check`](crate::gemm::GemmConfig::check), report a
+//! [`ProblemError`] naming the operand at fault instead of a bare status.
+
+use core::ffi::{c_char, c_int, CStr};
+use core::fmt;
+
+use crate::types::{cutlass_status_t, Status};
+
+/// A CUDA runtime error code, `cudaError_t`. `0` is `cudaSuccess`.
+pub type cudaError_t = c_int;
+
+/// `cudaErrorMemoryAllocation`
+pub const cudaErrorMemoryAllocation: cudaError_t = 2;
+
+/// Error of a shim entry point or of a CUDA runtime call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CutlassError {
+    /// A CUTLASS operation failed with this status (never
+    /// [`Status::Success`]).
+    Status(Status),
+    /// A CUDA runtime call failed with this `cudaError_t` (never `0`).
+    Cuda(cudaError_t),
+    /// The host-side checks rejected the problem before any launch.
+    Problem(ProblemError),
+}
+
+impl CutlassError {
+    /// `Ok(())` for [`Status::Success`], [`CutlassError::Status`] otherwise.
+    pub const fn check(status: Status) -> Result<(), Self> {
+        match status {
+            Status::Success => Ok(()),
+            status => Err(Self::Status(status)),
+        }
+    }
+
+    /// `Ok(())` for `cudaSuccess`, [`CutlassError::Cuda`] otherwise.
+    pub const fn cuda(error: cudaError_t) -> Result<(), Self> {
+        match error {
+            0 => Ok(()),
+            error => Err(Self::Cuda(error)),
+        }
+    }
+
+    /// The closest [`Status`], for APIs that only report one: the status
+    /// itself, [`Status::ErrorMemoryAllocation`] for an out-of-memory CUDA
+    /// error, [`Status::ErrorInternal`] for any other and
+    /// [`ProblemError::status`] for a rejected problem.
+    pub const fn status(self) -> Status {
+        match self {
+            Self::Status(status) => status,
+            Self::Cuda(cudaErrorMemoryAllocation) => Status::ErrorMemoryAllocation,
+            Self::Cuda(_) => Status::ErrorInternal,
+            Self::Problem(error) => error.status(),
+        }
+    }
+}
+
+impl From<Status> for CutlassError {
+    fn from(status: Status) -> Self {
+        Self::Status(status)
+    }
+}
+
+impl From<ProblemError> for CutlassError {
+    fn from(error: ProblemError) -> Self {
+        Self::Problem(error)
+    }
+}
+
+impl fmt::Display for CutlassError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Status(status) => f.write_str(status_string(status)),
+            Self::Cuda(error) => write!(
+                f,
+                "CUDA error {} ({}): {}",
+                error,
+                cuda_error_name(error),
+                cuda_error_string(error)
+            ),
+            Self::Problem(error) => error.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CutlassError {}
+
+/// A matrix operand of an operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operand {
+    A,
+    B,
+    C,
+    D,
+    /// The aux tensor an epilogue reads.
+    Aux,
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Operand::A => "A",
+            Operand::B => "B",
+            Operand::C => "C",
+            Operand::D => "D",
+            Operand::Aux => "aux",
+        })
+    }
+}
+
+/// A leading dimension a kernel cannot take, found on the host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProblemError {
+    /// The leading dimension of `operand` is shorter than the contiguous
+    /// extent of its layout, `required`.
+    LeadingDimension {
+        operand: Operand,
+        required: i64,
+        got: i64,
+    },
+    /// The leading dimension of `operand` is not a multiple of the
+    /// `required` elements the kernel accesses at once.
+    MisalignedOperand {
+        operand: Operand,
+        required: i64,
+        got: i64,
+    },
+}
+
+impl ProblemError {
+    /// The status the kernel would have failed with.
+    pub const fn status(self) -> Status {
+        match self {
+            Self::LeadingDimension { .. } => Status::ErrorInvalidProblem,
+            Self::MisalignedOperand { .. } => Status::ErrorMisalignedOperand,
+        }
+    }
+}
+
+impl fmt::Display for ProblemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::LeadingDimension {
+                operand,
+                required,
+                got,
+            } => write!(
+                f,
+                "leading dimension of {} is {}, shorter than its extent {}",
+                operand, got, required
+            ),
+            Self::MisalignedOperand {
+                operand,
+                required,
+                got,
+            } => write!(
+                f,
+                "leading dimension of {} is {}, not a multiple of {} elements",
+                operand, got, required
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProblemError {}
+
+/// `cutlassGetStatusString(status)`.
+pub fn status_string(status: Status) -> &'static str {
+    // SAFETY: the shim returns a static C string for any value.
+    unsafe { static_str(cutlass_sys_status_string(status.into())) }
+}
+
+/// `cudaGetErrorName(error)`, e.g. `cudaErrorMemoryAllocation`.
+pub fn cuda_error_name(error: cudaError_t) -> &'static str {
+    // SAFETY: the shim returns a static C string for any value.
+    unsafe { static_str(cutlass_sys_cuda_error_name(error)) }
+}
+
+/// `cudaGetErrorString(error)`, e.g. `out of memory`.
+pub fn cuda_error_string(error: cudaError_t) -> &'static str {
+    // SAFETY: the shim returns a static C string for any value.
+    unsafe { static_str(cutlass_sys_cuda_error_string(error)) }
+}
+
+/// # Safety
+///
+/// `ptr` must be null or a NUL-terminated string that is never freed.
+unsafe fn static_str(ptr: *const c_char) -> &'static str {
+    if ptr.is_null() {
+        return "";
+    }
+    // SAFETY: the caller vouches for the string.
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .unwrap_or("(invalid UTF-8)")
+}
+
+extern "C" {
+    /// `cutlassGetStatusString`: a static description of a raw
+    /// `cutlass::Status`.
+    pub fn cutlass_sys_status_string(status: cutlass_status_t) -> *const c_char;
+
+    /// `cudaGetErrorName`: the enumerator name of a `cudaError_t`.
+    pub fn cutlass_sys_cuda_error_name(error: cudaError_t) -> *const c_char;
+
+    /// `cudaGetErrorString`: a static description of a `cudaError_t`.
+    pub fn cutlass_sys_cuda_error_string(error: cudaError_t) -> *const c_char;
+}
+
+/// Runs `f` with device `ordinal` current, restoring the previous one.
+#[cfg(any(feature = "autotune", feature = "managed"))]
+pub(crate) fn on_device<R>(
+    ordinal: c_int,
+    f: impl FnOnce() -> Result<R, CutlassError>,
+) -> Result<R, CutlassError> {
+    let mut previous = 0;
+    // SAFETY: only writes `previous`.
+    CutlassError::cuda(unsafe { cudaGetDevice(&mut previous) })?;
+    if previous != ordinal {
+        // SAFETY: `ordinal` is the device of a `CutlassContext`.
+        CutlassError::cuda(unsafe { cudaSetDevice(ordinal) })?;
+    }
+    let result = f();
+    if previous != ordinal {
+        // SAFETY: restores the device current on entry.
+        unsafe { cudaSetDevice(previous) };
+    }
+    result
+}
+
+// The CUDA runtime calls the safe wrappers of the `autotune` and `managed`
+// modules make on the caller's behalf.
+
+#[cfg(any(feature = "autotune", feature = "managed"))]
+extern "C" {
+    pub(crate) fn cudaGetDevice(device: *mut c_int) -> cudaError_t;
+    pub(crate) fn cudaSetDevice(device: c_int) -> cudaError_t;
+}
+
+/// `cudaMemcpyKind` values.
+#[cfg(feature = "managed")]
+pub(crate) const cudaMemcpyHostToDevice: c_int = 1;
+#[cfg(feature = "managed")]
+pub(crate) const cudaMemcpyDeviceToHost: c_int = 2;
+
+#[cfg(feature = "managed")]
+extern "C" {
+    pub(crate) fn cudaMalloc(ptr: *mut *mut core::ffi::c_void, size: usize) -> cudaError_t;
+    pub(crate) fn cudaMallocAsync(
+        ptr: *mut *mut core::ffi::c_void,
+        size: usize,
+        stream: crate::types::cudaStream_t,
+    ) -> cudaError_t;
+    pub(crate) fn cudaMallocPitch(
+        ptr: *mut *mut core::ffi::c_void,
+        pitch: *mut usize,
+        width: usize,
+        height: usize,
+    ) -> cudaError_t;
+    pub(crate) fn cudaFree(ptr: *mut core::ffi::c_void) -> cudaError_t;
+    pub(crate) fn cudaFreeAsync(
+        ptr: *mut core::ffi::c_void,
+        stream: crate::types::cudaStream_t,
+    ) -> cudaError_t;
+    pub(crate) fn cudaMemcpy(
+        dst: *mut core::ffi::c_void,
+        src: *const core::ffi::c_void,
+        count: usize,
+        kind: c_int,
+    ) -> cudaError_t;
+}
+
+/// `cudaEvent_t`
+#[cfg(feature = "autotune")]
+pub(crate) type cudaEvent_t = *mut core::ffi::c_void;
+
+#[cfg(feature = "autotune")]
+extern "C" {
+    pub(crate) fn cudaEventCreate(event: *mut cudaEvent_t) -> cudaError_t;
+    pub(crate) fn cudaEventRecord(
+        event: cudaEvent_t,
+        stream: crate::types::cudaStream_t,
+    ) -> cudaError_t;
+    pub(crate) fn cudaEventSynchronize(event: cudaEvent_t) -> cudaError_t;
+    pub(crate) fn cudaEventElapsedTime(
+        ms: *mut f32,
+        start: cudaEvent_t,
+        stop: cudaEvent_t,
+    ) -> cudaError_t;
+    pub(crate) fn cudaEventDestroy(event: cudaEvent_t) -> cudaError_t;
+}
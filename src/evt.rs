@@ -0,0 +1,304 @@
+//! Epilogue visitor trees (EVT): custom fused GEMM epilogues without C++.
+//!
+//! Arbitrary trees cannot be compiled at run time, so the shim instantiates a
+//! fixed set of tree shapes (see [`EvtShape`]) for every [`BinaryOp`] and
+//! [`Activation`]. [`EvtBuilder`] describes the epilogue as a chain of steps
+//! applied to the accumulator and folds that chain into one of the shapes,
+//! rejecting chains no shape can express.
+//!
+//! All arithmetic happens in `f32`; the final store casts to the output
+//! element type of the GEMM. Aux tensors have that element type as well.
+//!
+//! ```no_run
+//! use cutlass_sys::evt::{AuxTensor, BinaryOp, EvtBuilder};
+//! # let (residual, ld, d) = (core::ptr::null(), 0, core::ptr::null_mut());
+//!
+//! // D = 0.5 * (acc + residual), stored as the GEMM's output type
+//! let program = EvtBuilder::new()
+//!     .binary(BinaryOp::Add, AuxTensor::new(residual, ld))
+//!     .scale(0.5)
+//!     .store(d, ld)
+//!     .expect("residual add + scale is a supported shape");
+//! ```
+
+use core::ffi::c_void;
+use core::fmt;
+
+use crate::context::CutlassContext;
+use crate::error::CutlassError;
+use crate::gemm::{Activation, GemmConfig};
+use crate::types::{cudaStream_t, Status};
+
+/// Elementwise binary operation combining the accumulator with an aux tensor.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BinaryOp {
+    #[default]
+    Add = 0,
+    Mul = 1,
+    Max = 2,
+}
+
+/// Pre-instantiated epilogue tree topologies.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EvtShape {
+    /// `D = out_scale * act(op(acc_scale * acc, aux_scale * aux))`
+    #[default]
+    AuxThenActivation = 0,
+    /// `D = op(out_scale * act(acc_scale * acc), aux_scale * aux)`
+    ActivationThenAux = 1,
+}
+
+/// A fully bound epilogue tree, ready for [`cutlass_sys_gemm_evt`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct EvtProgram {
+    pub shape: EvtShape,
+    pub op: BinaryOp,
+    pub activation: Activation,
+    pub acc_scale: f32,
+    pub aux_scale: f32,
+    pub out_scale: f32,
+    /// Row-major `M x N` aux tensor, or null to read zeros.
+    pub aux: *const c_void,
+    pub ldaux: i64,
+    /// Row-major `M x N` output.
+    pub d: *mut c_void,
+    pub ldd: i64,
+}
+
+impl EvtProgram {
+    /// Bytes of device workspace [`cutlass_sys_gemm_evt`] needs to run this
+    /// program over `config`.
+    pub fn workspace_size(&self, context: &CutlassContext, config: &GemmConfig) -> usize {
+        // SAFETY: only reads `context`, `config` and `self`.
+        unsafe { cutlass_sys_gemm_evt_workspace_size(context, config, self) }
+    }
+
+    /// Checks that [`cutlass_sys_gemm_evt`] can run this program over
+    /// `config` before any operand is allocated, returning the status the
+    /// launch would fail with.
+    pub fn supports(&self, context: &CutlassContext, config: &GemmConfig) -> Result<(), Status> {
+        // SAFETY: only reads `context`, `config` and `self`.
+        unsafe { cutlass_sys_gemm_evt_can_implement(context, config, self) }.result()
+    }
+
+    /// Launches the GEMM of `config` with this program as its epilogue on
+    /// `stream`, through [`cutlass_sys_gemm_evt`].
+    ///
+    /// # Safety
+    ///
+    /// `a`, `b` and the tensors the program reads and writes must address
+    /// device memory of `context`'s device covering the problem and stay
+    /// valid until the launch completes on `stream`, a stream of that
+    /// device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory, or
+    /// may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        config: &GemmConfig,
+        a: *const c_void,
+        b: *const c_void,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_gemm_evt(context, config, self, a, b, workspace, stream)
+        })
+    }
+}
+
+/// Row-major `M x N` device tensor read by the epilogue.
+#[derive(Clone, Copy, Debug)]
+pub struct AuxTensor {
+    pub ptr: *const c_void,
+    pub ld: i64,
+    pub scale: f32,
+}
+
+impl AuxTensor {
+    pub const fn new(ptr: *const c_void, ld: i64) -> Self {
+        Self {
+            ptr,
+            ld,
+            scale: 1.0,
+        }
+    }
+
+    /// Multiply the tensor by `scale` before combining it.
+    pub const fn scaled(self, scale: f32) -> Self {
+        Self { scale, ..self }
+    }
+}
+
+/// Error returned by [`EvtBuilder::store`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvtError {
+    /// The chain of steps does not fold into any [`EvtShape`].
+    UnsupportedShape,
+}
+
+impl fmt::Display for EvtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvtError::UnsupportedShape => {
+                f.write_str("epilogue does not match any pre-instantiated visitor tree shape")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EvtError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Stage {
+    Accumulator,
+    Binary,
+    BinaryActivation,
+    Activation,
+    ActivationBinary,
+    Invalid,
+}
+
+/// Builds an [`EvtProgram`] from a chain of steps applied to the accumulator.
+///
+/// Supported chains, with any number of [`scale`](Self::scale) steps where
+/// shown:
+///
+/// - `scale* binary [activation] scale*`
+/// - `scale* activation scale* [binary]`
+#[derive(Clone, Copy, Debug)]
+pub struct EvtBuilder {
+    stage: Stage,
+    shape: EvtShape,
+    op: BinaryOp,
+    activation: Activation,
+    acc_scale: f32,
+    out_scale: f32,
+    aux: AuxTensor,
+}
+
+impl Default for EvtBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EvtBuilder {
+    /// Start from the raw accumulator.
+    pub const fn new() -> Self {
+        Self {
+            stage: Stage::Accumulator,
+            shape: EvtShape::AuxThenActivation,
+            op: BinaryOp::Add,
+            activation: Activation::Identity,
+            acc_scale: 1.0,
+            out_scale: 1.0,
+            aux: AuxTensor::new(core::ptr::null(), 0),
+        }
+    }
+
+    /// Multiply the current value by `factor`.
+    pub fn scale(mut self, factor: f32) -> Self {
+        match self.stage {
+            Stage::Accumulator => self.acc_scale *= factor,
+            // Scaling ahead of a later activation cannot be expressed, so an
+            // activation is no longer allowed after this.
+            Stage::Binary => {
+                self.out_scale *= factor;
+                self.stage = Stage::BinaryActivation;
+            }
+            Stage::BinaryActivation | Stage::Activation => self.out_scale *= factor,
+            Stage::ActivationBinary | Stage::Invalid => self.stage = Stage::Invalid,
+        }
+        self
+    }
+
+    /// Combine the current value with `aux` as `op(value, aux)`.
+    pub fn binary(mut self, op: BinaryOp, aux: AuxTensor) -> Self {
+        self.stage = match self.stage {
+            Stage::Accumulator => {
+                self.shape = EvtShape::AuxThenActivation;
+                Stage::Binary
+            }
+            Stage::Activation => {
+                self.shape = EvtShape::ActivationThenAux;
+                Stage::ActivationBinary
+            }
+            _ => Stage::Invalid,
+        };
+        self.op = op;
+        self.aux = aux;
+        self
+    }
+
+    /// Apply `activation` to the current value.
+    pub fn activation(mut self, activation: Activation) -> Self {
+        self.stage = match self.stage {
+            Stage::Accumulator => {
+                self.shape = EvtShape::ActivationThenAux;
+                Stage::Activation
+            }
+            Stage::Binary => Stage::BinaryActivation,
+            _ => Stage::Invalid,
+        };
+        self.activation = activation;
+        self
+    }
+
+    /// Store the result to the row-major `M x N` tensor `d`.
+    pub fn store(self, d: *mut c_void, ldd: i64) -> Result<EvtProgram, EvtError> {
+        if self.stage == Stage::Invalid {
+            return Err(EvtError::UnsupportedShape);
+        }
+        Ok(EvtProgram {
+            shape: self.shape,
+            op: self.op,
+            activation: self.activation,
+            acc_scale: self.acc_scale,
+            aux_scale: self.aux.scale,
+            out_scale: self.out_scale,
+            aux: self.aux.ptr,
+            ldaux: self.aux.ld,
+            d,
+            ldd,
+        })
+    }
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_gemm_evt`] needs.
+    pub fn cutlass_sys_gemm_evt_workspace_size(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+        program: *const EvtProgram,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_gemm_evt`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_gemm_evt_can_implement(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+        program: *const EvtProgram,
+    ) -> Status;
+
+    /// Launch a GEMM whose epilogue is `program`.
+    ///
+    /// Only the problem shape, element type, `lda`/`ldb`, scheduler and
+    /// `split_k_slices` of `config` are used; `alpha`, `beta`, `ldc`, `ldd`,
+    /// bias and activation are replaced by the program.
+    pub fn cutlass_sys_gemm_evt(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+        program: *const EvtProgram,
+        a: *const c_void,
+        b: *const c_void,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
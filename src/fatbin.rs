@@ -0,0 +1,226 @@
+//! Multi-arch bundles of device code (`fatbin` feature).
+//!
+//! A [`FatbinBuilder`], typically run from a build script, packs cubins and
+//! PTX compiled for several SM versions into one blob, which the crate can
+//! then embed with `include_bytes!`. At runtime [`Fatbin::select`] picks the
+//! image a device should run, by the same rule the CUDA runtime applies to
+//! the shim's own fatbin: SASS for the same major version and no newer
+//! minor, otherwise the newest PTX no newer than the device, which the
+//! driver JIT-compiles. With the `driver` feature, [`Fatbin::load`] also
+//! loads the picked image as a `module::Module`.
+//!
+//! The layout is this crate's own, not NVIDIA's fatbinary format, so it can
+//! be built without `fatbinary` and inspected without the driver: an 8-byte
+//! magic, a little-endian `u32` version and image count, then one 24-byte
+//! entry per image (`u32` kind, `u32` SM version, `u64` offset and size)
+//! followed by the 8-byte-aligned images. PTX images are NUL-terminated.
+
+use std::path::Path;
+use std::string::String;
+use std::vec::Vec;
+use std::{fs, io};
+
+#[cfg(feature = "driver")]
+use crate::module::{
+    device_attribute, Module, ModuleError, CU_DEVICE_ATTRIBUTE_COMPUTE_CAPABILITY_MAJOR,
+    CU_DEVICE_ATTRIBUTE_COMPUTE_CAPABILITY_MINOR,
+};
+use crate::types::Status;
+
+const MAGIC: &[u8; 8] = b"CSFATBIN";
+const VERSION: u32 = 1;
+const HEADER_SIZE: usize = 16;
+const ENTRY_SIZE: usize = 24;
+
+/// Kind of code an image holds.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ImageKind {
+    /// SASS for one SM version, as produced by `nvcc -cubin` or NVRTC.
+    Cubin = 0,
+    /// PTX, JIT-compiled by the driver for its SM version and newer.
+    Ptx = 1,
+}
+
+/// One image of a [`Fatbin`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FatbinImage<'a> {
+    pub kind: ImageKind,
+    /// SM version the image was compiled for, e.g. `86`.
+    pub arch: i32,
+    /// The image; NUL-terminated for PTX.
+    pub data: &'a [u8],
+}
+
+/// Packs cubins and PTX for several archs into one blob.
+#[derive(Clone, Debug, Default)]
+pub struct FatbinBuilder {
+    images: Vec<(ImageKind, i32, Vec<u8>)>,
+}
+
+impl FatbinBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a cubin compiled for SM version `arch`.
+    pub fn cubin(mut self, arch: i32, cubin: impl Into<Vec<u8>>) -> Self {
+        self.images.push((ImageKind::Cubin, arch, cubin.into()));
+        self
+    }
+
+    /// Adds PTX compiled for SM version `arch`.
+    pub fn ptx(mut self, arch: i32, ptx: impl Into<String>) -> Self {
+        let mut ptx = ptx.into().into_bytes();
+        if ptx.last() != Some(&0) {
+            ptx.push(0);
+        }
+        self.images.push((ImageKind::Ptx, arch, ptx));
+        self
+    }
+
+    /// Adds the image at `path` compiled for SM version `arch`: PTX for a
+    /// `.ptx` file, a cubin otherwise.
+    pub fn file(self, arch: i32, path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        if path.extension().is_some_and(|ext| ext == "ptx") {
+            let ptx = fs::read_to_string(path)?;
+            Ok(self.ptx(arch, ptx))
+        } else {
+            let cubin = fs::read(path)?;
+            Ok(self.cubin(arch, cubin))
+        }
+    }
+
+    /// The blob.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut offset = align8(HEADER_SIZE + ENTRY_SIZE * self.images.len());
+        let mut blob = Vec::with_capacity(offset);
+        blob.extend_from_slice(MAGIC);
+        blob.extend_from_slice(&VERSION.to_le_bytes());
+        blob.extend_from_slice(&(self.images.len() as u32).to_le_bytes());
+        for (kind, arch, data) in &self.images {
+            blob.extend_from_slice(&(*kind as u32).to_le_bytes());
+            blob.extend_from_slice(&(*arch as u32).to_le_bytes());
+            blob.extend_from_slice(&(offset as u64).to_le_bytes());
+            blob.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            offset = align8(offset + data.len());
+        }
+        for (_, _, data) in &self.images {
+            blob.resize(align8(blob.len()), 0);
+            blob.extend_from_slice(data);
+        }
+        blob
+    }
+
+    /// Writes the blob to `path`, e.g. in `OUT_DIR` for `include_bytes!`.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+}
+
+fn align8(n: usize) -> usize {
+    (n + 7) & !7
+}
+
+/// A parsed blob of a [`FatbinBuilder`], borrowing its images.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fatbin<'a> {
+    images: Vec<FatbinImage<'a>>,
+}
+
+impl<'a> Fatbin<'a> {
+    /// Parses `blob`; [`Status::ErrorInvalidProblem`] if it is not a
+    /// well-formed blob of this version.
+    pub fn parse(blob: &'a [u8]) -> Result<Self, Status> {
+        let invalid = Status::ErrorInvalidProblem;
+        if blob.len() < HEADER_SIZE || &blob[..8] != MAGIC || read_u32(blob, 8) != VERSION {
+            return Err(invalid);
+        }
+        let count = read_u32(blob, 12) as usize;
+        let end = count
+            .checked_mul(ENTRY_SIZE)
+            .and_then(|size| size.checked_add(HEADER_SIZE))
+            .ok_or(invalid)?;
+        let entries = blob.get(HEADER_SIZE..end).ok_or(invalid)?;
+        let images = entries
+            .chunks_exact(ENTRY_SIZE)
+            .map(|entry| {
+                let kind = match read_u32(entry, 0) {
+                    0 => ImageKind::Cubin,
+                    1 => ImageKind::Ptx,
+                    _ => return Err(invalid),
+                };
+                let offset = read_u64(entry, 8) as usize;
+                let size = read_u64(entry, 16) as usize;
+                let data = blob
+                    .get(offset..offset.checked_add(size).ok_or(invalid)?)
+                    .ok_or(invalid)?;
+                Ok(FatbinImage {
+                    kind,
+                    arch: read_u32(entry, 4) as i32,
+                    data,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { images })
+    }
+
+    /// Every image, in the order they were added.
+    pub fn images(&self) -> &[FatbinImage<'a>] {
+        &self.images
+    }
+
+    /// The image a device of compute capability `compute_capability`
+    /// (`major * 10 + minor`) should run: the cubin of the newest arch with
+    /// the same major version and no newer minor, else the PTX of the newest
+    /// arch no newer than the device. `None` if neither exists.
+    pub fn select(&self, compute_capability: i32) -> Option<FatbinImage<'a>> {
+        let (major, minor) = (compute_capability / 10, compute_capability % 10);
+        let newest = |kind: ImageKind, runs: &dyn Fn(i32) -> bool| {
+            self.images
+                .iter()
+                .filter(|image| image.kind == kind && runs(image.arch))
+                .max_by_key(|image| image.arch)
+                .copied()
+        };
+        newest(ImageKind::Cubin, &|arch| {
+            arch / 10 == major && arch % 10 <= minor
+        })
+        .or_else(|| newest(ImageKind::Ptx, &|arch| arch <= compute_capability))
+    }
+
+    /// Loads the image [`select`](Self::select) picks for device ordinal
+    /// `device`, or fails with [`Status::ErrorArchMismatch`] if there is
+    /// none.
+    #[cfg(feature = "driver")]
+    pub fn load(&self, device: i32) -> Result<Module, ModuleError> {
+        let major = device_attribute(device, CU_DEVICE_ATTRIBUTE_COMPUTE_CAPABILITY_MAJOR)?;
+        let minor = device_attribute(device, CU_DEVICE_ATTRIBUTE_COMPUTE_CAPABILITY_MINOR)?;
+        let image = self
+            .select(major * 10 + minor)
+            .ok_or(Status::ErrorArchMismatch)?;
+        // An embedded blob is only byte-aligned; the driver wants 8 bytes
+        let mut aligned = std::vec![0u64; image.data.len().div_ceil(8)];
+        // SAFETY: `aligned` holds at least `image.data.len()` bytes.
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                image.data.as_ptr(),
+                aligned.as_mut_ptr().cast::<u8>(),
+                image.data.len(),
+            );
+        }
+        // SAFETY: `aligned` is initialized `u64`s, viewed as their bytes.
+        let bytes =
+            unsafe { core::slice::from_raw_parts(aligned.as_ptr().cast::<u8>(), image.data.len()) };
+        Module::load(device, bytes)
+    }
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+}
@@ -0,0 +1,139 @@
+//! The raw `extern "C"` entry points of the shim, in one place.
+//!
+//! Each entry point is also declared next to the types it takes (e.g.
+//! [`cutlass_sys_gemm`] in [`gemm`](crate::gemm)), and most callers want the
+//! checked methods there instead: `launch` on each config (e.g.
+//! [`GemmConfig::launch`](crate::gemm::GemmConfig::launch)) calls the entry
+//! point and returns `Result<(), CutlassError>`, with the pointer and stream
+//! requirements of the call spelled out in its `# Safety` section. This
+//! module is for bindings generators and callers that manage the
+//! [`Status`](crate::types::Status) themselves.
+
+pub use crate::b2b_conv::{
+    cutlass_sys_b2b_conv2d_fprop, cutlass_sys_b2b_conv2d_fprop_can_implement,
+    cutlass_sys_b2b_conv2d_fprop_workspace_size,
+};
+
+pub use crate::b2b_gemm::{
+    cutlass_sys_b2b_gemm, cutlass_sys_b2b_gemm_can_implement, cutlass_sys_b2b_gemm_workspace_size,
+};
+
+pub use crate::blas3::{
+    cutlass_sys_hemm, cutlass_sys_hemm_can_implement, cutlass_sys_hemm_workspace_size,
+    cutlass_sys_her2k, cutlass_sys_her2k_can_implement, cutlass_sys_her2k_workspace_size,
+    cutlass_sys_herk, cutlass_sys_herk_can_implement, cutlass_sys_herk_workspace_size,
+    cutlass_sys_symm, cutlass_sys_symm_can_implement, cutlass_sys_symm_workspace_size,
+    cutlass_sys_syr2k, cutlass_sys_syr2k_can_implement, cutlass_sys_syr2k_workspace_size,
+    cutlass_sys_syrk, cutlass_sys_syrk_can_implement, cutlass_sys_syrk_workspace_size,
+    cutlass_sys_trmm, cutlass_sys_trmm_can_implement, cutlass_sys_trmm_workspace_size,
+};
+
+#[cfg(feature = "sm100")]
+pub use crate::block_scaled::{
+    cutlass_sys_block_scaled_gemm, cutlass_sys_block_scaled_gemm_can_implement,
+    cutlass_sys_block_scaled_gemm_workspace_size, cutlass_sys_block_scaled_sfa_size,
+    cutlass_sys_block_scaled_sfb_size,
+};
+
+pub use crate::context::{
+    cutlass_sys_compiled_archs, cutlass_sys_context_check_arch, cutlass_sys_context_init,
+};
+
+pub use crate::conv::{
+    cutlass_sys_conv2d_dgrad, cutlass_sys_conv2d_dgrad_can_implement,
+    cutlass_sys_conv2d_dgrad_workspace_size, cutlass_sys_conv2d_fprop,
+    cutlass_sys_conv2d_fprop_can_implement, cutlass_sys_conv2d_fprop_workspace_size,
+    cutlass_sys_conv2d_wgrad, cutlass_sys_conv2d_wgrad_can_implement,
+    cutlass_sys_conv2d_wgrad_workspace_size, cutlass_sys_conv3d_dgrad,
+    cutlass_sys_conv3d_dgrad_can_implement, cutlass_sys_conv3d_dgrad_workspace_size,
+    cutlass_sys_conv3d_fprop, cutlass_sys_conv3d_fprop_can_implement,
+    cutlass_sys_conv3d_fprop_workspace_size, cutlass_sys_conv3d_wgrad,
+    cutlass_sys_conv3d_wgrad_can_implement, cutlass_sys_conv3d_wgrad_workspace_size,
+};
+
+pub use crate::conv_quant::{
+    cutlass_sys_conv2d_fprop_quant, cutlass_sys_conv2d_fprop_quant_can_implement,
+    cutlass_sys_conv2d_fprop_quant_workspace_size,
+};
+
+pub use crate::ell_gemm::{
+    cutlass_sys_ell_gemm, cutlass_sys_ell_gemm_can_implement, cutlass_sys_ell_gemm_workspace_size,
+};
+
+pub use crate::error::{
+    cutlass_sys_cuda_error_name, cutlass_sys_cuda_error_string, cutlass_sys_status_string,
+};
+
+pub use crate::evt::{
+    cutlass_sys_gemm_evt, cutlass_sys_gemm_evt_can_implement, cutlass_sys_gemm_evt_workspace_size,
+};
+
+#[cfg(feature = "reference")]
+pub use crate::fill::{
+    cutlass_sys_block_compare_equal, cutlass_sys_block_compare_relatively_equal,
+    cutlass_sys_tensor_fill, cutlass_sys_tensor_fill_gaussian, cutlass_sys_tensor_fill_uniform,
+};
+
+pub use crate::gemm::{
+    cutlass_sys_gemm, cutlass_sys_gemm_array, cutlass_sys_gemm_array_can_implement,
+    cutlass_sys_gemm_array_workspace_size, cutlass_sys_gemm_can_implement, cutlass_sys_gemm_gather,
+    cutlass_sys_gemm_gather_can_implement, cutlass_sys_gemm_gather_workspace_size,
+    cutlass_sys_gemm_resources, cutlass_sys_gemm_tile_shape, cutlass_sys_gemm_workspace_size,
+};
+
+pub use crate::gemm_layernorm::{
+    cutlass_sys_gemm_layernorm, cutlass_sys_gemm_layernorm_can_implement,
+    cutlass_sys_gemm_layernorm_workspace_size,
+};
+
+pub use crate::gemm_softmax::{
+    cutlass_sys_gemm_softmax, cutlass_sys_gemm_softmax_can_implement,
+    cutlass_sys_gemm_softmax_workspace_size,
+};
+
+pub use crate::gemv::{
+    cutlass_sys_gemv, cutlass_sys_gemv_can_implement, cutlass_sys_gemv_workspace_size,
+};
+
+#[cfg(feature = "library")]
+pub use crate::manifest::{
+    cutlass_sys_library_handle_create, cutlass_sys_library_handle_destroy,
+    cutlass_sys_library_handle_gemm, cutlass_sys_library_handle_last_operation,
+    cutlass_sys_library_handle_set_stream, cutlass_sys_library_handle_set_workspace_size,
+    cutlass_sys_library_handle_workspace_size, cutlass_sys_manifest_create,
+    cutlass_sys_manifest_destroy, cutlass_sys_manifest_operation,
+    cutlass_sys_manifest_operation_count, cutlass_sys_operation_description,
+    cutlass_sys_operation_gemm, cutlass_sys_operation_gemm_can_implement,
+    cutlass_sys_operation_gemm_description, cutlass_sys_operation_gemm_workspace_size,
+};
+
+pub use crate::planar_complex::{
+    cutlass_sys_planar_complex_gemm, cutlass_sys_planar_complex_gemm_can_implement,
+    cutlass_sys_planar_complex_gemm_workspace_size,
+};
+
+pub use crate::reduction::{
+    cutlass_sys_reduce_split_k, cutlass_sys_reduce_split_k_can_implement,
+    cutlass_sys_reduce_split_k_workspace_size, cutlass_sys_reduce_tensor,
+    cutlass_sys_reduce_tensor_can_implement, cutlass_sys_reduce_tensor_workspace_size,
+};
+
+#[cfg(feature = "reference")]
+pub use crate::reference::{
+    cutlass_sys_reference_conv_device_dgrad, cutlass_sys_reference_conv_device_fprop,
+    cutlass_sys_reference_conv_device_wgrad, cutlass_sys_reference_conv_dgrad,
+    cutlass_sys_reference_conv_fprop, cutlass_sys_reference_conv_wgrad, cutlass_sys_reference_gemm,
+    cutlass_sys_reference_gemm_device,
+};
+
+pub use crate::sparse::{
+    cutlass_sys_sparse_compress, cutlass_sys_sparse_gemm, cutlass_sys_sparse_gemm_can_implement,
+    cutlass_sys_sparse_gemm_workspace_size, cutlass_sys_sparse_metadata_size,
+};
+
+pub use crate::transform::{
+    cutlass_sys_convert, cutlass_sys_convert_can_implement, cutlass_sys_convert_workspace_size,
+    cutlass_sys_layout_transform, cutlass_sys_layout_transform_can_implement,
+    cutlass_sys_layout_transform_workspace_size, cutlass_sys_permute,
+    cutlass_sys_permute_can_implement, cutlass_sys_permute_workspace_size,
+};
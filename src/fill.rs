@@ -0,0 +1,275 @@
+//! Device tensor initialization and comparison (`reference` feature).
+//!
+//! Binds the `cutlass::reference::device` fill and block-compare helpers, so
+//! benchmarks and tests can initialize device operands and check results
+//! without writing CUDA. The fills take a row- or column-major
+//! [`TensorView`] and leave the gaps between its rows (columns) untouched;
+//! the comparisons take contiguous blocks and synchronize the stream to
+//! return their answer.
+//!
+//! `element` names the type of the data, `F16`, `BF16`, `F32`, `S8` or `F64`,
+//! and must be the size of `T`; values are given as `f64` and converted.
+//! Random fills are deterministic in `seed`. A non-negative `bits` rounds each
+//! random value to that many fractional bits, so that small integers
+//! (`bits = 0`) make floating-point GEMM results exact and reproducible.
+
+use core::ffi::c_void;
+use core::mem::size_of;
+
+use crate::context::CutlassContext;
+use crate::coord::MatrixCoord;
+use crate::library::{LayoutTypeID, NumericTypeID};
+use crate::tensor::TensorView;
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Distribution of the values written by [`fill_random`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Distribution {
+    /// Uniform over `[min, max]`.
+    Uniform { min: f64, max: f64 },
+    /// Normal with the given mean and standard deviation.
+    Gaussian { mean: f64, stddev: f64 },
+}
+
+/// Tolerance of [`block_relatively_equal`]: elements match when their
+/// difference is within `epsilon` relative to their magnitude, magnitudes
+/// below `nonzero_floor` counting as zero.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance {
+    pub epsilon: f64,
+    pub nonzero_floor: f64,
+}
+
+impl Tolerance {
+    pub const fn new(epsilon: f64, nonzero_floor: f64) -> Self {
+        Self {
+            epsilon,
+            nonzero_floor,
+        }
+    }
+}
+
+/// Fills `view` with `value`.
+///
+/// # Safety
+///
+/// `view` must address device memory of `context`'s device, and `stream` be
+/// a stream of that device.
+pub unsafe fn fill<T>(
+    context: &CutlassContext,
+    element: DataType,
+    view: &TensorView<T>,
+    value: f64,
+    stream: cudaStream_t,
+) -> Result<(), Status> {
+    let (ptr, extent, ld) = row_major(element, view)?;
+    // SAFETY: the caller vouches for the view and stream.
+    unsafe {
+        cutlass_sys_tensor_fill(
+            context,
+            element,
+            ptr,
+            extent.row,
+            extent.column,
+            ld,
+            value,
+            stream,
+        )
+    }
+    .result()
+}
+
+/// Fills `view` with values drawn from `distribution`, rounded to `bits`
+/// fractional bits unless negative.
+///
+/// # Safety
+///
+/// As for [`fill`].
+pub unsafe fn fill_random<T>(
+    context: &CutlassContext,
+    element: DataType,
+    view: &TensorView<T>,
+    distribution: Distribution,
+    seed: u64,
+    bits: i32,
+    stream: cudaStream_t,
+) -> Result<(), Status> {
+    let (ptr, extent, ld) = row_major(element, view)?;
+    let (rows, columns) = (extent.row, extent.column);
+    // SAFETY: the caller vouches for the view and stream.
+    unsafe {
+        match distribution {
+            Distribution::Uniform { min, max } => cutlass_sys_tensor_fill_uniform(
+                context, element, ptr, rows, columns, ld, seed, min, max, bits, stream,
+            ),
+            Distribution::Gaussian { mean, stddev } => cutlass_sys_tensor_fill_gaussian(
+                context, element, ptr, rows, columns, ld, seed, mean, stddev, bits, stream,
+            ),
+        }
+    }
+    .result()
+}
+
+/// Whether the `count` elements at `a` and `b` are exactly equal, waiting
+/// for `stream`.
+///
+/// # Safety
+///
+/// `a` and `b` must hold `count` elements of device memory of `context`'s
+/// device, and `stream` be a stream of that device.
+pub unsafe fn block_equal<T>(
+    context: &CutlassContext,
+    element: DataType,
+    a: *const T,
+    b: *const T,
+    count: usize,
+    stream: cudaStream_t,
+) -> Result<bool, Status> {
+    check_type::<T>(element)?;
+    let mut equal = false;
+    // SAFETY: the caller vouches for the blocks and stream.
+    unsafe {
+        cutlass_sys_block_compare_equal(
+            context,
+            element,
+            a.cast(),
+            b.cast(),
+            count,
+            &mut equal,
+            stream,
+        )
+    }
+    .result()?;
+    Ok(equal)
+}
+
+/// Whether the `count` elements at `a` and `b` agree within `tolerance`,
+/// waiting for `stream`.
+///
+/// # Safety
+///
+/// As for [`block_equal`].
+pub unsafe fn block_relatively_equal<T>(
+    context: &CutlassContext,
+    element: DataType,
+    a: *const T,
+    b: *const T,
+    count: usize,
+    tolerance: Tolerance,
+    stream: cudaStream_t,
+) -> Result<bool, Status> {
+    check_type::<T>(element)?;
+    let mut equal = false;
+    // SAFETY: the caller vouches for the blocks and stream.
+    unsafe {
+        cutlass_sys_block_compare_relatively_equal(
+            context,
+            element,
+            a.cast(),
+            b.cast(),
+            count,
+            tolerance.epsilon,
+            tolerance.nonzero_floor,
+            &mut equal,
+            stream,
+        )
+    }
+    .result()?;
+    Ok(equal)
+}
+
+/// `view` as the row-major matrix the shim fills: a column-major view is the
+/// row-major view of its transpose.
+fn row_major<T>(
+    element: DataType,
+    view: &TensorView<T>,
+) -> Result<(*mut c_void, MatrixCoord, i64), Status> {
+    check_type::<T>(element)?;
+    view.validate()?;
+    let extent = match view.layout() {
+        LayoutTypeID::ColumnMajor => MatrixCoord::new(view.extent.column, view.extent.row),
+        _ => view.extent,
+    };
+    Ok((view.ptr().cast(), extent, view.stride()))
+}
+
+/// Checks that `T` is the size of `element`.
+fn check_type<T>(element: DataType) -> Result<(), Status> {
+    if size_of::<T>() * 8 != NumericTypeID::from(element).size_in_bits() as usize {
+        return Err(Status::ErrorInvalidDataType);
+    }
+    Ok(())
+}
+
+extern "C" {
+    /// Fills a row-major `rows x columns` matrix with leading dimension `ld`
+    /// with `value` on `stream`.
+    pub fn cutlass_sys_tensor_fill(
+        context: *const CutlassContext,
+        element: DataType,
+        ptr: *mut c_void,
+        rows: i32,
+        columns: i32,
+        ld: i64,
+        value: f64,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Fills a row-major matrix with uniform values in `[min, max]`, rounded
+    /// to `bits` fractional bits unless negative.
+    pub fn cutlass_sys_tensor_fill_uniform(
+        context: *const CutlassContext,
+        element: DataType,
+        ptr: *mut c_void,
+        rows: i32,
+        columns: i32,
+        ld: i64,
+        seed: u64,
+        min: f64,
+        max: f64,
+        bits: i32,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Fills a row-major matrix with normal values, rounded to `bits`
+    /// fractional bits unless negative.
+    pub fn cutlass_sys_tensor_fill_gaussian(
+        context: *const CutlassContext,
+        element: DataType,
+        ptr: *mut c_void,
+        rows: i32,
+        columns: i32,
+        ld: i64,
+        seed: u64,
+        mean: f64,
+        stddev: f64,
+        bits: i32,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Writes whether `count` contiguous elements at `a` and `b` are equal to
+    /// `equal`. Synchronizes `stream`.
+    pub fn cutlass_sys_block_compare_equal(
+        context: *const CutlassContext,
+        element: DataType,
+        a: *const c_void,
+        b: *const c_void,
+        count: usize,
+        equal: *mut bool,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// As [`cutlass_sys_block_compare_equal`], within a relative `epsilon`
+    /// and treating magnitudes below `nonzero_floor` as zero.
+    pub fn cutlass_sys_block_compare_relatively_equal(
+        context: *const CutlassContext,
+        element: DataType,
+        a: *const c_void,
+        b: *const c_void,
+        count: usize,
+        epsilon: f64,
+        nonzero_floor: f64,
+        equal: *mut bool,
+        stream: cudaStream_t,
+    ) -> Status;
+}
@@ -0,0 +1,1200 @@
+//! Dense GEMM:
+//!
+//! ```text
+//! D = activation(alpha * row_scale * col_scale * (A * B) + beta * C + bias) + zero_point
+//! ```
+//!
+//! where every term past `alpha * A * B + beta * C` is optional. The scale
+//! vectors make alpha per-row or per-column, and [`GemmConfig::beta_mode`]
+//! does the same for beta, e.g. for per-example loss scaling.
+//!
+//! Without scales, beta vectors or a zero point, [`GemmConfig::aux_op`] can
+//! instead combine the activated result with a full `M x N` aux tensor,
+//! `D = op(activation(..), aux)`, which covers gating (`Mul`), masking (`Mul`
+//! by a 0/1 mask) and residual adds (`Add`) without an [`evt`](crate::evt)
+//! program.
+//!
+//! `C` and `D` are row-major. `A` and `B` default to row-major, except that
+//! the `S8` path expects `B` in column-major order (i.e. `B^T` stored
+//! row-major, `N x K`) as required by the int8 tensor-core instructions.
+//! [`GemmConfig::layout_a`] and [`GemmConfig::layout_b`] select either
+//! order for the floating-point types (NN, NT, TN or TT), so column-major
+//! operands need no transposition pass; a column-major `D` is the row-major
+//! `D^T = B^T * A^T`, i.e. the problem with `m` / `n` and the operands
+//! swapped and their layouts flipped. Layouts other than the default need a
+//! plain epilogue and the default [`GemmTypes`].
+//!
+//! `C` and `D` have [`GemmConfig::output`] as their element type, which
+//! together with [`GemmConfig::accumulator`] defaults to the types the
+//! operand type implies (see [`DataType`]). [`GemmTypes::SUPPORTED`] lists
+//! the combinations the kernels are compiled for; other combinations
+//! return [`Status::ErrorInvalidDataType`], and any but the default one need
+//! a plain `alpha * A * B + beta * C` epilogue.
+//!
+//! The exception are the down-converted outputs of the `F16` and `BF16`
+//! operands, whose `f32` results are multiplied by
+//! [`GemmConfig::out_scale`] and cast to fp8 or int8 (saturating) in the
+//! epilogue, after any bias and activation:
+//!
+//! ```text
+//! D = convert(out_scale * activation(alpha * (A * B) + beta * C + bias))
+//! ```
+//!
+//! `C` keeps the operand type there, and a non-null [`GemmOperands::amax`]
+//! receives the largest absolute value of the result before `out_scale`,
+//! from which the caller derives the scale of the next step. FP8 GEMMs
+//! then chain through fp8 tensors with no intermediate f16 one; on the host
+//! their elements are [`F8E4M3`](crate::types::F8E4M3) and
+//! [`F8E5M2`](crate::types::F8E5M2).
+
+use core::ffi::c_void;
+use core::fmt;
+
+use crate::context::CutlassContext;
+use crate::coord::{GemmCoord, MatrixCoord};
+use crate::error::{CutlassError, Operand, ProblemError};
+use crate::library::{LayoutTypeID, NumericTypeID};
+use crate::tensor::TensorView;
+use crate::types::{cudaStream_t, DataType, KernelResources, Status};
+
+/// How output tiles are distributed across the SMs of the device.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TileScheduler {
+    /// One CTA per output tile (optionally split along K by `split_k_slices`).
+    #[default]
+    DataParallel = 0,
+    /// Stream-K: the MAC-loop iterations of all tiles are evenly divided
+    /// across a grid sized to the device, with partial tiles fixed up through
+    /// the workspace. Gives much better load balance when the tile count is
+    /// not a multiple of the SM count.
+    StreamK = 1,
+    /// A grid sized to the device, whose CTAs take output tiles in turn
+    /// until none are left, so launch overhead and tail effects stay fixed
+    /// however many tiles a problem has; pays off over long runs of
+    /// medium-sized problems. No split-K and a plain `alpha` / `beta`
+    /// epilogue only. On SM80-class devices it runs the grouped GEMM kernel
+    /// on one problem, which needs a few hundred bytes of workspace; the
+    /// SM90 and SM100 kernels schedule data-parallel launches this way
+    /// already.
+    Persistent = 2,
+}
+
+/// Thread block cluster shape of the SM90 kernels, whose CTAs share operand
+/// tiles through TMA multicast. Bigger clusters save L2 bandwidth but leave
+/// SMs idle when the tile count is not a multiple of the cluster size, so
+/// the best shape depends on the problem. The other kernels take only
+/// [`Default`](Self::Default): anything else makes configs the SM90 kernels
+/// don't run fail with [`Status::ErrorNotSupported`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ClusterShape {
+    /// 2x1x1 on SM90, none elsewhere.
+    #[default]
+    Default = 0,
+    Shape1x1x1 = 1,
+    Shape2x1x1 = 2,
+    Shape2x2x1 = 3,
+}
+
+/// Bias vector fused into the epilogue. The vector has the output element type
+/// (`i32` for the `S8` path).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BiasMode {
+    #[default]
+    None = 0,
+    /// A length-`n` vector added to every row of `D` (one value per column).
+    Row = 1,
+    /// A length-`m` vector added to every column of `D` (one value per row).
+    Column = 2,
+}
+
+/// Elementwise activation applied in the epilogue after the bias add.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Activation {
+    #[default]
+    Identity = 0,
+    Relu = 1,
+    /// Exact (erf-based) GELU.
+    Gelu = 2,
+    /// `x * sigmoid(x)`, a.k.a. swish.
+    Silu = 3,
+    Tanh = 4,
+}
+
+/// Per-channel dequantization scales applied to the accumulator, on top of
+/// the per-tensor `alpha`: per-row or per-column alpha vectors. Scale
+/// vectors are `f32`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ScaleMode {
+    #[default]
+    None = 0,
+    /// A length-`m` vector, one scale per row of `D`.
+    PerRow = 1,
+    /// A length-`n` vector, one scale per column of `D`.
+    PerColumn = 2,
+    /// Both vectors, as in symmetric int8 GEMMs with per-token activation
+    /// scales and per-channel weight scales.
+    PerRowAndColumn = 3,
+}
+
+/// Output zero point added just before the cast to the output type.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ZeroPointMode {
+    #[default]
+    None = 0,
+    /// [`GemmConfig::zero_point`] is added to every element.
+    PerTensor = 1,
+    /// A length-`m` `f32` vector, one zero point per row.
+    PerRow = 2,
+    /// A length-`n` `f32` vector, one zero point per column.
+    PerColumn = 3,
+}
+
+/// Where the `beta` scaling `C` comes from.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BetaMode {
+    /// [`GemmConfig::beta`] scales every element.
+    #[default]
+    PerTensor = 0,
+    /// A length-`m` `f32` vector, one beta per row, replacing
+    /// [`GemmConfig::beta`].
+    PerRow = 1,
+    /// A length-`n` `f32` vector, one beta per column, replacing
+    /// [`GemmConfig::beta`].
+    PerColumn = 2,
+}
+
+/// Elementwise operation combining the epilogue result with the aux tensor
+/// [`GemmOperands::aux`], which has the output element type (`i32` for the
+/// `S8` path).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AuxOp {
+    #[default]
+    None = 0,
+    Add = 1,
+    Mul = 2,
+    Max = 3,
+}
+
+/// Operand, accumulator and output element types of a GEMM.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GemmTypes {
+    pub element: DataType,
+    pub accumulator: DataType,
+    pub output: DataType,
+}
+
+impl GemmTypes {
+    /// Every combination the dense GEMM kernels are compiled for, default
+    /// ones included.
+    pub const SUPPORTED: &'static [GemmTypes] = &[
+        Self::new(DataType::F16),
+        Self::with(DataType::F16, DataType::F32, DataType::F32),
+        Self::with(DataType::F16, DataType::F16, DataType::F16),
+        Self::new(DataType::BF16),
+        Self::with(DataType::F16, DataType::F32, DataType::E4M3),
+        Self::with(DataType::F16, DataType::F32, DataType::E5M2),
+        Self::with(DataType::F16, DataType::F32, DataType::S8),
+        Self::with(DataType::BF16, DataType::F32, DataType::F32),
+        Self::with(DataType::BF16, DataType::F32, DataType::E4M3),
+        Self::with(DataType::BF16, DataType::F32, DataType::E5M2),
+        Self::with(DataType::BF16, DataType::F32, DataType::S8),
+        Self::new(DataType::F32),
+        Self::new(DataType::S8),
+        Self::with(DataType::S8, DataType::S32, DataType::S8),
+        Self::with(DataType::S8, DataType::S32, DataType::F32),
+    ];
+
+    /// The accumulator and output types `element` implies, the ones of the
+    /// [`DataType`] table.
+    pub const fn new(element: DataType) -> Self {
+        let (accumulator, output) = match element {
+            DataType::F16 => (DataType::F32, DataType::F16),
+            DataType::BF16 => (DataType::F32, DataType::BF16),
+            DataType::S8 => (DataType::S32, DataType::S32),
+            other => (other, other),
+        };
+        Self::with(element, accumulator, output)
+    }
+
+    pub const fn with(element: DataType, accumulator: DataType, output: DataType) -> Self {
+        Self {
+            element,
+            accumulator,
+            output,
+        }
+    }
+
+    /// Whether these are the types `element` implies.
+    pub fn is_default(&self) -> bool {
+        *self == Self::new(self.element)
+    }
+
+    /// Whether the kernels are compiled for this combination.
+    pub fn is_supported(&self) -> bool {
+        Self::SUPPORTED.contains(self)
+    }
+
+    /// Whether the `f32` result is scaled and cast down to an fp8 or int8
+    /// output in the epilogue (see the [module docs](self)).
+    pub fn is_down_converted(&self) -> bool {
+        matches!(self.element, DataType::F16 | DataType::BF16)
+            && matches!(self.output, DataType::E4M3 | DataType::E5M2 | DataType::S8)
+    }
+
+    /// Elements of `A` / `B` and of `C` / `D` the SM80 kernel for these types
+    /// accesses at once, as the shim instantiates it: the `Sm80Traits`
+    /// alignments for the default types and the epilogue visitors of
+    /// down-converted outputs, and 128 bits of the output type for the other
+    /// combinations. `F32` runs on SIMT cores, one element at a time. The
+    /// SM75 kernels share these; the SM90 and SM100 kernels need 16 bytes
+    /// but leave other leading dimensions to the SM80 ones.
+    pub fn alignment(&self) -> (i64, i64) {
+        let (ab, cd) = match self.element {
+            DataType::F16 | DataType::BF16 => (8, 8),
+            DataType::S8 => (16, 4),
+            _ => (1, 1),
+        };
+        if self.is_default() || self.is_down_converted() {
+            (ab, cd)
+        } else {
+            (
+                ab,
+                128 / NumericTypeID::from(self.output).size_in_bits() as i64,
+            )
+        }
+    }
+
+    /// Element type of `C`: the operand type for down-converted outputs,
+    /// else the output type.
+    pub fn source(&self) -> DataType {
+        if self.is_down_converted() {
+            self.element
+        } else {
+            self.output
+        }
+    }
+}
+
+/// Problem description for [`cutlass_sys_gemm`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GemmConfig {
+    pub problem: GemmCoord,
+    pub element: DataType,
+    pub accumulator: DataType,
+    /// Element type of `D`, and of `C` unless down-converted (see
+    /// [`GemmTypes::source`]).
+    pub output: DataType,
+    /// [`LayoutTypeID::RowMajor`] or [`LayoutTypeID::ColumnMajor`].
+    pub layout_a: LayoutTypeID,
+    /// [`LayoutTypeID::RowMajor`] or [`LayoutTypeID::ColumnMajor`].
+    pub layout_b: LayoutTypeID,
+    pub lda: i64,
+    pub ldb: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub alpha: f32,
+    pub beta: f32,
+    pub scheduler: TileScheduler,
+    /// Number of K partitions. With [`TileScheduler::StreamK`] a value > 1
+    /// forces that many splits instead of letting the scheduler choose.
+    pub split_k_slices: i32,
+    pub cluster_shape: ClusterShape,
+    pub bias: BiasMode,
+    pub activation: Activation,
+    pub scale: ScaleMode,
+    pub beta_mode: BetaMode,
+    pub zero_point_mode: ZeroPointMode,
+    /// Used with [`ZeroPointMode::PerTensor`].
+    pub zero_point: f32,
+    /// Applied after the activation; not combinable with [`scale`](Self::scale),
+    /// a per-row or per-column [`beta_mode`](Self::beta_mode) or
+    /// [`zero_point_mode`](Self::zero_point_mode).
+    pub aux_op: AuxOp,
+    /// Leading dimension of the row-major `M x N` aux tensor.
+    pub ldaux: i64,
+    /// Multiplies the result before its down-conversion to an fp8 or int8
+    /// [`output`](Self::output); must be 1 for every other output type.
+    pub out_scale: f32,
+    /// Check `D` against the device reference after the launch (`reference`
+    /// feature, else [`Status::ErrorNotSupported`]). Needs more workspace,
+    /// synchronizes the stream and fails with [`Status::ErrorInternal`] on a
+    /// mismatch, so it is for debugging only.
+    pub verify: bool,
+}
+
+impl GemmConfig {
+    /// A tightly packed `m x n x k` problem with the default
+    /// [`GemmTypes`] of `element`, `alpha = 1`, `beta = 0`, data-parallel
+    /// scheduling and no bias, activation, aux tensor or
+    /// quantization scaling.
+    pub const fn new(m: i32, n: i32, k: i32, element: DataType) -> Self {
+        let (layout_b, ldb) = match element {
+            DataType::S8 => (LayoutTypeID::ColumnMajor, k as i64),
+            _ => (LayoutTypeID::RowMajor, n as i64),
+        };
+        let types = GemmTypes::new(element);
+        Self {
+            problem: GemmCoord::new(m, n, k),
+            element,
+            accumulator: types.accumulator,
+            output: types.output,
+            layout_a: LayoutTypeID::RowMajor,
+            layout_b,
+            lda: k as i64,
+            ldb,
+            ldc: n as i64,
+            ldd: n as i64,
+            alpha: 1.0,
+            beta: 0.0,
+            scheduler: TileScheduler::DataParallel,
+            split_k_slices: 1,
+            cluster_shape: ClusterShape::Default,
+            bias: BiasMode::None,
+            activation: Activation::Identity,
+            scale: ScaleMode::None,
+            beta_mode: BetaMode::PerTensor,
+            zero_point_mode: ZeroPointMode::None,
+            zero_point: 0.0,
+            aux_op: AuxOp::None,
+            ldaux: n as i64,
+            out_scale: 1.0,
+            verify: false,
+        }
+    }
+
+    /// Sets [`problem`](Self::problem), the operand layouts and the leading
+    /// dimensions from views of the operands and returns their pointers,
+    /// ready for [`cutlass_sys_gemm`]. The epilogue vectors of the result are
+    /// null.
+    ///
+    /// `a` is `M x K` and `b` is `K x N`, each row- or column-major except
+    /// for `S8`, which takes a row-major `a` and a column-major `b`. `c` /
+    /// `d` are row-major `M x N`. A wrong layout returns
+    /// [`Status::ErrorInvalidLayout`] and disagreeing extents
+    /// [`Status::ErrorInvalidProblem`]; debug builds also reject null and
+    /// misaligned pointers (see [`TensorView::check_pointer`]). A
+    /// down-converted output reads `C` in the operand type, so `c` must be
+    /// `None` there ([`Status::ErrorInvalidDataType`]); set
+    /// [`GemmOperands::c`] by hand instead.
+    pub fn bind_views<T, U>(
+        &mut self,
+        a: TensorView<T>,
+        b: TensorView<T>,
+        c: Option<TensorView<U>>,
+        d: TensorView<U>,
+    ) -> Result<GemmOperands, Status> {
+        let problem = GemmCoord::new(a.extent.row, d.extent.column, a.extent.column);
+        let element_bytes = NumericTypeID::from(self.element).size_in_bits() as usize / 8;
+        let output_bytes = NumericTypeID::from(self.output).size_in_bits() as usize / 8;
+        if self.element == DataType::S8
+            && (a.layout() != LayoutTypeID::RowMajor || b.layout() != LayoutTypeID::ColumnMajor)
+        {
+            return Err(Status::ErrorInvalidLayout);
+        }
+        if c.is_some() && self.types().is_down_converted() {
+            return Err(Status::ErrorInvalidDataType);
+        }
+
+        check_operand(&a, a.layout(), problem.mk(), element_bytes)?;
+        check_operand(&b, b.layout(), problem.kn(), element_bytes)?;
+        if let Some(c) = &c {
+            check_operand(c, LayoutTypeID::RowMajor, problem.mn(), output_bytes)?;
+        }
+        check_operand(&d, LayoutTypeID::RowMajor, problem.mn(), output_bytes)?;
+
+        self.problem = problem;
+        self.layout_a = a.layout();
+        self.layout_b = b.layout();
+        self.lda = a.stride();
+        self.ldb = b.stride();
+        self.ldc = c.map_or(d.stride(), |c| c.stride());
+        self.ldd = d.stride();
+        Ok(GemmOperands::new(
+            a.ptr().cast_const().cast(),
+            b.ptr().cast_const().cast(),
+            c.map_or(core::ptr::null(), |c| c.ptr().cast_const().cast()),
+            d.ptr().cast(),
+        ))
+    }
+
+    /// The operand, accumulator and output types of this config.
+    pub const fn types(&self) -> GemmTypes {
+        GemmTypes::with(self.element, self.accumulator, self.output)
+    }
+
+    /// Whether [`cutlass_sys_gemm`] routes this config to the
+    /// [GEMV kernel](crate::gemv): an `n == 1` problem of the default types
+    /// and layouts with unit `ldb` (any `ldb` for `S8`), `ldc` and `ldd`,
+    /// data-parallel scheduling, no split-K or cluster shape and no fused
+    /// epilogue.
+    pub fn runs_on_gemv(&self) -> bool {
+        let layout_b = match self.element {
+            DataType::S8 => LayoutTypeID::ColumnMajor,
+            _ => LayoutTypeID::RowMajor,
+        };
+        self.problem.n == 1
+            && self.scheduler == TileScheduler::DataParallel
+            && self.split_k_slices == 1
+            && self.cluster_shape == ClusterShape::Default
+            && self.bias == BiasMode::None
+            && self.activation == Activation::Identity
+            && self.scale == ScaleMode::None
+            && self.beta_mode == BetaMode::PerTensor
+            && self.zero_point_mode == ZeroPointMode::None
+            && self.aux_op == AuxOp::None
+            && self.out_scale == 1.0
+            && self.types().is_default()
+            && self.layout_a == LayoutTypeID::RowMajor
+            && self.layout_b == layout_b
+            && (self.element == DataType::S8 || self.ldb == 1)
+            && self.ldc == 1
+            && self.ldd == 1
+    }
+
+    /// Checks the leading dimensions on the host: each must cover the
+    /// contiguous extent of its operand's layout and be a multiple of the
+    /// elements the SM80 kernel for these types accesses at once (see
+    /// [`GemmTypes::alignment`]). `C` is only checked
+    /// when the epilogue reads it, and the aux tensor when
+    /// [`aux_op`](Self::aux_op) is set. A config that
+    /// [`runs_on_gemv`](Self::runs_on_gemv) has no alignment requirement,
+    /// so only its extents are checked.
+    ///
+    /// [`supports`](Self::supports) and [`launch`](Self::launch) run this
+    /// first.
+    pub fn check(&self) -> Result<(), ProblemError> {
+        self.check_on(self.runs_on_gemv())
+    }
+
+    /// [`check`](Self::check) for a launch that runs on the GEMV kernel
+    /// when `gemv` is set, and on a tiled kernel otherwise. The array and
+    /// gather launches never route to GEMV.
+    fn check_on(&self, gemv: bool) -> Result<(), ProblemError> {
+        let p = self.problem;
+        let (access_ab, access_cd) = self.types().alignment();
+        let contiguous = |layout: LayoutTypeID, rows: i32, columns: i32| match layout {
+            LayoutTypeID::ColumnMajor => rows,
+            _ => columns,
+        };
+        let reads_c = self.beta != 0.0 || self.beta_mode != BetaMode::PerTensor;
+        let operands = [
+            (
+                Operand::A,
+                true,
+                self.lda,
+                contiguous(self.layout_a, p.m, p.k),
+                access_ab,
+            ),
+            (
+                Operand::B,
+                true,
+                self.ldb,
+                contiguous(self.layout_b, p.k, p.n),
+                access_ab,
+            ),
+            (Operand::C, reads_c, self.ldc, p.n, access_cd),
+            (Operand::D, true, self.ldd, p.n, access_cd),
+            (
+                Operand::Aux,
+                self.aux_op != AuxOp::None,
+                self.ldaux,
+                p.n,
+                access_cd,
+            ),
+        ];
+        for (operand, used, ld, extent, required) in operands {
+            if !used {
+                continue;
+            }
+            if ld < extent as i64 {
+                return Err(ProblemError::LeadingDimension {
+                    operand,
+                    required: extent as i64,
+                    got: ld,
+                });
+            }
+            if !gemv && ld % required != 0 {
+                return Err(ProblemError::MisalignedOperand {
+                    operand,
+                    required,
+                    got: ld,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_gemm`] needs for this config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_gemm_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_gemm`] can run this config before any operand
+    /// is allocated, returning the status the launch would fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        self.check().map_err(ProblemError::status)?;
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_gemm_can_implement(context, self) }.result()
+    }
+
+    /// Launches this config on `stream` through [`cutlass_sys_gemm`], after
+    /// [`check`](Self::check), whose failure is returned as
+    /// [`CutlassError::Problem`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem with this config's leading dimensions
+    /// (plus any bias, scale or zero-point vectors its epilogue reads), and
+    /// stay valid until the launch completes on `stream`, a stream of that
+    /// device. `workspace` must hold [`workspace_size`](Self::workspace_size)
+    /// bytes of device memory, or may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &GemmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        self.check()?;
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe { cutlass_sys_gemm(context, self, operands, workspace, stream) })
+    }
+
+    /// Tile one CTA computes in the kernel [`cutlass_sys_gemm`] runs this
+    /// config on, `0 x 0 x 0` for the GEMV kernel. Fails like
+    /// [`supports`](Self::supports) for an unsupported config.
+    pub fn tile_shape(&self, context: &CutlassContext) -> Result<GemmCoord, Status> {
+        let mut tile = GemmCoord::default();
+        // SAFETY: only reads `context` and `self` and writes `tile`.
+        unsafe { cutlass_sys_gemm_tile_shape(context, self, &mut tile) }.result()?;
+        Ok(tile)
+    }
+
+    /// Threads, registers, shared memory, occupancy and grid of the kernel
+    /// [`launch`](Self::launch) would run this config on. Fails like
+    /// [`supports`](Self::supports) for an unsupported config, and with
+    /// [`Status::ErrorNotSupported`] for the GEMV kernel.
+    pub fn resources(&self, context: &CutlassContext) -> Result<KernelResources, Status> {
+        let mut resources = KernelResources::default();
+        // SAFETY: only reads `context` and `self` and writes `resources`.
+        unsafe { cutlass_sys_gemm_resources(context, self, &mut resources) }.result()?;
+        Ok(resources)
+    }
+
+    /// Picks the tile scheduling and split-K slice count for this config's
+    /// problem on `context`'s device, ignoring the ones set here. See
+    /// [`GemmPlan`] for the rules.
+    pub fn plan(&self, context: &CutlassContext) -> Result<GemmPlan, Status> {
+        self.plan_within(context, usize::MAX)
+    }
+
+    /// [`plan`](Self::plan), skipping schedules that need more than
+    /// `workspace_size` bytes of workspace.
+    pub fn plan_within(
+        &self,
+        context: &CutlassContext,
+        workspace_size: usize,
+    ) -> Result<GemmPlan, Status> {
+        let plan = GemmPlan::new(
+            context,
+            GemmConfig {
+                scheduler: TileScheduler::DataParallel,
+                split_k_slices: 1,
+                ..*self
+            },
+        )?;
+        if plan.workspace_size > workspace_size {
+            return Err(Status::ErrorWorkspaceNull);
+        }
+        let tile = plan.threadblock;
+        // The GEMV kernel has no tiles to schedule
+        if tile.m == 0 {
+            return Ok(plan);
+        }
+
+        let sm_count = context.sm_count.max(1) as u64;
+        let k_iterations = (self.problem.k.max(1) as u64).div_ceil(tile.k as u64);
+        let candidate = |scheduler, split_k_slices| {
+            let config = GemmConfig {
+                scheduler,
+                split_k_slices,
+                ..*self
+            };
+            GemmPlan::new(context, config)
+                .ok()
+                .filter(|plan| plan.workspace_size <= workspace_size)
+        };
+        let stream_k = || candidate(TileScheduler::StreamK, 1);
+
+        if plan.tiles >= sm_count {
+            let waves = plan.tiles.div_ceil(sm_count);
+            if plan.tiles * 10 >= waves * sm_count * MIN_WAVE_EFFICIENCY_TENTHS {
+                return Ok(plan);
+            }
+            return Ok(stream_k().unwrap_or(plan));
+        }
+
+        // Fewer tiles than SMs: split K over the idle SMs
+        let mut split_k_slices = 1;
+        while split_k_slices * 2 <= MAX_SPLIT_K_SLICES
+            && plan.tiles * (split_k_slices as u64 * 2) <= sm_count
+            && k_iterations >= (split_k_slices as u64 * 2) * MIN_K_ITERATIONS_PER_SLICE
+        {
+            split_k_slices *= 2;
+        }
+        let split_k = (split_k_slices > 1)
+            .then(|| candidate(TileScheduler::DataParallel, split_k_slices))
+            .flatten();
+        let long_k_stream_k = || {
+            (k_iterations >= 2 * MIN_K_ITERATIONS_PER_SLICE)
+                .then(stream_k)
+                .flatten()
+        };
+        Ok(split_k.or_else(long_k_stream_k).unwrap_or(plan))
+    }
+
+    /// Launches this config with the schedule [`plan_within`](Self::plan_within)
+    /// picks for `workspace_size` bytes of workspace, and returns the plan
+    /// for logging.
+    ///
+    /// # Safety
+    ///
+    /// As for [`launch`](Self::launch), with `workspace` holding
+    /// `workspace_size` bytes of device memory.
+    pub unsafe fn launch_auto(
+        &self,
+        context: &CutlassContext,
+        operands: &GemmOperands,
+        workspace: *mut c_void,
+        workspace_size: usize,
+        stream: cudaStream_t,
+    ) -> Result<GemmPlan, CutlassError> {
+        let plan = self.plan_within(context, workspace_size)?;
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        unsafe { plan.launch(context, operands, workspace, stream) }?;
+        Ok(plan)
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_gemm_array`] needs for
+    /// `batch_count` problems of this config.
+    pub fn array_workspace_size(&self, context: &CutlassContext, batch_count: i32) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_gemm_array_workspace_size(context, self, batch_count) }
+    }
+
+    /// Checks that [`cutlass_sys_gemm_array`] can run this config before any
+    /// operand is allocated, returning the status the launch would fail with.
+    pub fn array_supports(&self, context: &CutlassContext, batch_count: i32) -> Result<(), Status> {
+        self.check_on(false).map_err(ProblemError::status)?;
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_gemm_array_can_implement(context, self, batch_count) }.result()
+    }
+
+    /// Launches `batch_count` problems of this config on `stream` through
+    /// [`cutlass_sys_gemm_array`], after the checks of
+    /// [`check`](Self::check), whose failure is returned as
+    /// [`CutlassError::Problem`].
+    ///
+    /// # Safety
+    ///
+    /// `operands` must point to `batch_count` device pointers per operand,
+    /// each addressing a problem as for [`launch`](Self::launch), with the
+    /// pointer arrays themselves in device memory too. `workspace` must hold
+    /// [`array_workspace_size`](Self::array_workspace_size) bytes.
+    pub unsafe fn launch_array(
+        &self,
+        context: &CutlassContext,
+        batch_count: i32,
+        operands: &GemmArrayOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        self.check_on(false)?;
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_gemm_array(context, self, batch_count, operands, workspace, stream)
+        })
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_gemm_gather`] needs for this
+    /// config.
+    pub fn gather_workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_gemm_gather_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_gemm_gather`] can run this config before any
+    /// operand is allocated, returning the status the launch would fail with.
+    pub fn gather_supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        self.check_on(false).map_err(ProblemError::status)?;
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_gemm_gather_can_implement(context, self) }.result()
+    }
+
+    /// Launches this config on `stream` with rows of `A` and `B` gathered
+    /// and rows of `C` and `D` scattered through the index arrays of
+    /// `operands`, through [`cutlass_sys_gemm_gather`], after the checks of
+    /// [`check`](Self::check), whose failure is returned as
+    /// [`CutlassError::Problem`].
+    ///
+    /// # Safety
+    ///
+    /// Each non-null index array must hold `m` (for `gather_a` and
+    /// `scatter_d`) or `k` (for `gather_b`) `i32` row indices in device
+    /// memory, every one addressing a row inside its operand. The operands
+    /// must cover the rows indexed, with the leading dimensions of the
+    /// config, in device memory of `context`'s device. Everything must stay
+    /// valid until the launch completes on `stream`, a stream of that device,
+    /// and `workspace` must hold
+    /// [`gather_workspace_size`](Self::gather_workspace_size) bytes.
+    pub unsafe fn launch_gather(
+        &self,
+        context: &CutlassContext,
+        operands: &GemmGatherOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        self.check_on(false)?;
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_gemm_gather(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Output-tile utilization of the last wave, in tenths, below which
+/// [`GemmConfig::plan`] switches a data-parallel launch to Stream-K.
+const MIN_WAVE_EFFICIENCY_TENTHS: u64 = 9;
+
+/// Fewest MAC-loop iterations a split-K slice is given.
+const MIN_K_ITERATIONS_PER_SLICE: u64 = 4;
+
+const MAX_SPLIT_K_SLICES: i32 = 16;
+
+/// A [`GemmConfig`] with its tile scheduling chosen by [`GemmConfig::plan`],
+/// plus the kernel facts the choice was made from.
+///
+/// Following CUTLASS's Stream-K heuristics, the plan stays data-parallel when
+/// the output tiles fill at least 90% of the last wave of CTAs. With more
+/// tiles than SMs and a poorly filled last wave it switches to Stream-K. With
+/// fewer tiles than SMs it splits K in powers of two (up to 16 slices) over
+/// the idle SMs while each slice keeps at least 4 MAC-loop iterations, or
+/// failing that uses Stream-K for long K. Schedules the kernel doesn't
+/// support fall back to plain data-parallel.
+///
+/// Formats as e.g. `stream-k, 128x128x32 tiles (300 tiles on 108 SMs)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GemmPlan {
+    /// The config to launch.
+    pub config: GemmConfig,
+    /// Tile one CTA computes, `0 x 0 x 0` for the GEMV kernel.
+    pub threadblock: GemmCoord,
+    /// Output tiles of the problem.
+    pub tiles: u64,
+    /// SMs of the device planned for.
+    pub sm_count: i32,
+    /// Bytes of device workspace [`config`](Self::config) needs.
+    pub workspace_size: usize,
+}
+
+impl GemmPlan {
+    pub(crate) fn new(context: &CutlassContext, config: GemmConfig) -> Result<Self, Status> {
+        let threadblock = config.tile_shape(context)?;
+        let tiles = if threadblock.m == 0 {
+            0
+        } else {
+            (config.problem.m as u64).div_ceil(threadblock.m as u64)
+                * (config.problem.n as u64).div_ceil(threadblock.n as u64)
+        };
+        Ok(Self {
+            config,
+            threadblock,
+            tiles,
+            sm_count: context.sm_count,
+            workspace_size: config.workspace_size(context),
+        })
+    }
+
+    /// Launches [`config`](Self::config) on `stream`.
+    ///
+    /// # Safety
+    ///
+    /// As for [`GemmConfig::launch`], with `workspace` holding at least
+    /// [`workspace_size`](Self::workspace_size) bytes.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &GemmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        unsafe { self.config.launch(context, operands, workspace, stream) }
+    }
+}
+
+impl fmt::Display for GemmPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.threadblock.m == 0 {
+            return write!(f, "gemv");
+        }
+        let tile = self.threadblock;
+        write!(
+            f,
+            "{}, {}x{}x{} tiles ({} tiles on {} SMs)",
+            Schedule(&self.config),
+            tile.m,
+            tile.n,
+            tile.k,
+            self.tiles,
+            self.sm_count
+        )
+    }
+}
+
+/// Name of a config's tile scheduling and split-K slice count, as plans and
+/// the autotuner report it: `stream-k`, `persistent` or
+/// `data-parallel split-k <slices>`.
+pub(crate) struct Schedule<'a>(pub &'a GemmConfig);
+
+impl fmt::Display for Schedule<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.scheduler {
+            TileScheduler::StreamK => write!(f, "stream-k"),
+            TileScheduler::Persistent => write!(f, "persistent"),
+            TileScheduler::DataParallel => {
+                write!(f, "data-parallel split-k {}", self.0.split_k_slices)
+            }
+        }
+    }
+}
+
+fn check_operand<T>(
+    view: &TensorView<T>,
+    layout: LayoutTypeID,
+    extent: MatrixCoord,
+    alignment: usize,
+) -> Result<(), Status> {
+    view.validate()?;
+    if view.layout() != layout {
+        return Err(Status::ErrorInvalidLayout);
+    }
+    if view.extent != extent {
+        return Err(Status::ErrorInvalidProblem);
+    }
+    view.check_pointer(alignment)
+}
+
+/// Device pointers of a [`cutlass_sys_gemm`] launch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct GemmOperands {
+    pub a: *const c_void,
+    pub b: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+    /// Ignored unless [`GemmConfig::bias`] is set.
+    pub bias: *const c_void,
+    /// Ignored unless [`GemmConfig::scale`] includes per-row scales.
+    pub row_scale: *const f32,
+    /// Ignored unless [`GemmConfig::scale`] includes per-column scales.
+    pub col_scale: *const f32,
+    /// Ignored unless [`GemmConfig::beta_mode`] is per-row or per-column.
+    pub beta: *const f32,
+    /// Ignored unless [`GemmConfig::zero_point_mode`] is per-row or
+    /// per-column.
+    pub zero_point: *const f32,
+    /// Ignored unless [`GemmConfig::aux_op`] is set.
+    pub aux: *const c_void,
+    /// With a down-converted output, a device `f32` the launch raises to
+    /// the largest absolute value of the result before
+    /// [`GemmConfig::out_scale`], so it must start at zero. May be null.
+    pub amax: *mut f32,
+}
+
+impl GemmOperands {
+    pub const fn new(a: *const c_void, b: *const c_void, c: *const c_void, d: *mut c_void) -> Self {
+        Self {
+            a,
+            b,
+            c,
+            d,
+            bias: core::ptr::null(),
+            row_scale: core::ptr::null(),
+            col_scale: core::ptr::null(),
+            beta: core::ptr::null(),
+            zero_point: core::ptr::null(),
+            aux: core::ptr::null(),
+            amax: core::ptr::null_mut(),
+        }
+    }
+}
+
+/// Device arrays of per-problem device pointers for a pointer-array batched
+/// GEMM. Each array has one entry per problem; the arrays themselves must be
+/// in device memory.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct GemmArrayOperands {
+    pub a: *const *const c_void,
+    pub b: *const *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const *const c_void,
+    pub d: *const *mut c_void,
+}
+
+/// Device pointers and row indices of a gather/scatter GEMM, as in CUTLASS
+/// example 36.
+///
+/// Row `i` of the `m x k` operand `A` is row `gather_a[i]` of `a` and row `j`
+/// of the `k x n` operand `B` row `gather_b[j]` of `b`; row `i` of the result
+/// is read from row `scatter_d[i]` of `c` and written to row `scatter_d[i]`
+/// of `d`. A null index array leaves its operand unindexed. Gathering `A`
+/// and scattering `D` routes tokens to and from the experts of a
+/// mixture-of-experts layer; gathering `B` sums embedding rows the way an
+/// embedding bag does, with `A` holding the per-bag weights. Neither needs a
+/// separate permutation kernel.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct GemmGatherOperands {
+    pub a: *const c_void,
+    pub b: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+    pub gather_a: *const i32,
+    /// Not supported for `S8`, whose `B` is column-major.
+    pub gather_b: *const i32,
+    pub scatter_d: *const i32,
+}
+
+impl GemmGatherOperands {
+    pub const fn new(a: *const c_void, b: *const c_void, c: *const c_void, d: *mut c_void) -> Self {
+        Self {
+            a,
+            b,
+            c,
+            d,
+            gather_a: core::ptr::null(),
+            gather_b: core::ptr::null(),
+            scatter_d: core::ptr::null(),
+        }
+    }
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_gemm`] needs for `config`.
+    /// Zero for most data-parallel launches; Stream-K and split-K need scratch
+    /// space for partial accumulators, and SM80 persistent launches room for
+    /// their problem descriptor.
+    pub fn cutlass_sys_gemm_workspace_size(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_gemm`] can run `config`, checked without touching
+    /// any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_gemm_can_implement(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+    ) -> Status;
+
+    /// Tile one CTA computes in the kernel [`cutlass_sys_gemm`] would run
+    /// `config` on, written to `tile` (all zeros for the GEMV kernel), or the
+    /// status the launch would fail with.
+    pub fn cutlass_sys_gemm_tile_shape(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+        tile: *mut GemmCoord,
+    ) -> Status;
+
+    /// Resources of the kernel [`cutlass_sys_gemm`] would launch `config`
+    /// on, written to `resources`, or the status the launch would fail
+    /// with; `ErrorNotSupported` for the GEMV kernel.
+    pub fn cutlass_sys_gemm_resources(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+        resources: *mut KernelResources,
+    ) -> Status;
+
+    /// Launch a GEMM on `stream`.
+    ///
+    /// An `n == 1` problem with unit `ldb` (any `ldb` for `S8`), `ldc` and
+    /// `ldd`, data-parallel scheduling, no split-K and no fused epilogue runs
+    /// on the [GEMV kernel](crate::gemv) instead.
+    ///
+    /// With the `sm90` feature, on an SM90 device, `F16`, `BF16` and `S8`
+    /// problems without a fused epilogue run on warp-specialized TMA / WGMMA
+    /// kernels launched in clusters of [`GemmConfig::cluster_shape`]
+    /// (2x1 by default). Data-parallel scheduling with split-K
+    /// and leading dimensions that are not 16-byte multiples stay on the SM80
+    /// kernels. The `sm100` feature does the same with tcgen05 kernels on
+    /// SM100 devices. With `sm75`, Turing devices run `F16` and `S8`
+    /// data-parallel problems without a fused epilogue; anything else returns
+    /// [`Status::ErrorNotSupported`] there.
+    ///
+    /// `workspace` must point to at least
+    /// [`cutlass_sys_gemm_workspace_size`] bytes of device memory (it may be
+    /// null when that size is zero).
+    pub fn cutlass_sys_gemm(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+        operands: *const GemmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_gemm_array`] needs.
+    pub fn cutlass_sys_gemm_array_workspace_size(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+        batch_count: i32,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_gemm_array`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_gemm_array_can_implement(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+        batch_count: i32,
+    ) -> Status;
+
+    /// Launch `batch_count` GEMMs of the same shape whose operands are
+    /// scattered through memory, as with arena allocators, on `stream`.
+    ///
+    /// Every problem shares `config`'s shape, leading dimensions and
+    /// `alpha` / `beta`. Only data-parallel scheduling without split-K, a
+    /// fused epilogue or `verify` is supported; anything else returns
+    /// [`Status::ErrorNotSupported`].
+    pub fn cutlass_sys_gemm_array(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+        batch_count: i32,
+        operands: *const GemmArrayOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_gemm_gather`] needs.
+    pub fn cutlass_sys_gemm_gather_workspace_size(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_gemm_gather`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_gemm_gather_can_implement(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+    ) -> Status;
+
+    /// Launch a GEMM gathering and scattering rows through the index arrays
+    /// of `operands` on `stream`.
+    ///
+    /// Only data-parallel scheduling without split-K, a fused epilogue or
+    /// `verify` is supported; anything else, or `gather_b` for `S8`, returns
+    /// [`Status::ErrorNotSupported`].
+    pub fn cutlass_sys_gemm_gather(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+        operands: *const GemmGatherOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn misaligned(operand: Operand, required: i64, got: i64) -> Result<(), ProblemError> {
+        Err(ProblemError::MisalignedOperand {
+            operand,
+            required,
+            got,
+        })
+    }
+
+    #[test]
+    fn simt_f32_takes_any_leading_dimension() {
+        let config = GemmConfig::new(7, 5, 3, DataType::F32);
+        assert_eq!(config.types().alignment(), (1, 1));
+        assert_eq!(config.check(), Ok(()));
+    }
+
+    #[test]
+    fn tensor_op_types_need_128_bit_rows() {
+        assert_eq!(
+            GemmConfig::new(64, 64, 12, DataType::F16).check(),
+            misaligned(Operand::A, 8, 12)
+        );
+        assert_eq!(
+            GemmConfig::new(64, 64, 24, DataType::S8).check(),
+            misaligned(Operand::A, 16, 24)
+        );
+        // The S8 kernel writes i32, four to 128 bits
+        assert_eq!(GemmConfig::new(64, 68, 64, DataType::S8).check(), Ok(()));
+        assert_eq!(
+            GemmConfig::new(64, 66, 64, DataType::S8).check(),
+            misaligned(Operand::D, 4, 66)
+        );
+    }
+
+    #[test]
+    fn output_alignment_follows_the_epilogue() {
+        let with = |types: GemmTypes, n: i32| GemmConfig {
+            element: types.element,
+            accumulator: types.accumulator,
+            output: types.output,
+            ..GemmConfig::new(64, n, 64, types.element)
+        };
+        let widened = GemmTypes::with(DataType::F16, DataType::F32, DataType::F32);
+        assert_eq!(widened.alignment(), (8, 4));
+        assert_eq!(with(widened, 72).check(), Ok(()));
+        assert_eq!(with(widened, 68).check(), misaligned(Operand::B, 8, 68));
+
+        let narrowed = GemmTypes::with(DataType::S8, DataType::S32, DataType::S8);
+        assert_eq!(narrowed.alignment(), (16, 16));
+
+        // Down-converted outputs keep the operand type's visitor thread map
+        let fp8 = GemmTypes::with(DataType::BF16, DataType::F32, DataType::E4M3);
+        assert_eq!(fp8.alignment(), (8, 8));
+    }
+
+    #[test]
+    fn only_gemv_routed_problems_skip_alignment() {
+        let gemv = GemmConfig::new(64, 1, 60, DataType::F16);
+        assert!(gemv.runs_on_gemv());
+        assert_eq!(gemv.check(), Ok(()));
+        assert_eq!(gemv.check_on(false), misaligned(Operand::A, 8, 60));
+
+        let split_k = GemmConfig {
+            split_k_slices: 2,
+            ..gemv
+        };
+        assert!(!split_k.runs_on_gemv());
+        assert_eq!(split_k.check(), misaligned(Operand::A, 8, 60));
+
+        let fused = GemmConfig {
+            bias: BiasMode::Row,
+            ..GemmConfig::new(64, 1, 64, DataType::F16)
+        };
+        assert!(!fused.runs_on_gemv());
+        assert_eq!(fused.check(), misaligned(Operand::B, 8, 1));
+    }
+}
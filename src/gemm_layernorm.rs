@@ -0,0 +1,148 @@
+//! GEMM, layer normalization and GEMM fused as in CUTLASS example 37:
+//!
+//! ```text
+//! D   = alpha * A * B0 + beta * C
+//! Out = (gamma * (D - mean) / sqrt(variance + eps) + beta_ln) * B1
+//! ```
+//!
+//! with the mean and variance taken over each row of `D`, `eps` the small
+//! constant of CUTLASS's statistics kernel, and `gamma` and `beta_ln` the
+//! per-column LayerNorm weight and shift. GEMM0's epilogue writes `D` and
+//! accumulates each row's partial sums in the workspace, a small kernel
+//! turns them into the mean and variance, and GEMM1 normalizes its `A`
+//! operand as it loads it, so the normalized activations are never written:
+//! the projection that follows a transformer's LayerNorm needs no
+//! normalization pass of its own.
+//!
+//! Problem 0 is `m x n x k`, problem 1 `m x n1 x n`. `A`, `C`, `D` and `Out`
+//! are row-major, `B0` and `B1` column-major (i.e. `B^T` stored row-major,
+//! as weight matrices usually are). The element type is `F16` or `BF16`,
+//! with the epilogue and statistics in `f32`; anything else returns
+//! [`Status::ErrorInvalidDataType`].
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::coord::GemmCoord;
+use crate::error::CutlassError;
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Problem description of a GEMM-LayerNorm-GEMM.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GemmLayernormConfig {
+    /// `m x n x k`.
+    pub problem0: GemmCoord,
+    /// `m x n1 x n`.
+    pub problem1: GemmCoord,
+    pub element: DataType,
+    pub lda: i64,
+    pub ldb0: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub ldb1: i64,
+    pub ld_out: i64,
+    pub alpha: f32,
+    pub beta: f32,
+}
+
+impl GemmLayernormConfig {
+    /// Tightly packed `m x n x k` and `m x n1 x n` problems with `alpha = 1`,
+    /// `beta = 0`.
+    pub const fn new(m: i32, n: i32, n1: i32, k: i32, element: DataType) -> Self {
+        Self {
+            problem0: GemmCoord::new(m, n, k),
+            problem1: GemmCoord::new(m, n1, n),
+            element,
+            lda: k as i64,
+            ldb0: k as i64,
+            ldc: n as i64,
+            ldd: n as i64,
+            ldb1: n as i64,
+            ld_out: n1 as i64,
+            alpha: 1.0,
+            beta: 0.0,
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_gemm_layernorm`] needs for
+    /// this config: the per-row partial statistics.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_gemm_layernorm_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_gemm_layernorm`] can run this config before
+    /// any operand is allocated, returning the status the launch would fail
+    /// with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_gemm_layernorm_can_implement(context, self) }.result()
+    }
+
+    /// Launches the fused GEMMs on `stream` through
+    /// [`cutlass_sys_gemm_layernorm`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering both problems, with `gamma` and `beta_ln` holding `n`
+    /// elements, and stay valid until the launch completes on `stream`, a
+    /// stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &GemmLayernormOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_gemm_layernorm(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers of a GEMM-LayerNorm-GEMM launch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct GemmLayernormOperands {
+    pub a: *const c_void,
+    pub b0: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    /// The output of GEMM0, before normalization.
+    pub d: *mut c_void,
+    /// LayerNorm weight, `n` elements of the element type.
+    pub gamma: *const c_void,
+    /// LayerNorm shift, `n` elements of the element type.
+    pub beta_ln: *const c_void,
+    pub b1: *const c_void,
+    pub out: *mut c_void,
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_gemm_layernorm`] needs.
+    pub fn cutlass_sys_gemm_layernorm_workspace_size(
+        context: *const CutlassContext,
+        config: *const GemmLayernormConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_gemm_layernorm`] can run `config`, checked
+    /// without touching any operand: the status the launch would fail with,
+    /// or [`Status::Success`].
+    pub fn cutlass_sys_gemm_layernorm_can_implement(
+        context: *const CutlassContext,
+        config: *const GemmLayernormConfig,
+    ) -> Status;
+
+    /// Launch GEMM0, the statistics reduction and GEMM1 on `stream`.
+    pub fn cutlass_sys_gemm_layernorm(
+        context: *const CutlassContext,
+        config: *const GemmLayernormConfig,
+        operands: *const GemmLayernormOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
@@ -0,0 +1,178 @@
+//! GEMM fused with a row-wise softmax, as in CUTLASS example 35:
+//!
+//! ```text
+//! D       = scale * A * B + mask
+//! Softmax = softmax(D) along each row
+//! ```
+//!
+//! with `A` row-major `m x k`, `B` column-major `k x n` (so `B^T` is a
+//! row-major `n x k` matrix, e.g. the keys of an attention head) and `mask`,
+//! `D` and `Softmax` row-major `m x n`. This is the score computation of
+//! attention, `softmax(Q K^T / sqrt(d) + mask)`, without a round trip of the
+//! unnormalized scores through a separate softmax kernel: the GEMM epilogue
+//! keeps each row's partial maximum and sum of exponentials per threadblock
+//! column in the workspace, and two small kernels on the same stream combine
+//! them and write `Softmax` from `D`.
+//!
+//! The mask is added to the scaled scores before the softmax, so masked
+//! positions hold `-inf` (or a large negative value) and unmasked ones `0`; a
+//! null mask adds nothing. A row masked entirely produces NaNs. A mask batch
+//! stride of 0 shares one mask, e.g. a causal mask, across the batch.
+//!
+//! The element type of every operand is `F16` or `BF16`, accumulating and
+//! computing the softmax in `f32`; anything else returns
+//! [`Status::ErrorInvalidDataType`]. `k`, `n` and the leading dimensions must
+//! be multiples of 8 elements for the kernels' 128-bit accesses. With
+//! `batch_count > 1` the launch computes that many independent problems,
+//! operand `i` starting `i * batch_stride_*` elements after its pointer.
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::coord::GemmCoord;
+use crate::error::CutlassError;
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Problem description of a (batched) GEMM + softmax.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GemmSoftmaxConfig {
+    pub problem: GemmCoord,
+    pub element: DataType,
+    pub batch_count: i32,
+    pub lda: i64,
+    /// Leading dimension of the column-major `B`.
+    pub ldb: i64,
+    pub ld_mask: i64,
+    pub ldd: i64,
+    pub ld_softmax: i64,
+    pub batch_stride_a: i64,
+    pub batch_stride_b: i64,
+    pub batch_stride_mask: i64,
+    pub batch_stride_d: i64,
+    pub batch_stride_softmax: i64,
+    /// Factor applied to `A * B` before the mask is added, e.g.
+    /// `1 / sqrt(head_dim)`.
+    pub scale: f32,
+}
+
+impl GemmSoftmaxConfig {
+    /// A single tightly packed `m x n x k` problem with `scale = 1`.
+    pub const fn new(m: i32, n: i32, k: i32, element: DataType) -> Self {
+        Self::batched(m, n, k, element, 1)
+    }
+
+    /// `batch_count` tightly packed `m x n x k` problems stored back to
+    /// back, each with a mask of its own, with `scale = 1`.
+    pub const fn batched(m: i32, n: i32, k: i32, element: DataType, batch_count: i32) -> Self {
+        Self {
+            problem: GemmCoord::new(m, n, k),
+            element,
+            batch_count,
+            lda: k as i64,
+            ldb: k as i64,
+            ld_mask: n as i64,
+            ldd: n as i64,
+            ld_softmax: n as i64,
+            batch_stride_a: m as i64 * k as i64,
+            batch_stride_b: k as i64 * n as i64,
+            batch_stride_mask: m as i64 * n as i64,
+            batch_stride_d: m as i64 * n as i64,
+            batch_stride_softmax: m as i64 * n as i64,
+            scale: 1.0,
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_gemm_softmax`] needs for this
+    /// config: the per-row partial maxima and sums.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_gemm_softmax_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_gemm_softmax`] can run this config before
+    /// any operand is allocated, returning the status the launch would fail
+    /// with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_gemm_softmax_can_implement(context, self) }.result()
+    }
+
+    /// Launches the GEMM and softmax on `stream` through
+    /// [`cutlass_sys_gemm_softmax`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &GemmSoftmaxOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_gemm_softmax(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers of a GEMM + softmax launch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct GemmSoftmaxOperands {
+    pub a: *const c_void,
+    pub b: *const c_void,
+    /// Additive mask; may be null for none.
+    pub mask: *const c_void,
+    /// The scaled, masked scores the softmax is taken over.
+    pub d: *mut c_void,
+    pub softmax: *mut c_void,
+}
+
+impl GemmSoftmaxOperands {
+    /// Operands without a mask.
+    pub const fn new(
+        a: *const c_void,
+        b: *const c_void,
+        d: *mut c_void,
+        softmax: *mut c_void,
+    ) -> Self {
+        Self {
+            a,
+            b,
+            mask: core::ptr::null(),
+            d,
+            softmax,
+        }
+    }
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_gemm_softmax`] needs.
+    pub fn cutlass_sys_gemm_softmax_workspace_size(
+        context: *const CutlassContext,
+        config: *const GemmSoftmaxConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_gemm_softmax`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_gemm_softmax_can_implement(
+        context: *const CutlassContext,
+        config: *const GemmSoftmaxConfig,
+    ) -> Status;
+
+    /// Launch the GEMM and the two softmax kernels on `stream`.
+    pub fn cutlass_sys_gemm_softmax(
+        context: *const CutlassContext,
+        config: *const GemmSoftmaxConfig,
+        operands: *const GemmSoftmaxOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
@@ -0,0 +1,136 @@
+//! Matrix-vector multiply:
+//!
+//! ```text
+//! d = alpha * A * x + beta * c
+//! ```
+//!
+//! with `A` a row-major `m x k` matrix and `x`, `c` and `d` contiguous
+//! vectors, using the element types of [`DataType`]. Decode-phase inference
+//! is dominated by these `N = 1` products, where a GEMM tile would leave
+//! almost all of its columns empty.
+//!
+//! [`cutlass_sys_gemm`](crate::gemm::cutlass_sys_gemm) already routes
+//! single-column problems with contiguous `B`, `C` and `D` and no fused
+//! epilogue here; call [`cutlass_sys_gemv`] directly for batching.
+//!
+//! With `batch_count > 1` the launch computes that many independent products,
+//! operand `i` starting `i * batch_stride_*` elements after its pointer. A
+//! stride of 0 shares one operand across the batch.
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::error::CutlassError;
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Problem description of a (batched) GEMV.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GemvConfig {
+    pub m: i32,
+    pub k: i32,
+    pub element: DataType,
+    pub batch_count: i32,
+    pub lda: i64,
+    pub batch_stride_a: i64,
+    pub batch_stride_x: i64,
+    pub batch_stride_c: i64,
+    pub batch_stride_d: i64,
+    pub alpha: f32,
+    pub beta: f32,
+}
+
+impl GemvConfig {
+    /// A single tightly packed `m x k` product with `alpha = 1`, `beta = 0`.
+    pub const fn new(m: i32, k: i32, element: DataType) -> Self {
+        Self::batched(m, k, element, 1)
+    }
+
+    /// `batch_count` tightly packed `m x k` products stored back to back,
+    /// with `alpha = 1`, `beta = 0`.
+    pub const fn batched(m: i32, k: i32, element: DataType, batch_count: i32) -> Self {
+        Self {
+            m,
+            k,
+            element,
+            batch_count,
+            lda: k as i64,
+            batch_stride_a: m as i64 * k as i64,
+            batch_stride_x: k as i64,
+            batch_stride_c: m as i64,
+            batch_stride_d: m as i64,
+            alpha: 1.0,
+            beta: 0.0,
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_gemv`] needs for this config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_gemv_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_gemv`] can run this config before any operand
+    /// is allocated, returning the status the launch would fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_gemv_can_implement(context, self) }.result()
+    }
+
+    /// Launches the GEMV on `stream` through [`cutlass_sys_gemv`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory, or
+    /// may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &GemvOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe { cutlass_sys_gemv(context, self, operands, workspace, stream) })
+    }
+}
+
+/// Device pointers of a GEMV launch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct GemvOperands {
+    pub a: *const c_void,
+    pub x: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_gemv`] needs.
+    pub fn cutlass_sys_gemv_workspace_size(
+        context: *const CutlassContext,
+        config: *const GemvConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_gemv`] can run `config`, checked without touching
+    /// any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_gemv_can_implement(
+        context: *const CutlassContext,
+        config: *const GemvConfig,
+    ) -> Status;
+
+    /// Launch a (batched) GEMV on `stream`. Rows are read with 128-bit
+    /// vector loads when `k` and `lda` allow it.
+    pub fn cutlass_sys_gemv(
+        context: *const CutlassContext,
+        config: *const GemvConfig,
+        operands: *const GemvOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
@@ -0,0 +1,386 @@
+//! Packed 4-bit integers, as CUTLASS stores `cutlass::int4b_t` operands.
+//!
+//! Two values share a byte, the even-indexed one in the low nibble, and
+//! packing runs along the contiguous dimension of the layout, so every row
+//! (column) of a packed matrix must start on a byte: an even leading
+//! dimension in elements. [`pack_matrix`] and [`unpack_matrix`] move
+//! between a logical row-major `i8` matrix and that packing for the plain
+//! and interleaved ([`LayoutTypeID::ColumnMajorInterleavedK64`] and
+//! friends) matrix layouts; [`interleave_and_bias`] then rearranges packed
+//! weights for the fast int4 to f16 conversion of mixed-input kernels.
+//!
+//! Values outside `-8..=7` saturate, as CUTLASS's `NumericConverter` to
+//! `int4b_t` does.
+
+use core::fmt;
+
+use crate::coord::MatrixCoord;
+use crate::library::LayoutTypeID;
+use crate::types::Status;
+
+/// Two signed 4-bit values in one byte, laid out like a pair of
+/// `cutlass::int4b_t` in a packed array.
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Int4x2(u8);
+
+impl Int4x2 {
+    pub const MIN: i8 = -8;
+    pub const MAX: i8 = 7;
+
+    /// `lo` in the low nibble and `hi` in the high one, each saturated to
+    /// [`MIN`](Self::MIN)`..=`[`MAX`](Self::MAX).
+    pub const fn new(lo: i8, hi: i8) -> Self {
+        Self(nibble(lo) | nibble(hi) << 4)
+    }
+
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    pub const fn to_bits(self) -> u8 {
+        self.0
+    }
+
+    /// The value in the low nibble, the even-indexed one.
+    pub const fn lo(self) -> i8 {
+        ((self.0 << 4) as i8) >> 4
+    }
+
+    /// The value in the high nibble, the odd-indexed one.
+    pub const fn hi(self) -> i8 {
+        (self.0 as i8) >> 4
+    }
+
+    /// Value `i` (`0` or `1`) of the pair.
+    pub const fn get(self, i: usize) -> i8 {
+        if i & 1 == 0 {
+            self.lo()
+        } else {
+            self.hi()
+        }
+    }
+
+    /// Replaces value `i` (`0` or `1`) of the pair, saturating it.
+    pub fn set(&mut self, i: usize, value: i8) {
+        let shift = (i & 1) * 4;
+        self.0 = (self.0 & !(0xf << shift)) | nibble(value) << shift;
+    }
+}
+
+impl fmt::Debug for Int4x2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Int4x2")
+            .field(&self.lo())
+            .field(&self.hi())
+            .finish()
+    }
+}
+
+/// Two's-complement nibble of `value`, saturated to four bits.
+const fn nibble(value: i8) -> u8 {
+    let clamped = if value < Int4x2::MIN {
+        Int4x2::MIN
+    } else if value > Int4x2::MAX {
+        Int4x2::MAX
+    } else {
+        value
+    };
+    clamped as u8 & 0xf
+}
+
+/// Packs `src` into `dst`, element `i` into nibble `i % 2` of `dst[i / 2]`.
+/// An odd count leaves the high nibble of the last pair zero.
+///
+/// [`Status::ErrorInvalidProblem`] if `dst` is shorter than
+/// `src.len().div_ceil(2)`.
+pub fn pack(src: &[i8], dst: &mut [Int4x2]) -> Result<(), Status> {
+    if dst.len() < src.len().div_ceil(2) {
+        return Err(Status::ErrorInvalidProblem);
+    }
+    for (pair, values) in dst.iter_mut().zip(src.chunks(2)) {
+        *pair = Int4x2::new(values[0], values.get(1).copied().unwrap_or(0));
+    }
+    Ok(())
+}
+
+/// Unpacks the first `dst.len()` values of `src` into `dst`.
+///
+/// [`Status::ErrorInvalidProblem`] if `src` holds fewer values.
+pub fn unpack(src: &[Int4x2], dst: &mut [i8]) -> Result<(), Status> {
+    if src.len() * 2 < dst.len() {
+        return Err(Status::ErrorInvalidProblem);
+    }
+    for (i, value) in dst.iter_mut().enumerate() {
+        *value = src[i / 2].get(i);
+    }
+    Ok(())
+}
+
+/// Pairs a packed `extent` matrix in `layout` takes. `None` for layouts
+/// other than the (interleaved) row- and column-major ones, and for extents
+/// a row (column) of which would not start on a byte: an odd contiguous
+/// extent, or one the interleave does not divide.
+pub const fn packed_len(extent: MatrixCoord, layout: LayoutTypeID) -> Option<usize> {
+    if !layout.is_matrix() || extent.row < 0 || extent.column < 0 {
+        return None;
+    }
+    let interleave = layout.interleave();
+    let (contiguous, strided) = if layout.is_row_major() {
+        (extent.column, extent.row)
+    } else {
+        (extent.row, extent.column)
+    };
+    let divisible = if interleave == 1 {
+        contiguous % 2 == 0
+    } else {
+        strided % interleave == 0
+    };
+    if !divisible {
+        return None;
+    }
+    Some(extent.row as usize * extent.column as usize / 2)
+}
+
+/// Offset in elements of `(row, column)` of a packed `extent` matrix in
+/// `layout`, as the `cutlass::layout` of the same name computes it.
+fn offset(extent: MatrixCoord, layout: LayoutTypeID, row: usize, column: usize) -> usize {
+    let (rows, columns) = (extent.row as usize, extent.column as usize);
+    let interleave = layout.interleave() as usize;
+    if layout.is_row_major() {
+        // RowMajorInterleaved<I>: I rows side by side, column by column
+        (row / interleave) * columns * interleave + column * interleave + row % interleave
+    } else {
+        (column / interleave) * rows * interleave + row * interleave + column % interleave
+    }
+}
+
+/// Packs the logical row-major `extent` matrix `src` into `dst` in
+/// `layout`: [`LayoutTypeID::RowMajor`] for a K-contiguous `A`,
+/// [`LayoutTypeID::ColumnMajor`] for a K-contiguous `B`, or an interleaved
+/// layout such as [`LayoutTypeID::ColumnMajorInterleavedK64`] for the
+/// interleaved int4 kernels. The result is tightly packed; pad the leading
+/// dimension afterwards if the kernel needs more alignment.
+///
+/// [`Status::ErrorInvalidLayout`] for a layout [`packed_len`] does not take
+/// and [`Status::ErrorInvalidProblem`] for an extent it rejects or slices
+/// that do not fit.
+pub fn pack_matrix(
+    src: &[i8],
+    extent: MatrixCoord,
+    layout: LayoutTypeID,
+    dst: &mut [Int4x2],
+) -> Result<(), Status> {
+    let len = check_matrix(src.len(), extent, layout, dst.len())?;
+    dst[..len].fill(Int4x2::default());
+    let columns = extent.column as usize;
+    for (i, &value) in src.iter().enumerate() {
+        let at = offset(extent, layout, i / columns, i % columns);
+        dst[at / 2].set(at, value);
+    }
+    Ok(())
+}
+
+/// Inverse of [`pack_matrix`]: unpacks the `extent` matrix `src`, packed in
+/// `layout`, into the logical row-major `dst`. Fails like [`pack_matrix`].
+pub fn unpack_matrix(
+    src: &[Int4x2],
+    extent: MatrixCoord,
+    layout: LayoutTypeID,
+    dst: &mut [i8],
+) -> Result<(), Status> {
+    check_matrix(dst.len(), extent, layout, src.len())?;
+    let columns = extent.column as usize;
+    for (i, value) in dst.iter_mut().enumerate() {
+        let at = offset(extent, layout, i / columns, i % columns);
+        *value = src[at / 2].get(at);
+    }
+    Ok(())
+}
+
+/// Checks a logical matrix of `logical` elements and a packed one of
+/// `packed` pairs against `extent` and `layout`, returning the pairs used.
+fn check_matrix(
+    logical: usize,
+    extent: MatrixCoord,
+    layout: LayoutTypeID,
+    packed: usize,
+) -> Result<usize, Status> {
+    if !layout.is_matrix() {
+        return Err(Status::ErrorInvalidLayout);
+    }
+    let len = packed_len(extent, layout).ok_or(Status::ErrorInvalidProblem)?;
+    if logical != extent.row as usize * extent.column as usize || packed < len {
+        return Err(Status::ErrorInvalidProblem);
+    }
+    Ok(len)
+}
+
+/// Rearranges packed int4 weights for
+/// `cutlass::FastInterleavedAndBiasedNumericArrayConverter`, which mixed-input
+/// kernels use to convert them to f16 or bf16 a register at a time: within
+/// every 32-bit word of eight values, values `0, 2, 4, 6` move to the low
+/// four nibbles and `1, 3, 5, 7` to the high four, and each is biased by
+/// `+8` into the unsigned `0..=15` the converter subtracts the bias from.
+/// The result is no longer a signed [`Int4x2`] pair, only bits for the
+/// kernel.
+///
+/// [`Status::ErrorInvalidProblem`] unless `packed` is a whole number of
+/// words (a multiple of four pairs).
+pub fn interleave_and_bias(packed: &mut [Int4x2]) -> Result<(), Status> {
+    if !packed.len().is_multiple_of(4) {
+        return Err(Status::ErrorInvalidProblem);
+    }
+    for word in packed.chunks_exact_mut(4) {
+        let mut values = [0u32; 8];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = (word[i / 2].get(i) + 8) as u32;
+        }
+        let mut bits = 0u32;
+        for (j, pair) in values.chunks_exact(2).enumerate() {
+            bits |= pair[0] << (4 * j);
+            bits |= pair[1] << (4 * (j + 4));
+        }
+        for (pair, byte) in word.iter_mut().zip(bits.to_le_bytes()) {
+            *pair = Int4x2::from_bits(byte);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec;
+    use std::vec::Vec;
+
+    use super::*;
+
+    /// `rows x columns` values cycling through `-8..=7`.
+    fn values(extent: MatrixCoord) -> Vec<i8> {
+        (0..extent.product()).map(|i| (i % 16) as i8 - 8).collect()
+    }
+
+    fn round_trip(extent: MatrixCoord, layout: LayoutTypeID) -> Vec<Int4x2> {
+        let src = values(extent);
+        let len = packed_len(extent, layout).unwrap();
+        let mut packed = vec![Int4x2::default(); len];
+        pack_matrix(&src, extent, layout, &mut packed).unwrap();
+        let mut unpacked = vec![0; src.len()];
+        unpack_matrix(&packed, extent, layout, &mut unpacked).unwrap();
+        assert_eq!(unpacked, src, "{layout:?}");
+        packed
+    }
+
+    #[test]
+    fn matrices_round_trip() {
+        let packed = round_trip(MatrixCoord::new(3, 4), LayoutTypeID::RowMajor);
+        assert_eq!(packed[0], Int4x2::new(-8, -7));
+        assert_eq!(packed[2], Int4x2::new(-4, -3));
+
+        // Down the columns: (0, 0) and (1, 0) share a byte
+        let packed = round_trip(MatrixCoord::new(4, 3), LayoutTypeID::ColumnMajor);
+        assert_eq!(packed[0], Int4x2::new(-8, -5));
+        assert_eq!(packed[2], Int4x2::new(-7, -4));
+
+        // Four columns side by side, row by row
+        let packed = round_trip(
+            MatrixCoord::new(3, 8),
+            LayoutTypeID::ColumnMajorInterleavedK4,
+        );
+        assert_eq!(packed[0], Int4x2::new(-8, -7));
+        assert_eq!(packed[2], Int4x2::new(0, 1));
+
+        round_trip(MatrixCoord::new(4, 3), LayoutTypeID::RowMajorInterleavedK2);
+        round_trip(
+            MatrixCoord::new(64, 2),
+            LayoutTypeID::RowMajorInterleavedK64,
+        );
+        round_trip(MatrixCoord::new(0, 0), LayoutTypeID::ColumnMajor);
+    }
+
+    #[test]
+    fn out_of_range_values_saturate() {
+        let pair = Int4x2::new(100, -100);
+        assert_eq!((pair.lo(), pair.hi()), (7, -8));
+
+        let src = [-9, 8, -128, 127, -8, 7];
+        let mut packed = [Int4x2::default(); 3];
+        pack(&src, &mut packed).unwrap();
+        let mut unpacked = [0; 6];
+        unpack(&packed, &mut unpacked).unwrap();
+        assert_eq!(unpacked, [-8, 7, -8, 7, -8, 7]);
+
+        let mut pair = Int4x2::default();
+        pair.set(1, 9);
+        assert_eq!(pair.to_bits(), 0x70);
+    }
+
+    #[test]
+    fn packed_len_rejects_unaligned_extents() {
+        let extent = MatrixCoord::new;
+        assert_eq!(packed_len(extent(3, 4), LayoutTypeID::RowMajor), Some(6));
+        assert_eq!(packed_len(extent(4, 3), LayoutTypeID::ColumnMajor), Some(6));
+        // A row (column) would start mid-byte
+        assert_eq!(packed_len(extent(4, 3), LayoutTypeID::RowMajor), None);
+        assert_eq!(packed_len(extent(3, 4), LayoutTypeID::ColumnMajor), None);
+        // The interleave must divide the strided extent
+        assert_eq!(
+            packed_len(extent(3, 8), LayoutTypeID::ColumnMajorInterleavedK4),
+            Some(12)
+        );
+        assert_eq!(
+            packed_len(extent(3, 6), LayoutTypeID::ColumnMajorInterleavedK4),
+            None
+        );
+        assert_eq!(
+            packed_len(extent(32, 2), LayoutTypeID::RowMajorInterleavedK64),
+            None
+        );
+        assert_eq!(packed_len(extent(-2, 2), LayoutTypeID::RowMajor), None);
+        assert_eq!(packed_len(extent(2, 2), LayoutTypeID::TensorNHWC), None);
+
+        let src = values(extent(2, 2));
+        let mut dst = [Int4x2::default(); 2];
+        assert_eq!(
+            pack_matrix(&src, extent(2, 2), LayoutTypeID::TensorNHWC, &mut dst),
+            Err(Status::ErrorInvalidLayout)
+        );
+        assert_eq!(
+            pack_matrix(&src, extent(2, 3), LayoutTypeID::RowMajor, &mut dst),
+            Err(Status::ErrorInvalidProblem)
+        );
+        assert_eq!(
+            pack_matrix(&src, extent(2, 2), LayoutTypeID::RowMajor, &mut dst[..1]),
+            Err(Status::ErrorInvalidProblem)
+        );
+        let mut short = [0; 3];
+        assert_eq!(
+            unpack_matrix(&dst, extent(2, 2), LayoutTypeID::RowMajor, &mut short),
+            Err(Status::ErrorInvalidProblem)
+        );
+        assert_eq!(pack(&src, &mut dst[..1]), Err(Status::ErrorInvalidProblem));
+    }
+
+    #[test]
+    fn interleave_and_bias_reorders_each_word() {
+        let values = [-8, -3, 0, 1, 2, 5, 7, -1];
+        let mut word = [Int4x2::default(); 4];
+        pack(&values, &mut word).unwrap();
+        interleave_and_bias(&mut word).unwrap();
+
+        // Nibbles hold values [0, 2, 4, 6, 1, 3, 5, 7], each plus 8
+        let order = [0, 2, 4, 6, 1, 3, 5, 7];
+        let expected = order.iter().enumerate().fold(0u32, |bits, (k, &i)| {
+            bits | ((values[i] + 8) as u32) << (4 * k)
+        });
+        assert_eq!(expected, 0x7d95_fa80);
+        let bits = u32::from_le_bytes(word.map(Int4x2::to_bits));
+        assert_eq!(bits, expected);
+
+        let mut ragged = [Int4x2::default(); 6];
+        assert_eq!(
+            interleave_and_bias(&mut ragged),
+            Err(Status::ErrorInvalidProblem)
+        );
+    }
+}
@@ -0,0 +1,551 @@
+//! Runtime compilation of CUTLASS kernels with NVRTC (`jit` feature).
+//!
+//! Ahead-of-time instantiation covers the common configurations; the `jit`
+//! feature covers the long tail. A [`JitCompiler`] compiles CUDA C++ that
+//! includes CUTLASS headers (the cached CUTLASS tree and the CUDA toolkit
+//! headers are on its include path) into a cubin for one SM version, and
+//! keeps every cubin in a disk cache keyed by the source, architecture,
+//! options and NVRTC version, so each instantiation is compiled once per
+//! machine, and loads it on a [`CutlassContext`]'s device as a
+//! [`Module`].
+//!
+//! [`JitGemm`] builds on both: it generates a CUTLASS 2.x tensor-core GEMM
+//! with any threadblock / warp tile and stage count from a [`JitGemmConfig`]
+//! and launches it with the same [`GemmConfig`] / [`GemmOperands`] as
+//! [`cutlass_sys_gemm`](crate::gemm::cutlass_sys_gemm).
+//!
+//! The cache lives in `CUTLASS_JIT_CACHE` if set, otherwise in a
+//! `cutlass-sys/jit` directory of the user cache directory.
+
+use core::ffi::{c_char, c_int, c_void, CStr};
+use core::ptr;
+
+use std::ffi::CString;
+use std::path::PathBuf;
+use std::string::{String, ToString};
+use std::vec::Vec;
+use std::{env, fmt, fs, io, process};
+
+use crate::cache::user_cache_dir;
+use crate::context::CutlassContext;
+use crate::coord::GemmCoord;
+use crate::gemm::{
+    Activation, AuxOp, BetaMode, BiasMode, ClusterShape, GemmConfig, GemmOperands, ScaleMode,
+    TileScheduler, ZeroPointMode,
+};
+use crate::library::LayoutTypeID;
+use crate::module::{CUresult, GemmKernelArgs, Module, ModuleError};
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Error returned by the JIT.
+#[derive(Debug)]
+pub enum JitError {
+    /// NVRTC failed; `log` holds the compiler output, if any.
+    Nvrtc { code: nvrtcResult, log: String },
+    /// A CUDA driver API call failed with this `CUresult`.
+    Driver(CUresult),
+    /// Reading or writing the disk cache failed.
+    Io(io::Error),
+    /// The configuration or launch was rejected before reaching CUDA.
+    Status(Status),
+}
+
+impl fmt::Display for JitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JitError::Nvrtc { code, log } => {
+                write!(f, "NVRTC error {}", code)?;
+                if !log.is_empty() {
+                    write!(f, ":\n{}", log)?;
+                }
+                Ok(())
+            }
+            JitError::Driver(code) => write!(f, "CUDA driver error {}", code),
+            JitError::Io(err) => write!(f, "JIT cache I/O error: {}", err),
+            JitError::Status(status) => write!(f, "{}", status),
+        }
+    }
+}
+
+impl std::error::Error for JitError {}
+
+impl From<io::Error> for JitError {
+    fn from(err: io::Error) -> Self {
+        JitError::Io(err)
+    }
+}
+
+impl From<Status> for JitError {
+    fn from(status: Status) -> Self {
+        JitError::Status(status)
+    }
+}
+
+impl From<ModuleError> for JitError {
+    fn from(err: ModuleError) -> Self {
+        match err {
+            ModuleError::Driver(code) => JitError::Driver(code),
+            ModuleError::Status(status) => JitError::Status(status),
+        }
+    }
+}
+
+fn nvrtc(result: nvrtcResult) -> Result<(), JitError> {
+    match result {
+        NVRTC_SUCCESS => Ok(()),
+        code => Err(JitError::Nvrtc {
+            code,
+            log: String::new(),
+        }),
+    }
+}
+
+/// Compiles CUDA C++ to cubins with NVRTC, through a disk cache.
+#[derive(Clone, Debug)]
+pub struct JitCompiler {
+    include_dirs: Vec<PathBuf>,
+    options: Vec<String>,
+    cache_dir: Option<PathBuf>,
+}
+
+impl Default for JitCompiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JitCompiler {
+    /// A compiler with the CUTLASS and CUDA toolkit headers this crate was
+    /// built against on its include path, C++17, and the default cache.
+    pub fn new() -> Self {
+        Self {
+            include_dirs: std::vec![
+                PathBuf::from(env!("CUTLASS_INCLUDE_DIR")),
+                PathBuf::from(env!("CUTLASS_SYS_CUDA_INCLUDE")),
+            ],
+            options: std::vec!["--std=c++17".to_string()],
+            cache_dir: default_cache_dir(),
+        }
+    }
+
+    /// Adds `dir` to the include path.
+    pub fn include_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.include_dirs.push(dir.into());
+        self
+    }
+
+    /// Adds an NVRTC option, e.g. `-DMY_DEFINE=1` or `--use_fast_math`.
+    pub fn option(mut self, option: impl Into<String>) -> Self {
+        self.options.push(option.into());
+        self
+    }
+
+    /// Caches cubins in `dir`, or nowhere for `None`.
+    pub fn cache_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.cache_dir = dir;
+        self
+    }
+
+    /// Compiles `source` into a cubin for SM version `arch` (e.g. `80`), or
+    /// returns the cached cubin of an identical earlier compilation.
+    pub fn compile(&self, source: &str, arch: i32) -> Result<Vec<u8>, JitError> {
+        let mut options = std::vec![std::format!("--gpu-architecture=sm_{}", arch)];
+        options.extend(self.options.iter().cloned());
+        options.extend(
+            self.include_dirs
+                .iter()
+                .map(|dir| std::format!("--include-path={}", dir.display())),
+        );
+
+        let cached = self.cache_dir.as_ref().map(|dir| {
+            let mut hash = Fnv1a::new();
+            hash.write(env!("CARGO_PKG_VERSION").as_bytes());
+            hash.write(&nvrtc_version().to_le_bytes());
+            hash.write(source.as_bytes());
+            for option in &options {
+                hash.write(option.as_bytes());
+            }
+            dir.join(std::format!("{:016x}.cubin", hash.finish()))
+        });
+        if let Some(path) = &cached {
+            if let Ok(cubin) = fs::read(path) {
+                return Ok(cubin);
+            }
+        }
+
+        let cubin = nvrtc_compile(source, &options)?;
+        if let Some(path) = &cached {
+            // Written under a unique name and renamed, so concurrent
+            // compilations never expose a partial cubin
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let partial = path.with_extension(std::format!("{}.tmp", process::id()));
+            fs::write(&partial, &cubin)?;
+            fs::rename(&partial, path)?;
+        }
+        Ok(cubin)
+    }
+
+    /// Compiles `source` for the device of `context` and loads it there.
+    pub fn load(&self, context: &CutlassContext, source: &str) -> Result<Module, JitError> {
+        let cubin = self.compile(source, context.compute_capability())?;
+        Ok(Module::load(context.device, &cubin)?)
+    }
+}
+
+/// The `cutlass-sys/jit` directory of the user cache directory, or
+/// `CUTLASS_JIT_CACHE`.
+fn default_cache_dir() -> Option<PathBuf> {
+    match env::var_os("CUTLASS_JIT_CACHE") {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => Some(user_cache_dir().join("jit")),
+    }
+}
+
+/// 64-bit FNV-1a, a hash that is stable across Rust versions for cache keys.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    /// Hashes `bytes` followed by a separator, so consecutive parts can't
+    /// run together.
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes.iter().chain(&[0xff]) {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn nvrtc_version() -> u32 {
+    let (mut major, mut minor) = (0, 0);
+    // SAFETY: only writes `major` and `minor`.
+    unsafe { nvrtcVersion(&mut major, &mut minor) };
+    (major as u32) << 16 | minor as u32
+}
+
+fn nvrtc_compile(source: &str, options: &[String]) -> Result<Vec<u8>, JitError> {
+    let source = CString::new(source).map_err(|_| Status::ErrorInvalidProblem)?;
+    let options: Vec<CString> = options
+        .iter()
+        .map(|option| CString::new(option.as_str()))
+        .collect::<Result<_, _>>()
+        .map_err(|_| Status::ErrorInvalidProblem)?;
+    let option_ptrs: Vec<*const c_char> = options.iter().map(|option| option.as_ptr()).collect();
+
+    let mut program = ptr::null_mut();
+    // SAFETY: `source` and the name are NUL-terminated; no headers are
+    // passed.
+    nvrtc(unsafe {
+        nvrtcCreateProgram(
+            &mut program,
+            source.as_ptr(),
+            c"cutlass_sys_jit.cu".as_ptr(),
+            0,
+            ptr::null(),
+            ptr::null(),
+        )
+    })?;
+    let result = (|| {
+        // SAFETY: `program` is live and `option_ptrs` holds
+        // `option_ptrs.len()` NUL-terminated strings.
+        let compiled = unsafe {
+            nvrtcCompileProgram(program, option_ptrs.len() as c_int, option_ptrs.as_ptr())
+        };
+        if compiled != NVRTC_SUCCESS {
+            return Err(JitError::Nvrtc {
+                code: compiled,
+                log: program_log(program),
+            });
+        }
+        let mut size = 0;
+        // SAFETY: `program` compiled successfully.
+        nvrtc(unsafe { nvrtcGetCUBINSize(program, &mut size) })?;
+        let mut cubin = std::vec![0u8; size];
+        // SAFETY: `cubin` holds the `size` bytes NVRTC writes.
+        nvrtc(unsafe { nvrtcGetCUBIN(program, cubin.as_mut_ptr().cast()) })?;
+        Ok(cubin)
+    })();
+    // SAFETY: `program` is live and destroyed once.
+    unsafe { nvrtcDestroyProgram(&mut program) };
+    result
+}
+
+fn program_log(program: nvrtcProgram) -> String {
+    let mut size = 0;
+    // SAFETY: `program` is live.
+    if unsafe { nvrtcGetProgramLogSize(program, &mut size) } != NVRTC_SUCCESS || size == 0 {
+        return String::new();
+    }
+    let mut log = std::vec![0u8; size];
+    // SAFETY: `log` holds the `size` bytes NVRTC writes.
+    if unsafe { nvrtcGetProgramLog(program, log.as_mut_ptr().cast()) } != NVRTC_SUCCESS {
+        return String::new();
+    }
+    CStr::from_bytes_until_nul(&log)
+        .map(|log| log.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Tiling of a [`JitGemm`]: a CUTLASS 2.x multistage tensor-core GEMM for
+/// SM80 and newer, with row-major `A`, `B`, `C` and `D` as in [`crate::gemm`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct JitGemmConfig {
+    /// `F16`, `BF16` or `F32` (3xTF32, like the ahead-of-time kernels).
+    pub element: DataType,
+    pub threadblock: GemmCoord,
+    pub warp: GemmCoord,
+    pub stages: i32,
+}
+
+impl JitGemmConfig {
+    /// The tiling of the ahead-of-time SM80 kernels for `element`.
+    pub const fn new(element: DataType) -> Self {
+        let k = match element {
+            DataType::F32 => 16,
+            _ => 32,
+        };
+        Self {
+            element,
+            threadblock: GemmCoord::new(128, 128, k),
+            warp: GemmCoord::new(64, 64, k),
+            stages: match element {
+                DataType::F32 => 4,
+                _ => 3,
+            },
+        }
+    }
+
+    pub const fn with_tiles(self, threadblock: GemmCoord, warp: GemmCoord, stages: i32) -> Self {
+        Self {
+            threadblock,
+            warp,
+            stages,
+            ..self
+        }
+    }
+
+    /// Operand alignment in elements: 128-bit accesses.
+    const fn alignment(&self) -> i64 {
+        match self.element {
+            DataType::F32 => 4,
+            _ => 8,
+        }
+    }
+
+    /// CUDA C++ source of the kernel, `cutlass_sys_jit_gemm`. It builds the
+    /// kernel's `Params` on the device from plain arguments, so no host-side
+    /// CUTLASS code is needed to launch it, and exports the block size and
+    /// shared memory it needs as `cutlass_sys_jit_threads` /
+    /// `cutlass_sys_jit_shared_memory`.
+    pub fn source(&self) -> Result<String, Status> {
+        let (element, instruction, math) = match self.element {
+            DataType::F16 => ("cutlass::half_t", "16, 8, 16", "OpMultiplyAdd"),
+            DataType::BF16 => ("cutlass::bfloat16_t", "16, 8, 16", "OpMultiplyAdd"),
+            DataType::F32 => ("float", "16, 8, 8", "OpMultiplyAddFastF32"),
+            _ => return Err(Status::ErrorInvalidDataType),
+        };
+        let (tb, warp) = (self.threadblock, self.warp);
+        Ok(std::format!(
+            r#"#include <cutlass/epilogue/thread/linear_combination.h>
+#include <cutlass/gemm/kernel/default_gemm.h>
+#include <cutlass/gemm/threadblock/threadblock_swizzle.h>
+
+using Element = {element};
+using Kernel = typename cutlass::gemm::kernel::DefaultGemm<
+    Element, cutlass::layout::RowMajor, {align},
+    Element, cutlass::layout::RowMajor, {align},
+    Element, cutlass::layout::RowMajor, float,
+    cutlass::arch::OpClassTensorOp, cutlass::arch::Sm80,
+    cutlass::gemm::GemmShape<{tbm}, {tbn}, {tbk}>,
+    cutlass::gemm::GemmShape<{wm}, {wn}, {wk}>,
+    cutlass::gemm::GemmShape<{instruction}>,
+    cutlass::epilogue::thread::LinearCombination<Element, {align}, float, float>,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<1>,
+    {stages}, false, cutlass::arch::{math}>::GemmKernel;
+
+extern "C" __device__ int cutlass_sys_jit_threads = Kernel::kThreadCount;
+extern "C" __device__ int cutlass_sys_jit_shared_memory =
+    int(sizeof(typename Kernel::SharedStorage));
+
+extern "C" __global__ void __launch_bounds__(Kernel::kThreadCount)
+cutlass_sys_jit_gemm(int m, int n, int k,
+                     Element const* a, long long lda,
+                     Element const* b, long long ldb,
+                     Element const* c, long long ldc,
+                     Element* d, long long ldd,
+                     float alpha, float beta) {{
+  cutlass::gemm::GemmCoord problem(m, n, k);
+  cutlass::gemm::GemmCoord tiles =
+      cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<1>().get_tiled_shape(
+          problem, {{ {tbm}, {tbn}, {tbk} }}, 1);
+  typename Kernel::Params params(
+      problem, tiles,
+      {{const_cast<Element*>(a), cutlass::layout::RowMajor(int(lda))}},
+      {{const_cast<Element*>(b), cutlass::layout::RowMajor(int(ldb))}},
+      {{const_cast<Element*>(c), cutlass::layout::RowMajor(int(ldc))}},
+      {{d, cutlass::layout::RowMajor(int(ldd))}},
+      {{alpha, beta}});
+  extern __shared__ int shared_storage[];
+  Kernel op;
+  op(params, *reinterpret_cast<typename Kernel::SharedStorage*>(shared_storage));
+}}
+"#,
+            align = self.alignment(),
+            tbm = tb.m,
+            tbn = tb.n,
+            tbk = tb.k,
+            wm = warp.m,
+            wn = warp.n,
+            wk = warp.k,
+            stages = self.stages,
+        ))
+    }
+}
+
+/// A GEMM kernel compiled at runtime for one [`JitGemmConfig`] and device.
+pub struct JitGemm {
+    module: Module,
+    config: JitGemmConfig,
+    threads: u32,
+    shared_memory: u32,
+}
+
+impl JitGemm {
+    /// Compiles (or fetches from the cache) and loads the kernel of `config`
+    /// for `context`'s device, which must be SM80 or newer.
+    pub fn new(
+        compiler: &JitCompiler,
+        context: &CutlassContext,
+        config: JitGemmConfig,
+    ) -> Result<Self, JitError> {
+        if context.compute_capability() < 80 {
+            return Err(Status::ErrorArchMismatch.into());
+        }
+        let module = compiler.load(context, &config.source()?)?;
+        let threads = module.read_global::<i32>("cutlass_sys_jit_threads")? as u32;
+        let shared_memory = module.read_global::<i32>("cutlass_sys_jit_shared_memory")? as u32;
+        module
+            .function("cutlass_sys_jit_gemm")?
+            .set_max_dynamic_shared_memory(shared_memory)?;
+        Ok(Self {
+            module,
+            config,
+            threads,
+            shared_memory,
+        })
+    }
+
+    pub fn config(&self) -> &JitGemmConfig {
+        &self.config
+    }
+
+    /// Checks that [`launch`](Self::launch) can run `gemm`: a plain
+    /// `alpha * A * B + beta * C` data-parallel problem of the kernel's
+    /// element type and its default accumulator and output types, with
+    /// row-major operands and leading dimensions aligned to 128 bits.
+    pub fn supports(&self, gemm: &GemmConfig) -> Result<(), Status> {
+        if gemm.element != self.config.element || !gemm.types().is_default() {
+            return Err(Status::ErrorInvalidDataType);
+        }
+        if gemm.layout_a != LayoutTypeID::RowMajor || gemm.layout_b != LayoutTypeID::RowMajor {
+            return Err(Status::ErrorInvalidLayout);
+        }
+        if gemm.scheduler != TileScheduler::DataParallel
+            || gemm.split_k_slices != 1
+            || gemm.cluster_shape != ClusterShape::Default
+            || gemm.bias != BiasMode::None
+            || gemm.activation != Activation::Identity
+            || gemm.scale != ScaleMode::None
+            || gemm.beta_mode != BetaMode::PerTensor
+            || gemm.zero_point_mode != ZeroPointMode::None
+            || gemm.aux_op != AuxOp::None
+            || gemm.out_scale != 1.0
+        {
+            return Err(Status::ErrorNotSupported);
+        }
+        let align = self.config.alignment();
+        if [gemm.lda, gemm.ldb, gemm.ldc, gemm.ldd]
+            .iter()
+            .any(|ld| ld % align != 0)
+        {
+            return Err(Status::ErrorMisalignedOperand);
+        }
+        Ok(())
+    }
+
+    /// Launches `gemm` on `stream`.
+    ///
+    /// # Safety
+    ///
+    /// The pointers of `operands` must be device memory of the module's
+    /// device holding the extents `gemm` describes, aligned to 16 bytes, and
+    /// `stream` null or a live stream of that device.
+    pub unsafe fn launch(
+        &self,
+        gemm: &GemmConfig,
+        operands: &GemmOperands,
+        stream: cudaStream_t,
+    ) -> Result<(), JitError> {
+        self.supports(gemm)?;
+        let args = GemmKernelArgs {
+            problem: gemm.problem,
+            a: operands.a,
+            lda: gemm.lda,
+            b: operands.b,
+            ldb: gemm.ldb,
+            c: operands.c,
+            ldc: gemm.ldc,
+            d: operands.d,
+            ldd: gemm.ldd,
+            alpha: gemm.alpha,
+            beta: gemm.beta,
+        };
+        // SAFETY: the function is the generated GEMM kernel; the caller
+        // vouches for the operands and `stream`.
+        unsafe {
+            Ok(args.launch(
+                self.module.function("cutlass_sys_jit_gemm")?,
+                self.config.threadblock,
+                self.threads,
+                self.shared_memory,
+                stream,
+            )?)
+        }
+    }
+}
+
+pub type nvrtcResult = c_int;
+pub type nvrtcProgram = *mut c_void;
+
+pub const NVRTC_SUCCESS: nvrtcResult = 0;
+
+extern "C" {
+    pub fn nvrtcVersion(major: *mut c_int, minor: *mut c_int) -> nvrtcResult;
+    pub fn nvrtcCreateProgram(
+        program: *mut nvrtcProgram,
+        source: *const c_char,
+        name: *const c_char,
+        num_headers: c_int,
+        headers: *const *const c_char,
+        include_names: *const *const c_char,
+    ) -> nvrtcResult;
+    pub fn nvrtcCompileProgram(
+        program: nvrtcProgram,
+        num_options: c_int,
+        options: *const *const c_char,
+    ) -> nvrtcResult;
+    pub fn nvrtcGetProgramLogSize(program: nvrtcProgram, size: *mut usize) -> nvrtcResult;
+    pub fn nvrtcGetProgramLog(program: nvrtcProgram, log: *mut c_char) -> nvrtcResult;
+    pub fn nvrtcGetCUBINSize(program: nvrtcProgram, size: *mut usize) -> nvrtcResult;
+    pub fn nvrtcGetCUBIN(program: nvrtcProgram, cubin: *mut c_char) -> nvrtcResult;
+    pub fn nvrtcDestroyProgram(program: *mut nvrtcProgram) -> nvrtcResult;
+
+}
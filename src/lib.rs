@@ -31,17 +31,227 @@
 //!
 //! - `CUTLASS_VERSION`: Override the CUTLASS version to download (e.g., `v3.5.1`)
 //!
-//! ## Note
+//! ## Compiled bindings
 //!
-//! This is a header-only library wrapper. No actual Rust bindings are provided,
-//! but the headers are made available for use in your own build scripts with
-//! `cc` or `bindgen`.
+//! By default this is a header-only library wrapper: the headers are made
+//! available for use in your own build scripts with `cc` or `bindgen`.
+//!
+//! Enabling the `shim` feature additionally compiles a small C ABI shim over a
+//! fixed set of CUTLASS kernel instantiations (this requires `nvcc`) and
+//! exposes its `extern "C"` declarations:
+//!
+//! - [`gemm`]: dense GEMM with data-parallel, Stream-K or persistent tile
+//!   scheduling and fused bias, activation, aux tensor, de/quantization
+//!   scaling and fp8/int8 down-conversion epilogues, plus pointer-array
+//!   batched and gather/scatter GEMM
+//! - [`b2b_conv`]: a convolution and a pointwise convolution fused into one
+//!   kernel, with a checker for the chains that fuse
+//! - [`b2b_gemm`]: two GEMMs fused into one kernel, back to back with an
+//!   activation in between or gated as in SwiGLU MLPs
+//! - [`blas3`]: symmetric and Hermitian rank-k updates (SYRK, HERK), symmetric
+//!   and Hermitian rank-2k updates (SYR2K, HER2K) and triangular, symmetric
+//!   and Hermitian multiplies (TRMM, SYMM, HEMM)
+//! - [`context`]: the per-device handle every entry point takes
+//! - [`conv`]: implicit-GEMM convolution (Conv2d and Conv3d fprop, dgrad and
+//!   wgrad; grouped and depthwise Conv2d fprop) over NHWC / NDHWC tensors
+//! - [`conv_quant`]: int8 Conv2d fprop with a per-channel requantizing
+//!   epilogue writing int8
+//! - [`ell_gemm`]: Blocked-ELL sparse x dense GEMM for block-pruned weights
+//! - [`error`]: CUTLASS status and CUDA error strings
+//! - [`evt`]: GEMM with a custom epilogue visitor tree built in Rust
+//! - [`gemm_layernorm`]: GEMM, LayerNorm and GEMM fused, the LayerNorm
+//!   applied as the second GEMM loads its operand
+//! - [`gemm_softmax`]: GEMM fused with a row-wise softmax over scaled and
+//!   masked scores, for attention
+//! - [`gemv`]: (batched) matrix-vector multiply, used automatically by
+//!   [`gemm`] for single-column problems
+//! - [`planar_complex`]: (batched) complex GEMM over split real/imaginary
+//!   planes
+//! - [`reduction`]: split-K partial-sum reduction and NHWC tensor reductions
+//! - [`sparse`]: 2:4 structured-sparse GEMM and the matching compression of
+//!   dense operands
+//! - [`transform`]: NCHW / NHWC layout conversion, transposes and general
+//!   tensor axis permutations, and element type conversion
+//!
+//! The shim keeps no global state and never allocates device memory. Every
+//! entry point takes a [`context::CutlassContext`] naming the device to run
+//! on, and every config has a safe `workspace_size` query (e.g.
+//! [`gemm::GemmConfig::workspace_size`]) so the caller can take the launch's
+//! workspace from its own allocator or pool. A matching `supports` check
+//! (e.g. [`gemm::GemmConfig::supports`]) runs CUTLASS's `can_implement`
+//! without launching, so unsupported shapes and alignments can fall back
+//! before any operand is allocated.
+//!
+//! Every raw entry point returns a [`types::Status`]; with the `std` feature
+//! it implements `std::error::Error`. [`ffi`] gathers the raw entry points,
+//! and each config wraps its own in `unsafe` launch methods (e.g.
+//! [`gemm::GemmConfig::launch`]) that document what the pointers and stream
+//! must satisfy and return `Result<(), error::CutlassError>`, which combines
+//! the status with CUDA runtime errors and prints both with CUTLASS's and
+//! CUDA's own messages. GEMM problem sizes are passed as
+//! [`coord::GemmCoord`], laid out like `cutlass::gemm::GemmCoord`, and
+//! [`library`] names element types and layouts the way
+//! `cutlass::library` does for runtime kernel selection. The
+//! [`tensor`] views bundle a device pointer with its layout, stride and
+//! extent, and `bind_views` on the GEMM and convolution configs derives the
+//! problem shape and leading dimensions from them. [`int4`] packs 4-bit
+//! integers into the plain and interleaved layouts CUTLASS's int4 kernels
+//! read.
+//!
+//! The `sm75`, `sm80`, `sm86`, `sm89`, `sm90` and `sm100` features (plus any
+//! archs listed in `CUTLASS_CUDA_ARCH`) select the SM architectures the shim
+//! is compiled for, `sm80` alone by default; see [`context`] for how the
+//! matching code is picked at runtime. Some archs add kernels of their own:
+//! Turing GEMMs for `sm75`, Hopper warp-specialized TMA / WGMMA GEMMs for
+//! `sm90` and Blackwell tcgen05 GEMMs for `sm100`, which [`gemm`] uses on
+//! those devices, along with `block_scaled`, an FP4 / FP6 / FP8 GEMM with
+//! per-block scale factors, for `sm100`.
+//!
+//! The `library` feature also builds CUTLASS's own kernel library
+//! (`tools/library`, as used by `cutlass_profiler`) with CMake for the same
+//! archs and links it statically, caching the result per CUTLASS version,
+//! CUDA toolkit and arch list. `manifest` enumerates its operations and
+//! launches them, directly or through a `cutlass::library::Handle`. The
+//! `profiler` feature builds `cutlass_profiler` from the same kernels, and
+//! `profiler` runs it and parses its report.
+//!
+//! The `jit` feature compiles CUTLASS kernel instantiations at runtime with
+//! NVRTC instead, for configurations no ahead-of-time build covers: `jit`
+//! caches the resulting cubins on disk, loads them with the CUDA driver API
+//! (`module`), and generates GEMMs of any tiling through `jit::JitGemm`.
+//!
+//! The `prebuilt-kernels` feature needs no `nvcc` at all: `prebuilt` embeds
+//! a curated fatbin of common GEMM kernels, compiled once for the release,
+//! and loads it through `module` at runtime.
+//!
+//! `module` itself is the `driver` feature, which only links `libcuda`. The
+//! `fatbin` feature bundles cubins and PTX for several archs into one blob,
+//! from a build script, and picks the image for a device at runtime, so one
+//! binary can ship to a mixed GPU fleet.
+//!
+//! The `reference` feature compiles CUTLASS's host and device reference GEMM
+//! and convolution into the shim, and `reference` runs them so tests can
+//! check device results against CUTLASS's own arithmetic. It also enables
+//! the configs' `verify` flag, which checks each launch against the device
+//! reference, and `fill` to initialize and compare device tensors.
+//!
+//! The `managed` feature adds `managed::DeviceBuffer`, device allocations
+//! aligned for CUTLASS's vector and TMA loads, and `managed::ManagedTensor`,
+//! the counterpart of `cutlass::HostTensor`: paired host and device
+//! allocations of a tensor, synchronized on request and viewed as a
+//! [`tensor::TensorView`] for the launch helpers. Unlike the shim, it
+//! allocates device memory itself.
+//!
+//! The `autotune` feature times the candidate kernels for a problem, from
+//! the shim's GEMM scheduling variants to the CUTLASS library's operations,
+//! and caches the fastest per device and problem bucket on disk. Untuned
+//! problems fall back to [`gemm::GemmConfig::plan`], which picks a schedule
+//! from the kernel's tile shape and the device's SM count.
+//!
+//! The `build` feature adds `build::apply_to`, which configures a
+//! `cc::Build` in a dependent crate's build script for the CUTLASS headers
+//! and SM versions this crate uses, and `build::gencode_flags`, which turns
+//! an arch list such as `["80", "90a", "compute_90"]` into `-gencode` flags.
+//! `build::KernelManifest` instantiates the GEMMs a `cutlass-kernels.toml`
+//! declares, with `extern "C"` entry points and their Rust declarations.
+//! The `bindgen` feature adds `bindgen_builder`, a `bindgen::Builder` set up
+//! for headers that include CUTLASS.
+//!
+//! The `cute` feature ports CuTe's layout algebra (`cute::Layout` with
+//! coalesce, complement, composition and the logical divide and product) to
+//! Rust, for build scripts that reason about kernel layouts, along with a
+//! table of the MMA and copy atoms each architecture offers.
+//!
+//! The `half` feature implements [`types::Element`] for `half::f16` and
+//! `half::bf16`, so f16 and bf16 data can be held in those rather than as
+//! `u16` bit patterns.
 
 #![no_std]
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 
-// This crate is intentionally minimal - it exists primarily to manage
-// CUTLASS headers as a Cargo dependency. The actual C++/CUDA code is
-// header-only and will be included by dependent crates via their build scripts.
+#[cfg(feature = "std")]
+extern crate std;
+
+// The CUTLASS library is hosted C++, so its bindings may allocate.
+#[cfg(feature = "library")]
+extern crate alloc;
+
+// This crate exists primarily to manage CUTLASS headers as a Cargo
+// dependency. The actual C++/CUDA code is header-only and will be included by
+// dependent crates via their build scripts; the optional shim below only
+// covers operations that benefit from being instantiated once, here.
+
+pub mod coord;
+pub mod int4;
+pub mod library;
+pub mod tensor;
+pub mod types;
+
+#[cfg(feature = "autotune")]
+pub mod autotune;
+#[cfg(feature = "shim")]
+pub mod b2b_conv;
+#[cfg(feature = "shim")]
+pub mod b2b_gemm;
+#[cfg(feature = "shim")]
+pub mod blas3;
+#[cfg(feature = "sm100")]
+pub mod block_scaled;
+#[cfg(feature = "build")]
+pub mod build;
+#[cfg(feature = "bindgen")]
+pub use build::bindgen_builder;
+#[cfg(any(feature = "autotune", feature = "jit"))]
+mod cache;
+#[cfg(feature = "shim")]
+pub mod context;
+#[cfg(feature = "shim")]
+pub mod conv;
+#[cfg(feature = "shim")]
+pub mod conv_quant;
+#[cfg(feature = "cute")]
+pub mod cute;
+#[cfg(feature = "shim")]
+pub mod ell_gemm;
+#[cfg(feature = "shim")]
+pub mod error;
+#[cfg(feature = "shim")]
+pub mod evt;
+#[cfg(feature = "fatbin")]
+pub mod fatbin;
+#[cfg(feature = "shim")]
+pub mod ffi;
+#[cfg(feature = "reference")]
+pub mod fill;
+#[cfg(feature = "shim")]
+pub mod gemm;
+#[cfg(feature = "shim")]
+pub mod gemm_layernorm;
+#[cfg(feature = "shim")]
+pub mod gemm_softmax;
+#[cfg(feature = "shim")]
+pub mod gemv;
+#[cfg(feature = "jit")]
+pub mod jit;
+#[cfg(feature = "managed")]
+pub mod managed;
+#[cfg(feature = "library")]
+pub mod manifest;
+#[cfg(feature = "driver")]
+pub mod module;
+#[cfg(feature = "shim")]
+pub mod planar_complex;
+#[cfg(feature = "prebuilt-kernels")]
+pub mod prebuilt;
+#[cfg(feature = "profiler")]
+pub mod profiler;
+#[cfg(feature = "shim")]
+pub mod reduction;
+#[cfg(feature = "reference")]
+pub mod reference;
+#[cfg(feature = "shim")]
+pub mod sparse;
+#[cfg(feature = "shim")]
+pub mod transform;
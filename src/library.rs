@@ -0,0 +1,569 @@
+//! Operand and operation descriptions mirroring `cutlass::library`'s
+//! `NumericTypeID`, `LayoutTypeID` and the other enums of
+//! `tools/library/include/cutlass/library/types.h`, for code that picks
+//! kernels at runtime (see `manifest` with the `library` feature).
+//!
+//! The discriminants follow the declaration order of the CUTLASS enums, so a
+//! value read from the CUTLASS library converts with a plain cast.
+
+use core::fmt;
+
+use crate::types::DataType;
+
+/// Numeric type of a tensor element, as `cutlass::library::NumericTypeID`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NumericTypeID {
+    #[default]
+    Unknown = 0,
+    Void = 1,
+    B1 = 2,
+    U2 = 3,
+    U4 = 4,
+    U8 = 5,
+    U16 = 6,
+    U32 = 7,
+    U64 = 8,
+    S2 = 9,
+    S4 = 10,
+    S8 = 11,
+    S16 = 12,
+    S32 = 13,
+    S64 = 14,
+    /// `float_e4m3_t`
+    FE4M3 = 15,
+    /// `float_e5m2_t`
+    FE5M2 = 16,
+    /// `float_e2m3_t`
+    FE2M3 = 17,
+    /// `float_e3m2_t`
+    FE3M2 = 18,
+    /// `float_e2m1_t`
+    FE2M1 = 19,
+    /// `float_ue8m0_t`, the power-of-two block scale of MX formats.
+    FUE8M0 = 20,
+    /// `float_ue4m3_t`, the block scale of NVFP4.
+    FUE4M3 = 21,
+    /// 8-bit float whose encoding is chosen at runtime.
+    F8 = 22,
+    /// 6-bit float whose encoding is chosen at runtime.
+    F6 = 23,
+    /// 4-bit float whose encoding is chosen at runtime.
+    F4 = 24,
+    F16 = 25,
+    BF16 = 26,
+    TF32 = 27,
+    F32 = 28,
+    F64 = 29,
+    CF16 = 30,
+    CBF16 = 31,
+    CF32 = 32,
+    CTF32 = 33,
+    CF64 = 34,
+    CS2 = 35,
+    CS4 = 36,
+    CS8 = 37,
+    CS16 = 38,
+    CS32 = 39,
+    CS64 = 40,
+    CU2 = 41,
+    CU4 = 42,
+    CU8 = 43,
+    CU16 = 44,
+    CU32 = 45,
+    CU64 = 46,
+    Invalid = 47,
+}
+
+impl NumericTypeID {
+    /// Storage size of one element in bits; `0` for [`Unknown`](Self::Unknown),
+    /// [`Void`](Self::Void) and [`Invalid`](Self::Invalid).
+    pub const fn size_in_bits(self) -> u32 {
+        match self {
+            Self::Unknown | Self::Void | Self::Invalid => 0,
+            Self::B1 => 1,
+            Self::U2 | Self::S2 => 2,
+            Self::U4 | Self::S4 | Self::FE2M1 | Self::F4 | Self::CS2 | Self::CU2 => 4,
+            Self::FE2M3 | Self::FE3M2 | Self::F6 => 6,
+            Self::U8
+            | Self::S8
+            | Self::FE4M3
+            | Self::FE5M2
+            | Self::FUE8M0
+            | Self::FUE4M3
+            | Self::F8
+            | Self::CS4
+            | Self::CU4 => 8,
+            Self::U16 | Self::S16 | Self::F16 | Self::BF16 | Self::CS8 | Self::CU8 => 16,
+            Self::U32
+            | Self::S32
+            | Self::TF32
+            | Self::F32
+            | Self::CF16
+            | Self::CBF16
+            | Self::CS16
+            | Self::CU16 => 32,
+            Self::U64
+            | Self::S64
+            | Self::F64
+            | Self::CF32
+            | Self::CTF32
+            | Self::CS32
+            | Self::CU32 => 64,
+            Self::CF64 | Self::CS64 | Self::CU64 => 128,
+        }
+    }
+
+    /// Whether this is a real or complex floating-point type.
+    pub const fn is_float(self) -> bool {
+        matches!(
+            self,
+            Self::FE4M3
+                | Self::FE5M2
+                | Self::FE2M3
+                | Self::FE3M2
+                | Self::FE2M1
+                | Self::FUE8M0
+                | Self::FUE4M3
+                | Self::F8
+                | Self::F6
+                | Self::F4
+                | Self::F16
+                | Self::BF16
+                | Self::TF32
+                | Self::F32
+                | Self::F64
+                | Self::CF16
+                | Self::CBF16
+                | Self::CF32
+                | Self::CTF32
+                | Self::CF64
+        )
+    }
+
+    /// Whether this is a real or complex integer type (including [`B1`](Self::B1)).
+    pub const fn is_integer(self) -> bool {
+        matches!(
+            self,
+            Self::B1
+                | Self::U2
+                | Self::U4
+                | Self::U8
+                | Self::U16
+                | Self::U32
+                | Self::U64
+                | Self::S2
+                | Self::S4
+                | Self::S8
+                | Self::S16
+                | Self::S32
+                | Self::S64
+                | Self::CS2
+                | Self::CS4
+                | Self::CS8
+                | Self::CS16
+                | Self::CS32
+                | Self::CS64
+                | Self::CU2
+                | Self::CU4
+                | Self::CU8
+                | Self::CU16
+                | Self::CU32
+                | Self::CU64
+        )
+    }
+
+    /// Whether the type can represent negative values. The unsigned scale
+    /// types [`FUE8M0`](Self::FUE8M0) and [`FUE4M3`](Self::FUE4M3) cannot.
+    pub const fn is_signed(self) -> bool {
+        match self {
+            Self::FUE8M0 | Self::FUE4M3 => false,
+            Self::S2
+            | Self::S4
+            | Self::S8
+            | Self::S16
+            | Self::S32
+            | Self::S64
+            | Self::CS2
+            | Self::CS4
+            | Self::CS8
+            | Self::CS16
+            | Self::CS32
+            | Self::CS64 => true,
+            other => other.is_float(),
+        }
+    }
+
+    pub const fn is_complex(self) -> bool {
+        matches!(
+            self,
+            Self::CF16
+                | Self::CBF16
+                | Self::CF32
+                | Self::CTF32
+                | Self::CF64
+                | Self::CS2
+                | Self::CS4
+                | Self::CS8
+                | Self::CS16
+                | Self::CS32
+                | Self::CS64
+                | Self::CU2
+                | Self::CU4
+                | Self::CU8
+                | Self::CU16
+                | Self::CU32
+                | Self::CU64
+        )
+    }
+
+    /// The real type of one component of a complex type; real types map to
+    /// themselves.
+    pub const fn real(self) -> Self {
+        match self {
+            Self::CF16 => Self::F16,
+            Self::CBF16 => Self::BF16,
+            Self::CF32 => Self::F32,
+            Self::CTF32 => Self::TF32,
+            Self::CF64 => Self::F64,
+            Self::CS2 => Self::S2,
+            Self::CS4 => Self::S4,
+            Self::CS8 => Self::S8,
+            Self::CS16 => Self::S16,
+            Self::CS32 => Self::S32,
+            Self::CS64 => Self::S64,
+            Self::CU2 => Self::U2,
+            Self::CU4 => Self::U4,
+            Self::CU8 => Self::U8,
+            Self::CU16 => Self::U16,
+            Self::CU32 => Self::U32,
+            Self::CU64 => Self::U64,
+            other => other,
+        }
+    }
+
+    /// The operand type of the shim's [`DataType`], if it has one.
+    pub const fn data_type(self) -> Option<DataType> {
+        match self {
+            Self::F16 => Some(DataType::F16),
+            Self::BF16 => Some(DataType::BF16),
+            Self::F32 => Some(DataType::F32),
+            Self::S8 => Some(DataType::S8),
+            Self::F64 => Some(DataType::F64),
+            Self::CF32 => Some(DataType::C32),
+            Self::CF64 => Some(DataType::C64),
+            Self::S32 => Some(DataType::S32),
+            Self::FE4M3 => Some(DataType::E4M3),
+            Self::FE5M2 => Some(DataType::E5M2),
+            _ => None,
+        }
+    }
+
+    /// The name `cutlass::library::to_string` gives this type, e.g. `"f16"`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Void => "void",
+            Self::B1 => "b1",
+            Self::U2 => "u2",
+            Self::U4 => "u4",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::S2 => "s2",
+            Self::S4 => "s4",
+            Self::S8 => "s8",
+            Self::S16 => "s16",
+            Self::S32 => "s32",
+            Self::S64 => "s64",
+            Self::FE4M3 => "fe4m3",
+            Self::FE5M2 => "fe5m2",
+            Self::FE2M3 => "fe2m3",
+            Self::FE3M2 => "fe3m2",
+            Self::FE2M1 => "fe2m1",
+            Self::FUE8M0 => "fue8m0",
+            Self::FUE4M3 => "fue4m3",
+            Self::F8 => "f8",
+            Self::F6 => "f6",
+            Self::F4 => "f4",
+            Self::F16 => "f16",
+            Self::BF16 => "bf16",
+            Self::TF32 => "tf32",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+            Self::CF16 => "cf16",
+            Self::CBF16 => "cbf16",
+            Self::CF32 => "cf32",
+            Self::CTF32 => "ctf32",
+            Self::CF64 => "cf64",
+            Self::CS2 => "cs2",
+            Self::CS4 => "cs4",
+            Self::CS8 => "cs8",
+            Self::CS16 => "cs16",
+            Self::CS32 => "cs32",
+            Self::CS64 => "cs64",
+            Self::CU2 => "cu2",
+            Self::CU4 => "cu4",
+            Self::CU8 => "cu8",
+            Self::CU16 => "cu16",
+            Self::CU32 => "cu32",
+            Self::CU64 => "cu64",
+            Self::Invalid => "invalid",
+        }
+    }
+}
+
+impl From<DataType> for NumericTypeID {
+    fn from(element: DataType) -> Self {
+        match element {
+            DataType::F16 => Self::F16,
+            DataType::BF16 => Self::BF16,
+            DataType::F32 => Self::F32,
+            DataType::S8 => Self::S8,
+            DataType::F64 => Self::F64,
+            DataType::C32 => Self::CF32,
+            DataType::C64 => Self::CF64,
+            DataType::S32 => Self::S32,
+            DataType::E4M3 => Self::FE4M3,
+            DataType::E5M2 => Self::FE5M2,
+        }
+    }
+}
+
+impl fmt::Display for NumericTypeID {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Memory layout of a matrix or tensor operand, as
+/// `cutlass::library::LayoutTypeID`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LayoutTypeID {
+    #[default]
+    Unknown = 0,
+    ColumnMajor = 1,
+    RowMajor = 2,
+    /// Scale-factor tensor of a block-scaled operand.
+    BlockScalingTensor = 3,
+    ColumnMajorInterleavedK2 = 4,
+    RowMajorInterleavedK2 = 5,
+    ColumnMajorInterleavedK4 = 6,
+    RowMajorInterleavedK4 = 7,
+    ColumnMajorInterleavedK16 = 8,
+    RowMajorInterleavedK16 = 9,
+    ColumnMajorInterleavedK32 = 10,
+    RowMajorInterleavedK32 = 11,
+    ColumnMajorInterleavedK64 = 12,
+    RowMajorInterleavedK64 = 13,
+    TensorNCHW = 14,
+    TensorNCDHW = 15,
+    TensorNHWC = 16,
+    TensorNDHWC = 17,
+    TensorNC32HW32 = 18,
+    TensorC32RSK32 = 19,
+    TensorNC64HW64 = 20,
+    TensorC64RSK64 = 21,
+    Invalid = 22,
+}
+
+impl LayoutTypeID {
+    /// Whether this is a (possibly interleaved) column-major matrix layout.
+    pub const fn is_column_major(self) -> bool {
+        matches!(
+            self,
+            Self::ColumnMajor
+                | Self::ColumnMajorInterleavedK2
+                | Self::ColumnMajorInterleavedK4
+                | Self::ColumnMajorInterleavedK16
+                | Self::ColumnMajorInterleavedK32
+                | Self::ColumnMajorInterleavedK64
+        )
+    }
+
+    /// Whether this is a (possibly interleaved) row-major matrix layout.
+    pub const fn is_row_major(self) -> bool {
+        matches!(
+            self,
+            Self::RowMajor
+                | Self::RowMajorInterleavedK2
+                | Self::RowMajorInterleavedK4
+                | Self::RowMajorInterleavedK16
+                | Self::RowMajorInterleavedK32
+                | Self::RowMajorInterleavedK64
+        )
+    }
+
+    pub const fn is_matrix(self) -> bool {
+        self.is_column_major() || self.is_row_major()
+    }
+
+    /// Whether this is an activation or filter tensor layout.
+    pub const fn is_tensor(self) -> bool {
+        matches!(
+            self,
+            Self::TensorNCHW
+                | Self::TensorNCDHW
+                | Self::TensorNHWC
+                | Self::TensorNDHWC
+                | Self::TensorNC32HW32
+                | Self::TensorC32RSK32
+                | Self::TensorNC64HW64
+                | Self::TensorC64RSK64
+        )
+    }
+
+    /// Number of elements interleaved along the contiguous dimension; `1` for
+    /// plain layouts.
+    pub const fn interleave(self) -> i32 {
+        match self {
+            Self::ColumnMajorInterleavedK2 | Self::RowMajorInterleavedK2 => 2,
+            Self::ColumnMajorInterleavedK4 | Self::RowMajorInterleavedK4 => 4,
+            Self::ColumnMajorInterleavedK16 | Self::RowMajorInterleavedK16 => 16,
+            Self::ColumnMajorInterleavedK32
+            | Self::RowMajorInterleavedK32
+            | Self::TensorNC32HW32
+            | Self::TensorC32RSK32 => 32,
+            Self::ColumnMajorInterleavedK64
+            | Self::RowMajorInterleavedK64
+            | Self::TensorNC64HW64
+            | Self::TensorC64RSK64 => 64,
+            _ => 1,
+        }
+    }
+
+    /// Number of strides describing the layout, as
+    /// `cutlass::library::get_layout_stride_rank`; `0` for layouts without
+    /// one.
+    pub const fn stride_rank(self) -> i32 {
+        if self.is_matrix() {
+            1
+        } else {
+            match self {
+                Self::TensorNCHW
+                | Self::TensorNHWC
+                | Self::TensorNC32HW32
+                | Self::TensorC32RSK32
+                | Self::TensorNC64HW64
+                | Self::TensorC64RSK64 => 3,
+                Self::TensorNCDHW | Self::TensorNDHWC => 4,
+                _ => 0,
+            }
+        }
+    }
+
+    /// The name `cutlass::library::to_string` gives this layout, e.g. `"t"`
+    /// for row-major.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::ColumnMajor => "n",
+            Self::RowMajor => "t",
+            Self::BlockScalingTensor => "SfAtom",
+            Self::ColumnMajorInterleavedK2 => "n2",
+            Self::RowMajorInterleavedK2 => "t2",
+            Self::ColumnMajorInterleavedK4 => "n4",
+            Self::RowMajorInterleavedK4 => "t4",
+            Self::ColumnMajorInterleavedK16 => "n16",
+            Self::RowMajorInterleavedK16 => "t16",
+            Self::ColumnMajorInterleavedK32 => "n32",
+            Self::RowMajorInterleavedK32 => "t32",
+            Self::ColumnMajorInterleavedK64 => "n64",
+            Self::RowMajorInterleavedK64 => "t64",
+            Self::TensorNCHW => "nchw",
+            Self::TensorNCDHW => "ncdhw",
+            Self::TensorNHWC => "nhwc",
+            Self::TensorNDHWC => "ndhwc",
+            Self::TensorNC32HW32 => "nc32hw32",
+            Self::TensorC32RSK32 => "c32rsk32",
+            Self::TensorNC64HW64 => "nc64hw64",
+            Self::TensorC64RSK64 => "c64rsk64",
+            Self::Invalid => "invalid",
+        }
+    }
+}
+
+impl fmt::Display for LayoutTypeID {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Conjugation applied to a complex operand, as
+/// `cutlass::library::ComplexTransform`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ComplexTransform {
+    #[default]
+    None = 0,
+    Conjugate = 1,
+    Invalid = 2,
+}
+
+/// Who implements an operation, as `cutlass::library::Provider`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Provider {
+    #[default]
+    None = 0,
+    Cutlass = 1,
+    ReferenceHost = 2,
+    ReferenceDevice = 3,
+    Cublas = 4,
+    Cudnn = 5,
+    Invalid = 6,
+}
+
+/// Family of an operation in the CUTLASS library, as
+/// `cutlass::library::OperationKind`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OperationKind {
+    #[default]
+    Gemm = 0,
+    BlockScaledGemm = 1,
+    BlockwiseGemm = 2,
+    RankK = 3,
+    Rank2K = 4,
+    Trmm = 5,
+    Symm = 6,
+    Conv2d = 7,
+    Conv3d = 8,
+    EqGemm = 9,
+    SparseGemm = 10,
+    Reduction = 11,
+    GroupedGemm = 12,
+    Invalid = 13,
+}
+
+/// Kind of math unit an operation's MMAs run on, as
+/// `cutlass::library::OpcodeClassID`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OpcodeClassID {
+    #[default]
+    Simt = 0,
+    TensorOp = 1,
+    WmmaTensorOp = 2,
+    SparseTensorOp = 3,
+    BlockScaledTensorOp = 4,
+    Invalid = 5,
+}
+
+/// Variant of a GEMM operation, as `cutlass::library::GemmKind`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GemmKind {
+    #[default]
+    Gemm = 0,
+    Sparse = 1,
+    /// `GemmUniversal`: plain, split-K, batched and pointer-array GEMM in one
+    /// kernel.
+    Universal = 2,
+    PlanarComplex = 3,
+    PlanarComplexArray = 4,
+    Grouped = 5,
+    Invalid = 6,
+}
@@ -0,0 +1,464 @@
+//! Device allocations and tensors owning paired host and device copies
+//! (`managed` feature).
+//!
+//! [`DeviceBuffer`] is an RAII wrapper over `cudaMalloc` / `cudaMallocAsync`
+//! that guarantees the alignment CUTLASS kernels check their operands
+//! against: [`VECTOR_ALIGNMENT`] for the 128-bit accesses of every kernel and
+//! [`TMA_ALIGNMENT`] for the TMA descriptors of the sm_90 / sm_100 kernels.
+//! A misaligned operand otherwise only surfaces as
+//! [`Status::ErrorMisalignedOperand`] at launch time.
+//!
+//! Failing CUDA calls are reported as [`CutlassError::Cuda`] with their
+//! `cudaError_t`, so an out-of-memory allocation reads as such.
+//! [`DeviceBuffer::pitched`] allocates a matrix whose rows (columns) each
+//! start on such a boundary, for leading dimensions that are not a multiple
+//! of the kernel's alignment.
+//!
+//! [`ManagedTensor`] mirrors `cutlass::HostTensor`: it allocates a host
+//! buffer and a device buffer of the same capacity for a matrix or NHWC
+//! tensor, remembers its layout, stride and extent, and copies between the
+//! two with [`sync_device`](ManagedTensor::sync_device) and
+//! [`sync_host`](ManagedTensor::sync_host). The device side converts to the
+//! [`TensorRef`] and [`TensorView`] the launch helpers take, so examples and
+//! tests can set up operands without their own allocation and copy code:
+//!
+//! ```ignore
+//! let mut a = ManagedTensor::<u16>::row_major(&context, MatrixCoord::new(m, k))?;
+//! a.host_mut().fill(0x3c00); // 1.0 in f16, or `half::f16::ONE` with the `half` feature
+//! a.sync_device()?;
+//! let mut config = GemmConfig::new(m, n, k, DataType::F16);
+//! let operands = config.bind_views(a.device_view(), b.device_view(), None, d.device_view())?;
+//! // ... launch, then
+//! d.sync_host()?;
+//! ```
+//!
+//! Everything is freed on drop. Synchronous allocation goes straight to
+//! `cudaMalloc`, so it is meant for setup code rather than hot paths, which
+//! should use [`DeviceBuffer::new_async`] or their own allocator.
+
+use core::ffi::{c_int, c_void};
+use core::mem::size_of;
+use core::ptr;
+use std::vec::Vec;
+
+use crate::context::CutlassContext;
+use crate::coord::{MatrixCoord, Tensor4DCoord};
+use crate::error::{
+    cudaFree, cudaFreeAsync, cudaMalloc, cudaMallocAsync, cudaMallocPitch, cudaMemcpy,
+    cudaMemcpyDeviceToHost, cudaMemcpyHostToDevice, on_device, CutlassError,
+};
+use crate::library::LayoutTypeID;
+use crate::tensor::{TensorRef, TensorView};
+use crate::types::{cudaStream_t, Status};
+
+/// Alignment in bytes every CUTLASS kernel needs of its operands, for
+/// 128-bit vector accesses.
+pub const VECTOR_ALIGNMENT: usize = 16;
+
+/// Alignment in bytes of operands loaded through TMA, as the sm_90 and
+/// sm_100 kernels do. The default of [`DeviceBuffer`].
+pub const TMA_ALIGNMENT: usize = 128;
+
+/// Alignment `cudaMalloc` and `cudaMallocAsync` guarantee; larger ones are
+/// reached by over-allocating.
+const ALLOCATION_ALIGNMENT: usize = 256;
+
+/// `len` elements of device memory on one device, aligned to at least
+/// [`TMA_ALIGNMENT`] bytes unless created with another alignment, and freed
+/// on drop. The contents start out uninitialized.
+#[derive(Debug)]
+pub struct DeviceBuffer<T> {
+    ptr: *mut T,
+    /// The allocation as returned by CUDA, which `ptr` may be offset into.
+    base: *mut c_void,
+    len: usize,
+    ordinal: c_int,
+    /// The stream of a `cudaMallocAsync` allocation, which frees it.
+    stream: Option<cudaStream_t>,
+}
+
+impl<T> DeviceBuffer<T> {
+    /// `len` elements on `context`'s device, aligned to [`TMA_ALIGNMENT`].
+    pub fn new(context: &CutlassContext, len: usize) -> Result<Self, CutlassError> {
+        Self::with_alignment(context, len, TMA_ALIGNMENT)
+    }
+
+    /// `len` elements aligned to `alignment` bytes, a power of two, else
+    /// [`Status::ErrorInvalidProblem`].
+    pub fn with_alignment(
+        context: &CutlassContext,
+        len: usize,
+        alignment: usize,
+    ) -> Result<Self, CutlassError> {
+        Self::allocate(context, len, alignment, None)
+    }
+
+    /// [`with_alignment`](Self::with_alignment) from the stream-ordered
+    /// allocator: the memory is usable by work queued on `stream` after this
+    /// call, and freed on `stream` on drop.
+    ///
+    /// # Safety
+    ///
+    /// `stream` must be a stream of `context`'s device that outlives the
+    /// buffer.
+    pub unsafe fn new_async(
+        context: &CutlassContext,
+        len: usize,
+        alignment: usize,
+        stream: cudaStream_t,
+    ) -> Result<Self, CutlassError> {
+        Self::allocate(context, len, alignment, Some(stream))
+    }
+
+    /// A `rows x columns` matrix in `layout` (row- or column-major) whose
+    /// leading dimension is padded by `cudaMallocPitch`, so that every row
+    /// (column) starts on the device's pitch alignment, at least
+    /// [`TMA_ALIGNMENT`] on every GPU CUTLASS supports. Returns the buffer
+    /// and a view of the matrix in it.
+    pub fn pitched(
+        context: &CutlassContext,
+        extent: MatrixCoord,
+        layout: LayoutTypeID,
+    ) -> Result<(Self, TensorView<T>), CutlassError> {
+        let (outer, inner) = match layout {
+            LayoutTypeID::RowMajor => (extent.row, extent.column),
+            LayoutTypeID::ColumnMajor => (extent.column, extent.row),
+            _ => return Err(Status::ErrorInvalidLayout.into()),
+        };
+        let (outer, inner) = match (usize::try_from(outer), usize::try_from(inner)) {
+            (Ok(outer), Ok(inner)) => (outer, inner),
+            _ => return Err(Status::ErrorInvalidProblem.into()),
+        };
+        let width = inner
+            .checked_mul(size_of::<T>())
+            .ok_or(Status::ErrorMemoryAllocation)?;
+        let mut buffer = Self::empty(context, None);
+        if width == 0 || outer == 0 {
+            let view = TensorView::new(
+                TensorRef::new(ptr::null_mut(), layout, inner.max(1) as i64),
+                extent,
+            );
+            return Ok((buffer, view));
+        }
+        let mut pitch = 0;
+        on_device(buffer.ordinal, || {
+            // SAFETY: only writes the allocation and its pitch.
+            CutlassError::cuda(unsafe {
+                cudaMallocPitch(&mut buffer.base, &mut pitch, width, outer)
+            })
+        })?;
+        buffer.ptr = buffer.base.cast();
+        // The pitch is a multiple of a power of two no smaller than any
+        // element, so this only fails for odd-sized `T`.
+        if pitch % size_of::<T>() != 0 {
+            return Err(Status::ErrorMisalignedOperand.into());
+        }
+        let ld = pitch / size_of::<T>();
+        buffer.len = ld * outer;
+        let view = TensorView::new(TensorRef::new(buffer.ptr, layout, ld as i64), extent);
+        Ok((buffer, view))
+    }
+
+    fn empty(context: &CutlassContext, stream: Option<cudaStream_t>) -> Self {
+        Self {
+            ptr: ptr::null_mut(),
+            base: ptr::null_mut(),
+            len: 0,
+            ordinal: context.device,
+            stream,
+        }
+    }
+
+    fn allocate(
+        context: &CutlassContext,
+        len: usize,
+        alignment: usize,
+        stream: Option<cudaStream_t>,
+    ) -> Result<Self, CutlassError> {
+        if !alignment.is_power_of_two() {
+            return Err(Status::ErrorInvalidProblem.into());
+        }
+        let bytes = len
+            .checked_mul(size_of::<T>())
+            .ok_or(Status::ErrorMemoryAllocation)?;
+        let mut buffer = Self::empty(context, stream);
+        if bytes == 0 {
+            return Ok(buffer);
+        }
+        let slack = alignment.saturating_sub(ALLOCATION_ALIGNMENT);
+        let size = bytes
+            .checked_add(slack)
+            .ok_or(Status::ErrorMemoryAllocation)?;
+        on_device(buffer.ordinal, || {
+            // SAFETY: only writes the allocation; the caller of `new_async`
+            // vouches for the stream.
+            CutlassError::cuda(unsafe {
+                match stream {
+                    Some(stream) => cudaMallocAsync(&mut buffer.base, size, stream),
+                    None => cudaMalloc(&mut buffer.base, size),
+                }
+            })
+        })?;
+        let address = buffer.base as usize;
+        let offset = address.next_multiple_of(alignment) - address;
+        // Only an allocator breaking its own guarantee leaves too little
+        // slack; drop frees the allocation.
+        if offset > slack {
+            return Err(Status::ErrorMisalignedOperand.into());
+        }
+        buffer.ptr = buffer.base.wrapping_byte_add(offset).cast();
+        buffer.len = len;
+        Ok(buffer)
+    }
+
+    /// The aligned device pointer, null for an empty buffer.
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    pub fn as_mut_ptr(&self) -> *mut T {
+        self.ptr
+    }
+
+    /// Number of elements, padding included for a pitched buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Copies `src` to the start of the buffer, waiting for the copy.
+    /// [`Status::ErrorInvalidProblem`] if `src` is longer than the buffer.
+    ///
+    /// The copy is ordered on the legacy default stream: synchronize the
+    /// stream of a [`new_async`](Self::new_async) buffer first.
+    pub fn copy_from_host(&self, src: &[T]) -> Result<(), CutlassError> {
+        if src.len() > self.len {
+            return Err(Status::ErrorInvalidProblem.into());
+        }
+        self.copy(
+            self.ptr.cast(),
+            src.as_ptr().cast(),
+            src.len(),
+            cudaMemcpyHostToDevice,
+        )
+    }
+
+    /// Copies the start of the buffer to `dst`, waiting for the copy.
+    /// [`Status::ErrorInvalidProblem`] if `dst` is longer than the buffer.
+    pub fn copy_to_host(&self, dst: &mut [T]) -> Result<(), CutlassError> {
+        if dst.len() > self.len {
+            return Err(Status::ErrorInvalidProblem.into());
+        }
+        self.copy(
+            dst.as_mut_ptr().cast(),
+            self.ptr.cast(),
+            dst.len(),
+            cudaMemcpyDeviceToHost,
+        )
+    }
+
+    fn copy(
+        &self,
+        dst: *mut c_void,
+        src: *const c_void,
+        len: usize,
+        kind: c_int,
+    ) -> Result<(), CutlassError> {
+        let bytes = len * size_of::<T>();
+        if bytes == 0 {
+            return Ok(());
+        }
+        // SAFETY: both buffers hold `bytes` bytes and stay alive for the
+        // synchronous copy.
+        on_device(self.ordinal, || {
+            CutlassError::cuda(unsafe { cudaMemcpy(dst, src, bytes, kind) })
+        })
+    }
+}
+
+impl<T> Drop for DeviceBuffer<T> {
+    fn drop(&mut self) {
+        if self.base.is_null() {
+            return;
+        }
+        // SAFETY: the allocation was made on `ordinal`, on `stream` if any,
+        // and is freed once.
+        let _ = on_device(self.ordinal, || {
+            CutlassError::cuda(unsafe {
+                match self.stream {
+                    Some(stream) => cudaFreeAsync(self.base, stream),
+                    None => cudaFree(self.base),
+                }
+            })
+        });
+    }
+}
+
+/// A tensor with a host and a device copy, in the style of
+/// `cutlass::HostTensor`. `E` is the extent: [`MatrixCoord`] for matrices,
+/// [`Tensor4DCoord`] for NHWC tensors.
+///
+/// The copies are only made consistent by the explicit `sync_*` calls.
+#[derive(Debug)]
+pub struct ManagedTensor<T, E = MatrixCoord> {
+    host: Vec<T>,
+    device: DeviceBuffer<T>,
+    view: TensorView<T, E>,
+}
+
+impl<T: Copy + Default> ManagedTensor<T> {
+    /// A tightly packed row-major matrix on `context`'s device.
+    pub fn row_major(context: &CutlassContext, extent: MatrixCoord) -> Result<Self, CutlassError> {
+        Self::matrix(
+            context,
+            extent,
+            LayoutTypeID::RowMajor,
+            extent.column as i64,
+        )
+    }
+
+    /// A tightly packed column-major matrix on `context`'s device.
+    pub fn column_major(
+        context: &CutlassContext,
+        extent: MatrixCoord,
+    ) -> Result<Self, CutlassError> {
+        Self::matrix(
+            context,
+            extent,
+            LayoutTypeID::ColumnMajor,
+            extent.row as i64,
+        )
+    }
+
+    /// A row- or column-major matrix with leading dimension `stride`, for
+    /// padded operands. Fails like [`TensorView::validate`].
+    pub fn matrix(
+        context: &CutlassContext,
+        extent: MatrixCoord,
+        layout: LayoutTypeID,
+        stride: i64,
+    ) -> Result<Self, CutlassError> {
+        let view = TensorView::new(TensorRef::new(ptr::null_mut(), layout, stride), extent);
+        view.validate()?;
+        Self::allocate(context, view, view.span())
+    }
+}
+
+impl<T: Copy + Default> ManagedTensor<T, Tensor4DCoord> {
+    /// A tightly packed NHWC tensor on `context`'s device.
+    pub fn nhwc(context: &CutlassContext, extent: Tensor4DCoord) -> Result<Self, CutlassError> {
+        let view = TensorView::nhwc(ptr::null_mut(), extent);
+        view.validate()?;
+        Self::allocate(context, view, extent.product())
+    }
+}
+
+impl<T: Copy + Default, E: Copy> ManagedTensor<T, E> {
+    /// Allocates `capacity` elements on both sides for `view`, whose pointer
+    /// is replaced by the device allocation. The host copy starts out as
+    /// `T::default()`, the device copy uninitialized.
+    fn allocate(
+        context: &CutlassContext,
+        view: TensorView<T, E>,
+        capacity: i64,
+    ) -> Result<Self, CutlassError> {
+        let capacity = usize::try_from(capacity).map_err(|_| Status::ErrorInvalidProblem)?;
+        let device = DeviceBuffer::new(context, capacity)?;
+        let mut view = view;
+        view.tensor_ref.ptr = device.as_mut_ptr();
+        Ok(Self {
+            host: std::vec![T::default(); capacity],
+            device,
+            view,
+        })
+    }
+}
+
+impl<T, E: Copy> ManagedTensor<T, E> {
+    /// The host copy, in the tensor's layout, gaps included.
+    pub fn host(&self) -> &[T] {
+        &self.host
+    }
+
+    pub fn host_mut(&mut self) -> &mut [T] {
+        &mut self.host
+    }
+
+    /// The device allocation, null for an empty tensor.
+    pub fn device_ptr(&self) -> *mut T {
+        self.device.as_mut_ptr()
+    }
+
+    pub fn device_ref(&self) -> TensorRef<T> {
+        self.view.tensor_ref
+    }
+
+    /// The device copy as a view, for the launch helpers.
+    pub fn device_view(&self) -> TensorView<T, E> {
+        self.view
+    }
+
+    pub fn extent(&self) -> E {
+        self.view.extent
+    }
+
+    pub fn layout(&self) -> LayoutTypeID {
+        self.view.layout()
+    }
+
+    pub fn stride(&self) -> i64 {
+        self.view.stride()
+    }
+
+    /// Number of elements in each copy.
+    pub fn capacity(&self) -> usize {
+        self.host.len()
+    }
+
+    /// Copies the host copy to the device, waiting for the copy.
+    pub fn sync_device(&self) -> Result<(), CutlassError> {
+        self.device.copy_from_host(&self.host)
+    }
+
+    /// Copies the device copy to the host, waiting for the copy and any work
+    /// queued before it on the legacy default stream.
+    pub fn sync_host(&mut self) -> Result<(), CutlassError> {
+        self.device.copy_to_host(&mut self.host)
+    }
+}
+
+impl<T> ManagedTensor<T> {
+    /// The host element at `coord`, panicking outside the extent.
+    pub fn at(&self, coord: MatrixCoord) -> &T {
+        &self.host[self.index(coord)]
+    }
+
+    pub fn at_mut(&mut self, coord: MatrixCoord) -> &mut T {
+        let index = self.index(coord);
+        &mut self.host[index]
+    }
+
+    fn index(&self, coord: MatrixCoord) -> usize {
+        let extent = self.view.extent;
+        assert!(
+            (0..extent.row).contains(&coord.row) && (0..extent.column).contains(&coord.column),
+            "coordinate outside the tensor"
+        );
+        self.view.tensor_ref.offset(coord) as usize
+    }
+}
+
+impl<T, E: Copy> From<&ManagedTensor<T, E>> for TensorRef<T> {
+    fn from(tensor: &ManagedTensor<T, E>) -> Self {
+        tensor.device_ref()
+    }
+}
+
+impl<T, E: Copy> From<&ManagedTensor<T, E>> for TensorView<T, E> {
+    fn from(tensor: &ManagedTensor<T, E>) -> Self {
+        tensor.device_view()
+    }
+}
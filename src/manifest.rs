@@ -0,0 +1,600 @@
+//! Runtime access to CUTLASS's prebuilt kernel library (`cutlass::library`),
+//! built and linked by the `library` feature.
+//!
+//! A [`Manifest`] lists every [`Operation`] the library instantiates, each
+//! with a description of its problem type and tiling, so kernels can be
+//! enumerated and picked at runtime instead of compiled in. Universal GEMM
+//! operations launch through [`cutlass_sys_operation_gemm`], with the usual
+//! [`Operation::gemm_workspace_size`] / [`Operation::gemm_supports`] queries
+//! on a [`LibraryGemmArguments`].
+//!
+//! [`Manifest::find_gemm`] looks operations up by the operand types and
+//! layouts known at runtime (a [`GemmFunctionalKey`]), ordered by
+//! preference, and [`Manifest::select_gemm`] picks the best one that can run
+//! a given problem.
+//!
+//! A [`LibraryHandle`] is CUTLASS's own front end to the same kernels: each
+//! [`cutlass_sys_library_handle_gemm`] call picks a matching operation from
+//! CUTLASS's process-wide manifest. Unlike everything else in this crate, a
+//! handle owns a device workspace allocation.
+
+use core::ffi::{c_char, c_void, CStr};
+use core::marker::PhantomData;
+use core::ptr::{self, NonNull};
+
+use alloc::vec::Vec;
+
+use crate::context::CutlassContext;
+use crate::coord::GemmCoord;
+use crate::library::{
+    ComplexTransform, GemmKind, LayoutTypeID, NumericTypeID, OpcodeClassID, OperationKind, Provider,
+};
+use crate::types::{cudaStream_t, Status};
+
+/// Opaque `cutlass::library::Manifest`.
+#[repr(C)]
+pub struct RawManifest {
+    _opaque: [u8; 0],
+}
+
+/// Opaque `cutlass::library::Operation`.
+#[repr(C)]
+pub struct RawOperation {
+    _opaque: [u8; 0],
+}
+
+/// Opaque `cutlass::library::Handle`.
+#[repr(C)]
+pub struct RawLibraryHandle {
+    _opaque: [u8; 0],
+}
+
+/// How a universal GEMM treats its batch dimension, as
+/// `cutlass::gemm::GemmUniversalMode`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GemmUniversalMode {
+    #[default]
+    Gemm = 0,
+    /// Split-K with partial sums reduced in a separate kernel.
+    GemmSplitKParallel = 1,
+    /// Strided batches, `batch_count` of them.
+    Batched = 2,
+    /// `a`, `b`, `c` and `d` are device arrays of per-batch pointers.
+    Array = 3,
+}
+
+/// Element, layout and alignment of one operand of an operation.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TensorDescription {
+    pub element: NumericTypeID,
+    pub layout: LayoutTypeID,
+    /// Required alignment, in elements, of the pointer and leading
+    /// dimension.
+    pub alignment: i32,
+}
+
+/// What an operation computes and how it is tiled, flattened from
+/// `cutlass::library::OperationDescription` and its `TileDescription`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct OperationDescription {
+    /// Procedural name, e.g. `cutlass_tensorop_h16816gemm_128x128_32x3_nn_align8`;
+    /// see [`Operation::name`].
+    pub name: *const c_char,
+    pub kind: OperationKind,
+    pub provider: Provider,
+    pub threadblock_shape: GemmCoord,
+    pub threadblock_stages: i32,
+    pub warp_count: GemmCoord,
+    pub cluster_shape: GemmCoord,
+    pub instruction_shape: GemmCoord,
+    pub element_accumulator: NumericTypeID,
+    pub opcode_class: OpcodeClassID,
+    /// Lowest compute capability (`major * 10 + minor`) the operation runs on.
+    pub minimum_compute_capability: i32,
+    /// Highest compute capability the operation runs on.
+    pub maximum_compute_capability: i32,
+}
+
+impl OperationDescription {
+    /// Whether the operation was compiled for `context`'s device.
+    pub const fn supports_device(&self, context: &CutlassContext) -> bool {
+        let cc = context.compute_capability();
+        cc >= self.minimum_compute_capability && cc <= self.maximum_compute_capability
+    }
+}
+
+/// Operand types of a GEMM operation, from
+/// `cutlass::library::GemmDescription`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GemmDescription {
+    pub gemm_kind: GemmKind,
+    pub a: TensorDescription,
+    pub b: TensorDescription,
+    pub c: TensorDescription,
+    pub d: TensorDescription,
+    /// Type of `alpha` and `beta`.
+    pub element_epilogue: NumericTypeID,
+    pub transform_a: ComplexTransform,
+    pub transform_b: ComplexTransform,
+}
+
+/// Problem and operands of a universal GEMM launched through the library:
+///
+/// ```text
+/// D = alpha * A * B + beta * C
+/// ```
+///
+/// with each operand in the layout of the operation (or, for a
+/// [`LibraryHandle`], of the [`GemmDescription`] passed alongside).
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct LibraryGemmArguments {
+    pub mode: GemmUniversalMode,
+    pub problem: GemmCoord,
+    /// Batches for [`GemmUniversalMode::Batched`] and
+    /// [`GemmUniversalMode::Array`], or split-K slices for the split-K mode.
+    pub batch_count: i32,
+    /// Host pointer to `alpha`, of the operation's epilogue element type.
+    pub alpha: *const c_void,
+    /// Host pointer to `beta`, of the operation's epilogue element type.
+    pub beta: *const c_void,
+    pub a: *const c_void,
+    pub b: *const c_void,
+    pub c: *const c_void,
+    pub d: *mut c_void,
+    pub lda: i64,
+    pub ldb: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub batch_stride_a: i64,
+    pub batch_stride_b: i64,
+    pub batch_stride_c: i64,
+    pub batch_stride_d: i64,
+}
+
+impl LibraryGemmArguments {
+    /// A single `problem` GEMM with every pointer null and every leading
+    /// dimension zero, to be filled in.
+    pub const fn new(problem: GemmCoord) -> Self {
+        Self {
+            mode: GemmUniversalMode::Gemm,
+            problem,
+            batch_count: 1,
+            alpha: ptr::null(),
+            beta: ptr::null(),
+            a: ptr::null(),
+            b: ptr::null(),
+            c: ptr::null(),
+            d: ptr::null_mut(),
+            lda: 0,
+            ldb: 0,
+            ldc: 0,
+            ldd: 0,
+            batch_stride_a: 0,
+            batch_stride_b: 0,
+            batch_stride_c: 0,
+            batch_stride_d: 0,
+        }
+    }
+}
+
+/// What a GEMM computes, independent of how it is tiled: the operand types
+/// and layouts [`Manifest::find_gemm`] matches, after
+/// `cutlass::library::GemmFunctionalKey`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GemmFunctionalKey {
+    pub element_a: NumericTypeID,
+    pub layout_a: LayoutTypeID,
+    pub element_b: NumericTypeID,
+    pub layout_b: LayoutTypeID,
+    pub element_accumulator: NumericTypeID,
+}
+
+impl GemmFunctionalKey {
+    pub const fn new(
+        element_a: NumericTypeID,
+        element_b: NumericTypeID,
+        element_accumulator: NumericTypeID,
+        layout_a: LayoutTypeID,
+        layout_b: LayoutTypeID,
+    ) -> Self {
+        Self {
+            element_a,
+            layout_a,
+            element_b,
+            layout_b,
+            element_accumulator,
+        }
+    }
+
+    /// Whether an operation with these descriptions computes this key.
+    pub fn matches(&self, description: &OperationDescription, gemm: &GemmDescription) -> bool {
+        gemm.a.element == self.element_a
+            && gemm.a.layout == self.layout_a
+            && gemm.b.element == self.element_b
+            && gemm.b.layout == self.layout_b
+            && description.element_accumulator == self.element_accumulator
+    }
+}
+
+/// The operations of the CUTLASS library, initialized once and freed on drop.
+pub struct Manifest {
+    raw: NonNull<RawManifest>,
+}
+
+// SAFETY: the manifest is only read after `cutlass_sys_manifest_create`
+// initializes it, and its operations are immutable.
+unsafe impl Send for Manifest {}
+// SAFETY: as above.
+unsafe impl Sync for Manifest {}
+
+impl Manifest {
+    /// Initializes a manifest of every operation linked into the library.
+    pub fn new() -> Result<Self, Status> {
+        let mut raw = ptr::null_mut();
+        // SAFETY: `raw` is a valid, writable pointer.
+        unsafe { cutlass_sys_manifest_create(&mut raw) }.result()?;
+        NonNull::new(raw)
+            .map(|raw| Self { raw })
+            .ok_or(Status::ErrorInternal)
+    }
+
+    pub fn len(&self) -> usize {
+        // SAFETY: only reads the manifest.
+        unsafe { cutlass_sys_manifest_operation_count(self.raw.as_ptr()) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The operation at `index`, if there is one.
+    pub fn get(&self, index: usize) -> Option<Operation<'_>> {
+        // SAFETY: only reads the manifest; the operation lives as long as it.
+        let raw = unsafe { cutlass_sys_manifest_operation(self.raw.as_ptr(), index) };
+        NonNull::new(raw.cast_mut()).map(|raw| Operation {
+            raw,
+            _manifest: PhantomData,
+        })
+    }
+
+    pub fn operations(&self) -> impl Iterator<Item = Operation<'_>> + '_ {
+        (0..self.len()).filter_map(move |index| self.get(index))
+    }
+
+    /// The universal GEMM operations computing `key` that run on a device of
+    /// compute capability `arch` (`major * 10 + minor`, see
+    /// [`CutlassContext::compute_capability`]), most preferred first:
+    ///
+    /// 1. kernels for the newest architecture, i.e. the highest minimum
+    ///    compute capability, since they use the device's newest instructions;
+    /// 2. then the widest operand alignment, since wider loads are faster;
+    /// 3. then the largest threadblock tile, then the most pipeline stages.
+    ///
+    /// Wider alignments and larger tiles also reject more problems, so walk
+    /// the list and launch the first operation whose
+    /// [`Operation::gemm_supports`] succeeds; [`Manifest::select_gemm`] does
+    /// exactly that.
+    pub fn find_gemm(&self, key: &GemmFunctionalKey, arch: i32) -> Vec<Operation<'_>> {
+        let mut found: Vec<(GemmPreference, Operation<'_>)> = self
+            .operations()
+            .filter_map(|operation| {
+                let description = operation.description();
+                let gemm = operation.gemm_description()?;
+                let runs = gemm.gemm_kind == GemmKind::Universal
+                    && arch >= description.minimum_compute_capability
+                    && arch <= description.maximum_compute_capability
+                    && key.matches(&description, &gemm);
+                runs.then(|| (GemmPreference::new(&description, &gemm), operation))
+            })
+            .collect();
+        found.sort_by(|(a, _), (b, _)| b.cmp(a));
+        found.into_iter().map(|(_, operation)| operation).collect()
+    }
+
+    /// The most preferred operation of [`find_gemm`](Self::find_gemm) for
+    /// `context`'s device that can run `arguments`, or `None` if none can.
+    pub fn select_gemm(
+        &self,
+        context: &CutlassContext,
+        key: &GemmFunctionalKey,
+        arguments: &LibraryGemmArguments,
+    ) -> Option<Operation<'_>> {
+        self.find_gemm(key, context.compute_capability())
+            .into_iter()
+            .find(|operation| operation.gemm_supports(context, arguments).is_ok())
+    }
+
+    pub fn as_ptr(&self) -> *const RawManifest {
+        self.raw.as_ptr()
+    }
+}
+
+/// Sort key of [`Manifest::find_gemm`]; greater is preferred.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct GemmPreference {
+    minimum_compute_capability: i32,
+    alignment: i32,
+    tile_area: i64,
+    stages: i32,
+}
+
+impl GemmPreference {
+    fn new(description: &OperationDescription, gemm: &GemmDescription) -> Self {
+        let tile = description.threadblock_shape;
+        Self {
+            minimum_compute_capability: description.minimum_compute_capability,
+            alignment: gemm.a.alignment.min(gemm.b.alignment),
+            tile_area: tile.m as i64 * tile.n as i64,
+            stages: description.threadblock_stages,
+        }
+    }
+}
+
+impl Drop for Manifest {
+    fn drop(&mut self) {
+        // SAFETY: `raw` came from `cutlass_sys_manifest_create` and is freed
+        // once.
+        unsafe { cutlass_sys_manifest_destroy(self.raw.as_ptr()) }
+    }
+}
+
+/// One operation of a [`Manifest`], or the one a [`LibraryHandle`] last ran.
+#[derive(Clone, Copy, Debug)]
+pub struct Operation<'a> {
+    raw: NonNull<RawOperation>,
+    _manifest: PhantomData<&'a RawManifest>,
+}
+
+// SAFETY: operations are immutable; launching one only reads it.
+unsafe impl Send for Operation<'_> {}
+// SAFETY: as above.
+unsafe impl Sync for Operation<'_> {}
+
+impl<'a> Operation<'a> {
+    pub fn description(&self) -> OperationDescription {
+        let mut description = OperationDescription {
+            name: ptr::null(),
+            kind: OperationKind::Invalid,
+            provider: Provider::Invalid,
+            threadblock_shape: GemmCoord::new(0, 0, 0),
+            threadblock_stages: 0,
+            warp_count: GemmCoord::new(0, 0, 0),
+            cluster_shape: GemmCoord::new(0, 0, 0),
+            instruction_shape: GemmCoord::new(0, 0, 0),
+            element_accumulator: NumericTypeID::Invalid,
+            opcode_class: OpcodeClassID::Invalid,
+            minimum_compute_capability: 0,
+            maximum_compute_capability: 0,
+        };
+        // SAFETY: only reads the operation and writes `description`.
+        unsafe { cutlass_sys_operation_description(self.raw.as_ptr(), &mut description) };
+        description
+    }
+
+    /// The operation's procedural name.
+    pub fn name(&self) -> &'a CStr {
+        // SAFETY: the name is a NUL-terminated string owned by the operation.
+        unsafe { CStr::from_ptr(self.description().name) }
+    }
+
+    /// Operand types of a GEMM operation; `None` for any other kind.
+    pub fn gemm_description(&self) -> Option<GemmDescription> {
+        let mut description = GemmDescription::default();
+        // SAFETY: only reads the operation and writes `description`.
+        unsafe { cutlass_sys_operation_gemm_description(self.raw.as_ptr(), &mut description) }
+            .result()
+            .ok()
+            .map(|()| description)
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_operation_gemm`] needs to run
+    /// `arguments` on this operation.
+    pub fn gemm_workspace_size(
+        &self,
+        context: &CutlassContext,
+        arguments: &LibraryGemmArguments,
+    ) -> usize {
+        // SAFETY: only reads `context`, the operation and `arguments`.
+        unsafe { cutlass_sys_operation_gemm_workspace_size(context, self.raw.as_ptr(), arguments) }
+    }
+
+    /// Checks that [`cutlass_sys_operation_gemm`] can run `arguments` on this
+    /// operation before any operand is allocated, returning the status the
+    /// launch would fail with.
+    pub fn gemm_supports(
+        &self,
+        context: &CutlassContext,
+        arguments: &LibraryGemmArguments,
+    ) -> Result<(), Status> {
+        // SAFETY: only reads `context`, the operation and `arguments`.
+        unsafe { cutlass_sys_operation_gemm_can_implement(context, self.raw.as_ptr(), arguments) }
+            .result()
+    }
+
+    pub fn as_ptr(&self) -> *const RawOperation {
+        self.raw.as_ptr()
+    }
+}
+
+/// A `cutlass::library::Handle` bound to a device, with the stream and
+/// device workspace its calls use. Freed on drop.
+pub struct LibraryHandle {
+    raw: NonNull<RawLibraryHandle>,
+}
+
+// SAFETY: the handle makes its device current around every call that uses
+// it, so it may move between threads; `&mut self` serializes those calls.
+unsafe impl Send for LibraryHandle {}
+
+impl LibraryHandle {
+    /// Device workspace CUTLASS allocates for a handle by default.
+    pub const DEFAULT_WORKSPACE_SIZE: usize = 4 << 20;
+
+    /// Creates a handle on `context`'s device that launches on `stream`,
+    /// allocating `workspace_size` bytes of device workspace.
+    ///
+    /// # Safety
+    ///
+    /// `stream` must be null or a live stream of `context`'s device that
+    /// outlives the handle (or its next [`set_stream`](Self::set_stream)).
+    pub unsafe fn new(
+        context: &CutlassContext,
+        stream: cudaStream_t,
+        workspace_size: usize,
+    ) -> Result<Self, Status> {
+        let mut raw = ptr::null_mut();
+        // SAFETY: only reads `context` and writes `raw`; the caller vouches
+        // for `stream`.
+        unsafe { cutlass_sys_library_handle_create(context, stream, workspace_size, &mut raw) }
+            .result()?;
+        NonNull::new(raw)
+            .map(|raw| Self { raw })
+            .ok_or(Status::ErrorInternal)
+    }
+
+    /// # Safety
+    ///
+    /// As for [`new`](Self::new), `stream` must be null or a live stream of
+    /// the handle's device that outlives its use by the handle.
+    pub unsafe fn set_stream(&mut self, stream: cudaStream_t) {
+        // SAFETY: the handle is valid and exclusively borrowed; the caller
+        // vouches for `stream`.
+        unsafe { cutlass_sys_library_handle_set_stream(self.raw.as_ptr(), stream) }
+    }
+
+    pub fn workspace_size(&self) -> usize {
+        // SAFETY: only reads the handle.
+        unsafe { cutlass_sys_library_handle_workspace_size(self.raw.as_ptr()) }
+    }
+
+    /// Reallocates the device workspace to `workspace_size` bytes.
+    pub fn set_workspace_size(&mut self, workspace_size: usize) -> Result<(), Status> {
+        // SAFETY: the handle is valid and exclusively borrowed.
+        unsafe { cutlass_sys_library_handle_set_workspace_size(self.raw.as_ptr(), workspace_size) }
+            .result()
+    }
+
+    /// The operation the last successful GEMM ran on.
+    pub fn last_operation(&self) -> Option<Operation<'static>> {
+        // SAFETY: only reads the handle. Its operations belong to CUTLASS's
+        // process-wide manifest, which is never freed.
+        let raw = unsafe { cutlass_sys_library_handle_last_operation(self.raw.as_ptr()) };
+        NonNull::new(raw.cast_mut()).map(|raw| Operation {
+            raw,
+            _manifest: PhantomData,
+        })
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut RawLibraryHandle {
+        self.raw.as_ptr()
+    }
+}
+
+impl Drop for LibraryHandle {
+    fn drop(&mut self) {
+        // SAFETY: `raw` came from `cutlass_sys_library_handle_create` and is
+        // freed once.
+        unsafe { cutlass_sys_library_handle_destroy(self.raw.as_ptr()) }
+    }
+}
+
+extern "C" {
+    /// Initialize a manifest of every operation in the library.
+    pub fn cutlass_sys_manifest_create(manifest: *mut *mut RawManifest) -> Status;
+
+    /// Free a manifest and its operations.
+    pub fn cutlass_sys_manifest_destroy(manifest: *mut RawManifest);
+
+    pub fn cutlass_sys_manifest_operation_count(manifest: *const RawManifest) -> usize;
+
+    /// The operation at `index`, or null past the end. Valid until the
+    /// manifest is destroyed.
+    pub fn cutlass_sys_manifest_operation(
+        manifest: *const RawManifest,
+        index: usize,
+    ) -> *const RawOperation;
+
+    pub fn cutlass_sys_operation_description(
+        operation: *const RawOperation,
+        description: *mut OperationDescription,
+    );
+
+    /// Fill `description`, or [`Status::ErrorNotSupported`] unless
+    /// `operation` is a GEMM.
+    pub fn cutlass_sys_operation_gemm_description(
+        operation: *const RawOperation,
+        description: *mut GemmDescription,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_operation_gemm`] needs.
+    pub fn cutlass_sys_operation_gemm_workspace_size(
+        context: *const CutlassContext,
+        operation: *const RawOperation,
+        arguments: *const LibraryGemmArguments,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_operation_gemm`] can run `arguments`, checked
+    /// without touching any operand.
+    pub fn cutlass_sys_operation_gemm_can_implement(
+        context: *const CutlassContext,
+        operation: *const RawOperation,
+        arguments: *const LibraryGemmArguments,
+    ) -> Status;
+
+    /// Launch a universal GEMM operation on `stream`. Other GEMM kinds
+    /// return [`Status::ErrorNotSupported`], and a device outside the
+    /// operation's compute capability range [`Status::ErrorArchMismatch`].
+    pub fn cutlass_sys_operation_gemm(
+        context: *const CutlassContext,
+        operation: *const RawOperation,
+        arguments: *const LibraryGemmArguments,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Create a handle on `context`'s device, allocating its workspace.
+    pub fn cutlass_sys_library_handle_create(
+        context: *const CutlassContext,
+        stream: cudaStream_t,
+        workspace_size: usize,
+        handle: *mut *mut RawLibraryHandle,
+    ) -> Status;
+
+    /// Free a handle and its workspace.
+    pub fn cutlass_sys_library_handle_destroy(handle: *mut RawLibraryHandle);
+
+    pub fn cutlass_sys_library_handle_set_stream(
+        handle: *mut RawLibraryHandle,
+        stream: cudaStream_t,
+    );
+
+    pub fn cutlass_sys_library_handle_workspace_size(handle: *const RawLibraryHandle) -> usize;
+
+    pub fn cutlass_sys_library_handle_set_workspace_size(
+        handle: *mut RawLibraryHandle,
+        workspace_size: usize,
+    ) -> Status;
+
+    /// Run a universal GEMM on the handle's stream, with the operation of
+    /// CUTLASS's process-wide manifest it picks for the device, operand
+    /// types and alignment: operand elements and layouts, transforms and the
+    /// scalar type (`element_epilogue`) come from `types`, accumulating in
+    /// `element_compute`. Returns [`Status::ErrorNotSupported`] when no
+    /// operation matches.
+    pub fn cutlass_sys_library_handle_gemm(
+        handle: *mut RawLibraryHandle,
+        element_compute: NumericTypeID,
+        types: *const GemmDescription,
+        arguments: *const LibraryGemmArguments,
+    ) -> Status;
+
+    /// The operation the handle's last successful call ran, or null.
+    pub fn cutlass_sys_library_handle_last_operation(
+        handle: *const RawLibraryHandle,
+    ) -> *const RawOperation;
+}
@@ -0,0 +1,350 @@
+//! Loading and launching device code through the CUDA driver API (`driver`
+//! feature), for kernels compiled outside the shim: by `jit`, by the
+//! `prebuilt-kernels` release build, or by a consumer's own build script and
+//! bundled with `fatbin`.
+//!
+//! A [`Module`] loads a cubin, fatbin or PTX image into the primary context
+//! of a device, the context the CUDA runtime and the shim use, so its
+//! kernels share streams and allocations with everything else. Only
+//! `libcuda` is needed, not `nvcc` or the shim.
+
+use core::ffi::{c_char, c_int, c_uint, c_void};
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use std::ffi::CString;
+use std::fmt;
+
+#[cfg(any(feature = "jit", feature = "prebuilt-kernels"))]
+use crate::coord::GemmCoord;
+use crate::types::{cudaStream_t, Status};
+
+/// Error returned by [`Module`] and [`Function`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModuleError {
+    /// A CUDA driver API call failed with this `CUresult`.
+    Driver(CUresult),
+    /// The request was rejected before reaching CUDA.
+    Status(Status),
+}
+
+impl fmt::Display for ModuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModuleError::Driver(code) => write!(f, "CUDA driver error {}", code),
+            ModuleError::Status(status) => write!(f, "{}", status),
+        }
+    }
+}
+
+impl std::error::Error for ModuleError {}
+
+impl From<Status> for ModuleError {
+    fn from(status: Status) -> Self {
+        ModuleError::Status(status)
+    }
+}
+
+fn driver(result: CUresult) -> Result<(), ModuleError> {
+    match result {
+        CUDA_SUCCESS => Ok(()),
+        code => Err(ModuleError::Driver(code)),
+    }
+}
+
+/// Attribute `attribute` (a `CU_DEVICE_ATTRIBUTE_*` value) of device
+/// ordinal `device`.
+pub fn device_attribute(device: i32, attribute: c_int) -> Result<i32, ModuleError> {
+    let mut handle = 0;
+    let mut value = 0;
+    // SAFETY: only writes `handle` and `value`.
+    unsafe {
+        driver(cuInit(0))?;
+        driver(cuDeviceGet(&mut handle, device))?;
+        driver(cuDeviceGetAttribute(&mut value, attribute, handle))?;
+    }
+    Ok(value)
+}
+
+/// The primary context of a device, retained for as long as this lives and
+/// made current around each driver call.
+struct PrimaryContext {
+    device: CUdevice,
+    raw: CUcontext,
+}
+
+impl PrimaryContext {
+    fn retain(ordinal: i32) -> Result<Self, ModuleError> {
+        let mut device = 0;
+        let mut raw = ptr::null_mut();
+        // SAFETY: only writes `device` and `raw`.
+        unsafe {
+            driver(cuInit(0))?;
+            driver(cuDeviceGet(&mut device, ordinal))?;
+            driver(cuDevicePrimaryCtxRetain(&mut raw, device))?;
+        }
+        Ok(Self { device, raw })
+    }
+
+    /// Runs `f` with this context current on the calling thread.
+    fn with<T>(&self, f: impl FnOnce() -> Result<T, ModuleError>) -> Result<T, ModuleError> {
+        // SAFETY: the context is retained.
+        driver(unsafe { cuCtxPushCurrent_v2(self.raw) })?;
+        let result = f();
+        let mut popped = ptr::null_mut();
+        // SAFETY: pops the context pushed above.
+        unsafe { cuCtxPopCurrent_v2(&mut popped) };
+        result
+    }
+}
+
+impl Drop for PrimaryContext {
+    fn drop(&mut self) {
+        // SAFETY: releases the reference taken by `retain`.
+        unsafe { cuDevicePrimaryCtxRelease_v2(self.device) };
+    }
+}
+
+/// Device code loaded on one device. Unloaded on drop.
+pub struct Module {
+    context: PrimaryContext,
+    raw: CUmodule,
+}
+
+// SAFETY: the module is only used with its context pushed, which the driver
+// allows from any thread.
+unsafe impl Send for Module {}
+// SAFETY: loaded modules are immutable; launches only read them.
+unsafe impl Sync for Module {}
+
+impl Module {
+    /// Loads `image` into the primary context of device ordinal `device`.
+    /// `image` is a cubin, a fatbin (the driver picks the SASS for the
+    /// device or JIT-compiles its PTX) or NUL-terminated PTX.
+    pub fn load(device: i32, image: &[u8]) -> Result<Self, ModuleError> {
+        let primary = PrimaryContext::retain(device)?;
+        let mut raw = ptr::null_mut();
+        // SAFETY: `image` is a complete image read during the call.
+        primary.with(|| driver(unsafe { cuModuleLoadData(&mut raw, image.as_ptr().cast()) }))?;
+        Ok(Self {
+            context: primary,
+            raw,
+        })
+    }
+
+    /// The `extern "C"` kernel `name`.
+    pub fn function(&self, name: &str) -> Result<Function<'_>, ModuleError> {
+        let name = CString::new(name).map_err(|_| Status::ErrorInvalidProblem)?;
+        let mut raw = ptr::null_mut();
+        // SAFETY: the module is loaded and `name` is NUL-terminated.
+        self.context
+            .with(|| driver(unsafe { cuModuleGetFunction(&mut raw, self.raw, name.as_ptr()) }))?;
+        Ok(Function { module: self, raw })
+    }
+
+    /// Reads the `extern "C"` `__device__` variable `name`, which must be a
+    /// `T`.
+    pub fn read_global<T: Copy>(&self, name: &str) -> Result<T, ModuleError> {
+        let name = CString::new(name).map_err(|_| Status::ErrorInvalidProblem)?;
+        self.context.with(|| {
+            let mut address = 0;
+            let mut size = 0;
+            // SAFETY: the module is loaded and `name` is NUL-terminated.
+            driver(unsafe {
+                cuModuleGetGlobal_v2(&mut address, &mut size, self.raw, name.as_ptr())
+            })?;
+            if size != core::mem::size_of::<T>() {
+                return Err(Status::ErrorInvalidDataType.into());
+            }
+            let mut value = MaybeUninit::<T>::uninit();
+            // SAFETY: `value` has room for the `size` bytes copied into it,
+            // which the caller vouches form a `T`.
+            unsafe {
+                driver(cuMemcpyDtoH_v2(value.as_mut_ptr().cast(), address, size))?;
+                Ok(value.assume_init())
+            }
+        })
+    }
+}
+
+impl Drop for Module {
+    fn drop(&mut self) {
+        let raw = self.raw;
+        // SAFETY: the module was loaded by `load` and is unloaded once.
+        let _ = self.context.with(|| driver(unsafe { cuModuleUnload(raw) }));
+    }
+}
+
+/// A kernel of a [`Module`].
+#[derive(Clone, Copy)]
+pub struct Function<'a> {
+    module: &'a Module,
+    raw: CUfunction,
+}
+
+impl Function<'_> {
+    /// Allows launches with up to `bytes` of dynamic shared memory, needed
+    /// above 48 KiB.
+    pub fn set_max_dynamic_shared_memory(&self, bytes: u32) -> Result<(), ModuleError> {
+        self.module.context.with(|| {
+            // SAFETY: the function belongs to a loaded module.
+            driver(unsafe {
+                cuFuncSetAttribute(
+                    self.raw,
+                    CU_FUNC_ATTRIBUTE_MAX_DYNAMIC_SHARED_SIZE_BYTES,
+                    bytes as c_int,
+                )
+            })
+        })
+    }
+
+    /// Launches the kernel on `stream`.
+    ///
+    /// # Safety
+    ///
+    /// `params` must point to one value of each kernel parameter's type, in
+    /// order, and the kernel must be safe to run with them (valid device
+    /// pointers and extents). `stream` must be null or a live stream of the
+    /// module's device.
+    pub unsafe fn launch(
+        &self,
+        grid: [u32; 3],
+        block: [u32; 3],
+        shared_memory: u32,
+        stream: cudaStream_t,
+        params: &mut [*mut c_void],
+    ) -> Result<(), ModuleError> {
+        self.module.context.with(|| {
+            // SAFETY: the caller vouches for `params` and `stream`.
+            driver(unsafe {
+                cuLaunchKernel(
+                    self.raw,
+                    grid[0],
+                    grid[1],
+                    grid[2],
+                    block[0],
+                    block[1],
+                    block[2],
+                    shared_memory,
+                    stream.cast(),
+                    params.as_mut_ptr(),
+                    ptr::null_mut(),
+                )
+            })
+        })
+    }
+}
+
+/// Parameters of the row-major `alpha * A * B + beta * C` GEMM kernels the
+/// `jit` and `prebuilt-kernels` features generate, in kernel parameter
+/// order.
+#[cfg(any(feature = "jit", feature = "prebuilt-kernels"))]
+pub(crate) struct GemmKernelArgs {
+    pub problem: GemmCoord,
+    pub a: *const c_void,
+    pub lda: i64,
+    pub b: *const c_void,
+    pub ldb: i64,
+    pub c: *const c_void,
+    pub ldc: i64,
+    pub d: *mut c_void,
+    pub ldd: i64,
+    pub alpha: f32,
+    pub beta: f32,
+}
+
+#[cfg(any(feature = "jit", feature = "prebuilt-kernels"))]
+impl GemmKernelArgs {
+    /// Launches `function`, compiled with threadblock tile `threadblock`,
+    /// over a grid of one CTA per output tile.
+    ///
+    /// # Safety
+    ///
+    /// `function` must be one of the GEMM kernels, and the operands valid
+    /// device memory of its device as for [`Function::launch`].
+    pub unsafe fn launch(
+        mut self,
+        function: Function<'_>,
+        threadblock: GemmCoord,
+        threads: u32,
+        shared_memory: u32,
+        stream: cudaStream_t,
+    ) -> Result<(), ModuleError> {
+        let grid = [
+            (self.problem.m as u32).div_ceil(threadblock.m as u32),
+            (self.problem.n as u32).div_ceil(threadblock.n as u32),
+            1,
+        ];
+        let mut params: [*mut c_void; 13] = [
+            ptr::addr_of_mut!(self.problem.m).cast(),
+            ptr::addr_of_mut!(self.problem.n).cast(),
+            ptr::addr_of_mut!(self.problem.k).cast(),
+            ptr::addr_of_mut!(self.a).cast(),
+            ptr::addr_of_mut!(self.lda).cast(),
+            ptr::addr_of_mut!(self.b).cast(),
+            ptr::addr_of_mut!(self.ldb).cast(),
+            ptr::addr_of_mut!(self.c).cast(),
+            ptr::addr_of_mut!(self.ldc).cast(),
+            ptr::addr_of_mut!(self.d).cast(),
+            ptr::addr_of_mut!(self.ldd).cast(),
+            ptr::addr_of_mut!(self.alpha).cast(),
+            ptr::addr_of_mut!(self.beta).cast(),
+        ];
+        // SAFETY: `params` matches the kernel's signature; the caller
+        // vouches for the operands and `stream`.
+        unsafe { function.launch(grid, [threads, 1, 1], shared_memory, stream, &mut params) }
+    }
+}
+
+pub type CUresult = c_int;
+pub type CUdevice = c_int;
+pub type CUcontext = *mut c_void;
+pub type CUmodule = *mut c_void;
+pub type CUfunction = *mut c_void;
+pub type CUstream = *mut c_void;
+pub type CUdeviceptr = u64;
+
+pub const CUDA_SUCCESS: CUresult = 0;
+pub const CU_FUNC_ATTRIBUTE_MAX_DYNAMIC_SHARED_SIZE_BYTES: c_int = 8;
+pub const CU_DEVICE_ATTRIBUTE_MULTIPROCESSOR_COUNT: c_int = 16;
+pub const CU_DEVICE_ATTRIBUTE_COMPUTE_CAPABILITY_MAJOR: c_int = 75;
+pub const CU_DEVICE_ATTRIBUTE_COMPUTE_CAPABILITY_MINOR: c_int = 76;
+
+extern "C" {
+    pub fn cuInit(flags: c_uint) -> CUresult;
+    pub fn cuDeviceGet(device: *mut CUdevice, ordinal: c_int) -> CUresult;
+    pub fn cuDeviceGetAttribute(value: *mut c_int, attribute: c_int, device: CUdevice) -> CUresult;
+    pub fn cuDevicePrimaryCtxRetain(context: *mut CUcontext, device: CUdevice) -> CUresult;
+    pub fn cuDevicePrimaryCtxRelease_v2(device: CUdevice) -> CUresult;
+    pub fn cuCtxPushCurrent_v2(context: CUcontext) -> CUresult;
+    pub fn cuCtxPopCurrent_v2(context: *mut CUcontext) -> CUresult;
+    pub fn cuModuleLoadData(module: *mut CUmodule, image: *const c_void) -> CUresult;
+    pub fn cuModuleUnload(module: CUmodule) -> CUresult;
+    pub fn cuModuleGetFunction(
+        function: *mut CUfunction,
+        module: CUmodule,
+        name: *const c_char,
+    ) -> CUresult;
+    pub fn cuModuleGetGlobal_v2(
+        address: *mut CUdeviceptr,
+        size: *mut usize,
+        module: CUmodule,
+        name: *const c_char,
+    ) -> CUresult;
+    pub fn cuMemcpyDtoH_v2(host: *mut c_void, device: CUdeviceptr, size: usize) -> CUresult;
+    pub fn cuFuncSetAttribute(function: CUfunction, attribute: c_int, value: c_int) -> CUresult;
+    #[allow(clippy::too_many_arguments)]
+    pub fn cuLaunchKernel(
+        function: CUfunction,
+        grid_x: c_uint,
+        grid_y: c_uint,
+        grid_z: c_uint,
+        block_x: c_uint,
+        block_y: c_uint,
+        block_z: c_uint,
+        shared_memory: c_uint,
+        stream: CUstream,
+        params: *mut *mut c_void,
+        extra: *mut *mut c_void,
+    ) -> CUresult;
+}
@@ -0,0 +1,147 @@
+//! Planar-complex GEMM:
+//!
+//! ```text
+//! D = alpha * A * B + beta * C
+//! ```
+//!
+//! over complex matrices whose real and imaginary parts are stored in
+//! separate planes, each a row-major matrix with the same leading dimension.
+//! `alpha` and `beta` are complex. The element type is `F16` (accumulating in
+//! `f32`) or `F32`; anything else returns
+//! [`Status::ErrorInvalidDataType`].
+//!
+//! With `batch_count > 1` the launch computes that many independent GEMMs,
+//! matrix `i` of each plane starting `i * batch_stride_*` elements after the
+//! plane pointer.
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::coord::GemmCoord;
+use crate::error::CutlassError;
+use crate::types::{cudaStream_t, Complex, DataType, Status};
+
+/// Problem description of a (batched) planar-complex GEMM.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlanarComplexGemmConfig {
+    pub problem: GemmCoord,
+    pub element: DataType,
+    pub batch_count: i32,
+    pub lda: i64,
+    pub ldb: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub batch_stride_a: i64,
+    pub batch_stride_b: i64,
+    pub batch_stride_c: i64,
+    pub batch_stride_d: i64,
+    pub alpha: Complex<f32>,
+    pub beta: Complex<f32>,
+}
+
+impl PlanarComplexGemmConfig {
+    /// A single tightly packed `m x n x k` problem with `alpha = 1`,
+    /// `beta = 0`.
+    pub const fn new(m: i32, n: i32, k: i32, element: DataType) -> Self {
+        Self::batched(m, n, k, element, 1)
+    }
+
+    /// `batch_count` tightly packed `m x n x k` problems stored back to back
+    /// in each plane, with `alpha = 1`, `beta = 0`.
+    pub const fn batched(m: i32, n: i32, k: i32, element: DataType, batch_count: i32) -> Self {
+        Self {
+            problem: GemmCoord::new(m, n, k),
+            element,
+            batch_count,
+            lda: k as i64,
+            ldb: n as i64,
+            ldc: n as i64,
+            ldd: n as i64,
+            batch_stride_a: m as i64 * k as i64,
+            batch_stride_b: k as i64 * n as i64,
+            batch_stride_c: m as i64 * n as i64,
+            batch_stride_d: m as i64 * n as i64,
+            alpha: Complex::new(1.0, 0.0),
+            beta: Complex::new(0.0, 0.0),
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_planar_complex_gemm`] needs for this
+    /// config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_planar_complex_gemm_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_planar_complex_gemm`] can run this config
+    /// before any operand is allocated, returning the status the launch would
+    /// fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_planar_complex_gemm_can_implement(context, self) }.result()
+    }
+
+    /// Launches the GEMM on `stream` through [`cutlass_sys_planar_complex_gemm`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory, or
+    /// may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &PlanarComplexOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_planar_complex_gemm(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers to the real and imaginary planes of each operand.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct PlanarComplexOperands {
+    pub a_real: *const c_void,
+    pub a_imag: *const c_void,
+    pub b_real: *const c_void,
+    pub b_imag: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c_real: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c_imag: *const c_void,
+    pub d_real: *mut c_void,
+    pub d_imag: *mut c_void,
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_planar_complex_gemm`] needs.
+    pub fn cutlass_sys_planar_complex_gemm_workspace_size(
+        context: *const CutlassContext,
+        config: *const PlanarComplexGemmConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_planar_complex_gemm`] can run `config`, checked
+    /// without touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_planar_complex_gemm_can_implement(
+        context: *const CutlassContext,
+        config: *const PlanarComplexGemmConfig,
+    ) -> Status;
+
+    /// Launch a (batched) planar-complex GEMM on `stream`.
+    pub fn cutlass_sys_planar_complex_gemm(
+        context: *const CutlassContext,
+        config: *const PlanarComplexGemmConfig,
+        operands: *const PlanarComplexOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
@@ -0,0 +1,306 @@
+//! A curated pack of common GEMM kernels, compiled ahead of time and
+//! embedded in the crate (`prebuilt-kernels` feature).
+//!
+//! The pack is a fatbin of the kernels in `src/prebuilt/gemm.cu`, with SASS
+//! for SM80, SM86, SM89 and SM90 and SM90 PTX for newer devices. Published
+//! crates ship it prebuilt, so this feature needs neither `nvcc` nor the
+//! shim: [`PrebuiltGemms::load`] hands the embedded image to the driver with
+//! `cuModuleLoadData` and only `libcuda` is linked. Without a shipped pack
+//! (e.g. in a git checkout) the build script compiles it with `nvcc`.
+//!
+//! Every kernel computes `D = alpha * A * B + beta * C` with row-major `A`,
+//! `B`, `C` and `D` of one element type and `f32` accumulation, the same
+//! problem as a plain [`GemmConfig`](crate::gemm::GemmConfig) of the shim.
+
+use core::ffi::c_void;
+
+use std::vec::Vec;
+
+use crate::coord::GemmCoord;
+use crate::module::{
+    device_attribute, GemmKernelArgs, Module, ModuleError,
+    CU_DEVICE_ATTRIBUTE_COMPUTE_CAPABILITY_MAJOR, CU_DEVICE_ATTRIBUTE_COMPUTE_CAPABILITY_MINOR,
+    CU_DEVICE_ATTRIBUTE_MULTIPROCESSOR_COUNT,
+};
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Alignment the driver expects of a fatbin image.
+#[repr(C, align(8))]
+struct Aligned<T: ?Sized>(T);
+
+static GEMM_FATBIN: &Aligned<[u8]> = &Aligned(*include_bytes!(concat!(
+    env!("OUT_DIR"),
+    "/prebuilt_gemm.fatbin"
+)));
+
+/// One kernel of the pack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PrebuiltGemmKernel {
+    /// The kernel's `extern "C"` name.
+    pub name: &'static str,
+    /// `F16`, `BF16` or `F32` (3xTF32).
+    pub element: DataType,
+    pub threadblock: GemmCoord,
+    pub warp: GemmCoord,
+    pub stages: i32,
+}
+
+impl PrebuiltGemmKernel {
+    const fn new(
+        name: &'static str,
+        element: DataType,
+        threadblock: GemmCoord,
+        warp: GemmCoord,
+        stages: i32,
+    ) -> Self {
+        Self {
+            name,
+            element,
+            threadblock,
+            warp,
+            stages,
+        }
+    }
+
+    /// Operand alignment in elements: 128-bit accesses.
+    pub const fn alignment(&self) -> i64 {
+        match self.element {
+            DataType::F32 => 4,
+            _ => 8,
+        }
+    }
+}
+
+/// Every kernel of the pack, largest tiles first for each element type.
+pub const PREBUILT_GEMM_KERNELS: &[PrebuiltGemmKernel] = &[
+    PrebuiltGemmKernel::new(
+        "cutlass_sys_prebuilt_gemm_f16_128x256x32",
+        DataType::F16,
+        GemmCoord::new(128, 256, 32),
+        GemmCoord::new(64, 64, 32),
+        3,
+    ),
+    PrebuiltGemmKernel::new(
+        "cutlass_sys_prebuilt_gemm_f16_128x128x32",
+        DataType::F16,
+        GemmCoord::new(128, 128, 32),
+        GemmCoord::new(64, 64, 32),
+        3,
+    ),
+    PrebuiltGemmKernel::new(
+        "cutlass_sys_prebuilt_gemm_f16_64x64x32",
+        DataType::F16,
+        GemmCoord::new(64, 64, 32),
+        GemmCoord::new(32, 32, 32),
+        4,
+    ),
+    PrebuiltGemmKernel::new(
+        "cutlass_sys_prebuilt_gemm_bf16_128x256x32",
+        DataType::BF16,
+        GemmCoord::new(128, 256, 32),
+        GemmCoord::new(64, 64, 32),
+        3,
+    ),
+    PrebuiltGemmKernel::new(
+        "cutlass_sys_prebuilt_gemm_bf16_128x128x32",
+        DataType::BF16,
+        GemmCoord::new(128, 128, 32),
+        GemmCoord::new(64, 64, 32),
+        3,
+    ),
+    PrebuiltGemmKernel::new(
+        "cutlass_sys_prebuilt_gemm_bf16_64x64x32",
+        DataType::BF16,
+        GemmCoord::new(64, 64, 32),
+        GemmCoord::new(32, 32, 32),
+        4,
+    ),
+    PrebuiltGemmKernel::new(
+        "cutlass_sys_prebuilt_gemm_f32_128x256x16",
+        DataType::F32,
+        GemmCoord::new(128, 256, 16),
+        GemmCoord::new(64, 64, 16),
+        3,
+    ),
+    PrebuiltGemmKernel::new(
+        "cutlass_sys_prebuilt_gemm_f32_128x128x16",
+        DataType::F32,
+        GemmCoord::new(128, 128, 16),
+        GemmCoord::new(64, 64, 16),
+        4,
+    ),
+    PrebuiltGemmKernel::new(
+        "cutlass_sys_prebuilt_gemm_f32_64x64x16",
+        DataType::F32,
+        GemmCoord::new(64, 64, 16),
+        GemmCoord::new(32, 32, 16),
+        4,
+    ),
+];
+
+/// Problem description of a prebuilt GEMM.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PrebuiltGemmConfig {
+    pub problem: GemmCoord,
+    pub element: DataType,
+    pub lda: i64,
+    pub ldb: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub alpha: f32,
+    pub beta: f32,
+}
+
+impl PrebuiltGemmConfig {
+    /// A tightly packed `m x n x k` problem with `alpha = 1`, `beta = 0`.
+    pub const fn new(m: i32, n: i32, k: i32, element: DataType) -> Self {
+        Self {
+            problem: GemmCoord::new(m, n, k),
+            element,
+            lda: k as i64,
+            ldb: n as i64,
+            ldc: n as i64,
+            ldd: n as i64,
+            alpha: 1.0,
+            beta: 0.0,
+        }
+    }
+}
+
+/// Device pointers of a prebuilt GEMM launch.
+#[derive(Clone, Copy, Debug)]
+pub struct PrebuiltGemmOperands {
+    pub a: *const c_void,
+    pub b: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+}
+
+/// Launch parameters of a loaded kernel, read from the module.
+struct Loaded {
+    kernel: &'static PrebuiltGemmKernel,
+    threads: u32,
+    shared_memory: u32,
+}
+
+/// The pack, loaded on one device.
+pub struct PrebuiltGemms {
+    module: Module,
+    sm_count: i32,
+    kernels: Vec<Loaded>,
+}
+
+impl PrebuiltGemms {
+    /// Loads the pack on device ordinal `device`, which must be SM80 or
+    /// newer.
+    pub fn load(device: i32) -> Result<Self, ModuleError> {
+        let major = device_attribute(device, CU_DEVICE_ATTRIBUTE_COMPUTE_CAPABILITY_MAJOR)?;
+        let minor = device_attribute(device, CU_DEVICE_ATTRIBUTE_COMPUTE_CAPABILITY_MINOR)?;
+        if major * 10 + minor < 80 {
+            return Err(Status::ErrorArchMismatch.into());
+        }
+        let sm_count = device_attribute(device, CU_DEVICE_ATTRIBUTE_MULTIPROCESSOR_COUNT)?;
+        let module = Module::load(device, &GEMM_FATBIN.0)?;
+        let kernels = PREBUILT_GEMM_KERNELS
+            .iter()
+            .map(|kernel| {
+                let threads =
+                    module.read_global::<i32>(&std::format!("{}_threads", kernel.name))?;
+                let shared_memory =
+                    module.read_global::<i32>(&std::format!("{}_shared_memory", kernel.name))?;
+                module
+                    .function(kernel.name)?
+                    .set_max_dynamic_shared_memory(shared_memory as u32)?;
+                Ok(Loaded {
+                    kernel,
+                    threads: threads as u32,
+                    shared_memory: shared_memory as u32,
+                })
+            })
+            .collect::<Result<_, ModuleError>>()?;
+        Ok(Self {
+            module,
+            sm_count,
+            kernels,
+        })
+    }
+
+    /// The kernel [`launch`](Self::launch) runs `config` with: the largest
+    /// tile that still gives every SM an output tile, else the smallest.
+    /// Fails for element types outside the pack and leading dimensions not
+    /// aligned to 128 bits.
+    pub fn select(
+        &self,
+        config: &PrebuiltGemmConfig,
+    ) -> Result<&'static PrebuiltGemmKernel, Status> {
+        self.select_loaded(config).map(|loaded| loaded.kernel)
+    }
+
+    fn select_loaded(&self, config: &PrebuiltGemmConfig) -> Result<&Loaded, Status> {
+        let candidates: Vec<&Loaded> = self
+            .kernels
+            .iter()
+            .filter(|loaded| loaded.kernel.element == config.element)
+            .collect();
+        let smallest = candidates.last().ok_or(Status::ErrorInvalidDataType)?;
+        let align = smallest.kernel.alignment();
+        if [config.lda, config.ldb, config.ldc, config.ldd]
+            .iter()
+            .any(|ld| ld % align != 0)
+        {
+            return Err(Status::ErrorMisalignedOperand);
+        }
+        let problem = config.problem;
+        Ok(candidates
+            .iter()
+            .find(|loaded| {
+                let tile = loaded.kernel.threadblock;
+                let tiles = (problem.m as u64).div_ceil(tile.m as u64)
+                    * (problem.n as u64).div_ceil(tile.n as u64);
+                tiles >= self.sm_count as u64
+            })
+            .unwrap_or(smallest))
+    }
+
+    /// Launches `config` on `stream` with the kernel [`select`](Self::select)
+    /// picks.
+    ///
+    /// # Safety
+    ///
+    /// The pointers of `operands` must be device memory of the pack's device
+    /// holding the extents `config` describes, aligned to 16 bytes, and
+    /// `stream` null or a live stream of that device.
+    pub unsafe fn launch(
+        &self,
+        config: &PrebuiltGemmConfig,
+        operands: &PrebuiltGemmOperands,
+        stream: cudaStream_t,
+    ) -> Result<(), ModuleError> {
+        let loaded = self.select_loaded(config)?;
+        let args = GemmKernelArgs {
+            problem: config.problem,
+            a: operands.a,
+            lda: config.lda,
+            b: operands.b,
+            ldb: config.ldb,
+            c: operands.c,
+            ldc: config.ldc,
+            d: operands.d,
+            ldd: config.ldd,
+            alpha: config.alpha,
+            beta: config.beta,
+        };
+        // SAFETY: the function is a GEMM kernel of the pack; the caller
+        // vouches for the operands and `stream`.
+        unsafe {
+            args.launch(
+                self.module.function(loaded.kernel.name)?,
+                loaded.kernel.threadblock,
+                loaded.threads,
+                loaded.shared_memory,
+                stream,
+            )
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// Curated GEMM kernels embedded by the `prebuilt-kernels` feature (src/prebuilt.rs).
+//
+// Not part of the shim: build.rs compiles this file with `nvcc -fatbin` only when no prebuilt
+// gemm.fatbin ships next to it, and src/prebuilt.rs loads the result with cuModuleLoadData. The
+// kernels are launched through the driver API, so each builds its CUTLASS `Params` on the device
+// from plain arguments and exports the block size and shared memory it needs. Every kernel
+// declared here must be listed in PREBUILT_GEMM_KERNELS.
+
+#include <cutlass/epilogue/thread/linear_combination.h>
+#include <cutlass/gemm/kernel/default_gemm.h>
+#include <cutlass/gemm/threadblock/threadblock_swizzle.h>
+
+namespace {
+
+// Same math as the shim's Sm80TensorOpTraits: f16 / bf16 MMAs, 3xTF32 for f32.
+template <typename Element>
+struct Math {
+  using Operator = cutlass::arch::OpMultiplyAdd;
+  using InstructionShape = cutlass::gemm::GemmShape<16, 8, 16>;
+  static constexpr int kAlignment = 8;
+};
+
+template <>
+struct Math<float> {
+  using Operator = cutlass::arch::OpMultiplyAddFastF32;
+  using InstructionShape = cutlass::gemm::GemmShape<16, 8, 8>;
+  static constexpr int kAlignment = 4;
+};
+
+using Swizzle = cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<1>;
+
+template <typename Element, int kTileM, int kTileN, int kTileK, int kWarpM, int kWarpN,
+          int kStages>
+using Kernel = typename cutlass::gemm::kernel::DefaultGemm<
+    Element, cutlass::layout::RowMajor, Math<Element>::kAlignment,
+    Element, cutlass::layout::RowMajor, Math<Element>::kAlignment,
+    Element, cutlass::layout::RowMajor, float,
+    cutlass::arch::OpClassTensorOp, cutlass::arch::Sm80,
+    cutlass::gemm::GemmShape<kTileM, kTileN, kTileK>,
+    cutlass::gemm::GemmShape<kWarpM, kWarpN, kTileK>,
+    typename Math<Element>::InstructionShape,
+    cutlass::epilogue::thread::LinearCombination<Element, Math<Element>::kAlignment, float,
+                                                 float>,
+    Swizzle, kStages, false, typename Math<Element>::Operator>::GemmKernel;
+
+template <typename Kernel, typename Element>
+__device__ void run(int m, int n, int k, Element const* a, long long lda, Element const* b,
+                    long long ldb, Element const* c, long long ldc, Element* d, long long ldd,
+                    float alpha, float beta) {
+  using Shape = typename Kernel::Mma::Shape;
+  cutlass::gemm::GemmCoord problem(m, n, k);
+  cutlass::gemm::GemmCoord tiles =
+      Swizzle().get_tiled_shape(problem, {Shape::kM, Shape::kN, Shape::kK}, 1);
+  typename Kernel::Params params(
+      problem, tiles,
+      {const_cast<Element*>(a), cutlass::layout::RowMajor(int(lda))},
+      {const_cast<Element*>(b), cutlass::layout::RowMajor(int(ldb))},
+      {const_cast<Element*>(c), cutlass::layout::RowMajor(int(ldc))},
+      {d, cutlass::layout::RowMajor(int(ldd))},
+      {alpha, beta});
+  extern __shared__ int shared_storage[];
+  Kernel op;
+  op(params, *reinterpret_cast<typename Kernel::SharedStorage*>(shared_storage));
+}
+
+}  // namespace
+
+#define CUTLASS_SYS_PREBUILT_GEMM(name, Element, ...)                                          \
+  using name##_kernel = Kernel<Element, __VA_ARGS__>;                                          \
+  extern "C" __device__ int name##_threads = name##_kernel::kThreadCount;                      \
+  extern "C" __device__ int name##_shared_memory =                                             \
+      int(sizeof(typename name##_kernel::SharedStorage));                                      \
+  extern "C" __global__ void __launch_bounds__(name##_kernel::kThreadCount)                    \
+      name(int m, int n, int k, Element const* a, long long lda, Element const* b,              \
+           long long ldb, Element const* c, long long ldc, Element* d, long long ldd,          \
+           float alpha, float beta) {                                                          \
+    run<name##_kernel>(m, n, k, a, lda, b, ldb, c, ldc, d, ldd, alpha, beta);                  \
+  }
+
+// Largest tiles first, as PREBUILT_GEMM_KERNELS lists them.
+CUTLASS_SYS_PREBUILT_GEMM(cutlass_sys_prebuilt_gemm_f16_128x256x32, cutlass::half_t,
+                          128, 256, 32, 64, 64, 3)
+CUTLASS_SYS_PREBUILT_GEMM(cutlass_sys_prebuilt_gemm_f16_128x128x32, cutlass::half_t,
+                          128, 128, 32, 64, 64, 3)
+CUTLASS_SYS_PREBUILT_GEMM(cutlass_sys_prebuilt_gemm_f16_64x64x32, cutlass::half_t,
+                          64, 64, 32, 32, 32, 4)
+CUTLASS_SYS_PREBUILT_GEMM(cutlass_sys_prebuilt_gemm_bf16_128x256x32, cutlass::bfloat16_t,
+                          128, 256, 32, 64, 64, 3)
+CUTLASS_SYS_PREBUILT_GEMM(cutlass_sys_prebuilt_gemm_bf16_128x128x32, cutlass::bfloat16_t,
+                          128, 128, 32, 64, 64, 3)
+CUTLASS_SYS_PREBUILT_GEMM(cutlass_sys_prebuilt_gemm_bf16_64x64x32, cutlass::bfloat16_t,
+                          64, 64, 32, 32, 32, 4)
+CUTLASS_SYS_PREBUILT_GEMM(cutlass_sys_prebuilt_gemm_f32_128x256x16, float,
+                          128, 256, 16, 64, 64, 3)
+CUTLASS_SYS_PREBUILT_GEMM(cutlass_sys_prebuilt_gemm_f32_128x128x16, float,
+                          128, 128, 16, 64, 64, 4)
+CUTLASS_SYS_PREBUILT_GEMM(cutlass_sys_prebuilt_gemm_f32_64x64x16, float,
+                          64, 64, 16, 32, 32, 4)
@@ -0,0 +1,353 @@
+//! Runs `cutlass_profiler`, built by the `profiler` feature alongside the
+//! CUTLASS library, and parses its CSV report.
+//!
+//! [`Profiler::run`] takes the profiler's own command-line arguments (e.g.
+//! `--operation=Gemm --m=4096 --n=4096 --k=4096 --A=f16:row`, see
+//! `cutlass_profiler --help`) and returns one [`ProfilerRecord`] per
+//! operation and problem profiled, so tile configurations can be chosen from
+//! measured runtimes without a separate C++ build of the profiler.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::string::{String, ToString};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::vec::Vec;
+use std::{env, fmt, fs, io, process};
+
+/// Error returned by [`Profiler::run`].
+#[derive(Debug)]
+pub enum ProfilerError {
+    /// Running the profiler or reading its report failed.
+    Io(io::Error),
+    /// The profiler exited unsuccessfully.
+    Failed { status: ExitStatus, stderr: String },
+    /// A report line had a different number of fields than its header.
+    Malformed { line: String },
+}
+
+impl fmt::Display for ProfilerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfilerError::Io(err) => write!(f, "cutlass_profiler I/O error: {}", err),
+            ProfilerError::Failed { status, stderr } => {
+                write!(f, "cutlass_profiler failed ({}): {}", status, stderr.trim())
+            }
+            ProfilerError::Malformed { line } => {
+                write!(f, "malformed cutlass_profiler report line: {}", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProfilerError {}
+
+impl From<io::Error> for ProfilerError {
+    fn from(err: io::Error) -> Self {
+        ProfilerError::Io(err)
+    }
+}
+
+/// One row of a profiler report: an operation profiled on one problem.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfilerRecord {
+    fields: Vec<(String, String)>,
+}
+
+impl ProfilerRecord {
+    /// The value of report column `column` (e.g. `"m"`, `"Runtime"`).
+    pub fn get(&self, column: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Every column of the row, in report order.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// The operation's procedural name, as `Operation::name` in the
+    /// `manifest` module.
+    pub fn operation(&self) -> Option<&str> {
+        self.get("Operation")
+    }
+
+    /// The operation kind, e.g. `"gemm"`.
+    pub fn operation_kind(&self) -> Option<&str> {
+        self.get("OperationKind")
+    }
+
+    /// `"passed"` when the result was verified, `"not_verified"` when
+    /// verification was off; anything else means the run failed.
+    pub fn disposition(&self) -> Option<&str> {
+        self.get("Disposition")
+    }
+
+    /// Average runtime in milliseconds.
+    pub fn runtime_ms(&self) -> Option<f64> {
+        self.get("Runtime")?.parse().ok()
+    }
+
+    pub fn gflops(&self) -> Option<f64> {
+        self.get("GFLOPs")?.parse().ok()
+    }
+
+    pub fn gbps(&self) -> Option<f64> {
+        self.get("GB/s")?.parse().ok()
+    }
+
+    fn succeeded(&self) -> bool {
+        matches!(self.disposition(), Some("passed" | "not_verified"))
+    }
+}
+
+/// The parsed CSV report of one [`Profiler::run`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProfilerReport {
+    pub records: Vec<ProfilerRecord>,
+}
+
+impl ProfilerReport {
+    /// Parses a CSV report as written by `cutlass_profiler --output`.
+    pub fn parse(csv: &str) -> Result<Self, ProfilerError> {
+        let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+        let header = match lines.next() {
+            Some(header) => split_csv(header),
+            None => return Ok(Self::default()),
+        };
+        let records = lines
+            .map(|line| {
+                let values = split_csv(line);
+                if values.len() != header.len() {
+                    return Err(ProfilerError::Malformed {
+                        line: line.to_string(),
+                    });
+                }
+                Ok(ProfilerRecord {
+                    fields: header.iter().cloned().zip(values).collect(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { records })
+    }
+
+    /// The successful record with the lowest runtime.
+    pub fn fastest(&self) -> Option<&ProfilerRecord> {
+        self.records
+            .iter()
+            .filter(|record| record.succeeded())
+            .filter_map(|record| Some((record.runtime_ms()?, record)))
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, record)| record)
+    }
+}
+
+/// Splits one CSV line into fields, honouring double-quoted fields.
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// A `cutlass_profiler` executable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profiler {
+    path: PathBuf,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler {
+    /// The profiler the build script built for this crate's archs.
+    pub fn new() -> Self {
+        Self::with_path(env!("CUTLASS_PROFILER"))
+    }
+
+    /// A profiler executable at `path`, e.g. from another CUTLASS build.
+    pub fn with_path(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Runs the profiler with `args` and returns its report. `--output` is
+    /// supplied here; every report file the run writes (one per operation
+    /// kind) is parsed and removed.
+    pub fn run<I, S>(&self, args: I) -> Result<ProfilerReport, ProfilerError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let dir = report_dir();
+        fs::create_dir_all(&dir)?;
+        let result = self.run_in(&dir, args);
+        let _ = fs::remove_dir_all(&dir);
+        result
+    }
+
+    fn run_in<I, S>(&self, dir: &Path, args: I) -> Result<ProfilerReport, ProfilerError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut output = OsString::from("--output=");
+        output.push(dir.join("report.csv"));
+
+        let mut command = Command::new(&self.path);
+        command.args(args).arg(output);
+        // The shared CUTLASS libraries sit next to the executable
+        if let Some(bin_dir) = self.path.parent() {
+            let mut paths = std::vec![bin_dir.to_path_buf()];
+            if let Some(existing) = env::var_os("LD_LIBRARY_PATH") {
+                paths.extend(env::split_paths(&existing));
+            }
+            if let Ok(joined) = env::join_paths(paths) {
+                command.env("LD_LIBRARY_PATH", joined);
+            }
+        }
+        let run = command.output()?;
+        if !run.status.success() {
+            return Err(ProfilerError::Failed {
+                status: run.status,
+                stderr: String::from_utf8_lossy(&run.stderr).into_owned(),
+            });
+        }
+
+        let mut reports: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "csv"))
+            .collect();
+        reports.sort();
+        let mut report = ProfilerReport::default();
+        for path in &reports {
+            report
+                .records
+                .extend(ProfilerReport::parse(&fs::read_to_string(path)?)?.records);
+        }
+        Ok(report)
+    }
+}
+
+/// A fresh directory name for one run's report files.
+fn report_dir() -> PathBuf {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    env::temp_dir().join(std::format!(
+        "cutlass-profiler-{}-{}",
+        process::id(),
+        RUNS.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rows of a `cutlass_profiler --operation=Gemm --output=...` report: a
+    /// verified run, one without verification, a kernel the device cannot
+    /// run and a failed one.
+    const REPORT: &str = "\
+Problem,Provider,OperationKind,Operation,Disposition,Status,gemm_kind,m,n,k,A,B,C,D,alpha,beta,\
+split_k_mode,split_k_slices,batch_count,op_class,accum,cta_m,cta_n,cta_k,stages,warps_m,warps_n,\
+warps_k,inst_m,inst_n,inst_k,min_cc,max_cc,Bytes,Flops,Flops/Byte,Runtime,GB/s,GFLOPs\r
+1,CUTLASS,gemm,cutlass_tensorop_f16_s16816gemm_f16_256x128_32x3_tn_align8,passed,success,\
+universal,4096,4096,4096,f16:column,f16:row,f16:column,f16:column,1,0,serial,1,1,tensorop,f32,\
+256,128,32,3,4,2,1,16,8,16,80,1024,100663296,137438953472,1365,0.912384,102.752,150636\r
+1,CUTLASS,gemm,cutlass_tensorop_f16_s16816gemm_f16_128x128_32x4_tn_align8,not_verified,success,\
+universal,4096,4096,4096,f16:column,f16:row,f16:column,f16:column,1,0,serial,1,1,tensorop,f32,\
+128,128,32,4,2,2,1,16,8,16,80,1024,100663296,137438953472,1365,0.958781,97.7801,143347\r
+1,CUTLASS,gemm,cutlass3x_sm90_tensorop_gemm_f16_f16_f32_f16_f16_128x256x64_1x2x1_0_tnn_align8,\
+not_supported,error_not_supported,universal,4096,4096,4096,f16:column,f16:row,f16:column,\
+f16:column,1,0,serial,1,1,tensorop,f32,128,256,64,0,4,2,1,64,256,16,90,90,100663296,\
+137438953472,1365,0,0,0\r
+1,CUTLASS,gemm,cutlass_tensorop_f16_s16816gemm_f16_64x64_64x5_tn_align8,failed,\
+error_internal,universal,4096,4096,4096,f16:column,f16:row,f16:column,f16:column,1,0,serial,\
+1,1,tensorop,f32,64,64,64,5,2,2,1,16,8,16,80,1024,100663296,137438953472,1365,0.5,201.3,274877\r
+";
+
+    #[test]
+    fn parses_a_gemm_report() {
+        let report = ProfilerReport::parse(REPORT).unwrap();
+        assert_eq!(report.records.len(), 4);
+
+        let first = &report.records[0];
+        assert_eq!(first.operation_kind(), Some("gemm"));
+        assert_eq!(
+            first.operation(),
+            Some("cutlass_tensorop_f16_s16816gemm_f16_256x128_32x3_tn_align8")
+        );
+        assert_eq!(first.disposition(), Some("passed"));
+        assert_eq!(first.get("m"), Some("4096"));
+        assert_eq!(first.get("A"), Some("f16:column"));
+        assert_eq!(first.runtime_ms(), Some(0.912384));
+        assert_eq!(first.gbps(), Some(102.752));
+        // The trailing `\r` is not part of the last column
+        assert_eq!(first.gflops(), Some(150636.0));
+        assert_eq!(first.fields().count(), 39);
+        assert_eq!(first.get("cluster_m"), None);
+
+        let dispositions: Vec<_> = report
+            .records
+            .iter()
+            .map(|record| record.disposition().unwrap())
+            .collect();
+        assert_eq!(
+            dispositions,
+            ["passed", "not_verified", "not_supported", "failed"]
+        );
+        assert_eq!(report.records[2].get("Status"), Some("error_not_supported"));
+        assert_eq!(report.records[2].runtime_ms(), Some(0.0));
+    }
+
+    #[test]
+    fn fastest_skips_unsuccessful_runs() {
+        let report = ProfilerReport::parse(REPORT).unwrap();
+        // The not_supported row reports 0 ms and the failed one 0.5 ms
+        let fastest = report.fastest().unwrap();
+        assert_eq!(fastest.get("cta_m"), Some("256"));
+        assert_eq!(fastest.runtime_ms(), Some(0.912384));
+
+        let report = ProfilerReport::parse(&REPORT.replace("passed", "incorrect")).unwrap();
+        assert_eq!(report.fastest().unwrap().get("cta_m"), Some("128"));
+    }
+
+    #[test]
+    fn quoted_fields_and_malformed_lines() {
+        let report = ProfilerReport::parse("Operation,Runtime\n\"a,\"\"b\"\"\",1.5\n\n").unwrap();
+        assert_eq!(report.records[0].operation(), Some("a,\"b\""));
+        assert_eq!(report.records[0].runtime_ms(), Some(1.5));
+
+        assert_eq!(
+            ProfilerReport::parse("").unwrap(),
+            ProfilerReport::default()
+        );
+        match ProfilerReport::parse("Operation,Runtime\ngemm\n") {
+            Err(ProfilerError::Malformed { line }) => assert_eq!(line, "gemm"),
+            other => panic!("expected a malformed line, got {:?}", other),
+        }
+    }
+}
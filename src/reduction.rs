@@ -0,0 +1,250 @@
+//! Device reductions.
+//!
+//! - [`cutlass_sys_reduce_split_k`] sums the partial accumulator tiles of a
+//!   parallel split-K launch and applies `D = alpha * sum + beta * C`. The
+//!   partials have the accumulator type of the [`DataType`] (`f32`, or `i32`
+//!   for `S8`) and `C` / `D` its output type; all are row-major.
+//! - [`cutlass_sys_reduce_tensor`] reduces one axis of a packed NHWC tensor
+//!   with a sum, maximum or minimum, computing in `f32`. The output is the
+//!   input extent with the reduced axis set to 1. Only the floating-point
+//!   types are supported.
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::error::CutlassError;
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Problem description of a split-K reduction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SplitKReduceConfig {
+    pub m: i32,
+    pub n: i32,
+    /// Operand type of the GEMM that produced the partials.
+    pub element: DataType,
+    pub partitions: i32,
+    /// Elements between the start of consecutive partial tiles.
+    pub partition_stride: i64,
+    /// Leading dimension of each partial tile.
+    pub ldw: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub alpha: f32,
+    pub beta: f32,
+}
+
+impl SplitKReduceConfig {
+    /// `partitions` tightly packed `m x n` partials stored back to back,
+    /// reduced with `alpha = 1`, `beta = 0`.
+    pub const fn new(m: i32, n: i32, element: DataType, partitions: i32) -> Self {
+        Self {
+            m,
+            n,
+            element,
+            partitions,
+            partition_stride: m as i64 * n as i64,
+            ldw: n as i64,
+            ldc: n as i64,
+            ldd: n as i64,
+            alpha: 1.0,
+            beta: 0.0,
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_reduce_split_k`] needs for this
+    /// config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_reduce_split_k_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_reduce_split_k`] can run this config before
+    /// any operand is allocated, returning the status the launch would fail
+    /// with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_reduce_split_k_can_implement(context, self) }.result()
+    }
+
+    /// Launches the reduction on `stream` through [`cutlass_sys_reduce_split_k`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory, or
+    /// may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &SplitKReduceOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_reduce_split_k(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers of a split-K reduction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct SplitKReduceOperands {
+    pub partials: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+}
+
+/// Reduction applied by [`cutlass_sys_reduce_tensor`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ReduceOp {
+    #[default]
+    Sum = 0,
+    Max = 1,
+    Min = 2,
+}
+
+/// Axis of an NHWC tensor.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ReduceAxis {
+    N = 0,
+    H = 1,
+    W = 2,
+    #[default]
+    C = 3,
+}
+
+/// Problem description of a tensor reduction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TensorReduceConfig {
+    pub n: i32,
+    pub h: i32,
+    pub w: i32,
+    pub c: i32,
+    pub axis: ReduceAxis,
+    pub op: ReduceOp,
+    pub element: DataType,
+}
+
+impl TensorReduceConfig {
+    pub const fn new(
+        n: i32,
+        h: i32,
+        w: i32,
+        c: i32,
+        axis: ReduceAxis,
+        op: ReduceOp,
+        element: DataType,
+    ) -> Self {
+        Self {
+            n,
+            h,
+            w,
+            c,
+            axis,
+            op,
+            element,
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_reduce_tensor`] needs for this
+    /// config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_reduce_tensor_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_reduce_tensor`] can run this config before any
+    /// operand is allocated, returning the status the launch would fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_reduce_tensor_can_implement(context, self) }.result()
+    }
+
+    /// Launches the reduction on `stream` through [`cutlass_sys_reduce_tensor`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory, or
+    /// may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &TensorReduceOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_reduce_tensor(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers of a tensor reduction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct TensorReduceOperands {
+    pub src: *const c_void,
+    pub dst: *mut c_void,
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_reduce_split_k`] needs.
+    pub fn cutlass_sys_reduce_split_k_workspace_size(
+        context: *const CutlassContext,
+        config: *const SplitKReduceConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_reduce_split_k`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_reduce_split_k_can_implement(
+        context: *const CutlassContext,
+        config: *const SplitKReduceConfig,
+    ) -> Status;
+
+    /// Launch a split-K reduction on `stream`.
+    pub fn cutlass_sys_reduce_split_k(
+        context: *const CutlassContext,
+        config: *const SplitKReduceConfig,
+        operands: *const SplitKReduceOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_reduce_tensor`] needs; non-zero
+    /// when the reduction is split into two passes.
+    pub fn cutlass_sys_reduce_tensor_workspace_size(
+        context: *const CutlassContext,
+        config: *const TensorReduceConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_reduce_tensor`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_reduce_tensor_can_implement(
+        context: *const CutlassContext,
+        config: *const TensorReduceConfig,
+    ) -> Status;
+
+    /// Launch a tensor reduction on `stream`.
+    pub fn cutlass_sys_reduce_tensor(
+        context: *const CutlassContext,
+        config: *const TensorReduceConfig,
+        operands: *const TensorReduceOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
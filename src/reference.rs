@@ -0,0 +1,288 @@
+//! Host reference GEMM and convolution (`reference` feature).
+//!
+//! Binds `cutlass::reference::host`, the CPU implementations CUTLASS's own
+//! unit tests check its kernels against, so device results can be validated
+//! in Rust tests with CUTLASS's conversions, rounding and epilogue arithmetic
+//! rather than a reimplementation of them. Each reference is instantiated
+//! with the element, output and accumulator types of the matching device
+//! entry point: `f32` accumulation for `F16`, `BF16` and `F32`, and `i32`
+//! accumulation and output for `S8`.
+//!
+//! Integer results match the device bit for bit. Floating-point results
+//! share every conversion but not the order of the K-dimension sum (nor the
+//! 3xTF32 emulation of `F32` convolutions), so they should be compared with a
+//! tolerance that grows with `K`.
+//!
+//! The operands are host slices of any type of the element's size (`u16`,
+//! or `half::f16` / `half::bf16` with the `half` feature, for the 16-bit
+//! types) laid out as for the device entry points; see
+//! [`gemm`](crate::gemm) and [`conv`](crate::conv). The GEMM references take
+//! any of the layouts [`GemmConfig::layout_a`](crate::gemm::GemmConfig::layout_a)
+//! and `layout_b` select. Without `c`, `d` is read
+//! as `C`, so it must be initialized unless `beta == 0`.
+//!
+//! [`gemm_device`] and [`conv_device`] run `cutlass::reference::device`
+//! instead: one thread per output element on device pointers, slow but
+//! independent of the kernels' tiling, for problems too large for the host.
+//! The same kernels back the `verify` flag of
+//! [`GemmConfig`](crate::gemm::GemmConfig::verify) and
+//! [`ConvConfig`](crate::conv::ConvConfig::verify), which checks a launch's
+//! `D` against them in place.
+
+use core::mem::size_of;
+
+use crate::context::CutlassContext;
+use crate::conv::{ConvConfig, ConvOperands, ConvOperator};
+use crate::gemm::{GemmConfig, GemmOperands};
+use crate::library::{LayoutTypeID, NumericTypeID};
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Runs the host reference of `config`, which must be data-parallel without
+/// a fused epilogue (`split_k_slices` is ignored).
+///
+/// [`Status::ErrorInvalidDataType`] if `T` or `U` is not the size of the
+/// config's element or output type, [`Status::ErrorInvalidProblem`] if a
+/// slice is too short for its extent and leading dimension.
+pub fn gemm<T: Copy, U: Copy>(
+    config: &GemmConfig,
+    a: &[T],
+    b: &[T],
+    c: Option<&[U]>,
+    d: &mut [U],
+) -> Result<(), Status> {
+    check_types::<T, U>(config.element)?;
+    let problem = config.problem;
+    if problem.m < 0 || problem.n < 0 || problem.k < 0 {
+        return Err(Status::ErrorInvalidProblem);
+    }
+    // Rows and columns of the operands as stored.
+    let stored = |layout, rows, columns| match layout {
+        LayoutTypeID::ColumnMajor => (columns, rows),
+        _ => (rows, columns),
+    };
+    check_len(
+        a.len(),
+        stored(config.layout_a, problem.m, problem.k),
+        config.lda,
+    )?;
+    check_len(
+        b.len(),
+        stored(config.layout_b, problem.k, problem.n),
+        config.ldb,
+    )?;
+    if let Some(c) = c {
+        check_len(c.len(), (problem.m, problem.n), config.ldc)?;
+    }
+    check_len(d.len(), (problem.m, problem.n), config.ldd)?;
+
+    let operands = GemmOperands::new(
+        a.as_ptr().cast(),
+        b.as_ptr().cast(),
+        c.map_or(core::ptr::null(), |c| c.as_ptr().cast()),
+        d.as_mut_ptr().cast(),
+    );
+    // SAFETY: the slices cover every element the problem addresses.
+    unsafe { cutlass_sys_reference_gemm(config, &operands) }.result()
+}
+
+/// Runs the host reference of `operator` for `config`, 2-D for a unit-depth
+/// config and 3-D otherwise, with the operands bound as in the
+/// [module docs](crate::conv). The split-K settings are ignored and only
+/// 2-D supports `groups != 1`.
+///
+/// Errors as for [`gemm`], checking packed NDHWC extents.
+pub fn conv<T: Copy, U: Copy>(
+    config: &ConvConfig,
+    operator: ConvOperator,
+    a: &[T],
+    b: &[T],
+    c: Option<&[U]>,
+    d: &mut [U],
+) -> Result<(), Status> {
+    check_types::<T, U>(config.element)?;
+    if config.groups < 1 || config.c % config.groups != 0 {
+        return Err(Status::ErrorInvalidProblem);
+    }
+    let z = config.output_depth();
+    let (p, q) = config.output_size();
+    let volume = |extents: [i32; 5]| {
+        extents
+            .iter()
+            .try_fold(1usize, |n, &e| n.checked_mul(usize::try_from(e).ok()?))
+            .ok_or(Status::ErrorInvalidProblem)
+    };
+    let activation = volume([config.n, config.d, config.h, config.w, config.c])?;
+    let filter = volume([
+        config.k,
+        config.t,
+        config.r,
+        config.s,
+        config.c / config.groups,
+    ])?;
+    let output = volume([config.n, z, p, q, config.k])?;
+    let (a_len, b_len, d_len) = match operator {
+        ConvOperator::Fprop => (activation, filter, output),
+        ConvOperator::Dgrad => (output, filter, activation),
+        ConvOperator::Wgrad => (output, activation, filter),
+    };
+    if a.len() < a_len || b.len() < b_len || c.is_some_and(|c| c.len() < d_len) || d.len() < d_len {
+        return Err(Status::ErrorInvalidProblem);
+    }
+
+    let operands = ConvOperands {
+        a: a.as_ptr().cast(),
+        b: b.as_ptr().cast(),
+        c: c.map_or(core::ptr::null(), |c| c.as_ptr().cast()),
+        d: d.as_mut_ptr().cast(),
+    };
+    // SAFETY: the slices cover the packed tensors of the problem.
+    unsafe {
+        match operator {
+            ConvOperator::Fprop => cutlass_sys_reference_conv_fprop(config, &operands),
+            ConvOperator::Dgrad => cutlass_sys_reference_conv_dgrad(config, &operands),
+            ConvOperator::Wgrad => cutlass_sys_reference_conv_wgrad(config, &operands),
+        }
+    }
+    .result()
+}
+
+/// Runs the device reference of `config` on `context`'s device, with the
+/// same restrictions as [`gemm`]. Synchronizes `stream`, since the reference
+/// runs on the legacy default stream.
+///
+/// # Safety
+///
+/// The operands must be device pointers covering the problem as for
+/// [`cutlass_sys_gemm`](crate::gemm::cutlass_sys_gemm), and `stream` a
+/// stream of that device.
+pub unsafe fn gemm_device(
+    context: &CutlassContext,
+    config: &GemmConfig,
+    operands: &GemmOperands,
+    stream: cudaStream_t,
+) -> Result<(), Status> {
+    // SAFETY: the caller vouches for the operands and stream.
+    unsafe { cutlass_sys_reference_gemm_device(context, config, operands, stream) }.result()
+}
+
+/// Runs the device reference of `operator` for `config` on `stream`, 2-D for
+/// a unit-depth config and 3-D otherwise. Only `groups == 1` is supported.
+///
+/// # Safety
+///
+/// As for [`gemm_device`], with packed tensors bound as in the
+/// [module docs](crate::conv).
+pub unsafe fn conv_device(
+    context: &CutlassContext,
+    config: &ConvConfig,
+    operator: ConvOperator,
+    operands: &ConvOperands,
+    stream: cudaStream_t,
+) -> Result<(), Status> {
+    // SAFETY: the caller vouches for the operands and stream.
+    unsafe {
+        match operator {
+            ConvOperator::Fprop => {
+                cutlass_sys_reference_conv_device_fprop(context, config, operands, stream)
+            }
+            ConvOperator::Dgrad => {
+                cutlass_sys_reference_conv_device_dgrad(context, config, operands, stream)
+            }
+            ConvOperator::Wgrad => {
+                cutlass_sys_reference_conv_device_wgrad(context, config, operands, stream)
+            }
+        }
+    }
+    .result()
+}
+
+/// Checks that `T` and `U` have the sizes of the element and output types
+/// the references use for `element`.
+fn check_types<T, U>(element: DataType) -> Result<(), Status> {
+    let element_bytes = NumericTypeID::from(element).size_in_bits() as usize / 8;
+    let output_bytes = match element {
+        DataType::S8 => 4,
+        _ => element_bytes,
+    };
+    if size_of::<T>() != element_bytes || size_of::<U>() != output_bytes {
+        return Err(Status::ErrorInvalidDataType);
+    }
+    Ok(())
+}
+
+/// Checks that `len` elements hold a `rows x columns` matrix with leading
+/// dimension `ld`.
+fn check_len(len: usize, (rows, columns): (i32, i32), ld: i64) -> Result<(), Status> {
+    if rows == 0 || columns == 0 {
+        return Ok(());
+    }
+    if ld < columns as i64 {
+        return Err(Status::ErrorInvalidProblem);
+    }
+    let needed = (rows as i64 - 1) * ld + columns as i64;
+    if (len as u64) < needed as u64 {
+        return Err(Status::ErrorInvalidProblem);
+    }
+    Ok(())
+}
+
+extern "C" {
+    /// Host reference of `config` on host pointers: data-parallel configs
+    /// without a fused epilogue only, else [`Status::ErrorNotSupported`].
+    /// A null `C` reads `D`.
+    pub fn cutlass_sys_reference_gemm(
+        config: *const GemmConfig,
+        operands: *const GemmOperands,
+    ) -> Status;
+
+    /// Host reference fprop on host pointers, 2-D for a unit-depth config.
+    pub fn cutlass_sys_reference_conv_fprop(
+        config: *const ConvConfig,
+        operands: *const ConvOperands,
+    ) -> Status;
+
+    /// Host reference dgrad on host pointers, 2-D for a unit-depth config.
+    pub fn cutlass_sys_reference_conv_dgrad(
+        config: *const ConvConfig,
+        operands: *const ConvOperands,
+    ) -> Status;
+
+    /// Host reference wgrad on host pointers, 2-D for a unit-depth config.
+    pub fn cutlass_sys_reference_conv_wgrad(
+        config: *const ConvConfig,
+        operands: *const ConvOperands,
+    ) -> Status;
+
+    /// Device reference of `config` on device pointers, writing `D` with
+    /// leading dimension `ldd`. Synchronizes `stream`.
+    pub fn cutlass_sys_reference_gemm_device(
+        context: *const CutlassContext,
+        config: *const GemmConfig,
+        operands: *const GemmOperands,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Device reference fprop on `stream`, 2-D for a unit-depth config.
+    pub fn cutlass_sys_reference_conv_device_fprop(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+        operands: *const ConvOperands,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Device reference dgrad on `stream`, 2-D for a unit-depth config.
+    pub fn cutlass_sys_reference_conv_device_dgrad(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+        operands: *const ConvOperands,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Device reference wgrad on `stream`, 2-D for a unit-depth config.
+    pub fn cutlass_sys_reference_conv_device_wgrad(
+        context: *const CutlassContext,
+        config: *const ConvConfig,
+        operands: *const ConvOperands,
+        stream: cudaStream_t,
+    ) -> Status;
+}
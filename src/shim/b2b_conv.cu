@@ -0,0 +1,172 @@
+// Fused convolution chain entry points (src/b2b_conv.rs).
+//
+// Instantiates the register-resident B2bImplicitGemmConvolution of CUTLASS
+// example 13: conv0's threadblock covers all of its output channels and each
+// warp owns full rows of the intermediate tile, whose activated accumulators
+// become the A fragments of the pointwise conv1. check_chain mirrors the
+// restrictions B2bConvConfig::check tests on the Rust side.
+
+#include <type_traits>
+
+#include <cutlass/epilogue/thread/linear_combination_generic.h>
+
+#include <device/b2b_implicit_gemm_convolution.h>
+#include <kernel/default_b2b_conv2d_fprop.h>
+
+#include "conv.cuh"
+#include "evt.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+// Output channels of conv0, one threadblock tile.
+constexpr int kB2bK0 = 64;
+
+template <typename Element, template <typename> class Activation, int K1>
+using B2bConv2dFprop = cutlass::conv::device::B2bImplicitGemmConvolution<
+    typename cutlass::conv::kernel::DefaultB2bConv2dFprop<
+        Element, cutlass::layout::TensorNHWC,
+        Element, cutlass::layout::TensorNHWC,
+        Element, cutlass::layout::TensorNHWC,
+        float,
+        cutlass::arch::OpClassTensorOp, cutlass::arch::Sm80,
+        cutlass::gemm::GemmShape<64, kB2bK0, 32>, cutlass::gemm::GemmShape<64, K1, 32>,
+        cutlass::gemm::GemmShape<16, kB2bK0, 32>, cutlass::gemm::GemmShape<16, K1, 32>,
+        cutlass::gemm::GemmShape<16, 8, 16>,
+        // The intermediate is produced one MMA accumulator fragment at a time.
+        cutlass::epilogue::thread::LinearCombinationGeneric<
+            Activation, Element, 16 * 8 / 32, float, float,
+            cutlass::epilogue::thread::ScaleType::OnlyAlphaScaling>,
+        ConvEpilogue<Element, Element>,
+        cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<1>,
+        3,
+        cutlass::arch::OpMultiplyAdd,
+        cutlass::conv::IteratorAlgorithm::kOptimized>::Kernel>;
+
+cutlass::Status check_chain(const cutlass_sys_b2b_conv_config_t& cfg) {
+  const cutlass_sys_conv_config_t& c0 = cfg.conv0;
+  const cutlass_sys_conv_config_t& c1 = cfg.conv1;
+  if (!is_conv2d(c0) || !is_conv2d(c1) || c0.groups != 1 || c1.groups != 1 ||
+      c0.split_k_slices != 1 || c1.split_k_slices != 1 || c0.verify || c1.verify) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  if (c0.element != c1.element) {
+    return cutlass::Status::kErrorInvalidDataType;
+  }
+  if (c1.r != 1 || c1.s != 1 || c1.stride_h != 1 || c1.stride_w != 1 || c1.pad_h != 0 ||
+      c1.pad_w != 0 || c0.beta != 0.0f) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  cutlass::conv::Conv2dProblemSize p0 = conv2d_problem(c0);
+  if (c1.n != c0.n || c1.h != p0.P || c1.w != p0.Q || c1.c != c0.k) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  if (c0.k != kB2bK0 || (c1.k != 64 && c1.k != 128)) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  if (c0.c % 8 != 0) {
+    return cutlass::Status::kErrorMisalignedOperand;
+  }
+  return cutlass::Status::kSuccess;
+}
+
+template <typename Conv>
+cutlass::Status run_b2b_conv(const cutlass_sys_b2b_conv_config_t& cfg,
+                             const cutlass_sys_b2b_conv_operands_t& o,
+                             void* workspace,
+                             cudaStream_t stream,
+                             size_t* workspace_size) {
+  using Element = typename Conv::ElementA;
+  using Layout = cutlass::layout::TensorNHWC;
+  constexpr auto kFprop = cutlass::conv::Operator::kFprop;
+  cutlass::conv::Conv2dProblemSize p0 = conv2d_problem(cfg.conv0);
+  cutlass::conv::Conv2dProblemSize p1 = conv2d_problem(cfg.conv1);
+
+  typename Conv::Arguments args(
+      p0,
+      p1,
+      {static_cast<Element*>(const_cast<void*>(o.a)),
+       Layout::packed(cutlass::conv::implicit_gemm_tensor_a_extent(kFprop, p0))},
+      {static_cast<Element*>(const_cast<void*>(o.filter0)),
+       Layout::packed(cutlass::conv::implicit_gemm_tensor_b_extent(kFprop, p0))},
+      {},
+      {},
+      {},
+      {static_cast<Element*>(const_cast<void*>(o.filter1)),
+       Layout::packed(cutlass::conv::implicit_gemm_tensor_b_extent(kFprop, p1))},
+      {static_cast<Element*>(const_cast<void*>(o.c)),
+       Layout::packed(cutlass::conv::implicit_gemm_tensor_c_extent(kFprop, p1))},
+      {static_cast<Element*>(o.d),
+       Layout::packed(cutlass::conv::implicit_gemm_tensor_c_extent(kFprop, p1))},
+      {cfg.conv0.alpha, 0.0f},
+      {cfg.conv1.alpha, cfg.conv1.beta});
+
+  return query_or_run<Conv>(args, workspace, stream, workspace_size);
+}
+
+cutlass::Status b2b_conv2d_fprop(const cutlass_sys_b2b_conv_config_t& cfg,
+                                 const cutlass_sys_b2b_conv_operands_t* ops,
+                                 void* workspace,
+                                 cudaStream_t stream,
+                                 size_t* workspace_size) {
+  cutlass::Status status = check_chain(cfg);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+  cutlass_sys_b2b_conv_operands_t o = ops ? *ops : cutlass_sys_b2b_conv_operands_t{};
+  return dispatch_dtype(cfg.conv0.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (!std::is_same_v<Element, cutlass::half_t> &&
+                  !std::is_same_v<Element, cutlass::bfloat16_t>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      return evt::dispatch_activation(cfg.activation, [&](auto act) {
+        using Act = decltype(act);
+        if (cfg.conv1.k == 64) {
+          return run_b2b_conv<B2bConv2dFprop<Element, Act::template type, 64>>(
+              cfg, o, workspace, stream, workspace_size);
+        }
+        return run_b2b_conv<B2bConv2dFprop<Element, Act::template type, 128>>(
+            cfg, o, workspace, stream, workspace_size);
+      });
+    }
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_b2b_conv2d_fprop_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_b2b_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::b2b_conv2d_fprop(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_b2b_conv2d_fprop_can_implement(
+    const cutlass_sys_context_t* context, const cutlass_sys_b2b_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(
+      cutlass_sys::b2b_conv2d_fprop(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_b2b_conv2d_fprop(const cutlass_sys_context_t* context,
+                                            const cutlass_sys_b2b_conv_config_t* config,
+                                            const cutlass_sys_b2b_conv_operands_t* operands,
+                                            void* workspace,
+                                            cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::b2b_conv2d_fprop(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
@@ -0,0 +1,281 @@
+// Fused GEMM pair entry points (src/b2b_gemm.rs).
+//
+// Back-to-back mode instantiates the register-resident B2bGemm of CUTLASS
+// example 13: the threadblock of GEMM0 covers all n0 columns, and its warps
+// each own full rows of the intermediate, so the activated accumulators feed
+// the GEMM1 mainloop as its A fragments. Gated mode instantiates the DualGemm
+// of example 45, which runs both products over one pass of A and combines
+// their epilogue outputs with LeftActivationAndMul below.
+
+#include <type_traits>
+
+#include <cutlass/epilogue/thread/linear_combination.h>
+#include <cutlass/epilogue/thread/linear_combination_generic.h>
+
+#include <45_dual_gemm/device/dual_gemm.h>
+#include <device/b2b_gemm.h>
+
+#include "common.cuh"
+#include "evt.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+using cutlass::epilogue::thread::ScaleType;
+
+// act(lhs) * rhs, computed in ElementCompute: example 45's LeftSiLUAndMul
+// with the activation as a parameter.
+template <template <typename> class Activation,
+          typename ElementOutput_,
+          int Count,
+          typename ElementAccumulator_,
+          typename ElementCompute_>
+class LeftActivationAndMul {
+ public:
+  using ElementOutput = ElementOutput_;
+  using ElementAccumulator = ElementAccumulator_;
+  using ElementCompute = ElementCompute_;
+
+  static int const kCount = Count;
+  using FragmentOutput = cutlass::Array<ElementOutput, kCount>;
+  using FragmentAccumulator = cutlass::Array<ElementAccumulator, kCount>;
+  using ComputeFragment = cutlass::Array<ElementCompute, kCount>;
+
+  static cutlass::FloatRoundStyle const kRound = cutlass::FloatRoundStyle::round_to_nearest;
+
+  struct Params {};
+
+  CUTLASS_HOST_DEVICE
+  LeftActivationAndMul(Params const&) {}
+
+  CUTLASS_HOST_DEVICE
+  bool is_source_needed() const { return true; }
+
+  CUTLASS_HOST_DEVICE
+  void set_k_partition(int, int) { assert(false); }
+
+  CUTLASS_HOST_DEVICE
+  FragmentOutput operator()(FragmentAccumulator const& lhs, FragmentAccumulator const& rhs) const {
+    cutlass::NumericArrayConverter<ElementCompute, ElementAccumulator, kCount, kRound> to_compute;
+    cutlass::NumericArrayConverter<ElementOutput, ElementCompute, kCount, kRound> to_output;
+    Activation<ComputeFragment> activation;
+    cutlass::multiplies<ComputeFragment> mul;
+    return to_output(mul(activation(to_compute(lhs)), to_compute(rhs)));
+  }
+
+  CUTLASS_HOST_DEVICE
+  ElementOutput operator()(ElementAccumulator const& lhs, ElementAccumulator const& rhs) const {
+    Activation<ElementCompute> activation;
+    return ElementOutput(activation(ElementCompute(lhs)) * ElementCompute(rhs));
+  }
+};
+
+// Elements per 128-bit access.
+template <typename Element>
+constexpr int kVector = 128 / cutlass::sizeof_bits<Element>::value;
+
+template <typename Element>
+using OutputOp = cutlass::epilogue::thread::LinearCombination<Element, kVector<Element>, float,
+                                                              float, ScaleType::OnlyAlphaScaling>;
+
+// Intermediate (GEMM0) tile width of the back-to-back kernels.
+constexpr int kB2bN0 = 64;
+
+template <typename Element, template <typename> class Activation, int N1>
+using B2bGemm = cutlass::gemm::device::B2bGemm<
+    Element, cutlass::layout::RowMajor,
+    Element, cutlass::layout::ColumnMajor,
+    Element, cutlass::layout::RowMajor,
+    float,
+    cutlass::arch::OpClassTensorOp, cutlass::arch::Sm80,
+    cutlass::gemm::GemmShape<64, kB2bN0, 32>, cutlass::gemm::GemmShape<64, N1, 32>,
+    cutlass::gemm::GemmShape<16, kB2bN0, 32>, cutlass::gemm::GemmShape<16, N1, 32>,
+    cutlass::gemm::GemmShape<16, 8, 16>,
+    // The intermediate is produced one MMA accumulator fragment at a time.
+    cutlass::epilogue::thread::LinearCombinationGeneric<
+        Activation, Element, 16 * 8 / 32, float, float, ScaleType::OnlyAlphaScaling>,
+    cutlass::epilogue::thread::LinearCombination<Element, kVector<Element>, float, float>,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<1>,
+    3>;
+
+template <typename Element, template <typename> class Activation>
+using DualGemm = cutlass::gemm::device::DualGemm<
+    Element, cutlass::layout::RowMajor,
+    Element, cutlass::layout::ColumnMajor, cutlass::layout::ColumnMajor,
+    Element, cutlass::layout::RowMajor,
+    float,
+    cutlass::arch::OpClassTensorOp, cutlass::arch::Sm80,
+    cutlass::gemm::GemmShape<128, 64, 32>,
+    cutlass::gemm::GemmShape<64, 32, 32>,
+    cutlass::gemm::GemmShape<16, 8, 16>,
+    OutputOp<Element>,
+    OutputOp<Element>,
+    LeftActivationAndMul<Activation, Element, kVector<Element>, float, float>,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<1>,
+    3,
+    /*StoreD0=*/false,
+    /*StoreD1=*/false,
+    /*SplitKSerial=*/false>;
+
+template <typename Op>
+cutlass::Status run_b2b(const cutlass_sys_b2b_gemm_config_t& cfg,
+                        const cutlass_sys_b2b_gemm_operands_t& o,
+                        void* workspace,
+                        cudaStream_t stream,
+                        size_t* workspace_size) {
+  using Element = typename Op::ElementA;
+  using LayoutScaleBias = cutlass::layout::RowMajor;
+  typename Op::Arguments args(
+      cfg.batch_count > 1 ? cutlass::gemm::GemmUniversalMode::kBatched
+                          : cutlass::gemm::GemmUniversalMode::kGemm,
+      to_cutlass(cfg.problem0),
+      to_cutlass(cfg.problem1),
+      {static_cast<Element const*>(o.a), typename Op::LayoutA(cfg.lda)},
+      {static_cast<Element const*>(o.b0), typename Op::LayoutB(cfg.ldb0)},
+      {nullptr, typename Op::LayoutC(0)},
+      {nullptr, LayoutScaleBias(0)},
+      {nullptr, LayoutScaleBias(0)},
+      {static_cast<Element const*>(o.b1), typename Op::LayoutB(cfg.ldb1)},
+      {static_cast<Element const*>(o.c), typename Op::LayoutC(cfg.ldc)},
+      {static_cast<Element*>(o.d), typename Op::LayoutC(cfg.ldd)},
+      cfg.batch_stride_a,
+      cfg.batch_stride_b0,
+      cfg.batch_stride_b1,
+      cfg.batch_stride_c,
+      cfg.batch_stride_d,
+      0,
+      0,
+      {cfg.alpha0, 0.0f},
+      {cfg.alpha1, cfg.beta1},
+      cfg.batch_count);
+
+  return query_or_run<Op>(args, workspace, stream, workspace_size);
+}
+
+template <typename Op>
+cutlass::Status run_dual(const cutlass_sys_b2b_gemm_config_t& cfg,
+                         const cutlass_sys_b2b_gemm_operands_t& o,
+                         void* workspace,
+                         cudaStream_t stream,
+                         size_t* workspace_size) {
+  using Element = typename Op::ElementA;
+  typename Op::Arguments args(
+      cfg.batch_count > 1 ? cutlass::gemm::DualGemmMode::kBatched
+                          : cutlass::gemm::DualGemmMode::kGemm,
+      to_cutlass(cfg.problem0),
+      {static_cast<Element const*>(o.a), typename Op::LayoutA(cfg.lda)},
+      {static_cast<Element const*>(o.b0), typename Op::LayoutB0(cfg.ldb0)},
+      {nullptr, typename Op::LayoutC(0)},
+      {nullptr, typename Op::LayoutC(0)},
+      {static_cast<Element const*>(o.b1), typename Op::LayoutB1(cfg.ldb1)},
+      {nullptr, typename Op::LayoutC(0)},
+      {nullptr, typename Op::LayoutC(0)},
+      {static_cast<Element*>(o.d), typename Op::LayoutC(cfg.ldd)},
+      {cfg.alpha0, 0.0f},
+      {cfg.alpha1, 0.0f},
+      {},
+      /*split_k_slices=*/1,
+      cfg.batch_count,
+      cfg.batch_stride_a,
+      cfg.batch_stride_b0,
+      cfg.batch_stride_b1,
+      0,
+      cfg.batch_stride_d);
+
+  return query_or_run<Op>(args, workspace, stream, workspace_size);
+}
+
+cutlass::Status check_problems(const cutlass_sys_b2b_gemm_config_t& cfg) {
+  const cutlass_sys_gemm_coord_t& p0 = cfg.problem0;
+  const cutlass_sys_gemm_coord_t& p1 = cfg.problem1;
+  if (cfg.batch_count < 1) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  switch (cfg.mode) {
+    case CUTLASS_SYS_B2B_MODE_BACK_TO_BACK:
+      if (p1.m != p0.m || p1.k != p0.n) {
+        return cutlass::Status::kErrorInvalidProblem;
+      }
+      if (p0.n != kB2bN0 || (p1.n != 64 && p1.n != 128)) {
+        return cutlass::Status::kErrorNotSupported;
+      }
+      return cutlass::Status::kSuccess;
+    case CUTLASS_SYS_B2B_MODE_GATED:
+      if (p1.m != p0.m || p1.n != p0.n || p1.k != p0.k) {
+        return cutlass::Status::kErrorInvalidProblem;
+      }
+      return cutlass::Status::kSuccess;
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+cutlass::Status b2b_gemm(const cutlass_sys_b2b_gemm_config_t& cfg,
+                         const cutlass_sys_b2b_gemm_operands_t* ops,
+                         void* workspace,
+                         cudaStream_t stream,
+                         size_t* workspace_size) {
+  cutlass::Status status = check_problems(cfg);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+  cutlass_sys_b2b_gemm_operands_t o = ops ? *ops : cutlass_sys_b2b_gemm_operands_t{};
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (!std::is_same_v<Element, cutlass::half_t> &&
+                  !std::is_same_v<Element, cutlass::bfloat16_t>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      return evt::dispatch_activation(cfg.activation, [&](auto act) {
+        using Act = decltype(act);
+        if (cfg.mode == CUTLASS_SYS_B2B_MODE_GATED) {
+          return run_dual<DualGemm<Element, Act::template type>>(cfg, o, workspace, stream,
+                                                                 workspace_size);
+        }
+        if (cfg.problem1.n == 64) {
+          return run_b2b<B2bGemm<Element, Act::template type, 64>>(cfg, o, workspace, stream,
+                                                                   workspace_size);
+        }
+        return run_b2b<B2bGemm<Element, Act::template type, 128>>(cfg, o, workspace, stream,
+                                                                  workspace_size);
+      });
+    }
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_b2b_gemm_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_b2b_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::b2b_gemm(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_b2b_gemm_can_implement(const cutlass_sys_context_t* context,
+                                                  const cutlass_sys_b2b_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::b2b_gemm(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_b2b_gemm(const cutlass_sys_context_t* context,
+                                    const cutlass_sys_b2b_gemm_config_t* config,
+                                    const cutlass_sys_b2b_gemm_operands_t* operands,
+                                    void* workspace,
+                                    cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::b2b_gemm(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
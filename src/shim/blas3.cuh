@@ -0,0 +1,148 @@
+// Helpers shared by the BLAS3 translation units. All BLAS3 routines use
+// column-major matrices, like BLAS and cuBLAS.
+
+#pragma once
+
+#include <type_traits>
+
+#include <cutlass/blas3.h>
+#include <cutlass/complex.h>
+#include <cutlass/epilogue/thread/linear_combination.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+
+// Kernel configuration of the SM80 BLAS3 instantiations, keyed by element
+// type. Every type runs on tensor cores; f32 and c32 use 3xTF32.
+template <typename Element>
+struct Sm80Blas3Traits;
+
+template <>
+struct Sm80Blas3Traits<float> {
+  using MathOperator = cutlass::arch::OpMultiplyAddFastF32;
+  using ThreadblockShape = cutlass::gemm::GemmShape<128, 128, 32>;
+  using WarpShape = cutlass::gemm::GemmShape<64, 64, 32>;
+  using InstructionShape = cutlass::gemm::GemmShape<16, 8, 8>;
+  static constexpr int kStages = 3;
+};
+
+template <>
+struct Sm80Blas3Traits<double> {
+  using MathOperator = cutlass::arch::OpMultiplyAdd;
+  using ThreadblockShape = cutlass::gemm::GemmShape<64, 64, 16>;
+  using WarpShape = cutlass::gemm::GemmShape<32, 32, 16>;
+  using InstructionShape = cutlass::gemm::GemmShape<8, 8, 4>;
+  static constexpr int kStages = 4;
+};
+
+template <>
+struct Sm80Blas3Traits<cutlass::complex<float>> {
+  using MathOperator = cutlass::arch::OpMultiplyAddComplexFastF32;
+  using ThreadblockShape = cutlass::gemm::GemmShape<64, 64, 16>;
+  using WarpShape = cutlass::gemm::GemmShape<32, 32, 16>;
+  using InstructionShape = cutlass::gemm::GemmShape<16, 8, 8>;
+  static constexpr int kStages = 3;
+};
+
+template <>
+struct Sm80Blas3Traits<cutlass::complex<double>> {
+  using MathOperator = cutlass::arch::OpMultiplyAddComplex;
+  using ThreadblockShape = cutlass::gemm::GemmShape<64, 64, 8>;
+  using WarpShape = cutlass::gemm::GemmShape<32, 32, 8>;
+  using InstructionShape = cutlass::gemm::GemmShape<8, 8, 4>;
+  static constexpr int kStages = 3;
+};
+
+template <typename Element>
+constexpr bool is_complex_v = cutlass::is_complex<Element>::value;
+
+// Element-wise epilogue; BLAS3 matrices are only guaranteed scalar alignment.
+template <typename Element>
+using Blas3Epilogue =
+    cutlass::epilogue::thread::LinearCombination<Element, 1, Element, Element>;
+
+// Calls `f` with a value of the CUTLASS element type named by `dtype`, for
+// the types with a BLAS3 instantiation.
+template <typename F>
+cutlass::Status dispatch_blas3_dtype(cutlass_sys_dtype_t dtype, F&& f) {
+  switch (dtype) {
+    case CUTLASS_SYS_DTYPE_F32:
+      return f(float{});
+    case CUTLASS_SYS_DTYPE_F64:
+      return f(double{});
+    case CUTLASS_SYS_DTYPE_C32:
+      return f(cutlass::complex<float>{});
+    case CUTLASS_SYS_DTYPE_C64:
+      return f(cutlass::complex<double>{});
+    default:
+      return cutlass::Status::kErrorInvalidDataType;
+  }
+}
+
+// Calls `f` with the fill mode as an integral constant.
+template <typename F>
+cutlass::Status dispatch_fill(cutlass_sys_fill_mode_t fill, F&& f) {
+  switch (fill) {
+    case CUTLASS_SYS_FILL_LOWER:
+      return f(std::integral_constant<cutlass::FillMode, cutlass::FillMode::kLower>{});
+    case CUTLASS_SYS_FILL_UPPER:
+      return f(std::integral_constant<cutlass::FillMode, cutlass::FillMode::kUpper>{});
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+// Calls `f` with the side mode as an integral constant.
+template <typename F>
+cutlass::Status dispatch_side(cutlass_sys_side_mode_t side, F&& f) {
+  switch (side) {
+    case CUTLASS_SYS_SIDE_LEFT:
+      return f(std::integral_constant<cutlass::SideMode, cutlass::SideMode::kLeft>{});
+    case CUTLASS_SYS_SIDE_RIGHT:
+      return f(std::integral_constant<cutlass::SideMode, cutlass::SideMode::kRight>{});
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+// Calls `f` with the diagonal type as an integral constant.
+template <typename F>
+cutlass::Status dispatch_diag(cutlass_sys_diag_type_t diag, F&& f) {
+  switch (diag) {
+    case CUTLASS_SYS_DIAG_NON_UNIT:
+      return f(std::integral_constant<cutlass::DiagType, cutlass::DiagType::kNonUnit>{});
+    case CUTLASS_SYS_DIAG_UNIT:
+      return f(std::integral_constant<cutlass::DiagType, cutlass::DiagType::kUnit>{});
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+// Calls `f` with the Type<> of the layout that stores a column-major operand
+// transposed or not: a transposed column-major matrix is a row-major one.
+template <typename F>
+cutlass::Status dispatch_transpose(cutlass_sys_transpose_t trans, F&& f) {
+  switch (trans) {
+    case CUTLASS_SYS_TRANSPOSE_N:
+      return f(Type<cutlass::layout::ColumnMajor>{});
+    case CUTLASS_SYS_TRANSPOSE_T:
+      return f(Type<cutlass::layout::RowMajor>{});
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+// Converts a C ABI scalar to `Element`, dropping the imaginary part for the
+// real types.
+template <typename Element>
+Element blas3_scalar(cutlass_sys_complex_f64_t value) {
+  if constexpr (is_complex_v<Element>) {
+    using Real = typename cutlass::RealType<Element>::Type;
+    return Element(static_cast<Real>(value.re), static_cast<Real>(value.im));
+  } else {
+    return static_cast<Element>(value.re);
+  }
+}
+
+}  // namespace cutlass_sys
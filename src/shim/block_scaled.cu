@@ -0,0 +1,234 @@
+// Block-scaled narrow-precision GEMM entry points (src/block_scaled.rs,
+// `sm100` feature).
+//
+// tcgen05 block-scaled MMAs multiply FP4 / FP6 / FP8 operands, applying one
+// scale factor per 16 (NVFP4) or 32 (MX formats) consecutive elements along K
+// as part of the instruction.
+
+#include "common.cuh"
+
+#if defined(CUTLASS_SYS_SM100)
+
+#include <cute/tensor.hpp>
+#include <cutlass/detail/sm100_blockscaled_layout.hpp>
+#include <cutlass/epilogue/collective/collective_builder.hpp>
+#include <cutlass/float_subbyte.h>
+#include <cutlass/gemm/collective/collective_builder.hpp>
+#include <cutlass/gemm/device/gemm_universal_adapter.h>
+#include <cutlass/gemm/kernel/gemm_universal.hpp>
+
+namespace cutlass_sys {
+namespace {
+
+// A block-scaled operand type with the alignment, in elements, its TMA loads
+// need: 128 bits for FP4 and FP8, and 128 elements for FP6.
+template <typename T, int Alignment>
+struct Operand {
+  using type = T;
+  static constexpr int kAlignment = Alignment;
+};
+
+// Calls `f` with the Operand<> named by `type`.
+template <typename F>
+cutlass::Status dispatch_block_scaled_type(cutlass_sys_block_scaled_type_t type, F&& f) {
+  switch (type) {
+    case CUTLASS_SYS_BLOCK_SCALED_NVFP4:
+      return f(Operand<cutlass::nv_float4_t<cutlass::float_e2m1_t>, 32>{});
+    case CUTLASS_SYS_BLOCK_SCALED_MXFP4:
+      return f(Operand<cutlass::mx_float4_t<cutlass::float_e2m1_t>, 32>{});
+    case CUTLASS_SYS_BLOCK_SCALED_MXFP6_E3M2:
+      return f(Operand<cutlass::mx_float6_t<cutlass::float_e3m2_t>, 128>{});
+    case CUTLASS_SYS_BLOCK_SCALED_MXFP6_E2M3:
+      return f(Operand<cutlass::mx_float6_t<cutlass::float_e2m3_t>, 128>{});
+    case CUTLASS_SYS_BLOCK_SCALED_MXFP8_E4M3:
+      return f(Operand<cutlass::mx_float8_t<cutlass::float_e4m3_t>, 16>{});
+    case CUTLASS_SYS_BLOCK_SCALED_MXFP8_E5M2:
+      return f(Operand<cutlass::mx_float8_t<cutlass::float_e5m2_t>, 16>{});
+    default:
+      return cutlass::Status::kErrorInvalidDataType;
+  }
+}
+
+// Calls `f` with a value of the C / D element type named by `dtype`.
+template <typename F>
+cutlass::Status dispatch_output(cutlass_sys_dtype_t dtype, F&& f) {
+  switch (dtype) {
+    case CUTLASS_SYS_DTYPE_F16:
+      return f(cutlass::half_t{});
+    case CUTLASS_SYS_DTYPE_BF16:
+      return f(cutlass::bfloat16_t{});
+    case CUTLASS_SYS_DTYPE_F32:
+      return f(float{});
+    default:
+      return cutlass::Status::kErrorInvalidDataType;
+  }
+}
+
+using TileShape = cute::Shape<cute::_256, cute::_256, cute::_256>;
+using ClusterShape = cute::Shape<cute::_2, cute::_1, cute::_1>;
+
+// A is row-major and B column-major, i.e. both K-major as the sub-byte MMAs
+// require; C and D are row-major.
+template <typename Op, typename ElementD>
+struct BlockScaledGemm {
+  using ElementAB = typename Op::type;
+  using ElementData = typename ElementAB::DataType;
+  using ElementScale = typename ElementAB::ScaleFactorType;
+  static constexpr int kAlignmentD = 128 / cutlass::sizeof_bits<ElementD>::value;
+
+  using CollectiveEpilogue = typename cutlass::epilogue::collective::CollectiveBuilder<
+      cutlass::arch::Sm100, cutlass::arch::OpClassTensorOp,
+      TileShape, ClusterShape,
+      cutlass::epilogue::collective::EpilogueTileAuto,
+      float, float,
+      ElementD, cutlass::layout::RowMajor, kAlignmentD,
+      ElementD, cutlass::layout::RowMajor, kAlignmentD,
+      cutlass::epilogue::collective::EpilogueScheduleAuto>::CollectiveOp;
+
+  using CollectiveMainloop = typename cutlass::gemm::collective::CollectiveBuilder<
+      cutlass::arch::Sm100, cutlass::arch::OpClassBlockScaledTensorOp,
+      ElementAB, cutlass::layout::RowMajor, Op::kAlignment,
+      ElementAB, cutlass::layout::ColumnMajor, Op::kAlignment,
+      float,
+      TileShape, ClusterShape,
+      cutlass::gemm::collective::StageCountAutoCarveout<static_cast<int>(
+          sizeof(typename CollectiveEpilogue::SharedStorage))>,
+      cutlass::gemm::collective::KernelScheduleAuto>::CollectiveOp;
+
+  using Kernel = cutlass::gemm::kernel::GemmUniversal<
+      cute::Shape<int, int, int, int>, CollectiveMainloop, CollectiveEpilogue, void>;
+
+  using Operator = cutlass::gemm::device::GemmUniversalAdapter<Kernel>;
+
+  using ScaleConfig = typename Kernel::CollectiveMainloop::Sm1xxBlkScaledConfig;
+};
+
+// Calls `f` with the Type<> of the BlockScaledGemm instantiation for `cfg`.
+template <typename F>
+cutlass::Status dispatch_gemm(const cutlass_sys_block_scaled_gemm_config_t& cfg, F&& f) {
+  return dispatch_block_scaled_type(cfg.element, [&](auto op) {
+    return dispatch_output(cfg.output, [&](auto output) {
+      return f(Type<BlockScaledGemm<decltype(op), decltype(output)>>{});
+    });
+  });
+}
+
+// Bytes of the scale factors of A and B in the interleaved layout the kernel
+// reads them in.
+cutlass::Status scale_factor_sizes(const cutlass_sys_block_scaled_gemm_config_t& cfg,
+                                   size_t* sfa_size,
+                                   size_t* sfb_size) {
+  return dispatch_gemm(cfg, [&](auto gemm) {
+    using Gemm = typename decltype(gemm)::type;
+    using ScaleConfig = typename Gemm::ScaleConfig;
+    auto problem = cute::make_shape(cfg.problem.m, cfg.problem.n, cfg.problem.k, 1);
+    *sfa_size = cute::size(cute::filter_zeros(ScaleConfig::tile_atom_to_shape_SFA(problem))) *
+                sizeof(typename Gemm::ElementScale);
+    *sfb_size = cute::size(cute::filter_zeros(ScaleConfig::tile_atom_to_shape_SFB(problem))) *
+                sizeof(typename Gemm::ElementScale);
+    return cutlass::Status::kSuccess;
+  });
+}
+
+cutlass::Status block_scaled_gemm(const cutlass_sys_context_t& context,
+                                  const cutlass_sys_block_scaled_gemm_config_t& cfg,
+                                  const cutlass_sys_block_scaled_gemm_operands_t* launch_ops,
+                                  void* workspace,
+                                  cudaStream_t stream,
+                                  size_t* workspace_size) {
+  // Only sm_100a carries the block-scaled tcgen05 instructions.
+  if (context.cc_major != 10 || context.cc_minor != 0) {
+    return cutlass::Status::kErrorArchMismatch;
+  }
+  return dispatch_gemm(cfg, [&](auto gemm) {
+    using Gemm = typename decltype(gemm)::type;
+    using Kernel = typename Gemm::Kernel;
+    using ScaleConfig = typename Gemm::ScaleConfig;
+    using ElementData = typename Gemm::ElementData;
+    using ElementScale = typename Gemm::ElementScale;
+    using ElementD = typename Kernel::ElementD;
+
+    cutlass_sys_block_scaled_gemm_operands_t ops =
+        launch_ops ? *launch_ops : cutlass_sys_block_scaled_gemm_operands_t{};
+    auto problem = cute::make_shape(cfg.problem.m, cfg.problem.n, cfg.problem.k, 1);
+
+    typename Gemm::Operator::Arguments args{};
+    args.mode = cutlass::gemm::GemmUniversalMode::kGemm;
+    args.problem_shape = {cfg.problem.m, cfg.problem.n, cfg.problem.k, 1};
+    args.mainloop.ptr_A = static_cast<const ElementData*>(ops.a);
+    args.mainloop.dA = leading_stride<typename Kernel::StrideA>(cfg.lda);
+    args.mainloop.ptr_B = static_cast<const ElementData*>(ops.b);
+    args.mainloop.dB = leading_stride<typename Kernel::StrideB>(cfg.ldb);
+    args.mainloop.ptr_SFA = static_cast<const ElementScale*>(ops.sfa);
+    args.mainloop.layout_SFA = ScaleConfig::tile_atom_to_shape_SFA(problem);
+    args.mainloop.ptr_SFB = static_cast<const ElementScale*>(ops.sfb);
+    args.mainloop.layout_SFB = ScaleConfig::tile_atom_to_shape_SFB(problem);
+    args.epilogue.thread.alpha = cfg.alpha;
+    args.epilogue.thread.beta = cfg.beta;
+    args.epilogue.ptr_C = static_cast<const ElementD*>(ops.c);
+    args.epilogue.dC = leading_stride<typename Kernel::StrideC>(cfg.ldc);
+    args.epilogue.ptr_D = static_cast<ElementD*>(ops.d);
+    args.epilogue.dD = leading_stride<typename Kernel::StrideD>(cfg.ldd);
+    args.hw_info.device_id = context.device;
+    args.hw_info.sm_count = context.sm_count;
+
+    return query_or_run<typename Gemm::Operator>(args, workspace, stream, workspace_size);
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_block_scaled_sfa_size(
+    const cutlass_sys_block_scaled_gemm_config_t* config) {
+  size_t sfa = 0, sfb = 0;
+  cutlass_sys::scale_factor_sizes(*config, &sfa, &sfb);
+  return sfa;
+}
+
+extern "C" size_t cutlass_sys_block_scaled_sfb_size(
+    const cutlass_sys_block_scaled_gemm_config_t* config) {
+  size_t sfa = 0, sfb = 0;
+  cutlass_sys::scale_factor_sizes(*config, &sfa, &sfb);
+  return sfb;
+}
+
+extern "C" size_t cutlass_sys_block_scaled_gemm_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_block_scaled_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::block_scaled_gemm(device.context(), *config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_block_scaled_gemm_can_implement(
+    const cutlass_sys_context_t* context, const cutlass_sys_block_scaled_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::block_scaled_gemm(device.context(), *config, nullptr,
+                                                         nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_block_scaled_gemm(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_block_scaled_gemm_config_t* config,
+    const cutlass_sys_block_scaled_gemm_operands_t* operands,
+    void* workspace,
+    cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::block_scaled_gemm(device.context(), *config, operands,
+                                                         workspace,
+                                                         static_cast<cudaStream_t>(stream),
+                                                         nullptr));
+}
+
+#endif
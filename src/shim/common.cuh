@@ -0,0 +1,475 @@
+// Helpers shared by the shim translation units. Not part of the C ABI.
+
+#pragma once
+
+#include <cuda_runtime.h>
+
+#include <type_traits>
+#include <utility>
+
+#include <cute/layout.hpp>
+#include <cutlass/arch/arch.h>
+#include <cutlass/arch/mma.h>
+#include <cutlass/cutlass.h>
+#include <cutlass/device_kernel.h>
+#include <cutlass/gemm/gemm.h>
+#include <cutlass/gemm/threadblock/threadblock_swizzle.h>
+#include <cutlass/gemm/threadblock/threadblock_swizzle_streamk.h>
+#include <cutlass/layout/matrix.h>
+#include <cutlass/matrix_coord.h>
+#include <cutlass/numeric_types.h>
+#include <cutlass/tensor_coord.h>
+
+#include "cutlass_sys.h"
+#include "cutlass_sys_archs.h"
+
+namespace cutlass_sys {
+
+// Kernel configuration of the SM80 instantiations, keyed by operand type.
+template <typename Element>
+struct Sm80Traits;
+
+template <>
+struct Sm80Traits<cutlass::half_t> {
+  using ElementC = cutlass::half_t;
+  using ElementAccumulator = float;
+  using LayoutB = cutlass::layout::RowMajor;
+  using OperatorClass = cutlass::arch::OpClassTensorOp;
+  using MathOperator = cutlass::arch::OpMultiplyAdd;
+  using ThreadblockShape = cutlass::gemm::GemmShape<128, 128, 32>;
+  using WarpShape = cutlass::gemm::GemmShape<64, 64, 32>;
+  using InstructionShape = cutlass::gemm::GemmShape<16, 8, 16>;
+  static constexpr int kStages = 3;
+  static constexpr int kAlignmentA = 8;
+  static constexpr int kAlignmentB = 8;
+  static constexpr int kAlignmentC = 8;
+};
+
+template <>
+struct Sm80Traits<cutlass::bfloat16_t> {
+  using ElementC = cutlass::bfloat16_t;
+  using ElementAccumulator = float;
+  using LayoutB = cutlass::layout::RowMajor;
+  using OperatorClass = cutlass::arch::OpClassTensorOp;
+  using MathOperator = cutlass::arch::OpMultiplyAdd;
+  using ThreadblockShape = cutlass::gemm::GemmShape<128, 128, 32>;
+  using WarpShape = cutlass::gemm::GemmShape<64, 64, 32>;
+  using InstructionShape = cutlass::gemm::GemmShape<16, 8, 16>;
+  static constexpr int kStages = 3;
+  static constexpr int kAlignmentA = 8;
+  static constexpr int kAlignmentB = 8;
+  static constexpr int kAlignmentC = 8;
+};
+
+// f32 stays on the SIMT path so results match a plain fp32 reference
+// (tensor cores would silently round operands to tf32).
+template <>
+struct Sm80Traits<float> {
+  using ElementC = float;
+  using ElementAccumulator = float;
+  using LayoutB = cutlass::layout::RowMajor;
+  using OperatorClass = cutlass::arch::OpClassSimt;
+  using MathOperator = cutlass::arch::OpMultiplyAdd;
+  using ThreadblockShape = cutlass::gemm::GemmShape<128, 128, 8>;
+  using WarpShape = cutlass::gemm::GemmShape<32, 64, 8>;
+  using InstructionShape = cutlass::gemm::GemmShape<1, 1, 1>;
+  static constexpr int kStages = 3;
+  static constexpr int kAlignmentA = 1;
+  static constexpr int kAlignmentB = 1;
+  static constexpr int kAlignmentC = 1;
+};
+
+// int8 tensor-core MMAs require the "TN" arrangement, so B is column-major.
+template <>
+struct Sm80Traits<int8_t> {
+  using ElementC = int32_t;
+  using ElementAccumulator = int32_t;
+  using LayoutB = cutlass::layout::ColumnMajor;
+  using OperatorClass = cutlass::arch::OpClassTensorOp;
+  using MathOperator = cutlass::arch::OpMultiplyAddSaturate;
+  using ThreadblockShape = cutlass::gemm::GemmShape<128, 128, 64>;
+  using WarpShape = cutlass::gemm::GemmShape<64, 64, 64>;
+  using InstructionShape = cutlass::gemm::GemmShape<16, 8, 32>;
+  static constexpr int kStages = 3;
+  static constexpr int kAlignmentA = 16;
+  static constexpr int kAlignmentB = 16;
+  static constexpr int kAlignmentC = 4;
+};
+
+// Sm80Traits for the kernels CUTLASS only provides on tensor cores (planar
+// complex, blocked-ELL and implicit-GEMM convolution), where f32 runs 3xTF32
+// MMAs: fp32 accuracy, but not bitwise fp32 results, and 128-bit alignment.
+template <typename Element>
+struct Sm80TensorOpTraits : Sm80Traits<Element> {};
+
+template <>
+struct Sm80TensorOpTraits<float> : Sm80Traits<float> {
+  using OperatorClass = cutlass::arch::OpClassTensorOp;
+  using MathOperator = cutlass::arch::OpMultiplyAddFastF32;
+  using ThreadblockShape = cutlass::gemm::GemmShape<128, 128, 16>;
+  using WarpShape = cutlass::gemm::GemmShape<64, 64, 16>;
+  using InstructionShape = cutlass::gemm::GemmShape<16, 8, 8>;
+  static constexpr int kStages = 4;
+  static constexpr int kAlignmentA = 4;
+  static constexpr int kAlignmentB = 4;
+  static constexpr int kAlignmentC = 4;
+};
+
+// Whether `cfg` asks for the accumulator and output types its element type
+// implies, the ElementAccumulator and ElementC of Sm80Traits. An output
+// scale only exists for down-converted outputs, so it counts as another type.
+inline bool default_gemm_types(const cutlass_sys_gemm_config_t& cfg) {
+  if (cfg.out_scale != 1.0f) {
+    return false;
+  }
+  switch (cfg.element) {
+    case CUTLASS_SYS_DTYPE_F16:
+    case CUTLASS_SYS_DTYPE_BF16:
+      return cfg.accumulator == CUTLASS_SYS_DTYPE_F32 && cfg.output == cfg.element;
+    case CUTLASS_SYS_DTYPE_S8:
+      return cfg.accumulator == CUTLASS_SYS_DTYPE_S32 && cfg.output == CUTLASS_SYS_DTYPE_S32;
+    default:
+      return cfg.accumulator == cfg.element && cfg.output == cfg.element;
+  }
+}
+
+// Whether `cfg` scales its f32 result and casts it down to an fp8 or int8
+// output in the epilogue (GemmTypes::is_down_converted).
+inline bool down_converted_gemm_output(const cutlass_sys_gemm_config_t& cfg) {
+  const bool f16_operands =
+      cfg.element == CUTLASS_SYS_DTYPE_F16 || cfg.element == CUTLASS_SYS_DTYPE_BF16;
+  const bool narrow_output = cfg.output == CUTLASS_SYS_DTYPE_E4M3 ||
+                             cfg.output == CUTLASS_SYS_DTYPE_E5M2 ||
+                             cfg.output == CUTLASS_SYS_DTYPE_S8;
+  return f16_operands && cfg.accumulator == CUTLASS_SYS_DTYPE_F32 && narrow_output;
+}
+
+// Whether `cfg` has the operand layouts of Sm80Traits: row-major A, and B
+// row-major or, for s8, column-major.
+inline bool default_gemm_layouts(const cutlass_sys_gemm_config_t& cfg) {
+  cutlass_sys_layout_t layout_b = cfg.element == CUTLASS_SYS_DTYPE_S8
+                                      ? CUTLASS_SYS_LAYOUT_COLUMN_MAJOR
+                                      : CUTLASS_SYS_LAYOUT_ROW_MAJOR;
+  return cfg.layout_a == CUTLASS_SYS_LAYOUT_ROW_MAJOR && cfg.layout_b == layout_b;
+}
+
+// Conversions from the C ABI coordinates to their CUTLASS counterparts.
+inline cutlass::gemm::GemmCoord to_cutlass(cutlass_sys_gemm_coord_t c) {
+  return {c.m, c.n, c.k};
+}
+
+inline cutlass::MatrixCoord to_cutlass(cutlass_sys_matrix_coord_t c) {
+  return {c.row, c.column};
+}
+
+inline cutlass::Tensor4DCoord to_cutlass(cutlass_sys_tensor4d_coord_t c) {
+  return {c.n, c.h, c.w, c.c};
+}
+
+// A CuTe stride of a CUTLASS 3.x kernel with `ld` as its leading dimension:
+// the dynamic one of the first two modes. The batch stride stays zero.
+template <typename Stride>
+Stride leading_stride(int64_t ld) {
+  using First = cute::remove_cvref_t<decltype(cute::get<0>(Stride{}))>;
+  Stride stride{};
+  if constexpr (cute::is_static<First>::value) {
+    cute::get<1>(stride) = ld;
+  } else {
+    cute::get<0>(stride) = ld;
+  }
+  return stride;
+}
+
+// SM versions the shim was compiled for (CUTLASS_CUDA_ARCH and the sm*
+// features), in ascending order.
+constexpr int32_t kCompiledArchs[] = {CUTLASS_SYS_ARCHS};
+constexpr size_t kCompiledArchCount = sizeof(kCompiledArchs) / sizeof(kCompiledArchs[0]);
+
+// Oldest arch the SM80-class kernels behind most entry points run on, and the
+// oldest any entry point runs on (the GEMM, GEMV, reduction and transform
+// kernels).
+constexpr int32_t kSm80 = 80;
+constexpr int32_t kSm75 = 75;
+
+// Whether the shim carries code of kernels needing at least `min_arch` for a
+// device of compute capability `cc` (major * 10 + minor): SASS built for an
+// arch of the same major version and no newer minor, or the PTX of the newest
+// compiled arch, which the driver JIT-compiles for any later device.
+inline bool arch_supported(int32_t cc, int32_t min_arch) {
+  if (cc < min_arch) {
+    return false;
+  }
+  for (int32_t arch : kCompiledArchs) {
+    if (arch >= min_arch && arch / 10 == cc / 10 && arch <= cc) {
+      return true;
+    }
+  }
+  int32_t newest = kCompiledArchs[kCompiledArchCount - 1];
+  return newest >= min_arch && cc >= newest;
+}
+
+// Fills `context` with the properties of the calling thread's current device.
+inline cutlass::Status current_context(cutlass_sys_context_t* context) {
+  int device, sm_count, major, minor;
+  if (cudaGetDevice(&device) != cudaSuccess ||
+      cudaDeviceGetAttribute(&sm_count, cudaDevAttrMultiProcessorCount, device) !=
+          cudaSuccess ||
+      cudaDeviceGetAttribute(&major, cudaDevAttrComputeCapabilityMajor, device) != cudaSuccess ||
+      cudaDeviceGetAttribute(&minor, cudaDevAttrComputeCapabilityMinor, device) != cudaSuccess) {
+    return cutlass::Status::kErrorInternal;
+  }
+  *context = {device, sm_count, major, minor};
+  return cutlass::Status::kSuccess;
+}
+
+// kErrorArchMismatch unless the shim carries code of kernels needing at least
+// `min_arch` for the device of `context`.
+inline cutlass::Status check_arch(const cutlass_sys_context_t& context, int32_t min_arch) {
+  return arch_supported(context.cc_major * 10 + context.cc_minor, min_arch)
+             ? cutlass::Status::kSuccess
+             : cutlass::Status::kErrorArchMismatch;
+}
+
+// Makes the device of `context` current for the lifetime of the scope and
+// restores the previous device afterwards. A null context leaves the current
+// device alone. Either way, a device the compiled kernels needing at least
+// `min_arch` can't run on fails the scope with kErrorArchMismatch, and
+// context() describes the device the entry point runs on, for kernels
+// selected by architecture.
+class DeviceScope {
+ public:
+  explicit DeviceScope(const cutlass_sys_context_t* context, int32_t min_arch = kSm80) {
+    if (context == nullptr) {
+      status_ = current_context(&context_);
+      if (status_ == cutlass::Status::kSuccess) {
+        status_ = check_arch(context_, min_arch);
+      }
+      return;
+    }
+    context_ = *context;
+    status_ = check_arch(context_, min_arch);
+    if (status_ != cutlass::Status::kSuccess) {
+      return;
+    }
+    if (cudaGetDevice(&previous_) != cudaSuccess) {
+      status_ = cutlass::Status::kErrorInternal;
+      return;
+    }
+    if (previous_ != context->device) {
+      if (cudaSetDevice(context->device) != cudaSuccess) {
+        status_ = cutlass::Status::kErrorInvalidProblem;
+        return;
+      }
+      restore_ = true;
+    }
+  }
+
+  ~DeviceScope() {
+    if (restore_) {
+      cudaSetDevice(previous_);
+    }
+  }
+
+  DeviceScope(const DeviceScope&) = delete;
+  DeviceScope& operator=(const DeviceScope&) = delete;
+
+  explicit operator bool() const { return status_ == cutlass::Status::kSuccess; }
+  cutlass::Status status() const { return status_; }
+  const cutlass_sys_context_t& context() const { return context_; }
+
+ private:
+  cutlass_sys_context_t context_{};
+  int previous_ = 0;
+  bool restore_ = false;
+  cutlass::Status status_ = cutlass::Status::kSuccess;
+};
+
+// Carries a type through a generic lambda without constructing it.
+template <typename T>
+struct Type {
+  using type = T;
+};
+
+// Calls `f` with a value of the CUTLASS element type named by `dtype`.
+template <typename F>
+cutlass::Status dispatch_dtype(cutlass_sys_dtype_t dtype, F&& f) {
+  switch (dtype) {
+    case CUTLASS_SYS_DTYPE_F16:
+      return f(cutlass::half_t{});
+    case CUTLASS_SYS_DTYPE_BF16:
+      return f(cutlass::bfloat16_t{});
+    case CUTLASS_SYS_DTYPE_F32:
+      return f(float{});
+    case CUTLASS_SYS_DTYPE_S8:
+      return f(int8_t{});
+    default:
+      return cutlass::Status::kErrorInvalidDataType;
+  }
+}
+
+// Calls `f` with a value of the matrix layout `layout` names.
+template <typename F>
+cutlass::Status dispatch_layout(cutlass_sys_layout_t layout, F&& f) {
+  switch (layout) {
+    case CUTLASS_SYS_LAYOUT_ROW_MAJOR:
+      return f(cutlass::layout::RowMajor{});
+    case CUTLASS_SYS_LAYOUT_COLUMN_MAJOR:
+      return f(cutlass::layout::ColumnMajor{});
+    default:
+      return cutlass::Status::kErrorInvalidLayout;
+  }
+}
+
+// Calls `f` with values of the layouts of `cfg`'s A and B.
+template <typename F>
+cutlass::Status dispatch_gemm_layouts(const cutlass_sys_gemm_config_t& cfg, F&& f) {
+  return dispatch_layout(cfg.layout_a, [&](auto layout_a) {
+    return dispatch_layout(cfg.layout_b, [&](auto layout_b) { return f(layout_a, layout_b); });
+  });
+}
+
+// Calls `f` with a value of the threadblock swizzle implementing `scheduler`.
+template <typename F>
+cutlass::Status dispatch_scheduler(cutlass_sys_tile_scheduler_t scheduler, F&& f) {
+  switch (scheduler) {
+    case CUTLASS_SYS_TILE_SCHEDULER_DATA_PARALLEL:
+      return f(cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>{});
+    case CUTLASS_SYS_TILE_SCHEDULER_STREAM_K:
+      return f(cutlass::gemm::threadblock::ThreadblockSwizzleStreamK{});
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+// Set by ResourceQuery while a *_resources entry point walks the dispatch of
+// a query; query_or_run then fills it from the operator it reaches.
+inline thread_local cutlass_sys_kernel_resources_t* kernel_resources = nullptr;
+
+class ResourceQuery {
+ public:
+  explicit ResourceQuery(cutlass_sys_kernel_resources_t* resources) {
+    *resources = {};
+    kernel_resources = resources;
+  }
+  ~ResourceQuery() { kernel_resources = nullptr; }
+  ResourceQuery(const ResourceQuery&) = delete;
+  ResourceQuery& operator=(const ResourceQuery&) = delete;
+};
+
+template <typename Operator, typename = void>
+struct HasGemmKernel : std::false_type {};
+
+template <typename Operator>
+struct HasGemmKernel<Operator, std::void_t<typename Operator::GemmKernel>> : std::true_type {};
+
+// CUTLASS 3.x kernels, launched through cutlass::device_kernel.
+template <typename Kernel, typename = void>
+struct IsKernel3x : std::false_type {};
+
+template <typename Kernel>
+struct IsKernel3x<Kernel, std::void_t<decltype(Kernel::SharedStorageSize)>> : std::true_type {};
+
+// 2.x kernels with a static invoke, which GemmUniversalBase launches through
+// cutlass::Kernel2 rather than cutlass::Kernel.
+template <typename Kernel, typename = void>
+struct HasInvoke : std::false_type {};
+
+template <typename Kernel>
+struct HasInvoke<Kernel, std::void_t<decltype(&Kernel::invoke)>> : std::true_type {};
+
+template <typename Operator, typename = void>
+struct HasGridShape : std::false_type {};
+
+template <typename Operator>
+struct HasGridShape<Operator,
+                    std::void_t<decltype(Operator::get_grid_shape(
+                        std::declval<typename Operator::Arguments const&>()))>>
+    : std::true_type {};
+
+// Fills `resources` with the block size, attributes, occupancy and grid of the
+// kernel `Operator` launches for `args`. kErrorNotSupported for an operator
+// whose kernel isn't known here.
+template <typename Operator>
+cutlass::Status query_resources(typename Operator::Arguments const& args,
+                                cutlass_sys_kernel_resources_t* resources) {
+  if constexpr (!HasGemmKernel<Operator>::value) {
+    (void)args;
+    (void)resources;
+    return cutlass::Status::kErrorNotSupported;
+  } else {
+    using Kernel = typename Operator::GemmKernel;
+    const void* function;
+    int threads;
+    int smem;
+    if constexpr (IsKernel3x<Kernel>::value) {
+      function = reinterpret_cast<const void*>(cutlass::device_kernel<Kernel>);
+      threads = Kernel::MaxThreadsPerBlock;
+      smem = Kernel::SharedStorageSize;
+    } else {
+      if constexpr (HasInvoke<Kernel>::value) {
+        function = reinterpret_cast<const void*>(cutlass::Kernel2<Kernel>);
+      } else {
+        function = reinterpret_cast<const void*>(cutlass::Kernel<Kernel>);
+      }
+      threads = Kernel::kThreadCount;
+      smem = static_cast<int>(sizeof(typename Kernel::SharedStorage));
+    }
+    // The occupancy calculator honours the dynamic shared memory limit the
+    // launch raises, so raise it here too.
+    if (smem >= (48 << 10) &&
+        cudaFuncSetAttribute(function, cudaFuncAttributeMaxDynamicSharedMemorySize, smem) !=
+            cudaSuccess) {
+      return cutlass::Status::kErrorInternal;
+    }
+    cudaFuncAttributes attributes;
+    int ctas = 0;
+    if (cudaFuncGetAttributes(&attributes, function) != cudaSuccess ||
+        cudaOccupancyMaxActiveBlocksPerMultiprocessor(&ctas, function, threads, smem) !=
+            cudaSuccess) {
+      return cutlass::Status::kErrorInternal;
+    }
+    dim3 grid(0, 0, 0);
+    if constexpr (HasGridShape<Operator>::value) {
+      grid = Operator::get_grid_shape(args);
+    }
+    *resources = {threads,
+                  attributes.numRegs,
+                  smem + static_cast<int32_t>(attributes.sharedSizeBytes),
+                  static_cast<int32_t>(attributes.localSizeBytes),
+                  ctas,
+                  {grid.x, grid.y, grid.z}};
+    return cutlass::Status::kSuccess;
+  }
+}
+
+// Reports the workspace size of `args` and whether the operator can implement
+// them when `workspace_size` is non-null, otherwise checks, initializes and
+// launches the operator on `stream`. A query under a ResourceQuery also
+// reports the kernel's resources.
+template <typename Operator>
+cutlass::Status query_or_run(typename Operator::Arguments const& args,
+                             void* workspace,
+                             cudaStream_t stream,
+                             size_t* workspace_size) {
+  if (workspace_size != nullptr) {
+    *workspace_size = Operator::get_workspace_size(args);
+    cutlass::Status status = Operator::can_implement(args);
+    if (status == cutlass::Status::kSuccess && kernel_resources != nullptr) {
+      status = query_resources<Operator>(args, kernel_resources);
+    }
+    return status;
+  }
+
+  Operator op;
+  cutlass::Status status = op.can_implement(args);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+  status = op.initialize(args, workspace, stream);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+  return op.run(stream);
+}
+
+}  // namespace cutlass_sys
@@ -0,0 +1,22 @@
+// Per-device context (src/context.rs).
+
+#include "common.cuh"
+
+extern "C" int cutlass_sys_context_init(int32_t device, cutlass_sys_context_t* context) {
+  cudaDeviceProp prop;
+  if (cudaGetDeviceProperties(&prop, device) != cudaSuccess) {
+    return static_cast<int>(cutlass::Status::kErrorInvalidProblem);
+  }
+  *context = {device, prop.multiProcessorCount, prop.major, prop.minor};
+  return static_cast<int>(cutlass::Status::kSuccess);
+}
+
+extern "C" size_t cutlass_sys_compiled_archs(const int32_t** archs) {
+  *archs = cutlass_sys::kCompiledArchs;
+  return cutlass_sys::kCompiledArchCount;
+}
+
+extern "C" int cutlass_sys_context_check_arch(const cutlass_sys_context_t* context) {
+  cutlass_sys::DeviceScope device(context);
+  return static_cast<int>(device.status());
+}
@@ -0,0 +1,220 @@
+// Helpers shared by the implicit-GEMM convolution translation units.
+
+#pragma once
+
+#include <cutlass/conv/conv2d_problem_size.h>
+#include <cutlass/conv/conv3d_problem_size.h>
+#include <cutlass/conv/convolution.h>
+#include <cutlass/conv/device/implicit_gemm_convolution.h>
+#include <cutlass/epilogue/thread/linear_combination.h>
+#include <cutlass/layout/tensor.h>
+#include <cutlass/tensor_ref.h>
+
+#include "common.cuh"
+#include "reduction.cuh"
+#include "verify.cuh"
+
+namespace cutlass_sys {
+
+inline cutlass::conv::Mode conv_mode(cutlass_sys_conv_mode_t mode) {
+  return mode == CUTLASS_SYS_CONV_MODE_CONVOLUTION
+             ? cutlass::conv::Mode::kConvolution
+             : cutlass::conv::Mode::kCrossCorrelation;
+}
+
+// Output extent of one spatial dimension.
+inline int conv_output_extent(int input, int filter, int pad, int stride, int dilation) {
+  return (input + 2 * pad - dilation * (filter - 1) - 1) / stride + 1;
+}
+
+// The 2-D entry points only accept configs with a unit depth dimension.
+inline bool is_conv2d(const cutlass_sys_conv_config_t& cfg) {
+  return cfg.d == 1 && cfg.t == 1 && cfg.pad_d == 0;
+}
+
+inline cutlass::conv::Conv2dProblemSize conv2d_problem(
+    const cutlass_sys_conv_config_t& cfg) {
+  int p = conv_output_extent(cfg.h, cfg.r, cfg.pad_h, cfg.stride_h, cfg.dilation_h);
+  int q = conv_output_extent(cfg.w, cfg.s, cfg.pad_w, cfg.stride_w, cfg.dilation_w);
+  return cutlass::conv::Conv2dProblemSize(
+      cfg.n, cfg.h, cfg.w, cfg.c, cfg.k, cfg.r, cfg.s, p, q,
+      cfg.pad_h, cfg.pad_w, cfg.stride_h, cfg.stride_w,
+      cfg.dilation_h, cfg.dilation_w, conv_mode(cfg.mode), cfg.split_k_slices,
+      cfg.groups);
+}
+
+inline cutlass::conv::Conv3dProblemSize conv3d_problem(
+    const cutlass_sys_conv_config_t& cfg) {
+  int z = conv_output_extent(cfg.d, cfg.t, cfg.pad_d, cfg.stride_d, cfg.dilation_d);
+  int p = conv_output_extent(cfg.h, cfg.r, cfg.pad_h, cfg.stride_h, cfg.dilation_h);
+  int q = conv_output_extent(cfg.w, cfg.s, cfg.pad_w, cfg.stride_w, cfg.dilation_w);
+  return cutlass::conv::Conv3dProblemSize(
+      cfg.n, cfg.d, cfg.h, cfg.w, cfg.c, cfg.k, cfg.t, cfg.r, cfg.s, z, p, q,
+      cfg.pad_d, cfg.pad_h, cfg.pad_w, cfg.stride_d, cfg.stride_h, cfg.stride_w,
+      cfg.dilation_d, cfg.dilation_h, cfg.dilation_w, conv_mode(cfg.mode),
+      cfg.split_k_slices);
+}
+
+// A convolution in the shape of the entry points' internals.
+using ConvRun = cutlass::Status (*)(const cutlass_sys_conv_config_t&,
+                                    const cutlass_sys_conv_operands_t*,
+                                    void*,
+                                    cudaStream_t,
+                                    size_t*);
+
+// `run`, checking D (the packed output of direction `kind`) against the
+// device reference of rank `kRank` for a verified config.
+template <int kRank>
+cutlass::Status run_conv_verified(cutlass::conv::Operator kind,
+                                  ConvRun run,
+                                  const cutlass_sys_conv_config_t& cfg,
+                                  const cutlass_sys_conv_operands_t* ops,
+                                  void* workspace,
+                                  cudaStream_t stream,
+                                  size_t* workspace_size) {
+  int64_t count = 0;
+  if (cfg.verify) {
+    if constexpr (kRank == 2) {
+      count = cutlass::conv::implicit_gemm_tensor_c_extent(kind, conv2d_problem(cfg)).product();
+    } else {
+      count = cutlass::conv::implicit_gemm_tensor_c_extent(kind, conv3d_problem(cfg)).product();
+    }
+  }
+  VerifyOutput out{cfg.element, ops ? ops->d : nullptr, 1, count, count};
+  return run_verified(
+      cfg.verify, out, workspace, stream, workspace_size,
+      [&](void* run_workspace, size_t* run_workspace_size) {
+        return run(cfg, ops, run_workspace, stream, run_workspace_size);
+      },
+      [&](void* reference) {
+#if defined(CUTLASS_SYS_REFERENCE)
+        return reference_conv_device(kind, kRank, cfg, *ops, reference, stream);
+#else
+        (void)reference;
+        return cutlass::Status::kErrorNotSupported;
+#endif
+      });
+}
+
+// Linear-combination epilogue writing `ElementOutput` (the GEMM output type
+// by default; the accumulator type for parallel split-K partials).
+template <typename Element,
+          typename ElementOutput = typename Sm80TensorOpTraits<Element>::ElementC>
+using ConvEpilogue = cutlass::epilogue::thread::LinearCombination<
+    ElementOutput, 128 / cutlass::sizeof_bits<ElementOutput>::value,
+    typename Sm80TensorOpTraits<Element>::ElementAccumulator, float>;
+
+// Builds the arguments of the device-level convolution `Conv` from packed
+// tensors and reports the workspace size or launches it.
+template <typename Conv, typename ProblemSize>
+cutlass::Status run_conv(const ProblemSize& problem,
+                         const cutlass_sys_conv_operands_t* ops,
+                         float alpha,
+                         float beta,
+                         cutlass::conv::SplitKMode split_k_mode,
+                         void* workspace,
+                         cudaStream_t stream,
+                         size_t* workspace_size) {
+  using ElementA = typename Conv::ElementA;
+  using ElementB = typename Conv::ElementB;
+  using ElementC = typename Conv::ElementC;
+  using LayoutA = typename Conv::LayoutA;
+  using LayoutB = typename Conv::LayoutB;
+  using LayoutC = typename Conv::LayoutC;
+  constexpr auto kind = Conv::kConvolutionalOperator;
+
+  cutlass_sys_conv_operands_t o = ops ? *ops : cutlass_sys_conv_operands_t{};
+
+  typename Conv::Arguments args{
+      problem,
+      {static_cast<ElementA*>(const_cast<void*>(o.a)),
+       LayoutA::packed(cutlass::conv::implicit_gemm_tensor_a_extent(kind, problem))},
+      {static_cast<ElementB*>(const_cast<void*>(o.b)),
+       LayoutB::packed(cutlass::conv::implicit_gemm_tensor_b_extent(kind, problem))},
+      {static_cast<ElementC*>(const_cast<void*>(o.c)),
+       LayoutC::packed(cutlass::conv::implicit_gemm_tensor_c_extent(kind, problem))},
+      {static_cast<ElementC*>(o.d),
+       LayoutC::packed(cutlass::conv::implicit_gemm_tensor_c_extent(kind, problem))},
+      {alpha, beta},
+      split_k_mode,
+  };
+
+  return query_or_run<Conv>(args, workspace, stream, workspace_size);
+}
+
+// Parallel split-K: `Conv` (whose output type is the accumulator type) writes
+// one partial tensor per K slice into the workspace, then ReduceSplitK sums
+// them through the linear-combination epilogue into D as `ElementC`.
+template <typename Conv, typename ElementC, typename ProblemSize>
+cutlass::Status run_conv_parallel(const ProblemSize& problem,
+                                  const cutlass_sys_conv_operands_t* ops,
+                                  float alpha,
+                                  float beta,
+                                  void* workspace,
+                                  cudaStream_t stream,
+                                  size_t* workspace_size) {
+  using ElementA = typename Conv::ElementA;
+  using ElementB = typename Conv::ElementB;
+  using ElementAccumulator = typename Conv::ElementC;
+  using LayoutA = typename Conv::LayoutA;
+  using LayoutB = typename Conv::LayoutB;
+  using LayoutC = typename Conv::LayoutC;
+  constexpr auto kind = Conv::kConvolutionalOperator;
+
+  using Reduction = SplitKReduction<ElementC, ElementAccumulator>;
+  using StrideIndex = typename Reduction::StrideIndex;
+
+  cutlass_sys_conv_operands_t o = ops ? *ops : cutlass_sys_conv_operands_t{};
+  LayoutC layout_c =
+      LayoutC::packed(cutlass::conv::implicit_gemm_tensor_c_extent(kind, problem));
+
+  typename Conv::Arguments args{
+      problem,
+      {static_cast<ElementA*>(const_cast<void*>(o.a)),
+       LayoutA::packed(cutlass::conv::implicit_gemm_tensor_a_extent(kind, problem))},
+      {static_cast<ElementB*>(const_cast<void*>(o.b)),
+       LayoutB::packed(cutlass::conv::implicit_gemm_tensor_b_extent(kind, problem))},
+      {nullptr, layout_c},
+      {static_cast<ElementAccumulator*>(workspace), layout_c},
+      {1.0f, 0.0f},
+      cutlass::conv::SplitKMode::kParallel,
+  };
+
+  if (workspace_size != nullptr) {
+    *workspace_size = Conv::get_workspace_size(args);
+    return Conv::can_implement(args);
+  }
+
+  Conv conv;
+  cutlass::Status status = conv.can_implement(args);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+  status = conv.initialize(args, workspace, stream);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+  status = conv.run(stream);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+
+  StrideIndex stride(layout_c.stride()[Conv::UnderlyingKernel::kTensorCStrideIdx]);
+  typename Reduction::Arguments reduction_args(
+      cutlass::conv::implicit_gemm_problem_size(kind, problem).mn(),
+      problem.split_k_slices,
+      cutlass::conv::implicit_gemm_tensor_c_size(kind, problem),
+      {static_cast<ElementAccumulator*>(workspace), stride},
+      {static_cast<ElementC*>(o.d), stride},
+      {static_cast<ElementC*>(const_cast<void*>(o.c)), stride},
+      {alpha, beta});
+
+  Reduction reduction;
+  status = reduction.initialize(reduction_args, nullptr, stream);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+  return reduction.run(stream);
+}
+
+}  // namespace cutlass_sys
@@ -0,0 +1,389 @@
+// 2-D implicit-GEMM convolution entry points (src/conv.rs).
+
+#include <type_traits>
+
+#include <cutlass/conv/kernel/default_conv2d_dgrad.h>
+#include <cutlass/conv/kernel/default_conv2d_fprop.h>
+#include <cutlass/conv/kernel/default_conv2d_group_fprop.h>
+#include <cutlass/conv/kernel/default_conv2d_wgrad.h>
+#include <cutlass/conv/kernel/default_depthwise_fprop.h>
+#include <cutlass/conv/threadblock/threadblock_swizzle.h>
+
+#include "conv.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+template <typename Element>
+using Conv2dFprop = cutlass::conv::device::ImplicitGemmConvolution<
+    typename cutlass::conv::kernel::DefaultConv2dFprop<
+        Element, cutlass::layout::TensorNHWC,
+        Element, cutlass::layout::TensorNHWC,
+        typename Sm80TensorOpTraits<Element>::ElementC, cutlass::layout::TensorNHWC,
+        typename Sm80TensorOpTraits<Element>::ElementAccumulator,
+        typename Sm80TensorOpTraits<Element>::OperatorClass, cutlass::arch::Sm80,
+        typename Sm80TensorOpTraits<Element>::ThreadblockShape,
+        typename Sm80TensorOpTraits<Element>::WarpShape,
+        typename Sm80TensorOpTraits<Element>::InstructionShape,
+        ConvEpilogue<Element>,
+        cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+        Sm80TensorOpTraits<Element>::kStages,
+        typename Sm80TensorOpTraits<Element>::MathOperator,
+        cutlass::conv::IteratorAlgorithm::kOptimized,
+        cutlass::conv::StrideSupport::kStrided,
+        Sm80TensorOpTraits<Element>::kAlignmentA,
+        Sm80TensorOpTraits<Element>::kAlignmentB>::Kernel>;
+
+// Unit-stride dgrad is a plain implicit GEMM; strided dgrad needs its own
+// iterators and swizzle to skip the zeros a strided forward pass implies.
+template <typename Element, cutlass::conv::StrideSupport kStrideSupport,
+          typename Swizzle>
+using Conv2dDgrad = cutlass::conv::device::ImplicitGemmConvolution<
+    typename cutlass::conv::kernel::DefaultConv2dDgrad<
+        Element, cutlass::layout::TensorNHWC,
+        Element, cutlass::layout::TensorNHWC,
+        typename Sm80TensorOpTraits<Element>::ElementC, cutlass::layout::TensorNHWC,
+        typename Sm80TensorOpTraits<Element>::ElementAccumulator,
+        typename Sm80TensorOpTraits<Element>::OperatorClass, cutlass::arch::Sm80,
+        typename Sm80TensorOpTraits<Element>::ThreadblockShape,
+        typename Sm80TensorOpTraits<Element>::WarpShape,
+        typename Sm80TensorOpTraits<Element>::InstructionShape,
+        ConvEpilogue<Element>,
+        Swizzle,
+        Sm80TensorOpTraits<Element>::kStages,
+        typename Sm80TensorOpTraits<Element>::MathOperator,
+        cutlass::conv::IteratorAlgorithm::kOptimized,
+        kStrideSupport,
+        Sm80TensorOpTraits<Element>::kAlignmentA,
+        Sm80TensorOpTraits<Element>::kAlignmentB>::Kernel>;
+
+template <typename Element>
+using Conv2dDgradUnity = Conv2dDgrad<
+    Element, cutlass::conv::StrideSupport::kUnity,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>>;
+
+template <typename Element>
+using Conv2dDgradStrided = Conv2dDgrad<
+    Element, cutlass::conv::StrideSupport::kStrided,
+    cutlass::conv::threadblock::StridedDgradIdentityThreadblockSwizzle<1>>;
+
+template <typename Element,
+          typename ElementOutput = typename Sm80TensorOpTraits<Element>::ElementC>
+using Conv2dWgrad = cutlass::conv::device::ImplicitGemmConvolution<
+    typename cutlass::conv::kernel::DefaultConv2dWgrad<
+        Element, cutlass::layout::TensorNHWC,
+        Element, cutlass::layout::TensorNHWC,
+        ElementOutput, cutlass::layout::TensorNHWC,
+        typename Sm80TensorOpTraits<Element>::ElementAccumulator,
+        typename Sm80TensorOpTraits<Element>::OperatorClass, cutlass::arch::Sm80,
+        typename Sm80TensorOpTraits<Element>::ThreadblockShape,
+        typename Sm80TensorOpTraits<Element>::WarpShape,
+        typename Sm80TensorOpTraits<Element>::InstructionShape,
+        ConvEpilogue<Element, ElementOutput>,
+        cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+        Sm80TensorOpTraits<Element>::kStages,
+        typename Sm80TensorOpTraits<Element>::MathOperator,
+        cutlass::conv::IteratorAlgorithm::kOptimized,
+        cutlass::conv::StrideSupport::kStrided,
+        Sm80TensorOpTraits<Element>::kAlignmentA,
+        Sm80TensorOpTraits<Element>::kAlignmentB>::Kernel>;
+
+// Grouped fprop either keeps each threadblock within one group (when a group's
+// K is a multiple of the tile N) or packs several small groups into one tile.
+template <typename Element, cutlass::conv::GroupMode kGroupMode>
+using Conv2dGroupFprop = cutlass::conv::device::ImplicitGemmConvolution<
+    typename cutlass::conv::kernel::DefaultConv2dGroupFprop<
+        Element, cutlass::layout::TensorNHWC,
+        Element, cutlass::layout::TensorNHWC,
+        typename Sm80TensorOpTraits<Element>::ElementC, cutlass::layout::TensorNHWC,
+        typename Sm80TensorOpTraits<Element>::ElementAccumulator,
+        typename Sm80TensorOpTraits<Element>::OperatorClass, cutlass::arch::Sm80,
+        typename Sm80TensorOpTraits<Element>::ThreadblockShape,
+        typename Sm80TensorOpTraits<Element>::WarpShape,
+        typename Sm80TensorOpTraits<Element>::InstructionShape,
+        ConvEpilogue<Element>,
+        cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+        Sm80TensorOpTraits<Element>::kStages,
+        typename Sm80TensorOpTraits<Element>::MathOperator,
+        kGroupMode,
+        cutlass::conv::IteratorAlgorithm::kAnalytic,
+        cutlass::conv::StrideSupport::kStrided,
+        Sm80TensorOpTraits<Element>::kAlignmentA,
+        Sm80TensorOpTraits<Element>::kAlignmentB>::Kernel>;
+
+// Depthwise convolutions have a GEMM-K of R * S, far too small for tensor
+// cores, so they run on SIMT cores with a scalar epilogue.
+template <typename Element>
+using Conv2dDepthwiseFprop = cutlass::conv::device::ImplicitGemmConvolution<
+    typename cutlass::conv::kernel::DefaultDepthwiseFprop<
+        Element, cutlass::layout::TensorNHWC,
+        Element, cutlass::layout::TensorNHWC,
+        Element, cutlass::layout::TensorNHWC,
+        float,
+        cutlass::arch::OpClassSimt, cutlass::arch::Sm80,
+        cutlass::gemm::GemmShape<64, 64, 8>,
+        cutlass::gemm::GemmShape<32, 32, 8>,
+        cutlass::gemm::GemmShape<1, 1, 1>,
+        cutlass::epilogue::thread::LinearCombination<Element, 1, float, float>,
+        cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+        2,
+        cutlass::arch::OpMultiplyAdd,
+        cutlass::conv::IteratorAlgorithm::kAnalytic,
+        cutlass::conv::StrideSupport::kStrided>::Kernel>;
+
+// groups == C == K is depthwise; any other group count is a grouped GEMM over
+// C / groups input and K / groups output channels per group.
+template <typename Element>
+cutlass::Status grouped_fprop(const cutlass_sys_conv_config_t& cfg,
+                              const cutlass_sys_conv_operands_t* ops,
+                              void* workspace,
+                              cudaStream_t stream,
+                              size_t* workspace_size) {
+  if (cfg.c % cfg.groups != 0 || cfg.k % cfg.groups != 0) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  auto problem = conv2d_problem(cfg);
+  if (cfg.groups == cfg.c && cfg.groups == cfg.k) {
+    return run_conv<Conv2dDepthwiseFprop<Element>>(
+        problem, ops, cfg.alpha, cfg.beta, cutlass::conv::SplitKMode::kSerial,
+        workspace, stream, workspace_size);
+  }
+  constexpr int kTileN = Sm80TensorOpTraits<Element>::ThreadblockShape::kN;
+  if ((cfg.k / cfg.groups) % kTileN == 0) {
+    return run_conv<Conv2dGroupFprop<Element, cutlass::conv::GroupMode::kSingleGroup>>(
+        problem, ops, cfg.alpha, cfg.beta, cutlass::conv::SplitKMode::kSerial,
+        workspace, stream, workspace_size);
+  }
+  return run_conv<Conv2dGroupFprop<Element, cutlass::conv::GroupMode::kMultipleGroup>>(
+      problem, ops, cfg.alpha, cfg.beta, cutlass::conv::SplitKMode::kSerial,
+      workspace, stream, workspace_size);
+}
+
+cutlass::Status run_fprop(const cutlass_sys_conv_config_t& cfg,
+                          const cutlass_sys_conv_operands_t* ops,
+                          void* workspace,
+                          cudaStream_t stream,
+                          size_t* workspace_size) {
+  if (!is_conv2d(cfg)) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  if (cfg.split_k_mode != CUTLASS_SYS_SPLIT_K_SERIAL) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  if (cfg.groups < 1) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if (cfg.groups == 1) {
+      return run_conv<Conv2dFprop<Element>>(
+          conv2d_problem(cfg), ops, cfg.alpha, cfg.beta,
+          cutlass::conv::SplitKMode::kSerial, workspace, stream, workspace_size);
+    }
+    if constexpr (std::is_same_v<Element, int8_t>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      return grouped_fprop<Element>(cfg, ops, workspace, stream, workspace_size);
+    }
+  });
+}
+
+// Gradients are only instantiated for the floating-point types.
+cutlass::Status run_dgrad(const cutlass_sys_conv_config_t& cfg,
+                          const cutlass_sys_conv_operands_t* ops,
+                          void* workspace,
+                          cudaStream_t stream,
+                          size_t* workspace_size) {
+  if (!is_conv2d(cfg)) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  if (cfg.split_k_mode != CUTLASS_SYS_SPLIT_K_SERIAL || cfg.groups != 1) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (std::is_same_v<Element, int8_t>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      auto problem = conv2d_problem(cfg);
+      if (cfg.stride_h == 1 && cfg.stride_w == 1) {
+        return run_conv<Conv2dDgradUnity<Element>>(
+            problem, ops, cfg.alpha, cfg.beta, cutlass::conv::SplitKMode::kSerial,
+            workspace, stream, workspace_size);
+      }
+      return run_conv<Conv2dDgradStrided<Element>>(
+          problem, ops, cfg.alpha, cfg.beta, cutlass::conv::SplitKMode::kSerial,
+          workspace, stream, workspace_size);
+    }
+  });
+}
+
+// The GEMM-K dimension of wgrad is N * P * Q, which for large batches dwarfs
+// the K x (R * S * C) output, so split-K is usually needed to fill the device.
+cutlass::Status run_wgrad(const cutlass_sys_conv_config_t& cfg,
+                          const cutlass_sys_conv_operands_t* ops,
+                          void* workspace,
+                          cudaStream_t stream,
+                          size_t* workspace_size) {
+  if (!is_conv2d(cfg)) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  if (cfg.groups != 1) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (std::is_same_v<Element, int8_t>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      using ElementAccumulator = typename Sm80TensorOpTraits<Element>::ElementAccumulator;
+      using ElementC = typename Sm80TensorOpTraits<Element>::ElementC;
+      auto problem = conv2d_problem(cfg);
+      switch (cfg.split_k_mode) {
+        case CUTLASS_SYS_SPLIT_K_SERIAL:
+          return run_conv<Conv2dWgrad<Element>>(
+              problem, ops, cfg.alpha, cfg.beta, cutlass::conv::SplitKMode::kSerial,
+              workspace, stream, workspace_size);
+        case CUTLASS_SYS_SPLIT_K_PARALLEL:
+          return run_conv_parallel<Conv2dWgrad<Element, ElementAccumulator>, ElementC>(
+              problem, ops, cfg.alpha, cfg.beta, workspace, stream, workspace_size);
+        default:
+          return cutlass::Status::kErrorNotSupported;
+      }
+    }
+  });
+}
+
+// The entry points, checking D against the device reference for a verified
+// config.
+cutlass::Status fprop(const cutlass_sys_conv_config_t& cfg,
+                      const cutlass_sys_conv_operands_t* ops,
+                      void* workspace,
+                      cudaStream_t stream,
+                      size_t* workspace_size) {
+  return run_conv_verified<2>(cutlass::conv::Operator::kFprop, run_fprop, cfg, ops, workspace,
+                                  stream, workspace_size);
+}
+
+cutlass::Status dgrad(const cutlass_sys_conv_config_t& cfg,
+                      const cutlass_sys_conv_operands_t* ops,
+                      void* workspace,
+                      cudaStream_t stream,
+                      size_t* workspace_size) {
+  return run_conv_verified<2>(cutlass::conv::Operator::kDgrad, run_dgrad, cfg, ops, workspace,
+                                  stream, workspace_size);
+}
+
+cutlass::Status wgrad(const cutlass_sys_conv_config_t& cfg,
+                      const cutlass_sys_conv_operands_t* ops,
+                      void* workspace,
+                      cudaStream_t stream,
+                      size_t* workspace_size) {
+  return run_conv_verified<2>(cutlass::conv::Operator::kWgrad, run_wgrad, cfg, ops, workspace,
+                                  stream, workspace_size);
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_conv2d_fprop_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::fprop(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_conv2d_fprop_can_implement(const cutlass_sys_context_t* context,
+                                                      const cutlass_sys_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::fprop(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_conv2d_fprop(const cutlass_sys_context_t* context,
+                                        const cutlass_sys_conv_config_t* config,
+                                        const cutlass_sys_conv_operands_t* operands,
+                                        void* workspace,
+                                        cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::fprop(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
+
+extern "C" size_t cutlass_sys_conv2d_dgrad_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::dgrad(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_conv2d_dgrad_can_implement(const cutlass_sys_context_t* context,
+                                                      const cutlass_sys_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::dgrad(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_conv2d_dgrad(const cutlass_sys_context_t* context,
+                                        const cutlass_sys_conv_config_t* config,
+                                        const cutlass_sys_conv_operands_t* operands,
+                                        void* workspace,
+                                        cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::dgrad(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
+
+extern "C" size_t cutlass_sys_conv2d_wgrad_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::wgrad(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_conv2d_wgrad_can_implement(const cutlass_sys_context_t* context,
+                                                      const cutlass_sys_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::wgrad(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_conv2d_wgrad(const cutlass_sys_context_t* context,
+                                        const cutlass_sys_conv_config_t* config,
+                                        const cutlass_sys_conv_operands_t* operands,
+                                        void* workspace,
+                                        cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::wgrad(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
@@ -0,0 +1,290 @@
+// 3-D implicit-GEMM convolution entry points (src/conv.rs).
+
+#include <type_traits>
+
+#include <cutlass/conv/kernel/default_conv3d_dgrad.h>
+#include <cutlass/conv/kernel/default_conv3d_fprop.h>
+#include <cutlass/conv/kernel/default_conv3d_wgrad.h>
+
+#include "conv.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+template <typename Element>
+using Conv3dFprop = cutlass::conv::device::ImplicitGemmConvolution<
+    typename cutlass::conv::kernel::DefaultConv3dFprop<
+        Element, cutlass::layout::TensorNDHWC,
+        Element, cutlass::layout::TensorNDHWC,
+        typename Sm80TensorOpTraits<Element>::ElementC, cutlass::layout::TensorNDHWC,
+        typename Sm80TensorOpTraits<Element>::ElementAccumulator,
+        typename Sm80TensorOpTraits<Element>::OperatorClass, cutlass::arch::Sm80,
+        typename Sm80TensorOpTraits<Element>::ThreadblockShape,
+        typename Sm80TensorOpTraits<Element>::WarpShape,
+        typename Sm80TensorOpTraits<Element>::InstructionShape,
+        ConvEpilogue<Element>,
+        cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+        Sm80TensorOpTraits<Element>::kStages,
+        typename Sm80TensorOpTraits<Element>::MathOperator,
+        cutlass::conv::IteratorAlgorithm::kOptimized>::Kernel>;
+
+// The optimized 3-D dgrad iterators only handle unit strides; strided
+// problems fall back to the analytic iterators.
+template <typename Element, cutlass::conv::IteratorAlgorithm kAlgorithm,
+          cutlass::conv::StrideSupport kStrideSupport>
+using Conv3dDgrad = cutlass::conv::device::ImplicitGemmConvolution<
+    typename cutlass::conv::kernel::DefaultConv3dDgrad<
+        Element, cutlass::layout::TensorNDHWC,
+        Element, cutlass::layout::TensorNDHWC,
+        typename Sm80TensorOpTraits<Element>::ElementC, cutlass::layout::TensorNDHWC,
+        typename Sm80TensorOpTraits<Element>::ElementAccumulator,
+        typename Sm80TensorOpTraits<Element>::OperatorClass, cutlass::arch::Sm80,
+        typename Sm80TensorOpTraits<Element>::ThreadblockShape,
+        typename Sm80TensorOpTraits<Element>::WarpShape,
+        typename Sm80TensorOpTraits<Element>::InstructionShape,
+        ConvEpilogue<Element>,
+        cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+        Sm80TensorOpTraits<Element>::kStages,
+        typename Sm80TensorOpTraits<Element>::MathOperator,
+        kAlgorithm,
+        kStrideSupport>::Kernel>;
+
+template <typename Element>
+using Conv3dDgradUnity = Conv3dDgrad<
+    Element, cutlass::conv::IteratorAlgorithm::kOptimized,
+    cutlass::conv::StrideSupport::kUnity>;
+
+template <typename Element>
+using Conv3dDgradStrided = Conv3dDgrad<
+    Element, cutlass::conv::IteratorAlgorithm::kAnalytic,
+    cutlass::conv::StrideSupport::kStrided>;
+
+template <typename Element,
+          typename ElementOutput = typename Sm80TensorOpTraits<Element>::ElementC>
+using Conv3dWgrad = cutlass::conv::device::ImplicitGemmConvolution<
+    typename cutlass::conv::kernel::DefaultConv3dWgrad<
+        Element, cutlass::layout::TensorNDHWC,
+        Element, cutlass::layout::TensorNDHWC,
+        ElementOutput, cutlass::layout::TensorNDHWC,
+        typename Sm80TensorOpTraits<Element>::ElementAccumulator,
+        typename Sm80TensorOpTraits<Element>::OperatorClass, cutlass::arch::Sm80,
+        typename Sm80TensorOpTraits<Element>::ThreadblockShape,
+        typename Sm80TensorOpTraits<Element>::WarpShape,
+        typename Sm80TensorOpTraits<Element>::InstructionShape,
+        ConvEpilogue<Element, ElementOutput>,
+        cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+        Sm80TensorOpTraits<Element>::kStages,
+        typename Sm80TensorOpTraits<Element>::MathOperator,
+        cutlass::conv::IteratorAlgorithm::kOptimized>::Kernel>;
+
+// 3-D convolutions are only instantiated for the floating-point types.
+cutlass::Status run_fprop(const cutlass_sys_conv_config_t& cfg,
+                          const cutlass_sys_conv_operands_t* ops,
+                          void* workspace,
+                          cudaStream_t stream,
+                          size_t* workspace_size) {
+  if (cfg.split_k_mode != CUTLASS_SYS_SPLIT_K_SERIAL || cfg.groups != 1) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (std::is_same_v<Element, int8_t>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      return run_conv<Conv3dFprop<Element>>(
+          conv3d_problem(cfg), ops, cfg.alpha, cfg.beta,
+          cutlass::conv::SplitKMode::kSerial, workspace, stream, workspace_size);
+    }
+  });
+}
+
+cutlass::Status run_dgrad(const cutlass_sys_conv_config_t& cfg,
+                          const cutlass_sys_conv_operands_t* ops,
+                          void* workspace,
+                          cudaStream_t stream,
+                          size_t* workspace_size) {
+  if (cfg.split_k_mode != CUTLASS_SYS_SPLIT_K_SERIAL || cfg.groups != 1) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (std::is_same_v<Element, int8_t>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      auto problem = conv3d_problem(cfg);
+      if (cfg.stride_d == 1 && cfg.stride_h == 1 && cfg.stride_w == 1) {
+        return run_conv<Conv3dDgradUnity<Element>>(
+            problem, ops, cfg.alpha, cfg.beta, cutlass::conv::SplitKMode::kSerial,
+            workspace, stream, workspace_size);
+      }
+      return run_conv<Conv3dDgradStrided<Element>>(
+          problem, ops, cfg.alpha, cfg.beta, cutlass::conv::SplitKMode::kSerial,
+          workspace, stream, workspace_size);
+    }
+  });
+}
+
+cutlass::Status run_wgrad(const cutlass_sys_conv_config_t& cfg,
+                          const cutlass_sys_conv_operands_t* ops,
+                          void* workspace,
+                          cudaStream_t stream,
+                          size_t* workspace_size) {
+  if (cfg.groups != 1) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (std::is_same_v<Element, int8_t>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      using ElementAccumulator = typename Sm80TensorOpTraits<Element>::ElementAccumulator;
+      using ElementC = typename Sm80TensorOpTraits<Element>::ElementC;
+      auto problem = conv3d_problem(cfg);
+      switch (cfg.split_k_mode) {
+        case CUTLASS_SYS_SPLIT_K_SERIAL:
+          return run_conv<Conv3dWgrad<Element>>(
+              problem, ops, cfg.alpha, cfg.beta, cutlass::conv::SplitKMode::kSerial,
+              workspace, stream, workspace_size);
+        case CUTLASS_SYS_SPLIT_K_PARALLEL:
+          return run_conv_parallel<Conv3dWgrad<Element, ElementAccumulator>, ElementC>(
+              problem, ops, cfg.alpha, cfg.beta, workspace, stream, workspace_size);
+        default:
+          return cutlass::Status::kErrorNotSupported;
+      }
+    }
+  });
+}
+
+// The entry points, checking D against the device reference for a verified
+// config.
+cutlass::Status fprop(const cutlass_sys_conv_config_t& cfg,
+                      const cutlass_sys_conv_operands_t* ops,
+                      void* workspace,
+                      cudaStream_t stream,
+                      size_t* workspace_size) {
+  return run_conv_verified<3>(cutlass::conv::Operator::kFprop, run_fprop, cfg, ops, workspace,
+                                  stream, workspace_size);
+}
+
+cutlass::Status dgrad(const cutlass_sys_conv_config_t& cfg,
+                      const cutlass_sys_conv_operands_t* ops,
+                      void* workspace,
+                      cudaStream_t stream,
+                      size_t* workspace_size) {
+  return run_conv_verified<3>(cutlass::conv::Operator::kDgrad, run_dgrad, cfg, ops, workspace,
+                                  stream, workspace_size);
+}
+
+cutlass::Status wgrad(const cutlass_sys_conv_config_t& cfg,
+                      const cutlass_sys_conv_operands_t* ops,
+                      void* workspace,
+                      cudaStream_t stream,
+                      size_t* workspace_size) {
+  return run_conv_verified<3>(cutlass::conv::Operator::kWgrad, run_wgrad, cfg, ops, workspace,
+                                  stream, workspace_size);
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_conv3d_fprop_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::fprop(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_conv3d_fprop_can_implement(const cutlass_sys_context_t* context,
+                                                      const cutlass_sys_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::fprop(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_conv3d_fprop(const cutlass_sys_context_t* context,
+                                        const cutlass_sys_conv_config_t* config,
+                                        const cutlass_sys_conv_operands_t* operands,
+                                        void* workspace,
+                                        cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::fprop(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
+
+extern "C" size_t cutlass_sys_conv3d_dgrad_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::dgrad(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_conv3d_dgrad_can_implement(const cutlass_sys_context_t* context,
+                                                      const cutlass_sys_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::dgrad(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_conv3d_dgrad(const cutlass_sys_context_t* context,
+                                        const cutlass_sys_conv_config_t* config,
+                                        const cutlass_sys_conv_operands_t* operands,
+                                        void* workspace,
+                                        cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::dgrad(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
+
+extern "C" size_t cutlass_sys_conv3d_wgrad_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::wgrad(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_conv3d_wgrad_can_implement(const cutlass_sys_context_t* context,
+                                                      const cutlass_sys_conv_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::wgrad(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_conv3d_wgrad(const cutlass_sys_context_t* context,
+                                        const cutlass_sys_conv_config_t* config,
+                                        const cutlass_sys_conv_operands_t* operands,
+                                        void* workspace,
+                                        cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::wgrad(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
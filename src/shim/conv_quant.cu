@@ -0,0 +1,174 @@
+// Quantized int8 convolution entry points (src/conv_quant.rs).
+//
+// An int8 fprop whose epilogue is a visitor tree over the i32 accumulators:
+// a per-channel fused multiply-add, a clamp, and a store that rounds to
+// int8. GEMM-M is N * P * Q and GEMM-N is K, so the output channels run
+// along the tile's columns like the columns of a GEMM.
+
+#include <cutlass/conv/kernel/default_conv2d_fprop_with_visitor.h>
+
+#include "conv.cuh"
+#include "evt.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+using Traits = Sm80Traits<int8_t>;
+
+// The int8 output is written 128 bits at a time.
+constexpr int kAlignmentD = 16;
+
+using ThreadMap = cutlass::epilogue::threadblock::OutputTileThreadLayout<
+    Traits::ThreadblockShape, Traits::WarpShape, int8_t, kAlignmentD,
+    evt::kEpilogueStages>;
+
+// Length-K f32 vector, one value per output channel.
+using ChannelVector = cutlass::epilogue::threadblock::VisitorRowBroadcast<
+    ThreadMap, float, evt::RowStride>;
+
+using Store = cutlass::epilogue::threadblock::VisitorAuxStore<
+    ThreadMap, int8_t, cutlass::FloatRoundStyle::round_to_nearest, evt::MatrixStride>;
+
+template <class T>
+using Minimum = cutlass::minimum<T>;
+
+// min(max(scale * acc + bias, clamp_min), clamp_max), rounded into D.
+using Callbacks = evt::Sm80EVT<
+    Store,
+    evt::Sm80EVT<
+        evt::Compute<Minimum>,
+        evt::Sm80EVT<evt::Compute<evt::Maximum>,
+                     evt::Sm80EVT<evt::Compute<cutlass::multiply_add>, ChannelVector,
+                                  evt::Accum, ChannelVector>,
+                     evt::Scalar>,
+        evt::Scalar>>;
+
+using QuantConv2dFprop = cutlass::conv::device::ImplicitGemmConvolution<
+    typename cutlass::conv::kernel::DefaultConv2dFpropWithVisitor<
+        int8_t, cutlass::layout::TensorNHWC,
+        int8_t, cutlass::layout::TensorNHWC,
+        Traits::ElementAccumulator,
+        Traits::OperatorClass, cutlass::arch::Sm80,
+        Traits::ThreadblockShape,
+        Traits::WarpShape,
+        Traits::InstructionShape,
+        Callbacks,
+        cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+        Traits::kStages,
+        Traits::MathOperator,
+        cutlass::conv::IteratorAlgorithm::kOptimized,
+        cutlass::conv::StrideSupport::kStrided,
+        Traits::kAlignmentA,
+        Traits::kAlignmentB,
+        evt::kEpilogueStages>::Kernel>;
+
+cutlass::Status check_config(const cutlass_sys_conv_quant_config_t& cfg) {
+  const cutlass_sys_conv_config_t& conv = cfg.conv;
+  if (conv.element != CUTLASS_SYS_DTYPE_S8) {
+    return cutlass::Status::kErrorInvalidDataType;
+  }
+  if (!is_conv2d(conv) || !(cfg.clamp_min <= cfg.clamp_max)) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  if (conv.groups != 1 || conv.split_k_slices != 1 ||
+      conv.split_k_mode != CUTLASS_SYS_SPLIT_K_SERIAL || conv.beta != 0.0f || conv.verify) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  if (conv.c % Traits::kAlignmentA != 0 || conv.k % kAlignmentD != 0) {
+    return cutlass::Status::kErrorMisalignedOperand;
+  }
+  return cutlass::Status::kSuccess;
+}
+
+cutlass::Status conv2d_fprop_quant(const cutlass_sys_conv_quant_config_t& cfg,
+                                   const cutlass_sys_conv_quant_operands_t* ops,
+                                   void* workspace,
+                                   cudaStream_t stream,
+                                   size_t* workspace_size) {
+  cutlass::Status status = check_config(cfg);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+  using Layout = cutlass::layout::TensorNHWC;
+  constexpr auto kFprop = cutlass::conv::Operator::kFprop;
+  cutlass::conv::Conv2dProblemSize problem = conv2d_problem(cfg.conv);
+  cutlass::Tensor4DCoord extent_d = cutlass::conv::implicit_gemm_tensor_c_extent(kFprop, problem);
+  // D viewed as the row-major (N * P * Q) x K GEMM output.
+  int64_t ldd = cfg.conv.k;
+  int64_t size_d = int64_t(extent_d.n()) * extent_d.h() * extent_d.w() * extent_d.c();
+
+  cutlass_sys_conv_quant_operands_t o = ops ? *ops : cutlass_sys_conv_quant_operands_t{};
+  auto channel_vector = [&](const float* ptr, float null_default) {
+    return typename ChannelVector::Arguments{
+        ptr, null_default, {cute::_0{}, cute::_1{}, cfg.conv.k}};
+  };
+  typename Callbacks::Arguments callbacks{
+      {
+          {
+              {
+                  channel_vector(o.scale, 1.0f),
+                  {},
+                  channel_vector(o.bias, 0.0f),
+                  {},
+              },
+              evt::scalar(cfg.clamp_min),
+              {},
+          },
+          evt::scalar(cfg.clamp_max),
+          {},
+      },
+      {static_cast<int8_t*>(o.d), {ldd, cute::_1{}, size_d}},
+  };
+
+  typename QuantConv2dFprop::Arguments args{
+      problem,
+      {static_cast<int8_t*>(const_cast<void*>(o.a)),
+       Layout::packed(cutlass::conv::implicit_gemm_tensor_a_extent(kFprop, problem))},
+      {static_cast<int8_t*>(const_cast<void*>(o.b)),
+       Layout::packed(cutlass::conv::implicit_gemm_tensor_b_extent(kFprop, problem))},
+      {nullptr, Layout::packed(extent_d)},
+      {nullptr, Layout::packed(extent_d)},
+      callbacks,
+      cutlass::conv::SplitKMode::kSerial,
+  };
+
+  return query_or_run<QuantConv2dFprop>(args, workspace, stream, workspace_size);
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_conv2d_fprop_quant_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_conv_quant_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::conv2d_fprop_quant(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_conv2d_fprop_quant_can_implement(
+    const cutlass_sys_context_t* context, const cutlass_sys_conv_quant_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(
+      cutlass_sys::conv2d_fprop_quant(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_conv2d_fprop_quant(const cutlass_sys_context_t* context,
+                                              const cutlass_sys_conv_quant_config_t* config,
+                                              const cutlass_sys_conv_quant_operands_t* operands,
+                                              void* workspace,
+                                              cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::conv2d_fprop_quant(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
@@ -0,0 +1,1485 @@
+// C ABI of the cutlass-sys shim.
+//
+// Every struct and enum here has a #[repr(C)] mirror in the Rust crate
+// (src/types.rs and the per-operation modules); keep the two in sync.
+// Functions return a cutlass::Status cast to int.
+
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct CUstream_st* cutlass_sys_stream_t;  // cudaStream_t
+
+// ---------------------------------------------------------------------------
+// Shared types (src/types.rs)
+// ---------------------------------------------------------------------------
+
+typedef enum {
+  CUTLASS_SYS_DTYPE_F16 = 0,
+  CUTLASS_SYS_DTYPE_BF16 = 1,
+  CUTLASS_SYS_DTYPE_F32 = 2,
+  CUTLASS_SYS_DTYPE_S8 = 3,
+  CUTLASS_SYS_DTYPE_F64 = 4,
+  CUTLASS_SYS_DTYPE_C32 = 5,
+  CUTLASS_SYS_DTYPE_C64 = 6,
+  CUTLASS_SYS_DTYPE_S32 = 7,
+  CUTLASS_SYS_DTYPE_E4M3 = 8,
+  CUTLASS_SYS_DTYPE_E5M2 = 9,
+} cutlass_sys_dtype_t;
+
+typedef struct {
+  float re;
+  float im;
+} cutlass_sys_complex_f32_t;
+
+typedef struct {
+  double re;
+  double im;
+} cutlass_sys_complex_f64_t;
+
+typedef enum {
+  CUTLASS_SYS_SPLIT_K_SERIAL = 0,
+  CUTLASS_SYS_SPLIT_K_PARALLEL = 1,
+} cutlass_sys_split_k_mode_t;
+
+// What one kernel asks of the device: block size, registers, shared and
+// local memory per CTA, how many CTAs of it fit on an SM, and the grid it
+// launches for a given problem. Filled by the *_resources queries.
+typedef struct {
+  int32_t threads;
+  int32_t registers;
+  int32_t shared_memory;
+  int32_t local_memory;
+  int32_t max_active_ctas;
+  uint32_t grid[3];
+} cutlass_sys_kernel_resources_t;
+
+// ---------------------------------------------------------------------------
+// Device context (src/context.rs)
+// ---------------------------------------------------------------------------
+
+// Every entry point taking a context runs on its device, restoring the
+// calling thread's current device before returning. A null context uses the
+// current device.
+//
+// Each *_workspace_size query has a *_can_implement counterpart taking the
+// same arguments, which returns the status the launch would fail with before
+// touching any operand, or success.
+typedef struct {
+  int32_t device;
+  int32_t sm_count;
+  int32_t cc_major;
+  int32_t cc_minor;
+} cutlass_sys_context_t;
+
+int cutlass_sys_context_init(int32_t device, cutlass_sys_context_t* context);
+
+// The shim is compiled for every SM version in CUTLASS_CUDA_ARCH or selected
+// by the sm* cargo features, plus PTX for the newest one; the CUDA runtime
+// picks the matching code for each device. An entry point called for a device
+// none of them can run on returns kErrorArchMismatch. Only the GEMM, GEMV,
+// reduction and transform entry points run on sm_75.
+// cutlass_sys_compiled_archs points `archs` at the ascending list of compiled
+// SM versions (e.g. 80 for sm_80) and returns its length;
+// cutlass_sys_context_check_arch reports up front whether the context's device
+// can run the SM80-class kernels.
+size_t cutlass_sys_compiled_archs(const int32_t** archs);
+int cutlass_sys_context_check_arch(const cutlass_sys_context_t* context);
+
+// ---------------------------------------------------------------------------
+// Error strings (src/error.rs)
+// ---------------------------------------------------------------------------
+
+// Static, never-null descriptions: cutlassGetStatusString for a
+// cutlass::Status value (every int returned by the entry points above and
+// below), and cudaGetErrorName / cudaGetErrorString for a cudaError_t.
+const char* cutlass_sys_status_string(int status);
+const char* cutlass_sys_cuda_error_name(int error);
+const char* cutlass_sys_cuda_error_string(int error);
+
+// ---------------------------------------------------------------------------
+// Coordinates (src/coord.rs)
+// ---------------------------------------------------------------------------
+
+typedef struct {
+  int32_t m;
+  int32_t n;
+  int32_t k;
+} cutlass_sys_gemm_coord_t;
+
+typedef struct {
+  int32_t row;
+  int32_t column;
+} cutlass_sys_matrix_coord_t;
+
+typedef struct {
+  int32_t n;
+  int32_t h;
+  int32_t w;
+  int32_t c;
+} cutlass_sys_tensor4d_coord_t;
+
+// ---------------------------------------------------------------------------
+// GEMM (src/gemm.rs)
+// ---------------------------------------------------------------------------
+
+// Values of cutlass::library::LayoutTypeID.
+typedef enum {
+  CUTLASS_SYS_LAYOUT_COLUMN_MAJOR = 1,
+  CUTLASS_SYS_LAYOUT_ROW_MAJOR = 2,
+} cutlass_sys_layout_t;
+
+typedef enum {
+  CUTLASS_SYS_TILE_SCHEDULER_DATA_PARALLEL = 0,
+  CUTLASS_SYS_TILE_SCHEDULER_STREAM_K = 1,
+  CUTLASS_SYS_TILE_SCHEDULER_PERSISTENT = 2,
+} cutlass_sys_tile_scheduler_t;
+
+// Thread block cluster shape of the SM90 GEMM kernels. DEFAULT is 2x1x1
+// there and the only shape the other kernels take.
+typedef enum {
+  CUTLASS_SYS_CLUSTER_SHAPE_DEFAULT = 0,
+  CUTLASS_SYS_CLUSTER_SHAPE_1X1X1 = 1,
+  CUTLASS_SYS_CLUSTER_SHAPE_2X1X1 = 2,
+  CUTLASS_SYS_CLUSTER_SHAPE_2X2X1 = 3,
+} cutlass_sys_cluster_shape_t;
+
+typedef enum {
+  CUTLASS_SYS_BIAS_NONE = 0,
+  CUTLASS_SYS_BIAS_ROW = 1,
+  CUTLASS_SYS_BIAS_COLUMN = 2,
+} cutlass_sys_bias_mode_t;
+
+typedef enum {
+  CUTLASS_SYS_ACTIVATION_IDENTITY = 0,
+  CUTLASS_SYS_ACTIVATION_RELU = 1,
+  CUTLASS_SYS_ACTIVATION_GELU = 2,
+  CUTLASS_SYS_ACTIVATION_SILU = 3,
+  CUTLASS_SYS_ACTIVATION_TANH = 4,
+} cutlass_sys_activation_t;
+
+typedef enum {
+  CUTLASS_SYS_SCALE_NONE = 0,
+  CUTLASS_SYS_SCALE_PER_ROW = 1,
+  CUTLASS_SYS_SCALE_PER_COLUMN = 2,
+  CUTLASS_SYS_SCALE_PER_ROW_AND_COLUMN = 3,
+} cutlass_sys_scale_mode_t;
+
+typedef enum {
+  CUTLASS_SYS_BETA_PER_TENSOR = 0,
+  CUTLASS_SYS_BETA_PER_ROW = 1,
+  CUTLASS_SYS_BETA_PER_COLUMN = 2,
+} cutlass_sys_beta_mode_t;
+
+typedef enum {
+  CUTLASS_SYS_ZERO_POINT_NONE = 0,
+  CUTLASS_SYS_ZERO_POINT_PER_TENSOR = 1,
+  CUTLASS_SYS_ZERO_POINT_PER_ROW = 2,
+  CUTLASS_SYS_ZERO_POINT_PER_COLUMN = 3,
+} cutlass_sys_zero_point_mode_t;
+
+typedef enum {
+  CUTLASS_SYS_AUX_OP_NONE = 0,
+  CUTLASS_SYS_AUX_OP_ADD = 1,
+  CUTLASS_SYS_AUX_OP_MUL = 2,
+  CUTLASS_SYS_AUX_OP_MAX = 3,
+} cutlass_sys_aux_op_t;
+
+typedef struct {
+  cutlass_sys_gemm_coord_t problem;
+  cutlass_sys_dtype_t element;
+  cutlass_sys_dtype_t accumulator;
+  cutlass_sys_dtype_t output;  // of C and D
+  cutlass_sys_layout_t layout_a;
+  cutlass_sys_layout_t layout_b;
+  int64_t lda;
+  int64_t ldb;
+  int64_t ldc;
+  int64_t ldd;
+  float alpha;
+  float beta;
+  cutlass_sys_tile_scheduler_t scheduler;
+  int32_t split_k_slices;
+  cutlass_sys_cluster_shape_t cluster_shape;
+  cutlass_sys_bias_mode_t bias;
+  cutlass_sys_activation_t activation;
+  cutlass_sys_scale_mode_t scale;
+  cutlass_sys_beta_mode_t beta_mode;
+  cutlass_sys_zero_point_mode_t zero_point_mode;
+  float zero_point;
+  cutlass_sys_aux_op_t aux_op;
+  int64_t ldaux;
+  // Multiplies the result before its down-conversion to an fp8 or int8
+  // output; 1 for every other output type.
+  float out_scale;
+  // Check D against the device reference after the launch (`reference`
+  // feature, see src/shim/verify.cuh): more workspace, a stream sync, and
+  // kErrorInternal on a mismatch. For debugging only.
+  bool verify;
+} cutlass_sys_gemm_config_t;
+
+typedef struct {
+  const void* a;
+  const void* b;
+  const void* c;
+  void* d;
+  const void* bias;
+  const float* row_scale;
+  const float* col_scale;
+  const float* beta;
+  const float* zero_point;
+  const void* aux;
+  float* amax;  // max |D| before out_scale, atomically folded in; may be null
+} cutlass_sys_gemm_operands_t;
+
+size_t cutlass_sys_gemm_workspace_size(const cutlass_sys_context_t* context,
+                                       const cutlass_sys_gemm_config_t* config);
+
+int cutlass_sys_gemm_can_implement(const cutlass_sys_context_t* context,
+                                   const cutlass_sys_gemm_config_t* config);
+
+int cutlass_sys_gemm_tile_shape(const cutlass_sys_context_t* context,
+                                const cutlass_sys_gemm_config_t* config,
+                                cutlass_sys_gemm_coord_t* tile);
+
+// Resources of the kernel cutlass_sys_gemm would launch `config` on, or the
+// status the launch would fail with. kErrorNotSupported for the GEMV kernel.
+int cutlass_sys_gemm_resources(const cutlass_sys_context_t* context,
+                               const cutlass_sys_gemm_config_t* config,
+                               cutlass_sys_kernel_resources_t* resources);
+
+int cutlass_sys_gemm(const cutlass_sys_context_t* context,
+                     const cutlass_sys_gemm_config_t* config,
+                     const cutlass_sys_gemm_operands_t* operands,
+                     void* workspace,
+                     cutlass_sys_stream_t stream);
+
+typedef struct {
+  const void* const* a;
+  const void* const* b;
+  const void* const* c;
+  void* const* d;
+} cutlass_sys_gemm_array_operands_t;
+
+size_t cutlass_sys_gemm_array_workspace_size(const cutlass_sys_context_t* context,
+                                             const cutlass_sys_gemm_config_t* config,
+                                             int32_t batch_count);
+
+int cutlass_sys_gemm_array_can_implement(const cutlass_sys_context_t* context,
+                                         const cutlass_sys_gemm_config_t* config,
+                                         int32_t batch_count);
+
+int cutlass_sys_gemm_array(const cutlass_sys_context_t* context,
+                           const cutlass_sys_gemm_config_t* config,
+                           int32_t batch_count,
+                           const cutlass_sys_gemm_array_operands_t* operands,
+                           void* workspace,
+                           cutlass_sys_stream_t stream);
+
+// Gather/scatter GEMM: row i of the m x k operand A is row gather_a[i] of a,
+// row j of the k x n operand B is row gather_b[j] of b (f16, bf16 and f32
+// only), and row i of the result is read from row scatter_d[i] of c and
+// written to row scatter_d[i] of d. A null index array leaves its operand
+// unindexed.
+typedef struct {
+  const void* a;
+  const void* b;
+  const void* c;
+  void* d;
+  const int32_t* gather_a;
+  const int32_t* gather_b;
+  const int32_t* scatter_d;
+} cutlass_sys_gemm_gather_operands_t;
+
+size_t cutlass_sys_gemm_gather_workspace_size(const cutlass_sys_context_t* context,
+                                              const cutlass_sys_gemm_config_t* config);
+
+int cutlass_sys_gemm_gather_can_implement(const cutlass_sys_context_t* context,
+                                          const cutlass_sys_gemm_config_t* config);
+
+int cutlass_sys_gemm_gather(const cutlass_sys_context_t* context,
+                            const cutlass_sys_gemm_config_t* config,
+                            const cutlass_sys_gemm_gather_operands_t* operands,
+                            void* workspace,
+                            cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// Fused GEMM pairs (src/b2b_gemm.rs)
+// ---------------------------------------------------------------------------
+
+typedef enum {
+  // D = alpha1 * act(alpha0 * A * B0) * B1 + beta1 * C
+  CUTLASS_SYS_B2B_MODE_BACK_TO_BACK = 0,
+  // D = act(alpha0 * A * B0) * (alpha1 * A * B1)
+  CUTLASS_SYS_B2B_MODE_GATED = 1,
+} cutlass_sys_b2b_mode_t;
+
+// A, C and D row-major, B0 and B1 column-major.
+typedef struct {
+  cutlass_sys_b2b_mode_t mode;
+  cutlass_sys_gemm_coord_t problem0;
+  cutlass_sys_gemm_coord_t problem1;
+  cutlass_sys_dtype_t element;
+  cutlass_sys_activation_t activation;
+  int32_t batch_count;
+  int64_t lda;
+  int64_t ldb0;
+  int64_t ldb1;
+  int64_t ldc;
+  int64_t ldd;
+  int64_t batch_stride_a;
+  int64_t batch_stride_b0;
+  int64_t batch_stride_b1;
+  int64_t batch_stride_c;
+  int64_t batch_stride_d;
+  float alpha0;
+  float alpha1;
+  float beta1;
+} cutlass_sys_b2b_gemm_config_t;
+
+typedef struct {
+  const void* a;
+  const void* b0;
+  const void* b1;
+  const void* c;
+  void* d;
+} cutlass_sys_b2b_gemm_operands_t;
+
+size_t cutlass_sys_b2b_gemm_workspace_size(const cutlass_sys_context_t* context,
+                                           const cutlass_sys_b2b_gemm_config_t* config);
+
+int cutlass_sys_b2b_gemm_can_implement(const cutlass_sys_context_t* context,
+                                       const cutlass_sys_b2b_gemm_config_t* config);
+
+int cutlass_sys_b2b_gemm(const cutlass_sys_context_t* context,
+                         const cutlass_sys_b2b_gemm_config_t* config,
+                         const cutlass_sys_b2b_gemm_operands_t* operands,
+                         void* workspace,
+                         cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// GEMV (src/gemv.rs)
+// ---------------------------------------------------------------------------
+
+typedef struct {
+  int32_t m;
+  int32_t k;
+  cutlass_sys_dtype_t element;
+  int32_t batch_count;
+  int64_t lda;
+  int64_t batch_stride_a;
+  int64_t batch_stride_x;
+  int64_t batch_stride_c;
+  int64_t batch_stride_d;
+  float alpha;
+  float beta;
+} cutlass_sys_gemv_config_t;
+
+typedef struct {
+  const void* a;
+  const void* x;
+  const void* c;
+  void* d;
+} cutlass_sys_gemv_operands_t;
+
+size_t cutlass_sys_gemv_workspace_size(const cutlass_sys_context_t* context,
+                                       const cutlass_sys_gemv_config_t* config);
+
+int cutlass_sys_gemv_can_implement(const cutlass_sys_context_t* context,
+                                   const cutlass_sys_gemv_config_t* config);
+
+int cutlass_sys_gemv(const cutlass_sys_context_t* context,
+                     const cutlass_sys_gemv_config_t* config,
+                     const cutlass_sys_gemv_operands_t* operands,
+                     void* workspace,
+                     cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// GEMM-LayerNorm-GEMM (src/gemm_layernorm.rs)
+// ---------------------------------------------------------------------------
+
+// D = alpha * A * B0 + beta * C, out = layernorm(D) * B1 with the LayerNorm
+// over the rows of D, scaled by gamma and shifted by beta_ln (n elements
+// each). A, C, D and out row-major, B0 and B1 column-major.
+typedef struct {
+  cutlass_sys_gemm_coord_t problem0;
+  cutlass_sys_gemm_coord_t problem1;
+  cutlass_sys_dtype_t element;
+  int64_t lda;
+  int64_t ldb0;
+  int64_t ldc;
+  int64_t ldd;
+  int64_t ldb1;
+  int64_t ld_out;
+  float alpha;
+  float beta;
+} cutlass_sys_gemm_layernorm_config_t;
+
+typedef struct {
+  const void* a;
+  const void* b0;
+  const void* c;
+  void* d;
+  const void* gamma;
+  const void* beta_ln;
+  const void* b1;
+  void* out;
+} cutlass_sys_gemm_layernorm_operands_t;
+
+size_t cutlass_sys_gemm_layernorm_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_gemm_layernorm_config_t* config);
+
+int cutlass_sys_gemm_layernorm_can_implement(const cutlass_sys_context_t* context,
+                                             const cutlass_sys_gemm_layernorm_config_t* config);
+
+int cutlass_sys_gemm_layernorm(const cutlass_sys_context_t* context,
+                               const cutlass_sys_gemm_layernorm_config_t* config,
+                               const cutlass_sys_gemm_layernorm_operands_t* operands,
+                               void* workspace,
+                               cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// GEMM + softmax (src/gemm_softmax.rs)
+// ---------------------------------------------------------------------------
+
+// D = scale * A * B + mask, softmax = row-wise softmax(D). A row-major, B
+// column-major, mask, D and softmax row-major. A null mask adds nothing.
+typedef struct {
+  cutlass_sys_gemm_coord_t problem;
+  cutlass_sys_dtype_t element;
+  int32_t batch_count;
+  int64_t lda;
+  int64_t ldb;
+  int64_t ld_mask;
+  int64_t ldd;
+  int64_t ld_softmax;
+  int64_t batch_stride_a;
+  int64_t batch_stride_b;
+  int64_t batch_stride_mask;
+  int64_t batch_stride_d;
+  int64_t batch_stride_softmax;
+  float scale;
+} cutlass_sys_gemm_softmax_config_t;
+
+typedef struct {
+  const void* a;
+  const void* b;
+  const void* mask;
+  void* d;
+  void* softmax;
+} cutlass_sys_gemm_softmax_operands_t;
+
+size_t cutlass_sys_gemm_softmax_workspace_size(const cutlass_sys_context_t* context,
+                                               const cutlass_sys_gemm_softmax_config_t* config);
+
+int cutlass_sys_gemm_softmax_can_implement(const cutlass_sys_context_t* context,
+                                           const cutlass_sys_gemm_softmax_config_t* config);
+
+int cutlass_sys_gemm_softmax(const cutlass_sys_context_t* context,
+                             const cutlass_sys_gemm_softmax_config_t* config,
+                             const cutlass_sys_gemm_softmax_operands_t* operands,
+                             void* workspace,
+                             cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// Epilogue visitor trees (src/evt.rs)
+// ---------------------------------------------------------------------------
+
+typedef enum {
+  CUTLASS_SYS_BINARY_OP_ADD = 0,
+  CUTLASS_SYS_BINARY_OP_MUL = 1,
+  CUTLASS_SYS_BINARY_OP_MAX = 2,
+} cutlass_sys_binary_op_t;
+
+typedef enum {
+  // D = out_scale * act(op(acc_scale * acc, aux_scale * aux))
+  CUTLASS_SYS_EVT_SHAPE_AUX_THEN_ACTIVATION = 0,
+  // D = op(out_scale * act(acc_scale * acc), aux_scale * aux)
+  CUTLASS_SYS_EVT_SHAPE_ACTIVATION_THEN_AUX = 1,
+} cutlass_sys_evt_shape_t;
+
+typedef struct {
+  cutlass_sys_evt_shape_t shape;
+  cutlass_sys_binary_op_t op;
+  cutlass_sys_activation_t activation;
+  float acc_scale;
+  float aux_scale;
+  float out_scale;
+  const void* aux;
+  int64_t ldaux;
+  void* d;
+  int64_t ldd;
+} cutlass_sys_evt_program_t;
+
+size_t cutlass_sys_gemm_evt_workspace_size(const cutlass_sys_context_t* context,
+                                           const cutlass_sys_gemm_config_t* config,
+                                           const cutlass_sys_evt_program_t* program);
+
+int cutlass_sys_gemm_evt_can_implement(const cutlass_sys_context_t* context,
+                                       const cutlass_sys_gemm_config_t* config,
+                                       const cutlass_sys_evt_program_t* program);
+
+int cutlass_sys_gemm_evt(const cutlass_sys_context_t* context,
+                         const cutlass_sys_gemm_config_t* config,
+                         const cutlass_sys_evt_program_t* program,
+                         const void* a,
+                         const void* b,
+                         void* workspace,
+                         cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// Implicit-GEMM convolution (src/conv.rs)
+// ---------------------------------------------------------------------------
+
+typedef enum {
+  CUTLASS_SYS_CONV_MODE_CROSS_CORRELATION = 0,
+  CUTLASS_SYS_CONV_MODE_CONVOLUTION = 1,
+} cutlass_sys_conv_mode_t;
+
+typedef struct {
+  int32_t n;
+  int32_t d;
+  int32_t h;
+  int32_t w;
+  int32_t c;
+  int32_t k;
+  int32_t t;
+  int32_t r;
+  int32_t s;
+  int32_t pad_d;
+  int32_t pad_h;
+  int32_t pad_w;
+  int32_t stride_d;
+  int32_t stride_h;
+  int32_t stride_w;
+  int32_t dilation_d;
+  int32_t dilation_h;
+  int32_t dilation_w;
+  int32_t groups;
+  cutlass_sys_conv_mode_t mode;
+  cutlass_sys_dtype_t element;
+  float alpha;
+  float beta;
+  int32_t split_k_slices;
+  cutlass_sys_split_k_mode_t split_k_mode;
+  // Check D against the device reference after the launch, as for GEMM.
+  bool verify;
+} cutlass_sys_conv_config_t;
+
+typedef struct {
+  const void* a;
+  const void* b;
+  const void* c;
+  void* d;
+} cutlass_sys_conv_operands_t;
+
+size_t cutlass_sys_conv2d_fprop_workspace_size(const cutlass_sys_context_t* context,
+                                               const cutlass_sys_conv_config_t* config);
+
+int cutlass_sys_conv2d_fprop_can_implement(const cutlass_sys_context_t* context,
+                                           const cutlass_sys_conv_config_t* config);
+
+int cutlass_sys_conv2d_fprop(const cutlass_sys_context_t* context,
+                             const cutlass_sys_conv_config_t* config,
+                             const cutlass_sys_conv_operands_t* operands,
+                             void* workspace,
+                             cutlass_sys_stream_t stream);
+
+size_t cutlass_sys_conv2d_dgrad_workspace_size(const cutlass_sys_context_t* context,
+                                               const cutlass_sys_conv_config_t* config);
+
+int cutlass_sys_conv2d_dgrad_can_implement(const cutlass_sys_context_t* context,
+                                           const cutlass_sys_conv_config_t* config);
+
+int cutlass_sys_conv2d_dgrad(const cutlass_sys_context_t* context,
+                             const cutlass_sys_conv_config_t* config,
+                             const cutlass_sys_conv_operands_t* operands,
+                             void* workspace,
+                             cutlass_sys_stream_t stream);
+
+size_t cutlass_sys_conv2d_wgrad_workspace_size(const cutlass_sys_context_t* context,
+                                               const cutlass_sys_conv_config_t* config);
+
+int cutlass_sys_conv2d_wgrad_can_implement(const cutlass_sys_context_t* context,
+                                           const cutlass_sys_conv_config_t* config);
+
+int cutlass_sys_conv2d_wgrad(const cutlass_sys_context_t* context,
+                             const cutlass_sys_conv_config_t* config,
+                             const cutlass_sys_conv_operands_t* operands,
+                             void* workspace,
+                             cutlass_sys_stream_t stream);
+
+size_t cutlass_sys_conv3d_fprop_workspace_size(const cutlass_sys_context_t* context,
+                                               const cutlass_sys_conv_config_t* config);
+
+int cutlass_sys_conv3d_fprop_can_implement(const cutlass_sys_context_t* context,
+                                           const cutlass_sys_conv_config_t* config);
+
+int cutlass_sys_conv3d_fprop(const cutlass_sys_context_t* context,
+                             const cutlass_sys_conv_config_t* config,
+                             const cutlass_sys_conv_operands_t* operands,
+                             void* workspace,
+                             cutlass_sys_stream_t stream);
+
+size_t cutlass_sys_conv3d_dgrad_workspace_size(const cutlass_sys_context_t* context,
+                                               const cutlass_sys_conv_config_t* config);
+
+int cutlass_sys_conv3d_dgrad_can_implement(const cutlass_sys_context_t* context,
+                                           const cutlass_sys_conv_config_t* config);
+
+int cutlass_sys_conv3d_dgrad(const cutlass_sys_context_t* context,
+                             const cutlass_sys_conv_config_t* config,
+                             const cutlass_sys_conv_operands_t* operands,
+                             void* workspace,
+                             cutlass_sys_stream_t stream);
+
+size_t cutlass_sys_conv3d_wgrad_workspace_size(const cutlass_sys_context_t* context,
+                                               const cutlass_sys_conv_config_t* config);
+
+int cutlass_sys_conv3d_wgrad_can_implement(const cutlass_sys_context_t* context,
+                                           const cutlass_sys_conv_config_t* config);
+
+int cutlass_sys_conv3d_wgrad(const cutlass_sys_context_t* context,
+                             const cutlass_sys_conv_config_t* config,
+                             const cutlass_sys_conv_operands_t* operands,
+                             void* workspace,
+                             cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// Quantized int8 convolution (src/conv_quant.rs)
+// ---------------------------------------------------------------------------
+
+// int8 2-D fprop writing int8:
+// d = clamp(round(scale[k] * acc + bias[k]), clamp_min, clamp_max), with
+// scale and bias length-K f32 vectors (null: 1 and 0). conv.element must be
+// S8; conv.alpha is ignored.
+typedef struct {
+  cutlass_sys_conv_config_t conv;
+  float clamp_min;
+  float clamp_max;
+} cutlass_sys_conv_quant_config_t;
+
+typedef struct {
+  const void* a;
+  const void* b;
+  void* d;
+  const float* scale;
+  const float* bias;
+} cutlass_sys_conv_quant_operands_t;
+
+size_t cutlass_sys_conv2d_fprop_quant_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_conv_quant_config_t* config);
+
+int cutlass_sys_conv2d_fprop_quant_can_implement(const cutlass_sys_context_t* context,
+                                                 const cutlass_sys_conv_quant_config_t* config);
+
+int cutlass_sys_conv2d_fprop_quant(const cutlass_sys_context_t* context,
+                                   const cutlass_sys_conv_quant_config_t* config,
+                                   const cutlass_sys_conv_quant_operands_t* operands,
+                                   void* workspace,
+                                   cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// Fused back-to-back convolution (src/b2b_conv.rs)
+// ---------------------------------------------------------------------------
+
+// d = conv1(act(conv0(a, filter0)), filter1) scaled by conv1.alpha, plus
+// conv1.beta * c. conv1 must be a 1x1, unit-stride, unpadded convolution of
+// conv0's output, conv0.k 64 and conv1.k 64 or 128; anything else returns
+// kErrorNotSupported or kErrorInvalidProblem. Packed NHWC, f16 or bf16.
+typedef struct {
+  cutlass_sys_conv_config_t conv0;
+  cutlass_sys_conv_config_t conv1;
+  cutlass_sys_activation_t activation;
+} cutlass_sys_b2b_conv_config_t;
+
+typedef struct {
+  const void* a;
+  const void* filter0;
+  const void* filter1;
+  const void* c;
+  void* d;
+} cutlass_sys_b2b_conv_operands_t;
+
+size_t cutlass_sys_b2b_conv2d_fprop_workspace_size(const cutlass_sys_context_t* context,
+                                                   const cutlass_sys_b2b_conv_config_t* config);
+
+int cutlass_sys_b2b_conv2d_fprop_can_implement(const cutlass_sys_context_t* context,
+                                               const cutlass_sys_b2b_conv_config_t* config);
+
+int cutlass_sys_b2b_conv2d_fprop(const cutlass_sys_context_t* context,
+                                 const cutlass_sys_b2b_conv_config_t* config,
+                                 const cutlass_sys_b2b_conv_operands_t* operands,
+                                 void* workspace,
+                                 cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// 2:4 structured-sparse GEMM (src/sparse.rs)
+// ---------------------------------------------------------------------------
+
+typedef struct {
+  cutlass_sys_gemm_coord_t problem;
+  cutlass_sys_dtype_t element;
+  int64_t lda;
+  int64_t ldb;
+  int64_t ldc;
+  int64_t ldd;
+  float alpha;
+  float beta;
+  int32_t split_k_slices;
+} cutlass_sys_sparse_gemm_config_t;
+
+typedef struct {
+  const void* a;
+  const void* e;
+  const void* b;
+  const void* c;
+  void* d;
+} cutlass_sys_sparse_gemm_operands_t;
+
+size_t cutlass_sys_sparse_metadata_size(const cutlass_sys_sparse_gemm_config_t* config);
+
+int cutlass_sys_sparse_compress(const cutlass_sys_context_t* context,
+                                const cutlass_sys_sparse_gemm_config_t* config,
+                                const void* dense,
+                                int64_t ld_dense,
+                                void* compressed,
+                                void* metadata,
+                                cutlass_sys_stream_t stream);
+
+size_t cutlass_sys_sparse_gemm_workspace_size(const cutlass_sys_context_t* context,
+                                              const cutlass_sys_sparse_gemm_config_t* config);
+
+int cutlass_sys_sparse_gemm_can_implement(const cutlass_sys_context_t* context,
+                                          const cutlass_sys_sparse_gemm_config_t* config);
+
+int cutlass_sys_sparse_gemm(const cutlass_sys_context_t* context,
+                            const cutlass_sys_sparse_gemm_config_t* config,
+                            const cutlass_sys_sparse_gemm_operands_t* operands,
+                            void* workspace,
+                            cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// Blocked-ELL sparse GEMM (src/ell_gemm.rs)
+// ---------------------------------------------------------------------------
+
+// A's stored values are m x columns, its block-column indices a row-major
+// (m / block_size) x (columns / block_size) int32 tensor, -1 (relative to
+// index_base) for padding blocks. A, B, C and D row-major.
+typedef struct {
+  int32_t block_size;
+  int32_t columns;
+  int32_t index_base;
+} cutlass_sys_blocked_ell_t;
+
+typedef struct {
+  cutlass_sys_gemm_coord_t problem;
+  cutlass_sys_dtype_t element;
+  cutlass_sys_blocked_ell_t ell;
+  int64_t lda;
+  int64_t ldb;
+  int64_t ldc;
+  int64_t ldd;
+  float alpha;
+  float beta;
+} cutlass_sys_ell_gemm_config_t;
+
+typedef struct {
+  const void* a;
+  const int32_t* column_indices;
+  const void* b;
+  const void* c;
+  void* d;
+} cutlass_sys_ell_gemm_operands_t;
+
+size_t cutlass_sys_ell_gemm_workspace_size(const cutlass_sys_context_t* context,
+                                           const cutlass_sys_ell_gemm_config_t* config);
+
+int cutlass_sys_ell_gemm_can_implement(const cutlass_sys_context_t* context,
+                                       const cutlass_sys_ell_gemm_config_t* config);
+
+int cutlass_sys_ell_gemm(const cutlass_sys_context_t* context,
+                         const cutlass_sys_ell_gemm_config_t* config,
+                         const cutlass_sys_ell_gemm_operands_t* operands,
+                         void* workspace,
+                         cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// Block-scaled narrow-precision GEMM (src/block_scaled.rs, `sm100` feature)
+// ---------------------------------------------------------------------------
+
+// Defined only when the shim is built with the `sm100` feature.
+
+typedef enum {
+  CUTLASS_SYS_BLOCK_SCALED_NVFP4 = 0,
+  CUTLASS_SYS_BLOCK_SCALED_MXFP4 = 1,
+  CUTLASS_SYS_BLOCK_SCALED_MXFP6_E3M2 = 2,
+  CUTLASS_SYS_BLOCK_SCALED_MXFP6_E2M3 = 3,
+  CUTLASS_SYS_BLOCK_SCALED_MXFP8_E4M3 = 4,
+  CUTLASS_SYS_BLOCK_SCALED_MXFP8_E5M2 = 5,
+} cutlass_sys_block_scaled_type_t;
+
+typedef struct {
+  cutlass_sys_gemm_coord_t problem;
+  cutlass_sys_block_scaled_type_t element;
+  cutlass_sys_dtype_t output;
+  int64_t lda;
+  int64_t ldb;
+  int64_t ldc;
+  int64_t ldd;
+  float alpha;
+  float beta;
+} cutlass_sys_block_scaled_gemm_config_t;
+
+typedef struct {
+  const void* a;
+  const void* b;
+  const void* sfa;
+  const void* sfb;
+  const void* c;
+  void* d;
+} cutlass_sys_block_scaled_gemm_operands_t;
+
+size_t cutlass_sys_block_scaled_sfa_size(const cutlass_sys_block_scaled_gemm_config_t* config);
+
+size_t cutlass_sys_block_scaled_sfb_size(const cutlass_sys_block_scaled_gemm_config_t* config);
+
+size_t cutlass_sys_block_scaled_gemm_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_block_scaled_gemm_config_t* config);
+
+int cutlass_sys_block_scaled_gemm_can_implement(
+    const cutlass_sys_context_t* context, const cutlass_sys_block_scaled_gemm_config_t* config);
+
+int cutlass_sys_block_scaled_gemm(const cutlass_sys_context_t* context,
+                                  const cutlass_sys_block_scaled_gemm_config_t* config,
+                                  const cutlass_sys_block_scaled_gemm_operands_t* operands,
+                                  void* workspace,
+                                  cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// BLAS3 (src/blas3.rs)
+// ---------------------------------------------------------------------------
+
+typedef enum {
+  CUTLASS_SYS_FILL_LOWER = 0,
+  CUTLASS_SYS_FILL_UPPER = 1,
+} cutlass_sys_fill_mode_t;
+
+typedef enum {
+  CUTLASS_SYS_TRANSPOSE_N = 0,
+  CUTLASS_SYS_TRANSPOSE_T = 1,
+} cutlass_sys_transpose_t;
+
+typedef enum {
+  CUTLASS_SYS_SIDE_LEFT = 0,
+  CUTLASS_SYS_SIDE_RIGHT = 1,
+} cutlass_sys_side_mode_t;
+
+typedef enum {
+  CUTLASS_SYS_DIAG_NON_UNIT = 0,
+  CUTLASS_SYS_DIAG_UNIT = 1,
+} cutlass_sys_diag_type_t;
+
+typedef struct {
+  int32_t n;
+  int32_t k;
+  cutlass_sys_dtype_t element;
+  cutlass_sys_fill_mode_t fill;
+  cutlass_sys_transpose_t trans;
+  int64_t lda;
+  int64_t ldc;
+  int64_t ldd;
+  cutlass_sys_complex_f64_t alpha;
+  cutlass_sys_complex_f64_t beta;
+} cutlass_sys_rank_k_config_t;
+
+typedef struct {
+  const void* a;
+  const void* c;
+  void* d;
+} cutlass_sys_rank_k_operands_t;
+
+size_t cutlass_sys_syrk_workspace_size(const cutlass_sys_context_t* context,
+                                       const cutlass_sys_rank_k_config_t* config);
+
+int cutlass_sys_syrk_can_implement(const cutlass_sys_context_t* context,
+                                   const cutlass_sys_rank_k_config_t* config);
+
+int cutlass_sys_syrk(const cutlass_sys_context_t* context,
+                     const cutlass_sys_rank_k_config_t* config,
+                     const cutlass_sys_rank_k_operands_t* operands,
+                     void* workspace,
+                     cutlass_sys_stream_t stream);
+
+size_t cutlass_sys_herk_workspace_size(const cutlass_sys_context_t* context,
+                                       const cutlass_sys_rank_k_config_t* config);
+
+int cutlass_sys_herk_can_implement(const cutlass_sys_context_t* context,
+                                   const cutlass_sys_rank_k_config_t* config);
+
+int cutlass_sys_herk(const cutlass_sys_context_t* context,
+                     const cutlass_sys_rank_k_config_t* config,
+                     const cutlass_sys_rank_k_operands_t* operands,
+                     void* workspace,
+                     cutlass_sys_stream_t stream);
+
+typedef struct {
+  int32_t n;
+  int32_t k;
+  cutlass_sys_dtype_t element;
+  cutlass_sys_fill_mode_t fill;
+  cutlass_sys_transpose_t trans;
+  int64_t lda;
+  int64_t ldb;
+  int64_t ldc;
+  int64_t ldd;
+  cutlass_sys_complex_f64_t alpha;
+  cutlass_sys_complex_f64_t beta;
+} cutlass_sys_rank_2k_config_t;
+
+typedef struct {
+  const void* a;
+  const void* b;
+  const void* c;
+  void* d;
+} cutlass_sys_rank_2k_operands_t;
+
+size_t cutlass_sys_syr2k_workspace_size(const cutlass_sys_context_t* context,
+                                        const cutlass_sys_rank_2k_config_t* config);
+
+int cutlass_sys_syr2k_can_implement(const cutlass_sys_context_t* context,
+                                    const cutlass_sys_rank_2k_config_t* config);
+
+int cutlass_sys_syr2k(const cutlass_sys_context_t* context,
+                      const cutlass_sys_rank_2k_config_t* config,
+                      const cutlass_sys_rank_2k_operands_t* operands,
+                      void* workspace,
+                      cutlass_sys_stream_t stream);
+
+size_t cutlass_sys_her2k_workspace_size(const cutlass_sys_context_t* context,
+                                        const cutlass_sys_rank_2k_config_t* config);
+
+int cutlass_sys_her2k_can_implement(const cutlass_sys_context_t* context,
+                                    const cutlass_sys_rank_2k_config_t* config);
+
+int cutlass_sys_her2k(const cutlass_sys_context_t* context,
+                      const cutlass_sys_rank_2k_config_t* config,
+                      const cutlass_sys_rank_2k_operands_t* operands,
+                      void* workspace,
+                      cutlass_sys_stream_t stream);
+
+typedef struct {
+  int32_t m;
+  int32_t n;
+  cutlass_sys_dtype_t element;
+  cutlass_sys_side_mode_t side;
+  cutlass_sys_fill_mode_t fill;
+  cutlass_sys_transpose_t trans;
+  cutlass_sys_diag_type_t diag;
+  int64_t lda;
+  int64_t ldb;
+  int64_t ldd;
+  cutlass_sys_complex_f64_t alpha;
+} cutlass_sys_trmm_config_t;
+
+typedef struct {
+  const void* a;
+  const void* b;
+  void* d;
+} cutlass_sys_trmm_operands_t;
+
+size_t cutlass_sys_trmm_workspace_size(const cutlass_sys_context_t* context,
+                                       const cutlass_sys_trmm_config_t* config);
+
+int cutlass_sys_trmm_can_implement(const cutlass_sys_context_t* context,
+                                   const cutlass_sys_trmm_config_t* config);
+
+int cutlass_sys_trmm(const cutlass_sys_context_t* context,
+                     const cutlass_sys_trmm_config_t* config,
+                     const cutlass_sys_trmm_operands_t* operands,
+                     void* workspace,
+                     cutlass_sys_stream_t stream);
+
+typedef struct {
+  int32_t m;
+  int32_t n;
+  cutlass_sys_dtype_t element;
+  cutlass_sys_side_mode_t side;
+  cutlass_sys_fill_mode_t fill;
+  int64_t lda;
+  int64_t ldb;
+  int64_t ldc;
+  int64_t ldd;
+  cutlass_sys_complex_f64_t alpha;
+  cutlass_sys_complex_f64_t beta;
+} cutlass_sys_symm_config_t;
+
+typedef struct {
+  const void* a;
+  const void* b;
+  const void* c;
+  void* d;
+} cutlass_sys_symm_operands_t;
+
+size_t cutlass_sys_symm_workspace_size(const cutlass_sys_context_t* context,
+                                       const cutlass_sys_symm_config_t* config);
+
+int cutlass_sys_symm_can_implement(const cutlass_sys_context_t* context,
+                                   const cutlass_sys_symm_config_t* config);
+
+int cutlass_sys_symm(const cutlass_sys_context_t* context,
+                     const cutlass_sys_symm_config_t* config,
+                     const cutlass_sys_symm_operands_t* operands,
+                     void* workspace,
+                     cutlass_sys_stream_t stream);
+
+size_t cutlass_sys_hemm_workspace_size(const cutlass_sys_context_t* context,
+                                       const cutlass_sys_symm_config_t* config);
+
+int cutlass_sys_hemm_can_implement(const cutlass_sys_context_t* context,
+                                   const cutlass_sys_symm_config_t* config);
+
+int cutlass_sys_hemm(const cutlass_sys_context_t* context,
+                     const cutlass_sys_symm_config_t* config,
+                     const cutlass_sys_symm_operands_t* operands,
+                     void* workspace,
+                     cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// Planar-complex GEMM (src/planar_complex.rs)
+// ---------------------------------------------------------------------------
+
+typedef struct {
+  cutlass_sys_gemm_coord_t problem;
+  cutlass_sys_dtype_t element;
+  int32_t batch_count;
+  int64_t lda;
+  int64_t ldb;
+  int64_t ldc;
+  int64_t ldd;
+  int64_t batch_stride_a;
+  int64_t batch_stride_b;
+  int64_t batch_stride_c;
+  int64_t batch_stride_d;
+  cutlass_sys_complex_f32_t alpha;
+  cutlass_sys_complex_f32_t beta;
+} cutlass_sys_planar_complex_gemm_config_t;
+
+typedef struct {
+  const void* a_real;
+  const void* a_imag;
+  const void* b_real;
+  const void* b_imag;
+  const void* c_real;
+  const void* c_imag;
+  void* d_real;
+  void* d_imag;
+} cutlass_sys_planar_complex_operands_t;
+
+size_t cutlass_sys_planar_complex_gemm_workspace_size(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_planar_complex_gemm_config_t* config);
+
+int cutlass_sys_planar_complex_gemm_can_implement(const cutlass_sys_context_t* context,
+                                                  const cutlass_sys_planar_complex_gemm_config_t* config);
+
+int cutlass_sys_planar_complex_gemm(const cutlass_sys_context_t* context,
+                                    const cutlass_sys_planar_complex_gemm_config_t* config,
+                                    const cutlass_sys_planar_complex_operands_t* operands,
+                                    void* workspace,
+                                    cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// Reductions (src/reduction.rs)
+// ---------------------------------------------------------------------------
+
+typedef struct {
+  int32_t m;
+  int32_t n;
+  cutlass_sys_dtype_t element;
+  int32_t partitions;
+  int64_t partition_stride;
+  int64_t ldw;
+  int64_t ldc;
+  int64_t ldd;
+  float alpha;
+  float beta;
+} cutlass_sys_split_k_reduce_config_t;
+
+typedef struct {
+  const void* partials;
+  const void* c;
+  void* d;
+} cutlass_sys_split_k_reduce_operands_t;
+
+typedef enum {
+  CUTLASS_SYS_REDUCE_SUM = 0,
+  CUTLASS_SYS_REDUCE_MAX = 1,
+  CUTLASS_SYS_REDUCE_MIN = 2,
+} cutlass_sys_reduce_op_t;
+
+typedef enum {
+  CUTLASS_SYS_REDUCE_AXIS_N = 0,
+  CUTLASS_SYS_REDUCE_AXIS_H = 1,
+  CUTLASS_SYS_REDUCE_AXIS_W = 2,
+  CUTLASS_SYS_REDUCE_AXIS_C = 3,
+} cutlass_sys_reduce_axis_t;
+
+typedef struct {
+  int32_t n;
+  int32_t h;
+  int32_t w;
+  int32_t c;
+  cutlass_sys_reduce_axis_t axis;
+  cutlass_sys_reduce_op_t op;
+  cutlass_sys_dtype_t element;
+} cutlass_sys_tensor_reduce_config_t;
+
+typedef struct {
+  const void* src;
+  void* dst;
+} cutlass_sys_tensor_reduce_operands_t;
+
+size_t cutlass_sys_reduce_split_k_workspace_size(const cutlass_sys_context_t* context,
+                                                 const cutlass_sys_split_k_reduce_config_t* config);
+
+int cutlass_sys_reduce_split_k_can_implement(const cutlass_sys_context_t* context,
+                                             const cutlass_sys_split_k_reduce_config_t* config);
+
+int cutlass_sys_reduce_split_k(const cutlass_sys_context_t* context,
+                               const cutlass_sys_split_k_reduce_config_t* config,
+                               const cutlass_sys_split_k_reduce_operands_t* operands,
+                               void* workspace,
+                               cutlass_sys_stream_t stream);
+
+size_t cutlass_sys_reduce_tensor_workspace_size(const cutlass_sys_context_t* context,
+                                                const cutlass_sys_tensor_reduce_config_t* config);
+
+int cutlass_sys_reduce_tensor_can_implement(const cutlass_sys_context_t* context,
+                                            const cutlass_sys_tensor_reduce_config_t* config);
+
+int cutlass_sys_reduce_tensor(const cutlass_sys_context_t* context,
+                              const cutlass_sys_tensor_reduce_config_t* config,
+                              const cutlass_sys_tensor_reduce_operands_t* operands,
+                              void* workspace,
+                              cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// Layout transforms and conversions (src/transform.rs)
+// ---------------------------------------------------------------------------
+
+typedef enum {
+  CUTLASS_SYS_TENSOR_LAYOUT_NHWC = 0,
+  CUTLASS_SYS_TENSOR_LAYOUT_NCHW = 1,
+} cutlass_sys_tensor_layout_t;
+
+typedef struct {
+  int32_t n;
+  int32_t h;
+  int32_t w;
+  int32_t c;
+  cutlass_sys_tensor_layout_t src;
+  cutlass_sys_tensor_layout_t dst;
+  cutlass_sys_dtype_t element;
+} cutlass_sys_layout_transform_config_t;
+
+typedef struct {
+  int32_t extent[4];
+  int32_t perm[4];
+  cutlass_sys_dtype_t element;
+} cutlass_sys_permute_config_t;
+
+// dst[i] = convert(scale * src[i]) over `count` elements.
+typedef struct {
+  int64_t count;
+  cutlass_sys_dtype_t src;
+  cutlass_sys_dtype_t dst;
+  float scale;
+} cutlass_sys_convert_config_t;
+
+typedef struct {
+  const void* src;
+  void* dst;
+} cutlass_sys_transform_operands_t;
+
+size_t cutlass_sys_layout_transform_workspace_size(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_layout_transform_config_t* config);
+
+int cutlass_sys_layout_transform_can_implement(const cutlass_sys_context_t* context,
+                                               const cutlass_sys_layout_transform_config_t* config);
+
+int cutlass_sys_layout_transform(const cutlass_sys_context_t* context,
+                                 const cutlass_sys_layout_transform_config_t* config,
+                                 const cutlass_sys_transform_operands_t* operands,
+                                 void* workspace,
+                                 cutlass_sys_stream_t stream);
+
+size_t cutlass_sys_permute_workspace_size(const cutlass_sys_context_t* context,
+                                          const cutlass_sys_permute_config_t* config);
+
+int cutlass_sys_permute_can_implement(const cutlass_sys_context_t* context,
+                                      const cutlass_sys_permute_config_t* config);
+
+int cutlass_sys_permute(const cutlass_sys_context_t* context,
+                        const cutlass_sys_permute_config_t* config,
+                        const cutlass_sys_transform_operands_t* operands,
+                        void* workspace,
+                        cutlass_sys_stream_t stream);
+
+size_t cutlass_sys_convert_workspace_size(const cutlass_sys_context_t* context,
+                                          const cutlass_sys_convert_config_t* config);
+
+int cutlass_sys_convert_can_implement(const cutlass_sys_context_t* context,
+                                      const cutlass_sys_convert_config_t* config);
+
+int cutlass_sys_convert(const cutlass_sys_context_t* context,
+                        const cutlass_sys_convert_config_t* config,
+                        const cutlass_sys_transform_operands_t* operands,
+                        void* workspace,
+                        cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// CUTLASS library (src/manifest.rs, `library` feature)
+// ---------------------------------------------------------------------------
+
+// Fields documented as a cutlass::library enum hold its integer value
+// (src/library.rs mirrors the declaration order).
+
+typedef struct cutlass_sys_manifest cutlass_sys_manifest_t;  // cutlass::library::Manifest
+typedef struct cutlass_sys_operation cutlass_sys_operation_t;  // cutlass::library::Operation
+typedef struct cutlass_sys_library_handle cutlass_sys_library_handle_t;  // library::Handle
+
+typedef enum {
+  CUTLASS_SYS_GEMM_UNIVERSAL_MODE_GEMM = 0,
+  CUTLASS_SYS_GEMM_UNIVERSAL_MODE_GEMM_SPLIT_K_PARALLEL = 1,
+  CUTLASS_SYS_GEMM_UNIVERSAL_MODE_BATCHED = 2,
+  CUTLASS_SYS_GEMM_UNIVERSAL_MODE_ARRAY = 3,
+} cutlass_sys_gemm_universal_mode_t;
+
+typedef struct {
+  int32_t element;  // NumericTypeID
+  int32_t layout;   // LayoutTypeID
+  int32_t alignment;
+} cutlass_sys_tensor_description_t;
+
+typedef struct {
+  const char* name;
+  int32_t kind;      // OperationKind
+  int32_t provider;  // Provider
+  cutlass_sys_gemm_coord_t threadblock_shape;
+  int32_t threadblock_stages;
+  cutlass_sys_gemm_coord_t warp_count;
+  cutlass_sys_gemm_coord_t cluster_shape;
+  cutlass_sys_gemm_coord_t instruction_shape;
+  int32_t element_accumulator;  // NumericTypeID
+  int32_t opcode_class;         // OpcodeClassID
+  int32_t minimum_compute_capability;
+  int32_t maximum_compute_capability;
+} cutlass_sys_operation_description_t;
+
+typedef struct {
+  int32_t gemm_kind;  // GemmKind
+  cutlass_sys_tensor_description_t a;
+  cutlass_sys_tensor_description_t b;
+  cutlass_sys_tensor_description_t c;
+  cutlass_sys_tensor_description_t d;
+  int32_t element_epilogue;  // NumericTypeID
+  int32_t transform_a;       // ComplexTransform
+  int32_t transform_b;       // ComplexTransform
+} cutlass_sys_gemm_description_t;
+
+// alpha and beta point to host scalars of the epilogue's element type.
+typedef struct {
+  cutlass_sys_gemm_universal_mode_t mode;
+  cutlass_sys_gemm_coord_t problem;
+  int32_t batch_count;
+  const void* alpha;
+  const void* beta;
+  const void* a;
+  const void* b;
+  const void* c;
+  void* d;
+  int64_t lda;
+  int64_t ldb;
+  int64_t ldc;
+  int64_t ldd;
+  int64_t batch_stride_a;
+  int64_t batch_stride_b;
+  int64_t batch_stride_c;
+  int64_t batch_stride_d;
+} cutlass_sys_library_gemm_arguments_t;
+
+// The manifest owns its operations; operation pointers stay valid until it is
+// destroyed.
+int cutlass_sys_manifest_create(cutlass_sys_manifest_t** manifest);
+void cutlass_sys_manifest_destroy(cutlass_sys_manifest_t* manifest);
+size_t cutlass_sys_manifest_operation_count(const cutlass_sys_manifest_t* manifest);
+const cutlass_sys_operation_t* cutlass_sys_manifest_operation(
+    const cutlass_sys_manifest_t* manifest, size_t index);
+
+void cutlass_sys_operation_description(const cutlass_sys_operation_t* operation,
+                                       cutlass_sys_operation_description_t* description);
+
+// kErrorNotSupported unless `operation` is a GEMM.
+int cutlass_sys_operation_gemm_description(const cutlass_sys_operation_t* operation,
+                                           cutlass_sys_gemm_description_t* description);
+
+// Only universal GEMM operations (GemmKind::kUniversal) can be launched; a
+// device outside the operation's compute capability range returns
+// kErrorArchMismatch.
+size_t cutlass_sys_operation_gemm_workspace_size(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_operation_t* operation,
+    const cutlass_sys_library_gemm_arguments_t* arguments);
+
+int cutlass_sys_operation_gemm_can_implement(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_operation_t* operation,
+    const cutlass_sys_library_gemm_arguments_t* arguments);
+
+int cutlass_sys_operation_gemm(const cutlass_sys_context_t* context,
+                               const cutlass_sys_operation_t* operation,
+                               const cutlass_sys_library_gemm_arguments_t* arguments,
+                               void* workspace,
+                               cutlass_sys_stream_t stream);
+
+// Unlike the rest of the shim, a handle owns a device workspace of
+// `workspace_size` bytes, allocated on the context's device, and selects the
+// operation of each call itself from CUTLASS's process-wide manifest.
+int cutlass_sys_library_handle_create(const cutlass_sys_context_t* context,
+                                      cutlass_sys_stream_t stream,
+                                      size_t workspace_size,
+                                      cutlass_sys_library_handle_t** handle);
+void cutlass_sys_library_handle_destroy(cutlass_sys_library_handle_t* handle);
+void cutlass_sys_library_handle_set_stream(cutlass_sys_library_handle_t* handle,
+                                           cutlass_sys_stream_t stream);
+size_t cutlass_sys_library_handle_workspace_size(const cutlass_sys_library_handle_t* handle);
+int cutlass_sys_library_handle_set_workspace_size(cutlass_sys_library_handle_t* handle,
+                                                  size_t workspace_size);
+
+// Element and layout of each operand, the transforms of A and B and the
+// scalar type (element_epilogue) come from `types`; alignments and gemm_kind
+// are ignored.
+int cutlass_sys_library_handle_gemm(cutlass_sys_library_handle_t* handle,
+                                    int32_t element_compute,
+                                    const cutlass_sys_gemm_description_t* types,
+                                    const cutlass_sys_library_gemm_arguments_t* arguments);
+
+// The operation the last successful call ran, or null.
+const cutlass_sys_operation_t* cutlass_sys_library_handle_last_operation(
+    const cutlass_sys_library_handle_t* handle);
+
+// ---------------------------------------------------------------------------
+// Host reference implementations (src/reference.rs, `reference` feature)
+// ---------------------------------------------------------------------------
+
+// Defined only when the shim is built with the `reference` feature. The host
+// ones run cutlass::reference::host on the CPU, so every pointer is host
+// memory. The GEMMs take data-parallel configs without a fused epilogue; a
+// null C reads D.
+
+int cutlass_sys_reference_gemm(const cutlass_sys_gemm_config_t* config,
+                               const cutlass_sys_gemm_operands_t* operands);
+
+int cutlass_sys_reference_conv_fprop(const cutlass_sys_conv_config_t* config,
+                                     const cutlass_sys_conv_operands_t* operands);
+int cutlass_sys_reference_conv_dgrad(const cutlass_sys_conv_config_t* config,
+                                     const cutlass_sys_conv_operands_t* operands);
+int cutlass_sys_reference_conv_wgrad(const cutlass_sys_conv_config_t* config,
+                                     const cutlass_sys_conv_operands_t* operands);
+
+// Device references (cutlass::reference::device) on device pointers: one
+// thread per output element, slow but independent of the kernels' tiling. The
+// GEMM writes D with leading dimension ldd and synchronizes `stream`, since
+// the reference runs on the legacy default stream; the convolutions run on
+// `stream` without groups. These also back the configs' `verify` flag.
+int cutlass_sys_reference_gemm_device(const cutlass_sys_context_t* context,
+                                      const cutlass_sys_gemm_config_t* config,
+                                      const cutlass_sys_gemm_operands_t* operands,
+                                      cutlass_sys_stream_t stream);
+
+int cutlass_sys_reference_conv_device_fprop(const cutlass_sys_context_t* context,
+                                            const cutlass_sys_conv_config_t* config,
+                                            const cutlass_sys_conv_operands_t* operands,
+                                            cutlass_sys_stream_t stream);
+int cutlass_sys_reference_conv_device_dgrad(const cutlass_sys_context_t* context,
+                                            const cutlass_sys_conv_config_t* config,
+                                            const cutlass_sys_conv_operands_t* operands,
+                                            cutlass_sys_stream_t stream);
+int cutlass_sys_reference_conv_device_wgrad(const cutlass_sys_context_t* context,
+                                            const cutlass_sys_conv_config_t* config,
+                                            const cutlass_sys_conv_operands_t* operands,
+                                            cutlass_sys_stream_t stream);
+
+// ---------------------------------------------------------------------------
+// Device tensor fill and compare (src/fill.rs, `reference` feature)
+// ---------------------------------------------------------------------------
+
+// cutlass::reference::device fills of a row-major `rows` x `columns` matrix
+// with leading dimension `ld`, of F16, BF16, F32, S8 or F64 elements. Values
+// are converted from double; a non-negative `bits` rounds random values to
+// that many fractional bits.
+int cutlass_sys_tensor_fill(const cutlass_sys_context_t* context,
+                            cutlass_sys_dtype_t element,
+                            void* ptr,
+                            int32_t rows,
+                            int32_t columns,
+                            int64_t ld,
+                            double value,
+                            cutlass_sys_stream_t stream);
+
+int cutlass_sys_tensor_fill_uniform(const cutlass_sys_context_t* context,
+                                    cutlass_sys_dtype_t element,
+                                    void* ptr,
+                                    int32_t rows,
+                                    int32_t columns,
+                                    int64_t ld,
+                                    uint64_t seed,
+                                    double min,
+                                    double max,
+                                    int32_t bits,
+                                    cutlass_sys_stream_t stream);
+
+int cutlass_sys_tensor_fill_gaussian(const cutlass_sys_context_t* context,
+                                     cutlass_sys_dtype_t element,
+                                     void* ptr,
+                                     int32_t rows,
+                                     int32_t columns,
+                                     int64_t ld,
+                                     uint64_t seed,
+                                     double mean,
+                                     double stddev,
+                                     int32_t bits,
+                                     cutlass_sys_stream_t stream);
+
+// Compare `count` contiguous elements of `a` and `b`, exactly or within a
+// relative `epsilon` (differences of values below `nonzero_floor` count as
+// zero), writing the answer to `equal`. Both synchronize `stream`.
+int cutlass_sys_block_compare_equal(const cutlass_sys_context_t* context,
+                                    cutlass_sys_dtype_t element,
+                                    const void* a,
+                                    const void* b,
+                                    size_t count,
+                                    bool* equal,
+                                    cutlass_sys_stream_t stream);
+
+int cutlass_sys_block_compare_relatively_equal(const cutlass_sys_context_t* context,
+                                               cutlass_sys_dtype_t element,
+                                               const void* a,
+                                               const void* b,
+                                               size_t count,
+                                               double epsilon,
+                                               double nonzero_floor,
+                                               bool* equal,
+                                               cutlass_sys_stream_t stream);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
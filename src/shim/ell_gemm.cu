@@ -0,0 +1,117 @@
+// Blocked-ELL sparse GEMM entry points (src/ell_gemm.rs).
+//
+// Instantiates CUTLASS's EllGemm (example 43) with A sparse: the mainloop
+// reads each strip's block-column indices and loads only the stored blocks
+// of A and the rows of B they multiply.
+
+#include <type_traits>
+
+#include <cutlass/epilogue/thread/linear_combination.h>
+#include <cutlass/gemm/device/ell_gemm.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+// A, B, C and D row-major, with the SM80 tile configuration of the dense
+// kernels.
+template <typename Element>
+using EllGemm = cutlass::gemm::device::EllGemm<
+    Element, cutlass::layout::RowMajor,
+    Element, cutlass::layout::RowMajor,
+    typename Sm80TensorOpTraits<Element>::ElementC, cutlass::layout::RowMajor,
+    typename Sm80TensorOpTraits<Element>::ElementAccumulator,
+    typename Sm80TensorOpTraits<Element>::OperatorClass, cutlass::arch::Sm80,
+    typename Sm80TensorOpTraits<Element>::ThreadblockShape,
+    typename Sm80TensorOpTraits<Element>::WarpShape,
+    typename Sm80TensorOpTraits<Element>::InstructionShape,
+    cutlass::epilogue::thread::LinearCombination<
+        typename Sm80TensorOpTraits<Element>::ElementC, Sm80TensorOpTraits<Element>::kAlignmentC,
+        typename Sm80TensorOpTraits<Element>::ElementAccumulator, float>,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+    Sm80TensorOpTraits<Element>::kStages,
+    Sm80TensorOpTraits<Element>::kAlignmentA,
+    Sm80TensorOpTraits<Element>::kAlignmentB,
+    false,
+    typename Sm80TensorOpTraits<Element>::MathOperator,
+    true>;
+
+// Mirrors BlockedEll::check.
+bool valid_ell(const cutlass_sys_ell_gemm_config_t& cfg) {
+  const cutlass_sys_blocked_ell_t& ell = cfg.ell;
+  int bs = ell.block_size;
+  return bs > 0 && (bs & (bs - 1)) == 0 && cfg.problem.m % bs == 0 &&
+         cfg.problem.k % bs == 0 && ell.columns > 0 && ell.columns % bs == 0 &&
+         ell.columns <= cfg.problem.k && cfg.lda >= ell.columns;
+}
+
+cutlass::Status ell_gemm(const cutlass_sys_ell_gemm_config_t& cfg,
+                         const cutlass_sys_ell_gemm_operands_t* ops,
+                         void* workspace,
+                         cudaStream_t stream,
+                         size_t* workspace_size) {
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (std::is_same_v<Element, int8_t>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      using Gemm = EllGemm<Element>;
+      using ElementC = typename Gemm::ElementC;
+      if (!valid_ell(cfg)) {
+        return cutlass::Status::kErrorInvalidProblem;
+      }
+      cutlass_sys_ell_gemm_operands_t o = ops ? *ops : cutlass_sys_ell_gemm_operands_t{};
+      typename Gemm::Arguments args{
+          to_cutlass(cfg.problem),
+          {static_cast<const Element*>(o.a), typename Gemm::LayoutA(cfg.lda)},
+          {static_cast<const Element*>(o.b), typename Gemm::LayoutB(cfg.ldb)},
+          {static_cast<const ElementC*>(o.c), typename Gemm::LayoutC(cfg.ldc)},
+          {static_cast<ElementC*>(o.d), typename Gemm::LayoutC(cfg.ldd)},
+          o.column_indices,
+          cfg.ell.columns,
+          cfg.ell.block_size,
+          cfg.ell.index_base,
+          {cfg.alpha, cfg.beta},
+      };
+      return query_or_run<Gemm>(args, workspace, stream, workspace_size);
+    }
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_ell_gemm_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_ell_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::ell_gemm(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_ell_gemm_can_implement(const cutlass_sys_context_t* context,
+                                                  const cutlass_sys_ell_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::ell_gemm(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_ell_gemm(const cutlass_sys_context_t* context,
+                                    const cutlass_sys_ell_gemm_config_t* config,
+                                    const cutlass_sys_ell_gemm_operands_t* operands,
+                                    void* workspace,
+                                    cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::ell_gemm(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
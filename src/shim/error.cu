@@ -0,0 +1,15 @@
+// Error strings (src/error.rs).
+
+#include "common.cuh"
+
+extern "C" const char* cutlass_sys_status_string(int status) {
+  return cutlass::cutlassGetStatusString(static_cast<cutlass::Status>(status));
+}
+
+extern "C" const char* cutlass_sys_cuda_error_name(int error) {
+  return cudaGetErrorName(static_cast<cudaError_t>(error));
+}
+
+extern "C" const char* cutlass_sys_cuda_error_string(int error) {
+  return cudaGetErrorString(static_cast<cudaError_t>(error));
+}
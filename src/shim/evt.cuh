@@ -0,0 +1,291 @@
+// Building blocks for SM80 epilogue visitor trees (EVT).
+//
+// Nodes are composed with Sm80EVT<Node, Children...>, whose Arguments are
+// {children..., node}. All matrix operands are row-major with a batch stride
+// as the third mode.
+
+#pragma once
+
+#include <cute/tensor.hpp>
+#include <cutlass/epilogue/thread/activation.h>
+#include <cutlass/epilogue/threadblock/fusion/visitors.hpp>
+#include <cutlass/float8.h>
+#include <cutlass/functional.h>
+#include <cutlass/gemm/device/gemm_universal_adapter.h>
+#include <cutlass/gemm/kernel/default_gemm_universal.h>
+#include <cutlass/gemm/kernel/default_gemm_universal_with_visitor.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+namespace evt {
+
+using cutlass::epilogue::threadblock::Sm80EVT;
+
+constexpr int kEpilogueStages = 1;
+
+using ElementCompute = float;
+
+using MatrixStride = cute::Stride<int64_t, cute::_1, int64_t>;
+using RowStride = cute::Stride<cute::_0, cute::_1, int32_t>;
+using ColStride = cute::Stride<cute::_1, cute::_0, int32_t>;
+
+// The warp shape of a SIMT element type (f32), tagged so the thread map
+// below can tell it apart from a tensor-op one.
+template <typename Element>
+struct SimtWarpShape : Sm80Traits<Element>::WarpShape {};
+
+template <typename Element>
+using EpilogueWarpShape = std::conditional_t<
+    std::is_same_v<typename Sm80Traits<Element>::OperatorClass, cutlass::arch::OpClassSimt>,
+    SimtWarpShape<Element>, typename Sm80Traits<Element>::WarpShape>;
+
+// Epilogue of the plain SIMT GEMM, whose output thread map the visitor nodes
+// of a SIMT element type must share.
+template <typename Element>
+using SimtEpilogue = typename cutlass::gemm::kernel::DefaultGemmUniversal<
+    Element, cutlass::layout::RowMajor, cutlass::ComplexTransform::kNone,
+    Sm80Traits<Element>::kAlignmentA,
+    Element, typename Sm80Traits<Element>::LayoutB, cutlass::ComplexTransform::kNone,
+    Sm80Traits<Element>::kAlignmentB,
+    typename Sm80Traits<Element>::ElementC, cutlass::layout::RowMajor,
+    typename Sm80Traits<Element>::ElementAccumulator,
+    cutlass::arch::OpClassSimt, cutlass::arch::Sm80,
+    typename Sm80Traits<Element>::ThreadblockShape,
+    typename Sm80Traits<Element>::WarpShape,
+    typename Sm80Traits<Element>::InstructionShape,
+    cutlass::epilogue::thread::LinearCombination<
+        typename Sm80Traits<Element>::ElementC, Sm80Traits<Element>::kAlignmentC,
+        typename Sm80Traits<Element>::ElementAccumulator, ElementCompute>,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+    Sm80Traits<Element>::kStages,
+    typename Sm80Traits<Element>::MathOperator>::GemmKernel::Epilogue;
+
+}  // namespace evt
+}  // namespace cutlass_sys
+
+// OutputTileThreadLayout always derives from the tensor-op thread map; for a
+// SIMT warp shape, hand it the SIMT epilogue's map instead.
+namespace cutlass::epilogue::threadblock {
+
+template <typename ThreadblockShape, typename Element, int PartitionsK,
+          typename ElementOutput, int ElementsPerAccess>
+struct DefaultThreadMapTensorOp<ThreadblockShape, cutlass_sys::evt::SimtWarpShape<Element>,
+                                PartitionsK, ElementOutput, ElementsPerAccess> {
+  using Type =
+      typename cutlass_sys::evt::SimtEpilogue<Element>::OutputTileIterator::ThreadMap;
+};
+
+}  // namespace cutlass::epilogue::threadblock
+
+namespace cutlass_sys {
+namespace evt {
+
+template <typename Element>
+using ThreadMap = cutlass::epilogue::threadblock::OutputTileThreadLayout<
+    typename Sm80Traits<Element>::ThreadblockShape,
+    EpilogueWarpShape<Element>,
+    typename Sm80Traits<Element>::ElementC,
+    Sm80Traits<Element>::kAlignmentC,
+    kEpilogueStages>;
+
+using Accum = cutlass::epilogue::threadblock::VisitorAccFetch;
+
+using Scalar = cutlass::epilogue::threadblock::VisitorScalarBroadcast<ElementCompute>;
+
+template <template <class> class Fn>
+using Compute = cutlass::epilogue::threadblock::VisitorCompute<
+    Fn, ElementCompute, ElementCompute, cutlass::FloatRoundStyle::round_to_nearest>;
+
+// Full M x N tensor of the output element type.
+template <typename Element>
+using MatrixLoad = cutlass::epilogue::threadblock::VisitorAuxLoad<
+    ThreadMap<Element>, typename Sm80Traits<Element>::ElementC, MatrixStride>;
+
+// Length-N vector broadcast down the rows. Vectors default to the output
+// element type; scale vectors are f32.
+template <typename Element, typename T = typename Sm80Traits<Element>::ElementC>
+using RowBroadcast = cutlass::epilogue::threadblock::VisitorRowBroadcast<
+    ThreadMap<Element>, T, RowStride>;
+
+// Length-M vector broadcast across the columns.
+template <typename Element, typename T = typename Sm80Traits<Element>::ElementC>
+using ColBroadcast = cutlass::epilogue::threadblock::VisitorColBroadcast<
+    ThreadMap<Element>, T, ColStride>;
+
+template <typename Element>
+using MatrixStore = cutlass::epilogue::threadblock::VisitorAuxStore<
+    ThreadMap<Element>, typename Sm80Traits<Element>::ElementC,
+    cutlass::FloatRoundStyle::round_to_nearest, MatrixStride>;
+
+// Full M x N tensor of another type than the output element type, e.g. the
+// fp8 or int8 of a down-converted GEMM. Float-to-int8 stores saturate.
+template <typename Element, typename T>
+using MatrixStoreAs = cutlass::epilogue::threadblock::VisitorAuxStore<
+    ThreadMap<Element>, T, cutlass::FloatRoundStyle::round_to_nearest, MatrixStride>;
+
+// Largest absolute value of its input over the whole problem, folded into an
+// f32 in global memory with an atomic max. Passes the input through.
+template <typename Element>
+using AbsMax = cutlass::epilogue::threadblock::VisitorScalarReduction<
+    cutlass::maximum_absolute_value_reduction, ThreadMap<Element>, float, ElementCompute,
+    cutlass::FloatRoundStyle::round_to_nearest>;
+
+// alpha * acc + beta * C
+template <typename Element>
+using LinearCombination = Sm80EVT<
+    Compute<cutlass::multiply_add>,
+    Scalar,
+    MatrixLoad<Element>,
+    Sm80EVT<Compute<cutlass::multiplies>, Scalar, Accum>>;
+
+// Device-level GEMM whose epilogue is the visitor tree `Callbacks`.
+template <typename Element, typename Swizzle, typename Callbacks>
+using Gemm = cutlass::gemm::device::GemmUniversalAdapter<
+    typename cutlass::gemm::kernel::DefaultGemmWithVisitor<
+        Element, cutlass::layout::RowMajor, cutlass::ComplexTransform::kNone,
+        Sm80Traits<Element>::kAlignmentA,
+        Element, typename Sm80Traits<Element>::LayoutB,
+        cutlass::ComplexTransform::kNone, Sm80Traits<Element>::kAlignmentB,
+        typename Sm80Traits<Element>::ElementC, cutlass::layout::RowMajor,
+        Sm80Traits<Element>::kAlignmentC,
+        typename Sm80Traits<Element>::ElementAccumulator, ElementCompute,
+        typename Sm80Traits<Element>::OperatorClass, cutlass::arch::Sm80,
+        typename Sm80Traits<Element>::ThreadblockShape,
+        typename Sm80Traits<Element>::WarpShape,
+        typename Sm80Traits<Element>::InstructionShape,
+        Callbacks, Swizzle, Sm80Traits<Element>::kStages,
+        typename Sm80Traits<Element>::MathOperator,
+        kEpilogueStages>::GemmKernel>;
+
+// Wraps an elementwise functor template so it can travel through a generic
+// lambda: `[](auto fn) { Compute<decltype(fn)::template type> ... }`.
+template <template <class> class F>
+struct Fn {
+  template <class T>
+  using type = F<T>;
+};
+
+template <class T>
+using Maximum = cutlass::maximum<T>;
+
+// Calls `f` with the Fn<> tag of the functor implementing `activation`.
+template <typename F>
+cutlass::Status dispatch_activation(cutlass_sys_activation_t activation, F&& f) {
+  using namespace cutlass::epilogue::thread;
+  switch (activation) {
+    case CUTLASS_SYS_ACTIVATION_IDENTITY:
+      return f(Fn<Identity>{});
+    case CUTLASS_SYS_ACTIVATION_RELU:
+      return f(Fn<ReLu>{});
+    case CUTLASS_SYS_ACTIVATION_GELU:
+      return f(Fn<GELU>{});
+    case CUTLASS_SYS_ACTIVATION_SILU:
+      return f(Fn<SiLu>{});
+    case CUTLASS_SYS_ACTIVATION_TANH:
+      return f(Fn<Tanh>{});
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+// Calls `f` with the Fn<> tag of the functor implementing `op`.
+template <typename F>
+cutlass::Status dispatch_binary_op(cutlass_sys_binary_op_t op, F&& f) {
+  switch (op) {
+    case CUTLASS_SYS_BINARY_OP_ADD:
+      return f(Fn<cutlass::plus>{});
+    case CUTLASS_SYS_BINARY_OP_MUL:
+      return f(Fn<cutlass::multiplies>{});
+    case CUTLASS_SYS_BINARY_OP_MAX:
+      return f(Fn<Maximum>{});
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+// Argument helpers, in the same order as the aliases above.
+
+inline typename Scalar::Arguments scalar(float value) {
+  return {{value}};
+}
+
+template <typename Element>
+typename MatrixLoad<Element>::Arguments matrix_load(const void* ptr,
+                                                    int64_t ld,
+                                                    int64_t batch_stride) {
+  using ElementC = typename Sm80Traits<Element>::ElementC;
+  return {static_cast<const ElementC*>(ptr), ElementC(0),
+          {ld, cute::_1{}, batch_stride}};
+}
+
+// A null `ptr` broadcasts `null_default`.
+template <typename Element, typename T = typename Sm80Traits<Element>::ElementC>
+typename RowBroadcast<Element, T>::Arguments row_broadcast(const void* ptr,
+                                                           int32_t n,
+                                                           T null_default = T(0)) {
+  return {static_cast<const T*>(ptr), null_default, {cute::_0{}, cute::_1{}, n}};
+}
+
+template <typename Element, typename T = typename Sm80Traits<Element>::ElementC>
+typename ColBroadcast<Element, T>::Arguments col_broadcast(const void* ptr,
+                                                           int32_t m,
+                                                           T null_default = T(0)) {
+  return {static_cast<const T*>(ptr), null_default, {cute::_1{}, cute::_0{}, m}};
+}
+
+template <typename Element>
+typename MatrixStore<Element>::Arguments matrix_store(void* ptr,
+                                                      int64_t ld,
+                                                      int64_t batch_stride) {
+  using ElementC = typename Sm80Traits<Element>::ElementC;
+  return {static_cast<ElementC*>(ptr), {ld, cute::_1{}, batch_stride}};
+}
+
+template <typename Element, typename T>
+typename MatrixStoreAs<Element, T>::Arguments matrix_store_as(void* ptr,
+                                                              int64_t ld,
+                                                              int64_t batch_stride) {
+  return {static_cast<T*>(ptr), {ld, cute::_1{}, batch_stride}};
+}
+
+// A null `amax` skips the reduction.
+template <typename Element>
+typename AbsMax<Element>::Arguments abs_max(float* amax) {
+  return {amax, 0.0f};
+}
+
+template <typename Element>
+typename LinearCombination<Element>::Arguments linear_combination(
+    float alpha, float beta, const void* c, int64_t ldc, int64_t batch_stride_c) {
+  return {
+      scalar(beta),
+      matrix_load<Element>(c, ldc, batch_stride_c),
+      {scalar(alpha), {}, {}},
+      {},
+  };
+}
+
+// Runs a single (non-batched) problem through `GemmOp`, whose epilogue
+// arguments are `callbacks`.
+template <typename GemmOp, typename CallbackArgs>
+cutlass::Status run(const cutlass_sys_gemm_config_t& cfg,
+                    CallbackArgs const& callbacks,
+                    const void* a,
+                    const void* b,
+                    void* workspace,
+                    cudaStream_t stream,
+                    size_t* workspace_size) {
+  typename GemmOp::Arguments args(
+      cutlass::gemm::GemmUniversalMode::kGemm,
+      to_cutlass(cfg.problem),
+      cfg.split_k_slices,
+      callbacks,
+      a, b, nullptr, nullptr,
+      0, 0, 0, 0,
+      cfg.lda, cfg.ldb, 0, 0);
+  return query_or_run<GemmOp>(args, workspace, stream, workspace_size);
+}
+
+}  // namespace evt
+}  // namespace cutlass_sys
@@ -0,0 +1,198 @@
+// Device tensor fill and compare entry points (src/fill.rs, `reference`
+// feature).
+//
+// Thin wrappers over the cutlass::reference::device fill and block-compare
+// helpers from tools/util. The fills take a row-major matrix view, which the
+// Rust side also uses for column-major views by swapping the extents; the
+// comparisons take contiguous blocks and synchronize to read their result.
+
+#include "common.cuh"
+
+#if defined(CUTLASS_SYS_REFERENCE)
+
+#include <type_traits>
+
+#include <cutlass/util/reference/device/tensor_compare.h>
+#include <cutlass/util/reference/device/tensor_fill.h>
+
+namespace cutlass_sys {
+namespace {
+
+// Calls `f` with a value of the real element type named by `dtype`: the
+// element types of the device entry points plus F64.
+template <typename F>
+cutlass::Status dispatch_real(cutlass_sys_dtype_t dtype, F&& f) {
+  if (dtype == CUTLASS_SYS_DTYPE_F64) {
+    return f(double{});
+  }
+  return dispatch_dtype(dtype, f);
+}
+
+// `value` in `T`, through float for the narrow types, which lack a
+// conversion from double.
+template <typename T>
+T from_double(double value) {
+  if constexpr (std::is_same_v<T, double>) {
+    return value;
+  } else {
+    return T(static_cast<float>(value));
+  }
+}
+
+template <typename Element>
+cutlass::TensorView<Element, cutlass::layout::RowMajor> matrix_view(void* ptr,
+                                                                     int32_t rows,
+                                                                     int32_t columns,
+                                                                     int64_t ld) {
+  using RowMajor = cutlass::layout::RowMajor;
+  return cutlass::TensorView<Element, RowMajor>(static_cast<Element*>(ptr), RowMajor(ld),
+                                                cutlass::MatrixCoord(rows, columns));
+}
+
+cutlass::Status launch_status() {
+  return cudaGetLastError() == cudaSuccess ? cutlass::Status::kSuccess
+                                           : cutlass::Status::kErrorInternal;
+}
+
+bool valid_matrix(const void* ptr, int32_t rows, int32_t columns, int64_t ld) {
+  return rows >= 0 && columns >= 0 && ld >= columns && ld >= 1 &&
+         (ptr != nullptr || rows == 0 || columns == 0);
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" int cutlass_sys_tensor_fill(const cutlass_sys_context_t* context,
+                                       cutlass_sys_dtype_t element,
+                                       void* ptr,
+                                       int32_t rows,
+                                       int32_t columns,
+                                       int64_t ld,
+                                       double value,
+                                       cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  if (!cutlass_sys::valid_matrix(ptr, rows, columns, ld)) {
+    return static_cast<int>(cutlass::Status::kErrorInvalidProblem);
+  }
+  return static_cast<int>(cutlass_sys::dispatch_real(element, [&](auto e) {
+    using Element = decltype(e);
+    cutlass::reference::device::TensorFill(
+        cutlass_sys::matrix_view<Element>(ptr, rows, columns, ld),
+        cutlass_sys::from_double<Element>(value), static_cast<cudaStream_t>(stream));
+    return cutlass_sys::launch_status();
+  }));
+}
+
+extern "C" int cutlass_sys_tensor_fill_uniform(const cutlass_sys_context_t* context,
+                                               cutlass_sys_dtype_t element,
+                                               void* ptr,
+                                               int32_t rows,
+                                               int32_t columns,
+                                               int64_t ld,
+                                               uint64_t seed,
+                                               double min,
+                                               double max,
+                                               int32_t bits,
+                                               cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  if (!cutlass_sys::valid_matrix(ptr, rows, columns, ld) || !(min <= max)) {
+    return static_cast<int>(cutlass::Status::kErrorInvalidProblem);
+  }
+  return static_cast<int>(cutlass_sys::dispatch_real(element, [&](auto e) {
+    using Element = decltype(e);
+    using Real = typename cutlass::RealType<Element>::Type;
+    cutlass::reference::device::TensorFillRandomUniform(
+        cutlass_sys::matrix_view<Element>(ptr, rows, columns, ld), seed,
+        cutlass_sys::from_double<Real>(max), cutlass_sys::from_double<Real>(min), bits,
+        /*pnan=*/0.0, static_cast<cudaStream_t>(stream));
+    return cutlass_sys::launch_status();
+  }));
+}
+
+extern "C" int cutlass_sys_tensor_fill_gaussian(const cutlass_sys_context_t* context,
+                                                cutlass_sys_dtype_t element,
+                                                void* ptr,
+                                                int32_t rows,
+                                                int32_t columns,
+                                                int64_t ld,
+                                                uint64_t seed,
+                                                double mean,
+                                                double stddev,
+                                                int32_t bits,
+                                                cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  if (!cutlass_sys::valid_matrix(ptr, rows, columns, ld) || !(stddev >= 0.0)) {
+    return static_cast<int>(cutlass::Status::kErrorInvalidProblem);
+  }
+  return static_cast<int>(cutlass_sys::dispatch_real(element, [&](auto e) {
+    using Element = decltype(e);
+    using Real = typename cutlass::RealType<Element>::Type;
+    cutlass::reference::device::TensorFillRandomGaussian(
+        cutlass_sys::matrix_view<Element>(ptr, rows, columns, ld), seed,
+        cutlass_sys::from_double<Real>(mean), cutlass_sys::from_double<Real>(stddev), bits,
+        static_cast<cudaStream_t>(stream));
+    return cutlass_sys::launch_status();
+  }));
+}
+
+extern "C" int cutlass_sys_block_compare_equal(const cutlass_sys_context_t* context,
+                                               cutlass_sys_dtype_t element,
+                                               const void* a,
+                                               const void* b,
+                                               size_t count,
+                                               bool* equal,
+                                               cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  if (count != 0 && (a == nullptr || b == nullptr)) {
+    return static_cast<int>(cutlass::Status::kErrorInvalidProblem);
+  }
+  return static_cast<int>(cutlass_sys::dispatch_real(element, [&](auto e) {
+    using Element = decltype(e);
+    *equal = count == 0 || cutlass::reference::device::BlockCompareEqual(
+                               static_cast<const Element*>(a), static_cast<const Element*>(b),
+                               count, 0, 0, static_cast<cudaStream_t>(stream));
+    return cutlass_sys::launch_status();
+  }));
+}
+
+extern "C" int cutlass_sys_block_compare_relatively_equal(const cutlass_sys_context_t* context,
+                                                          cutlass_sys_dtype_t element,
+                                                          const void* a,
+                                                          const void* b,
+                                                          size_t count,
+                                                          double epsilon,
+                                                          double nonzero_floor,
+                                                          bool* equal,
+                                                          cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  if (count != 0 && (a == nullptr || b == nullptr)) {
+    return static_cast<int>(cutlass::Status::kErrorInvalidProblem);
+  }
+  return static_cast<int>(cutlass_sys::dispatch_real(element, [&](auto e) {
+    using Element = decltype(e);
+    *equal = count == 0 ||
+             cutlass::reference::device::BlockCompareRelativelyEqual(
+                 static_cast<const Element*>(a), static_cast<const Element*>(b), count,
+                 cutlass_sys::from_double<Element>(epsilon),
+                 cutlass_sys::from_double<Element>(nonzero_floor), 0, 0,
+                 static_cast<cudaStream_t>(stream));
+    return cutlass_sys::launch_status();
+  }));
+}
+
+#endif
@@ -0,0 +1,877 @@
+// Dense GEMM entry points (src/gemm.rs).
+
+#include <algorithm>
+#include <type_traits>
+
+#include <cutlass/epilogue/thread/linear_combination.h>
+#include <cutlass/epilogue/thread/linear_combination_clamp.h>
+#include <cutlass/gemm/device/gemm_grouped.h>
+#include <cutlass/gemm/device/gemm_universal.h>
+#include <cutlass/gemm/kernel/default_gemm_grouped.h>
+
+#include "common.cuh"
+#include "evt.cuh"
+#include "gemm.cuh"
+#include "sm100.cuh"
+#include "sm90.cuh"
+#include "verify.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+struct Launch {
+  const cutlass_sys_context_t& context;
+  const cutlass_sys_gemm_operands_t* ops;  // null when only querying
+  void* workspace;
+  cudaStream_t stream;
+  size_t* workspace_size;
+};
+
+// PlainGemm accumulating in ElementAccumulator and writing C and D as
+// ElementOutput, for the combinations of GemmTypes::SUPPORTED other than the
+// defaults. An int8 output saturates.
+template <typename Element, typename ElementAccumulator, typename ElementOutput,
+          typename Swizzle>
+using TypedGemm = cutlass::gemm::device::GemmUniversal<
+    Element, cutlass::layout::RowMajor,
+    Element, typename Sm80Traits<Element>::LayoutB,
+    ElementOutput, cutlass::layout::RowMajor,
+    ElementAccumulator,
+    typename Sm80Traits<Element>::OperatorClass, cutlass::arch::Sm80,
+    typename Sm80Traits<Element>::ThreadblockShape,
+    typename Sm80Traits<Element>::WarpShape,
+    typename Sm80Traits<Element>::InstructionShape,
+    std::conditional_t<
+        std::is_same_v<ElementOutput, int8_t>,
+        cutlass::epilogue::thread::LinearCombinationClamp<
+            ElementOutput, 128 / cutlass::sizeof_bits<ElementOutput>::value,
+            ElementAccumulator, float>,
+        cutlass::epilogue::thread::LinearCombination<
+            ElementOutput, 128 / cutlass::sizeof_bits<ElementOutput>::value,
+            ElementAccumulator, float>>,
+    Swizzle,
+    Sm80Traits<Element>::kStages,
+    Sm80Traits<Element>::kAlignmentA,
+    Sm80Traits<Element>::kAlignmentB,
+    typename Sm80Traits<Element>::MathOperator>;
+
+// The grouped GEMM kernel with PlainGemm's configuration, run on a single
+// problem as the SM80 persistent kernel: a grid sized to the device whose CTAs
+// each take every grid-size-th output tile until none are left.
+template <typename Element>
+using PersistentGemm = cutlass::gemm::device::GemmGrouped<
+    typename cutlass::gemm::kernel::DefaultGemmGrouped<
+        Element, cutlass::layout::RowMajor, cutlass::ComplexTransform::kNone,
+        Sm80Traits<Element>::kAlignmentA,
+        Element, typename Sm80Traits<Element>::LayoutB, cutlass::ComplexTransform::kNone,
+        Sm80Traits<Element>::kAlignmentB,
+        typename Sm80Traits<Element>::ElementC, cutlass::layout::RowMajor,
+        typename Sm80Traits<Element>::ElementAccumulator,
+        typename Sm80Traits<Element>::OperatorClass, cutlass::arch::Sm80,
+        typename Sm80Traits<Element>::ThreadblockShape,
+        typename Sm80Traits<Element>::WarpShape,
+        typename Sm80Traits<Element>::InstructionShape,
+        cutlass::epilogue::thread::LinearCombination<
+            typename Sm80Traits<Element>::ElementC, Sm80Traits<Element>::kAlignmentC,
+            typename Sm80Traits<Element>::ElementAccumulator, float>,
+        cutlass::gemm::threadblock::GemmBatchedIdentityThreadblockSwizzle,
+        Sm80Traits<Element>::kStages,
+        cutlass::gemm::kernel::GroupScheduleMode::kDeviceOnly,
+        typename Sm80Traits<Element>::MathOperator>::GemmKernel>;
+
+// The one-entry problem and operand arrays the grouped kernel reads from
+// device memory, kept at the start of the workspace.
+template <typename Gemm>
+struct PersistentProblem {
+  cutlass::gemm::GemmCoord problem;
+  typename Gemm::ElementA* a;
+  typename Gemm::ElementB* b;
+  typename Gemm::ElementC* c;
+  typename Gemm::ElementC* d;
+  int64_t lda;
+  int64_t ldb;
+  int64_t ldc;
+  int64_t ldd;
+};
+
+#if defined(CUTLASS_SYS_SM75)
+// Kernel configuration of the Turing instantiations, keyed by operand type.
+// Turing has neither bf16 tensor cores nor asynchronous copies, so only f16
+// and s8 run there, on two-stage pipelined kernels.
+template <typename Element>
+struct Sm75Traits;
+
+template <>
+struct Sm75Traits<cutlass::half_t> {
+  using ElementC = cutlass::half_t;
+  using ElementAccumulator = float;
+  using LayoutB = cutlass::layout::RowMajor;
+  using MathOperator = cutlass::arch::OpMultiplyAdd;
+  using ThreadblockShape = cutlass::gemm::GemmShape<128, 128, 32>;
+  using WarpShape = cutlass::gemm::GemmShape<64, 64, 32>;
+  using InstructionShape = cutlass::gemm::GemmShape<16, 8, 8>;
+  static constexpr int kAlignmentA = 8;
+  static constexpr int kAlignmentB = 8;
+  static constexpr int kAlignmentC = 8;
+};
+
+template <>
+struct Sm75Traits<int8_t> {
+  using ElementC = int32_t;
+  using ElementAccumulator = int32_t;
+  using LayoutB = cutlass::layout::ColumnMajor;
+  using MathOperator = cutlass::arch::OpMultiplyAddSaturate;
+  using ThreadblockShape = cutlass::gemm::GemmShape<128, 128, 64>;
+  using WarpShape = cutlass::gemm::GemmShape<64, 64, 64>;
+  using InstructionShape = cutlass::gemm::GemmShape<8, 8, 16>;
+  static constexpr int kAlignmentA = 16;
+  static constexpr int kAlignmentB = 16;
+  static constexpr int kAlignmentC = 4;
+};
+
+template <typename Element>
+using Sm75Gemm = cutlass::gemm::device::GemmUniversal<
+    Element, cutlass::layout::RowMajor,
+    Element, typename Sm75Traits<Element>::LayoutB,
+    typename Sm75Traits<Element>::ElementC, cutlass::layout::RowMajor,
+    typename Sm75Traits<Element>::ElementAccumulator,
+    cutlass::arch::OpClassTensorOp, cutlass::arch::Sm75,
+    typename Sm75Traits<Element>::ThreadblockShape,
+    typename Sm75Traits<Element>::WarpShape,
+    typename Sm75Traits<Element>::InstructionShape,
+    cutlass::epilogue::thread::LinearCombination<
+        typename Sm75Traits<Element>::ElementC, Sm75Traits<Element>::kAlignmentC,
+        typename Sm75Traits<Element>::ElementAccumulator, float>,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+    2,
+    Sm75Traits<Element>::kAlignmentA,
+    Sm75Traits<Element>::kAlignmentB,
+    typename Sm75Traits<Element>::MathOperator>;
+#endif
+
+template <typename Gemm>
+cutlass::Status gemm(const cutlass_sys_gemm_config_t& cfg, const Launch& launch) {
+  cutlass_sys_gemm_operands_t ops = launch.ops ? *launch.ops : cutlass_sys_gemm_operands_t{};
+  typename Gemm::Arguments args(
+      cutlass::gemm::GemmUniversalMode::kGemm,
+      to_cutlass(cfg.problem),
+      cfg.split_k_slices,
+      {cfg.alpha, cfg.beta},
+      ops.a, ops.b, ops.c, ops.d,
+      0, 0, 0, 0,
+      cfg.lda, cfg.ldb, cfg.ldc, cfg.ldd);
+
+  return query_or_run<Gemm>(args, launch.workspace, launch.stream,
+                            launch.workspace_size);
+}
+
+// act(alpha * acc + beta * C + bias), with `Bias` a row or column broadcast
+// node. A null bias pointer broadcasts zeros.
+template <typename Element, typename Swizzle, typename Bias,
+          template <class> class Act>
+cutlass::Status gemm_fused(const cutlass_sys_gemm_config_t& cfg,
+                           const Launch& launch,
+                           typename Bias::Arguments const& bias_args) {
+  using Callbacks = evt::Sm80EVT<
+      evt::MatrixStore<Element>,
+      evt::Sm80EVT<evt::Compute<Act>,
+                   evt::Sm80EVT<evt::Compute<cutlass::plus>,
+                                evt::LinearCombination<Element>, Bias>>>;
+  using GemmOp = evt::Gemm<Element, Swizzle, Callbacks>;
+
+  cutlass_sys_gemm_operands_t ops = launch.ops ? *launch.ops : cutlass_sys_gemm_operands_t{};
+  typename Callbacks::Arguments callbacks{
+      {
+          {
+              evt::linear_combination<Element>(cfg.alpha, cfg.beta, ops.c,
+                                               cfg.ldc, cfg.ldc * cfg.problem.m),
+              bias_args,
+              {},
+          },
+          {},
+      },
+      evt::matrix_store<Element>(ops.d, cfg.ldd, cfg.ldd * cfg.problem.m),
+  };
+
+  return evt::run<GemmOp>(cfg, callbacks, ops.a, ops.b, launch.workspace,
+                          launch.stream, launch.workspace_size);
+}
+
+// op(act(alpha * acc + beta * C + bias), aux): gemm_fused with the full
+// M x N aux tensor combined into the result by `Op`.
+template <typename Element, typename Swizzle, typename Bias,
+          template <class> class Act, template <class> class Op>
+cutlass::Status gemm_aux(const cutlass_sys_gemm_config_t& cfg,
+                         const Launch& launch,
+                         typename Bias::Arguments const& bias_args) {
+  using Callbacks = evt::Sm80EVT<
+      evt::MatrixStore<Element>,
+      evt::Sm80EVT<
+          evt::Compute<Op>,
+          evt::Sm80EVT<evt::Compute<Act>,
+                       evt::Sm80EVT<evt::Compute<cutlass::plus>,
+                                    evt::LinearCombination<Element>, Bias>>,
+          evt::MatrixLoad<Element>>>;
+  using GemmOp = evt::Gemm<Element, Swizzle, Callbacks>;
+
+  cutlass_sys_gemm_operands_t ops = launch.ops ? *launch.ops : cutlass_sys_gemm_operands_t{};
+  typename Callbacks::Arguments callbacks{
+      {
+          {
+              {
+                  evt::linear_combination<Element>(cfg.alpha, cfg.beta, ops.c,
+                                                   cfg.ldc, cfg.ldc * cfg.problem.m),
+                  bias_args,
+                  {},
+              },
+              {},
+          },
+          evt::matrix_load<Element>(ops.aux, cfg.ldaux, cfg.ldaux * cfg.problem.m),
+          {},
+      },
+      evt::matrix_store<Element>(ops.d, cfg.ldd, cfg.ldd * cfg.problem.m),
+  };
+
+  return evt::run<GemmOp>(cfg, callbacks, ops.a, ops.b, launch.workspace,
+                          launch.stream, launch.workspace_size);
+}
+
+// convert(out_scale * act(alpha * acc + beta * C + bias)): gemm_fused with
+// the f32 result scaled and stored as `Out`, and its largest absolute value
+// before the scaling reduced into `amax` when that is not null.
+template <typename Element, typename Swizzle, typename Out, typename Bias,
+          template <class> class Act>
+cutlass::Status gemm_down_convert(const cutlass_sys_gemm_config_t& cfg,
+                                  const Launch& launch,
+                                  typename Bias::Arguments const& bias_args) {
+  using Callbacks = evt::Sm80EVT<
+      evt::MatrixStoreAs<Element, Out>,
+      evt::Sm80EVT<
+          evt::Compute<cutlass::multiplies>, evt::Scalar,
+          evt::Sm80EVT<
+              evt::AbsMax<Element>,
+              evt::Sm80EVT<evt::Compute<Act>,
+                           evt::Sm80EVT<evt::Compute<cutlass::plus>,
+                                        evt::LinearCombination<Element>, Bias>>>>>;
+  using GemmOp = evt::Gemm<Element, Swizzle, Callbacks>;
+
+  cutlass_sys_gemm_operands_t ops = launch.ops ? *launch.ops : cutlass_sys_gemm_operands_t{};
+  typename Callbacks::Arguments callbacks{
+      {
+          evt::scalar(cfg.out_scale),
+          {
+              {
+                  {
+                      evt::linear_combination<Element>(cfg.alpha, cfg.beta, ops.c,
+                                                       cfg.ldc, cfg.ldc * cfg.problem.m),
+                      bias_args,
+                      {},
+                  },
+                  {},
+              },
+              evt::abs_max<Element>(ops.amax),
+          },
+          {},
+      },
+      evt::matrix_store_as<Element, Out>(ops.d, cfg.ldd, cfg.ldd * cfg.problem.m),
+  };
+
+  return evt::run<GemmOp>(cfg, callbacks, ops.a, ops.b, launch.workspace,
+                          launch.stream, launch.workspace_size);
+}
+
+// act(alpha * row_scale * col_scale * acc + beta * C + bias) + zero_point,
+// with `Beta` a scalar or a row or column broadcast node. Scale vectors the
+// config does not use are passed as null and read as 1.
+template <typename Element, typename Swizzle, typename Beta, typename Bias,
+          typename ZeroPoint, template <class> class Act>
+cutlass::Status gemm_quant(const cutlass_sys_gemm_config_t& cfg,
+                           const Launch& launch,
+                           typename Beta::Arguments const& beta_args,
+                           typename Bias::Arguments const& bias_args,
+                           typename ZeroPoint::Arguments const& zero_point_args) {
+  using RowScale = evt::ColBroadcast<Element, float>;  // one value per row
+  using ColScale = evt::RowBroadcast<Element, float>;  // one value per column
+  using ScaledAcc = evt::Sm80EVT<
+      evt::Compute<cutlass::multiplies>, evt::Scalar,
+      evt::Sm80EVT<evt::Compute<cutlass::multiplies>, ColScale,
+                   evt::Sm80EVT<evt::Compute<cutlass::multiplies>, RowScale,
+                                evt::Accum>>>;
+  using Callbacks = evt::Sm80EVT<
+      evt::MatrixStore<Element>,
+      evt::Sm80EVT<
+          evt::Compute<cutlass::plus>,
+          evt::Sm80EVT<
+              evt::Compute<Act>,
+              evt::Sm80EVT<evt::Compute<cutlass::plus>,
+                           evt::Sm80EVT<evt::Compute<cutlass::multiply_add>,
+                                        Beta, evt::MatrixLoad<Element>,
+                                        ScaledAcc>,
+                           Bias>>,
+          ZeroPoint>>;
+  using GemmOp = evt::Gemm<Element, Swizzle, Callbacks>;
+
+  cutlass_sys_gemm_operands_t ops = launch.ops ? *launch.ops : cutlass_sys_gemm_operands_t{};
+  const bool per_row = cfg.scale == CUTLASS_SYS_SCALE_PER_ROW ||
+                       cfg.scale == CUTLASS_SYS_SCALE_PER_ROW_AND_COLUMN;
+  const bool per_col = cfg.scale == CUTLASS_SYS_SCALE_PER_COLUMN ||
+                       cfg.scale == CUTLASS_SYS_SCALE_PER_ROW_AND_COLUMN;
+
+  typename Callbacks::Arguments callbacks{
+      {
+          {
+              {
+                  {
+                      beta_args,
+                      evt::matrix_load<Element>(ops.c, cfg.ldc, cfg.ldc * cfg.problem.m),
+                      {
+                          evt::scalar(cfg.alpha),
+                          {
+                              evt::row_broadcast<Element, float>(
+                                  per_col ? ops.col_scale : nullptr, cfg.problem.n, 1.0f),
+                              {
+                                  evt::col_broadcast<Element, float>(
+                                      per_row ? ops.row_scale : nullptr, cfg.problem.m, 1.0f),
+                                  {},
+                                  {},
+                              },
+                              {},
+                          },
+                          {},
+                      },
+                      {},
+                  },
+                  bias_args,
+                  {},
+              },
+              {},
+          },
+          zero_point_args,
+          {},
+      },
+      evt::matrix_store<Element>(ops.d, cfg.ldd, cfg.ldd * cfg.problem.m),
+  };
+
+  return evt::run<GemmOp>(cfg, callbacks, ops.a, ops.b, launch.workspace,
+                          launch.stream, launch.workspace_size);
+}
+
+// Calls `f` with the Type<> of the bias node selected by `cfg.bias` and its
+// arguments. Without a bias, a null row broadcast adds zeros.
+template <typename Element, typename F>
+cutlass::Status dispatch_bias(const cutlass_sys_gemm_config_t& cfg,
+                              const Launch& launch,
+                              F&& f) {
+  const void* bias = launch.ops ? launch.ops->bias : nullptr;
+  switch (cfg.bias) {
+    case CUTLASS_SYS_BIAS_NONE:
+      return f(Type<evt::RowBroadcast<Element>>{},
+               evt::row_broadcast<Element>(nullptr, cfg.problem.n));
+    case CUTLASS_SYS_BIAS_ROW:
+      return f(Type<evt::RowBroadcast<Element>>{},
+               evt::row_broadcast<Element>(bias, cfg.problem.n));
+    case CUTLASS_SYS_BIAS_COLUMN:
+      return f(Type<evt::ColBroadcast<Element>>{},
+               evt::col_broadcast<Element>(bias, cfg.problem.m));
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+// Calls `f` with the Type<> of the beta node selected by `cfg.beta_mode` and
+// its arguments.
+template <typename Element, typename F>
+cutlass::Status dispatch_beta(const cutlass_sys_gemm_config_t& cfg,
+                              const Launch& launch,
+                              F&& f) {
+  const float* beta = launch.ops ? launch.ops->beta : nullptr;
+  switch (cfg.beta_mode) {
+    case CUTLASS_SYS_BETA_PER_TENSOR:
+      return f(Type<evt::Scalar>{}, evt::scalar(cfg.beta));
+    case CUTLASS_SYS_BETA_PER_ROW:
+      return f(Type<evt::ColBroadcast<Element, float>>{},
+               evt::col_broadcast<Element, float>(beta, cfg.problem.m));
+    case CUTLASS_SYS_BETA_PER_COLUMN:
+      return f(Type<evt::RowBroadcast<Element, float>>{},
+               evt::row_broadcast<Element, float>(beta, cfg.problem.n));
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+// Calls `f` with the Type<> of the zero-point node selected by
+// `cfg.zero_point_mode` and its arguments.
+template <typename Element, typename F>
+cutlass::Status dispatch_zero_point(const cutlass_sys_gemm_config_t& cfg,
+                                    const Launch& launch,
+                                    F&& f) {
+  const float* zero_point = launch.ops ? launch.ops->zero_point : nullptr;
+  switch (cfg.zero_point_mode) {
+    case CUTLASS_SYS_ZERO_POINT_NONE:
+      return f(Type<evt::Scalar>{}, evt::scalar(0.0f));
+    case CUTLASS_SYS_ZERO_POINT_PER_TENSOR:
+      return f(Type<evt::Scalar>{}, evt::scalar(cfg.zero_point));
+    case CUTLASS_SYS_ZERO_POINT_PER_ROW:
+      return f(Type<evt::ColBroadcast<Element, float>>{},
+               evt::col_broadcast<Element, float>(zero_point, cfg.problem.m));
+    case CUTLASS_SYS_ZERO_POINT_PER_COLUMN:
+      return f(Type<evt::RowBroadcast<Element, float>>{},
+               evt::row_broadcast<Element, float>(zero_point, cfg.problem.n));
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+// Calls `f` with the Fn<> of the binary op selected by `cfg.aux_op`.
+template <typename F>
+cutlass::Status dispatch_aux_op(const cutlass_sys_gemm_config_t& cfg, F&& f) {
+  switch (cfg.aux_op) {
+    case CUTLASS_SYS_AUX_OP_ADD:
+      return evt::dispatch_binary_op(CUTLASS_SYS_BINARY_OP_ADD, f);
+    case CUTLASS_SYS_AUX_OP_MUL:
+      return evt::dispatch_binary_op(CUTLASS_SYS_BINARY_OP_MUL, f);
+    case CUTLASS_SYS_AUX_OP_MAX:
+      return evt::dispatch_binary_op(CUTLASS_SYS_BINARY_OP_MAX, f);
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+// Calls `f` with the Type<> of the fp8 or int8 output `cfg.output` names.
+template <typename F>
+cutlass::Status dispatch_down_converted(const cutlass_sys_gemm_config_t& cfg, F&& f) {
+  switch (cfg.output) {
+    case CUTLASS_SYS_DTYPE_E4M3:
+      return f(Type<cutlass::float_e4m3_t>{});
+    case CUTLASS_SYS_DTYPE_E5M2:
+      return f(Type<cutlass::float_e5m2_t>{});
+    case CUTLASS_SYS_DTYPE_S8:
+      return f(Type<int8_t>{});
+    default:
+      return cutlass::Status::kErrorInvalidDataType;
+  }
+}
+
+// Runs the accumulator and output types of `cfg` when they are not the
+// defaults of `Element`, on the TypedGemm instantiated for them.
+template <typename Element, typename Swizzle>
+cutlass::Status dispatch_types(const cutlass_sys_gemm_config_t& cfg, const Launch& launch) {
+  auto is = [&](cutlass_sys_dtype_t accumulator, cutlass_sys_dtype_t output) {
+    return cfg.accumulator == accumulator && cfg.output == output;
+  };
+  if constexpr (std::is_same_v<Element, cutlass::half_t>) {
+    if (is(CUTLASS_SYS_DTYPE_F32, CUTLASS_SYS_DTYPE_F32)) {
+      return gemm<TypedGemm<Element, float, float, Swizzle>>(cfg, launch);
+    }
+    if (is(CUTLASS_SYS_DTYPE_F16, CUTLASS_SYS_DTYPE_F16)) {
+      return gemm<TypedGemm<Element, cutlass::half_t, cutlass::half_t, Swizzle>>(cfg, launch);
+    }
+  } else if constexpr (std::is_same_v<Element, cutlass::bfloat16_t>) {
+    if (is(CUTLASS_SYS_DTYPE_F32, CUTLASS_SYS_DTYPE_F32)) {
+      return gemm<TypedGemm<Element, float, float, Swizzle>>(cfg, launch);
+    }
+  } else if constexpr (std::is_same_v<Element, int8_t>) {
+    if (is(CUTLASS_SYS_DTYPE_S32, CUTLASS_SYS_DTYPE_S8)) {
+      return gemm<TypedGemm<Element, int32_t, int8_t, Swizzle>>(cfg, launch);
+    }
+    if (is(CUTLASS_SYS_DTYPE_S32, CUTLASS_SYS_DTYPE_F32)) {
+      return gemm<TypedGemm<Element, int32_t, float, Swizzle>>(cfg, launch);
+    }
+  }
+  return cutlass::Status::kErrorInvalidDataType;
+}
+
+// Runs the A and B layouts of `cfg` when they are not the defaults of
+// `Element`: NN, NT, TN or TT for the floating-point types. The int8 MMAs
+// only take TN, the default.
+template <typename Element, typename Swizzle>
+cutlass::Status dispatch_layouts(const cutlass_sys_gemm_config_t& cfg, const Launch& launch) {
+  if constexpr (std::is_same_v<Element, int8_t>) {
+    return cutlass::Status::kErrorInvalidLayout;
+  } else {
+    return dispatch_gemm_layouts(cfg, [&](auto layout_a, auto layout_b) {
+      using Gemm = PlainGemm<Element, Swizzle, decltype(layout_a), decltype(layout_b)>;
+      return gemm<Gemm>(cfg, launch);
+    });
+  }
+}
+
+template <typename Element, typename Swizzle>
+cutlass::Status dispatch_epilogue(const cutlass_sys_gemm_config_t& cfg,
+                                  const Launch& launch) {
+  const bool quantized = cfg.scale != CUTLASS_SYS_SCALE_NONE ||
+                         cfg.beta_mode != CUTLASS_SYS_BETA_PER_TENSOR ||
+                         cfg.zero_point_mode != CUTLASS_SYS_ZERO_POINT_NONE;
+  const bool aux = cfg.aux_op != CUTLASS_SYS_AUX_OP_NONE;
+  if (quantized && aux) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  const bool plain = !quantized && !aux && cfg.activation == CUTLASS_SYS_ACTIVATION_IDENTITY &&
+                     cfg.bias == CUTLASS_SYS_BIAS_NONE;
+  if (down_converted_gemm_output(cfg)) {
+    if constexpr (std::is_same_v<Element, cutlass::half_t> ||
+                  std::is_same_v<Element, cutlass::bfloat16_t>) {
+      if (quantized || aux || !default_gemm_layouts(cfg)) {
+        return cutlass::Status::kErrorNotSupported;
+      }
+      return dispatch_down_converted(cfg, [&](auto out) {
+        using Out = typename decltype(out)::type;
+        return evt::dispatch_activation(cfg.activation, [&](auto act) {
+          using Act = decltype(act);
+          return dispatch_bias<Element>(cfg, launch, [&](auto bias, auto const& bias_args) {
+            using Bias = typename decltype(bias)::type;
+            return gemm_down_convert<Element, Swizzle, Out, Bias, Act::template type>(
+                cfg, launch, bias_args);
+          });
+        });
+      });
+    }
+  }
+  if (cfg.out_scale != 1.0f) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  // The fused epilogues compute in the default types and read the default
+  // layouts only, and so do the kernels of other output types.
+  if (!default_gemm_layouts(cfg)) {
+    return plain && default_gemm_types(cfg) ? dispatch_layouts<Element, Swizzle>(cfg, launch)
+                                            : cutlass::Status::kErrorNotSupported;
+  }
+  if (!default_gemm_types(cfg)) {
+    return plain ? dispatch_types<Element, Swizzle>(cfg, launch)
+                 : cutlass::Status::kErrorNotSupported;
+  }
+  if (plain) {
+    return gemm<PlainGemm<Element, Swizzle>>(cfg, launch);
+  }
+
+  return evt::dispatch_activation(cfg.activation, [&](auto act) {
+    using Act = decltype(act);
+    return dispatch_bias<Element>(cfg, launch, [&](auto bias, auto const& bias_args) {
+      using Bias = typename decltype(bias)::type;
+      if (aux) {
+        return dispatch_aux_op(cfg, [&](auto op) {
+          using Op = decltype(op);
+          return gemm_aux<Element, Swizzle, Bias, Act::template type, Op::template type>(
+              cfg, launch, bias_args);
+        });
+      }
+      if (!quantized) {
+        return gemm_fused<Element, Swizzle, Bias, Act::template type>(cfg, launch,
+                                                                      bias_args);
+      }
+      return dispatch_beta<Element>(cfg, launch, [&](auto beta, auto const& beta_args) {
+        using Beta = typename decltype(beta)::type;
+        return dispatch_zero_point<Element>(
+            cfg, launch, [&](auto zero_point, auto const& zero_point_args) {
+              using ZeroPoint = typename decltype(zero_point)::type;
+              return gemm_quant<Element, Swizzle, Beta, Bias, ZeroPoint, Act::template type>(
+                  cfg, launch, beta_args, bias_args, zero_point_args);
+            });
+      });
+    });
+  });
+}
+
+// A single-column problem with contiguous B, C and D and a plain epilogue is
+// a GEMV, which the GEMV kernel runs far faster than a GEMM tile would.
+bool is_gemv(const cutlass_sys_gemm_config_t& cfg) {
+  return cfg.problem.n == 1 && cfg.scheduler == CUTLASS_SYS_TILE_SCHEDULER_DATA_PARALLEL &&
+         cfg.split_k_slices == 1 && cfg.cluster_shape == CUTLASS_SYS_CLUSTER_SHAPE_DEFAULT &&
+         cfg.bias == CUTLASS_SYS_BIAS_NONE &&
+         cfg.activation == CUTLASS_SYS_ACTIVATION_IDENTITY &&
+         cfg.scale == CUTLASS_SYS_SCALE_NONE && cfg.beta_mode == CUTLASS_SYS_BETA_PER_TENSOR &&
+         cfg.zero_point_mode == CUTLASS_SYS_ZERO_POINT_NONE &&
+         cfg.aux_op == CUTLASS_SYS_AUX_OP_NONE && default_gemm_types(cfg) &&
+         default_gemm_layouts(cfg) &&
+         (cfg.element == CUTLASS_SYS_DTYPE_S8 || cfg.ldb == 1) && cfg.ldc == 1 &&
+         cfg.ldd == 1;
+}
+
+cutlass_sys_gemv_config_t as_gemv(const cutlass_sys_gemm_config_t& cfg) {
+  return {cfg.problem.m, cfg.problem.k, cfg.element, 1, cfg.lda, 0, 0, 0, 0,
+          cfg.alpha, cfg.beta};
+}
+
+// Turing runs data-parallel launches with a plain epilogue only.
+cutlass::Status dispatch_sm75(const cutlass_sys_gemm_config_t& cfg, const Launch& launch) {
+#if defined(CUTLASS_SYS_SM75)
+  if (cfg.scheduler != CUTLASS_SYS_TILE_SCHEDULER_DATA_PARALLEL ||
+      cfg.cluster_shape != CUTLASS_SYS_CLUSTER_SHAPE_DEFAULT ||
+      cfg.bias != CUTLASS_SYS_BIAS_NONE || cfg.activation != CUTLASS_SYS_ACTIVATION_IDENTITY ||
+      cfg.scale != CUTLASS_SYS_SCALE_NONE || cfg.beta_mode != CUTLASS_SYS_BETA_PER_TENSOR ||
+      cfg.zero_point_mode != CUTLASS_SYS_ZERO_POINT_NONE ||
+      cfg.aux_op != CUTLASS_SYS_AUX_OP_NONE || !default_gemm_types(cfg) ||
+      !default_gemm_layouts(cfg)) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  switch (cfg.element) {
+    case CUTLASS_SYS_DTYPE_F16:
+      return gemm<Sm75Gemm<cutlass::half_t>>(cfg, launch);
+    case CUTLASS_SYS_DTYPE_S8:
+      return gemm<Sm75Gemm<int8_t>>(cfg, launch);
+    default:
+      return cutlass::Status::kErrorInvalidDataType;
+  }
+#else
+  (void)cfg;
+  (void)launch;
+  return cutlass::Status::kErrorArchMismatch;
+#endif
+}
+
+// `cfg` on PersistentGemm, its problem and operand arrays first copied into
+// the workspace on the launch stream.
+template <typename Element>
+cutlass::Status gemm_persistent(const cutlass_sys_gemm_config_t& cfg, const Launch& launch) {
+  using Gemm = PersistentGemm<Element>;
+  using Problem = PersistentProblem<Gemm>;
+  using Traits = Sm80Traits<Element>;
+  using Shape = typename Traits::ThreadblockShape;
+  constexpr size_t kProblemBytes = (sizeof(Problem) + 255) / 256 * 256;
+
+  // The grouped kernel's can_implement checks neither extents nor alignment.
+  const cutlass_sys_gemm_coord_t& p = cfg.problem;
+  if (p.m <= 0 || p.n <= 0 || p.k <= 0) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  int64_t contiguous_b =
+      std::is_same_v<typename Traits::LayoutB, cutlass::layout::RowMajor> ? p.n : p.k;
+  if (p.k % Traits::kAlignmentA != 0 || cfg.lda % Traits::kAlignmentA != 0 ||
+      contiguous_b % Traits::kAlignmentB != 0 || cfg.ldb % Traits::kAlignmentB != 0 ||
+      p.n % Traits::kAlignmentC != 0 || cfg.ldc % Traits::kAlignmentC != 0 ||
+      cfg.ldd % Traits::kAlignmentC != 0) {
+    return cutlass::Status::kErrorMisalignedOperand;
+  }
+
+  cutlass_sys_gemm_operands_t ops = launch.ops ? *launch.ops : cutlass_sys_gemm_operands_t{};
+  Problem host{to_cutlass(cfg.problem),
+               static_cast<typename Gemm::ElementA*>(const_cast<void*>(ops.a)),
+               static_cast<typename Gemm::ElementB*>(const_cast<void*>(ops.b)),
+               static_cast<typename Gemm::ElementC*>(const_cast<void*>(ops.c)),
+               static_cast<typename Gemm::ElementC*>(ops.d),
+               cfg.lda, cfg.ldb, cfg.ldc, cfg.ldd};
+  int64_t tiles = (int64_t(p.m) + Shape::kM - 1) / Shape::kM *
+                  ((int64_t(p.n) + Shape::kN - 1) / Shape::kN);
+  int occupancy = Gemm::maximum_active_blocks();
+  if (occupancy <= 0) {
+    return cutlass::Status::kErrorInternal;
+  }
+  int threadblocks = static_cast<int>(
+      std::max<int64_t>(1, std::min<int64_t>(tiles, int64_t(occupancy) * launch.context.sm_count)));
+
+  Problem* device = launch.workspace_size == nullptr ? static_cast<Problem*>(launch.workspace)
+                                                     : nullptr;
+  typename Gemm::Arguments args(
+      device ? &device->problem : nullptr, 1, threadblocks, {cfg.alpha, cfg.beta},
+      device ? &device->a : nullptr, device ? &device->b : nullptr,
+      device ? &device->c : nullptr, device ? &device->d : nullptr,
+      device ? &device->lda : nullptr, device ? &device->ldb : nullptr,
+      device ? &device->ldc : nullptr, device ? &device->ldd : nullptr,
+      &host.problem);
+
+  if (launch.workspace_size != nullptr) {
+    cutlass::Status status = query_or_run<Gemm>(args, nullptr, nullptr, launch.workspace_size);
+    *launch.workspace_size += kProblemBytes;
+    return status;
+  }
+  if (device == nullptr) {
+    return cutlass::Status::kErrorWorkspaceNull;
+  }
+  // A pageable source is staged before the call returns, so `host` may go.
+  if (cudaMemcpyAsync(device, &host, sizeof(Problem), cudaMemcpyHostToDevice, launch.stream) !=
+      cudaSuccess) {
+    return cutlass::Status::kErrorInternal;
+  }
+  return query_or_run<Gemm>(args, static_cast<char*>(launch.workspace) + kProblemBytes,
+                            launch.stream, nullptr);
+}
+
+// Persistent scheduling runs the plain epilogue only, without split-K.
+cutlass::Status dispatch_persistent(const cutlass_sys_gemm_config_t& cfg, const Launch& launch) {
+  if (cfg.split_k_slices != 1 || cfg.bias != CUTLASS_SYS_BIAS_NONE ||
+      cfg.activation != CUTLASS_SYS_ACTIVATION_IDENTITY || cfg.scale != CUTLASS_SYS_SCALE_NONE ||
+      cfg.beta_mode != CUTLASS_SYS_BETA_PER_TENSOR ||
+      cfg.zero_point_mode != CUTLASS_SYS_ZERO_POINT_NONE ||
+      cfg.aux_op != CUTLASS_SYS_AUX_OP_NONE || !default_gemm_types(cfg) ||
+      !default_gemm_layouts(cfg)) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    return gemm_persistent<decltype(element)>(cfg, launch);
+  });
+}
+
+cutlass::Status dispatch(const cutlass_sys_gemm_config_t& cfg, const Launch& launch) {
+  if (is_gemv(cfg)) {
+    // The entry point has already made the context's device current.
+    cutlass_sys_gemv_config_t gemv = as_gemv(cfg);
+    if (launch.workspace_size != nullptr) {
+      *launch.workspace_size = cutlass_sys_gemv_workspace_size(nullptr, &gemv);
+      return static_cast<cutlass::Status>(cutlass_sys_gemv_can_implement(nullptr, &gemv));
+    }
+    cutlass_sys_gemv_operands_t ops{launch.ops->a, launch.ops->b, launch.ops->c,
+                                    launch.ops->d};
+    return static_cast<cutlass::Status>(
+        cutlass_sys_gemv(nullptr, &gemv, &ops, launch.workspace, launch.stream));
+  }
+  // Devices the SM80-class kernels can't run on, such as Turing or any device
+  // of an sm_75-only build, take the Turing kernels.
+  if (!arch_supported(launch.context.cc_major * 10 + launch.context.cc_minor, kSm80)) {
+    return dispatch_sm75(cfg, launch);
+  }
+  // Configs the Hopper and Blackwell kernels can't take fall through to the
+  // SM80 ones.
+#if defined(CUTLASS_SYS_SM90)
+  if (sm90_gemm_supported(launch.context, cfg)) {
+    return sm90_gemm(launch.context, cfg, launch.ops, launch.workspace, launch.stream,
+                     launch.workspace_size);
+  }
+#endif
+#if defined(CUTLASS_SYS_SM100)
+  if (sm100_gemm_supported(launch.context, cfg)) {
+    return sm100_gemm(launch.context, cfg, launch.ops, launch.workspace, launch.stream,
+                      launch.workspace_size);
+  }
+#endif
+  // Only the SM90 kernels launch clusters of another shape.
+  if (cfg.cluster_shape != CUTLASS_SYS_CLUSTER_SHAPE_DEFAULT) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  if (cfg.scheduler == CUTLASS_SYS_TILE_SCHEDULER_PERSISTENT) {
+    return dispatch_persistent(cfg, launch);
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    return dispatch_scheduler(cfg.scheduler, [&](auto swizzle) {
+      return dispatch_epilogue<decltype(element), decltype(swizzle)>(cfg, launch);
+    });
+  });
+}
+
+// Tile one CTA computes in the kernel `dispatch` runs `cfg` on, or all zeros
+// for the GEMV kernel. Follows the same routing as `dispatch`, for a config it
+// accepts.
+cutlass_sys_gemm_coord_t tile_shape(const cutlass_sys_gemm_config_t& cfg,
+                                    const cutlass_sys_context_t& context) {
+  cutlass_sys_gemm_coord_t tile{};
+  if (is_gemv(cfg)) {
+    return tile;
+  }
+  if (!arch_supported(context.cc_major * 10 + context.cc_minor, kSm80)) {
+#if defined(CUTLASS_SYS_SM75)
+    using Shape = typename Sm75Traits<cutlass::half_t>::ThreadblockShape;
+    using S8Shape = typename Sm75Traits<int8_t>::ThreadblockShape;
+    tile = cfg.element == CUTLASS_SYS_DTYPE_S8
+               ? cutlass_sys_gemm_coord_t{S8Shape::kM, S8Shape::kN, S8Shape::kK}
+               : cutlass_sys_gemm_coord_t{Shape::kM, Shape::kN, Shape::kK};
+#endif
+    return tile;
+  }
+#if defined(CUTLASS_SYS_SM90)
+  if (sm90_gemm_supported(context, cfg)) {
+    return sm90_gemm_tile_shape(cfg);
+  }
+#endif
+#if defined(CUTLASS_SYS_SM100)
+  if (sm100_gemm_supported(context, cfg)) {
+    return sm100_gemm_tile_shape(cfg);
+  }
+#endif
+  dispatch_dtype(cfg.element, [&](auto element) {
+    using Shape = typename Sm80Traits<decltype(element)>::ThreadblockShape;
+    tile = {Shape::kM, Shape::kN, Shape::kK};
+    return cutlass::Status::kSuccess;
+  });
+  return tile;
+}
+
+// `dispatch`, checking D against the device reference for a verified config.
+cutlass::Status dispatch_verified(const cutlass_sys_gemm_config_t& cfg, const Launch& launch) {
+  VerifyOutput out{cfg.element, launch.ops ? launch.ops->d : nullptr, cfg.problem.m,
+                   cfg.problem.n, cfg.ldd};
+  return run_verified(
+      cfg.verify, out, launch.workspace, launch.stream, launch.workspace_size,
+      [&](void* workspace, size_t* workspace_size) {
+        return dispatch(cfg, {launch.context, launch.ops, workspace, launch.stream,
+                              workspace_size});
+      },
+      [&](void* reference) {
+#if defined(CUTLASS_SYS_REFERENCE)
+        return reference_gemm_device(cfg, *launch.ops, reference, cfg.problem.n, launch.stream);
+#else
+        (void)reference;
+        return cutlass::Status::kErrorNotSupported;
+#endif
+      });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_gemm_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::dispatch_verified(*config, {device.context(), nullptr, nullptr, nullptr, &size});
+  return size;
+}
+
+extern "C" int cutlass_sys_gemm_can_implement(const cutlass_sys_context_t* context,
+                                              const cutlass_sys_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::dispatch_verified(
+      *config, {device.context(), nullptr, nullptr, nullptr, &size}));
+}
+
+extern "C" int cutlass_sys_gemm_tile_shape(const cutlass_sys_context_t* context,
+                                           const cutlass_sys_gemm_config_t* config,
+                                           cutlass_sys_gemm_coord_t* tile) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  cutlass::Status status =
+      cutlass_sys::dispatch(*config, {device.context(), nullptr, nullptr, nullptr, &size});
+  if (status == cutlass::Status::kSuccess) {
+    *tile = cutlass_sys::tile_shape(*config, device.context());
+  }
+  return static_cast<int>(status);
+}
+
+extern "C" int cutlass_sys_gemm_resources(const cutlass_sys_context_t* context,
+                                          const cutlass_sys_gemm_config_t* config,
+                                          cutlass_sys_kernel_resources_t* resources) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  cutlass_sys::ResourceQuery query(resources);
+  size_t size = 0;
+  cutlass::Status status =
+      cutlass_sys::dispatch(*config, {device.context(), nullptr, nullptr, nullptr, &size});
+  if (status == cutlass::Status::kSuccess && resources->threads == 0) {
+    status = cutlass::Status::kErrorNotSupported;
+  }
+  return static_cast<int>(status);
+}
+
+extern "C" int cutlass_sys_gemm(const cutlass_sys_context_t* context,
+                                const cutlass_sys_gemm_config_t* config,
+                                const cutlass_sys_gemm_operands_t* operands,
+                                void* workspace,
+                                cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  cutlass_sys::Launch launch{device.context(), operands, workspace,
+                             static_cast<cudaStream_t>(stream), nullptr};
+  return static_cast<int>(cutlass_sys::dispatch_verified(*config, launch));
+}
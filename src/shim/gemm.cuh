@@ -0,0 +1,36 @@
+// The SM80 dense GEMM shared by the GEMM translation units: the plain
+// kernel of the dense entry points and the pointer-array batched ones, which
+// compile separately so their instantiations build in parallel.
+
+#pragma once
+
+#include <cutlass/epilogue/thread/linear_combination.h>
+#include <cutlass/gemm/device/gemm_universal.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+
+// GemmUniversal with a plain linear-combination epilogue, on the default
+// operand layouts unless given others.
+template <typename Element, typename Swizzle, typename LayoutA = cutlass::layout::RowMajor,
+          typename LayoutB = typename Sm80Traits<Element>::LayoutB>
+using PlainGemm = cutlass::gemm::device::GemmUniversal<
+    Element, LayoutA,
+    Element, LayoutB,
+    typename Sm80Traits<Element>::ElementC, cutlass::layout::RowMajor,
+    typename Sm80Traits<Element>::ElementAccumulator,
+    typename Sm80Traits<Element>::OperatorClass, cutlass::arch::Sm80,
+    typename Sm80Traits<Element>::ThreadblockShape,
+    typename Sm80Traits<Element>::WarpShape,
+    typename Sm80Traits<Element>::InstructionShape,
+    cutlass::epilogue::thread::LinearCombination<
+        typename Sm80Traits<Element>::ElementC, Sm80Traits<Element>::kAlignmentC,
+        typename Sm80Traits<Element>::ElementAccumulator, float>,
+    Swizzle,
+    Sm80Traits<Element>::kStages,
+    Sm80Traits<Element>::kAlignmentA,
+    Sm80Traits<Element>::kAlignmentB,
+    typename Sm80Traits<Element>::MathOperator>;
+
+}  // namespace cutlass_sys
@@ -0,0 +1,85 @@
+// Pointer-array batched GEMM entry points (src/gemm.rs), on the plain SM80
+// kernel of the dense GEMM.
+
+#include "common.cuh"
+#include "gemm.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+// Pointer-array batched GEMM. The batch index is carried in the grid's K
+// coordinate, so there is no split-K, Stream-K or fused epilogue.
+cutlass::Status gemm_array(const cutlass_sys_gemm_config_t& cfg,
+                           int batch_count,
+                           const cutlass_sys_gemm_array_operands_t* ops,
+                           void* workspace,
+                           cudaStream_t stream,
+                           size_t* workspace_size) {
+  if (cfg.scheduler != CUTLASS_SYS_TILE_SCHEDULER_DATA_PARALLEL || cfg.split_k_slices != 1 ||
+      cfg.cluster_shape != CUTLASS_SYS_CLUSTER_SHAPE_DEFAULT || cfg.bias != CUTLASS_SYS_BIAS_NONE ||
+      cfg.activation != CUTLASS_SYS_ACTIVATION_IDENTITY ||
+      cfg.scale != CUTLASS_SYS_SCALE_NONE || cfg.beta_mode != CUTLASS_SYS_BETA_PER_TENSOR ||
+      cfg.zero_point_mode != CUTLASS_SYS_ZERO_POINT_NONE ||
+      cfg.aux_op != CUTLASS_SYS_AUX_OP_NONE || !default_gemm_types(cfg) ||
+      !default_gemm_layouts(cfg) || cfg.verify) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Gemm = PlainGemm<decltype(element),
+                           cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>>;
+
+    cutlass_sys_gemm_array_operands_t o = ops ? *ops : cutlass_sys_gemm_array_operands_t{};
+    typename Gemm::Arguments args(
+        cutlass::gemm::GemmUniversalMode::kArray,
+        to_cutlass(cfg.problem),
+        batch_count,
+        {cfg.alpha, cfg.beta},
+        o.a, o.b, o.c, const_cast<void**>(o.d),
+        0, 0, 0, 0,
+        cfg.lda, cfg.ldb, cfg.ldc, cfg.ldd);
+
+    return query_or_run<Gemm>(args, workspace, stream, workspace_size);
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_gemm_array_workspace_size(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_gemm_config_t* config,
+    int32_t batch_count) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::gemm_array(*config, batch_count, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_gemm_array_can_implement(const cutlass_sys_context_t* context,
+                                                    const cutlass_sys_gemm_config_t* config,
+                                                    int32_t batch_count) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(
+      cutlass_sys::gemm_array(*config, batch_count, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_gemm_array(const cutlass_sys_context_t* context,
+                                      const cutlass_sys_gemm_config_t* config,
+                                      int32_t batch_count,
+                                      const cutlass_sys_gemm_array_operands_t* operands,
+                                      void* workspace,
+                                      cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::gemm_array(
+      *config, batch_count, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
@@ -0,0 +1,179 @@
+// GEMM with a caller-described epilogue visitor tree (src/evt.rs).
+//
+// Each cutlass_sys_evt_shape_t is one tree topology, instantiated for every
+// binary op and activation; the scales, aux tensor and output are runtime
+// arguments of the tree.
+
+#include "common.cuh"
+#include "evt.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+using evt::Accum;
+using evt::Compute;
+using evt::Scalar;
+using evt::Sm80EVT;
+
+template <typename Element>
+using ScaledAcc = Sm80EVT<Compute<cutlass::multiplies>, Scalar, Accum>;
+
+template <typename Element>
+using ScaledAux =
+    Sm80EVT<Compute<cutlass::multiplies>, Scalar, evt::MatrixLoad<Element>>;
+
+template <typename Element, template <class> class Op, template <class> class Act>
+using AuxThenActivation = Sm80EVT<
+    evt::MatrixStore<Element>,
+    Sm80EVT<Compute<cutlass::multiplies>, Scalar,
+            Sm80EVT<Compute<Act>,
+                     Sm80EVT<Compute<Op>, ScaledAcc<Element>, ScaledAux<Element>>>>>;
+
+template <typename Element, template <class> class Op, template <class> class Act>
+using ActivationThenAux = Sm80EVT<
+    evt::MatrixStore<Element>,
+    Sm80EVT<Compute<Op>,
+            Sm80EVT<Compute<cutlass::multiplies>, Scalar,
+                    Sm80EVT<Compute<Act>, ScaledAcc<Element>>>,
+            ScaledAux<Element>>>;
+
+struct Launch {
+  const void* a;
+  const void* b;
+  void* workspace;
+  cudaStream_t stream;
+  size_t* workspace_size;
+};
+
+template <typename Element, typename Swizzle, template <class> class Op,
+          template <class> class Act>
+cutlass::Status aux_then_activation(const cutlass_sys_gemm_config_t& cfg,
+                                    const cutlass_sys_evt_program_t& p,
+                                    const Launch& launch) {
+  using Callbacks = AuxThenActivation<Element, Op, Act>;
+  using GemmOp = evt::Gemm<Element, Swizzle, Callbacks>;
+
+  typename Callbacks::Arguments callbacks{
+      {
+          evt::scalar(p.out_scale),
+          {
+              {
+                  {evt::scalar(p.acc_scale), {}, {}},
+                  {evt::scalar(p.aux_scale),
+                   evt::matrix_load<Element>(p.aux, p.ldaux, p.ldaux * cfg.problem.m), {}},
+                  {},
+              },
+              {},
+          },
+          {},
+      },
+      evt::matrix_store<Element>(p.d, p.ldd, p.ldd * cfg.problem.m),
+  };
+
+  return evt::run<GemmOp>(cfg, callbacks, launch.a, launch.b, launch.workspace,
+                          launch.stream, launch.workspace_size);
+}
+
+template <typename Element, typename Swizzle, template <class> class Op,
+          template <class> class Act>
+cutlass::Status activation_then_aux(const cutlass_sys_gemm_config_t& cfg,
+                                    const cutlass_sys_evt_program_t& p,
+                                    const Launch& launch) {
+  using Callbacks = ActivationThenAux<Element, Op, Act>;
+  using GemmOp = evt::Gemm<Element, Swizzle, Callbacks>;
+
+  typename Callbacks::Arguments callbacks{
+      {
+          {
+              evt::scalar(p.out_scale),
+              {{evt::scalar(p.acc_scale), {}, {}}, {}},
+              {},
+          },
+          {evt::scalar(p.aux_scale),
+           evt::matrix_load<Element>(p.aux, p.ldaux, p.ldaux * cfg.problem.m), {}},
+          {},
+      },
+      evt::matrix_store<Element>(p.d, p.ldd, p.ldd * cfg.problem.m),
+  };
+
+  return evt::run<GemmOp>(cfg, callbacks, launch.a, launch.b, launch.workspace,
+                          launch.stream, launch.workspace_size);
+}
+
+cutlass::Status dispatch(const cutlass_sys_gemm_config_t& cfg,
+                         const cutlass_sys_evt_program_t& p,
+                         const Launch& launch) {
+  // The tree computes in f32 and stores the default output type, and the
+  // kernel reads the default layouts without clusters.
+  if (!default_gemm_types(cfg) || !default_gemm_layouts(cfg) ||
+      cfg.cluster_shape != CUTLASS_SYS_CLUSTER_SHAPE_DEFAULT) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    return dispatch_scheduler(cfg.scheduler, [&](auto swizzle) {
+      using Swizzle = decltype(swizzle);
+      return evt::dispatch_binary_op(p.op, [&](auto op) {
+        using Op = decltype(op);
+        return evt::dispatch_activation(p.activation, [&](auto act) {
+          using Act = decltype(act);
+          switch (p.shape) {
+            case CUTLASS_SYS_EVT_SHAPE_AUX_THEN_ACTIVATION:
+              return aux_then_activation<Element, Swizzle, Op::template type,
+                                         Act::template type>(cfg, p, launch);
+            case CUTLASS_SYS_EVT_SHAPE_ACTIVATION_THEN_AUX:
+              return activation_then_aux<Element, Swizzle, Op::template type,
+                                         Act::template type>(cfg, p, launch);
+            default:
+              return cutlass::Status::kErrorNotSupported;
+          }
+        });
+      });
+    });
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_gemm_evt_workspace_size(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_gemm_config_t* config,
+    const cutlass_sys_evt_program_t* program) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::dispatch(*config, *program,
+                        {nullptr, nullptr, nullptr, nullptr, &size});
+  return size;
+}
+
+extern "C" int cutlass_sys_gemm_evt_can_implement(const cutlass_sys_context_t* context,
+                                                  const cutlass_sys_gemm_config_t* config,
+                                                  const cutlass_sys_evt_program_t* program) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(
+      cutlass_sys::dispatch(*config, *program, {nullptr, nullptr, nullptr, nullptr, &size}));
+}
+
+extern "C" int cutlass_sys_gemm_evt(const cutlass_sys_context_t* context,
+                                    const cutlass_sys_gemm_config_t* config,
+                                    const cutlass_sys_evt_program_t* program,
+                                    const void* a,
+                                    const void* b,
+                                    void* workspace,
+                                    cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  cutlass_sys::Launch launch{a, b, workspace, static_cast<cudaStream_t>(stream),
+                             nullptr};
+  return static_cast<int>(cutlass_sys::dispatch(*config, *program, launch));
+}
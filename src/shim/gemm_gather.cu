@@ -0,0 +1,128 @@
+// Gather/scatter GEMM entry points (src/gemm.rs): rows of A and B gathered
+// and rows of C and D scattered through index arrays.
+
+#include <type_traits>
+
+#include <cutlass/epilogue/thread/linear_combination.h>
+#include <cutlass/gemm/device/gemm_universal.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+// PlainGemm with rows of A and B gathered and rows of C and D scattered
+// through index arrays, as in CUTLASS example 36.
+template <typename Element, bool GatherA, bool GatherB, bool ScatterD>
+using GatherGemm = cutlass::gemm::device::GemmUniversal<
+    Element, cutlass::layout::RowMajor,
+    Element, typename Sm80Traits<Element>::LayoutB,
+    typename Sm80Traits<Element>::ElementC, cutlass::layout::RowMajor,
+    typename Sm80Traits<Element>::ElementAccumulator,
+    typename Sm80Traits<Element>::OperatorClass, cutlass::arch::Sm80,
+    typename Sm80Traits<Element>::ThreadblockShape,
+    typename Sm80Traits<Element>::WarpShape,
+    typename Sm80Traits<Element>::InstructionShape,
+    cutlass::epilogue::thread::LinearCombination<
+        typename Sm80Traits<Element>::ElementC, Sm80Traits<Element>::kAlignmentC,
+        typename Sm80Traits<Element>::ElementAccumulator, float>,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+    Sm80Traits<Element>::kStages,
+    Sm80Traits<Element>::kAlignmentA,
+    Sm80Traits<Element>::kAlignmentB,
+    typename Sm80Traits<Element>::MathOperator,
+    cutlass::ComplexTransform::kNone,
+    cutlass::ComplexTransform::kNone,
+    GatherA,
+    GatherB,
+    ScatterD>;
+
+// Calls `f` with std::true_type or std::false_type.
+template <typename F>
+cutlass::Status dispatch_bool(bool value, F&& f) {
+  return value ? f(std::true_type{}) : f(std::false_type{});
+}
+
+// Gather/scatter GEMM. Which operands are indexed follows from which index
+// arrays are non-null, so a query without operands checks the plain kernel,
+// whose workspace and restrictions the indexed ones share.
+cutlass::Status gemm_gather(const cutlass_sys_gemm_config_t& cfg,
+                            const cutlass_sys_gemm_gather_operands_t* ops,
+                            void* workspace,
+                            cudaStream_t stream,
+                            size_t* workspace_size) {
+  if (cfg.scheduler != CUTLASS_SYS_TILE_SCHEDULER_DATA_PARALLEL || cfg.split_k_slices != 1 ||
+      cfg.cluster_shape != CUTLASS_SYS_CLUSTER_SHAPE_DEFAULT || cfg.bias != CUTLASS_SYS_BIAS_NONE ||
+      cfg.activation != CUTLASS_SYS_ACTIVATION_IDENTITY ||
+      cfg.scale != CUTLASS_SYS_SCALE_NONE || cfg.beta_mode != CUTLASS_SYS_BETA_PER_TENSOR ||
+      cfg.zero_point_mode != CUTLASS_SYS_ZERO_POINT_NONE ||
+      cfg.aux_op != CUTLASS_SYS_AUX_OP_NONE || !default_gemm_types(cfg) ||
+      !default_gemm_layouts(cfg) || cfg.verify) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  cutlass_sys_gemm_gather_operands_t o = ops ? *ops : cutlass_sys_gemm_gather_operands_t{};
+  // S8's B is column-major, where the gathered dimension would be N.
+  if (cfg.element == CUTLASS_SYS_DTYPE_S8 && o.gather_b != nullptr) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    return dispatch_bool(o.gather_a != nullptr, [&](auto gather_a) {
+      return dispatch_bool(o.gather_b != nullptr, [&](auto gather_b) {
+        return dispatch_bool(o.scatter_d != nullptr, [&](auto scatter_d) {
+          using Gemm = GatherGemm<Element, decltype(gather_a)::value, decltype(gather_b)::value,
+                                  decltype(scatter_d)::value>;
+          typename Gemm::Arguments args(
+              cutlass::gemm::GemmUniversalMode::kGemm,
+              to_cutlass(cfg.problem),
+              1,
+              {cfg.alpha, cfg.beta},
+              o.a, o.b, o.c, o.d,
+              0, 0, 0, 0,
+              cfg.lda, cfg.ldb, cfg.ldc, cfg.ldd,
+              o.gather_a, o.gather_b, o.scatter_d);
+
+          return query_or_run<Gemm>(args, workspace, stream, workspace_size);
+        });
+      });
+    });
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_gemm_gather_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::gemm_gather(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_gemm_gather_can_implement(const cutlass_sys_context_t* context,
+                                                     const cutlass_sys_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(
+      cutlass_sys::gemm_gather(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_gemm_gather(const cutlass_sys_context_t* context,
+                                       const cutlass_sys_gemm_config_t* config,
+                                       const cutlass_sys_gemm_gather_operands_t* operands,
+                                       void* workspace,
+                                       cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::gemm_gather(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
@@ -0,0 +1,182 @@
+// GEMM-LayerNorm-GEMM entry points (src/gemm_layernorm.rs).
+//
+// Instantiates the GemmLayernorm operator of CUTLASS example 37: GEMM0's
+// epilogue visitor writes D and the partial row sums and sums of squares of
+// each threadblock tile, ApplyFinalReduction turns them into the row mean and
+// variance in place, and GEMM1's mainloop applies the normalization, gamma
+// and beta to its A operand (D) as it loads it.
+
+#include <type_traits>
+
+#include <cutlass/epilogue/thread/linear_combination.h>
+
+#include <37_gemm_layernorm_gemm_fusion/gemm_with_layernorm.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+template <typename Element>
+using GemmLayernorm = cutlass::GemmLayernorm<
+    Element, cutlass::layout::RowMajor,
+    Element, cutlass::layout::ColumnMajor,
+    Element, cutlass::layout::RowMajor,
+    float,
+    cutlass::epilogue::thread::LinearCombination<Element, Sm80Traits<Element>::kAlignmentC,
+                                                 float, float>,
+    typename Sm80Traits<Element>::ThreadblockShape,
+    typename Sm80Traits<Element>::WarpShape,
+    typename Sm80Traits<Element>::InstructionShape,
+    Sm80Traits<Element>::kStages,
+    Sm80Traits<Element>::kStages>;
+
+// Every operand is accessed 128 bits at a time.
+constexpr int kAlignment = 8;
+
+// Partial statistics of one kind the GEMM0 epilogue leaves: one per row and
+// threadblock column of D.
+template <typename Op>
+int64_t partial_columns(const cutlass_sys_gemm_layernorm_config_t& cfg) {
+  return (cfg.problem0.n + Op::ThreadblockShape::kN - 1) / Op::ThreadblockShape::kN;
+}
+
+// Byte offset of the mean partials in the workspace, after the variance.
+template <typename Op>
+size_t mean_offset(int64_t partials) {
+  return (size_t(partials) * sizeof(typename Op::ElementVariance) + 127) / 128 * 128;
+}
+
+cutlass::Status check_config(const cutlass_sys_gemm_layernorm_config_t& cfg) {
+  const cutlass_sys_gemm_coord_t& p0 = cfg.problem0;
+  const cutlass_sys_gemm_coord_t& p1 = cfg.problem1;
+  if (p0.m <= 0 || p0.n <= 0 || p0.k <= 0 || p1.n <= 0 || p1.m != p0.m || p1.k != p0.n ||
+      cfg.lda < p0.k || cfg.ldb0 < p0.k || cfg.ldd < p0.n || cfg.ldb1 < p1.k ||
+      cfg.ld_out < p1.n) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  if (p0.n % kAlignment != 0 || p0.k % kAlignment != 0 || p1.n % kAlignment != 0 ||
+      cfg.lda % kAlignment != 0 || cfg.ldb0 % kAlignment != 0 || cfg.ldc % kAlignment != 0 ||
+      cfg.ldd % kAlignment != 0 || cfg.ldb1 % kAlignment != 0 ||
+      cfg.ld_out % kAlignment != 0) {
+    return cutlass::Status::kErrorMisalignedOperand;
+  }
+  return cutlass::Status::kSuccess;
+}
+
+cutlass::Status gemm_layernorm(const cutlass_sys_gemm_layernorm_config_t& cfg,
+                               const cutlass_sys_gemm_layernorm_operands_t* ops,
+                               void* workspace,
+                               cudaStream_t stream,
+                               size_t* workspace_size) {
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (!std::is_same_v<Element, cutlass::half_t> &&
+                  !std::is_same_v<Element, cutlass::bfloat16_t>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      using Op = GemmLayernorm<Element>;
+      using ElementVariance = typename Op::ElementVariance;
+      using ElementMean = typename Op::ElementMean;
+
+      cutlass::Status status = check_config(cfg);
+      if (status != cutlass::Status::kSuccess) {
+        return status;
+      }
+      int64_t columns = partial_columns<Op>(cfg);
+      int64_t partials = int64_t(cfg.problem0.m) * columns;
+      if (workspace_size != nullptr) {
+        *workspace_size = mean_offset<Op>(partials) + size_t(partials) * sizeof(ElementMean);
+        return cutlass::Status::kSuccess;
+      }
+
+      cutlass_sys_gemm_layernorm_operands_t o =
+          ops ? *ops : cutlass_sys_gemm_layernorm_operands_t{};
+      if (o.a == nullptr || o.b0 == nullptr || o.d == nullptr || o.gamma == nullptr ||
+          o.beta_ln == nullptr || o.b1 == nullptr || o.out == nullptr ||
+          workspace == nullptr || (cfg.beta != 0.0f && o.c == nullptr)) {
+        return cutlass::Status::kErrorInvalidProblem;
+      }
+
+      // Without a source beta is zero and C is never read; D stands in for it.
+      Element* c = static_cast<Element*>(o.c ? const_cast<void*>(o.c) : o.d);
+      int64_t ldc = o.c ? cfg.ldc : cfg.ldd;
+      auto* variance = static_cast<ElementVariance*>(workspace);
+      auto* mean = reinterpret_cast<ElementMean*>(static_cast<char*>(workspace) +
+                                                  mean_offset<Op>(partials));
+      // gamma and beta are row vectors over the n columns of D.
+      cutlass::layout::RowMajor vector(0);
+
+      typename Op::Arguments args(
+          to_cutlass(cfg.problem0),
+          to_cutlass(cfg.problem1),
+          static_cast<Element*>(const_cast<void*>(o.a)),
+          static_cast<Element*>(const_cast<void*>(o.b0)),
+          c,
+          static_cast<Element*>(o.d),
+          static_cast<Element*>(const_cast<void*>(o.b1)),
+          static_cast<Element*>(o.out),
+          cfg.lda,
+          cfg.ldb0,
+          ldc,
+          cfg.ldd,
+          cfg.ldb1,
+          cfg.ld_out,
+          {cfg.alpha, cfg.beta},
+          {variance, typename Op::LayoutOutputC0(columns)},
+          {mean, typename Op::LayoutOutputC0(columns)},
+          {static_cast<ElementVariance*>(const_cast<void*>(o.gamma)), vector},
+          {static_cast<ElementMean*>(const_cast<void*>(o.beta_ln)), vector});
+
+      Op op;
+      status = op.initialize(args);
+      if (status != cutlass::Status::kSuccess) {
+        return status;
+      }
+      status = op.run(stream);
+      if (status != cutlass::Status::kSuccess) {
+        return status;
+      }
+      return cudaGetLastError() == cudaSuccess ? cutlass::Status::kSuccess
+                                               : cutlass::Status::kErrorInternal;
+    }
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_gemm_layernorm_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_gemm_layernorm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::gemm_layernorm(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_gemm_layernorm_can_implement(
+    const cutlass_sys_context_t* context, const cutlass_sys_gemm_layernorm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(
+      cutlass_sys::gemm_layernorm(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_gemm_layernorm(const cutlass_sys_context_t* context,
+                                          const cutlass_sys_gemm_layernorm_config_t* config,
+                                          const cutlass_sys_gemm_layernorm_operands_t* operands,
+                                          void* workspace,
+                                          cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::gemm_layernorm(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
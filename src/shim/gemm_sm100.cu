@@ -0,0 +1,216 @@
+// Blackwell tcgen05 GEMMs (src/gemm.rs, `sm100` feature).
+//
+// CUTLASS 4.x CollectiveBuilder kernels: TMA loads feed tcgen05 UMMAs that
+// accumulate in tensor memory, with 2-SM MMA instructions spanning the two
+// CTAs of each 2x1 cluster.
+
+#include "sm100.cuh"
+
+#if defined(CUTLASS_SYS_SM100)
+
+#include <type_traits>
+
+#include <cute/tensor.hpp>
+#include <cutlass/epilogue/collective/collective_builder.hpp>
+#include <cutlass/gemm/collective/collective_builder.hpp>
+#include <cutlass/gemm/device/gemm_universal_adapter.h>
+#include <cutlass/gemm/kernel/gemm_universal.hpp>
+#include <cutlass/gemm/kernel/tile_scheduler.hpp>
+
+namespace cutlass_sys {
+namespace {
+
+// Kernel configuration of the SM100 instantiations, keyed by operand type.
+// Operand layouts match Sm80Traits, so both paths accept the same configs.
+// Tile shapes are per 2-SM MMA, i.e. per cluster.
+template <typename Element>
+struct Sm100Traits;
+
+template <>
+struct Sm100Traits<cutlass::half_t> {
+  using ElementC = cutlass::half_t;
+  using ElementAccumulator = float;
+  using LayoutB = cutlass::layout::RowMajor;
+  using TileShape = cute::Shape<cute::_256, cute::_256, cute::_64>;
+  static constexpr int kAlignment = 8;
+  static constexpr int kAlignmentC = 8;
+};
+
+template <>
+struct Sm100Traits<cutlass::bfloat16_t> {
+  using ElementC = cutlass::bfloat16_t;
+  using ElementAccumulator = float;
+  using LayoutB = cutlass::layout::RowMajor;
+  using TileShape = cute::Shape<cute::_256, cute::_256, cute::_64>;
+  static constexpr int kAlignment = 8;
+  static constexpr int kAlignmentC = 8;
+};
+
+template <>
+struct Sm100Traits<int8_t> {
+  using ElementC = int32_t;
+  using ElementAccumulator = int32_t;
+  using LayoutB = cutlass::layout::ColumnMajor;
+  using TileShape = cute::Shape<cute::_256, cute::_256, cute::_128>;
+  static constexpr int kAlignment = 16;
+  static constexpr int kAlignmentC = 4;
+};
+
+using ClusterShape = cute::Shape<cute::_2, cute::_1, cute::_1>;
+
+template <typename Element, typename Scheduler>
+struct Sm100Gemm {
+  using Traits = Sm100Traits<Element>;
+  using ElementC = typename Traits::ElementC;
+
+  using CollectiveEpilogue = typename cutlass::epilogue::collective::CollectiveBuilder<
+      cutlass::arch::Sm100, cutlass::arch::OpClassTensorOp,
+      typename Traits::TileShape, ClusterShape,
+      cutlass::epilogue::collective::EpilogueTileAuto,
+      typename Traits::ElementAccumulator, float,
+      ElementC, cutlass::layout::RowMajor, Traits::kAlignmentC,
+      ElementC, cutlass::layout::RowMajor, Traits::kAlignmentC,
+      cutlass::epilogue::TmaWarpSpecialized2Sm>::CollectiveOp;
+
+  using CollectiveMainloop = typename cutlass::gemm::collective::CollectiveBuilder<
+      cutlass::arch::Sm100, cutlass::arch::OpClassTensorOp,
+      Element, cutlass::layout::RowMajor, Traits::kAlignment,
+      Element, typename Traits::LayoutB, Traits::kAlignment,
+      typename Traits::ElementAccumulator,
+      typename Traits::TileShape, ClusterShape,
+      cutlass::gemm::collective::StageCountAutoCarveout<static_cast<int>(
+          sizeof(typename CollectiveEpilogue::SharedStorage))>,
+      cutlass::gemm::KernelTmaWarpSpecialized2SmSm100>::CollectiveOp;
+
+  using Kernel = cutlass::gemm::kernel::GemmUniversal<
+      cute::Shape<int, int, int, int>, CollectiveMainloop, CollectiveEpilogue, Scheduler>;
+
+  using Operator = cutlass::gemm::device::GemmUniversalAdapter<Kernel>;
+};
+
+template <typename Element, typename Scheduler>
+cutlass::Status gemm(const cutlass_sys_context_t& context,
+                     const cutlass_sys_gemm_config_t& cfg,
+                     const cutlass_sys_gemm_operands_t* launch_ops,
+                     void* workspace,
+                     cudaStream_t stream,
+                     size_t* workspace_size) {
+  using Gemm = Sm100Gemm<Element, Scheduler>;
+  using Operator = typename Gemm::Operator;
+  using Kernel = typename Gemm::Kernel;
+  using ElementC = typename Gemm::ElementC;
+
+  cutlass_sys_gemm_operands_t ops = launch_ops ? *launch_ops : cutlass_sys_gemm_operands_t{};
+  typename Operator::Arguments args{};
+  args.mode = cutlass::gemm::GemmUniversalMode::kGemm;
+  args.problem_shape = {cfg.problem.m, cfg.problem.n, cfg.problem.k, 1};
+  args.mainloop.ptr_A = static_cast<const Element*>(ops.a);
+  args.mainloop.dA = leading_stride<typename Kernel::StrideA>(cfg.lda);
+  args.mainloop.ptr_B = static_cast<const Element*>(ops.b);
+  args.mainloop.dB = leading_stride<typename Kernel::StrideB>(cfg.ldb);
+  args.epilogue.thread.alpha = cfg.alpha;
+  args.epilogue.thread.beta = cfg.beta;
+  args.epilogue.ptr_C = static_cast<const ElementC*>(ops.c);
+  args.epilogue.dC = leading_stride<typename Kernel::StrideC>(cfg.ldc);
+  args.epilogue.ptr_D = static_cast<ElementC*>(ops.d);
+  args.epilogue.dD = leading_stride<typename Kernel::StrideD>(cfg.ldd);
+  args.hw_info.device_id = context.device;
+  args.hw_info.sm_count = context.sm_count;
+  if constexpr (std::is_same_v<Scheduler, cutlass::gemm::StreamKScheduler>) {
+    args.scheduler.splits = cfg.split_k_slices;
+  }
+
+  return query_or_run<Operator>(args, workspace, stream, workspace_size);
+}
+
+// Calls `f` with a value of the SM100 element type named by `dtype`.
+template <typename F>
+cutlass::Status dispatch_sm100_dtype(cutlass_sys_dtype_t dtype, F&& f) {
+  switch (dtype) {
+    case CUTLASS_SYS_DTYPE_F16:
+      return f(cutlass::half_t{});
+    case CUTLASS_SYS_DTYPE_BF16:
+      return f(cutlass::bfloat16_t{});
+    case CUTLASS_SYS_DTYPE_S8:
+      return f(int8_t{});
+    default:
+      return cutlass::Status::kErrorInvalidDataType;
+  }
+}
+
+// Calls `f` with a Type<> of the 4.x tile scheduler implementing `scheduler`.
+// Data-parallel and persistent launches both use the default
+// cluster-launch-control scheduler, which has no split-K.
+template <typename F>
+cutlass::Status dispatch_sm100_scheduler(const cutlass_sys_gemm_config_t& cfg, F&& f) {
+  switch (cfg.scheduler) {
+    case CUTLASS_SYS_TILE_SCHEDULER_DATA_PARALLEL:
+    case CUTLASS_SYS_TILE_SCHEDULER_PERSISTENT:
+      if (cfg.split_k_slices != 1) {
+        return cutlass::Status::kErrorNotSupported;
+      }
+      return f(Type<void>{});
+    case CUTLASS_SYS_TILE_SCHEDULER_STREAM_K:
+      return f(Type<cutlass::gemm::StreamKScheduler>{});
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+cutlass::Status dispatch(const cutlass_sys_context_t& context,
+                         const cutlass_sys_gemm_config_t& cfg,
+                         const cutlass_sys_gemm_operands_t* ops,
+                         void* workspace,
+                         cudaStream_t stream,
+                         size_t* workspace_size) {
+  if (cfg.cluster_shape != CUTLASS_SYS_CLUSTER_SHAPE_DEFAULT || cfg.bias != CUTLASS_SYS_BIAS_NONE ||
+      cfg.activation != CUTLASS_SYS_ACTIVATION_IDENTITY ||
+      cfg.scale != CUTLASS_SYS_SCALE_NONE || cfg.beta_mode != CUTLASS_SYS_BETA_PER_TENSOR ||
+      cfg.zero_point_mode != CUTLASS_SYS_ZERO_POINT_NONE ||
+      cfg.aux_op != CUTLASS_SYS_AUX_OP_NONE || !default_gemm_types(cfg) ||
+      !default_gemm_layouts(cfg)) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_sm100_dtype(cfg.element, [&](auto element) {
+    return dispatch_sm100_scheduler(cfg, [&](auto scheduler) {
+      return gemm<decltype(element), typename decltype(scheduler)::type>(
+          context, cfg, ops, workspace, stream, workspace_size);
+    });
+  });
+}
+
+}  // namespace
+
+bool sm100_gemm_supported(const cutlass_sys_context_t& context,
+                          const cutlass_sys_gemm_config_t& cfg) {
+  if (context.cc_major != 10 || context.cc_minor != 0) {
+    return false;
+  }
+  size_t size = 0;
+  return dispatch(context, cfg, nullptr, nullptr, nullptr, &size) == cutlass::Status::kSuccess;
+}
+
+cutlass_sys_gemm_coord_t sm100_gemm_tile_shape(const cutlass_sys_gemm_config_t& cfg) {
+  cutlass_sys_gemm_coord_t tile{};
+  dispatch_sm100_dtype(cfg.element, [&](auto element) {
+    // The 2-SM MMA splits each cluster tile along M.
+    using TileShape = typename Sm100Traits<decltype(element)>::TileShape;
+    tile = {cute::size<0>(TileShape{}) / cute::size<0>(ClusterShape{}),
+            cute::size<1>(TileShape{}), cute::size<2>(TileShape{})};
+    return cutlass::Status::kSuccess;
+  });
+  return tile;
+}
+
+cutlass::Status sm100_gemm(const cutlass_sys_context_t& context,
+                           const cutlass_sys_gemm_config_t& cfg,
+                           const cutlass_sys_gemm_operands_t* ops,
+                           void* workspace,
+                           cudaStream_t stream,
+                           size_t* workspace_size) {
+  return dispatch(context, cfg, ops, workspace, stream, workspace_size);
+}
+
+}  // namespace cutlass_sys
+
+#endif
@@ -0,0 +1,171 @@
+// GEMM + softmax entry points (src/gemm_softmax.rs).
+//
+// Instantiates the GemmSoftmax operator of CUTLASS example 35: a GEMM whose
+// epilogue visitor writes D = alpha * A * B + beta * C and the partial row
+// maxima and sums of exponentials of each threadblock tile, followed by a
+// kernel reducing the partials and one writing softmax(D). The mask is C,
+// added with beta = 1, and the scale is alpha.
+
+#include <type_traits>
+
+#include <cutlass/epilogue/thread/linear_combination.h>
+
+#include <35_gemm_softmax/gemm_with_softmax.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+template <typename Element>
+using GemmSoftmax = cutlass::GemmSoftmax<
+    Element, cutlass::layout::RowMajor,
+    Element, cutlass::layout::ColumnMajor,
+    Element, float,
+    cutlass::arch::OpClassTensorOp, cutlass::arch::Sm80,
+    typename Sm80Traits<Element>::ThreadblockShape,
+    typename Sm80Traits<Element>::WarpShape,
+    typename Sm80Traits<Element>::InstructionShape,
+    cutlass::epilogue::thread::LinearCombination<Element, Sm80Traits<Element>::kAlignmentC,
+                                                 float, float>,
+    Sm80Traits<Element>::kStages>;
+
+// Every operand is accessed 128 bits at a time.
+constexpr int kAlignment = 8;
+
+// Partial maxima (or sums) the GEMM epilogue leaves for one config: one per
+// row, batch and threadblock column.
+template <typename Op>
+int64_t partial_count(const cutlass_sys_gemm_softmax_config_t& cfg) {
+  int64_t columns = (cfg.problem.n + Op::ThreadblockShape::kN - 1) / Op::ThreadblockShape::kN;
+  return int64_t(cfg.batch_count) * cfg.problem.m * columns;
+}
+
+// Byte offset of the partial sums in the workspace, after the maxima.
+inline size_t sums_offset(int64_t partials) {
+  return (size_t(partials) * sizeof(float) + 127) / 128 * 128;
+}
+
+cutlass::Status check_config(const cutlass_sys_gemm_softmax_config_t& cfg) {
+  const cutlass_sys_gemm_coord_t& p = cfg.problem;
+  if (p.m <= 0 || p.n <= 0 || p.k <= 0 || cfg.batch_count < 1 || cfg.lda < p.k ||
+      cfg.ldb < p.k || cfg.ldd < p.n || cfg.ld_softmax < p.n) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  if (p.n % kAlignment != 0 || p.k % kAlignment != 0 || cfg.lda % kAlignment != 0 ||
+      cfg.ldb % kAlignment != 0 || cfg.ldd % kAlignment != 0 ||
+      cfg.ld_softmax % kAlignment != 0) {
+    return cutlass::Status::kErrorMisalignedOperand;
+  }
+  return cutlass::Status::kSuccess;
+}
+
+cutlass::Status gemm_softmax(const cutlass_sys_gemm_softmax_config_t& cfg,
+                             const cutlass_sys_gemm_softmax_operands_t* ops,
+                             void* workspace,
+                             cudaStream_t stream,
+                             size_t* workspace_size) {
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (!std::is_same_v<Element, cutlass::half_t> &&
+                  !std::is_same_v<Element, cutlass::bfloat16_t>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      using Op = GemmSoftmax<Element>;
+
+      cutlass::Status status = check_config(cfg);
+      if (status != cutlass::Status::kSuccess) {
+        return status;
+      }
+      int64_t partials = partial_count<Op>(cfg);
+      if (workspace_size != nullptr) {
+        *workspace_size = sums_offset(partials) + size_t(partials) * sizeof(float);
+        return cutlass::Status::kSuccess;
+      }
+
+      cutlass_sys_gemm_softmax_operands_t o =
+          ops ? *ops : cutlass_sys_gemm_softmax_operands_t{};
+      if (o.a == nullptr || o.b == nullptr || o.d == nullptr || o.softmax == nullptr ||
+          workspace == nullptr) {
+        return cutlass::Status::kErrorInvalidProblem;
+      }
+      bool masked = o.mask != nullptr;
+      if (masked && (cfg.ld_mask < cfg.problem.n || cfg.ld_mask % kAlignment != 0)) {
+        return cutlass::Status::kErrorMisalignedOperand;
+      }
+
+      // Without a mask beta is zero and C is never read; D stands in for it.
+      Element* c = static_cast<Element*>(masked ? const_cast<void*>(o.mask) : o.d);
+      int64_t ldc = masked ? cfg.ld_mask : cfg.ldd;
+      int64_t columns = partials / (int64_t(cfg.batch_count) * cfg.problem.m);
+      float* maxima = static_cast<float*>(workspace);
+      float* sums = reinterpret_cast<float*>(static_cast<char*>(workspace) +
+                                             sums_offset(partials));
+
+      typename Op::Arguments args(
+          to_cutlass(cfg.problem),
+          cfg.batch_count,
+          {static_cast<Element*>(const_cast<void*>(o.a)), typename Op::LayoutA(cfg.lda)},
+          {static_cast<Element*>(const_cast<void*>(o.b)), typename Op::LayoutB(cfg.ldb)},
+          {c, typename Op::LayoutC(ldc)},
+          {static_cast<Element*>(o.d), typename Op::LayoutC(cfg.ldd)},
+          {cfg.scale, masked ? 1.0f : 0.0f},
+          {maxima, typename Op::LayoutC(columns)},
+          {sums, typename Op::LayoutC(columns)},
+          {static_cast<Element*>(o.softmax), typename Op::LayoutC(cfg.ld_softmax)},
+          cfg.batch_stride_a,
+          cfg.batch_stride_b,
+          masked ? cfg.batch_stride_mask : cfg.batch_stride_d,
+          cfg.batch_stride_d,
+          cfg.problem.m * columns,
+          cfg.problem.m * columns,
+          cfg.batch_stride_softmax);
+
+      Op op(args);
+      status = op.run(stream);
+      if (status != cutlass::Status::kSuccess) {
+        return status;
+      }
+      return cudaGetLastError() == cudaSuccess ? cutlass::Status::kSuccess
+                                               : cutlass::Status::kErrorInternal;
+    }
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_gemm_softmax_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_gemm_softmax_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::gemm_softmax(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_gemm_softmax_can_implement(
+    const cutlass_sys_context_t* context, const cutlass_sys_gemm_softmax_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(
+      cutlass_sys::gemm_softmax(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_gemm_softmax(const cutlass_sys_context_t* context,
+                                        const cutlass_sys_gemm_softmax_config_t* config,
+                                        const cutlass_sys_gemm_softmax_operands_t* operands,
+                                        void* workspace,
+                                        cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::gemm_softmax(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
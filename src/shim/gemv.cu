@@ -0,0 +1,95 @@
+// GEMV entry points (src/gemv.rs).
+
+#include <cutlass/epilogue/thread/linear_combination.h>
+#include <cutlass/gemm/device/gemv.h>
+#include <cutlass/gemm/kernel/gemv.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+// One warp-cooperative dot product per row of the row-major A, reading
+// `kElementsPerAccess` elements per load.
+template <typename Element, int kElementsPerAccess>
+using Gemv = cutlass::gemm::device::Gemv<cutlass::gemm::kernel::Gemv<
+    Element, cutlass::layout::RowMajor,
+    Element,
+    typename Sm80Traits<Element>::ElementC,
+    typename Sm80Traits<Element>::ElementAccumulator,
+    cutlass::epilogue::thread::LinearCombination<
+        typename Sm80Traits<Element>::ElementC, 1,
+        typename Sm80Traits<Element>::ElementAccumulator, float>,
+    kElementsPerAccess>>;
+
+template <typename Op>
+cutlass::Status run_gemv(const cutlass_sys_gemv_config_t& cfg,
+                         const cutlass_sys_gemv_operands_t* ops,
+                         void* workspace,
+                         cudaStream_t stream,
+                         size_t* workspace_size) {
+  cutlass_sys_gemv_operands_t o = ops ? *ops : cutlass_sys_gemv_operands_t{};
+  typename Op::Arguments args(
+      {cfg.m, cfg.k},
+      cfg.batch_count,
+      {cfg.alpha, cfg.beta},
+      {static_cast<typename Op::ElementA const*>(o.a), typename Op::LayoutA(cfg.lda)},
+      o.x, o.c, o.d,
+      cfg.batch_stride_a, cfg.batch_stride_x, cfg.batch_stride_c, cfg.batch_stride_d);
+
+  return query_or_run<Op>(args, workspace, stream, workspace_size);
+}
+
+// Vectorized loads need K and lda to be multiples of a 128-bit access;
+// anything else takes the scalar kernel.
+cutlass::Status gemv(const cutlass_sys_gemv_config_t& cfg,
+                     const cutlass_sys_gemv_operands_t* ops,
+                     void* workspace,
+                     cudaStream_t stream,
+                     size_t* workspace_size) {
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    constexpr int kVector = 128 / cutlass::sizeof_bits<Element>::value;
+    if (cfg.k % kVector == 0 && cfg.lda % kVector == 0) {
+      return run_gemv<Gemv<Element, kVector>>(cfg, ops, workspace, stream, workspace_size);
+    }
+    return run_gemv<Gemv<Element, 1>>(cfg, ops, workspace, stream, workspace_size);
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_gemv_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_gemv_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::gemv(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_gemv_can_implement(const cutlass_sys_context_t* context,
+                                              const cutlass_sys_gemv_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::gemv(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_gemv(const cutlass_sys_context_t* context,
+                                const cutlass_sys_gemv_config_t* config,
+                                const cutlass_sys_gemv_operands_t* operands,
+                                void* workspace,
+                                cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::gemv(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
@@ -0,0 +1,335 @@
+// CUTLASS library entry points (src/manifest.rs, `library` feature).
+//
+// A C ABI over cutlass::library, CUTLASS's prebuilt kernel library: the
+// Manifest of every Operation it instantiates, each Operation's description
+// and launch, and the Handle that picks one for a GEMM by itself. The
+// cutlass::library enums cross the ABI as their integer values, mirrored in
+// declaration order by src/library.rs.
+
+#include "common.cuh"
+
+#if defined(CUTLASS_SYS_LIBRARY)
+
+#include <cstdint>
+#include <new>
+#include <vector>
+
+#include <cutlass/library/handle.h>
+#include <cutlass/library/library.h>
+#include <cutlass/library/manifest.h>
+
+struct cutlass_sys_manifest {
+  cutlass::library::Manifest manifest;
+};
+
+struct cutlass_sys_library_handle {
+  cutlass_sys_context_t context;
+  cutlass::library::Handle handle;
+};
+
+namespace cutlass_sys {
+namespace {
+
+const cutlass::library::Operation* unwrap(const cutlass_sys_operation_t* operation) {
+  return reinterpret_cast<const cutlass::library::Operation*>(operation);
+}
+
+const cutlass_sys_operation_t* wrap(const cutlass::library::Operation* operation) {
+  return reinterpret_cast<const cutlass_sys_operation_t*>(operation);
+}
+
+cutlass_sys_gemm_coord_t to_sys(cutlass::gemm::GemmCoord coord) {
+  return {coord.m(), coord.n(), coord.k()};
+}
+
+cutlass_sys_tensor_description_t to_sys(const cutlass::library::TensorDescription& tensor) {
+  return {static_cast<int32_t>(tensor.element), static_cast<int32_t>(tensor.layout),
+          tensor.alignment};
+}
+
+template <typename T>
+T to_library(int32_t value) {
+  return static_cast<T>(value);
+}
+
+// kErrorArchMismatch unless `operation` was compiled for the device of
+// `context`.
+cutlass::Status check_operation_arch(const cutlass_sys_context_t& context,
+                                     const cutlass::library::Operation& operation) {
+  const auto& tile = operation.description().tile_description;
+  int cc = context.cc_major * 10 + context.cc_minor;
+  if (cc < tile.minimum_compute_capability || cc > tile.maximum_compute_capability) {
+    return cutlass::Status::kErrorArchMismatch;
+  }
+  return cutlass::Status::kSuccess;
+}
+
+// Runs a universal GEMM operation, or reports its device workspace size and
+// can_implement status when `workspace_size` is non-null, like query_or_run.
+// The operation's host workspace (its initialized kernel parameters) lives on
+// the host heap for the duration of the call.
+cutlass::Status operation_gemm(const cutlass_sys_context_t& context,
+                               const cutlass::library::Operation& operation,
+                               const cutlass_sys_library_gemm_arguments_t& gemm,
+                               void* workspace,
+                               cudaStream_t stream,
+                               size_t* workspace_size) {
+  const auto& description = operation.description();
+  if (description.kind != cutlass::library::OperationKind::kGemm ||
+      static_cast<const cutlass::library::GemmDescription&>(description).gemm_kind !=
+          cutlass::library::GemmKind::kUniversal) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  cutlass::Status status = check_operation_arch(context, operation);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+
+  cutlass::gemm::GemmCoord problem(gemm.problem.m, gemm.problem.n, gemm.problem.k);
+  cutlass::gemm::GemmCoord cluster = description.tile_description.cluster_shape;
+
+  cutlass::library::GemmUniversalConfiguration config{};
+  config.mode = to_library<cutlass::gemm::GemmUniversalMode>(gemm.mode);
+  config.problem_size = problem;
+  config.cluster_shape = cluster;
+  config.cluster_shape_fallback = cluster;
+  config.batch_count = gemm.batch_count;
+  config.lda = gemm.lda;
+  config.ldb = gemm.ldb;
+  config.ldc = gemm.ldc;
+  config.ldd = gemm.ldd;
+
+  cutlass::library::GemmUniversalArguments args{};
+  args.problem_size = problem;
+  args.cluster_shape = cluster;
+  args.cluster_shape_fallback = cluster;
+  args.batch_count = gemm.batch_count;
+  args.A = gemm.a;
+  args.B = gemm.b;
+  args.C = gemm.c;
+  args.D = gemm.d;
+  args.alpha = gemm.alpha;
+  args.beta = gemm.beta;
+  args.pointer_mode = cutlass::library::ScalarPointerMode::kHost;
+  args.lda = gemm.lda;
+  args.ldb = gemm.ldb;
+  args.ldc = gemm.ldc;
+  args.ldd = gemm.ldd;
+  args.batch_stride_A = gemm.batch_stride_a;
+  args.batch_stride_B = gemm.batch_stride_b;
+  args.batch_stride_C = gemm.batch_stride_c;
+  args.batch_stride_D = gemm.batch_stride_d;
+  args.sm_count = context.sm_count;
+
+  status = operation.can_implement(&config, &args);
+  if (workspace_size != nullptr) {
+    *workspace_size = operation.get_device_workspace_size(&config, &args);
+    return status;
+  }
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+
+  std::vector<uint8_t> host_workspace(operation.get_host_workspace_size(&config));
+  status = operation.initialize(&config, host_workspace.data(), workspace, stream);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+  return operation.run(&args, host_workspace.data(), workspace, stream);
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" int cutlass_sys_manifest_create(cutlass_sys_manifest_t** manifest) {
+  auto* created = new (std::nothrow) cutlass_sys_manifest{};
+  if (created == nullptr) {
+    return static_cast<int>(cutlass::Status::kErrorMemoryAllocation);
+  }
+  cutlass::Status status = created->manifest.initialize();
+  if (status != cutlass::Status::kSuccess) {
+    delete created;
+    return static_cast<int>(status);
+  }
+  *manifest = created;
+  return static_cast<int>(cutlass::Status::kSuccess);
+}
+
+extern "C" void cutlass_sys_manifest_destroy(cutlass_sys_manifest_t* manifest) {
+  delete manifest;
+}
+
+extern "C" size_t cutlass_sys_manifest_operation_count(const cutlass_sys_manifest_t* manifest) {
+  return manifest->manifest.operations().size();
+}
+
+extern "C" const cutlass_sys_operation_t* cutlass_sys_manifest_operation(
+    const cutlass_sys_manifest_t* manifest, size_t index) {
+  const auto& operations = manifest->manifest.operations();
+  if (index >= operations.size()) {
+    return nullptr;
+  }
+  return cutlass_sys::wrap(operations[index].get());
+}
+
+extern "C" void cutlass_sys_operation_description(const cutlass_sys_operation_t* operation,
+                                                  cutlass_sys_operation_description_t* out) {
+  const auto& description = cutlass_sys::unwrap(operation)->description();
+  const auto& tile = description.tile_description;
+  out->name = description.name;
+  out->kind = static_cast<int32_t>(description.kind);
+  out->provider = static_cast<int32_t>(description.provider);
+  out->threadblock_shape = cutlass_sys::to_sys(tile.threadblock_shape);
+  out->threadblock_stages = tile.threadblock_stages;
+  out->warp_count = cutlass_sys::to_sys(tile.warp_count);
+  out->cluster_shape = cutlass_sys::to_sys(tile.cluster_shape);
+  out->instruction_shape = cutlass_sys::to_sys(tile.math_instruction.instruction_shape);
+  out->element_accumulator = static_cast<int32_t>(tile.math_instruction.element_accumulator);
+  out->opcode_class = static_cast<int32_t>(tile.math_instruction.opcode_class);
+  out->minimum_compute_capability = tile.minimum_compute_capability;
+  out->maximum_compute_capability = tile.maximum_compute_capability;
+}
+
+extern "C" int cutlass_sys_operation_gemm_description(const cutlass_sys_operation_t* operation,
+                                                      cutlass_sys_gemm_description_t* out) {
+  const auto& description = cutlass_sys::unwrap(operation)->description();
+  if (description.kind != cutlass::library::OperationKind::kGemm) {
+    return static_cast<int>(cutlass::Status::kErrorNotSupported);
+  }
+  const auto& gemm = static_cast<const cutlass::library::GemmDescription&>(description);
+  out->gemm_kind = static_cast<int32_t>(gemm.gemm_kind);
+  out->a = cutlass_sys::to_sys(gemm.A);
+  out->b = cutlass_sys::to_sys(gemm.B);
+  out->c = cutlass_sys::to_sys(gemm.C);
+  out->d = cutlass_sys::to_sys(gemm.D);
+  out->element_epilogue = static_cast<int32_t>(gemm.element_epilogue);
+  out->transform_a = static_cast<int32_t>(gemm.transform_A);
+  out->transform_b = static_cast<int32_t>(gemm.transform_B);
+  return static_cast<int>(cutlass::Status::kSuccess);
+}
+
+extern "C" size_t cutlass_sys_operation_gemm_workspace_size(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_operation_t* operation,
+    const cutlass_sys_library_gemm_arguments_t* arguments) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::operation_gemm(device.context(), *cutlass_sys::unwrap(operation), *arguments,
+                              nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_operation_gemm_can_implement(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_operation_t* operation,
+    const cutlass_sys_library_gemm_arguments_t* arguments) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::operation_gemm(
+      device.context(), *cutlass_sys::unwrap(operation), *arguments, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_operation_gemm(const cutlass_sys_context_t* context,
+                                          const cutlass_sys_operation_t* operation,
+                                          const cutlass_sys_library_gemm_arguments_t* arguments,
+                                          void* workspace,
+                                          cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::operation_gemm(
+      device.context(), *cutlass_sys::unwrap(operation), *arguments, workspace,
+      static_cast<cudaStream_t>(stream), nullptr));
+}
+
+extern "C" int cutlass_sys_library_handle_create(const cutlass_sys_context_t* context,
+                                                 cutlass_sys_stream_t stream,
+                                                 size_t workspace_size,
+                                                 cutlass_sys_library_handle_t** handle) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  // The Handle allocates its workspace on the current device and throws if
+  // that fails.
+  try {
+    *handle = new cutlass_sys_library_handle{
+        device.context(),
+        cutlass::library::Handle(static_cast<cudaStream_t>(stream), workspace_size)};
+  } catch (...) {
+    return static_cast<int>(cutlass::Status::kErrorMemoryAllocation);
+  }
+  return static_cast<int>(cutlass::Status::kSuccess);
+}
+
+extern "C" void cutlass_sys_library_handle_destroy(cutlass_sys_library_handle_t* handle) {
+  if (handle != nullptr) {
+    cutlass_sys::DeviceScope device(&handle->context, cutlass_sys::kSm75);
+    delete handle;
+  }
+}
+
+extern "C" void cutlass_sys_library_handle_set_stream(cutlass_sys_library_handle_t* handle,
+                                                      cutlass_sys_stream_t stream) {
+  handle->handle.set_stream(static_cast<cudaStream_t>(stream));
+}
+
+extern "C" size_t cutlass_sys_library_handle_workspace_size(
+    const cutlass_sys_library_handle_t* handle) {
+  return handle->handle.get_workspace_size();
+}
+
+extern "C" int cutlass_sys_library_handle_set_workspace_size(cutlass_sys_library_handle_t* handle,
+                                                             size_t workspace_size) {
+  cutlass_sys::DeviceScope device(&handle->context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  try {
+    handle->handle.set_workspace_size(workspace_size);
+  } catch (...) {
+    return static_cast<int>(cutlass::Status::kErrorMemoryAllocation);
+  }
+  return static_cast<int>(cutlass::Status::kSuccess);
+}
+
+extern "C" int cutlass_sys_library_handle_gemm(cutlass_sys_library_handle_t* handle,
+                                               int32_t element_compute,
+                                               const cutlass_sys_gemm_description_t* types,
+                                               const cutlass_sys_library_gemm_arguments_t* gemm) {
+  using cutlass::library::ComplexTransform;
+  using cutlass::library::LayoutTypeID;
+  using cutlass::library::NumericTypeID;
+  using cutlass_sys::to_library;
+
+  cutlass_sys::DeviceScope device(&handle->context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(handle->handle.gemm_universal(
+      to_library<cutlass::gemm::GemmUniversalMode>(gemm->mode), gemm->problem.m,
+      gemm->problem.n, gemm->problem.k, to_library<NumericTypeID>(element_compute),
+      to_library<NumericTypeID>(types->element_epilogue), gemm->alpha,
+      to_library<NumericTypeID>(types->a.element), to_library<LayoutTypeID>(types->a.layout),
+      to_library<ComplexTransform>(types->transform_a), gemm->a, gemm->lda,
+      to_library<NumericTypeID>(types->b.element), to_library<LayoutTypeID>(types->b.layout),
+      to_library<ComplexTransform>(types->transform_b), gemm->b, gemm->ldb, gemm->beta,
+      to_library<NumericTypeID>(types->c.element), to_library<LayoutTypeID>(types->c.layout),
+      gemm->c, gemm->ldc, to_library<NumericTypeID>(types->d.element),
+      to_library<LayoutTypeID>(types->d.layout), gemm->d, gemm->ldd, gemm->batch_count,
+      gemm->batch_stride_a, gemm->batch_stride_b, gemm->batch_stride_c, gemm->batch_stride_d));
+}
+
+extern "C" const cutlass_sys_operation_t* cutlass_sys_library_handle_last_operation(
+    const cutlass_sys_library_handle_t* handle) {
+  return cutlass_sys::wrap(handle->handle.get_last_operation());
+}
+
+#endif
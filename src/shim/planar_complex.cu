@@ -0,0 +1,127 @@
+// Planar-complex GEMM entry points (src/planar_complex.rs).
+
+#include <type_traits>
+
+#include <cutlass/epilogue/thread/linear_combination_planar_complex.h>
+#include <cutlass/gemm/device/gemm_universal_adapter.h>
+#include <cutlass/gemm/kernel/default_gemm_planar_complex_universal.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+// Planar-complex kernels keep four real accumulator tiles per output tile, so
+// the tiles are a quarter of the size of the real GEMM's.
+template <typename Element>
+struct Sm80PlanarComplexTraits;
+
+template <>
+struct Sm80PlanarComplexTraits<cutlass::half_t> {
+  using ThreadblockShape = cutlass::gemm::GemmShape<64, 64, 32>;
+  using WarpShape = cutlass::gemm::GemmShape<32, 32, 32>;
+  static constexpr int kStages = 3;
+};
+
+template <>
+struct Sm80PlanarComplexTraits<float> {
+  using ThreadblockShape = cutlass::gemm::GemmShape<64, 64, 16>;
+  using WarpShape = cutlass::gemm::GemmShape<32, 32, 16>;
+  static constexpr int kStages = 3;
+};
+
+template <typename Element>
+using PlanarComplexGemm = cutlass::gemm::device::GemmUniversalAdapter<
+    typename cutlass::gemm::kernel::DefaultGemmPlanarComplexUniversal<
+        Element, cutlass::layout::RowMajor, cutlass::ComplexTransform::kNone,
+        Sm80TensorOpTraits<Element>::kAlignmentA,
+        Element, cutlass::layout::RowMajor, cutlass::ComplexTransform::kNone,
+        Sm80TensorOpTraits<Element>::kAlignmentB,
+        typename Sm80TensorOpTraits<Element>::ElementC, cutlass::layout::RowMajor,
+        typename Sm80TensorOpTraits<Element>::ElementAccumulator,
+        cutlass::arch::OpClassTensorOp, cutlass::arch::Sm80,
+        typename Sm80PlanarComplexTraits<Element>::ThreadblockShape,
+        typename Sm80PlanarComplexTraits<Element>::WarpShape,
+        typename Sm80TensorOpTraits<Element>::InstructionShape,
+        cutlass::epilogue::thread::LinearCombinationPlanarComplex<
+            typename Sm80TensorOpTraits<Element>::ElementC,
+            Sm80TensorOpTraits<Element>::kAlignmentC,
+            typename Sm80TensorOpTraits<Element>::ElementAccumulator, float>,
+        cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+        Sm80PlanarComplexTraits<Element>::kStages,
+        typename Sm80TensorOpTraits<Element>::MathOperator>::GemmKernel>;
+
+cutlass::Status planar_complex_gemm(const cutlass_sys_planar_complex_gemm_config_t& cfg,
+                                    const cutlass_sys_planar_complex_operands_t* ops,
+                                    void* workspace,
+                                    cudaStream_t stream,
+                                    size_t* workspace_size) {
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (!std::is_same_v<Element, cutlass::half_t> &&
+                  !std::is_same_v<Element, float>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      using Gemm = PlanarComplexGemm<Element>;
+
+      cutlass_sys_planar_complex_operands_t o =
+          ops ? *ops : cutlass_sys_planar_complex_operands_t{};
+      typename Gemm::Arguments args(
+          cfg.batch_count > 1 ? cutlass::gemm::GemmUniversalMode::kBatched
+                              : cutlass::gemm::GemmUniversalMode::kGemm,
+          to_cutlass(cfg.problem),
+          cfg.batch_count,
+          {cutlass::complex<float>(cfg.alpha.re, cfg.alpha.im),
+           cutlass::complex<float>(cfg.beta.re, cfg.beta.im)},
+          o.a_real, o.a_imag, o.b_real, o.b_imag,
+          o.c_real, o.c_imag, o.d_real, o.d_imag,
+          cfg.lda, cfg.lda, cfg.ldb, cfg.ldb, cfg.ldc, cfg.ldc, cfg.ldd, cfg.ldd,
+          cfg.batch_stride_a, cfg.batch_stride_a,
+          cfg.batch_stride_b, cfg.batch_stride_b,
+          cfg.batch_stride_c, cfg.batch_stride_c,
+          cfg.batch_stride_d, cfg.batch_stride_d);
+
+      return query_or_run<Gemm>(args, workspace, stream, workspace_size);
+    }
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_planar_complex_gemm_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_planar_complex_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::planar_complex_gemm(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_planar_complex_gemm_can_implement(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_planar_complex_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(
+      cutlass_sys::planar_complex_gemm(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_planar_complex_gemm(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_planar_complex_gemm_config_t* config,
+    const cutlass_sys_planar_complex_operands_t* operands,
+    void* workspace,
+    cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::planar_complex_gemm(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
@@ -0,0 +1,146 @@
+// Symmetric and Hermitian rank-2k update entry points (src/blas3.rs).
+
+#include <cutlass/gemm/device/rank_2k.h>
+
+#include "blas3.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+template <typename Element, typename Layout, cutlass::FillMode kFill,
+          cutlass::BlasMode kBlasMode>
+using Rank2K = cutlass::gemm::device::Rank2K<
+    Element, Layout,
+    Element, Layout,
+    Element, cutlass::layout::ColumnMajor, kFill,
+    Element,
+    cutlass::arch::OpClassTensorOp, cutlass::arch::Sm80,
+    typename Sm80Blas3Traits<Element>::ThreadblockShape,
+    typename Sm80Blas3Traits<Element>::WarpShape,
+    typename Sm80Blas3Traits<Element>::InstructionShape,
+    Blas3Epilogue<Element>,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+    Sm80Blas3Traits<Element>::kStages,
+    1,
+    1,
+    false,
+    typename Sm80Blas3Traits<Element>::MathOperator,
+    cutlass::ComplexTransform::kNone,
+    cutlass::ComplexTransform::kNone,
+    kBlasMode>;
+
+// SYR2K takes every BLAS3 type, complex ones transposed without conjugation.
+// HER2K is only defined for the complex types and takes a real beta; the
+// kernel applies conj(alpha) to the B * A^H half.
+template <cutlass::BlasMode kBlasMode>
+cutlass::Status rank_2k(const cutlass_sys_rank_2k_config_t& cfg,
+                        const cutlass_sys_rank_2k_operands_t* ops,
+                        void* workspace,
+                        cudaStream_t stream,
+                        size_t* workspace_size) {
+  return dispatch_blas3_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (kBlasMode == cutlass::BlasMode::kHermitian && !is_complex_v<Element>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      return dispatch_fill(cfg.fill, [&](auto fill) {
+        return dispatch_transpose(cfg.trans, [&](auto layout) {
+          using Op = Rank2K<Element, typename decltype(layout)::type, decltype(fill)::value,
+                            kBlasMode>;
+
+          cutlass_sys_complex_f64_t beta = cfg.beta;
+          if constexpr (kBlasMode == cutlass::BlasMode::kHermitian) {
+            beta.im = 0.0;
+          }
+
+          cutlass_sys_rank_2k_operands_t o = ops ? *ops : cutlass_sys_rank_2k_operands_t{};
+          typename Op::Arguments args(
+              cutlass::gemm::GemmUniversalMode::kGemm,
+              {cfg.n, cfg.n, cfg.k},
+              1,
+              {blas3_scalar<Element>(cfg.alpha), blas3_scalar<Element>(beta)},
+              o.a, o.b, o.c, o.d,
+              0, 0, 0, 0,
+              cfg.lda, cfg.ldb, cfg.ldc, cfg.ldd);
+
+          return query_or_run<Op>(args, workspace, stream, workspace_size);
+        });
+      });
+    }
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_syr2k_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_rank_2k_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::rank_2k<cutlass::BlasMode::kSymmetric>(*config, nullptr, nullptr, nullptr,
+                                                       &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_syr2k_can_implement(const cutlass_sys_context_t* context,
+                                               const cutlass_sys_rank_2k_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::rank_2k<cutlass::BlasMode::kSymmetric>(
+      *config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_syr2k(const cutlass_sys_context_t* context,
+                                 const cutlass_sys_rank_2k_config_t* config,
+                                 const cutlass_sys_rank_2k_operands_t* operands,
+                                 void* workspace,
+                                 cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::rank_2k<cutlass::BlasMode::kSymmetric>(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
+
+extern "C" size_t cutlass_sys_her2k_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_rank_2k_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::rank_2k<cutlass::BlasMode::kHermitian>(*config, nullptr, nullptr, nullptr,
+                                                       &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_her2k_can_implement(const cutlass_sys_context_t* context,
+                                               const cutlass_sys_rank_2k_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::rank_2k<cutlass::BlasMode::kHermitian>(
+      *config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_her2k(const cutlass_sys_context_t* context,
+                                 const cutlass_sys_rank_2k_config_t* config,
+                                 const cutlass_sys_rank_2k_operands_t* operands,
+                                 void* workspace,
+                                 cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::rank_2k<cutlass::BlasMode::kHermitian>(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
@@ -0,0 +1,143 @@
+// Symmetric and Hermitian rank-k update entry points (src/blas3.rs).
+
+#include <cutlass/gemm/device/rank_k.h>
+
+#include "blas3.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+template <typename Element, typename LayoutA, cutlass::FillMode kFill,
+          cutlass::BlasMode kBlasMode>
+using RankK = cutlass::gemm::device::RankK<
+    Element, LayoutA,
+    Element, cutlass::layout::ColumnMajor, kFill,
+    Element,
+    cutlass::arch::OpClassTensorOp, cutlass::arch::Sm80,
+    typename Sm80Blas3Traits<Element>::ThreadblockShape,
+    typename Sm80Blas3Traits<Element>::WarpShape,
+    typename Sm80Blas3Traits<Element>::InstructionShape,
+    Blas3Epilogue<Element>,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+    Sm80Blas3Traits<Element>::kStages,
+    1,
+    false,
+    typename Sm80Blas3Traits<Element>::MathOperator,
+    cutlass::ComplexTransform::kNone,
+    kBlasMode>;
+
+// HERK is only defined for the complex types and takes real alpha and beta.
+template <cutlass::BlasMode kBlasMode>
+cutlass::Status rank_k(const cutlass_sys_rank_k_config_t& cfg,
+                       const cutlass_sys_rank_k_operands_t* ops,
+                       void* workspace,
+                       cudaStream_t stream,
+                       size_t* workspace_size) {
+  return dispatch_blas3_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (kBlasMode == cutlass::BlasMode::kHermitian && !is_complex_v<Element>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      return dispatch_fill(cfg.fill, [&](auto fill) {
+        return dispatch_transpose(cfg.trans, [&](auto layout) {
+          using Op = RankK<Element, typename decltype(layout)::type, decltype(fill)::value,
+                           kBlasMode>;
+
+          cutlass_sys_complex_f64_t alpha = cfg.alpha;
+          cutlass_sys_complex_f64_t beta = cfg.beta;
+          if constexpr (kBlasMode == cutlass::BlasMode::kHermitian) {
+            alpha.im = 0.0;
+            beta.im = 0.0;
+          }
+
+          cutlass_sys_rank_k_operands_t o = ops ? *ops : cutlass_sys_rank_k_operands_t{};
+          typename Op::Arguments args(
+              cutlass::gemm::GemmUniversalMode::kGemm,
+              {cfg.n, cfg.n, cfg.k},
+              1,
+              {blas3_scalar<Element>(alpha), blas3_scalar<Element>(beta)},
+              o.a, o.c, o.d,
+              0, 0, 0,
+              cfg.lda, cfg.ldc, cfg.ldd);
+
+          return query_or_run<Op>(args, workspace, stream, workspace_size);
+        });
+      });
+    }
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_syrk_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_rank_k_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::rank_k<cutlass::BlasMode::kSymmetric>(*config, nullptr, nullptr, nullptr,
+                                                      &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_syrk_can_implement(const cutlass_sys_context_t* context,
+                                              const cutlass_sys_rank_k_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::rank_k<cutlass::BlasMode::kSymmetric>(
+      *config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_syrk(const cutlass_sys_context_t* context,
+                                const cutlass_sys_rank_k_config_t* config,
+                                const cutlass_sys_rank_k_operands_t* operands,
+                                void* workspace,
+                                cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::rank_k<cutlass::BlasMode::kSymmetric>(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
+
+extern "C" size_t cutlass_sys_herk_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_rank_k_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::rank_k<cutlass::BlasMode::kHermitian>(*config, nullptr, nullptr, nullptr,
+                                                      &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_herk_can_implement(const cutlass_sys_context_t* context,
+                                              const cutlass_sys_rank_k_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::rank_k<cutlass::BlasMode::kHermitian>(
+      *config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_herk(const cutlass_sys_context_t* context,
+                                const cutlass_sys_rank_k_config_t* config,
+                                const cutlass_sys_rank_k_operands_t* operands,
+                                void* workspace,
+                                cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::rank_k<cutlass::BlasMode::kHermitian>(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
@@ -0,0 +1,179 @@
+// Reduction entry points (src/reduction.rs).
+
+#include <type_traits>
+
+#include <cutlass/functional.h>
+#include <cutlass/layout/tensor.h>
+#include <cutlass/reduction/device/tensor_reduce.h>
+
+#include "reduction.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+cutlass::Status reduce_split_k(const cutlass_sys_split_k_reduce_config_t& cfg,
+                               const cutlass_sys_split_k_reduce_operands_t* ops,
+                               void* workspace,
+                               cudaStream_t stream,
+                               size_t* workspace_size) {
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    using ElementC = typename Sm80Traits<Element>::ElementC;
+    using ElementAccumulator = typename Sm80Traits<Element>::ElementAccumulator;
+    using Reduction = SplitKReduction<ElementC, ElementAccumulator>;
+    using StrideIndex = typename Reduction::StrideIndex;
+
+    if (workspace_size != nullptr) {
+      *workspace_size = 0;
+      return cutlass::Status::kSuccess;
+    }
+
+    typename Reduction::Arguments args(
+        {cfg.m, cfg.n},
+        cfg.partitions,
+        static_cast<size_t>(cfg.partition_stride),
+        {static_cast<ElementAccumulator*>(const_cast<void*>(ops->partials)),
+         StrideIndex(cfg.ldw)},
+        {static_cast<ElementC*>(ops->d), StrideIndex(cfg.ldd)},
+        {static_cast<ElementC*>(const_cast<void*>(ops->c)), StrideIndex(cfg.ldc)},
+        {cfg.alpha, cfg.beta});
+
+    Reduction reduction;
+    cutlass::Status status = reduction.initialize(args, workspace, stream);
+    if (status != cutlass::Status::kSuccess) {
+      return status;
+    }
+    return reduction.run(stream);
+  });
+}
+
+// Calls `f` with the reduction functor over `T` selected by `op` and its
+// identity element.
+template <typename T, typename F>
+cutlass::Status dispatch_reduce_op(cutlass_sys_reduce_op_t op, F&& f) {
+  switch (op) {
+    case CUTLASS_SYS_REDUCE_SUM:
+      return f(cutlass::plus<T>{}, T(0));
+    case CUTLASS_SYS_REDUCE_MAX:
+      return f(cutlass::maximum<T>{}, -cutlass::platform::numeric_limits<T>::infinity());
+    case CUTLASS_SYS_REDUCE_MIN:
+      return f(cutlass::minimum<T>{}, cutlass::platform::numeric_limits<T>::infinity());
+    default:
+      return cutlass::Status::kErrorNotSupported;
+  }
+}
+
+// Reduces one axis of an NHWC tensor with f32 arithmetic. Only the
+// floating-point types are instantiated.
+cutlass::Status reduce_tensor(const cutlass_sys_tensor_reduce_config_t& cfg,
+                              const cutlass_sys_tensor_reduce_operands_t* ops,
+                              void* workspace,
+                              cudaStream_t stream,
+                              size_t* workspace_size) {
+  int axis = static_cast<int>(cfg.axis);
+  if (axis < 0 || axis > 3) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (std::is_same_v<Element, int8_t>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      return dispatch_reduce_op<float>(cfg.op, [&](auto op, float identity) {
+        using Op = cutlass::reduction::device::TensorReduction<
+            Element, Element, cutlass::layout::TensorNHWC, decltype(op), 1, float>;
+
+        cutlass::Tensor4DCoord extent(cfg.n, cfg.h, cfg.w, cfg.c);
+        if (!Op::good_extent(extent, axis)) {
+          return cutlass::Status::kErrorInvalidProblem;
+        }
+        Op reduction(extent, axis);
+        if (workspace_size != nullptr) {
+          *workspace_size = reduction.workspace_size();
+          return cutlass::Status::kSuccess;
+        }
+
+        cutlass::Tensor4DCoord dst_extent = extent;
+        dst_extent[axis] = 1;
+        return reduction.reduce(
+            {static_cast<Element*>(ops->dst), cutlass::layout::TensorNHWC::packed(dst_extent)},
+            {static_cast<Element*>(const_cast<void*>(ops->src)),
+             cutlass::layout::TensorNHWC::packed(extent)},
+            workspace, identity, op, stream);
+      });
+    }
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_reduce_split_k_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_split_k_reduce_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::reduce_split_k(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_reduce_split_k_can_implement(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_split_k_reduce_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::reduce_split_k(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_reduce_split_k(const cutlass_sys_context_t* context,
+                                          const cutlass_sys_split_k_reduce_config_t* config,
+                                          const cutlass_sys_split_k_reduce_operands_t* operands,
+                                          void* workspace,
+                                          cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::reduce_split_k(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
+
+extern "C" size_t cutlass_sys_reduce_tensor_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_tensor_reduce_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::reduce_tensor(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_reduce_tensor_can_implement(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_tensor_reduce_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::reduce_tensor(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_reduce_tensor(const cutlass_sys_context_t* context,
+                                         const cutlass_sys_tensor_reduce_config_t* config,
+                                         const cutlass_sys_tensor_reduce_operands_t* operands,
+                                         void* workspace,
+                                         cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::reduce_tensor(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
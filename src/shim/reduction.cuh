@@ -0,0 +1,28 @@
+// Reduction operators shared by the split-K paths and the reduction entry
+// points.
+
+#pragma once
+
+#include <cutlass/epilogue/thread/linear_combination.h>
+#include <cutlass/reduction/device/reduce_split_k.h>
+#include <cutlass/reduction/kernel/reduce_split_k.h>
+#include <cutlass/reduction/thread/reduction_operators.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+
+// Sums `partitions` accumulator-typed partial tiles and applies
+// `alpha * sum + beta * C`, writing `ElementC`.
+template <typename ElementC, typename ElementAccumulator>
+using SplitKReduction = cutlass::reduction::device::ReduceSplitK<
+    cutlass::reduction::kernel::ReduceSplitK<
+        cutlass::MatrixShape<4, 32 * (128 / cutlass::sizeof_bits<ElementC>::value)>,
+        cutlass::epilogue::thread::LinearCombination<
+            ElementC, 128 / cutlass::sizeof_bits<ElementC>::value, ElementAccumulator,
+            float>,
+        cutlass::reduction::thread::ReduceAdd<
+            ElementAccumulator, ElementAccumulator,
+            128 / cutlass::sizeof_bits<ElementC>::value>>>;
+
+}  // namespace cutlass_sys
@@ -0,0 +1,399 @@
+// Reference GEMM and convolution entry points (src/reference.rs, `reference`
+// feature).
+//
+// Thin wrappers over cutlass::reference::host and cutlass::reference::device,
+// instantiated for the element types of the device entry points so results
+// can be checked against CUTLASS's own conversions and epilogue arithmetic.
+// The host references take host pointers and never touch a device. The device
+// references also back the `verify` flag of the configs (verify.cuh).
+
+#include "conv.cuh"
+#include "verify.cuh"
+
+#if defined(CUTLASS_SYS_REFERENCE)
+
+#include <algorithm>
+#include <tuple>
+#include <type_traits>
+
+#include <cutlass/util/reference/device/convolution.h>
+#include <cutlass/util/reference/device/gemm.h>
+#include <cutlass/util/reference/host/convolution.h>
+#include <cutlass/util/reference/host/gemm.h>
+
+namespace cutlass_sys {
+namespace {
+
+cutlass::Status reference_gemm(const cutlass_sys_gemm_config_t& cfg,
+                               const cutlass_sys_gemm_operands_t& ops) {
+  if (cfg.scheduler != CUTLASS_SYS_TILE_SCHEDULER_DATA_PARALLEL ||
+      cfg.bias != CUTLASS_SYS_BIAS_NONE || cfg.activation != CUTLASS_SYS_ACTIVATION_IDENTITY ||
+      cfg.scale != CUTLASS_SYS_SCALE_NONE || cfg.beta_mode != CUTLASS_SYS_BETA_PER_TENSOR ||
+      cfg.zero_point_mode != CUTLASS_SYS_ZERO_POINT_NONE ||
+      cfg.aux_op != CUTLASS_SYS_AUX_OP_NONE || !default_gemm_types(cfg)) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    return dispatch_gemm_layouts(cfg, [&](auto layout_a, auto layout_b) {
+      using Element = decltype(element);
+      using Traits = Sm80Traits<Element>;
+      using ElementC = typename Traits::ElementC;
+      using LayoutA = decltype(layout_a);
+      using LayoutB = decltype(layout_b);
+      using RowMajor = cutlass::layout::RowMajor;
+
+      cutlass::TensorRef<Element, LayoutA> a(
+          static_cast<Element*>(const_cast<void*>(ops.a)), LayoutA(cfg.lda));
+      cutlass::TensorRef<Element, LayoutB> b(
+          static_cast<Element*>(const_cast<void*>(ops.b)), LayoutB(cfg.ldb));
+      cutlass::TensorRef<ElementC, RowMajor> c(
+          static_cast<ElementC*>(const_cast<void*>(ops.c)), RowMajor(cfg.ldc));
+      cutlass::TensorRef<ElementC, RowMajor> d(static_cast<ElementC*>(ops.d), RowMajor(cfg.ldd));
+      // Without C, D is read as C; beta must then be zero.
+      if (ops.c == nullptr) {
+        c = d;
+      }
+
+      cutlass::reference::host::Gemm<Element, LayoutA, Element, LayoutB, ElementC, RowMajor,
+                                     float, typename Traits::ElementAccumulator>
+          gemm;
+      gemm(to_cutlass(cfg.problem), cfg.alpha, a, b, cfg.beta, c, d,
+           typename Traits::ElementAccumulator(0));
+      return cutlass::Status::kSuccess;
+    });
+  });
+}
+
+template <typename Layout, typename ProblemSize>
+cutlass::Status reference_conv(cutlass::conv::Operator kind,
+                               const ProblemSize& problem,
+                               const cutlass_sys_conv_config_t& cfg,
+                               const cutlass_sys_conv_operands_t& ops) {
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    using Traits = Sm80Traits<Element>;
+    using ElementC = typename Traits::ElementC;
+
+    Layout layout_c = Layout::packed(cutlass::conv::implicit_gemm_tensor_c_extent(kind, problem));
+    cutlass::TensorRef<Element, Layout> a(
+        static_cast<Element*>(const_cast<void*>(ops.a)),
+        Layout::packed(cutlass::conv::implicit_gemm_tensor_a_extent(kind, problem)));
+    cutlass::TensorRef<Element, Layout> b(
+        static_cast<Element*>(const_cast<void*>(ops.b)),
+        Layout::packed(cutlass::conv::implicit_gemm_tensor_b_extent(kind, problem)));
+    cutlass::TensorRef<ElementC, Layout> c(static_cast<ElementC*>(const_cast<void*>(ops.c)),
+                                           layout_c);
+    cutlass::TensorRef<ElementC, Layout> d(static_cast<ElementC*>(ops.d), layout_c);
+    if (ops.c == nullptr) {
+      c = d;
+    }
+
+    if constexpr (std::is_same_v<ProblemSize, cutlass::conv::Conv2dProblemSize>) {
+      cutlass::reference::host::Conv2d<Element, Layout, Element, Layout, ElementC, Layout, float,
+                                       typename Traits::ElementAccumulator>(
+          kind, problem, a, b, c, d, cfg.alpha, cfg.beta);
+    } else {
+      cutlass::reference::host::Conv3d<Element, Layout, Element, Layout, ElementC, Layout, float,
+                                       typename Traits::ElementAccumulator>(
+          kind, problem, a, b, c, d, cfg.alpha, cfg.beta);
+    }
+    return cutlass::Status::kSuccess;
+  });
+}
+
+// A unit-depth config runs the 2-D reference, anything else the 3-D one,
+// which has no grouped convolution.
+cutlass::Status reference_conv(cutlass::conv::Operator kind,
+                               const cutlass_sys_conv_config_t* config,
+                               const cutlass_sys_conv_operands_t* operands) {
+  if (config == nullptr || operands == nullptr || config->groups < 1 ||
+      config->c % config->groups != 0 || config->k % config->groups != 0) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  if (is_conv2d(*config)) {
+    return reference_conv<cutlass::layout::TensorNHWC>(kind, conv2d_problem(*config), *config,
+                                                       *operands);
+  }
+  if (config->groups != 1) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return reference_conv<cutlass::layout::TensorNDHWC>(kind, conv3d_problem(*config), *config,
+                                                      *operands);
+}
+
+// Output type of the kernels for `element`.
+template <typename F>
+cutlass::Status dispatch_output(cutlass_sys_dtype_t element, F&& f) {
+  return dispatch_dtype(element, [&](auto value) {
+    return f(typename Sm80Traits<decltype(value)>::ElementC{});
+  });
+}
+
+// Largest relative difference from the reference a kernel's output may show:
+// the reference accumulates in a different order, and computes F32
+// convolutions with plain FMAs instead of 3xTF32. Integer outputs must match
+// exactly.
+template <typename T>
+constexpr float kVerifyEpsilon = std::is_integral_v<T> ? 0.0f : 1e-2f;
+template <>
+constexpr float kVerifyEpsilon<cutlass::bfloat16_t> = 4e-2f;
+template <>
+constexpr float kVerifyEpsilon<float> = 1e-4f;
+
+// Counts the elements of the rows x columns matrix `d` (leading dimension
+// `ld`) that differ from the packed `reference` by more than `epsilon`
+// relative to max(|reference|, 1).
+template <typename T>
+__global__ void count_mismatches(const T* d,
+                                 int64_t ld,
+                                 const T* reference,
+                                 int64_t rows,
+                                 int64_t columns,
+                                 float epsilon,
+                                 unsigned long long* mismatches) {
+  unsigned long long local = 0;
+  int64_t count = rows * columns;
+  for (int64_t i = blockIdx.x * int64_t(blockDim.x) + threadIdx.x; i < count;
+       i += int64_t(gridDim.x) * blockDim.x) {
+    T value = d[(i / columns) * ld + i % columns];
+    T expected = reference[i];
+    if constexpr (std::is_integral_v<T>) {
+      local += value != expected;
+    } else {
+      float x = float(value);
+      float y = float(expected);
+      local += !(fabsf(x - y) <= epsilon * fmaxf(fabsf(y), 1.0f));
+    }
+  }
+  if (local != 0) {
+    atomicAdd(mismatches, local);
+  }
+}
+
+size_t output_bytes(const VerifyOutput& out) {
+  size_t bytes = 0;
+  dispatch_output(out.element, [&](auto value) {
+    bytes = size_t(out.rows * out.columns) * sizeof(value);
+    return cutlass::Status::kSuccess;
+  });
+  return (bytes + 255) & ~size_t(255);
+}
+
+}  // namespace
+
+size_t verify_scratch_size(const VerifyOutput& out) {
+  return output_bytes(out) + sizeof(unsigned long long);
+}
+
+cutlass::Status compare_output(const VerifyOutput& out, void* scratch, cudaStream_t stream) {
+  auto* mismatches = reinterpret_cast<unsigned long long*>(static_cast<char*>(scratch) +
+                                                           output_bytes(out));
+  return dispatch_output(out.element, [&](auto value) {
+    using T = decltype(value);
+    if (cudaMemsetAsync(mismatches, 0, sizeof(*mismatches), stream) != cudaSuccess) {
+      return cutlass::Status::kErrorInternal;
+    }
+    int64_t count = out.rows * out.columns;
+    if (count > 0) {
+      int blocks = int(std::min<int64_t>((count + 255) / 256, 4096));
+      count_mismatches<T><<<blocks, 256, 0, stream>>>(
+          static_cast<const T*>(out.d), out.ld, static_cast<const T*>(scratch), out.rows,
+          out.columns, kVerifyEpsilon<T>, mismatches);
+    }
+    unsigned long long host = 0;
+    if (cudaMemcpyAsync(&host, mismatches, sizeof(host), cudaMemcpyDeviceToHost, stream) !=
+            cudaSuccess ||
+        cudaStreamSynchronize(stream) != cudaSuccess) {
+      return cutlass::Status::kErrorInternal;
+    }
+    return host == 0 ? cutlass::Status::kSuccess : cutlass::Status::kErrorInternal;
+  });
+}
+
+cutlass::Status reference_gemm_device(const cutlass_sys_gemm_config_t& cfg,
+                                      const cutlass_sys_gemm_operands_t& ops,
+                                      void* reference,
+                                      int64_t ld_reference,
+                                      cudaStream_t stream) {
+  if (cfg.bias != CUTLASS_SYS_BIAS_NONE || cfg.activation != CUTLASS_SYS_ACTIVATION_IDENTITY ||
+      cfg.scale != CUTLASS_SYS_SCALE_NONE || cfg.beta_mode != CUTLASS_SYS_BETA_PER_TENSOR ||
+      cfg.zero_point_mode != CUTLASS_SYS_ZERO_POINT_NONE ||
+      cfg.aux_op != CUTLASS_SYS_AUX_OP_NONE || !default_gemm_types(cfg)) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    return dispatch_gemm_layouts(cfg, [&](auto layout_a, auto layout_b) {
+      using Element = decltype(element);
+      using Traits = Sm80Traits<Element>;
+      using ElementC = typename Traits::ElementC;
+      using LayoutA = decltype(layout_a);
+      using LayoutB = decltype(layout_b);
+      using RowMajor = cutlass::layout::RowMajor;
+
+      cutlass::TensorRef<Element, LayoutA> a(
+          static_cast<Element*>(const_cast<void*>(ops.a)), LayoutA(cfg.lda));
+      cutlass::TensorRef<Element, LayoutB> b(
+          static_cast<Element*>(const_cast<void*>(ops.b)), LayoutB(cfg.ldb));
+      cutlass::TensorRef<ElementC, RowMajor> d(static_cast<ElementC*>(reference),
+                                               RowMajor(ld_reference));
+      // Without C, D is read as C; beta must then be zero.
+      cutlass::TensorRef<ElementC, RowMajor> c =
+          ops.c != nullptr
+              ? cutlass::TensorRef<ElementC, RowMajor>(
+                    static_cast<ElementC*>(const_cast<void*>(ops.c)), RowMajor(cfg.ldc))
+              : cutlass::TensorRef<ElementC, RowMajor>(static_cast<ElementC*>(ops.d),
+                                                       RowMajor(cfg.ldd));
+
+      if (cudaStreamSynchronize(stream) != cudaSuccess) {
+        return cutlass::Status::kErrorInternal;
+      }
+      cutlass::reference::device::Gemm<Element, LayoutA, Element, LayoutB, ElementC, RowMajor,
+                                       float, typename Traits::ElementAccumulator>
+          gemm;
+      gemm(to_cutlass(cfg.problem), cfg.alpha, a, b, cfg.beta, c, d,
+           typename Traits::ElementAccumulator(0));
+      return cudaStreamSynchronize(nullptr) == cudaSuccess ? cutlass::Status::kSuccess
+                                                           : cutlass::Status::kErrorInternal;
+    });
+  });
+}
+
+cutlass::Status reference_conv_device(cutlass::conv::Operator kind,
+                                      int rank,
+                                      const cutlass_sys_conv_config_t& cfg,
+                                      const cutlass_sys_conv_operands_t& ops,
+                                      void* reference,
+                                      cudaStream_t stream) {
+  // The device references have no grouped convolution.
+  if (cfg.groups != 1) {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  return dispatch_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    using Traits = Sm80Traits<Element>;
+    using ElementC = typename Traits::ElementC;
+    using ElementAccumulator = typename Traits::ElementAccumulator;
+
+    auto refs = [&](auto layout, const auto& problem) {
+      using Layout = decltype(layout);
+      Layout layout_c =
+          Layout::packed(cutlass::conv::implicit_gemm_tensor_c_extent(kind, problem));
+      cutlass::TensorRef<Element, Layout> a(
+          static_cast<Element*>(const_cast<void*>(ops.a)),
+          Layout::packed(cutlass::conv::implicit_gemm_tensor_a_extent(kind, problem)));
+      cutlass::TensorRef<Element, Layout> b(
+          static_cast<Element*>(const_cast<void*>(ops.b)),
+          Layout::packed(cutlass::conv::implicit_gemm_tensor_b_extent(kind, problem)));
+      const void* c = ops.c != nullptr ? ops.c : ops.d;
+      cutlass::TensorRef<ElementC, Layout> c_ref(static_cast<ElementC*>(const_cast<void*>(c)),
+                                                 layout_c);
+      cutlass::TensorRef<ElementC, Layout> d(static_cast<ElementC*>(reference), layout_c);
+      return std::make_tuple(a, b, c_ref, d);
+    };
+
+    if (rank == 2) {
+      auto problem = conv2d_problem(cfg);
+      auto [a, b, c, d] = refs(cutlass::layout::TensorNHWC{}, problem);
+      return cutlass::reference::device::Conv2d<Element, cutlass::layout::TensorNHWC, Element,
+                                                cutlass::layout::TensorNHWC, ElementC,
+                                                cutlass::layout::TensorNHWC, float,
+                                                ElementAccumulator>(
+          kind, problem, a, b, c, d, cfg.alpha, cfg.beta, stream);
+    }
+    auto problem = conv3d_problem(cfg);
+    auto [a, b, c, d] = refs(cutlass::layout::TensorNDHWC{}, problem);
+    return cutlass::reference::device::Conv3d<Element, cutlass::layout::TensorNDHWC, Element,
+                                              cutlass::layout::TensorNDHWC, ElementC,
+                                              cutlass::layout::TensorNDHWC, float,
+                                              ElementAccumulator>(
+        kind, problem, a, b, c, d, cfg.alpha, cfg.beta, stream);
+  });
+}
+
+}  // namespace cutlass_sys
+
+extern "C" int cutlass_sys_reference_gemm(const cutlass_sys_gemm_config_t* config,
+                                          const cutlass_sys_gemm_operands_t* operands) {
+  if (config == nullptr || operands == nullptr) {
+    return static_cast<int>(cutlass::Status::kErrorInvalidProblem);
+  }
+  return static_cast<int>(cutlass_sys::reference_gemm(*config, *operands));
+}
+
+extern "C" int cutlass_sys_reference_conv_fprop(const cutlass_sys_conv_config_t* config,
+                                                const cutlass_sys_conv_operands_t* operands) {
+  return static_cast<int>(
+      cutlass_sys::reference_conv(cutlass::conv::Operator::kFprop, config, operands));
+}
+
+extern "C" int cutlass_sys_reference_conv_dgrad(const cutlass_sys_conv_config_t* config,
+                                                const cutlass_sys_conv_operands_t* operands) {
+  return static_cast<int>(
+      cutlass_sys::reference_conv(cutlass::conv::Operator::kDgrad, config, operands));
+}
+
+extern "C" int cutlass_sys_reference_conv_wgrad(const cutlass_sys_conv_config_t* config,
+                                                const cutlass_sys_conv_operands_t* operands) {
+  return static_cast<int>(
+      cutlass_sys::reference_conv(cutlass::conv::Operator::kWgrad, config, operands));
+}
+
+extern "C" int cutlass_sys_reference_gemm_device(const cutlass_sys_context_t* context,
+                                                 const cutlass_sys_gemm_config_t* config,
+                                                 const cutlass_sys_gemm_operands_t* operands,
+                                                 cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::reference_gemm_device(
+      *config, *operands, operands->d, config->ldd, static_cast<cudaStream_t>(stream)));
+}
+
+namespace cutlass_sys {
+namespace {
+
+cutlass::Status reference_conv_device(cutlass::conv::Operator kind,
+                                      const cutlass_sys_context_t* context,
+                                      const cutlass_sys_conv_config_t* config,
+                                      const cutlass_sys_conv_operands_t* operands,
+                                      cudaStream_t stream) {
+  DeviceScope device(context);
+  if (!device) {
+    return device.status();
+  }
+  return reference_conv_device(kind, is_conv2d(*config) ? 2 : 3, *config, *operands,
+                               operands->d, stream);
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" int cutlass_sys_reference_conv_device_fprop(const cutlass_sys_context_t* context,
+                                                       const cutlass_sys_conv_config_t* config,
+                                                       const cutlass_sys_conv_operands_t* operands,
+                                                       cutlass_sys_stream_t stream) {
+  return static_cast<int>(cutlass_sys::reference_conv_device(
+      cutlass::conv::Operator::kFprop, context, config, operands,
+      static_cast<cudaStream_t>(stream)));
+}
+
+extern "C" int cutlass_sys_reference_conv_device_dgrad(const cutlass_sys_context_t* context,
+                                                       const cutlass_sys_conv_config_t* config,
+                                                       const cutlass_sys_conv_operands_t* operands,
+                                                       cutlass_sys_stream_t stream) {
+  return static_cast<int>(cutlass_sys::reference_conv_device(
+      cutlass::conv::Operator::kDgrad, context, config, operands,
+      static_cast<cudaStream_t>(stream)));
+}
+
+extern "C" int cutlass_sys_reference_conv_device_wgrad(const cutlass_sys_context_t* context,
+                                                       const cutlass_sys_conv_config_t* config,
+                                                       const cutlass_sys_conv_operands_t* operands,
+                                                       cutlass_sys_stream_t stream) {
+  return static_cast<int>(cutlass_sys::reference_conv_device(
+      cutlass::conv::Operator::kWgrad, context, config, operands,
+      static_cast<cudaStream_t>(stream)));
+}
+
+#endif
@@ -0,0 +1,34 @@
+// Hopper (SM90) kernels, compiled with the `sm90` feature. Not part of the C
+// ABI: the GEMM entry points route to them on SM90 devices.
+
+#pragma once
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+
+#if defined(CUTLASS_SYS_SM90)
+
+// Whether the SM90 device described by `context` can run `cfg` on a
+// warp-specialized TMA / WGMMA kernel (gemm_sm90.cu). Only f16, bf16 and s8
+// problems with a plain alpha / beta epilogue qualify, and the TMA descriptors
+// additionally need 16-byte aligned leading dimensions.
+bool sm90_gemm_supported(const cutlass_sys_context_t& context,
+                         const cutlass_sys_gemm_config_t& cfg);
+
+// Runs `cfg` on the SM90 kernel, or reports its workspace size and
+// can_implement status when `workspace_size` is non-null, like query_or_run.
+cutlass::Status sm90_gemm(const cutlass_sys_context_t& context,
+                          const cutlass_sys_gemm_config_t& cfg,
+                          const cutlass_sys_gemm_operands_t* ops,
+                          void* workspace,
+                          cudaStream_t stream,
+                          size_t* workspace_size);
+
+// Tile one CTA of the SM90 kernel for `cfg` computes, for a config
+// sm90_gemm_supported accepts.
+cutlass_sys_gemm_coord_t sm90_gemm_tile_shape(const cutlass_sys_gemm_config_t& cfg);
+
+#endif
+
+}  // namespace cutlass_sys
@@ -0,0 +1,274 @@
+// 2:4 structured-sparse GEMM entry points (src/sparse.rs).
+
+#include <type_traits>
+
+#include <cutlass/epilogue/thread/linear_combination.h>
+#include <cutlass/gemm/device/gemm_sparse.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+// Kernel configuration of the SM80 sparse tensor-core instantiations. There
+// is no f32 entry: TF32 sparsity is 1:2 rather than 2:4.
+template <typename Element>
+struct Sm80SparseTraits;
+
+template <>
+struct Sm80SparseTraits<cutlass::half_t> {
+  using ThreadblockShape = cutlass::gemm::GemmShape<128, 128, 64>;
+  using WarpShape = cutlass::gemm::GemmShape<64, 64, 64>;
+  using InstructionShape = cutlass::gemm::GemmShape<16, 8, 32>;
+  static constexpr int kStages = 3;
+};
+
+template <>
+struct Sm80SparseTraits<cutlass::bfloat16_t> : Sm80SparseTraits<cutlass::half_t> {};
+
+template <>
+struct Sm80SparseTraits<int8_t> {
+  using ThreadblockShape = cutlass::gemm::GemmShape<128, 128, 128>;
+  using WarpShape = cutlass::gemm::GemmShape<64, 64, 128>;
+  using InstructionShape = cutlass::gemm::GemmShape<16, 8, 64>;
+  static constexpr int kStages = 3;
+};
+
+// A is row-major and compressed to M x K/2; B is column-major for every type,
+// as the sparse MMAs require.
+template <typename Element>
+using SparseGemm = cutlass::gemm::device::SparseGemm<
+    Element, cutlass::layout::RowMajor,
+    Element, cutlass::layout::ColumnMajor,
+    typename Sm80Traits<Element>::ElementC, cutlass::layout::RowMajor,
+    typename Sm80Traits<Element>::ElementAccumulator,
+    cutlass::arch::OpClassTensorOp, cutlass::arch::Sm80,
+    typename Sm80SparseTraits<Element>::ThreadblockShape,
+    typename Sm80SparseTraits<Element>::WarpShape,
+    typename Sm80SparseTraits<Element>::InstructionShape,
+    cutlass::epilogue::thread::LinearCombination<
+        typename Sm80Traits<Element>::ElementC, Sm80Traits<Element>::kAlignmentC,
+        typename Sm80Traits<Element>::ElementAccumulator, float>,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+    Sm80SparseTraits<Element>::kStages,
+    Sm80Traits<Element>::kAlignmentA,
+    Sm80Traits<Element>::kAlignmentB,
+    true,
+    typename Sm80Traits<Element>::MathOperator>;
+
+// Calls `f` with a value of each operand type that has a sparse instantiation.
+template <typename F>
+cutlass::Status dispatch_sparse_dtype(cutlass_sys_dtype_t dtype, F&& f) {
+  return dispatch_dtype(dtype, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (std::is_same_v<Element, float>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      return f(element);
+    }
+  });
+}
+
+template <typename Gemm>
+cutlass::MatrixCoord metadata_extent(const cutlass_sys_sparse_gemm_config_t& cfg) {
+  return {cfg.problem.m, cfg.problem.k / Gemm::kSparse / Gemm::kElementsPerElementE};
+}
+
+// One thread per metadata element: keeps the two largest-magnitude values of
+// each group of four along K, packs their 2-bit indices, and stores the word
+// at the interleaved position the sparse MMA expects (cutlass::reorder_meta).
+template <typename Gemm, typename Element>
+__global__ void compress_kernel(int m,
+                                int k,
+                                const Element* dense,
+                                int64_t ld_dense,
+                                Element* compressed,
+                                int64_t ld_compressed,
+                                typename Gemm::ElementE* metadata) {
+  using ElementE = typename Gemm::ElementE;
+  constexpr int kElementsPerElementE = Gemm::kElementsPerElementE;
+  constexpr int kGroups = kElementsPerElementE / 2;
+
+  int meta_cols = k / Gemm::kSparse / kElementsPerElementE;
+  int idx = blockIdx.x * blockDim.x + threadIdx.x;
+  if (idx >= m * meta_cols) {
+    return;
+  }
+  int row = idx / meta_cols;
+  int col = idx % meta_cols;
+
+  const Element* src = dense + row * ld_dense + col * kGroups * 4;
+  Element* dst = compressed + row * ld_compressed + col * kGroups * 2;
+  ElementE word = 0;
+  for (int g = 0; g < kGroups; ++g) {
+    float mag[4];
+    for (int i = 0; i < 4; ++i) {
+      float v = static_cast<float>(src[g * 4 + i]);
+      mag[i] = v < 0 ? -v : v;
+    }
+    int first = 0;
+    for (int i = 1; i < 4; ++i) {
+      if (mag[i] > mag[first]) {
+        first = i;
+      }
+    }
+    int second = first == 0 ? 1 : 0;
+    for (int i = 0; i < 4; ++i) {
+      if (i != first && mag[i] > mag[second]) {
+        second = i;
+      }
+    }
+    int lo = first < second ? first : second;
+    int hi = first < second ? second : first;
+    dst[g * 2] = src[g * 4 + lo];
+    dst[g * 2 + 1] = src[g * 4 + hi];
+    word |= static_cast<ElementE>(lo | (hi << 2)) << (g * 4);
+  }
+
+  int group = sizeof(ElementE) == 2 ? 32 : 16;
+  int interweave = sizeof(ElementE) == 2 ? 4 : 2;
+  int dest_row = row / group * group + (row % 8) * interweave + (row % group) / 8;
+  int dest_col = col;
+  if (dest_row % 2 == 0 && dest_col % 2 == 1) {
+    ++dest_row;
+    --dest_col;
+  } else if (dest_row % 2 == 1 && dest_col % 2 == 0) {
+    --dest_row;
+    ++dest_col;
+  }
+
+  auto layout = Gemm::LayoutE::packed({m, meta_cols});
+  metadata[layout({dest_row, dest_col})] = word;
+}
+
+// K must cover whole metadata elements.
+template <typename Gemm>
+bool valid_k(const cutlass_sys_sparse_gemm_config_t& cfg) {
+  return cfg.problem.k % (Gemm::kSparse * Gemm::kElementsPerElementE) == 0;
+}
+
+cutlass::Status metadata_size(const cutlass_sys_sparse_gemm_config_t& cfg, size_t* size) {
+  return dispatch_sparse_dtype(cfg.element, [&](auto element) {
+    using Gemm = SparseGemm<decltype(element)>;
+    if (!valid_k<Gemm>(cfg)) {
+      return cutlass::Status::kErrorInvalidProblem;
+    }
+    auto extent = metadata_extent<Gemm>(cfg);
+    *size = size_t(extent.row()) * size_t(extent.column()) * sizeof(typename Gemm::ElementE);
+    return cutlass::Status::kSuccess;
+  });
+}
+
+cutlass::Status compress(const cutlass_sys_sparse_gemm_config_t& cfg,
+                         const void* dense,
+                         int64_t ld_dense,
+                         void* compressed,
+                         void* metadata,
+                         cudaStream_t stream) {
+  return dispatch_sparse_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    using Gemm = SparseGemm<Element>;
+    if (!valid_k<Gemm>(cfg)) {
+      return cutlass::Status::kErrorInvalidProblem;
+    }
+    auto extent = metadata_extent<Gemm>(cfg);
+    int threads = 256;
+    int blocks = (extent.row() * extent.column() + threads - 1) / threads;
+    compress_kernel<Gemm, Element><<<blocks, threads, 0, stream>>>(
+        cfg.problem.m, cfg.problem.k, static_cast<const Element*>(dense), ld_dense,
+        static_cast<Element*>(compressed), cfg.lda,
+        static_cast<typename Gemm::ElementE*>(metadata));
+    return cudaGetLastError() == cudaSuccess ? cutlass::Status::kSuccess
+                                             : cutlass::Status::kErrorInternal;
+  });
+}
+
+cutlass::Status sparse_gemm(const cutlass_sys_sparse_gemm_config_t& cfg,
+                            const cutlass_sys_sparse_gemm_operands_t* ops,
+                            void* workspace,
+                            cudaStream_t stream,
+                            size_t* workspace_size) {
+  return dispatch_sparse_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    using Gemm = SparseGemm<Element>;
+    using ElementC = typename Gemm::ElementC;
+    if (!valid_k<Gemm>(cfg)) {
+      return cutlass::Status::kErrorInvalidProblem;
+    }
+
+    cutlass_sys_sparse_gemm_operands_t o = ops ? *ops : cutlass_sys_sparse_gemm_operands_t{};
+    typename Gemm::Arguments args{
+        to_cutlass(cfg.problem),
+        {static_cast<const Element*>(o.a), typename Gemm::LayoutA(cfg.lda)},
+        {static_cast<const Element*>(o.b), typename Gemm::LayoutB(cfg.ldb)},
+        {static_cast<const ElementC*>(o.c), typename Gemm::LayoutC(cfg.ldc)},
+        {static_cast<ElementC*>(o.d), typename Gemm::LayoutC(cfg.ldd)},
+        {static_cast<const typename Gemm::ElementE*>(o.e),
+         Gemm::LayoutE::packed(metadata_extent<Gemm>(cfg))},
+        {cfg.alpha, cfg.beta},
+        cfg.split_k_slices,
+    };
+
+    return query_or_run<Gemm>(args, workspace, stream, workspace_size);
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_sparse_metadata_size(
+    const cutlass_sys_sparse_gemm_config_t* config) {
+  size_t size = 0;
+  cutlass_sys::metadata_size(*config, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_sparse_compress(const cutlass_sys_context_t* context,
+                                           const cutlass_sys_sparse_gemm_config_t* config,
+                                           const void* dense,
+                                           int64_t ld_dense,
+                                           void* compressed,
+                                           void* metadata,
+                                           cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::compress(
+      *config, dense, ld_dense, compressed, metadata, static_cast<cudaStream_t>(stream)));
+}
+
+extern "C" size_t cutlass_sys_sparse_gemm_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_sparse_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::sparse_gemm(*config, nullptr, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_sparse_gemm_can_implement(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_sparse_gemm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::sparse_gemm(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_sparse_gemm(const cutlass_sys_context_t* context,
+                                       const cutlass_sys_sparse_gemm_config_t* config,
+                                       const cutlass_sys_sparse_gemm_operands_t* operands,
+                                       void* workspace,
+                                       cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::sparse_gemm(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
@@ -0,0 +1,138 @@
+// Symmetric and Hermitian matrix-matrix multiply entry points (src/blas3.rs).
+
+#include <cutlass/gemm/device/symm.h>
+
+#include "blas3.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+template <typename Element, cutlass::SideMode kSide, cutlass::FillMode kFill,
+          cutlass::BlasMode kBlasMode>
+using Symm = cutlass::gemm::device::Symm<
+    Element, cutlass::layout::ColumnMajor, kSide, kFill,
+    Element, cutlass::layout::ColumnMajor,
+    Element, cutlass::layout::ColumnMajor,
+    Element,
+    cutlass::arch::OpClassTensorOp, cutlass::arch::Sm80,
+    typename Sm80Blas3Traits<Element>::ThreadblockShape,
+    typename Sm80Blas3Traits<Element>::WarpShape,
+    typename Sm80Blas3Traits<Element>::InstructionShape,
+    Blas3Epilogue<Element>,
+    cutlass::gemm::threadblock::GemmIdentityThreadblockSwizzle<>,
+    Sm80Blas3Traits<Element>::kStages,
+    1,
+    1,
+    false,
+    typename Sm80Blas3Traits<Element>::MathOperator,
+    kBlasMode>;
+
+// HEMM is only defined for the complex types.
+template <cutlass::BlasMode kBlasMode>
+cutlass::Status symm(const cutlass_sys_symm_config_t& cfg,
+                     const cutlass_sys_symm_operands_t* ops,
+                     void* workspace,
+                     cudaStream_t stream,
+                     size_t* workspace_size) {
+  return dispatch_blas3_dtype(cfg.element, [&](auto element) {
+    using Element = decltype(element);
+    if constexpr (kBlasMode == cutlass::BlasMode::kHermitian && !is_complex_v<Element>) {
+      return cutlass::Status::kErrorInvalidDataType;
+    } else {
+      return dispatch_side(cfg.side, [&](auto side) {
+        return dispatch_fill(cfg.fill, [&](auto fill) {
+          using Op = Symm<Element, decltype(side)::value, decltype(fill)::value, kBlasMode>;
+
+          // A is m x m on the left and n x n on the right.
+          int order = cfg.side == CUTLASS_SYS_SIDE_LEFT ? cfg.m : cfg.n;
+          cutlass_sys_symm_operands_t o = ops ? *ops : cutlass_sys_symm_operands_t{};
+          typename Op::Arguments args(
+              cutlass::gemm::GemmUniversalMode::kGemm,
+              {cfg.m, cfg.n, order},
+              1,
+              {blas3_scalar<Element>(cfg.alpha), blas3_scalar<Element>(cfg.beta)},
+              o.a, o.b, o.c, o.d,
+              0, 0, 0, 0,
+              cfg.lda, cfg.ldb, cfg.ldc, cfg.ldd);
+
+          return query_or_run<Op>(args, workspace, stream, workspace_size);
+        });
+      });
+    }
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_symm_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_symm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::symm<cutlass::BlasMode::kSymmetric>(*config, nullptr, nullptr, nullptr,
+                                                    &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_symm_can_implement(const cutlass_sys_context_t* context,
+                                              const cutlass_sys_symm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(
+      cutlass_sys::symm<cutlass::BlasMode::kSymmetric>(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_symm(const cutlass_sys_context_t* context,
+                                const cutlass_sys_symm_config_t* config,
+                                const cutlass_sys_symm_operands_t* operands,
+                                void* workspace,
+                                cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::symm<cutlass::BlasMode::kSymmetric>(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
+
+extern "C" size_t cutlass_sys_hemm_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_symm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::symm<cutlass::BlasMode::kHermitian>(*config, nullptr, nullptr, nullptr,
+                                                    &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_hemm_can_implement(const cutlass_sys_context_t* context,
+                                              const cutlass_sys_symm_config_t* config) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(
+      cutlass_sys::symm<cutlass::BlasMode::kHermitian>(*config, nullptr, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_hemm(const cutlass_sys_context_t* context,
+                                const cutlass_sys_symm_config_t* config,
+                                const cutlass_sys_symm_operands_t* operands,
+                                void* workspace,
+                                cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  return static_cast<int>(cutlass_sys::symm<cutlass::BlasMode::kHermitian>(
+      *config, operands, workspace, static_cast<cudaStream_t>(stream), nullptr));
+}
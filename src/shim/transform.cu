@@ -0,0 +1,406 @@
+// Layout transform, permutation and conversion entry points (src/transform.rs).
+
+#include <algorithm>
+
+#include <cuda_runtime.h>
+
+#include <cutlass/numeric_conversion.h>
+#include <cutlass/numeric_types.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+namespace {
+
+constexpr int kTile = 32;
+constexpr int kTileRows = 8;
+constexpr int kMaxGridY = 65535;
+constexpr int kMaxGridZ = 65535;
+
+// Calls `f` with a `Type<T>` of an unsigned word as wide as an element of
+// `dtype`. Permutations only move elements, so any same-sized type will do.
+template <typename F>
+cutlass::Status dispatch_element_bytes(cutlass_sys_dtype_t dtype, F&& f) {
+  switch (dtype) {
+    case CUTLASS_SYS_DTYPE_S8:
+    case CUTLASS_SYS_DTYPE_E4M3:
+    case CUTLASS_SYS_DTYPE_E5M2:
+      return f(Type<uint8_t>{});
+    case CUTLASS_SYS_DTYPE_F16:
+    case CUTLASS_SYS_DTYPE_BF16:
+      return f(Type<uint16_t>{});
+    case CUTLASS_SYS_DTYPE_F32:
+    case CUTLASS_SYS_DTYPE_S32:
+      return f(Type<uint32_t>{});
+    case CUTLASS_SYS_DTYPE_F64:
+    case CUTLASS_SYS_DTYPE_C32:
+      return f(Type<uint64_t>{});
+    case CUTLASS_SYS_DTYPE_C64:
+      return f(Type<uint4>{});
+    default:
+      return cutlass::Status::kErrorInvalidDataType;
+  }
+}
+
+// Transposes `batch` packed row-major `rows x cols` matrices through a padded
+// shared-memory tile, so both the loads and the stores are coalesced.
+template <typename T>
+__global__ void transpose_kernel(const T* __restrict__ src,
+                                 T* __restrict__ dst,
+                                 int64_t batch,
+                                 int64_t rows,
+                                 int64_t cols) {
+  __shared__ T tile[kTile][kTile + 1];
+  int64_t col0 = int64_t(blockIdx.x) * kTile;
+  int64_t row_tiles = (rows + kTile - 1) / kTile;
+  for (int64_t b = blockIdx.z; b < batch; b += gridDim.z) {
+    const T* in = src + b * rows * cols;
+    T* out = dst + b * rows * cols;
+    for (int64_t t = blockIdx.y; t < row_tiles; t += gridDim.y) {
+      int64_t row0 = t * kTile;
+      for (int i = threadIdx.y; i < kTile; i += kTileRows) {
+        int64_t r = row0 + i;
+        int64_t c = col0 + threadIdx.x;
+        if (r < rows && c < cols) {
+          tile[i][threadIdx.x] = in[r * cols + c];
+        }
+      }
+      __syncthreads();
+      for (int i = threadIdx.y; i < kTile; i += kTileRows) {
+        int64_t c = col0 + i;
+        int64_t r = row0 + threadIdx.x;
+        if (c < cols && r < rows) {
+          out[c * rows + r] = tile[threadIdx.x][i];
+        }
+      }
+      __syncthreads();
+    }
+  }
+}
+
+struct Permutation {
+  int64_t extent[4];
+  int perm[4];
+};
+
+// Writes output element `idx` (packed over the permuted extent) from the
+// input coordinate it maps to.
+template <typename T>
+__global__ void permute_kernel(const T* __restrict__ src,
+                               T* __restrict__ dst,
+                               Permutation p,
+                               int64_t total) {
+  int64_t stride[4];
+  stride[3] = 1;
+  for (int i = 2; i >= 0; --i) {
+    stride[i] = stride[i + 1] * p.extent[i + 1];
+  }
+  for (int64_t idx = int64_t(blockIdx.x) * blockDim.x + threadIdx.x; idx < total;
+       idx += int64_t(gridDim.x) * blockDim.x) {
+    int64_t rest = idx;
+    int64_t offset = 0;
+    for (int i = 3; i >= 0; --i) {
+      int64_t extent = p.extent[p.perm[i]];
+      offset += (rest % extent) * stride[p.perm[i]];
+      rest /= extent;
+    }
+    dst[idx] = src[offset];
+  }
+}
+
+// Splits `perm` into identity leading axes `[0, p)` followed by the axis
+// ranges `[q, 4)` and `[p, q)`, i.e. a batched transpose. Returns false for
+// any other permutation.
+bool as_batched_transpose(const Permutation& perm, int64_t* batch, int64_t* rows, int64_t* cols) {
+  for (int p = 0; p < 3; ++p) {
+    if (p > 0 && perm.perm[p - 1] != p - 1) {
+      return false;
+    }
+    for (int q = p + 1; q < 4; ++q) {
+      bool match = true;
+      int i = p;
+      for (int axis = q; axis < 4; ++axis) {
+        match = match && perm.perm[i++] == axis;
+      }
+      for (int axis = p; axis < q; ++axis) {
+        match = match && perm.perm[i++] == axis;
+      }
+      if (match) {
+        *batch = *rows = *cols = 1;
+        for (int axis = 0; axis < 4; ++axis) {
+          int64_t& dim = axis < p ? *batch : axis < q ? *rows : *cols;
+          dim *= perm.extent[axis];
+        }
+        return true;
+      }
+    }
+  }
+  return false;
+}
+
+cutlass::Status permute(const cutlass_sys_permute_config_t& cfg,
+                        const cutlass_sys_transform_operands_t* ops,
+                        cudaStream_t stream,
+                        size_t* workspace_size) {
+  Permutation perm;
+  int64_t total = 1;
+  unsigned seen = 0;
+  for (int i = 0; i < 4; ++i) {
+    if (cfg.extent[i] < 0 || cfg.perm[i] < 0 || cfg.perm[i] > 3) {
+      return cutlass::Status::kErrorInvalidProblem;
+    }
+    perm.extent[i] = cfg.extent[i];
+    perm.perm[i] = cfg.perm[i];
+    total *= cfg.extent[i];
+    seen |= 1u << cfg.perm[i];
+  }
+  if (seen != 0xf) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+
+  return dispatch_element_bytes(cfg.element, [&](auto type) {
+    using T = typename decltype(type)::type;
+    if (workspace_size != nullptr) {
+      *workspace_size = 0;
+      return cutlass::Status::kSuccess;
+    }
+    if (total == 0) {
+      return cutlass::Status::kSuccess;
+    }
+
+    const T* src = static_cast<const T*>(ops->src);
+    T* dst = static_cast<T*>(ops->dst);
+    int64_t batch, rows, cols;
+    if (perm.perm[0] == 0 && perm.perm[1] == 1 && perm.perm[2] == 2 && perm.perm[3] == 3) {
+      if (cudaMemcpyAsync(dst, src, total * sizeof(T), cudaMemcpyDeviceToDevice, stream) !=
+          cudaSuccess) {
+        return cutlass::Status::kErrorInternal;
+      }
+    } else if (as_batched_transpose(perm, &batch, &rows, &cols)) {
+      dim3 block(kTile, kTileRows);
+      dim3 grid(static_cast<unsigned>((cols + kTile - 1) / kTile),
+                static_cast<unsigned>(std::min<int64_t>((rows + kTile - 1) / kTile, kMaxGridY)),
+                static_cast<unsigned>(std::min<int64_t>(batch, kMaxGridZ)));
+      transpose_kernel<T><<<grid, block, 0, stream>>>(src, dst, batch, rows, cols);
+    } else {
+      int threads = 256;
+      int64_t blocks = std::min<int64_t>((total + threads - 1) / threads, 65535 * 8);
+      permute_kernel<T><<<static_cast<unsigned>(blocks), threads, 0, stream>>>(
+          src, dst, perm, total);
+    }
+    return cudaGetLastError() == cudaSuccess ? cutlass::Status::kSuccess
+                                             : cutlass::Status::kErrorInternal;
+  });
+}
+
+// A layout transform is the permutation taking the stored axis order of `src`
+// to that of `dst`.
+cutlass::Status layout_transform(const cutlass_sys_layout_transform_config_t& cfg,
+                                 const cutlass_sys_transform_operands_t* ops,
+                                 cudaStream_t stream,
+                                 size_t* workspace_size) {
+  cutlass_sys_permute_config_t permute_cfg{};
+  permute_cfg.element = cfg.element;
+  if (cfg.src == CUTLASS_SYS_TENSOR_LAYOUT_NHWC) {
+    int extent[4] = {cfg.n, cfg.h, cfg.w, cfg.c};
+    std::copy(extent, extent + 4, permute_cfg.extent);
+  } else if (cfg.src == CUTLASS_SYS_TENSOR_LAYOUT_NCHW) {
+    int extent[4] = {cfg.n, cfg.c, cfg.h, cfg.w};
+    std::copy(extent, extent + 4, permute_cfg.extent);
+  } else {
+    return cutlass::Status::kErrorNotSupported;
+  }
+
+  int identity[4] = {0, 1, 2, 3};
+  int nhwc_to_nchw[4] = {0, 3, 1, 2};
+  int nchw_to_nhwc[4] = {0, 2, 3, 1};
+  const int* perm = nullptr;
+  if (cfg.dst == cfg.src) {
+    perm = identity;
+  } else if (cfg.dst == CUTLASS_SYS_TENSOR_LAYOUT_NCHW) {
+    perm = nhwc_to_nchw;
+  } else if (cfg.dst == CUTLASS_SYS_TENSOR_LAYOUT_NHWC) {
+    perm = nchw_to_nhwc;
+  } else {
+    return cutlass::Status::kErrorNotSupported;
+  }
+  std::copy(perm, perm + 4, permute_cfg.perm);
+
+  return permute(permute_cfg, ops, stream, workspace_size);
+}
+
+// Calls `f` with a `Type<T>` of the CUTLASS element type of `dtype`, for the
+// types a conversion takes.
+template <typename F>
+cutlass::Status dispatch_convertible(cutlass_sys_dtype_t dtype, F&& f) {
+  switch (dtype) {
+    case CUTLASS_SYS_DTYPE_F16:
+      return f(Type<cutlass::half_t>{});
+    case CUTLASS_SYS_DTYPE_BF16:
+      return f(Type<cutlass::bfloat16_t>{});
+    case CUTLASS_SYS_DTYPE_F32:
+      return f(Type<float>{});
+    case CUTLASS_SYS_DTYPE_S8:
+      return f(Type<int8_t>{});
+    case CUTLASS_SYS_DTYPE_E4M3:
+      return f(Type<cutlass::float_e4m3_t>{});
+    case CUTLASS_SYS_DTYPE_E5M2:
+      return f(Type<cutlass::float_e5m2_t>{});
+    default:
+      return cutlass::Status::kErrorInvalidDataType;
+  }
+}
+
+// dst[i] = convert(scale * src[i]), through f32 with NumericConverter's
+// round-to-nearest. Conversions to int8 and fp8 saturate.
+template <typename Src, typename Dst>
+__global__ void convert_kernel(const Src* __restrict__ src,
+                               Dst* __restrict__ dst,
+                               int64_t count,
+                               float scale) {
+  cutlass::NumericConverter<float, Src> to_float;
+  cutlass::NumericConverter<Dst, float> from_float;
+  for (int64_t idx = int64_t(blockIdx.x) * blockDim.x + threadIdx.x; idx < count;
+       idx += int64_t(gridDim.x) * blockDim.x) {
+    dst[idx] = from_float(scale * to_float(src[idx]));
+  }
+}
+
+cutlass::Status convert(const cutlass_sys_convert_config_t& cfg,
+                        const cutlass_sys_transform_operands_t* ops,
+                        cudaStream_t stream,
+                        size_t* workspace_size) {
+  if (cfg.count < 0) {
+    return cutlass::Status::kErrorInvalidProblem;
+  }
+  return dispatch_convertible(cfg.src, [&](auto src_type) {
+    using Src = typename decltype(src_type)::type;
+    return dispatch_convertible(cfg.dst, [&](auto dst_type) {
+      using Dst = typename decltype(dst_type)::type;
+      if (workspace_size != nullptr) {
+        *workspace_size = 0;
+        return cutlass::Status::kSuccess;
+      }
+      if (cfg.count == 0) {
+        return cutlass::Status::kSuccess;
+      }
+
+      int threads = 256;
+      int64_t blocks = std::min<int64_t>((cfg.count + threads - 1) / threads, 65535 * 8);
+      convert_kernel<Src, Dst><<<static_cast<unsigned>(blocks), threads, 0, stream>>>(
+          static_cast<const Src*>(ops->src), static_cast<Dst*>(ops->dst), cfg.count,
+          cfg.scale);
+      return cudaGetLastError() == cudaSuccess ? cutlass::Status::kSuccess
+                                               : cutlass::Status::kErrorInternal;
+    });
+  });
+}
+
+}  // namespace
+}  // namespace cutlass_sys
+
+extern "C" size_t cutlass_sys_layout_transform_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_layout_transform_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::layout_transform(*config, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_layout_transform_can_implement(
+    const cutlass_sys_context_t* context,
+    const cutlass_sys_layout_transform_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::layout_transform(*config, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_layout_transform(const cutlass_sys_context_t* context,
+                                            const cutlass_sys_layout_transform_config_t* config,
+                                            const cutlass_sys_transform_operands_t* operands,
+                                            void* workspace,
+                                            cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  (void)workspace;
+  return static_cast<int>(cutlass_sys::layout_transform(
+      *config, operands, static_cast<cudaStream_t>(stream), nullptr));
+}
+
+extern "C" size_t cutlass_sys_permute_workspace_size(
+    const cutlass_sys_context_t* context, const cutlass_sys_permute_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::permute(*config, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_permute_can_implement(const cutlass_sys_context_t* context,
+                                                 const cutlass_sys_permute_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::permute(*config, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_permute(const cutlass_sys_context_t* context,
+                                   const cutlass_sys_permute_config_t* config,
+                                   const cutlass_sys_transform_operands_t* operands,
+                                   void* workspace,
+                                   cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  (void)workspace;
+  return static_cast<int>(
+      cutlass_sys::permute(*config, operands, static_cast<cudaStream_t>(stream), nullptr));
+}
+
+extern "C" size_t cutlass_sys_convert_workspace_size(const cutlass_sys_context_t* context,
+                                                     const cutlass_sys_convert_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return 0;
+  }
+  size_t size = 0;
+  cutlass_sys::convert(*config, nullptr, nullptr, &size);
+  return size;
+}
+
+extern "C" int cutlass_sys_convert_can_implement(const cutlass_sys_context_t* context,
+                                                 const cutlass_sys_convert_config_t* config) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  size_t size = 0;
+  return static_cast<int>(cutlass_sys::convert(*config, nullptr, nullptr, &size));
+}
+
+extern "C" int cutlass_sys_convert(const cutlass_sys_context_t* context,
+                                   const cutlass_sys_convert_config_t* config,
+                                   const cutlass_sys_transform_operands_t* operands,
+                                   void* workspace,
+                                   cutlass_sys_stream_t stream) {
+  cutlass_sys::DeviceScope device(context, cutlass_sys::kSm75);
+  if (!device) {
+    return static_cast<int>(device.status());
+  }
+  (void)workspace;
+  return static_cast<int>(
+      cutlass_sys::convert(*config, operands, static_cast<cudaStream_t>(stream), nullptr));
+}
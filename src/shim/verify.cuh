@@ -0,0 +1,111 @@
+// Self-checking launches: the `verify` flag of the GEMM and convolution
+// configs. A verified launch first writes the device reference
+// (cutlass::reference::device) of its output into scratch space past the
+// kernel's own workspace, then launches the kernel and compares D with the
+// reference, failing with kErrorInternal on any mismatch. It synchronizes the
+// stream to read the result back, so it is meant for debugging only.
+//
+// The reference kernels come from tools/util, so they are compiled only with
+// the `reference` feature; without it a verified config is kErrorNotSupported.
+
+#pragma once
+
+#include <cutlass/conv/convolution.h>
+
+#include "common.cuh"
+
+namespace cutlass_sys {
+
+// D of a verified launch, compared as `rows` x `columns` elements of the
+// output type of `element` with leading dimension `ld`.
+struct VerifyOutput {
+  cutlass_sys_dtype_t element;
+  void* d;
+  int64_t rows;
+  int64_t columns;
+  int64_t ld;
+};
+
+#if defined(CUTLASS_SYS_REFERENCE)
+
+// Scratch bytes verifying `out` takes: a packed reference of D plus a
+// mismatch counter.
+size_t verify_scratch_size(const VerifyOutput& out);
+
+// Compares `out` with the packed `reference` written before the launch, using
+// `scratch` (verify_scratch_size bytes starting at `reference`) for the
+// counter, and waits for the result.
+cutlass::Status compare_output(const VerifyOutput& out, void* scratch, cudaStream_t stream);
+
+// Writes the device reference of `cfg` into `reference`, with leading
+// dimension `ld_reference`, from the A, B and C of `ops`. The GEMM reference
+// runs on the legacy default stream, so this synchronizes with `stream` on
+// both sides.
+cutlass::Status reference_gemm_device(const cutlass_sys_gemm_config_t& cfg,
+                                      const cutlass_sys_gemm_operands_t& ops,
+                                      void* reference,
+                                      int64_t ld_reference,
+                                      cudaStream_t stream);
+
+// Same for a convolution of direction `kind` and rank 2 or 3 into the packed
+// `reference`, on `stream`.
+cutlass::Status reference_conv_device(cutlass::conv::Operator kind,
+                                      int rank,
+                                      const cutlass_sys_conv_config_t& cfg,
+                                      const cutlass_sys_conv_operands_t& ops,
+                                      void* reference,
+                                      cudaStream_t stream);
+
+#endif
+
+// Runs a launch, `run(workspace, workspace_size)` behaving like query_or_run,
+// verifying its output `out` against `reference(scratch)` when `verify` is
+// set. Queries add the scratch to the reported workspace size.
+template <typename Run, typename Reference>
+cutlass::Status run_verified(bool verify,
+                             const VerifyOutput& out,
+                             void* workspace,
+                             cudaStream_t stream,
+                             size_t* workspace_size,
+                             Run&& run,
+                             Reference&& reference) {
+  if (!verify) {
+    return run(workspace, workspace_size);
+  }
+#if defined(CUTLASS_SYS_REFERENCE)
+  size_t size = 0;
+  cutlass::Status status = run(nullptr, &size);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+  size_t offset = (size + 255) & ~size_t(255);
+  if (workspace_size != nullptr) {
+    *workspace_size = offset + verify_scratch_size(out);
+    return status;
+  }
+  if (workspace == nullptr) {
+    return cutlass::Status::kErrorWorkspaceNull;
+  }
+  // The reference runs first, since C may alias D.
+  void* scratch = static_cast<char*>(workspace) + offset;
+  status = reference(scratch);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+  status = run(workspace, nullptr);
+  if (status != cutlass::Status::kSuccess) {
+    return status;
+  }
+  return compare_output(out, scratch, stream);
+#else
+  (void)out;
+  (void)workspace;
+  (void)stream;
+  (void)workspace_size;
+  (void)run;
+  (void)reference;
+  return cutlass::Status::kErrorNotSupported;
+#endif
+}
+
+}  // namespace cutlass_sys
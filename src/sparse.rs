@@ -0,0 +1,187 @@
+//! 2:4 structured-sparse GEMM on Sparse Tensor Cores:
+//!
+//! ```text
+//! D = alpha * A * B + beta * C
+//! ```
+//!
+//! where at most two of every four consecutive elements along each row of
+//! `A` are non-zero. `A` is stored compressed: an `M x K/2` row-major matrix
+//! of the kept values plus a metadata tensor of their positions, both
+//! produced on the device by [`cutlass_sys_sparse_compress`]. `B` is
+//! column-major (`N x K` stored row-major) and `C` / `D` are row-major.
+//!
+//! Only `F16`, `BF16` and `S8` are supported; `F32` returns
+//! [`Status::ErrorInvalidDataType`]. `K` must be a multiple of 64 for the
+//! 16-bit types and 128 for `S8`, otherwise [`Status::ErrorInvalidProblem`]
+//! is returned.
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::coord::GemmCoord;
+use crate::error::CutlassError;
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Problem description of a sparse GEMM.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SparseGemmConfig {
+    pub problem: GemmCoord,
+    pub element: DataType,
+    /// Leading dimension of the compressed `A`, at least `k / 2`.
+    pub lda: i64,
+    pub ldb: i64,
+    pub ldc: i64,
+    pub ldd: i64,
+    pub alpha: f32,
+    pub beta: f32,
+    /// Number of K partitions, reduced serially through the workspace.
+    pub split_k_slices: i32,
+}
+
+impl SparseGemmConfig {
+    /// A tightly packed `m x n x k` problem with `alpha = 1`, `beta = 0`.
+    pub const fn new(m: i32, n: i32, k: i32, element: DataType) -> Self {
+        Self {
+            problem: GemmCoord::new(m, n, k),
+            element,
+            lda: (k / 2) as i64,
+            ldb: k as i64,
+            ldc: n as i64,
+            ldd: n as i64,
+            alpha: 1.0,
+            beta: 0.0,
+            split_k_slices: 1,
+        }
+    }
+
+    /// Bytes of metadata of the compressed `A`; see
+    /// [`cutlass_sys_sparse_metadata_size`].
+    pub fn metadata_size(&self) -> usize {
+        // SAFETY: only reads `self`.
+        unsafe { cutlass_sys_sparse_metadata_size(self) }
+    }
+
+    /// Compresses the dense row-major `m x k` matrix at `dense` into
+    /// `compressed` and `metadata` on `stream`, through
+    /// [`cutlass_sys_sparse_compress`].
+    ///
+    /// # Safety
+    ///
+    /// `dense` must address `m` rows of `ld_dense` elements, `compressed`
+    /// the `m x k/2` matrix with leading dimension `lda` and `metadata`
+    /// [`metadata_size`](Self::metadata_size) bytes, all device memory of
+    /// `context`'s device that stays valid until the compression completes
+    /// on `stream`, a stream of that device.
+    pub unsafe fn compress(
+        &self,
+        context: &CutlassContext,
+        dense: *const c_void,
+        ld_dense: i64,
+        compressed: *mut c_void,
+        metadata: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the buffers and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_sparse_compress(
+                context, self, dense, ld_dense, compressed, metadata, stream,
+            )
+        })
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_sparse_gemm`] needs for this
+    /// config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_sparse_gemm_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_sparse_gemm`] can run this config before any
+    /// operand is allocated, returning the status the launch would fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_sparse_gemm_can_implement(context, self) }.result()
+    }
+
+    /// Launches the GEMM on `stream` through [`cutlass_sys_sparse_gemm`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory, or
+    /// may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &SparseGemmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_sparse_gemm(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers of a sparse GEMM launch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct SparseGemmOperands {
+    /// Compressed `A`, `m x k/2`.
+    pub a: *const c_void,
+    /// Metadata of `a`, [`cutlass_sys_sparse_metadata_size`] bytes.
+    pub e: *const c_void,
+    pub b: *const c_void,
+    /// May be null when `beta == 0`.
+    pub c: *const c_void,
+    pub d: *mut c_void,
+}
+
+extern "C" {
+    /// Bytes of metadata describing the compressed `A` of `config`, or 0 for
+    /// an unsupported element type or `K`.
+    pub fn cutlass_sys_sparse_metadata_size(config: *const SparseGemmConfig) -> usize;
+
+    /// Compress the dense row-major `m x k` matrix `dense` into `compressed`
+    /// (with leading dimension `config.lda`) and `metadata` on `stream`.
+    ///
+    /// Each group of four is reduced to its two largest-magnitude values, so
+    /// a matrix already pruned to 2:4 round-trips exactly and any other
+    /// matrix is pruned on the way.
+    pub fn cutlass_sys_sparse_compress(
+        context: *const CutlassContext,
+        config: *const SparseGemmConfig,
+        dense: *const c_void,
+        ld_dense: i64,
+        compressed: *mut c_void,
+        metadata: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_sparse_gemm`] needs.
+    pub fn cutlass_sys_sparse_gemm_workspace_size(
+        context: *const CutlassContext,
+        config: *const SparseGemmConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_sparse_gemm`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_sparse_gemm_can_implement(
+        context: *const CutlassContext,
+        config: *const SparseGemmConfig,
+    ) -> Status;
+
+    /// Launch a sparse GEMM on `stream`.
+    pub fn cutlass_sys_sparse_gemm(
+        context: *const CutlassContext,
+        config: *const SparseGemmConfig,
+        operands: *const SparseGemmOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
@@ -0,0 +1,226 @@
+//! Device tensor references in the style of `cutlass::TensorRef` and
+//! `cutlass::TensorView`.
+//!
+//! A [`TensorRef`] is a device pointer plus the layout and leading stride
+//! needed to address it; a [`TensorView`] adds the extent. Launch helpers such
+//! as [`GemmConfig::bind_views`](crate::gemm::GemmConfig::bind_views) take
+//! views and derive the problem shape and leading dimensions from them, so the
+//! pointer / leading-dimension / extent triple can't disagree.
+//!
+//! The pointers are never dereferenced on the host. Views check their own
+//! consistency in [`validate`](TensorView::validate); null and misaligned
+//! pointers are additionally rejected in debug builds by
+//! [`check_pointer`](TensorView::check_pointer).
+
+use crate::coord::{MatrixCoord, Tensor4DCoord};
+use crate::library::LayoutTypeID;
+use crate::types::Status;
+
+/// A device pointer with the layout and leading stride used to address it.
+///
+/// `stride` is the distance in elements between consecutive rows (row-major),
+/// columns (column-major) or pixels (NHWC).
+#[derive(Debug)]
+pub struct TensorRef<T> {
+    pub ptr: *mut T,
+    pub layout: LayoutTypeID,
+    pub stride: i64,
+}
+
+impl<T> TensorRef<T> {
+    pub const fn new(ptr: *mut T, layout: LayoutTypeID, stride: i64) -> Self {
+        Self {
+            ptr,
+            layout,
+            stride,
+        }
+    }
+
+    pub const fn row_major(ptr: *mut T, ld: i64) -> Self {
+        Self::new(ptr, LayoutTypeID::RowMajor, ld)
+    }
+
+    pub const fn column_major(ptr: *mut T, ld: i64) -> Self {
+        Self::new(ptr, LayoutTypeID::ColumnMajor, ld)
+    }
+
+    /// Offset in elements of `coord` from the start of the matrix.
+    pub const fn offset(&self, coord: MatrixCoord) -> i64 {
+        match self.layout {
+            LayoutTypeID::ColumnMajor => coord.column as i64 * self.stride + coord.row as i64,
+            _ => coord.row as i64 * self.stride + coord.column as i64,
+        }
+    }
+
+    /// The same tensor with its element type reinterpreted.
+    pub const fn cast<U>(self) -> TensorRef<U> {
+        TensorRef::new(self.ptr.cast(), self.layout, self.stride)
+    }
+}
+
+// Manual impls: the derives would needlessly require `T: Clone`.
+impl<T> Clone for TensorRef<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TensorRef<T> {}
+
+/// A [`TensorRef`] together with its extent, either a [`MatrixCoord`] for
+/// matrices or a [`Tensor4DCoord`] for NHWC activations and filters.
+#[derive(Debug)]
+pub struct TensorView<T, E = MatrixCoord> {
+    pub tensor_ref: TensorRef<T>,
+    pub extent: E,
+}
+
+impl<T, E: Copy> Clone for TensorView<T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E: Copy> Copy for TensorView<T, E> {}
+
+impl<T, E> TensorView<T, E> {
+    pub const fn new(tensor_ref: TensorRef<T>, extent: E) -> Self {
+        Self { tensor_ref, extent }
+    }
+
+    pub const fn ptr(&self) -> *mut T {
+        self.tensor_ref.ptr
+    }
+
+    pub const fn layout(&self) -> LayoutTypeID {
+        self.tensor_ref.layout
+    }
+
+    pub const fn stride(&self) -> i64 {
+        self.tensor_ref.stride
+    }
+}
+
+impl<T> TensorView<T, MatrixCoord> {
+    /// A tightly packed row-major matrix.
+    pub const fn row_major(ptr: *mut T, extent: MatrixCoord) -> Self {
+        Self::new(TensorRef::row_major(ptr, extent.column as i64), extent)
+    }
+
+    /// A tightly packed column-major matrix.
+    pub const fn column_major(ptr: *mut T, extent: MatrixCoord) -> Self {
+        Self::new(TensorRef::column_major(ptr, extent.row as i64), extent)
+    }
+
+    /// Checks that the view describes an addressable matrix:
+    /// [`Status::ErrorInvalidLayout`] for anything but plain row- or
+    /// column-major, [`Status::ErrorInvalidProblem`] for a negative extent or
+    /// a stride shorter than a row (column).
+    pub const fn validate(&self) -> Result<(), Status> {
+        let contiguous = match self.layout() {
+            LayoutTypeID::RowMajor => self.extent.column,
+            LayoutTypeID::ColumnMajor => self.extent.row,
+            _ => return Err(Status::ErrorInvalidLayout),
+        };
+        if self.extent.row < 0 || self.extent.column < 0 {
+            return Err(Status::ErrorInvalidProblem);
+        }
+        if self.stride() < contiguous as i64 || self.stride() < 1 {
+            return Err(Status::ErrorInvalidProblem);
+        }
+        Ok(())
+    }
+
+    /// The `extent` sub-matrix starting at `offset`, or
+    /// [`Status::ErrorInvalidProblem`] if it does not fit inside this view.
+    pub const fn subview(&self, offset: MatrixCoord, extent: MatrixCoord) -> Result<Self, Status> {
+        if offset.row < 0
+            || offset.column < 0
+            || extent.row < 0
+            || extent.column < 0
+            || offset.row as i64 + extent.row as i64 > self.extent.row as i64
+            || offset.column as i64 + extent.column as i64 > self.extent.column as i64
+        {
+            return Err(Status::ErrorInvalidProblem);
+        }
+        let ptr = self
+            .ptr()
+            .wrapping_offset(self.tensor_ref.offset(offset) as isize);
+        Ok(Self::new(
+            TensorRef::new(ptr, self.layout(), self.stride()),
+            extent,
+        ))
+    }
+
+    /// Number of elements covered by the view, gaps between rows (columns)
+    /// included.
+    pub const fn span(&self) -> i64 {
+        let (outer, inner) = match self.layout() {
+            LayoutTypeID::ColumnMajor => (self.extent.column, self.extent.row),
+            _ => (self.extent.row, self.extent.column),
+        };
+        if outer == 0 || inner == 0 {
+            0
+        } else {
+            (outer as i64 - 1) * self.stride() + inner as i64
+        }
+    }
+
+    /// In debug builds, rejects a null pointer to a non-empty matrix with
+    /// [`Status::ErrorInvalidProblem`] and a pointer that is not a multiple of
+    /// `alignment` bytes with [`Status::ErrorMisalignedOperand`].
+    /// Always `Ok(())` in release builds.
+    pub fn check_pointer(&self, alignment: usize) -> Result<(), Status> {
+        check_pointer(self.ptr(), self.extent.product(), alignment)
+    }
+}
+
+impl<T> TensorView<T, Tensor4DCoord> {
+    /// A tightly packed NHWC tensor.
+    pub const fn nhwc(ptr: *mut T, extent: Tensor4DCoord) -> Self {
+        Self::new(
+            TensorRef::new(ptr, LayoutTypeID::TensorNHWC, extent.c as i64),
+            extent,
+        )
+    }
+
+    /// Checks that the view describes a packed NHWC tensor, the only kind the
+    /// shim's convolutions accept: [`Status::ErrorInvalidLayout`] for any
+    /// other layout, [`Status::ErrorInvalidProblem`] for a negative extent and
+    /// [`Status::ErrorNotSupported`] for a pixel stride other than `C`.
+    pub const fn validate(&self) -> Result<(), Status> {
+        if !matches!(self.layout(), LayoutTypeID::TensorNHWC) {
+            return Err(Status::ErrorInvalidLayout);
+        }
+        let e = self.extent;
+        if e.n < 0 || e.h < 0 || e.w < 0 || e.c < 0 {
+            return Err(Status::ErrorInvalidProblem);
+        }
+        if self.stride() != e.c as i64 {
+            return Err(Status::ErrorNotSupported);
+        }
+        Ok(())
+    }
+
+    /// The debug-build null and alignment checks of the matrix
+    /// `check_pointer`, for a tensor.
+    pub fn check_pointer(&self, alignment: usize) -> Result<(), Status> {
+        check_pointer(self.ptr(), self.extent.product(), alignment)
+    }
+}
+
+#[cfg(debug_assertions)]
+fn check_pointer<T>(ptr: *mut T, len: i64, alignment: usize) -> Result<(), Status> {
+    if len > 0 && ptr.is_null() {
+        return Err(Status::ErrorInvalidProblem);
+    }
+    if !(ptr as usize).is_multiple_of(alignment.max(1)) {
+        return Err(Status::ErrorMisalignedOperand);
+    }
+    Ok(())
+}
+
+#[cfg(not(debug_assertions))]
+fn check_pointer<T>(_: *mut T, _: i64, _: usize) -> Result<(), Status> {
+    Ok(())
+}
@@ -0,0 +1,313 @@
+//! Tensor layout transforms and axis permutations.
+//!
+//! - [`cutlass_sys_layout_transform`] converts a packed 4-D activation tensor
+//!   between NCHW and NHWC, as needed to feed NCHW data to the [`conv`]
+//!   kernels and to read their NHWC outputs back.
+//! - [`cutlass_sys_permute`] permutes the axes of any packed tensor of up to
+//!   four dimensions; lower ranks pad the leading extents with 1. A 2-D
+//!   transpose is [`PermuteConfig::transpose`].
+//! - [`cutlass_sys_convert`] converts a packed tensor between `F32`, `F16`,
+//!   `BF16`, `E4M3`, `E5M2` and `S8`, optionally scaling it, with CUTLASS's
+//!   `NumericConverter`, so mixed-precision pipelines need no separate
+//!   elementwise library. [`F8E4M3`](crate::types::F8E4M3) and
+//!   [`F8E5M2`](crate::types::F8E5M2) convert the same way on the host.
+//!
+//! Permutations that amount to a (batched) transpose, including both layout
+//! conversions, run through a tiled shared-memory transpose; anything else
+//! takes a slower gather kernel. Every [`DataType`] is accepted, since
+//! elements are only moved. `src` and `dst` must not overlap, and no
+//! workspace is needed by any of the three.
+//!
+//! [`conv`]: crate::conv
+
+use core::ffi::c_void;
+
+use crate::context::CutlassContext;
+use crate::error::CutlassError;
+use crate::types::{cudaStream_t, DataType, Status};
+
+/// Storage order of a 4-D activation tensor.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TensorLayout {
+    #[default]
+    Nhwc = 0,
+    Nchw = 1,
+}
+
+/// Problem description of a layout transform. The extents are the logical
+/// sizes, independent of either layout.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LayoutTransformConfig {
+    pub n: i32,
+    pub h: i32,
+    pub w: i32,
+    pub c: i32,
+    pub src: TensorLayout,
+    pub dst: TensorLayout,
+    pub element: DataType,
+}
+
+impl LayoutTransformConfig {
+    pub const fn new(
+        n: i32,
+        h: i32,
+        w: i32,
+        c: i32,
+        src: TensorLayout,
+        dst: TensorLayout,
+        element: DataType,
+    ) -> Self {
+        Self {
+            n,
+            h,
+            w,
+            c,
+            src,
+            dst,
+            element,
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_layout_transform`] needs for this
+    /// config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_layout_transform_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_layout_transform`] can run this config before
+    /// any operand is allocated, returning the status the launch would fail
+    /// with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_layout_transform_can_implement(context, self) }.result()
+    }
+
+    /// Launches the transform on `stream` through [`cutlass_sys_layout_transform`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory, or
+    /// may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &TransformOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_layout_transform(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Problem description of an axis permutation: output axis `i` is input axis
+/// `perm[i]`, so the output extent is `extent[perm[i]]`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PermuteConfig {
+    /// Input extent, outermost axis first.
+    pub extent: [i32; 4],
+    pub perm: [i32; 4],
+    pub element: DataType,
+}
+
+impl PermuteConfig {
+    pub const fn new(extent: [i32; 4], perm: [i32; 4], element: DataType) -> Self {
+        Self {
+            extent,
+            perm,
+            element,
+        }
+    }
+
+    /// Transpose of a packed row-major `rows x cols` matrix (equivalently, a
+    /// column-major `cols x rows` one).
+    pub const fn transpose(rows: i32, cols: i32, element: DataType) -> Self {
+        Self::new([1, 1, rows, cols], [0, 1, 3, 2], element)
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_permute`] needs for this config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_permute_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_permute`] can run this config before any
+    /// operand is allocated, returning the status the launch would fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_permute_can_implement(context, self) }.result()
+    }
+
+    /// Launches the permutation on `stream` through [`cutlass_sys_permute`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device covering the problem and stay valid until the launch completes
+    /// on `stream`, a stream of that device. `workspace` must hold
+    /// [`workspace_size`](Self::workspace_size) bytes of device memory, or
+    /// may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &TransformOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_permute(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Problem description of a conversion: `dst[i] = convert(scale * src[i])`
+/// for each of `count` packed elements. The value passes through `f32` and
+/// rounds to nearest; conversions to `S8` and the fp8 types saturate.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConvertConfig {
+    pub count: i64,
+    pub src: DataType,
+    pub dst: DataType,
+    pub scale: f32,
+}
+
+impl ConvertConfig {
+    /// An unscaled conversion of `count` elements.
+    pub const fn new(count: i64, src: DataType, dst: DataType) -> Self {
+        Self {
+            count,
+            src,
+            dst,
+            scale: 1.0,
+        }
+    }
+
+    /// Bytes of device workspace [`cutlass_sys_convert`] needs for this config.
+    pub fn workspace_size(&self, context: &CutlassContext) -> usize {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_convert_workspace_size(context, self) }
+    }
+
+    /// Checks that [`cutlass_sys_convert`] can run this config before any
+    /// operand is allocated, returning the status the launch would fail with.
+    pub fn supports(&self, context: &CutlassContext) -> Result<(), Status> {
+        // SAFETY: only reads `context` and `self`.
+        unsafe { cutlass_sys_convert_can_implement(context, self) }.result()
+    }
+
+    /// Launches the conversion on `stream` through [`cutlass_sys_convert`].
+    ///
+    /// # Safety
+    ///
+    /// The pointers in `operands` must address device memory of `context`'s
+    /// device holding `count` elements of their type and stay valid until
+    /// the launch completes on `stream`, a stream of that device.
+    /// `workspace` must hold [`workspace_size`](Self::workspace_size) bytes
+    /// of device memory, or may be null when that is zero.
+    pub unsafe fn launch(
+        &self,
+        context: &CutlassContext,
+        operands: &TransformOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Result<(), CutlassError> {
+        // SAFETY: the caller vouches for the operands, workspace and stream.
+        CutlassError::check(unsafe {
+            cutlass_sys_convert(context, self, operands, workspace, stream)
+        })
+    }
+}
+
+/// Device pointers of a layout transform, permutation or conversion.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct TransformOperands {
+    pub src: *const c_void,
+    pub dst: *mut c_void,
+}
+
+extern "C" {
+    /// Bytes of device workspace [`cutlass_sys_layout_transform`] needs;
+    /// always 0.
+    pub fn cutlass_sys_layout_transform_workspace_size(
+        context: *const CutlassContext,
+        config: *const LayoutTransformConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_layout_transform`] can run `config`, checked
+    /// without touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_layout_transform_can_implement(
+        context: *const CutlassContext,
+        config: *const LayoutTransformConfig,
+    ) -> Status;
+
+    /// Launch a layout transform on `stream`. Equal layouts make a copy.
+    pub fn cutlass_sys_layout_transform(
+        context: *const CutlassContext,
+        config: *const LayoutTransformConfig,
+        operands: *const TransformOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_permute`] needs; always 0.
+    pub fn cutlass_sys_permute_workspace_size(
+        context: *const CutlassContext,
+        config: *const PermuteConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_permute`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_permute_can_implement(
+        context: *const CutlassContext,
+        config: *const PermuteConfig,
+    ) -> Status;
+
+    /// Launch an axis permutation on `stream`. Returns
+    /// [`Status::ErrorInvalidProblem`] if `perm` is not a permutation of
+    /// `0..4`.
+    pub fn cutlass_sys_permute(
+        context: *const CutlassContext,
+        config: *const PermuteConfig,
+        operands: *const TransformOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+
+    /// Bytes of device workspace [`cutlass_sys_convert`] needs; always 0.
+    pub fn cutlass_sys_convert_workspace_size(
+        context: *const CutlassContext,
+        config: *const ConvertConfig,
+    ) -> usize;
+
+    /// Whether [`cutlass_sys_convert`] can run `config`, checked without
+    /// touching any operand: the status the launch would fail with, or
+    /// [`Status::Success`].
+    pub fn cutlass_sys_convert_can_implement(
+        context: *const CutlassContext,
+        config: *const ConvertConfig,
+    ) -> Status;
+
+    /// Launch a conversion on `stream`. Returns
+    /// [`Status::ErrorInvalidDataType`] for a type it does not convert.
+    pub fn cutlass_sys_convert(
+        context: *const CutlassContext,
+        config: *const ConvertConfig,
+        operands: *const TransformOperands,
+        workspace: *mut c_void,
+        stream: cudaStream_t,
+    ) -> Status;
+}
@@ -0,0 +1,619 @@
+//! Plain-old-data types shared by every shim entry point.
+//!
+//! Everything here is `#[repr(C)]` and mirrors a declaration in
+//! `src/shim/cutlass_sys.h`; keep the two in sync.
+
+use core::fmt;
+
+/// The opaque stream object behind [`cudaStream_t`], named as in the CUDA
+/// headers so handles from other CUDA bindings convert with a pointer cast.
+#[repr(C)]
+pub struct CUstream_st {
+    _private: [u8; 0],
+}
+
+/// CUDA stream handle (`cudaStream_t`, which is the driver API's `CUstream`
+/// as well). Every launch takes one explicitly; pass the stream your own work
+/// is ordered on.
+///
+/// A null pointer selects the legacy default stream, which synchronizes with
+/// all other blocking streams of the context; prefer a created stream or
+/// [`cudaStreamPerThread`].
+pub type cudaStream_t = *mut CUstream_st;
+
+/// The legacy default stream, `cudaStreamLegacy`.
+pub const cudaStreamLegacy: cudaStream_t = 0x1 as cudaStream_t;
+
+/// The calling host thread's default stream, `cudaStreamPerThread`. Unlike
+/// the legacy default stream it does not synchronize with other streams.
+pub const cudaStreamPerThread: cudaStream_t = 0x2 as cudaStream_t;
+
+/// Raw `cutlass::Status` value, as declared by the C header. `0` means
+/// success; see [`Status`] for the typed form the bindings return.
+pub type cutlass_status_t = i32;
+
+/// `cutlass::Status::kSuccess`
+pub const CUTLASS_STATUS_SUCCESS: cutlass_status_t = 0;
+
+/// Outcome of a shim call, mirroring `cutlass::Status` value for value.
+///
+/// The shim only ever returns `cutlass::Status` values, so every value it can
+/// produce is a variant here; raw values from elsewhere go through
+/// [`Status::from_raw`].
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[must_use]
+pub enum Status {
+    Success = 0,
+    /// An operand pointer or leading dimension violates the kernel's
+    /// alignment requirement.
+    ErrorMisalignedOperand = 1,
+    ErrorInvalidDataType = 2,
+    ErrorInvalidLayout = 3,
+    ErrorInvalidProblem = 4,
+    ErrorNotSupported = 5,
+    ErrorWorkspaceNull = 6,
+    ErrorInternal = 7,
+    ErrorArchMismatch = 8,
+    ErrorInsufficientDriver = 9,
+    ErrorMemoryAllocation = 10,
+    Invalid = 11,
+}
+
+impl Status {
+    /// Converts a raw `cutlass::Status` value; unknown values map to
+    /// [`Status::Invalid`].
+    pub const fn from_raw(raw: cutlass_status_t) -> Self {
+        match raw {
+            0 => Self::Success,
+            1 => Self::ErrorMisalignedOperand,
+            2 => Self::ErrorInvalidDataType,
+            3 => Self::ErrorInvalidLayout,
+            4 => Self::ErrorInvalidProblem,
+            5 => Self::ErrorNotSupported,
+            6 => Self::ErrorWorkspaceNull,
+            7 => Self::ErrorInternal,
+            8 => Self::ErrorArchMismatch,
+            9 => Self::ErrorInsufficientDriver,
+            10 => Self::ErrorMemoryAllocation,
+            _ => Self::Invalid,
+        }
+    }
+
+    pub const fn is_success(self) -> bool {
+        matches!(self, Self::Success)
+    }
+
+    /// `Ok(())` for [`Status::Success`], the status itself otherwise.
+    pub const fn result(self) -> Result<(), Status> {
+        match self {
+            Self::Success => Ok(()),
+            err => Err(err),
+        }
+    }
+
+    /// The message `cutlassGetStatusString` returns for this status.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "Success",
+            Self::ErrorMisalignedOperand => "Error Misaligned Operand",
+            Self::ErrorInvalidDataType => "Error Invalid Data Type",
+            Self::ErrorInvalidLayout => "Error Invalid Layout",
+            Self::ErrorInvalidProblem => "Error Invalid Problem",
+            Self::ErrorNotSupported => "Error Not Supported",
+            Self::ErrorWorkspaceNull => "Error Workspace Null",
+            Self::ErrorInternal => "Error Internal",
+            Self::ErrorArchMismatch => "Error Architecture Mismatch",
+            Self::ErrorInsufficientDriver => "Error Insufficient Driver",
+            Self::ErrorMemoryAllocation => "Error Memory Allocation",
+            Self::Invalid => "Invalid status",
+        }
+    }
+}
+
+impl From<cutlass_status_t> for Status {
+    fn from(raw: cutlass_status_t) -> Self {
+        Self::from_raw(raw)
+    }
+}
+
+impl From<Status> for cutlass_status_t {
+    fn from(status: Status) -> Self {
+        status as cutlass_status_t
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Status {}
+
+/// Element type of the A/B operands of an operation.
+///
+/// The accumulator and output types default to the ones implied by the
+/// operand type, which [`GemmConfig`](crate::gemm::GemmConfig) can override
+/// with another [`GemmTypes`](crate::gemm::GemmTypes) combination:
+///
+/// | operands | accumulator | C / D   |
+/// |----------|-------------|---------|
+/// | `F16`    | `f32`       | `f16`   |
+/// | `BF16`   | `f32`       | `bf16`  |
+/// | `F32`    | `f32`       | `f32`   |
+/// | `S8`     | `i32`       | `i32`   |
+/// | `F64`    | `f64`       | `f64`   |
+/// | `C32`    | `c32`       | `c32`   |
+/// | `C64`    | `c64`       | `c64`   |
+///
+/// The double-precision and complex types are only accepted by the BLAS3
+/// routines, `S32` only as an accumulator or output type, and the fp8 types
+/// only as the down-converted output of a GEMM.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DataType {
+    F16 = 0,
+    BF16 = 1,
+    F32 = 2,
+    S8 = 3,
+    F64 = 4,
+    /// Interleaved complex of two `f32`, laid out as [`Complex<f32>`].
+    C32 = 5,
+    /// Interleaved complex of two `f64`, laid out as [`Complex<f64>`].
+    C64 = 6,
+    S32 = 7,
+    /// 8-bit float with 4 exponent and 3 mantissa bits, held as [`F8E4M3`].
+    E4M3 = 8,
+    /// 8-bit float with 5 exponent and 2 mantissa bits, held as [`F8E5M2`].
+    E5M2 = 9,
+}
+
+/// A complex number, laid out like `cutlass::complex<T>` and C99 `_Complex`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    pub const fn new(re: T, im: T) -> Self {
+        Self { re, im }
+    }
+}
+
+/// Bit layout of an fp8 format, and how CUTLASS converts to it.
+struct Fp8Format {
+    mantissa_bits: u32,
+    exponent_bias: i32,
+    /// Largest finite value, which overflows and infinities saturate to.
+    max_bits: u8,
+    /// Whether the all-ones exponent holds infinities and NaNs (IEEE-style)
+    /// rather than only the all-ones NaN.
+    ieee: bool,
+}
+
+impl Fp8Format {
+    /// Positive NaN, which every NaN converts to.
+    const NAN: u8 = 0x7f;
+
+    /// Rounds `value` to nearest, ties to even, saturating to the largest
+    /// finite value, as `cutlass::float_e4m3_t(float)` and
+    /// `cutlass::float_e5m2_t(float)` do.
+    fn encode(&self, value: f32) -> u8 {
+        if value.is_nan() {
+            return Self::NAN;
+        }
+        let sign = if value.is_sign_negative() { 0x80 } else { 0 };
+        let magnitude = value.abs();
+        if magnitude > self.decode(self.max_bits) {
+            return sign | self.max_bits;
+        }
+
+        let bits = magnitude.to_bits();
+        let exponent = (bits >> 23) as i32 - 127;
+        if exponent == -127 {
+            // f32 subnormals are far below the smallest fp8 subnormal
+            return sign;
+        }
+        let full = (1 << 23) | (bits & 0x7f_ffff);
+        let min_exponent = 1 - self.exponent_bias;
+        let shift = 23 - self.mantissa_bits + (min_exponent - exponent).max(0) as u32;
+        if shift > 24 {
+            return sign;
+        }
+        let mut q = full >> shift;
+        let rem = full & ((1 << shift) - 1);
+        let half = 1 << (shift - 1);
+        if rem > half || (rem == half && q & 1 == 1) {
+            q += 1;
+        }
+        // A carry out of the mantissa moves on to the next exponent
+        let encoded = if exponent >= min_exponent {
+            (((exponent + self.exponent_bias) as u32) << self.mantissa_bits) + q
+                - (1 << self.mantissa_bits)
+        } else {
+            q
+        };
+        sign | encoded as u8
+    }
+
+    fn decode(&self, bits: u8) -> f32 {
+        let exponent_mask = (0x7f >> self.mantissa_bits) as u32;
+        let exponent = (bits as u32 >> self.mantissa_bits) & exponent_mask;
+        let mantissa = bits as u32 & ((1 << self.mantissa_bits) - 1);
+        let negative = bits & 0x80 != 0;
+        let magnitude = if exponent == exponent_mask && self.ieee {
+            if mantissa == 0 {
+                f32::INFINITY
+            } else {
+                f32::NAN
+            }
+        } else if exponent == exponent_mask && mantissa == (1 << self.mantissa_bits) - 1 {
+            f32::NAN
+        } else if exponent == 0 {
+            mantissa as f32 * pow2(1 - self.exponent_bias - self.mantissa_bits as i32)
+        } else {
+            ((1 << self.mantissa_bits) + mantissa) as f32
+                * pow2(exponent as i32 - self.exponent_bias - self.mantissa_bits as i32)
+        };
+        if negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+}
+
+/// `2^exponent`, for exponents of normal `f32`s.
+fn pow2(exponent: i32) -> f32 {
+    f32::from_bits(((exponent + 127) as u32) << 23)
+}
+
+macro_rules! fp8_type {
+    ($(#[$doc:meta])* $name:ident, $data_type:ident, $format:expr) => {
+        $(#[$doc])*
+        ///
+        /// Comparisons and hashing are of the bits, so `-0` and `+0` differ
+        /// and a NaN equals itself.
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name(u8);
+
+        impl $name {
+            const FORMAT: Fp8Format = $format;
+
+            pub const ZERO: Self = Self(0);
+            pub const NAN: Self = Self(Fp8Format::NAN);
+            /// Largest finite value.
+            pub const MAX: Self = Self(Self::FORMAT.max_bits);
+
+            pub const fn from_bits(bits: u8) -> Self {
+                Self(bits)
+            }
+
+            pub const fn to_bits(self) -> u8 {
+                self.0
+            }
+
+            /// Rounds to nearest (ties to even) like the CUTLASS type:
+            /// finite values beyond [`MAX`](Self::MAX) and infinities
+            /// saturate to `±MAX`, and NaNs become [`NAN`](Self::NAN).
+            pub fn from_f32(value: f32) -> Self {
+                Self(Self::FORMAT.encode(value))
+            }
+
+            /// Exact, as every fp8 value is an `f32`.
+            pub fn to_f32(self) -> f32 {
+                Self::FORMAT.decode(self.0)
+            }
+
+            pub fn is_nan(self) -> bool {
+                self.to_f32().is_nan()
+            }
+        }
+
+        impl From<$name> for f32 {
+            fn from(value: $name) -> Self {
+                value.to_f32()
+            }
+        }
+
+        impl From<f32> for $name {
+            fn from(value: f32) -> Self {
+                Self::from_f32(value)
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.to_f32(), f)
+            }
+        }
+
+        // SAFETY: one byte holding the format's bits, like the CUTLASS type.
+        unsafe impl Element for $name {
+            const DATA_TYPE: DataType = DataType::$data_type;
+        }
+    };
+}
+
+fp8_type!(
+    /// `cutlass::float_e4m3_t`: 4 exponent and 3 mantissa bits, no
+    /// infinities, a largest finite value of 448 and `S.1111.111` as NaN.
+    F8E4M3,
+    E4M3,
+    Fp8Format {
+        mantissa_bits: 3,
+        exponent_bias: 7,
+        max_bits: 0x7e,
+        ieee: false,
+    }
+);
+
+fp8_type!(
+    /// `cutlass::float_e5m2_t`: 5 exponent and 2 mantissa bits, IEEE-style
+    /// infinities and NaNs, and a largest finite value of 57344.
+    F8E5M2,
+    E5M2,
+    Fp8Format {
+        mantissa_bits: 2,
+        exponent_bias: 15,
+        max_bits: 0x7b,
+        ieee: true,
+    }
+);
+
+impl DataType {
+    /// The type `T` is laid out as.
+    pub const fn of<T: Element>() -> Self {
+        T::DATA_TYPE
+    }
+}
+
+/// A Rust type with the size, alignment and bit layout of the CUTLASS
+/// element type of [`DATA_TYPE`](Self::DATA_TYPE), so slices, tensor views
+/// and device buffers of it can be handed to the kernels as that type.
+///
+/// Without the `half` feature, `F16` and `BF16` data is held as `u16` bit
+/// patterns, which are not `Element` since they could be either; with it,
+/// `half::f16` and `half::bf16` are.
+///
+/// # Safety
+///
+/// Implementors must be layout-compatible with the CUTLASS type, bit for
+/// bit.
+pub unsafe trait Element: Copy {
+    const DATA_TYPE: DataType;
+}
+
+// SAFETY: IEEE binary32, binary64 and two's-complement integers, as in CUTLASS.
+unsafe impl Element for f32 {
+    const DATA_TYPE: DataType = DataType::F32;
+}
+
+// SAFETY: as above.
+unsafe impl Element for f64 {
+    const DATA_TYPE: DataType = DataType::F64;
+}
+
+// SAFETY: as above.
+unsafe impl Element for i8 {
+    const DATA_TYPE: DataType = DataType::S8;
+}
+
+// SAFETY: as above.
+unsafe impl Element for i32 {
+    const DATA_TYPE: DataType = DataType::S32;
+}
+
+// SAFETY: `Complex` is laid out like `cutlass::complex`.
+unsafe impl Element for Complex<f32> {
+    const DATA_TYPE: DataType = DataType::C32;
+}
+
+// SAFETY: as above.
+unsafe impl Element for Complex<f64> {
+    const DATA_TYPE: DataType = DataType::C64;
+}
+
+// SAFETY: `half::f16` and `half::bf16` are `repr(transparent)` over the
+// IEEE binary16 and bfloat16 bits `cutlass::half_t` and
+// `cutlass::bfloat16_t` store in their one `uint16_t` member.
+#[cfg(feature = "half")]
+unsafe impl Element for half::f16 {
+    const DATA_TYPE: DataType = DataType::F16;
+}
+
+// SAFETY: as above.
+#[cfg(feature = "half")]
+unsafe impl Element for half::bf16 {
+    const DATA_TYPE: DataType = DataType::BF16;
+}
+
+#[cfg(feature = "half")]
+const _: () = {
+    use core::mem::{align_of, size_of};
+    assert!(size_of::<half::f16>() == 2 && align_of::<half::f16>() == 2);
+    assert!(size_of::<half::bf16>() == 2 && align_of::<half::bf16>() == 2);
+};
+
+/// What one kernel asks of the device, as reported by the `resources`
+/// queries (e.g. [`GemmConfig::resources`](crate::gemm::GemmConfig::resources)).
+///
+/// A kernel of which not even one CTA fits on an SM, typically from asking
+/// for more shared memory than the device has, fails to launch with an
+/// invalid-configuration CUDA error that CUTLASS reports as
+/// [`Status::ErrorInternal`]; it shows here as a zero
+/// [`max_active_ctas`](Self::max_active_ctas).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct KernelResources {
+    /// Threads per CTA.
+    pub threads: i32,
+    /// Registers per thread.
+    pub registers: i32,
+    /// Shared memory per CTA in bytes, dynamic and static.
+    pub shared_memory: i32,
+    /// Local memory per thread in bytes, which is register spills.
+    pub local_memory: i32,
+    /// CTAs of the kernel that can be resident on one SM at once.
+    pub max_active_ctas: i32,
+    /// The grid launched for the queried problem, `x`, `y` and `z`; all zeros
+    /// where the kernel's grid is not known ahead of the launch.
+    pub grid: [u32; 3],
+}
+
+impl KernelResources {
+    /// CTAs in [`grid`](Self::grid).
+    pub const fn ctas(&self) -> u64 {
+        self.grid[0] as u64 * self.grid[1] as u64 * self.grid[2] as u64
+    }
+
+    /// CTAs that can run at once on a device of `sm_count` SMs.
+    pub const fn resident_ctas(&self, sm_count: i32) -> u64 {
+        self.max_active_ctas as u64 * sm_count as u64
+    }
+
+    /// Full or partial waves the grid takes on a device of `sm_count` SMs,
+    /// `0` if no CTA fits.
+    pub const fn waves(&self, sm_count: i32) -> u64 {
+        let resident = self.resident_ctas(sm_count);
+        if resident == 0 {
+            0
+        } else {
+            self.ctas().div_ceil(resident)
+        }
+    }
+}
+
+/// How a K dimension split into `split_k_slices` partitions is reduced.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SplitKMode {
+    /// Partitions accumulate into the output in turn, serialized through a
+    /// semaphore in the workspace.
+    #[default]
+    Serial = 0,
+    /// Partitions write accumulator-precision partial results into the
+    /// workspace, which a separate reduction kernel sums into the output.
+    /// Needs `split_k_slices` times the output size of workspace, in the
+    /// accumulator type.
+    Parallel = 1,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The bits `from_f32(to_f32(bits))` gives: the same ones, except that
+    /// every NaN becomes `NAN` and infinities saturate to `±MAX`.
+    fn round_trip(bits: u8, decoded: f32, max: u8) -> u8 {
+        if decoded.is_nan() {
+            Fp8Format::NAN
+        } else if decoded.is_infinite() {
+            bits & 0x80 | max
+        } else {
+            bits
+        }
+    }
+
+    #[test]
+    fn e4m3_round_trips_every_value() {
+        for bits in 0..=255u8 {
+            let value = F8E4M3::from_bits(bits).to_f32();
+            assert!(!value.is_infinite(), "{bits:#04x}");
+            let expected = round_trip(bits, value, F8E4M3::MAX.to_bits());
+            assert_eq!(F8E4M3::from_f32(value).to_bits(), expected, "{bits:#04x}");
+        }
+        assert!(F8E4M3::from_bits(0x7f).is_nan());
+        assert!(F8E4M3::from_bits(0xff).is_nan());
+        assert_eq!(F8E4M3::MAX.to_f32(), 448.0);
+    }
+
+    #[test]
+    fn e5m2_round_trips_every_value() {
+        for bits in 0..=255u8 {
+            let value = F8E5M2::from_bits(bits).to_f32();
+            let expected = round_trip(bits, value, F8E5M2::MAX.to_bits());
+            assert_eq!(F8E5M2::from_f32(value).to_bits(), expected, "{bits:#04x}");
+        }
+        assert_eq!(F8E5M2::from_bits(0x7c).to_f32(), f32::INFINITY);
+        assert_eq!(F8E5M2::from_bits(0xfc).to_f32(), f32::NEG_INFINITY);
+        assert!(F8E5M2::from_bits(0x7d).is_nan());
+        assert_eq!(F8E5M2::MAX.to_f32(), 57344.0);
+    }
+
+    #[test]
+    fn ties_round_to_even() {
+        // 1.0 (0x38) and 1.125 (0x39): down to the even mantissa
+        assert_eq!(F8E4M3::from_f32(1.0625).to_bits(), 0x38);
+        // 1.125 (0x39) and 1.25 (0x3a): up to the even mantissa
+        assert_eq!(F8E4M3::from_f32(1.1875).to_bits(), 0x3a);
+        // 1.875 (0x3f) and 2.0 (0x40): the carry moves to the next exponent
+        assert_eq!(F8E4M3::from_f32(1.9375).to_bits(), 0x40);
+        assert_eq!(F8E4M3::from_f32(-1.9375).to_bits(), 0xc0);
+        // Just off the tie rounds to the nearer value
+        assert_eq!(F8E4M3::from_f32(1.0626).to_bits(), 0x39);
+
+        assert_eq!(F8E5M2::from_f32(1.125).to_bits(), 0x3c);
+        assert_eq!(F8E5M2::from_f32(1.375).to_bits(), 0x3e);
+        assert_eq!(F8E5M2::from_f32(1.875).to_bits(), 0x40);
+    }
+
+    #[test]
+    fn overflow_saturates() {
+        for value in [464.0, 500.0, 1e10, f32::MAX, f32::INFINITY] {
+            assert_eq!(F8E4M3::from_f32(value), F8E4M3::MAX, "{value}");
+            assert_eq!(F8E4M3::from_f32(-value).to_bits(), 0xfe, "{value}");
+        }
+        for value in [61440.0, 1e10, f32::MAX, f32::INFINITY] {
+            assert_eq!(F8E5M2::from_f32(value), F8E5M2::MAX, "{value}");
+            assert_eq!(F8E5M2::from_f32(-value).to_bits(), 0xfb, "{value}");
+        }
+    }
+
+    #[test]
+    fn subnormals_and_underflow() {
+        let e4m3_min = pow2(-9);
+        let e5m2_min = pow2(-16);
+        assert_eq!(F8E4M3::from_bits(0x01).to_f32(), e4m3_min);
+        assert_eq!(F8E5M2::from_bits(0x01).to_f32(), e5m2_min);
+        assert_eq!(F8E4M3::from_f32(e4m3_min).to_bits(), 0x01);
+        assert_eq!(F8E5M2::from_f32(-e5m2_min).to_bits(), 0x81);
+
+        // Half the smallest subnormal ties to even zero; anything above
+        // rounds up to it
+        assert_eq!(F8E4M3::from_f32(e4m3_min / 2.0).to_bits(), 0x00);
+        assert_eq!(F8E4M3::from_f32(e4m3_min * 0.51).to_bits(), 0x01);
+        assert_eq!(F8E5M2::from_f32(e5m2_min / 2.0).to_bits(), 0x00);
+        assert_eq!(F8E5M2::from_f32(e5m2_min * 0.51).to_bits(), 0x01);
+
+        // Underflow keeps the sign
+        for value in [1e-30, 1e-40] {
+            assert_eq!(F8E4M3::from_f32(value).to_bits(), 0x00, "{value}");
+            assert_eq!(F8E4M3::from_f32(-value).to_bits(), 0x80, "{value}");
+            assert_eq!(F8E5M2::from_f32(value).to_bits(), 0x00, "{value}");
+            assert_eq!(F8E5M2::from_f32(-value).to_bits(), 0x80, "{value}");
+        }
+        assert_eq!(F8E4M3::from_f32(-0.0).to_bits(), 0x80);
+    }
+
+    #[test]
+    fn nan_becomes_canonical() {
+        for value in [f32::NAN, -f32::NAN, f32::from_bits(0x7f80_0001)] {
+            assert_eq!(F8E4M3::from_f32(value), F8E4M3::NAN);
+            assert_eq!(F8E5M2::from_f32(value), F8E5M2::NAN);
+        }
+        assert_eq!(F8E4M3::NAN.to_bits(), 0x7f);
+        assert_eq!(F8E5M2::NAN.to_bits(), 0x7f);
+    }
+}